@@ -16,7 +16,9 @@ use crate::app::build::is_up_to_date;
 use crate::app::build::task_result_marker::{AddMetadataMarkerHash, TaskResultMarker};
 use crate::app::context::ApplicationContext;
 use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
-use crate::wasm_rpc_stubgen::commands::metadata::add_metadata;
+use crate::wasm_rpc_stubgen::commands::metadata::{add_metadata, BuildMetadata};
+use chrono::{DateTime, Utc};
+use std::process::Command;
 
 pub async fn add_metadata_to_selected_components(
     ctx: &mut ApplicationContext,
@@ -24,24 +26,31 @@ pub async fn add_metadata_to_selected_components(
     log_action("Adding", "metadata to components");
     let _indent = LogIndent::new();
 
+    let build_metadata = build_metadata(ctx);
+
     for component_name in ctx.selected_component_names() {
-        let linked_wasm = ctx.application.component_temp_linked_wasm(component_name);
+        let linked_wasm = ctx
+            .application
+            .component_temp_metadata_input_wasm(component_name, ctx.build_profile());
         let final_linked_wasm = ctx
             .application
             .component_linked_wasm(component_name, ctx.build_profile());
 
         let root_package_name = ctx.wit.root_package_name(component_name)?;
 
-        let task_result_marker = TaskResultMarker::new(
+        let mut task_result_marker = TaskResultMarker::new(
             &ctx.application.task_result_marker_dir(),
             AddMetadataMarkerHash {
                 component_name,
                 root_package_name: root_package_name.clone(),
+                git_commit: build_metadata.git_commit.as_deref(),
+                app_version: build_metadata.app_version.as_deref(),
             },
         )?;
 
         if is_up_to_date(
-            ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date(),
+            &mut task_result_marker,
+            ctx.config.skip_up_to_date_checks,
             || vec![linked_wasm.clone()],
             || [final_linked_wasm.clone()],
         ) {
@@ -61,7 +70,12 @@ pub async fn add_metadata_to_selected_components(
                         component_name.as_str().log_color_highlight()
                     ),
                 );
-                add_metadata(&linked_wasm, root_package_name, &final_linked_wasm)
+                add_metadata(
+                    &linked_wasm,
+                    root_package_name,
+                    &build_metadata,
+                    &final_linked_wasm,
+                )
             }
             .await,
         )?;
@@ -69,3 +83,29 @@ pub async fn add_metadata_to_selected_components(
 
     Ok(())
 }
+
+/// Collects the build metadata: the current git commit (if the application is in a git
+/// repository), the build time, which respects SOURCE_DATE_EPOCH for reproducible builds,
+/// and the version of the application from the manifest
+pub fn build_metadata(ctx: &ApplicationContext) -> BuildMetadata {
+    let git_commit = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|commit| commit.trim().to_string())
+        .filter(|commit| !commit.is_empty());
+
+    let build_time = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<i64>().ok())
+        .and_then(|epoch| DateTime::<Utc>::from_timestamp(epoch, 0))
+        .unwrap_or_else(Utc::now);
+
+    BuildMetadata {
+        git_commit,
+        build_time: Some(build_time.to_rfc3339()),
+        app_version: ctx.application.version().map(|version| version.to_string()),
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::build::lock::LOCK_FILE_NAME;
+use crate::app::context::ApplicationContext;
+use crate::fs;
+use crate::fs::PathExtra;
+use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::wasm_rpc_stubgen::naming;
+use anyhow::{anyhow, Context};
+use fs_extra::dir::CopyOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+const BACKUP_JOURNAL_FILE_NAME: &str = "journal.yaml";
+
+/// Journal of a backup run, paths without a backup copy did not exist before the run,
+/// so they are deleted when restoring.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupJournal {
+    entries: Vec<BackupJournalEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupJournalEntry {
+    path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    backup: Option<PathBuf>,
+}
+
+/// The files and directories which can be overwritten when generating RPC artifacts for
+/// the selected components: the lockfile, the generated WITs, the clients and the
+/// Cargo.toml files updated for the generated WITs
+fn backed_up_paths(ctx: &ApplicationContext) -> BTreeSet<PathBuf> {
+    let mut paths = BTreeSet::new();
+    paths.insert(PathBuf::from(LOCK_FILE_NAME));
+
+    for component_name in ctx.selected_component_names() {
+        paths.insert(
+            ctx.application
+                .component_generated_wit(component_name, ctx.build_profile()),
+        );
+        if let Some(parent) = ctx
+            .application
+            .component_source_wit(component_name, ctx.build_profile())
+            .parent()
+        {
+            paths.insert(parent.join(naming::rust::CARGO_TOML));
+        }
+
+        for dep in ctx.application.component_dependencies(component_name) {
+            if let Some(dep) = dep.as_rpc_client_target() {
+                paths.insert(ctx.application.client_wit(&dep.name));
+                paths.insert(ctx.application.client_wasm(&dep.name));
+            }
+        }
+    }
+
+    paths
+}
+
+/// Backs up the files which can be overwritten by generating RPC artifacts into a new
+/// timestamped backup directory, which can be restored with [restore_last_backup]
+pub fn backup_gen_rpc_targets(ctx: &ApplicationContext) -> anyhow::Result<()> {
+    let backup_dir = ctx
+        .application
+        .backup_dir()
+        .join(chrono::Utc::now().format("%Y%m%d-%H%M%S%.3f").to_string());
+
+    log_action(
+        "Backing up",
+        format!(
+            "files to be overwritten to {}",
+            backup_dir.log_color_highlight()
+        ),
+    );
+    let _indent = LogIndent::new();
+
+    let mut journal = BackupJournal::default();
+    for (idx, path) in backed_up_paths(ctx).into_iter().enumerate() {
+        let backup = if path.exists() {
+            let backup = backup_dir.join(idx.to_string());
+            copy_path(&path, &backup)?;
+            log_action(
+                "Backed up",
+                format!(
+                    "{} to {}",
+                    path.log_color_highlight(),
+                    backup.log_color_highlight()
+                ),
+            );
+            Some(backup)
+        } else {
+            None
+        };
+        journal.entries.push(BackupJournalEntry { path, backup });
+    }
+
+    fs::write_str(
+        backup_dir.join(BACKUP_JOURNAL_FILE_NAME),
+        serde_yaml::to_string(&journal).context("Failed to serialize backup journal")?,
+    )
+}
+
+/// Restores the last backup and deletes it, returns false if there is no backup to restore
+pub fn restore_last_backup(ctx: &ApplicationContext) -> anyhow::Result<bool> {
+    let backup_root_dir = ctx.application.backup_dir();
+    if !backup_root_dir.exists() {
+        return Ok(false);
+    }
+
+    let last_backup_dir = std::fs::read_dir(&backup_root_dir)
+        .with_context(|| {
+            anyhow!(
+                "Failed to read backup directory {}",
+                backup_root_dir.log_color_highlight()
+            )
+        })?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join(BACKUP_JOURNAL_FILE_NAME).is_file())
+        .max();
+    let Some(backup_dir) = last_backup_dir else {
+        return Ok(false);
+    };
+
+    let journal_path = backup_dir.join(BACKUP_JOURNAL_FILE_NAME);
+    let journal: BackupJournal = serde_yaml::from_str(&fs::read_to_string(&journal_path)?)
+        .with_context(|| {
+            anyhow!(
+                "Failed to parse backup journal {}",
+                journal_path.log_color_highlight()
+            )
+        })?;
+
+    log_action(
+        "Restoring",
+        format!("backup {}", backup_dir.log_color_highlight()),
+    );
+    let _indent = LogIndent::new();
+
+    for entry in journal.entries {
+        match entry.backup {
+            Some(backup) => {
+                fs::remove(&entry.path)?;
+                copy_path(&backup, &entry.path)?;
+                log_action("Restored", entry.path.log_color_highlight());
+            }
+            None => {
+                if entry.path.exists() {
+                    fs::remove(&entry.path)?;
+                    log_warn_action("Deleted", entry.path.log_color_highlight());
+                }
+            }
+        }
+    }
+
+    fs::remove(&backup_dir)?;
+
+    Ok(true)
+}
+
+fn copy_path(source: &Path, target: &Path) -> anyhow::Result<()> {
+    if source.is_dir() {
+        fs::create_dir_all(target)?;
+        fs_extra::dir::copy(
+            source,
+            target,
+            &CopyOptions::new().content_only(true).overwrite(true),
+        )
+        .with_context(|| {
+            anyhow!(
+                "Failed to copy {} to {}",
+                source.log_color_highlight(),
+                target.log_color_highlight()
+            )
+        })?;
+    } else {
+        fs::create_dir_all(PathExtra::new(target).parent()?)?;
+        fs::copy(source, target)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,472 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::context::ApplicationContext;
+use crate::fs;
+use crate::fs::compile_and_collect_globs;
+use crate::log::{log_action, log_warn_action, LogColorize};
+use crate::model::app::{AppComponentName, ApplicationConfig, WithSource};
+use crate::model::app_raw;
+use anyhow::{anyhow, bail, Context};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildCacheEntryKind {
+    Component,
+    Linked,
+}
+
+impl BuildCacheEntryKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BuildCacheEntryKind::Component => "component",
+            BuildCacheEntryKind::Linked => "linked",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BuildCacheBackend {
+    Dir(PathBuf),
+    S3 {
+        url: String,
+        endpoint: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildCacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    pub stored: usize,
+    pub errors: usize,
+}
+
+impl Display for BuildCacheStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hit(s), {} miss(es), {} stored",
+            self.hits, self.misses, self.stored
+        )?;
+        if self.errors > 0 {
+            write!(f, ", {} error(s)", self.errors)?;
+        }
+        Ok(())
+    }
+}
+
+/// Shared cache of compiled and linked component WASMs, keyed by the hash of the inputs
+/// used for producing them.
+///
+/// Cache failures are only reported as warnings, and never fail the build.
+#[derive(Debug)]
+pub struct BuildCache {
+    backend: BuildCacheBackend,
+    read_only: bool,
+    stats: Mutex<BuildCacheStats>,
+}
+
+impl BuildCache {
+    /// Selects the cache of the manifest profile if there is one, otherwise the application
+    /// level one, relative dirs are resolved against the defining manifest's dir.
+    pub fn from_config(
+        config: &ApplicationConfig,
+        app_build_cache: Option<&WithSource<app_raw::BuildCache>>,
+    ) -> Option<Self> {
+        if config.no_cache {
+            return None;
+        }
+
+        let (source_dir, build_cache) = match &config.build_cache {
+            Some(build_cache) => (Path::new(""), build_cache),
+            None => {
+                let build_cache = app_build_cache?;
+                (build_cache.source.as_path(), &build_cache.value)
+            }
+        };
+
+        let backend = match (&build_cache.dir, &build_cache.s3) {
+            (Some(dir), None) => BuildCacheBackend::Dir(source_dir.join(dir)),
+            (None, Some(url)) => BuildCacheBackend::S3 {
+                url: url.trim_end_matches('/').to_string(),
+                endpoint: build_cache.endpoint.clone(),
+            },
+            // Invalid combinations are already reported when loading the application
+            _ => return None,
+        };
+
+        Some(Self {
+            backend,
+            read_only: build_cache.read_only,
+            stats: Mutex::new(BuildCacheStats::default()),
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        let location = match &self.backend {
+            BuildCacheBackend::Dir(dir) => dir.log_color_highlight().to_string(),
+            BuildCacheBackend::S3 { url, endpoint } => match endpoint {
+                Some(endpoint) => format!(
+                    "{} ({})",
+                    url.log_color_highlight(),
+                    endpoint.log_color_highlight()
+                ),
+                None => url.log_color_highlight().to_string(),
+            },
+        };
+        if self.read_only {
+            format!("{location}, read-only")
+        } else {
+            location
+        }
+    }
+
+    pub fn stats(&self) -> BuildCacheStats {
+        *self.stats.lock().unwrap()
+    }
+
+    /// Copies the cached entry to the target, returns false on cache misses and errors
+    pub fn restore(&self, kind: BuildCacheEntryKind, key: &str, target: &Path) -> bool {
+        let entry = entry_path(kind, key);
+        let result = match &self.backend {
+            BuildCacheBackend::Dir(dir) => {
+                let source = dir.join(&entry);
+                if source.exists() {
+                    fs::copy(&source, target).map(|_| true)
+                } else {
+                    Ok(false)
+                }
+            }
+            BuildCacheBackend::S3 { url, endpoint } => {
+                fs::create_dir_all(target.parent().unwrap_or(Path::new("")))
+                    .and_then(|_| {
+                        aws_s3_cp(
+                            endpoint.as_deref(),
+                            &format!("{url}/{entry}"),
+                            &target.to_string_lossy(),
+                        )
+                    })
+                    .map(|_| true)
+                    .or_else(|err| {
+                        if is_s3_not_found(&format!("{err:#}")) {
+                            Ok(false)
+                        } else {
+                            Err(err)
+                        }
+                    })
+            }
+        };
+
+        let mut stats = self.stats.lock().unwrap();
+        match result {
+            Ok(true) => {
+                stats.hits += 1;
+                log_action(
+                    "Restored",
+                    format!(
+                        "{} from build cache ({})",
+                        target.log_color_highlight(),
+                        key.log_color_highlight()
+                    ),
+                );
+                true
+            }
+            Ok(false) => {
+                stats.misses += 1;
+                false
+            }
+            Err(err) => {
+                stats.misses += 1;
+                stats.errors += 1;
+                log_warn_action(
+                    "Failed",
+                    format!(
+                        "to read build cache entry {}: {:#}",
+                        entry.log_color_highlight(),
+                        err
+                    ),
+                );
+                false
+            }
+        }
+    }
+
+    /// Copies the source into the cache, unless the cache is read-only
+    pub fn store(&self, kind: BuildCacheEntryKind, key: &str, source: &Path) {
+        if self.read_only {
+            return;
+        }
+
+        let entry = entry_path(kind, key);
+        let result = match &self.backend {
+            BuildCacheBackend::Dir(dir) => {
+                // Copy to a temporary file first, so concurrent builds never see partial entries
+                let target = dir.join(&entry);
+                let temp_target = target.with_extension(format!("{}.tmp", Uuid::new_v4()));
+                fs::copy(source, &temp_target).and_then(|_| {
+                    std::fs::rename(&temp_target, &target).with_context(|| {
+                        anyhow!(
+                            "Failed to move {} to {}",
+                            temp_target.log_color_highlight(),
+                            target.log_color_highlight()
+                        )
+                    })
+                })
+            }
+            BuildCacheBackend::S3 { url, endpoint } => aws_s3_cp(
+                endpoint.as_deref(),
+                &source.to_string_lossy(),
+                &format!("{url}/{entry}"),
+            ),
+        };
+
+        let mut stats = self.stats.lock().unwrap();
+        match result {
+            Ok(()) => {
+                stats.stored += 1;
+                log_action(
+                    "Stored",
+                    format!(
+                        "{} in build cache ({})",
+                        source.log_color_highlight(),
+                        key.log_color_highlight()
+                    ),
+                );
+            }
+            Err(err) => {
+                stats.errors += 1;
+                log_warn_action(
+                    "Failed",
+                    format!(
+                        "to write build cache entry {}: {:#}",
+                        entry.log_color_highlight(),
+                        err
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn entry_path(kind: BuildCacheEntryKind, key: &str) -> String {
+    format!("{}/{}.wasm", kind.as_str(), key)
+}
+
+fn aws_s3_cp(endpoint: Option<&str>, from: &str, to: &str) -> anyhow::Result<()> {
+    let mut command = Command::new("aws");
+    command
+        .args(["s3", "cp", from, to, "--only-show-errors"])
+        .args(
+            endpoint
+                .into_iter()
+                .flat_map(|endpoint| ["--endpoint-url", endpoint]),
+        );
+
+    let output = command
+        .output()
+        .context("Failed to execute aws, make sure it is installed and available in PATH")?;
+
+    if !output.status.success() {
+        bail!(
+            "aws s3 cp failed with exit code {}: {}",
+            output
+                .status
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "?".to_string()),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(())
+}
+
+fn is_s3_not_found(error: &str) -> bool {
+    error.contains("(404)") || error.contains("NoSuchKey") || error.contains("Not Found")
+}
+
+/// Cache key of the componentized WASM, or None if the build commands do not declare their
+/// sources, as then the inputs cannot be tracked.
+pub fn component_key(
+    ctx: &ApplicationContext,
+    component_name: &AppComponentName,
+) -> anyhow::Result<Option<String>> {
+    let component_properties = ctx
+        .application
+        .component_properties(component_name, ctx.build_profile());
+    let component_source_dir = ctx.application.component_source_dir(component_name);
+
+    let mut hasher = KeyHasher::new();
+    hasher.update_str(golem_common::golem_version());
+    hasher.update_str(component_name.as_str());
+    hasher.update_str(
+        ctx.build_profile()
+            .map(|profile| profile.as_str())
+            .unwrap_or_default(),
+    );
+    hasher.update_str(
+        &serde_json::to_string(&component_properties.build)
+            .context("Failed to serialize build commands")?,
+    );
+
+    for command in &component_properties.build {
+        match command {
+            app_raw::BuildCommand::External(command) => {
+                if command.sources.is_empty() {
+                    return Ok(None);
+                }
+                let build_dir = command
+                    .dir
+                    .as_ref()
+                    .map(|dir| component_source_dir.join(dir))
+                    .unwrap_or_else(|| component_source_dir.to_path_buf());
+                let mut sources = compile_and_collect_globs(&build_dir, &command.sources)?;
+                sources.sort();
+                for source in sources {
+                    hasher.update_file(&source)?;
+                }
+            }
+            app_raw::BuildCommand::QuickJSCrate(command) => {
+                hasher.update_file(&component_source_dir.join(&command.js))?;
+            }
+            app_raw::BuildCommand::QuickJSDTS(_) => {}
+        }
+    }
+
+    hasher.update_dir(
+        &ctx.application
+            .component_generated_wit(component_name, ctx.build_profile()),
+    )?;
+
+    Ok(Some(hasher.finalize()))
+}
+
+/// Cache key of the linked WASM, based on the component WASM and the WASMs linked into it
+pub fn linked_key(
+    component_wasm: &Path,
+    wasms_to_compose_with: &[PathBuf],
+    wasi_adapter: Option<&Path>,
+) -> anyhow::Result<String> {
+    let mut hasher = KeyHasher::new();
+    hasher.update_str(golem_common::golem_version());
+    hasher.update_content(&fs::read(component_wasm)?);
+    for wasm in wasms_to_compose_with {
+        hasher.update_content(&fs::read(wasm)?);
+    }
+    if let Some(wasi_adapter) = wasi_adapter {
+        hasher.update_content(&fs::read(wasi_adapter)?);
+    }
+    Ok(hasher.finalize())
+}
+
+/// Hashes strings and files with length prefixes, so different splits of the same bytes
+/// cannot produce the same key
+struct KeyHasher(blake3::Hasher);
+
+impl KeyHasher {
+    fn new() -> Self {
+        Self(blake3::Hasher::new())
+    }
+
+    fn update_content(&mut self, content: &[u8]) {
+        self.0.update(&(content.len() as u64).to_le_bytes());
+        self.0.update(content);
+    }
+
+    fn update_str(&mut self, str: &str) {
+        self.update_content(str.as_bytes());
+    }
+
+    /// Paths are hashed relative to the application root, so the keys match across machines
+    fn update_file(&mut self, path: &Path) -> anyhow::Result<()> {
+        self.update_str(&key_path(path));
+        self.update_content(&fs::read(path)?);
+        Ok(())
+    }
+
+    fn update_dir(&mut self, dir: &Path) -> anyhow::Result<()> {
+        if !dir.exists() {
+            return Ok(());
+        }
+        for entry in WalkDir::new(dir).sort_by_file_name() {
+            let entry = entry.with_context(|| {
+                anyhow!("Failed to read directory {}", dir.log_color_highlight())
+            })?;
+            if entry.file_type().is_file() {
+                self.update_file(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finalize(self) -> String {
+        self.0.finalize().to_hex().to_string()
+    }
+}
+
+fn key_path(path: &Path) -> String {
+    let path = std::env::current_dir()
+        .ok()
+        .and_then(|current_dir| path.strip_prefix(current_dir).ok())
+        .unwrap_or(path);
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::cache::{is_s3_not_found, BuildCacheStats, KeyHasher};
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn key_hasher_separates_inputs() {
+        let key = |parts: &[&str]| {
+            let mut hasher = KeyHasher::new();
+            for part in parts {
+                hasher.update_str(part);
+            }
+            hasher.finalize()
+        };
+
+        assert!(key(&["ab", "c"]) == key(&["ab", "c"]));
+        assert!(key(&["ab", "c"]) != key(&["a", "bc"]));
+    }
+
+    #[test]
+    fn s3_not_found_is_a_cache_miss() {
+        assert!(is_s3_not_found(
+            "fatal error: An error occurred (404) when calling the HeadObject operation: Key \"component/abc.wasm\" does not exist"
+        ));
+        assert!(!is_s3_not_found(
+            "fatal error: An error occurred (403) when calling the HeadObject operation: Forbidden"
+        ));
+    }
+
+    #[test]
+    fn stats_display() {
+        let stats = BuildCacheStats {
+            hits: 2,
+            misses: 1,
+            stored: 1,
+            errors: 0,
+        };
+        assert!(stats.to_string() == "2 hit(s), 1 miss(es), 1 stored");
+    }
+}
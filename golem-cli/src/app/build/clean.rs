@@ -1,10 +1,11 @@
+use crate::app::build::command::build_command_dir;
 use crate::app::build::delete_path_logged;
 use crate::app::context::ApplicationContext;
 use crate::fs::compile_and_collect_globs;
-use crate::log::{log_action, LogColorize, LogIndent};
+use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
 use crate::model::app::DependencyType;
 use std::collections::BTreeSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 // Copyright 2024-2025 Golem Cloud
 //
 // Licensed under the Golem Source License v1.0 (the "License");
@@ -19,8 +20,15 @@ use std::path::PathBuf;
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// TODO: clean is not selected_component_names aware yet!
+/// Cleans the selected components, and the clients generated for them. The common clean targets
+/// and the application build dir are only cleaned when all the components are selected.
+///
+/// Paths containing the source directory or the source WIT of any component are never deleted.
 pub fn clean_app(ctx: &ApplicationContext) -> anyhow::Result<()> {
+    let all_components_selected =
+        ctx.selected_component_names().len() == ctx.application.component_names().count();
+    let protected_paths = protected_source_paths(ctx);
+
     {
         log_action("Cleaning", "components");
         let _indent = LogIndent::new();
@@ -28,7 +36,7 @@ pub fn clean_app(ctx: &ApplicationContext) -> anyhow::Result<()> {
         let all_profiles = ctx.application.all_option_build_profiles();
         let paths = {
             let mut paths = BTreeSet::<(&'static str, PathBuf)>::new();
-            for component_name in ctx.application.component_names() {
+            for component_name in ctx.selected_component_names() {
                 for profile in &all_profiles {
                     paths.insert((
                         "generated wit",
@@ -51,18 +59,8 @@ pub fn clean_app(ctx: &ApplicationContext) -> anyhow::Result<()> {
                         .component_properties(component_name, profile.as_ref());
 
                     for build_step in &properties.build {
-                        let build_dir = build_step
-                            .dir()
-                            .map(|dir| {
-                                ctx.application
-                                    .component_source_dir(component_name)
-                                    .join(dir)
-                            })
-                            .unwrap_or_else(|| {
-                                ctx.application
-                                    .component_source_dir(component_name)
-                                    .to_path_buf()
-                            });
+                        let build_dir =
+                            build_command_dir(ctx, component_name, profile.as_ref(), build_step)?;
 
                         paths.extend(
                             compile_and_collect_globs(&build_dir, &build_step.targets())?
@@ -85,7 +83,7 @@ pub fn clean_app(ctx: &ApplicationContext) -> anyhow::Result<()> {
         };
 
         for (context, path) in paths {
-            delete_path_logged(context, &path)?;
+            delete_unprotected_path_logged(context, &path, &protected_paths)?;
         }
     }
 
@@ -95,7 +93,10 @@ pub fn clean_app(ctx: &ApplicationContext) -> anyhow::Result<()> {
 
         for dep in ctx.application.all_dependencies() {
             if dep.dep_type.is_wasm_rpc() {
-                if let Some(dep) = dep.as_dependent_app_component() {
+                if let Some(dep) = dep
+                    .as_dependent_app_component()
+                    .filter(|dep| ctx.selected_component_names().contains(&dep.name))
+                {
                     log_action(
                         "Cleaning",
                         format!(
@@ -105,21 +106,41 @@ pub fn clean_app(ctx: &ApplicationContext) -> anyhow::Result<()> {
                     );
                     let _indent = LogIndent::new();
 
-                    delete_path_logged("client wit", &ctx.application.client_wit(&dep.name))?;
+                    delete_unprotected_path_logged(
+                        "client wit",
+                        &ctx.application.client_wit(&dep.name),
+                        &protected_paths,
+                    )?;
                     if dep.dep_type == DependencyType::StaticWasmRpc {
-                        delete_path_logged("client wasm", &ctx.application.client_wasm(&dep.name))?;
+                        delete_unprotected_path_logged(
+                            "client wasm",
+                            &ctx.application.client_wasm(&dep.name),
+                            &protected_paths,
+                        )?;
                     }
                 }
             }
         }
     }
 
+    if !all_components_selected {
+        log_action(
+            "Skipping",
+            "cleaning common clean targets and application build dir, not all components are selected",
+        );
+        return Ok(());
+    }
+
     {
         log_action("Cleaning", "common clean targets");
         let _indent = LogIndent::new();
 
         for clean in ctx.application.common_clean() {
-            delete_path_logged("common clean target", &clean.source.join(&clean.value))?;
+            delete_unprotected_path_logged(
+                "common clean target",
+                &clean.source.join(&clean.value),
+                &protected_paths,
+            )?;
         }
     }
 
@@ -127,8 +148,50 @@ pub fn clean_app(ctx: &ApplicationContext) -> anyhow::Result<()> {
         log_action("Cleaning", "application build dir");
         let _indent = LogIndent::new();
 
-        delete_path_logged("temp dir", &ctx.application.temp_dir())?;
+        delete_unprotected_path_logged("temp dir", &ctx.application.temp_dir(), &protected_paths)?;
     }
 
     Ok(())
 }
+
+fn protected_source_paths(ctx: &ApplicationContext) -> BTreeSet<PathBuf> {
+    let all_profiles = ctx.application.all_option_build_profiles();
+    let mut paths = BTreeSet::new();
+    for component_name in ctx.application.component_names() {
+        paths.insert(
+            ctx.application
+                .component_source_dir(component_name)
+                .to_path_buf(),
+        );
+        for profile in &all_profiles {
+            paths.insert(
+                ctx.application
+                    .component_source_wit(component_name, profile.as_ref()),
+            );
+        }
+    }
+    paths
+}
+
+fn delete_unprotected_path_logged(
+    context: &str,
+    path: &Path,
+    protected_paths: &BTreeSet<PathBuf>,
+) -> anyhow::Result<()> {
+    if protected_paths
+        .iter()
+        .any(|protected_path| protected_path.starts_with(path))
+    {
+        log_warn_action(
+            "Skipping",
+            format!(
+                "deleting {} {}, as it contains component sources",
+                context,
+                path.log_color_highlight()
+            ),
+        );
+        return Ok(());
+    }
+
+    delete_path_logged(context, path)
+}
@@ -20,12 +20,18 @@ use crate::app::build::{delete_path_logged, is_up_to_date, valid_env_vars};
 use crate::app::context::ApplicationContext;
 use crate::app::error::CustomCommandError;
 use crate::fs::compile_and_collect_globs;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::log::{
+    log_action, log_skipping_up_to_date, logln, LogColorize, LogIndent, LogThreadPrefix,
+};
+use crate::model::app::{AppComponentName, Application, BuildProfileName, CommandDependency};
 use crate::model::app_raw;
-use anyhow::{anyhow, Context};
+use crate::model::template::{template_env, Template};
+use anyhow::{anyhow, bail, Context};
 use camino::Utf8Path;
+use itertools::Itertools;
+use serde::Serialize;
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tracing::debug;
 
@@ -56,7 +62,8 @@ pub fn execute_custom_command(
         let _indent = LogIndent::new();
 
         for step in &command.value {
-            if let Err(error) = execute_external_command(ctx, &command.source, step, HashMap::new())
+            if let Err(error) =
+                execute_external_command(ctx, &command.source, None, step, HashMap::new())
             {
                 return Err(CustomCommandError::CommandError { error });
             }
@@ -82,6 +89,7 @@ pub fn execute_custom_command(
                 if let Err(error) = execute_external_command(
                     ctx,
                     ctx.application.component_source_dir(component_name),
+                    Some(component_name),
                     step,
                     HashMap::new(),
                 ) {
@@ -94,23 +102,156 @@ pub fn execute_custom_command(
     Ok(())
 }
 
+/// Returns the commands and build steps to be executed for the requested dependencies, ordered
+/// topologically based on the dependsOn declarations of the custom commands, with every
+/// dependency preceding its dependents. Fails on unknown custom commands and on cycles.
+pub fn command_execution_order(
+    application: &Application,
+    profile: Option<&BuildProfileName>,
+    dependencies: Vec<CommandDependency>,
+) -> anyhow::Result<Vec<CommandDependency>> {
+    let all_custom_commands = application.all_custom_commands(profile);
+    order_command_dependencies(dependencies, |command_name| {
+        all_custom_commands
+            .contains(command_name)
+            .then(|| application.custom_command_dependencies(command_name, profile))
+    })
+}
+
+fn order_command_dependencies<F>(
+    dependencies: Vec<CommandDependency>,
+    dependencies_of: F,
+) -> anyhow::Result<Vec<CommandDependency>>
+where
+    F: Fn(&str) -> Option<Vec<CommandDependency>>,
+{
+    fn visit<F>(
+        dependency: CommandDependency,
+        dependencies_of: &F,
+        path: &mut Vec<String>,
+        order: &mut Vec<CommandDependency>,
+    ) -> anyhow::Result<()>
+    where
+        F: Fn(&str) -> Option<Vec<CommandDependency>>,
+    {
+        if order.contains(&dependency) {
+            return Ok(());
+        }
+
+        let CommandDependency::CustomCommand(command_name) = &dependency else {
+            order.push(dependency);
+            return Ok(());
+        };
+
+        if let Some(idx) = path.iter().position(|name| name == command_name) {
+            bail!(
+                "Found cycle in custom command dependencies: {}",
+                path[idx..]
+                    .iter()
+                    .chain(std::iter::once(command_name))
+                    .map(|name| name.log_color_error_highlight())
+                    .join(" -> ")
+            );
+        }
+
+        let Some(command_dependencies) = dependencies_of(command_name) else {
+            bail!(
+                "Custom command dependency {} not found{}",
+                command_name.log_color_error_highlight(),
+                path.last()
+                    .map(|dependent| format!(", required by {}", dependent.log_color_highlight()))
+                    .unwrap_or_default()
+            );
+        };
+
+        path.push(command_name.clone());
+        for command_dependency in command_dependencies {
+            visit(command_dependency, dependencies_of, path, order)?;
+        }
+        path.pop();
+
+        order.push(dependency);
+        Ok(())
+    }
+
+    let mut path = Vec::new();
+    let mut order = Vec::new();
+    for dependency in dependencies {
+        visit(dependency, &dependencies_of, &mut path, &mut order)?;
+    }
+    Ok(order)
+}
+
+/// Executes the custom commands the external build commands of the selected components depend on
+pub fn execute_build_command_dependencies(ctx: &ApplicationContext) -> anyhow::Result<()> {
+    let mut dependencies = Vec::<CommandDependency>::new();
+    for component_name in ctx.selected_component_names() {
+        let properties = ctx
+            .application
+            .component_properties(component_name, ctx.build_profile());
+        for command in &properties.build {
+            let app_raw::BuildCommand::External(command) = command else {
+                continue;
+            };
+            for dependency in command
+                .depends_on
+                .iter()
+                .map(|name| CommandDependency::parse(name))
+            {
+                if !dependencies.contains(&dependency) {
+                    dependencies.push(dependency);
+                }
+            }
+        }
+    }
+
+    if dependencies.is_empty() {
+        return Ok(());
+    }
+
+    let order = command_execution_order(&ctx.application, ctx.build_profile(), dependencies)?;
+    for dependency in order {
+        let CommandDependency::CustomCommand(command_name) = dependency else {
+            bail!(
+                "Build commands can only depend on custom commands, found dependency on build step {}",
+                dependency.to_string().log_color_error_highlight()
+            );
+        };
+        match execute_custom_command(ctx, &command_name) {
+            Ok(()) => {}
+            Err(CustomCommandError::CommandNotFound) => bail!(
+                "Custom command {} not found",
+                command_name.log_color_error_highlight()
+            ),
+            Err(CustomCommandError::CommandError { error }) => return Err(error),
+        }
+    }
+
+    Ok(())
+}
+
 pub fn execute_build_command(
     ctx: &ApplicationContext,
     base_build_dir: &Path,
+    component_name: &AppComponentName,
     command: &app_raw::BuildCommand,
     additional_env_vars: HashMap<String, String>,
 ) -> anyhow::Result<()> {
     match command {
-        app_raw::BuildCommand::External(external_command) => {
-            execute_external_command(ctx, base_build_dir, external_command, additional_env_vars)
-        }
+        app_raw::BuildCommand::External(external_command) => execute_external_command(
+            ctx,
+            base_build_dir,
+            Some(component_name),
+            external_command,
+            additional_env_vars,
+        ),
         app_raw::BuildCommand::QuickJSCrate(command) => {
             let base_build_dir = Utf8Path::from_path(base_build_dir).unwrap();
             let wit = base_build_dir.join(&command.wit);
             let js = base_build_dir.join(&command.js);
             let generate_quickjs_crate = base_build_dir.join(&command.generate_quickjs_crate);
 
-            let task_result_marker = TaskResultMarker::new(
+            let mut task_result_marker = TaskResultMarker::new(
                 &ctx.application.task_result_marker_dir(),
                 GenerateQuickJSCrateCommandMarkerHash {
                     build_dir: base_build_dir.as_std_path(),
@@ -118,11 +259,9 @@ pub fn execute_build_command(
                 },
             )?;
 
-            let skip_up_to_date_checks =
-                ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date();
-
             if is_up_to_date(
-                skip_up_to_date_checks,
+                &mut task_result_marker,
+                ctx.config.skip_up_to_date_checks,
                 || {
                     vec![
                         wit.clone().into_std_path_buf(),
@@ -160,7 +299,7 @@ pub fn execute_build_command(
             let wit = &base_build_dir.join(&command.wit);
             let generate_quickjs_dts = &base_build_dir.join(&command.generate_quickjs_dts);
 
-            let task_result_marker = TaskResultMarker::new(
+            let mut task_result_marker = TaskResultMarker::new(
                 &ctx.application.task_result_marker_dir(),
                 GenerateQuickJSDTSCommandMarkerHash {
                     build_dir: base_build_dir.as_std_path(),
@@ -168,11 +307,9 @@ pub fn execute_build_command(
                 },
             )?;
 
-            let skip_up_to_date_checks =
-                ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date();
-
             if is_up_to_date(
-                skip_up_to_date_checks,
+                &mut task_result_marker,
+                ctx.config.skip_up_to_date_checks,
                 || vec![wit.clone().into_std_path_buf()],
                 || vec![generate_quickjs_dts.clone().into_std_path_buf()],
             ) {
@@ -199,19 +336,137 @@ pub fn execute_build_command(
     }
 }
 
+/// Template context for the working directory and env values of external commands,
+/// the component related variables are only available for component commands
+fn external_command_template_context(
+    ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    profile: Option<&BuildProfileName>,
+) -> impl Serialize {
+    let profile_name = profile
+        .map(|profile| profile.as_str().to_string())
+        .unwrap_or_default();
+    match component_name {
+        Some(component_name) => minijinja::context! {
+            componentName => component_name.as_str(),
+            component_name => component_name.as_str(),
+            target_wasm => ctx
+                .application
+                .component_wasm(component_name, profile)
+                .to_string_lossy()
+                .to_string(),
+            profile => profile_name,
+            os => std::env::consts::OS,
+            arch => std::env::consts::ARCH,
+            ..minijinja::Value::from_serialize(ctx.application.variables())
+        },
+        None => minijinja::context! {
+            profile => profile_name,
+            os => std::env::consts::OS,
+            arch => std::env::consts::ARCH,
+            ..minijinja::Value::from_serialize(ctx.application.variables())
+        },
+    }
+}
+
+/// Returns the working directory of a component build command for the given build profile
+pub fn build_command_dir(
+    ctx: &ApplicationContext,
+    component_name: &AppComponentName,
+    profile: Option<&BuildProfileName>,
+    command: &app_raw::BuildCommand,
+) -> anyhow::Result<PathBuf> {
+    let source_dir = ctx.application.component_source_dir(component_name);
+    let dir = command
+        .dir()
+        .map(|dir| dir.to_string())
+        .render(
+            &template_env(),
+            &external_command_template_context(ctx, Some(component_name), profile),
+        )
+        .context("Failed to render working directory of build command")?;
+    Ok(dir
+        .map(|dir| source_dir.join(dir))
+        .unwrap_or_else(|| source_dir.to_path_buf()))
+}
+
+/// Evaluates the when condition of an external command, commands without a condition
+/// are always enabled
+pub fn external_command_enabled(
+    ctx: &ApplicationContext,
+    component_name: Option<&AppComponentName>,
+    profile: Option<&BuildProfileName>,
+    command: &app_raw::ExternalCommand,
+) -> anyhow::Result<bool> {
+    match &command.when {
+        Some(when) => evaluate_condition(
+            when,
+            external_command_template_context(ctx, component_name, profile),
+        ),
+        None => Ok(true),
+    }
+}
+
+fn evaluate_condition<C: Serialize>(condition: &str, template_ctx: C) -> anyhow::Result<bool> {
+    let template_env = template_env();
+    let expression = template_env
+        .compile_expression(condition)
+        .with_context(|| {
+            anyhow!(
+                "Failed to compile condition {}",
+                condition.log_color_highlight()
+            )
+        })?;
+    let result = expression.eval(template_ctx).with_context(|| {
+        anyhow!(
+            "Failed to evaluate condition {}",
+            condition.log_color_highlight()
+        )
+    })?;
+    Ok(result.is_true())
+}
+
 pub fn execute_external_command(
     ctx: &ApplicationContext,
     base_build_dir: &Path,
+    component_name: Option<&AppComponentName>,
     command: &app_raw::ExternalCommand,
     additional_env_vars: HashMap<String, String>,
 ) -> anyhow::Result<()> {
+    if !external_command_enabled(ctx, component_name, ctx.build_profile(), command)? {
+        if ctx.config.verbose {
+            log_action(
+                "Skipping",
+                format!(
+                    "external command '{}', condition {} is false",
+                    command.command.log_color_highlight(),
+                    command
+                        .when
+                        .as_deref()
+                        .unwrap_or_default()
+                        .log_color_highlight()
+                ),
+            );
+        }
+        return Ok(());
+    }
+
+    let template_env = template_env();
+    let template_ctx = external_command_template_context(ctx, component_name, ctx.build_profile());
+
     let build_dir = command
         .dir
-        .as_ref()
+        .render(&template_env, &template_ctx)
+        .context("Failed to render working directory of external command")?
         .map(|dir| base_build_dir.join(dir))
         .unwrap_or_else(|| base_build_dir.to_path_buf());
 
-    let task_result_marker = TaskResultMarker::new(
+    let command_env_vars = command
+        .env
+        .render(&template_env, &template_ctx)
+        .context("Failed to render env vars of external command")?;
+
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
         ResolvedExternalCommandMarkerHash {
             build_dir: &build_dir,
@@ -219,9 +474,6 @@ pub fn execute_external_command(
         },
     )?;
 
-    let skip_up_to_date_checks =
-        ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date();
-
     debug!(
         command = ?command,
         "execute external command"
@@ -231,6 +483,7 @@ pub fn execute_external_command(
         let mut map = HashMap::new();
         map.extend(valid_env_vars());
         map.extend(additional_env_vars);
+        map.extend(command_env_vars.clone());
         map
     };
 
@@ -241,7 +494,12 @@ pub fn execute_external_command(
         let sources = compile_and_collect_globs(&build_dir, &command.sources)?;
         let targets = compile_and_collect_globs(&build_dir, &command.targets)?;
 
-        if is_up_to_date(skip_up_to_date_checks, || sources, || targets) {
+        if is_up_to_date(
+            &mut task_result_marker,
+            ctx.config.skip_up_to_date_checks,
+            || sources,
+            || targets,
+        ) {
             log_skipping_up_to_date(format!(
                 "executing external command '{}' in directory {}",
                 command_string.log_color_highlight(),
@@ -290,11 +548,31 @@ pub fn execute_external_command(
             return Err(anyhow!("Empty command!"));
         }
 
-        let result = Command::new(command_tokens[0].clone())
+        let mut process = Command::new(command_tokens[0].clone());
+        process
             .args(command_tokens.iter().skip(1))
-            .current_dir(build_dir)
-            .status()
-            .with_context(|| "Failed to execute command".to_string())?;
+            .envs(command_env_vars)
+            .current_dir(build_dir);
+
+        // When running in parallel, the output is captured and logged line by line,
+        // so it gets the prefix of the task
+        let result = if LogThreadPrefix::is_active() {
+            let output = process
+                .output()
+                .with_context(|| "Failed to execute command".to_string())?;
+            let _indent = LogIndent::new();
+            for line in String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .chain(String::from_utf8_lossy(&output.stderr).lines())
+            {
+                logln(line);
+            }
+            output.status
+        } else {
+            process
+                .status()
+                .with_context(|| "Failed to execute command".to_string())?
+        };
 
         if result.success() {
             Ok(())
@@ -309,3 +587,78 @@ pub fn execute_external_command(
         }
     })())
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::command::{evaluate_condition, order_command_dependencies};
+    use crate::model::app::{AppBuildStep, CommandDependency};
+    use assert2::{assert, let_assert};
+    use test_r::test;
+
+    fn dependencies_of(command_name: &str) -> Option<Vec<CommandDependency>> {
+        match command_name {
+            "npm-install" => Some(vec![]),
+            "generate-openapi" => Some(vec![
+                CommandDependency::CustomCommand("npm-install".to_string()),
+                CommandDependency::BuildStep(AppBuildStep::Link),
+            ]),
+            "publish" => Some(vec![
+                CommandDependency::CustomCommand("generate-openapi".to_string()),
+                CommandDependency::CustomCommand("npm-install".to_string()),
+            ]),
+            "cycle-a" => Some(vec![CommandDependency::CustomCommand(
+                "cycle-b".to_string(),
+            )]),
+            "cycle-b" => Some(vec![CommandDependency::CustomCommand(
+                "cycle-a".to_string(),
+            )]),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn order_command_dependencies_orders_dependencies_first() {
+        let_assert!(
+            Ok(order) = order_command_dependencies(
+                vec![CommandDependency::CustomCommand("publish".to_string())],
+                dependencies_of,
+            )
+        );
+        assert!(
+            order
+                == vec![
+                    CommandDependency::CustomCommand("npm-install".to_string()),
+                    CommandDependency::BuildStep(AppBuildStep::Link),
+                    CommandDependency::CustomCommand("generate-openapi".to_string()),
+                    CommandDependency::CustomCommand("publish".to_string()),
+                ]
+        );
+    }
+
+    #[test]
+    fn order_command_dependencies_detects_cycles() {
+        let_assert!(
+            Err(error) = order_command_dependencies(
+                vec![CommandDependency::CustomCommand("cycle-a".to_string())],
+                dependencies_of,
+            )
+        );
+        assert!(error.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn evaluate_condition_against_profile_and_os() {
+        let template_ctx = minijinja::context! {
+            profile => "release",
+            os => "linux",
+        };
+
+        let_assert!(Ok(true) = evaluate_condition("profile == 'release'", &template_ctx));
+        let_assert!(
+            Ok(false) =
+                evaluate_condition("profile == 'release' and os == 'windows'", &template_ctx)
+        );
+        let_assert!(Ok(false) = evaluate_condition("undefined_variable", &template_ctx));
+        let_assert!(Err(_) = evaluate_condition("profile ==", &template_ctx));
+    }
+}
@@ -12,58 +12,197 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app::build::cache::{component_key, BuildCacheEntryKind};
 use crate::app::build::command::execute_build_command;
 use crate::app::context::ApplicationContext;
-use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::log::{
+    log_action, log_error_action, log_warn_action, LogColorize, LogIndent, LogThreadPrefix,
+};
 use crate::model::app::{AppComponentName, DependencyType};
 use crate::wasm_rpc_stubgen::wit_resolve::ExportedFunction;
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use heck::ToLowerCamelCase;
 use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
 
 pub fn componentize(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
     log_action("Building", "components");
     let _indent = LogIndent::new();
 
     let components_to_build = components_to_build(ctx);
-    for component_name in components_to_build {
-        let component_properties = ctx
-            .application
-            .component_properties(&component_name, ctx.build_profile());
-
-        if component_properties.build.is_empty() {
-            log_warn_action(
-                "Skipping",
-                format!(
-                    "building {}, no build steps",
-                    component_name.as_str().log_color_highlight(),
-                ),
-            );
-            continue;
+    let jobs = ctx.config.build_jobs.get();
+
+    if jobs == 1 || components_to_build.len() <= 1 {
+        for component_name in &components_to_build {
+            build_component(ctx, component_name)?;
         }
+        return Ok(());
+    }
+
+    log_action(
+        "Using",
+        format!("{} parallel jobs", jobs.to_string().log_color_highlight()),
+    );
+    for layer in build_layers(ctx, components_to_build) {
+        build_components_in_parallel(ctx, layer, jobs)?;
+    }
 
-        log_action(
-            "Building",
-            format!("{}", component_name.as_str().log_color_highlight()),
+    Ok(())
+}
+
+fn build_component(
+    ctx: &ApplicationContext,
+    component_name: &AppComponentName,
+) -> anyhow::Result<()> {
+    let component_properties = ctx
+        .application
+        .component_properties(component_name, ctx.build_profile());
+
+    if component_properties.build.is_empty() {
+        log_warn_action(
+            "Skipping",
+            format!(
+                "building {}, no build steps",
+                component_name.as_str().log_color_highlight(),
+            ),
         );
-        let _indent = LogIndent::new();
-
-        let env_vars = build_step_env_vars(ctx, &component_name)
-            .context("Failed to get env vars for build step")?;
-
-        for build_step in &component_properties.build {
-            execute_build_command(
-                ctx,
-                ctx.application.component_source_dir(&component_name),
-                build_step,
-                env_vars.clone(),
-            )?;
+        return Ok(());
+    }
+
+    log_action(
+        "Building",
+        format!("{}", component_name.as_str().log_color_highlight()),
+    );
+    let _indent = LogIndent::new();
+
+    let component_wasm = ctx
+        .application
+        .component_wasm(component_name, ctx.build_profile());
+    let cache_key = match &ctx.build_cache {
+        Some(_) => {
+            component_key(ctx, component_name).context("Failed to calculate build cache key")?
+        }
+        None => None,
+    };
+    if let (Some(build_cache), Some(cache_key)) = (&ctx.build_cache, &cache_key) {
+        if build_cache.restore(BuildCacheEntryKind::Component, cache_key, &component_wasm) {
+            return Ok(());
+        }
+    }
+
+    let env_vars = build_step_env_vars(ctx, component_name)
+        .context("Failed to get env vars for build step")?;
+
+    for build_step in &component_properties.build {
+        execute_build_command(
+            ctx,
+            ctx.application.component_source_dir(component_name),
+            component_name,
+            build_step,
+            env_vars.clone(),
+        )?;
+    }
+
+    if let (Some(build_cache), Some(cache_key)) = (&ctx.build_cache, &cache_key) {
+        if component_wasm.exists() {
+            build_cache.store(BuildCacheEntryKind::Component, cache_key, &component_wasm);
         }
     }
 
     Ok(())
 }
 
+fn build_components_in_parallel(
+    ctx: &ApplicationContext,
+    component_names: Vec<AppComponentName>,
+    jobs: usize,
+) -> anyhow::Result<()> {
+    let workers = jobs.min(component_names.len());
+    let queue = Mutex::new(component_names.into_iter());
+    let errors = Mutex::new(Vec::<(AppComponentName, anyhow::Error)>::new());
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                // Do not start new builds after a failure, but let the running ones finish
+                if !errors.lock().unwrap().is_empty() {
+                    break;
+                }
+                let Some(component_name) = queue.lock().unwrap().next() else {
+                    break;
+                };
+
+                let _prefix = LogThreadPrefix::new(
+                    format!("[{}]", component_name.as_str())
+                        .log_color_help_group()
+                        .to_string(),
+                );
+                if let Err(error) = build_component(ctx, &component_name) {
+                    errors.lock().unwrap().push((component_name, error));
+                }
+            });
+        }
+    });
+
+    let mut errors = errors.into_inner().unwrap();
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.pop().unwrap().1),
+        _ => {
+            for (component_name, error) in &errors {
+                log_error_action(
+                    "Failed",
+                    format!(
+                        "building {}: {:#}",
+                        component_name.as_str().log_color_highlight(),
+                        error
+                    ),
+                );
+            }
+            bail!("Failed to build {} components", errors.len())
+        }
+    }
+}
+
+/// Groups the components into layers, where every component only depends on components
+/// of previous layers, so components of the same layer can be built in parallel.
+fn build_layers(
+    ctx: &ApplicationContext,
+    components_to_build: BTreeSet<AppComponentName>,
+) -> Vec<Vec<AppComponentName>> {
+    let mut remaining = components_to_build;
+    let mut layers = Vec::new();
+
+    while !remaining.is_empty() {
+        let layer = remaining
+            .iter()
+            .filter(|component_name| {
+                ctx.application
+                    .component_dependencies(component_name)
+                    .iter()
+                    .filter(|dep| dep.dep_type == DependencyType::Wasm)
+                    .filter_map(|dep| dep.as_dependent_app_component())
+                    .all(|dep| dep.name == **component_name || !remaining.contains(&dep.name))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        // Dependency cycles cannot be ordered, build the rest of the components together
+        let layer = if layer.is_empty() {
+            std::mem::take(&mut remaining).into_iter().collect()
+        } else {
+            layer
+        };
+
+        for component_name in &layer {
+            remaining.remove(component_name);
+        }
+        layers.push(layer);
+    }
+
+    layers
+}
+
 fn components_to_build(ctx: &ApplicationContext) -> BTreeSet<AppComponentName> {
     let mut components_to_build = BTreeSet::new();
     let mut remaining: Vec<_> = ctx.selected_component_names().iter().cloned().collect();
@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::app::build::task_result_marker::{ComponentGeneratorMarkerHash, TaskResultMarker};
+use crate::app::build::task_result_marker::{
+    ComponentGeneratorMarkerHash, GeneratedWitMarkerHash, TaskResultMarker,
+};
 use crate::app::build::{delete_path_logged, env_var_flag, is_up_to_date};
 use crate::app::context::ApplicationContext;
 use crate::fs;
 use crate::fs::PathExtra;
-use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::log::{log_action, log_skipping_up_to_date, log_warn_action, LogColorize, LogIndent};
 use crate::model::app::{
     AppComponentName, BinaryComponentSource, DependencyType, DependentAppComponent,
 };
@@ -40,16 +42,16 @@ pub async fn gen_rpc(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
     log_action("Generating", "RPC artifacts");
     let _indent = LogIndent::new();
 
+    ctx.fetch_wit_deps(false)?;
+
     {
         for component_name in ctx.wit.component_order_cloned() {
             create_generated_base_wit(ctx, &component_name).await?;
         }
 
         for dep in &ctx.application.all_dependencies() {
-            if dep.dep_type.is_wasm_rpc() {
-                if let Some(dep) = dep.as_dependent_app_component() {
-                    build_client(ctx, &dep).await?;
-                }
+            if let Some(dep) = dep.as_rpc_client_target() {
+                build_client(ctx, &dep).await?;
             }
         }
     }
@@ -87,7 +89,7 @@ async fn create_generated_base_wit(
         inputs
     };
     let component_generated_base_wit = ctx.application.component_generated_base_wit(component_name);
-    let task_result_marker = TaskResultMarker::new(
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
         ComponentGeneratorMarkerHash {
             component_name,
@@ -96,9 +98,8 @@ async fn create_generated_base_wit(
     )?;
 
     if is_up_to_date(
-        ctx.config.skip_up_to_date_checks
-            || !task_result_marker.is_up_to_date()
-            || !ctx.wit.is_dep_graph_up_to_date(component_name)?,
+        &mut task_result_marker,
+        ctx.config.skip_up_to_date_checks || !ctx.wit.is_dep_graph_up_to_date(component_name)?,
         || inputs,
         || [component_generated_base_wit.clone()],
     ) {
@@ -253,19 +254,51 @@ fn create_generated_wit(
     let component_generated_wit = ctx
         .application
         .component_generated_wit(component_name, ctx.build_profile());
-    let task_result_marker = TaskResultMarker::new(
+    let package_renames = ctx
+        .application
+        .component_dependencies(component_name)
+        .iter()
+        .filter_map(|dep| dep.as_rpc_client_target())
+        .map(|dep| {
+            let renames = ctx
+                .application
+                .dependency_package_renames(component_name, &dep.name);
+            (dep.name, renames)
+        })
+        .filter(|(_, renames)| !renames.is_empty())
+        .collect::<Vec<_>>();
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
-        ComponentGeneratorMarkerHash {
+        GeneratedWitMarkerHash {
             component_name,
-            generator_kind: "wit",
+            package_renames: &package_renames,
         },
     )?;
 
+    // Clients of deployed and registry components are not part of the WIT dependency graph,
+    // so they are tracked as sources
+    let deployed_client_wits = ctx
+        .application
+        .component_dependencies(component_name)
+        .iter()
+        .filter(|dep| {
+            matches!(
+                dep.source,
+                BinaryComponentSource::Deployed { .. } | BinaryComponentSource::Registry { .. }
+            )
+        })
+        .filter_map(|dep| dep.as_rpc_client_target())
+        .map(|dep| ctx.application.client_wit(&dep.name))
+        .collect::<Vec<_>>();
+
     if is_up_to_date(
-        ctx.config.skip_up_to_date_checks
-            || !task_result_marker.is_up_to_date()
-            || !ctx.wit.is_dep_graph_up_to_date(component_name)?,
-        || [component_generated_base_wit.clone()],
+        &mut task_result_marker,
+        ctx.config.skip_up_to_date_checks || !ctx.wit.is_dep_graph_up_to_date(component_name)?,
+        || {
+            let mut sources = vec![component_generated_base_wit.clone()];
+            sources.extend(deployed_client_wits);
+            sources
+        },
         || [component_generated_wit.clone()],
     ) {
         log_skipping_up_to_date(format!(
@@ -342,12 +375,8 @@ async fn build_client(
     ctx: &mut ApplicationContext,
     component: &DependentAppComponent,
 ) -> anyhow::Result<bool> {
-    let stub_def = ctx.component_stub_def(
-        &component.name,
-        ctx.application
-            .component_properties(&component.name, ctx.build_profile())
-            .is_ephemeral(),
-    )?;
+    let is_ephemeral = ctx.client_target_is_ephemeral(&component.name)?;
+    let stub_def = ctx.component_stub_def(&component.name, is_ephemeral)?;
     let client_wit_root = stub_def.client_wit_root();
 
     let client_dep_package_ids = stub_def.stub_dep_package_ids();
@@ -366,7 +395,13 @@ async fn build_client(
 
     let client_wasm = ctx.application.client_wasm(&component.name);
     let client_wit = ctx.application.client_wit(&component.name);
-    let task_result_marker = TaskResultMarker::new(
+
+    let source_wit_hash_path = ctx.application.client_source_wit_hash(&component.name);
+    let source_wit_hash = source_wit_hash(&client_sources)?;
+    let client_outdated = source_wit_hash_path.exists()
+        && fs::read_to_string(&source_wit_hash_path)?.trim() != source_wit_hash;
+
+    let mut task_result_marker = TaskResultMarker::new(
         &ctx.application.task_result_marker_dir(),
         ComponentGeneratorMarkerHash {
             component_name: &component.name,
@@ -374,8 +409,11 @@ async fn build_client(
         },
     )?;
 
+    // The stored source WIT hash also catches changes which are not tracked by the task result
+    // marker, e.g. when the marker was removed, or the client was generated by an earlier build
     if is_up_to_date(
-        ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date(),
+        &mut task_result_marker,
+        ctx.config.skip_up_to_date_checks,
         || client_sources,
         || {
             if component.dep_type == DependencyType::StaticWasmRpc {
@@ -384,7 +422,8 @@ async fn build_client(
                 vec![client_wit.clone()]
             }
         },
-    ) {
+    ) && !client_outdated
+    {
         // TODO: message based on type
         log_skipping_up_to_date(format!(
             "generating WASM RPC client for {}",
@@ -392,6 +431,16 @@ async fn build_client(
         ));
         Ok(false)
     } else {
+        if client_outdated {
+            log_warn_action(
+                "Stub",
+                format!(
+                    "outdated for {}, regenerating",
+                    component.name.as_str().log_color_highlight()
+                ),
+            );
+        }
+
         task_result_marker.result(
             async {
                 match component.dep_type {
@@ -421,12 +470,7 @@ async fn build_client(
 
                         let offline = ctx.config.offline;
                         commands::generate::build(
-                            ctx.component_stub_def(
-                                &component.name,
-                                ctx.application
-                                    .component_properties(&component.name, ctx.build_profile())
-                                    .is_ephemeral(),
-                            )?,
+                            ctx.component_stub_def(&component.name, is_ephemeral)?,
                             &client_wasm,
                             &client_wit,
                             offline,
@@ -460,19 +504,16 @@ async fn build_client(
                         );
                         fs::create_dir_all(&client_wit_root)?;
 
-                        let stub_def = ctx.component_stub_def(
-                            &component.name,
-                            ctx.application
-                                .component_properties(&component.name, ctx.build_profile())
-                                .is_ephemeral(),
-                        )?;
+                        let stub_def = ctx.component_stub_def(&component.name, is_ephemeral)?;
                         commands::generate::generate_and_copy_client_wit(stub_def, &client_wit)
                     }
                     DependencyType::Wasm => {
                         // No need to generate RPC clients for this dependency type
-                        Ok(())
+                        return Ok(());
                     }
-                }
+                }?;
+
+                fs::write_str(&source_wit_hash_path, &source_wit_hash)
             }
             .await,
         )?;
@@ -481,6 +522,16 @@ async fn build_client(
     }
 }
 
+/// Hashes the paths and contents of the source WIT files of a client
+fn source_wit_hash(sources: &[PathBuf]) -> anyhow::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    for source in sources.iter().sorted() {
+        hasher.update(source.to_string_lossy().as_bytes());
+        hasher.update(blake3::hash(&fs::read(source)?).as_bytes());
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
 fn add_client_deps(
     ctx: &ApplicationContext,
     component_name: &AppComponentName,
@@ -500,26 +551,28 @@ fn add_client_deps(
         let _indent = LogIndent::new();
 
         for dep_component in dependencies {
-            if dep_component.dep_type.is_wasm_rpc() {
-                if let Some(dep_component) = dep_component.as_dependent_app_component() {
-                    log_action(
-                        "Adding",
-                        format!(
-                            "{} client wit dependency to {}",
-                            dep_component.name.as_str().log_color_highlight(),
-                            component_name.as_str().log_color_highlight()
-                        ),
-                    );
-                    let _indent = LogIndent::new();
+            if let Some(dep_component) = dep_component.as_rpc_client_target() {
+                log_action(
+                    "Adding",
+                    format!(
+                        "{} client wit dependency to {}",
+                        dep_component.name.as_str().log_color_highlight(),
+                        component_name.as_str().log_color_highlight()
+                    ),
+                );
+                let _indent = LogIndent::new();
 
-                    add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
-                        client_wit_root: ctx.application.client_wit(&dep_component.name),
-                        dest_wit_root: ctx
-                            .application
-                            .component_generated_wit(component_name, ctx.build_profile()),
-                        update_cargo_toml: UpdateCargoToml::NoUpdate,
-                    })?
-                }
+                add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
+                    client_wit_root: ctx.application.client_wit(&dep_component.name),
+                    dest_wit_root: ctx
+                        .application
+                        .component_generated_wit(component_name, ctx.build_profile()),
+                    update_cargo_toml: UpdateCargoToml::NoUpdate,
+                    package_renames: ctx
+                        .application
+                        .dependency_package_renames(component_name, &dep_component.name),
+                    client_naming: ctx.application.client_naming(),
+                })?
             }
         }
 
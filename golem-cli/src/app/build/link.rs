@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app::build::cache::{linked_key, BuildCacheEntryKind};
 use crate::app::build::is_up_to_date;
 use crate::app::build::task_result_marker::{LinkRpcMarkerHash, TaskResultMarker};
 use crate::app::context::ApplicationContext;
@@ -19,6 +20,7 @@ use crate::fs;
 use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
 use crate::model::app::DependencyType;
 use crate::wasm_rpc_stubgen::commands;
+use crate::wasm_rpc_stubgen::commands::composition::Composition;
 use itertools::Itertools;
 use std::collections::BTreeSet;
 
@@ -55,17 +57,26 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
             let path = ctx.resolve_binary_component_source(library_dep).await?;
             wasms_to_compose_with.push(path);
         }
+        let plugs = ctx
+            .application
+            .component_plugs(component_name, ctx.build_profile());
+        wasms_to_compose_with.extend(plugs.iter().cloned());
+        let wasi_adapter = ctx
+            .application
+            .component_wasi_adapter(component_name, ctx.build_profile());
 
         let component_wasm = ctx
             .application
             .component_wasm(component_name, ctx.build_profile());
         let linked_wasm = ctx.application.component_temp_linked_wasm(component_name);
 
-        let task_result_marker = TaskResultMarker::new(
+        let mut task_result_marker = TaskResultMarker::new(
             &ctx.application.task_result_marker_dir(),
             LinkRpcMarkerHash {
                 component_name,
                 dependencies: &static_dependencies,
+                plugs: &plugs,
+                wasi_adapter: wasi_adapter.as_deref(),
             },
         )?;
 
@@ -111,11 +122,27 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
             );
         }
 
+        if !plugs.is_empty() {
+            log_action(
+                "Found",
+                format!(
+                    "plugs ({}) for {}",
+                    plugs
+                        .iter()
+                        .map(|plug| plug.log_color_highlight())
+                        .join(", "),
+                    component_name.as_str().log_color_highlight(),
+                ),
+            );
+        }
+
         if is_up_to_date(
-            ctx.config.skip_up_to_date_checks || !task_result_marker.is_up_to_date(),
+            &mut task_result_marker,
+            ctx.config.skip_up_to_date_checks,
             || {
                 let mut inputs = wasms_to_compose_with.clone();
                 inputs.push(component_wasm.clone());
+                inputs.extend(wasi_adapter.clone());
                 inputs
             },
             || [linked_wasm.clone()],
@@ -127,9 +154,25 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
             continue;
         }
 
+        let needs_linking = !wasms_to_compose_with.is_empty() || wasi_adapter.is_some();
+        let cache_key = match &ctx.build_cache {
+            Some(_) if needs_linking => Some(linked_key(
+                &component_wasm,
+                &wasms_to_compose_with,
+                wasi_adapter.as_deref(),
+            )?),
+            _ => None,
+        };
+        let restored_from_cache = match (&ctx.build_cache, &cache_key) {
+            (Some(build_cache), Some(cache_key)) => {
+                build_cache.restore(BuildCacheEntryKind::Linked, cache_key, &linked_wasm)
+            }
+            _ => false,
+        };
+
         task_result_marker.result(
             async {
-                if wasms_to_compose_with.is_empty() {
+                if !needs_linking {
                     log_action(
                         "Copying",
                         format!(
@@ -138,6 +181,8 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
                         ),
                     );
                     fs::copy(&component_wasm, &linked_wasm).map(|_| ())
+                } else if restored_from_cache {
+                    Ok(())
                 } else {
                     log_action(
                         "Linking",
@@ -151,20 +196,30 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
                                         .iter()
                                         .map(|s| s.source.to_string().log_color_highlight()),
                                 )
+                                .chain(plugs.iter().map(|plug| plug.log_color_highlight()))
                                 .join(", "),
                             component_name.as_str().log_color_highlight(),
                         ),
                     );
                     let _indent = LogIndent::new();
 
-                    commands::composition::compose(
+                    let composition = commands::composition::compose(
                         ctx.application
                             .component_wasm(component_name, ctx.build_profile())
                             .as_path(),
                         &wasms_to_compose_with,
                         linked_wasm.as_path(),
+                        wasi_adapter.as_deref(),
                     )
-                    .await
+                    .await?;
+
+                    log_composition(&composition);
+
+                    if let (Some(build_cache), Some(cache_key)) = (&ctx.build_cache, &cache_key) {
+                        build_cache.store(BuildCacheEntryKind::Linked, cache_key, &linked_wasm);
+                    }
+
+                    Ok(())
                 }
             }
             .await,
@@ -173,3 +228,24 @@ pub async fn link(ctx: &ApplicationContext) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn log_composition(composition: &Composition) {
+    log_action("Composed", "imports:");
+    let _indent = LogIndent::new();
+    for (import, plug) in &composition.plugged_imports {
+        log_action(
+            "Plugged",
+            format!(
+                "{} with {}",
+                import.log_color_highlight(),
+                plug.log_color_highlight()
+            ),
+        );
+    }
+    for import in &composition.host_imports {
+        log_action(
+            "Left",
+            format!("{} for the host", import.log_color_highlight()),
+        );
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::context::ApplicationContext;
+use crate::fs;
+use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::wasm_rpc_stubgen::GOLEM_RPC_WIT_VERSION;
+use anyhow::{anyhow, bail, Context};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+pub const LOCK_FILE_NAME: &str = "golem.lock";
+
+/// Records the content hashes of the generated client WITs and of the WIT directories
+/// the client dependencies were copied into, together with the WASM RPC version used for
+/// generating them.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLock {
+    pub wasm_rpc_version: String,
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl AppLock {
+    fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        serde_yaml::from_str(&fs::read_to_string(path)?)
+            .map(Some)
+            .with_context(|| anyhow!("Failed to parse lockfile {}", path.log_color_highlight()))
+    }
+
+    fn current(ctx: &ApplicationContext) -> anyhow::Result<Self> {
+        Self::from_dirs(&tracked_dirs(ctx))
+    }
+
+    fn from_dirs(dirs: &[PathBuf]) -> anyhow::Result<Self> {
+        let mut files = BTreeMap::new();
+        for dir in dirs {
+            if !dir.exists() {
+                continue;
+            }
+            for entry in WalkDir::new(dir).sort_by_file_name() {
+                let entry = entry.with_context(|| {
+                    anyhow!("Failed to read directory {}", dir.log_color_highlight())
+                })?;
+                if entry.file_type().is_file() {
+                    files.insert(
+                        lock_path_key(entry.path()),
+                        blake3::hash(&fs::read(entry.path())?).to_hex().to_string(),
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            wasm_rpc_version: GOLEM_RPC_WIT_VERSION.to_string(),
+            files,
+        })
+    }
+
+    /// Locked files that still exist, but have a different content
+    fn drifted_files(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .filter(|(path, hash)| {
+                let path = Path::new(path);
+                path.exists()
+                    && fs::read(path)
+                        .map(|content| blake3::hash(&content).to_hex().as_str() != hash.as_str())
+                        .unwrap_or(true)
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    fn check_frozen(
+        stored: Option<&AppLock>,
+        current: &AppLock,
+        lock_file: &Path,
+    ) -> anyhow::Result<()> {
+        let Some(stored) = stored else {
+            bail!(
+                "Frozen mode is enabled, but the lockfile {} does not exist",
+                lock_file.log_color_error_highlight()
+            );
+        };
+        let diff = stored.diff(current);
+        if !diff.is_empty() {
+            bail!(
+                "Frozen mode is enabled, but the generated files differ from the lockfile {}:\n{}",
+                lock_file.log_color_error_highlight(),
+                diff.iter().map(|line| format!("  - {line}")).join("\n")
+            );
+        }
+        Ok(())
+    }
+
+    fn diff(&self, other: &AppLock) -> Vec<String> {
+        let mut diff = Vec::new();
+        if self.wasm_rpc_version != other.wasm_rpc_version {
+            diff.push(format!(
+                "WASM RPC version: {} -> {}",
+                self.wasm_rpc_version, other.wasm_rpc_version
+            ));
+        }
+        let paths = self
+            .files
+            .keys()
+            .chain(other.files.keys())
+            .collect::<BTreeSet<_>>();
+        for path in paths {
+            match (self.files.get(path), other.files.get(path)) {
+                (Some(hash), Some(other_hash)) if hash != other_hash => {
+                    diff.push(format!("changed: {path}"))
+                }
+                (Some(_), None) => diff.push(format!("removed: {path}")),
+                (None, Some(_)) => diff.push(format!("added: {path}")),
+                _ => {}
+            }
+        }
+        diff
+    }
+}
+
+fn lock_file() -> PathBuf {
+    PathBuf::from(LOCK_FILE_NAME)
+}
+
+fn lock_path_key(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn tracked_dirs(ctx: &ApplicationContext) -> Vec<PathBuf> {
+    let mut dirs = ctx
+        .application
+        .component_names()
+        .map(|component_name| {
+            ctx.application
+                .component_generated_wit(component_name, ctx.build_profile())
+        })
+        .collect::<Vec<_>>();
+    dirs.extend(
+        ctx.application
+            .all_dependencies()
+            .iter()
+            .filter_map(|dep| dep.as_rpc_client_target())
+            .map(|dep| ctx.application.client_wit(&dep.name)),
+    );
+    dirs
+}
+
+/// Checks the generated files against the lockfile before generating RPC artifacts.
+///
+/// Manual edits are reported, and the up-to-date checks are disabled for the current build,
+/// so all the generated files get regenerated.
+pub fn check_drift(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
+    let Some(lock) = AppLock::load(&lock_file())? else {
+        return Ok(());
+    };
+
+    let drifted = lock.drifted_files();
+
+    if lock.wasm_rpc_version != GOLEM_RPC_WIT_VERSION {
+        log_warn_action(
+            "Detected",
+            format!(
+                "WASM RPC version change since the last build: {} -> {}",
+                lock.wasm_rpc_version.log_color_highlight(),
+                GOLEM_RPC_WIT_VERSION.log_color_highlight()
+            ),
+        );
+        ctx.config.skip_up_to_date_checks = true;
+    }
+
+    if !drifted.is_empty() {
+        log_warn_action("Detected", "manual edits in generated files:");
+        let _indent = LogIndent::new();
+        for path in &drifted {
+            log_warn_action("Edited", path.log_color_highlight());
+        }
+        ctx.config.skip_up_to_date_checks = true;
+    }
+
+    Ok(())
+}
+
+/// Updates the lockfile after generating RPC artifacts, or in frozen mode fails if the lockfile
+/// is missing or the generated files differ from the locked ones.
+pub fn update_or_check_lock(ctx: &ApplicationContext) -> anyhow::Result<()> {
+    let lock_file = lock_file();
+    let stored = AppLock::load(&lock_file)?;
+    let current = AppLock::current(ctx)?;
+
+    if ctx.config.frozen {
+        return AppLock::check_frozen(stored.as_ref(), &current, &lock_file);
+    }
+
+    if stored.as_ref() != Some(&current) {
+        log_action(
+            "Updating",
+            format!("lockfile {}", lock_file.log_color_highlight()),
+        );
+        fs::write_str(
+            &lock_file,
+            serde_yaml::to_string(&current).context("Failed to serialize lockfile")?,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::lock::{lock_path_key, AppLock};
+    use crate::wasm_rpc_stubgen::GOLEM_RPC_WIT_VERSION;
+    use assert2::{assert, let_assert};
+    use std::path::{Path, PathBuf};
+    use test_r::test;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn diff_new_changed_and_removed_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wit_dir = temp_dir.path().join("wit");
+        let changed = wit_dir.join("client.wit");
+        let removed = wit_dir.join("deps/removed/package.wit");
+        let added = wit_dir.join("deps/added/package.wit");
+        let unchanged = wit_dir.join("deps/unchanged/package.wit");
+
+        write(&changed, "package test:client;");
+        write(&removed, "package test:removed;");
+        write(&unchanged, "package test:unchanged;");
+        let stored =
+            AppLock::from_dirs(&[wit_dir.clone(), temp_dir.path().join("missing")]).unwrap();
+        assert!(stored.wasm_rpc_version == GOLEM_RPC_WIT_VERSION);
+        assert!(stored.files.len() == 3);
+
+        write(&changed, "package test:client@1.0.0;");
+        std::fs::remove_file(&removed).unwrap();
+        write(&added, "package test:added;");
+        let current = AppLock::from_dirs(&[wit_dir]).unwrap();
+
+        assert!(
+            stored.diff(&current)
+                == vec![
+                    format!("changed: {}", lock_path_key(&changed)),
+                    format!("added: {}", lock_path_key(&added)),
+                    format!("removed: {}", lock_path_key(&removed)),
+                ]
+        );
+        assert!(current.diff(&current).is_empty());
+
+        let other_version = AppLock {
+            wasm_rpc_version: "0.0.1".to_string(),
+            ..current.clone()
+        };
+        assert!(
+            other_version.diff(&current)
+                == vec![format!(
+                    "WASM RPC version: 0.0.1 -> {GOLEM_RPC_WIT_VERSION}"
+                )]
+        );
+    }
+
+    #[test]
+    fn drifted_files_are_the_edited_ones() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wit_dir = temp_dir.path().join("wit");
+        let edited = wit_dir.join("client.wit");
+        let deleted = wit_dir.join("exports.wit");
+
+        write(&edited, "package test:client;");
+        write(&deleted, "package test:exports;");
+        write(
+            &wit_dir.join("deps/untouched.wit"),
+            "package test:untouched;",
+        );
+        let lock = AppLock::from_dirs(&[wit_dir]).unwrap();
+        assert!(lock.drifted_files().is_empty());
+
+        write(&edited, "package test:client; // edited");
+        std::fs::remove_file(&deleted).unwrap();
+
+        assert!(lock.drifted_files() == vec![lock_path_key(&edited)]);
+    }
+
+    #[test]
+    fn frozen_fails_on_missing_or_different_lock() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wit_dir = temp_dir.path().join("wit");
+        let client = wit_dir.join("client.wit");
+        let lock_file = PathBuf::from("golem.lock");
+
+        write(&client, "package test:client;");
+        let stored = AppLock::from_dirs(&[wit_dir.clone()]).unwrap();
+
+        let_assert!(Err(error) = AppLock::check_frozen(None, &stored, &lock_file));
+        assert!(error.to_string().contains("does not exist"));
+
+        assert!(AppLock::check_frozen(Some(&stored), &stored, &lock_file).is_ok());
+
+        write(&client, "package test:client@2.0.0;");
+        let current = AppLock::from_dirs(&[wit_dir]).unwrap();
+        let_assert!(Err(error) = AppLock::check_frozen(Some(&stored), &current, &lock_file));
+        let error = error.to_string();
+        assert!(error.contains("differ from the lockfile"));
+        assert!(error.contains(&format!("  - changed: {}", lock_path_key(&client))));
+    }
+}
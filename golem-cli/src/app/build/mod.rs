@@ -13,35 +13,48 @@
 // limitations under the License.
 
 use crate::app::build::add_metadata::add_metadata_to_selected_components;
+use crate::app::build::command::execute_build_command_dependencies;
 use crate::app::build::componentize::componentize;
 use crate::app::build::gen_rpc::gen_rpc;
 use crate::app::build::link::link;
+use crate::app::build::optimize::optimize;
+use crate::app::build::task_result_marker::{ContentHashes, TaskResultMarker};
 use crate::app::context::ApplicationContext;
 use crate::fs;
-use crate::log::{log_warn_action, LogColorize};
+use crate::log::{log_action, log_warn_action, LogColorize};
 use crate::model::app::AppBuildStep;
 use anyhow::{anyhow, Context};
-use chrono::{DateTime, Utc};
-use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fmt::Debug;
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
 use tracing::debug;
-use walkdir::WalkDir;
 
 pub mod add_metadata;
+pub mod backup;
+pub mod cache;
 pub mod clean;
 pub mod command;
 pub mod componentize;
 pub mod gen_rpc;
 pub mod link;
+pub mod lock;
+pub mod optimize;
+pub mod plan;
 pub mod task_result_marker;
+pub mod watch;
 
 pub async fn build_app(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
+    if ctx.config.should_run_step(AppBuildStep::Componentize) {
+        execute_build_command_dependencies(ctx)?;
+    }
     if ctx.config.should_run_step(AppBuildStep::GenRpc) {
+        if ctx.config.backup {
+            backup::backup_gen_rpc_targets(ctx)?;
+        }
+        lock::check_drift(ctx)?;
         gen_rpc(ctx).await?;
+        lock::update_or_check_lock(ctx)?;
     }
     if ctx.config.should_run_step(AppBuildStep::Componentize) {
         componentize(ctx)?;
@@ -49,10 +62,17 @@ pub async fn build_app(ctx: &mut ApplicationContext) -> anyhow::Result<()> {
     if ctx.config.should_run_step(AppBuildStep::Link) {
         link(ctx).await?;
     }
+    if ctx.config.should_run_step(AppBuildStep::Optimize) {
+        optimize(ctx)?;
+    }
     if ctx.config.should_run_step(AppBuildStep::AddMetadata) {
         add_metadata_to_selected_components(ctx).await?;
     }
 
+    if let Some(build_cache) = &ctx.build_cache {
+        log_action("Summary", format!("build cache: {}", build_cache.stats()));
+    }
+
     Ok(())
 }
 
@@ -113,87 +133,61 @@ fn delete_path_logged(context: &str, path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn is_up_to_date<S, T, FS, FT>(skip_check: bool, sources: FS, targets: FT) -> bool
+/// Content hash based up-to-date check for tasks with sources and targets.
+///
+/// The task is up-to-date if the last run of the task with the same properties was successful,
+/// and the content hashes of the sources and targets are the same as the ones saved after that
+/// run. The sources and targets are registered in the task result marker, so their hashes get
+/// saved on success.
+fn is_up_to_date<S, T, FS, FT>(
+    task_result_marker: &mut TaskResultMarker,
+    skip_check: bool,
+    sources: FS,
+    targets: FT,
+) -> bool
 where
     S: Debug + IntoIterator<Item = PathBuf>,
     T: Debug + IntoIterator<Item = PathBuf>,
     FS: FnOnce() -> S,
     FT: FnOnce() -> T,
 {
-    if skip_check {
-        debug!("skipping up-to-date check");
-        return false;
-    }
+    let sources = sources().into_iter().collect::<Vec<_>>();
+    debug!(source=?sources, "collected sources");
+    let targets = targets().into_iter().collect::<Vec<_>>();
+    debug!(targets=?targets, "collected targets");
 
-    fn max_modified(path: &Path) -> Option<SystemTime> {
-        let mut max_modified: Option<SystemTime> = None;
-        let mut update_max_modified = |modified: SystemTime| {
-            if max_modified.is_none_or(|max_mod| max_mod.cmp(&modified) == Ordering::Less) {
-                max_modified = Some(modified)
+    let up_to_date = if skip_check {
+        debug!("skipping up-to-date check");
+        false
+    } else if !task_result_marker.is_up_to_date() {
+        debug!("task properties changed or the last run failed, not up-to-date");
+        false
+    } else {
+        match (
+            task_result_marker.previous_content_hashes(),
+            ContentHashes::new(&sources, &targets),
+        ) {
+            (Some(previous), Ok(Some(current))) => {
+                let up_to_date = *previous == current;
+                debug!(up_to_date, "up to date result based on content hashes");
+                up_to_date
             }
-        };
-
-        if let Ok(metadata) = fs::metadata(path) {
-            if metadata.is_dir() {
-                WalkDir::new(path)
-                    .into_iter()
-                    .filter_map(|entry| entry.ok().and_then(|entry| entry.metadata().ok()))
-                    .filter(|metadata| !metadata.is_dir())
-                    .filter_map(|metadata| metadata.modified().ok())
-                    .for_each(update_max_modified)
-            } else if let Ok(modified) = metadata.modified() {
-                update_max_modified(modified)
+            (None, _) => {
+                debug!("missing previous content hashes, not up-to-date");
+                false
+            }
+            (_, Ok(None)) => {
+                debug!("missing sources or targets, not up-to-date");
+                false
+            }
+            (_, Err(err)) => {
+                debug!(err = %err, "failed to calculate content hashes, not up-to-date");
+                false
             }
-        }
-
-        debug!(
-            path = %path.display(),
-            max_modified = max_modified.map(|d| DateTime::<Utc>::from(d).to_string()),
-            "max modified"
-        );
-
-        max_modified
-    }
-
-    fn max_modified_short_circuit_on_missing<I: IntoIterator<Item = PathBuf>>(
-        paths: I,
-    ) -> Option<SystemTime> {
-        // Using Result and collect for short-circuit on any missing mod time
-        paths
-            .into_iter()
-            .map(|path| max_modified(path.as_path()).ok_or(()))
-            .collect::<Result<Vec<_>, _>>()
-            .and_then(|mod_times| mod_times.into_iter().max().ok_or(()))
-            .ok()
-    }
-
-    let targets = targets();
-    debug!(targets=?targets, "collected targets");
-
-    let max_target_modified = max_modified_short_circuit_on_missing(targets);
-
-    let max_target_modified = match max_target_modified {
-        Some(modified) => modified,
-        None => {
-            debug!("missing targets, not up-to-date");
-            return false;
         }
     };
 
-    let sources = sources();
-    debug!(source=?sources, "collected sources");
-
-    let max_source_modified = max_modified_short_circuit_on_missing(sources);
+    task_result_marker.set_content_paths(sources, targets);
 
-    match max_source_modified {
-        Some(max_source_modified) => {
-            let up_to_date = max_source_modified.cmp(&max_target_modified) == Ordering::Less;
-            debug!(up_to_date, "up to date result based on timestamps");
-            up_to_date
-        }
-        None => {
-            debug!("missing sources, not up-to-date");
-            false
-        }
-    }
+    up_to_date
 }
@@ -0,0 +1,168 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::build::is_up_to_date;
+use crate::app::build::task_result_marker::{OptimizeMarkerHash, TaskResultMarker};
+use crate::app::context::ApplicationContext;
+use crate::fs;
+use crate::log::{log_action, log_skipping_up_to_date, LogColorize, LogIndent};
+use crate::model::app_raw;
+use anyhow::{anyhow, Context};
+use std::path::Path;
+use std::process::Command;
+
+const WASM_OPT: &str = "wasm-opt";
+
+/// Optimizes the linked components which have optimization enabled, using wasm-opt from PATH
+pub fn optimize(ctx: &ApplicationContext) -> anyhow::Result<()> {
+    let components_to_optimize = ctx
+        .selected_component_names()
+        .iter()
+        .filter_map(|component_name| {
+            ctx.application
+                .component_properties(component_name, ctx.build_profile())
+                .optimize
+                .as_ref()
+                .map(|optimize| (component_name, optimize))
+        })
+        .collect::<Vec<_>>();
+
+    if components_to_optimize.is_empty() {
+        return Ok(());
+    }
+
+    log_action("Optimizing", "components");
+    let _indent = LogIndent::new();
+
+    for (component_name, optimize) in components_to_optimize {
+        let linked_wasm = ctx.application.component_temp_linked_wasm(component_name);
+        let optimized_wasm = ctx
+            .application
+            .component_temp_optimized_wasm(component_name);
+
+        let mut task_result_marker = TaskResultMarker::new(
+            &ctx.application.task_result_marker_dir(),
+            OptimizeMarkerHash {
+                component_name,
+                optimize,
+            },
+        )?;
+
+        if is_up_to_date(
+            &mut task_result_marker,
+            ctx.config.skip_up_to_date_checks,
+            || vec![linked_wasm.clone()],
+            || [optimized_wasm.clone()],
+        ) {
+            log_skipping_up_to_date(format!(
+                "optimizing {}",
+                component_name.as_str().log_color_highlight(),
+            ));
+            continue;
+        }
+
+        log_action(
+            "Optimizing",
+            format!(
+                "{} with {} {}",
+                component_name.as_str().log_color_highlight(),
+                WASM_OPT.log_color_highlight(),
+                optimize.level.as_arg().log_color_highlight()
+            ),
+        );
+
+        task_result_marker.result(run_wasm_opt(optimize, &linked_wasm, &optimized_wasm))?;
+    }
+
+    Ok(())
+}
+
+pub fn wasm_opt_args(optimize: &app_raw::Optimize, input: &Path, output: &Path) -> Vec<String> {
+    let mut args = vec![WASM_OPT.to_string(), optimize.level.as_arg().to_string()];
+    args.extend(optimize.passes.iter().map(|pass| {
+        if pass.starts_with('-') {
+            pass.clone()
+        } else {
+            format!("--{pass}")
+        }
+    }));
+    args.push(input.to_string_lossy().to_string());
+    args.push("-o".to_string());
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+fn run_wasm_opt(optimize: &app_raw::Optimize, input: &Path, output: &Path) -> anyhow::Result<()> {
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let args = wasm_opt_args(optimize, input, output);
+    let result = Command::new(&args[0])
+        .args(&args[1..])
+        .status()
+        .with_context(|| {
+            anyhow!(
+                "Failed to execute {}, make sure binaryen is installed and {} is available in PATH",
+                WASM_OPT.log_color_error_highlight(),
+                WASM_OPT.log_color_highlight()
+            )
+        })?;
+
+    if result.success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{} failed with exit code: {}",
+            WASM_OPT,
+            result
+                .code()
+                .map(|code| code.to_string().log_color_error_highlight().to_string())
+                .unwrap_or_else(|| "?".to_string())
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::optimize::wasm_opt_args;
+    use crate::model::app_raw;
+    use assert2::assert;
+    use std::path::Path;
+    use test_r::test;
+
+    #[test]
+    fn wasm_opt_args_from_level_and_passes() {
+        let optimize = app_raw::Optimize {
+            level: app_raw::WasmOptLevel::Oz,
+            passes: vec![
+                "strip-debug".to_string(),
+                "--enable-bulk-memory".to_string(),
+            ],
+        };
+
+        assert!(
+            wasm_opt_args(&optimize, Path::new("in.wasm"), Path::new("out.wasm"))
+                == vec![
+                    "wasm-opt",
+                    "-Oz",
+                    "--strip-debug",
+                    "--enable-bulk-memory",
+                    "in.wasm",
+                    "-o",
+                    "out.wasm"
+                ]
+        );
+    }
+}
@@ -0,0 +1,413 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::build::add_metadata::build_metadata;
+use crate::app::build::command::{build_command_dir, external_command_enabled};
+use crate::app::build::is_up_to_date;
+use crate::app::build::lock::LOCK_FILE_NAME;
+use crate::app::build::optimize::wasm_opt_args;
+use crate::app::build::task_result_marker::{
+    AddMetadataMarkerHash, LinkRpcMarkerHash, OptimizeMarkerHash,
+    ResolvedExternalCommandMarkerHash, TaskResultMarker,
+};
+use crate::app::context::ApplicationContext;
+use crate::fs::compile_and_collect_globs;
+use crate::model::app::{
+    AppBuildStep, AppComponentName, BinaryComponentSource, DependencyType, DependentComponent,
+};
+use crate::model::app_raw;
+use crate::model::build_plan::{BuildPlan, BuildPlanCommand, BuildPlanFile, BuildPlanTask};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Collects the build plan of the selected components, without executing any of the steps
+pub fn build_plan(ctx: &ApplicationContext) -> anyhow::Result<BuildPlan> {
+    let mut tasks = Vec::new();
+
+    if ctx.config.should_run_step(AppBuildStep::GenRpc) {
+        plan_gen_rpc(ctx, &mut tasks);
+    }
+    if ctx.config.should_run_step(AppBuildStep::Componentize) {
+        plan_componentize(ctx, &mut tasks)?;
+    }
+    if ctx.config.should_run_step(AppBuildStep::Link) {
+        plan_link(ctx, &mut tasks)?;
+    }
+    if ctx.config.should_run_step(AppBuildStep::Optimize) {
+        plan_optimize(ctx, &mut tasks)?;
+    }
+    if ctx.config.should_run_step(AppBuildStep::AddMetadata) {
+        plan_add_metadata(ctx, &mut tasks)?;
+    }
+
+    Ok(BuildPlan { tasks })
+}
+
+fn step_name(step: AppBuildStep) -> String {
+    clap::ValueEnum::to_possible_value(&step)
+        .expect("Missing build step name")
+        .get_name()
+        .to_string()
+}
+
+fn plan_gen_rpc(ctx: &ApplicationContext, tasks: &mut Vec<BuildPlanTask>) {
+    let step = step_name(AppBuildStep::GenRpc);
+
+    // One client task per dependency component, static clients also include the client WASM
+    let mut client_dependencies = BTreeMap::<AppComponentName, DependencyType>::new();
+    for component_name in ctx.selected_component_names() {
+        tasks.push(BuildPlanTask {
+            step: step.clone(),
+            component_name: Some(component_name.to_string()),
+            description: "generate WIT".to_string(),
+            up_to_date: false,
+            command: None,
+            files: vec![
+                BuildPlanFile::new(
+                    ctx.application.component_generated_base_wit(component_name),
+                    false,
+                ),
+                BuildPlanFile::new(
+                    ctx.application
+                        .component_generated_wit(component_name, ctx.build_profile()),
+                    false,
+                ),
+            ],
+        });
+
+        for dep in ctx
+            .application
+            .component_dependencies(component_name)
+            .iter()
+            .filter(|dep| dep.dep_type.is_wasm_rpc())
+        {
+            if let Some(dep_component) = dep.as_rpc_client_target() {
+                let dep_type = client_dependencies
+                    .entry(dep_component.name)
+                    .or_insert(dep.dep_type);
+                if dep.dep_type == DependencyType::StaticWasmRpc {
+                    *dep_type = DependencyType::StaticWasmRpc;
+                }
+            }
+        }
+    }
+
+    for (dep_component_name, dep_type) in client_dependencies {
+        let mut files = vec![BuildPlanFile::new(
+            ctx.application.client_wit(&dep_component_name),
+            false,
+        )];
+        if dep_type == DependencyType::StaticWasmRpc {
+            files.push(BuildPlanFile::new(
+                ctx.application.client_wasm(&dep_component_name),
+                false,
+            ));
+        }
+        tasks.push(BuildPlanTask {
+            step: step.clone(),
+            component_name: Some(dep_component_name.to_string()),
+            description: format!("generate {} client", dep_type.as_str()),
+            up_to_date: false,
+            command: None,
+            files,
+        });
+    }
+
+    tasks.push(BuildPlanTask {
+        step,
+        component_name: None,
+        description: "update lockfile".to_string(),
+        up_to_date: false,
+        command: None,
+        files: vec![BuildPlanFile::new(PathBuf::from(LOCK_FILE_NAME), false)],
+    });
+}
+
+fn plan_componentize(
+    ctx: &ApplicationContext,
+    tasks: &mut Vec<BuildPlanTask>,
+) -> anyhow::Result<()> {
+    let step = step_name(AppBuildStep::Componentize);
+
+    for component_name in ctx.selected_component_names() {
+        let properties = ctx
+            .application
+            .component_properties(component_name, ctx.build_profile());
+        for command in &properties.build {
+            let build_dir = build_command_dir(ctx, component_name, ctx.build_profile(), command)?;
+            let task = match command {
+                app_raw::BuildCommand::External(command) => {
+                    if !external_command_enabled(
+                        ctx,
+                        Some(component_name),
+                        ctx.build_profile(),
+                        command,
+                    )? {
+                        continue;
+                    }
+                    let up_to_date = external_command_up_to_date(ctx, &build_dir, command)?;
+                    BuildPlanTask {
+                        step: step.clone(),
+                        component_name: Some(component_name.to_string()),
+                        description: "execute build command".to_string(),
+                        up_to_date,
+                        command: Some(BuildPlanCommand {
+                            command: command.command.clone(),
+                            dir: build_dir.clone(),
+                        }),
+                        files: command
+                            .targets
+                            .iter()
+                            .map(|target| BuildPlanFile::new(build_dir.join(target), up_to_date))
+                            .collect(),
+                    }
+                }
+                app_raw::BuildCommand::QuickJSCrate(command) => BuildPlanTask {
+                    step: step.clone(),
+                    component_name: Some(component_name.to_string()),
+                    description: "generate QuickJS crate".to_string(),
+                    up_to_date: false,
+                    command: None,
+                    files: vec![BuildPlanFile::new(
+                        build_dir.join(&command.generate_quickjs_crate),
+                        false,
+                    )],
+                },
+                app_raw::BuildCommand::QuickJSDTS(command) => BuildPlanTask {
+                    step: step.clone(),
+                    component_name: Some(component_name.to_string()),
+                    description: "generate QuickJS TypeScript declarations".to_string(),
+                    up_to_date: false,
+                    command: None,
+                    files: vec![BuildPlanFile::new(
+                        build_dir.join(&command.generate_quickjs_dts),
+                        false,
+                    )],
+                },
+            };
+            tasks.push(task);
+        }
+    }
+
+    Ok(())
+}
+
+fn external_command_up_to_date(
+    ctx: &ApplicationContext,
+    build_dir: &Path,
+    command: &app_raw::ExternalCommand,
+) -> anyhow::Result<bool> {
+    if command.sources.is_empty() || command.targets.is_empty() {
+        return Ok(false);
+    }
+
+    let mut task_result_marker = TaskResultMarker::new(
+        &ctx.application.task_result_marker_dir(),
+        ResolvedExternalCommandMarkerHash { build_dir, command },
+    )?;
+    let sources = compile_and_collect_globs(build_dir, &command.sources)?;
+    let targets = compile_and_collect_globs(build_dir, &command.targets)?;
+
+    Ok(is_up_to_date(
+        &mut task_result_marker,
+        ctx.config.skip_up_to_date_checks,
+        || sources,
+        || targets,
+    ))
+}
+
+fn plan_link(ctx: &ApplicationContext, tasks: &mut Vec<BuildPlanTask>) -> anyhow::Result<()> {
+    let step = step_name(AppBuildStep::Link);
+
+    for component_name in ctx.selected_component_names() {
+        let static_dependencies = ctx
+            .application
+            .component_dependencies(component_name)
+            .iter()
+            .filter(|dep| dep.dep_type == DependencyType::StaticWasmRpc)
+            .collect::<BTreeSet<_>>();
+        let plugs = ctx
+            .application
+            .component_plugs(component_name, ctx.build_profile());
+        let wasi_adapter = ctx
+            .application
+            .component_wasi_adapter(component_name, ctx.build_profile());
+        let linked_wasm = ctx.application.component_temp_linked_wasm(component_name);
+
+        // Remote dependencies are not downloaded for planning, so those are always planned
+        let mut inputs = Some(vec![ctx
+            .application
+            .component_wasm(component_name, ctx.build_profile())]);
+        for dep in ctx
+            .application
+            .component_dependencies(component_name)
+            .iter()
+            .filter(|dep| dep.dep_type != DependencyType::DynamicWasmRpc)
+        {
+            match local_binary_component_source(ctx, dep) {
+                Some(path) => {
+                    if let Some(inputs) = inputs.as_mut() {
+                        inputs.push(path);
+                    }
+                }
+                None => inputs = None,
+            }
+        }
+
+        let up_to_date = match inputs {
+            Some(mut inputs) => {
+                inputs.extend(plugs.iter().cloned());
+                inputs.extend(wasi_adapter.clone());
+                let mut task_result_marker = TaskResultMarker::new(
+                    &ctx.application.task_result_marker_dir(),
+                    LinkRpcMarkerHash {
+                        component_name,
+                        dependencies: &static_dependencies,
+                        plugs: &plugs,
+                        wasi_adapter: wasi_adapter.as_deref(),
+                    },
+                )?;
+                is_up_to_date(
+                    &mut task_result_marker,
+                    ctx.config.skip_up_to_date_checks,
+                    || inputs,
+                    || [linked_wasm.clone()],
+                )
+            }
+            None => false,
+        };
+
+        tasks.push(BuildPlanTask {
+            step: step.clone(),
+            component_name: Some(component_name.to_string()),
+            description: "link dependencies".to_string(),
+            up_to_date,
+            command: None,
+            files: vec![BuildPlanFile::new(linked_wasm, up_to_date)],
+        });
+    }
+
+    Ok(())
+}
+
+fn local_binary_component_source(
+    ctx: &ApplicationContext,
+    dep: &DependentComponent,
+) -> Option<PathBuf> {
+    match &dep.source {
+        BinaryComponentSource::AppComponent { name } => Some(if dep.dep_type.is_wasm_rpc() {
+            ctx.application.client_wasm(name)
+        } else {
+            ctx.application.component_wasm(name, ctx.build_profile())
+        }),
+        BinaryComponentSource::LocalFile { path } => Some(path.clone()),
+        BinaryComponentSource::Url { .. } => None,
+        BinaryComponentSource::Deployed { name, .. }
+        | BinaryComponentSource::Registry { name, .. } => dep
+            .dep_type
+            .is_wasm_rpc()
+            .then(|| ctx.application.client_wasm(name)),
+    }
+}
+
+fn plan_optimize(ctx: &ApplicationContext, tasks: &mut Vec<BuildPlanTask>) -> anyhow::Result<()> {
+    let step = step_name(AppBuildStep::Optimize);
+
+    for component_name in ctx.selected_component_names() {
+        let Some(optimize) = &ctx
+            .application
+            .component_properties(component_name, ctx.build_profile())
+            .optimize
+        else {
+            continue;
+        };
+
+        let linked_wasm = ctx.application.component_temp_linked_wasm(component_name);
+        let optimized_wasm = ctx
+            .application
+            .component_temp_optimized_wasm(component_name);
+
+        let mut task_result_marker = TaskResultMarker::new(
+            &ctx.application.task_result_marker_dir(),
+            OptimizeMarkerHash {
+                component_name,
+                optimize,
+            },
+        )?;
+        let up_to_date = is_up_to_date(
+            &mut task_result_marker,
+            ctx.config.skip_up_to_date_checks,
+            || vec![linked_wasm.clone()],
+            || [optimized_wasm.clone()],
+        );
+
+        tasks.push(BuildPlanTask {
+            step: step.clone(),
+            component_name: Some(component_name.to_string()),
+            description: "optimize with wasm-opt".to_string(),
+            up_to_date,
+            command: Some(BuildPlanCommand {
+                command: wasm_opt_args(optimize, &linked_wasm, &optimized_wasm).join(" "),
+                dir: PathBuf::from("."),
+            }),
+            files: vec![BuildPlanFile::new(optimized_wasm, up_to_date)],
+        });
+    }
+
+    Ok(())
+}
+
+fn plan_add_metadata(
+    ctx: &ApplicationContext,
+    tasks: &mut Vec<BuildPlanTask>,
+) -> anyhow::Result<()> {
+    let step = step_name(AppBuildStep::AddMetadata);
+    let build_metadata = build_metadata(ctx);
+
+    for component_name in ctx.selected_component_names() {
+        let linked_wasm = ctx
+            .application
+            .component_temp_metadata_input_wasm(component_name, ctx.build_profile());
+        let final_linked_wasm = ctx
+            .application
+            .component_linked_wasm(component_name, ctx.build_profile());
+
+        let mut task_result_marker = TaskResultMarker::new(
+            &ctx.application.task_result_marker_dir(),
+            AddMetadataMarkerHash {
+                component_name,
+                root_package_name: ctx.wit.root_package_name(component_name)?,
+                git_commit: build_metadata.git_commit.as_deref(),
+                app_version: build_metadata.app_version.as_deref(),
+            },
+        )?;
+        let up_to_date = is_up_to_date(
+            &mut task_result_marker,
+            ctx.config.skip_up_to_date_checks,
+            || vec![linked_wasm],
+            || [final_linked_wasm.clone()],
+        );
+
+        tasks.push(BuildPlanTask {
+            step: step.clone(),
+            component_name: Some(component_name.to_string()),
+            description: "add metadata".to_string(),
+            up_to_date,
+            command: None,
+            files: vec![BuildPlanFile::new(final_linked_wasm, up_to_date)],
+        });
+    }
+
+    Ok(())
+}
@@ -14,16 +14,18 @@
 
 use crate::app::build::task_result_marker::TaskResultMarkerHashSourceKind::{Hash, HashFromString};
 use crate::fs;
-use crate::log::log_warn_action;
+use crate::log::{log_warn_action, LogColorize};
 use crate::model::app::{AppComponentName, DependentComponent};
 use crate::model::app_raw::{GenerateQuickJSCrate, GenerateQuickJSDTS};
 use crate::model::ProjectId;
 use crate::model::{app_raw, ComponentName};
+use crate::wasm_rpc_stubgen::wit_generate::PackageRename;
 use anyhow::{anyhow, bail, Context};
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 use wit_parser::PackageName;
 
 pub enum TaskResultMarkerHashSourceKind {
@@ -52,7 +54,7 @@ pub trait TaskResultMarkerHashSource {
     /// The source will be used for calculating the hash value for the task result marker.
     /// It should contain all the properties of the task which should trigger re-runs.
     /// Note that currently we usually do not include file sources in these, as for those
-    /// we use mod-time based checks together with task markers.
+    /// we use content hash based checks, which are also stored in the task markers.
     fn source(&self) -> anyhow::Result<TaskResultMarkerHashSourceKind>;
 }
 
@@ -71,6 +73,66 @@ pub struct TaskResult {
 
     pub hash_hex: String,
     pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hashes: Option<ContentHashes>,
+}
+
+/// Content hashes of the sources and targets of a task, keyed by the declared paths
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentHashes {
+    pub sources: BTreeMap<String, String>,
+    pub targets: BTreeMap<String, String>,
+}
+
+impl ContentHashes {
+    /// Returns None if any of the paths is missing
+    pub fn new(sources: &[PathBuf], targets: &[PathBuf]) -> anyhow::Result<Option<Self>> {
+        fn hashes(paths: &[PathBuf]) -> anyhow::Result<Option<BTreeMap<String, String>>> {
+            let mut hashes = BTreeMap::new();
+            for path in paths {
+                match path_content_hash(path)? {
+                    Some(hash) => {
+                        hashes.insert(path.to_string_lossy().to_string(), hash);
+                    }
+                    None => return Ok(None),
+                }
+            }
+            Ok(Some(hashes))
+        }
+
+        let Some(sources) = hashes(sources)? else {
+            return Ok(None);
+        };
+        let Some(targets) = hashes(targets)? else {
+            return Ok(None);
+        };
+        Ok(Some(Self { sources, targets }))
+    }
+}
+
+/// Hashes the file content, or in case of directories all the contained relative file paths and
+/// file contents. Returns None if the path does not exist.
+fn path_content_hash(path: &Path) -> anyhow::Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    if !path.is_dir() {
+        return Ok(Some(blake3::hash(&fs::read(path)?).to_hex().to_string()));
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    for entry in WalkDir::new(path).sort_by_file_name() {
+        let entry = entry
+            .with_context(|| anyhow!("Failed to read directory {}", path.log_color_highlight()))?;
+        if entry.file_type().is_file() {
+            let relative_path = entry.path().strip_prefix(path)?;
+            hasher.update(relative_path.to_string_lossy().as_bytes());
+            hasher.update(blake3::hash(&fs::read(entry.path())?).as_bytes());
+        }
+    }
+    Ok(Some(hasher.finalize().to_hex().to_string()))
 }
 
 #[derive(Serialize)]
@@ -155,9 +217,44 @@ impl TaskResultMarkerHashSource for ComponentGeneratorMarkerHash<'_> {
     }
 }
 
+pub struct GeneratedWitMarkerHash<'a> {
+    pub component_name: &'a AppComponentName,
+    pub package_renames: &'a [(AppComponentName, Vec<PackageRename>)],
+}
+
+impl TaskResultMarkerHashSource for GeneratedWitMarkerHash<'_> {
+    fn kind() -> &'static str {
+        "GeneratedWitMarkerHash"
+    }
+
+    fn id(&self) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    fn source(&self) -> anyhow::Result<TaskResultMarkerHashSourceKind> {
+        Ok(HashFromString(format!(
+            "{}#{}",
+            self.component_name,
+            self.package_renames
+                .iter()
+                .map(|(target_name, renames)| format!(
+                    "{}#{}",
+                    target_name,
+                    renames
+                        .iter()
+                        .map(|rename| format!("{}={}", rename.from(), rename.to()))
+                        .join(",")
+                ))
+                .join(";")
+        )))
+    }
+}
+
 pub struct LinkRpcMarkerHash<'a> {
     pub component_name: &'a AppComponentName,
     pub dependencies: &'a BTreeSet<&'a DependentComponent>,
+    pub plugs: &'a [PathBuf],
+    pub wasi_adapter: Option<&'a Path>,
 }
 
 impl TaskResultMarkerHashSource for LinkRpcMarkerHash<'_> {
@@ -171,12 +268,39 @@ impl TaskResultMarkerHashSource for LinkRpcMarkerHash<'_> {
 
     fn source(&self) -> anyhow::Result<TaskResultMarkerHashSourceKind> {
         Ok(HashFromString(format!(
-            "{}#{}",
+            "{}#{}#{}#{}",
             self.component_name,
             self.dependencies
                 .iter()
                 .map(|s| format!("{}#{}", s.source, s.dep_type.as_str()))
-                .join(",")
+                .join(","),
+            self.plugs.iter().map(|plug| plug.display()).join(","),
+            self.wasi_adapter
+                .map(|adapter| adapter.display().to_string())
+                .unwrap_or_default()
+        )))
+    }
+}
+
+pub struct OptimizeMarkerHash<'a> {
+    pub component_name: &'a AppComponentName,
+    pub optimize: &'a app_raw::Optimize,
+}
+
+impl TaskResultMarkerHashSource for OptimizeMarkerHash<'_> {
+    fn kind() -> &'static str {
+        "OptimizeMarkerHash"
+    }
+
+    fn id(&self) -> anyhow::Result<Option<String>> {
+        Ok(Some(self.component_name.to_string()))
+    }
+
+    fn source(&self) -> anyhow::Result<TaskResultMarkerHashSourceKind> {
+        Ok(HashFromString(format!(
+            "{}#{}",
+            self.component_name,
+            serde_json::to_string(self.optimize)?
         )))
     }
 }
@@ -184,6 +308,10 @@ impl TaskResultMarkerHashSource for LinkRpcMarkerHash<'_> {
 pub struct AddMetadataMarkerHash<'a> {
     pub component_name: &'a AppComponentName,
     pub root_package_name: PackageName,
+    // NOTE: the build time is not part of the hash, so it only changes when the metadata
+    //       is added again because of other changes
+    pub git_commit: Option<&'a str>,
+    pub app_version: Option<&'a str>,
 }
 
 impl TaskResultMarkerHashSource for AddMetadataMarkerHash<'_> {
@@ -196,7 +324,12 @@ impl TaskResultMarkerHashSource for AddMetadataMarkerHash<'_> {
     }
 
     fn source(&self) -> anyhow::Result<TaskResultMarkerHashSourceKind> {
-        Ok(HashFromString(self.root_package_name.to_string()))
+        Ok(HashFromString(format!(
+            "{}#{}#{}",
+            self.root_package_name,
+            self.git_commit.unwrap_or_default(),
+            self.app_version.unwrap_or_default()
+        )))
     }
 }
 
@@ -268,6 +401,7 @@ pub struct TaskResultMarker {
     marker_file_path: PathBuf,
     hash_hex: String,
     previous_result: Option<TaskResult>,
+    content_paths: Option<(Vec<PathBuf>, Vec<PathBuf>)>,
 }
 
 impl TaskResultMarker {
@@ -298,6 +432,7 @@ impl TaskResultMarker {
             marker_file_path,
             hash_hex,
             previous_result,
+            content_paths: None,
         };
 
         if marker_file_exists && !task_result_marker.is_up_to_date() {
@@ -371,6 +506,17 @@ impl TaskResultMarker {
         }
     }
 
+    pub fn previous_content_hashes(&self) -> Option<&ContentHashes> {
+        self.previous_result
+            .as_ref()
+            .and_then(|previous_result| previous_result.content_hashes.as_ref())
+    }
+
+    /// Sets the sources and targets of the task, their content hashes are saved on success
+    pub fn set_content_paths(&mut self, sources: Vec<PathBuf>, targets: Vec<PathBuf>) {
+        self.content_paths = Some((sources, targets));
+    }
+
     pub fn success(self) -> anyhow::Result<()> {
         self.save_marker_file(true)
     }
@@ -380,6 +526,11 @@ impl TaskResultMarker {
     }
 
     fn save_marker_file(self, success: bool) -> anyhow::Result<()> {
+        let content_hashes = match (&self.content_paths, success) {
+            (Some((sources, targets)), true) => ContentHashes::new(sources, targets)?,
+            _ => None,
+        };
+
         fs::write_str(
             &self.marker_file_path,
             &serde_json::to_string_pretty(&TaskResult {
@@ -389,6 +540,7 @@ impl TaskResultMarker {
                 hash_input: Some(self.hash_input),
                 hash_hex: self.hash_hex,
                 success,
+                content_hashes,
             })?,
         )
     }
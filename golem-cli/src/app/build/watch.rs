@@ -0,0 +1,280 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::build::command::build_command_dir;
+use crate::app::context::ApplicationContext;
+use crate::fs::compile_and_collect_globs;
+use crate::model::app::{AppComponentName, Application};
+use crate::model::app_raw;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+pub const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification time and size of the watched files, used for cheap change detection while polling
+pub type WatchSnapshot = BTreeMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// The files watched for changes: the application manifests, and the sources of the components,
+/// which are their source WIT directories and the sources declared in their build commands.
+#[derive(Debug, Clone, Default)]
+pub struct WatchedSources {
+    manifests: BTreeSet<PathBuf>,
+    component_sources: BTreeMap<AppComponentName, BTreeSet<PathBuf>>,
+}
+
+impl WatchedSources {
+    pub fn collect(ctx: &ApplicationContext) -> anyhow::Result<Self> {
+        let mut component_sources = BTreeMap::new();
+
+        for component_name in ctx.application.component_names() {
+            let mut sources = BTreeSet::new();
+
+            collect_dir_files(
+                &ctx.application
+                    .component_source_wit(component_name, ctx.build_profile()),
+                &mut sources,
+            );
+
+            let properties = ctx
+                .application
+                .component_properties(component_name, ctx.build_profile());
+            for command in &properties.build {
+                let build_dir =
+                    build_command_dir(ctx, component_name, ctx.build_profile(), command)?;
+                match command {
+                    app_raw::BuildCommand::External(command) => {
+                        sources.extend(compile_and_collect_globs(&build_dir, &command.sources)?);
+                    }
+                    app_raw::BuildCommand::QuickJSCrate(command) => {
+                        sources.insert(build_dir.join(&command.wit));
+                        sources.insert(build_dir.join(&command.js));
+                    }
+                    app_raw::BuildCommand::QuickJSDTS(command) => {
+                        sources.insert(build_dir.join(&command.wit));
+                    }
+                }
+            }
+
+            component_sources.insert(component_name.clone(), sources);
+        }
+
+        Ok(Self {
+            manifests: ctx.application.all_sources().clone(),
+            component_sources,
+        })
+    }
+
+    /// Only watches the manifests, used when the application cannot be loaded
+    pub fn manifests_only(manifests: BTreeSet<PathBuf>) -> Self {
+        Self {
+            manifests,
+            component_sources: BTreeMap::new(),
+        }
+    }
+
+    pub fn snapshot(&self) -> WatchSnapshot {
+        self.manifests
+            .iter()
+            .chain(self.component_sources.values().flatten())
+            .map(|path| {
+                let metadata = std::fs::metadata(path).ok();
+                (
+                    path.clone(),
+                    (
+                        metadata.as_ref().and_then(|m| m.modified().ok()),
+                        metadata.map(|m| m.len()).unwrap_or_default(),
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.manifests.len()
+            + self
+                .component_sources
+                .values()
+                .map(|s| s.len())
+                .sum::<usize>()
+    }
+
+    /// Returns None if any of the manifests changed, as then all the components are affected
+    pub fn affected_components(
+        &self,
+        changed_paths: &BTreeSet<PathBuf>,
+    ) -> Option<BTreeSet<AppComponentName>> {
+        if changed_paths
+            .iter()
+            .any(|path| self.manifests.contains(path))
+        {
+            return None;
+        }
+
+        Some(
+            self.component_sources
+                .iter()
+                .filter(|(_, sources)| !sources.is_disjoint(changed_paths))
+                .map(|(component_name, _)| component_name.clone())
+                .collect(),
+        )
+    }
+}
+
+fn collect_dir_files(dir: &Path, files: &mut BTreeSet<PathBuf>) {
+    for entry in WalkDir::new(dir).into_iter().filter_map(|entry| entry.ok()) {
+        if entry.file_type().is_file() {
+            files.insert(entry.path().to_path_buf());
+        }
+    }
+}
+
+pub fn changed_paths(old: &WatchSnapshot, new: &WatchSnapshot) -> BTreeSet<PathBuf> {
+    old.keys()
+        .chain(new.keys())
+        .filter(|path| old.get(*path) != new.get(*path))
+        .cloned()
+        .collect()
+}
+
+/// Collects the changes until the watched files settle, so saving multiple files at once
+/// triggers only one rebuild
+#[derive(Debug, Default)]
+pub struct ChangeDebouncer {
+    pending: BTreeSet<PathBuf>,
+}
+
+impl ChangeDebouncer {
+    /// Returns the collected changes once a poll finds no further changes
+    pub fn poll(&mut self, changed_paths: BTreeSet<PathBuf>) -> Option<BTreeSet<PathBuf>> {
+        if !changed_paths.is_empty() {
+            self.pending.extend(changed_paths);
+            None
+        } else if self.pending.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.pending))
+        }
+    }
+}
+
+/// Extends the components with all the components transitively depending on them
+pub fn with_dependents(
+    application: &Application,
+    components: BTreeSet<AppComponentName>,
+) -> BTreeSet<AppComponentName> {
+    let mut result = components;
+    loop {
+        let dependents = application
+            .component_names()
+            .filter(|component_name| !result.contains(*component_name))
+            .filter(|component_name| {
+                application
+                    .component_dependencies(component_name)
+                    .iter()
+                    .filter_map(|dep| dep.as_dependent_app_component())
+                    .any(|dep| result.contains(&dep.name))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if dependents.is_empty() {
+            return result;
+        }
+        result.extend(dependents);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::build::watch::{changed_paths, ChangeDebouncer, WatchedSources};
+    use crate::model::app::AppComponentName;
+    use assert2::assert;
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::path::PathBuf;
+    use test_r::test;
+
+    fn paths(paths: &[&str]) -> BTreeSet<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn changed_paths_of_snapshots() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let modified = temp_dir.path().join("modified.txt");
+        let removed = temp_dir.path().join("removed.txt");
+        let created = temp_dir.path().join("created.txt");
+        let unchanged = temp_dir.path().join("unchanged.txt");
+        std::fs::write(&modified, "a").unwrap();
+        std::fs::write(&removed, "a").unwrap();
+        std::fs::write(&unchanged, "a").unwrap();
+
+        let sources = WatchedSources::manifests_only(BTreeSet::from([
+            modified.clone(),
+            removed.clone(),
+            created.clone(),
+            unchanged.clone(),
+        ]));
+        let old = sources.snapshot();
+        assert!(changed_paths(&old, &sources.snapshot()).is_empty());
+
+        std::fs::write(&modified, "ab").unwrap();
+        std::fs::remove_file(&removed).unwrap();
+        std::fs::write(&created, "a").unwrap();
+
+        assert!(
+            changed_paths(&old, &sources.snapshot())
+                == BTreeSet::from([modified, removed, created])
+        );
+    }
+
+    #[test]
+    fn affected_components_of_changes() {
+        let sources = WatchedSources {
+            manifests: paths(&["golem.yaml"]),
+            component_sources: BTreeMap::from([
+                (AppComponentName::from("app:a"), paths(&["a/src/lib.rs"])),
+                (AppComponentName::from("app:b"), paths(&["b/src/lib.rs"])),
+            ]),
+        };
+
+        assert!(
+            sources.affected_components(&paths(&["a/src/lib.rs"]))
+                == Some(BTreeSet::from([AppComponentName::from("app:a")]))
+        );
+        assert!(sources.affected_components(&paths(&["c/src/lib.rs"])) == Some(BTreeSet::new()));
+        assert!(sources
+            .affected_components(&paths(&["a/src/lib.rs", "golem.yaml"]))
+            .is_none());
+
+        let manifests_only = WatchedSources::manifests_only(paths(&["golem.yaml"]));
+        assert!(manifests_only.file_count() == 1);
+        assert!(manifests_only
+            .affected_components(&paths(&["golem.yaml"]))
+            .is_none());
+    }
+
+    #[test]
+    fn debouncer_waits_for_changes_to_settle() {
+        let mut debouncer = ChangeDebouncer::default();
+        assert!(debouncer.poll(BTreeSet::new()).is_none());
+
+        assert!(debouncer.poll(paths(&["a.rs"])).is_none());
+        assert!(debouncer.poll(paths(&["b.rs", "a.rs"])).is_none());
+        assert!(debouncer.poll(BTreeSet::new()) == Some(paths(&["a.rs", "b.rs"])));
+
+        assert!(debouncer.poll(BTreeSet::new()).is_none());
+    }
+}
@@ -0,0 +1,503 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::context::ApplicationContext;
+use crate::log::LogColorize;
+use crate::model::app::AppComponentName;
+use crate::model::compat::{CompatDependency, CompatDifference, CompatReport};
+use crate::wasm_rpc_stubgen::naming;
+use anyhow::{anyhow, Context};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::path::Path;
+use wit_parser::{
+    Function, Handle, Interface, Type, TypeDefKind, UnresolvedPackage, UnresolvedPackageGroup,
+};
+
+/// Checks for every WASM RPC dependency, whether the target types vendored into the generated WIT
+/// of the caller are still compatible with the current source WIT of the target component.
+pub fn check_compat(ctx: &ApplicationContext) -> anyhow::Result<CompatReport> {
+    let mut target_packages = BTreeMap::<AppComponentName, UnresolvedPackage>::new();
+    let mut dependencies = Vec::new();
+
+    for component_name in ctx.application.component_names() {
+        let generated_wit = ctx
+            .application
+            .component_generated_wit(component_name, ctx.build_profile());
+
+        for dep in ctx
+            .application
+            .component_dependencies(component_name)
+            .iter()
+            .filter(|dep| dep.dep_type.is_wasm_rpc())
+            .filter_map(|dep| dep.as_dependent_app_component())
+        {
+            if !target_packages.contains_key(&dep.name) {
+                let source_wit = ctx
+                    .application
+                    .component_source_wit(&dep.name, ctx.build_profile());
+                target_packages.insert(dep.name.clone(), parse_package(&source_wit)?);
+            }
+            let target_package = &target_packages[&dep.name];
+
+            let vendored_dir = generated_wit.join(naming::wit::DEPS_DIR).join(
+                naming::wit::package_dep_dir_name_from_parser(&target_package.name),
+            );
+            let (vendored, differences) = if vendored_dir.exists() {
+                (
+                    true,
+                    diff_packages(&parse_package(&vendored_dir)?, target_package),
+                )
+            } else {
+                (false, Vec::new())
+            };
+
+            dependencies.push(CompatDependency {
+                component_name: component_name.to_string(),
+                dependency_name: dep.name.to_string(),
+                package_name: target_package.name.to_string(),
+                vendored,
+                differences,
+            });
+        }
+    }
+
+    Ok(CompatReport { dependencies })
+}
+
+fn parse_package(dir: &Path) -> anyhow::Result<UnresolvedPackage> {
+    Ok(UnresolvedPackageGroup::parse_dir(dir)
+        .with_context(|| {
+            anyhow!(
+                "Failed to parse WIT package in {}",
+                dir.log_color_highlight()
+            )
+        })?
+        .main)
+}
+
+/// Compares the named interfaces of the vendored and the current package
+fn diff_packages(
+    vendored: &UnresolvedPackage,
+    current: &UnresolvedPackage,
+) -> Vec<CompatDifference> {
+    let vendored_interfaces = named_interfaces(vendored);
+    let current_interfaces = named_interfaces(current);
+
+    let mut differences = Vec::new();
+    for interface_name in vendored_interfaces
+        .keys()
+        .chain(current_interfaces.keys())
+        .unique()
+    {
+        match (
+            vendored_interfaces.get(interface_name),
+            current_interfaces.get(interface_name),
+        ) {
+            (Some(_), None) => differences.push(CompatDifference {
+                interface_name: interface_name.to_string(),
+                item: "interface".to_string(),
+                change: "removed".to_string(),
+                breaking: true,
+            }),
+            (None, Some(_)) => differences.push(CompatDifference {
+                interface_name: interface_name.to_string(),
+                item: "interface".to_string(),
+                change: "added".to_string(),
+                breaking: false,
+            }),
+            (Some(vendored_interface), Some(current_interface)) => {
+                differences.extend(
+                    diff_interfaces(vendored, vendored_interface, current, current_interface)
+                        .into_iter()
+                        .map(|(item, change, breaking)| CompatDifference {
+                            interface_name: interface_name.to_string(),
+                            item,
+                            change,
+                            breaking,
+                        }),
+                );
+            }
+            (None, None) => {}
+        }
+    }
+    differences
+}
+
+fn named_interfaces(package: &UnresolvedPackage) -> BTreeMap<&str, &Interface> {
+    package
+        .interfaces
+        .iter()
+        .filter_map(|(_, interface)| {
+            interface
+                .name
+                .as_deref()
+                .map(|interface_name| (interface_name, interface))
+        })
+        .collect()
+}
+
+/// Returns the differences as (item, change, breaking) triples
+fn diff_interfaces(
+    vendored_package: &UnresolvedPackage,
+    vendored: &Interface,
+    current_package: &UnresolvedPackage,
+    current: &Interface,
+) -> Vec<(String, String, bool)> {
+    let mut differences = Vec::new();
+
+    for type_name in vendored.types.keys().chain(current.types.keys()).unique() {
+        let item = format!("type {type_name}");
+        match (vendored.types.get(type_name), current.types.get(type_name)) {
+            (Some(_), None) => differences.push((item, "removed".to_string(), true)),
+            (None, Some(_)) => differences.push((item, "added".to_string(), false)),
+            (Some(vendored_type_id), Some(current_type_id)) => {
+                differences.extend(
+                    diff_type_defs(
+                        vendored_package,
+                        &vendored_package.types[*vendored_type_id].kind,
+                        current_package,
+                        &current_package.types[*current_type_id].kind,
+                    )
+                    .into_iter()
+                    .map(|change| (item.clone(), change, true)),
+                );
+            }
+            (None, None) => {}
+        }
+    }
+
+    for function_name in vendored
+        .functions
+        .keys()
+        .chain(current.functions.keys())
+        .unique()
+    {
+        let item = format!("function {function_name}");
+        match (
+            vendored.functions.get(function_name),
+            current.functions.get(function_name),
+        ) {
+            (Some(_), None) => differences.push((item, "removed".to_string(), true)),
+            (None, Some(_)) => differences.push((item, "added".to_string(), false)),
+            (Some(vendored_function), Some(current_function)) => {
+                let vendored_signature = function_signature(vendored_package, vendored_function);
+                let current_signature = function_signature(current_package, current_function);
+                if vendored_signature != current_signature {
+                    differences.push((
+                        item,
+                        format!(
+                            "signature changed from {vendored_signature} to {current_signature}"
+                        ),
+                        true,
+                    ));
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    differences
+}
+
+/// Compares the fields, cases or flags of the type definitions one by one, other kinds of
+/// definitions are compared by their signatures
+fn diff_type_defs(
+    vendored_package: &UnresolvedPackage,
+    vendored: &TypeDefKind,
+    current_package: &UnresolvedPackage,
+    current: &TypeDefKind,
+) -> Vec<String> {
+    match (vendored, current) {
+        (TypeDefKind::Record(vendored), TypeDefKind::Record(current)) => diff_members(
+            "field",
+            &vendored
+                .fields
+                .iter()
+                .map(|field| {
+                    (
+                        field.name.clone(),
+                        type_signature(vendored_package, &field.ty),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            &current
+                .fields
+                .iter()
+                .map(|field| {
+                    (
+                        field.name.clone(),
+                        type_signature(current_package, &field.ty),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        ),
+        (TypeDefKind::Variant(vendored), TypeDefKind::Variant(current)) => diff_members(
+            "case",
+            &vendored
+                .cases
+                .iter()
+                .map(|case| {
+                    (
+                        case.name.clone(),
+                        opt_type_signature(vendored_package, &case.ty),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            &current
+                .cases
+                .iter()
+                .map(|case| {
+                    (
+                        case.name.clone(),
+                        opt_type_signature(current_package, &case.ty),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        ),
+        (TypeDefKind::Enum(vendored), TypeDefKind::Enum(current)) => diff_members(
+            "case",
+            &vendored
+                .cases
+                .iter()
+                .map(|case| (case.name.clone(), String::new()))
+                .collect::<Vec<_>>(),
+            &current
+                .cases
+                .iter()
+                .map(|case| (case.name.clone(), String::new()))
+                .collect::<Vec<_>>(),
+        ),
+        (TypeDefKind::Flags(vendored), TypeDefKind::Flags(current)) => diff_members(
+            "flag",
+            &vendored
+                .flags
+                .iter()
+                .map(|flag| (flag.name.clone(), String::new()))
+                .collect::<Vec<_>>(),
+            &current
+                .flags
+                .iter()
+                .map(|flag| (flag.name.clone(), String::new()))
+                .collect::<Vec<_>>(),
+        ),
+        _ => {
+            let vendored_signature = type_def_signature(vendored_package, vendored);
+            let current_signature = type_def_signature(current_package, current);
+            if vendored_signature != current_signature {
+                vec![format!(
+                    "changed from {vendored_signature} to {current_signature}"
+                )]
+            } else {
+                vec![]
+            }
+        }
+    }
+}
+
+/// Compares named members with their type signatures, the order of the members is also part of
+/// the binary representation
+fn diff_members(
+    member_kind: &str,
+    vendored: &[(String, String)],
+    current: &[(String, String)],
+) -> Vec<String> {
+    let vendored_by_name = vendored.iter().cloned().collect::<BTreeMap<_, _>>();
+    let current_by_name = current.iter().cloned().collect::<BTreeMap<_, _>>();
+
+    let mut changes = Vec::new();
+    for name in vendored
+        .iter()
+        .chain(current)
+        .map(|(name, _)| name)
+        .unique()
+    {
+        match (vendored_by_name.get(name), current_by_name.get(name)) {
+            (Some(_), None) => changes.push(format!("{member_kind} {name} removed")),
+            (None, Some(_)) => changes.push(format!("{member_kind} {name} added")),
+            (Some(vendored_type), Some(current_type)) if vendored_type != current_type => changes
+                .push(format!(
+                    "{member_kind} {name} type changed from {vendored_type} to {current_type}"
+                )),
+            _ => {}
+        }
+    }
+
+    if changes.is_empty()
+        && vendored
+            .iter()
+            .map(|(name, _)| name)
+            .ne(current.iter().map(|(name, _)| name))
+    {
+        changes.push(format!(
+            "{member_kind}s reordered from ({}) to ({})",
+            vendored.iter().map(|(name, _)| name).join(", "),
+            current.iter().map(|(name, _)| name).join(", ")
+        ));
+    }
+
+    changes
+}
+
+fn function_signature(package: &UnresolvedPackage, function: &Function) -> String {
+    format!(
+        "func({}){}",
+        function
+            .params
+            .iter()
+            .map(|(name, typ)| format!("{}: {}", name, type_signature(package, typ)))
+            .join(", "),
+        function
+            .result
+            .as_ref()
+            .map(|typ| format!(" -> {}", type_signature(package, typ)))
+            .unwrap_or_default()
+    )
+}
+
+fn opt_type_signature(package: &UnresolvedPackage, typ: &Option<Type>) -> String {
+    match typ {
+        Some(typ) => type_signature(package, typ),
+        None => "_".to_string(),
+    }
+}
+
+/// Named types are referred by their name, as their definitions are compared on their own
+fn type_signature(package: &UnresolvedPackage, typ: &Type) -> String {
+    let Type::Id(type_id) = typ else {
+        return format!("{typ:?}").to_lowercase();
+    };
+
+    let type_def = &package.types[*type_id];
+    match &type_def.name {
+        Some(name) => name.clone(),
+        None => type_def_signature(package, &type_def.kind),
+    }
+}
+
+fn type_def_signature(package: &UnresolvedPackage, kind: &TypeDefKind) -> String {
+    match kind {
+        TypeDefKind::Record(_) => "record".to_string(),
+        TypeDefKind::Variant(_) => "variant".to_string(),
+        TypeDefKind::Enum(_) => "enum".to_string(),
+        TypeDefKind::Flags(_) => "flags".to_string(),
+        TypeDefKind::Resource => "resource".to_string(),
+        TypeDefKind::Handle(Handle::Own(type_id)) => {
+            format!("own<{}>", type_signature(package, &Type::Id(*type_id)))
+        }
+        TypeDefKind::Handle(Handle::Borrow(type_id)) => {
+            format!("borrow<{}>", type_signature(package, &Type::Id(*type_id)))
+        }
+        TypeDefKind::Option(typ) => format!("option<{}>", type_signature(package, typ)),
+        TypeDefKind::List(typ) => format!("list<{}>", type_signature(package, typ)),
+        TypeDefKind::Tuple(tuple) => format!(
+            "tuple<{}>",
+            tuple
+                .types
+                .iter()
+                .map(|typ| type_signature(package, typ))
+                .join(", ")
+        ),
+        TypeDefKind::Result(result) => format!(
+            "result<{}, {}>",
+            opt_type_signature(package, &result.ok),
+            opt_type_signature(package, &result.err)
+        ),
+        TypeDefKind::Future(typ) => format!("future<{}>", opt_type_signature(package, typ)),
+        TypeDefKind::Stream(typ) => format!("stream<{}>", opt_type_signature(package, typ)),
+        TypeDefKind::Type(typ) => type_signature(package, typ),
+        _ => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::compat::diff_packages;
+    use crate::model::compat::CompatDifference;
+    use assert2::assert;
+    use test_r::test;
+    use wit_parser::UnresolvedPackageGroup;
+
+    fn difference(item: &str, change: &str, breaking: bool) -> CompatDifference {
+        CompatDifference {
+            interface_name: "api".to_string(),
+            item: item.to_string(),
+            change: change.to_string(),
+            breaking,
+        }
+    }
+
+    #[test]
+    fn diff_packages_reports_field_level_changes() {
+        let vendored = UnresolvedPackageGroup::parse(
+            "vendored.wit",
+            r#"package test:target;
+
+interface api {
+  record point {
+    x: s32,
+    y: s32,
+  }
+
+  enum color { red, green }
+
+  get-point: func() -> point;
+  set-color: func(color: color);
+}
+"#,
+        )
+        .unwrap()
+        .main;
+        let current = UnresolvedPackageGroup::parse(
+            "current.wit",
+            r#"package test:target;
+
+interface api {
+  record point {
+    x: s64,
+    z: s32,
+  }
+
+  enum color { green, red }
+
+  get-point: func() -> option<point>;
+  set-color: func(color: color);
+  reset: func();
+}
+"#,
+        )
+        .unwrap()
+        .main;
+
+        assert!(
+            diff_packages(&vendored, &current)
+                == vec![
+                    difference("type point", "field x type changed from s32 to s64", true),
+                    difference("type point", "field y removed", true),
+                    difference("type point", "field z added", true),
+                    difference(
+                        "type color",
+                        "cases reordered from (red, green) to (green, red)",
+                        true
+                    ),
+                    difference(
+                        "function get-point",
+                        "signature changed from func() -> point to func() -> option<point>",
+                        true
+                    ),
+                    difference("function reset", "added", false),
+                ]
+        );
+    }
+}
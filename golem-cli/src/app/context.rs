@@ -13,28 +13,39 @@
 // limitations under the License.
 
 use crate::app::build::build_app;
+use crate::app::build::cache::BuildCache;
 use crate::app::build::clean::clean_app;
-use crate::app::build::command::execute_custom_command;
+use crate::app::build::command::{command_execution_order, execute_custom_command};
 use crate::app::error::{format_warns, AppValidationError, CustomCommandError};
-use crate::app::remote_components::RemoteComponents;
+use crate::app::remote_components::{DeployedComponentInfo, RemoteComponents};
+use crate::app::stub_registry::StubInfo;
+use crate::app::{stub_registry, wit_deps};
 use crate::config::ProfileName;
+use crate::fs;
 use crate::fs::{compile_and_collect_globs, PathExtra};
 use crate::log::{log_action, logln, LogColorize, LogIndent, LogOutput, Output};
 use crate::model::app::{
-    includes_from_yaml_file, AppComponentName, Application, ApplicationComponentSelectMode,
-    ApplicationConfig, ApplicationSourceMode, BinaryComponentSource, BuildProfileName,
+    includes_from_yaml_file, AppBuildStep, AppComponentName, Application,
+    ApplicationComponentSelectMode, ApplicationConfig, ApplicationSourceMode,
+    BinaryComponentSource, BuildProfileName, ClientLanguage, CommandDependency,
     ComponentStubInterfaces, DependentComponent, DynamicHelpSections, DEFAULT_CONFIG_FILE_NAME,
 };
 use crate::model::app_raw;
 use crate::validation::{ValidatedResult, ValidationBuilder};
+use crate::wasm_rpc_stubgen::cargo::{
+    add_client_crate_to_cargo_workspace, find_cargo_workspace_toml,
+};
+use crate::wasm_rpc_stubgen::commands::generate::generate_client_for_language;
 use crate::wasm_rpc_stubgen::naming;
 use crate::wasm_rpc_stubgen::stub::{StubConfig, StubDefinition};
 use crate::wasm_rpc_stubgen::wit_resolve::{ResolvedWitApplication, WitDepsResolver};
 use anyhow::{anyhow, bail, Context};
 use colored::control::SHOULD_COLORIZE;
 use colored::Colorize;
+use fs_extra::dir::CopyOptions;
+use heck::ToSnakeCase;
 use itertools::Itertools;
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
@@ -44,6 +55,7 @@ pub struct ApplicationContext {
     pub application: Application,
     pub wit: ResolvedWitApplication,
     pub calling_working_dir: PathBuf,
+    pub build_cache: Option<BuildCache>,
     component_stub_defs: HashMap<AppComponentName, StubDefinition>,
     common_wit_deps: OnceLock<anyhow::Result<WitDepsResolver>>,
     component_generated_base_wit_deps: HashMap<AppComponentName, WitDepsResolver>,
@@ -86,7 +98,9 @@ impl ApplicationContext {
         config: ApplicationConfig,
         file_download_client: reqwest::Client,
     ) -> anyhow::Result<Option<ApplicationContext>> {
-        let Some(app_and_calling_working_dir) = load_app(available_profiles, source_mode) else {
+        let Some(app_and_calling_working_dir) =
+            load_app(available_profiles, source_mode, &config.template_vars)
+        else {
             return Ok(None);
         };
 
@@ -96,12 +110,14 @@ impl ApplicationContext {
                 ResolvedWitApplication::new(&application, config.build_profile.as_ref()).map({
                     let temp_dir = application.temp_dir();
                     let offline = config.offline;
+                    let build_cache = BuildCache::from_config(&config, application.build_cache());
                     move |wit| ApplicationContext {
                         loaded_with_warnings: false,
                         config,
                         application,
                         wit,
                         calling_working_dir,
+                        build_cache,
                         component_stub_defs: HashMap::new(),
                         common_wit_deps: OnceLock::new(),
                         component_generated_base_wit_deps: HashMap::new(),
@@ -126,6 +142,10 @@ impl ApplicationContext {
             log_action("Using", "offline mode");
         }
 
+        if let Some(build_cache) = &ctx.build_cache {
+            log_action("Using", format!("build cache {}", build_cache.describe()));
+        }
+
         Ok(Some(ctx))
     }
 
@@ -177,17 +197,20 @@ impl ApplicationContext {
             self.component_stub_defs.insert(
                 component_name.clone(),
                 StubDefinition::new(StubConfig {
-                    source_wit_root: self
-                        .application
-                        .component_generated_base_wit(component_name),
+                    source_wit_root: self.client_source_wit_root(component_name),
                     client_root: self.application.client_temp_build_dir(component_name),
                     selected_world: None,
                     stub_crate_version: golem_common::golem_version().to_string(),
-                    golem_rust_override: self.config.golem_rust_override.clone(),
+                    golem_rust_override: self.application.client_golem_rust_override(
+                        component_name,
+                        self.config.build_profile.as_ref(),
+                        &self.config.golem_rust_override,
+                    ),
                     extract_source_exports_package: false,
                     seal_cargo_workspace: true,
                     component_name: component_name.clone(),
                     is_ephemeral,
+                    client_naming: self.application.client_naming(),
                 })
                 .context("Failed to gather information for the stub generator")?,
             );
@@ -195,14 +218,66 @@ impl ApplicationContext {
         Ok(self.component_stub_defs.get(component_name).unwrap())
     }
 
+    /// Deployed dependency targets use the WIT reconstructed from the deployed component,
+    /// registry dependency targets use the WIT of the fetched stub
+    fn client_source_wit_root(&self, component_name: &AppComponentName) -> PathBuf {
+        if self.application.contains_component(component_name) {
+            self.application
+                .component_generated_base_wit(component_name)
+        } else if self
+            .application
+            .registry_dependency_targets()
+            .contains_key(component_name)
+        {
+            stub_registry::stub_wit_dir(&self.application.registry_stub_dir(component_name))
+        } else {
+            self.application.deployed_component_wit(component_name)
+        }
+    }
+
+    pub fn client_target_is_ephemeral(
+        &self,
+        component_name: &AppComponentName,
+    ) -> anyhow::Result<bool> {
+        if self.application.contains_component(component_name) {
+            return Ok(self
+                .application
+                .component_properties(component_name, self.build_profile())
+                .is_ephemeral());
+        }
+
+        if self
+            .application
+            .registry_dependency_targets()
+            .contains_key(component_name)
+        {
+            return match StubInfo::load(&stub_registry::stub_info_file(
+                &self.application.registry_stub_dir(component_name),
+            ))? {
+                Some(info) => Ok(info.ephemeral),
+                None => bail!(
+                    "The registry stub of component {} is not fetched yet",
+                    component_name.as_str().log_color_error_highlight()
+                ),
+            };
+        }
+
+        match DeployedComponentInfo::load(
+            &self.application.deployed_component_info(component_name),
+        )? {
+            Some(info) => Ok(info.ephemeral),
+            None => bail!(
+                "The WIT of the deployed component {} is not fetched yet",
+                component_name.as_str().log_color_error_highlight()
+            ),
+        }
+    }
+
     pub fn component_stub_interfaces(
         &mut self,
         component_name: &AppComponentName,
     ) -> anyhow::Result<ComponentStubInterfaces> {
-        let is_ephemeral = self
-            .application
-            .component_properties(component_name, self.build_profile())
-            .is_ephemeral();
+        let is_ephemeral = self.client_target_is_ephemeral(component_name)?;
         let stub_def = self.component_stub_def(component_name, is_ephemeral)?;
         let client_package_name = stub_def.client_parser_package_name();
         let result = ComponentStubInterfaces {
@@ -407,14 +482,155 @@ impl ApplicationContext {
         build_app(self).await
     }
 
-    pub fn custom_command(&self, command_name: &str) -> Result<(), CustomCommandError> {
-        execute_custom_command(self, command_name)
+    /// Executes the custom command, preceded by the custom commands and build steps it depends on
+    pub async fn custom_command(&mut self, command_name: &str) -> Result<(), CustomCommandError> {
+        if !self
+            .application
+            .all_custom_commands(self.build_profile())
+            .contains(command_name)
+        {
+            return Err(CustomCommandError::CommandNotFound);
+        }
+
+        let order = command_execution_order(
+            &self.application,
+            self.build_profile(),
+            vec![CommandDependency::CustomCommand(command_name.to_string())],
+        )
+        .map_err(|error| CustomCommandError::CommandError { error })?;
+
+        for dependency in order {
+            match dependency {
+                CommandDependency::Build => self
+                    .build_steps(HashSet::new())
+                    .await
+                    .map_err(|error| CustomCommandError::CommandError { error })?,
+                CommandDependency::BuildStep(step) => self
+                    .build_steps(HashSet::from([step]))
+                    .await
+                    .map_err(|error| CustomCommandError::CommandError { error })?,
+                CommandDependency::CustomCommand(command_name) => {
+                    execute_custom_command(self, &command_name)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn build_steps(&mut self, steps_filter: HashSet<AppBuildStep>) -> anyhow::Result<()> {
+        let original_steps_filter = std::mem::replace(&mut self.config.steps_filter, steps_filter);
+        let result = build_app(self).await;
+        self.config.steps_filter = original_steps_filter;
+        result
     }
 
     pub fn clean(&self) -> anyhow::Result<()> {
         clean_app(self)
     }
 
+    /// Generates client bindings for the selected components into per-component subdirectories
+    /// of the output directory, expects the generated base WITs to be up-to-date
+    pub fn fetch_wit_deps(&mut self, update: bool) -> anyhow::Result<()> {
+        wit_deps::fetch_wit_deps(&self.application, self.config.offline, update)?;
+        // Fetching may change the packages available in the common WIT deps
+        self.common_wit_deps = OnceLock::new();
+        Ok(())
+    }
+
+    /// Fetches the stubs of the registry dependency targets, see [stub_registry::fetch_stubs]
+    pub fn fetch_registry_stubs(&self, update: bool) -> anyhow::Result<()> {
+        stub_registry::fetch_stubs(&self.application, self.config.offline, update)
+    }
+
+    /// Generates client bindings into the output directory, optionally adding the generated Rust
+    /// crates to the Cargo workspace containing the output directory
+    pub fn generate_client_bindings(
+        &mut self,
+        language: ClientLanguage,
+        output: &Path,
+        cargo_workspace: bool,
+    ) -> anyhow::Result<()> {
+        let workspace_cargo_toml = if cargo_workspace {
+            fs::create_dir_all(output)?;
+            let output = output.canonicalize().with_context(|| {
+                anyhow!(
+                    "Failed to canonicalize output directory {}",
+                    output.log_color_highlight()
+                )
+            })?;
+            match find_cargo_workspace_toml(&output)? {
+                Some(workspace_cargo_toml) => Some(workspace_cargo_toml),
+                None => bail!(
+                    "No Cargo workspace found for output directory {}",
+                    output.log_color_error_highlight()
+                ),
+            }
+        } else {
+            None
+        };
+
+        for component_name in self.selected_component_names.clone() {
+            let is_ephemeral = self
+                .application
+                .component_properties(&component_name, self.build_profile())
+                .is_ephemeral();
+            let client_root = self.application.client_temp_build_dir(&component_name);
+            let target_dir = output.join(component_name.as_str().to_snake_case());
+
+            log_action(
+                "Generating",
+                format!(
+                    "{} client bindings for {} to {}",
+                    language,
+                    component_name.as_str().log_color_highlight(),
+                    target_dir.log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
+
+            if client_root.exists() {
+                fs::remove(&client_root)?;
+            }
+            fs::create_dir_all(&client_root)?;
+
+            let stub_def = self.component_stub_def(&component_name, is_ephemeral)?;
+            generate_client_for_language(stub_def, language)?;
+
+            fs::create_dir_all(&target_dir)?;
+            fs_extra::dir::copy(
+                &client_root,
+                &target_dir,
+                &CopyOptions::new().content_only(true).overwrite(true),
+            )
+            .with_context(|| {
+                anyhow!(
+                    "Failed to copy the generated client bindings to {}",
+                    target_dir.log_color_highlight()
+                )
+            })?;
+
+            fs::remove(&client_root)?;
+
+            if let Some(workspace_cargo_toml) = &workspace_cargo_toml {
+                add_client_crate_to_cargo_workspace(
+                    workspace_cargo_toml,
+                    &target_dir
+                        .canonicalize()
+                        .with_context(|| {
+                            anyhow!(
+                                "Failed to canonicalize client directory {}",
+                                target_dir.log_color_highlight()
+                            )
+                        })?
+                        .join(naming::rust::CARGO_TOML),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     pub async fn resolve_binary_component_source(
         &self,
         dep: &DependentComponent,
@@ -429,6 +645,17 @@ impl ApplicationContext {
             }
             BinaryComponentSource::LocalFile { path } => Ok(path.clone()),
             BinaryComponentSource::Url { url } => self.remote_components.get_from_url(url).await,
+            BinaryComponentSource::Deployed { name, .. }
+            | BinaryComponentSource::Registry { name, .. } => {
+                if dep.dep_type.is_wasm_rpc() {
+                    Ok(self.application.client_wasm(name))
+                } else {
+                    bail!(
+                        "Deployed or registry component {} can only be used as a WASM RPC dependency",
+                        dep.source.to_string().log_color_error_highlight()
+                    )
+                }
+            }
         }
     }
 
@@ -639,10 +866,11 @@ impl ApplicationContext {
 fn load_app(
     available_profiles: &BTreeSet<ProfileName>,
     source_mode: ApplicationSourceMode,
+    variable_overrides: &BTreeMap<String, String>,
 ) -> Option<ValidatedResult<(Application, PathBuf)>> {
     load_raw_apps(source_mode).map(|raw_apps_and_calling_working_dir| {
         raw_apps_and_calling_working_dir.and_then(|(raw_apps, calling_working_dir)| {
-            Application::from_raw_apps(available_profiles, raw_apps)
+            Application::from_raw_apps(available_profiles, raw_apps, variable_overrides)
                 .map(|app| (app, calling_working_dir))
         })
     })
@@ -715,19 +943,7 @@ fn collect_sources_and_switch_to_app_root(
         let source_dir = source_ext.parent().unwrap();
         std::env::set_current_dir(source_dir).expect("Failed to set current dir for config parent");
 
-        let includes = includes_from_yaml_file(source);
-        if includes.is_empty() {
-            Some(ValidatedResult::Ok(BTreeSet::from([source.to_path_buf()])))
-        } else {
-            Some(
-                ValidatedResult::from_result(compile_and_collect_globs(source_dir, &includes)).map(
-                    |mut sources| {
-                        sources.insert(0, source.to_path_buf());
-                        sources.into_iter().collect()
-                    },
-                ),
-            )
-        }
+        Some(collect_manifest_sources(source))
     }
 
     let sources = match root_manifest {
@@ -767,6 +983,79 @@ fn collect_sources_and_switch_to_app_root(
     })
 }
 
+/// Collects the root manifest and the transitively included manifests, every manifest is
+/// collected only once, even if it is included multiple times, or through symlinks.
+///
+/// Manifests are identified by their canonical path, falling back to the path itself when it
+/// cannot be canonicalized.
+fn collect_manifest_sources(source: &Path) -> ValidatedResult<BTreeSet<PathBuf>> {
+    let source = source
+        .canonicalize()
+        .unwrap_or_else(|_| source.to_path_buf());
+
+    let mut validation = ValidationBuilder::new();
+    let mut sources = BTreeSet::from([source.to_path_buf()]);
+    let mut queue = VecDeque::from([(source.to_path_buf(), true)]);
+
+    while let Some((manifest, is_root)) = queue.pop_front() {
+        let manifest_dir = manifest
+            .parent()
+            .expect("Failed to get manifest parent dir");
+        validation.with_context(
+            vec![("source", manifest.to_string_lossy().to_string())],
+            |validation| {
+                for include in includes_from_yaml_file(&manifest, is_root) {
+                    match compile_and_collect_globs(manifest_dir, &[include.clone()]) {
+                        Ok(included) => {
+                            if included.is_empty() && !is_root {
+                                validation.add_warn(format!(
+                                    "Include {} does not match any application manifest",
+                                    include.log_color_highlight()
+                                ));
+                            }
+                            for included in included {
+                                let included = included.canonicalize().unwrap_or(included);
+                                if sources.insert(included.clone()) {
+                                    queue.push_back((included, false));
+                                }
+                            }
+                        }
+                        Err(err) => validation.add_error(format!(
+                            "Invalid include {}: {}",
+                            include.log_color_error_highlight(),
+                            err
+                        )),
+                    }
+                }
+            },
+        );
+    }
+
+    validation.build(sources)
+}
+
+/// Best effort collection of the application manifests, without loading the application, so
+/// they can be watched for changes even if the application fails to load
+pub fn find_manifest_sources(source_mode: &ApplicationSourceMode) -> BTreeSet<PathBuf> {
+    match source_mode {
+        ApplicationSourceMode::Automatic => find_main_source()
+            .map(|source| collect_manifest_sources_or_root(&source))
+            .unwrap_or_default(),
+        ApplicationSourceMode::ByRootManifest(source) => collect_manifest_sources_or_root(source),
+        ApplicationSourceMode::Preloaded { raw_apps, .. } => {
+            raw_apps.iter().map(|app| app.source.clone()).collect()
+        }
+        ApplicationSourceMode::None => BTreeSet::new(),
+    }
+}
+
+fn collect_manifest_sources_or_root(source: &Path) -> BTreeSet<PathBuf> {
+    collect_manifest_sources(source)
+        .into_product()
+        .0
+        .unwrap_or_else(|| BTreeSet::from([source.to_path_buf()]))
+}
+
 fn find_main_source() -> Option<PathBuf> {
     let mut current_dir = std::env::current_dir().expect("Failed to get current dir");
     let mut last_source: Option<PathBuf> = None;
@@ -811,3 +1100,40 @@ fn to_anyhow<T>(
         })),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::context::collect_manifest_sources;
+    use crate::validation::ValidatedResult;
+    use assert2::{assert, let_assert};
+    use std::collections::BTreeSet;
+    use test_r::test;
+
+    #[cfg(unix)]
+    #[test]
+    fn collect_manifest_sources_through_symlinked_app_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let real_app_dir = temp_dir.path().canonicalize().unwrap().join("real-app");
+        let component_dir = real_app_dir.join("components").join("a");
+        std::fs::create_dir_all(&component_dir).unwrap();
+        // Without includes the root manifest uses the default "**/golem.yaml" include, which
+        // also matches the root manifest itself
+        std::fs::write(real_app_dir.join("golem.yaml"), "tempDir: target\n").unwrap();
+        std::fs::write(component_dir.join("golem.yaml"), "tempDir: target\n").unwrap();
+
+        let linked_app_dir = temp_dir.path().join("app");
+        std::os::unix::fs::symlink(&real_app_dir, &linked_app_dir).unwrap();
+
+        let_assert!(
+            ValidatedResult::Ok(sources) =
+                collect_manifest_sources(&linked_app_dir.join("golem.yaml"))
+        );
+        assert!(
+            sources
+                == BTreeSet::from([
+                    real_app_dir.join("golem.yaml"),
+                    component_dir.join("golem.yaml"),
+                ])
+        );
+    }
+}
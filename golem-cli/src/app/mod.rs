@@ -13,7 +13,13 @@
 // limitations under the License.
 
 pub mod build;
+pub mod compat;
 pub mod context;
 pub mod error;
 pub mod remote_components;
+pub mod shared_types;
+pub mod stub_registry;
+pub mod template_repo;
+pub mod validate;
+pub mod wit_deps;
 pub mod yaml_edit;
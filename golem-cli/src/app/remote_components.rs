@@ -16,9 +16,44 @@ use crate::context::check_http_response_success;
 use crate::log::{log_action, LogColorize};
 use anyhow::{anyhow, Context};
 use base64::prelude::*;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use url::Url;
 
+/// Version and type of a deployed component used as a WASM RPC dependency target, recorded
+/// next to its reconstructed WIT when fetching it from the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployedComponentInfo {
+    pub component_name: String,
+    pub version: u64,
+    pub ephemeral: bool,
+}
+
+impl DeployedComponentInfo {
+    pub fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        serde_yaml::from_str(&crate::fs::read_to_string(path)?)
+            .map(Some)
+            .with_context(|| {
+                anyhow!(
+                    "Failed to parse deployed component info {}",
+                    path.log_color_highlight()
+                )
+            })
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        crate::fs::write_str(
+            path,
+            serde_yaml::to_string(self).context("Failed to serialize deployed component info")?,
+        )
+    }
+}
+
 pub struct RemoteComponents {
     client: reqwest::Client,
     temp_dir: PathBuf,
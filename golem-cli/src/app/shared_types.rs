@@ -0,0 +1,609 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::context::ApplicationContext;
+use crate::fs;
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::app::AppComponentName;
+use crate::model::shared_types::{SharedType, SharedTypeOwner, SharedTypesReport};
+use crate::wasm_rpc_stubgen::naming;
+use anyhow::{anyhow, bail, Context};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use wit_parser::{Type, TypeDefKind, UnresolvedPackage, UnresolvedPackageGroup};
+
+const SHARED_TYPES_PACKAGE_NAME: &str = "shared-types";
+const SHARED_TYPES_INTERFACE_NAME: &str = "types";
+const SHARED_TYPE_KINDS: &[&str] = &["record", "variant", "enum", "flags"];
+
+/// A named type definition found in a component interface, keyed by its name and structure
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct TypeKey {
+    name: String,
+    signature: String,
+}
+
+/// Finds the record, variant, enum and flags definitions which are identical in multiple
+/// components, moves them into a common shared types package, adds the package to the WIT deps of
+/// the affected components, and replaces the definitions with uses of the shared package.
+///
+/// Only self-contained types are considered, which do not refer to other named types, so the
+/// shared package does not depend on any of the components.
+pub fn extract_shared_types(
+    ctx: &ApplicationContext,
+    package_name: Option<&str>,
+    dry_run: bool,
+) -> anyhow::Result<SharedTypesReport> {
+    let mut component_packages = BTreeMap::<AppComponentName, (PathBuf, UnresolvedPackage)>::new();
+    for component_name in ctx.application.component_names() {
+        let source_wit = ctx
+            .application
+            .component_source_wit(component_name, ctx.build_profile());
+        let package = UnresolvedPackageGroup::parse_dir(&source_wit)
+            .with_context(|| {
+                anyhow!(
+                    "Failed to parse WIT package of component {} in {}",
+                    component_name.as_str().log_color_highlight(),
+                    source_wit.log_color_highlight()
+                )
+            })?
+            .main;
+        component_packages.insert(component_name.clone(), (source_wit, package));
+    }
+
+    let package_name = match package_name {
+        Some(package_name) => {
+            if !package_name
+                .split_once(':')
+                .is_some_and(|(namespace, name)| !namespace.is_empty() && !name.is_empty())
+            {
+                bail!(
+                    "Invalid shared types package name: {}, expected 'namespace:name' form",
+                    package_name.log_color_error_highlight()
+                );
+            }
+            package_name.to_string()
+        }
+        None => {
+            let namespaces = component_packages
+                .values()
+                .map(|(_, package)| package.name.namespace.as_str())
+                .collect::<BTreeSet<_>>();
+            if namespaces.len() != 1 {
+                bail!(
+                    "The components use multiple package namespaces ({}), the shared types package name has to be specified",
+                    namespaces
+                        .iter()
+                        .map(|namespace| namespace.log_color_highlight())
+                        .join(", ")
+                );
+            }
+            format!(
+                "{}:{}",
+                namespaces.first().unwrap(),
+                SHARED_TYPES_PACKAGE_NAME
+            )
+        }
+    };
+
+    let mut owners_by_type = BTreeMap::<TypeKey, Vec<(AppComponentName, String)>>::new();
+    for (component_name, (_, package)) in &component_packages {
+        for (_, interface) in package.interfaces.iter() {
+            let Some(interface_name) = &interface.name else {
+                continue;
+            };
+            for (type_name, type_id) in &interface.types {
+                if let Some(signature) =
+                    shared_type_signature(package, &package.types[*type_id].kind)
+                {
+                    owners_by_type
+                        .entry(TypeKey {
+                            name: type_name.clone(),
+                            signature,
+                        })
+                        .or_default()
+                        .push((component_name.clone(), interface_name.clone()));
+                }
+            }
+        }
+    }
+    owners_by_type.retain(|_, owners| {
+        owners
+            .iter()
+            .map(|(component_name, _)| component_name)
+            .unique()
+            .count()
+            > 1
+    });
+
+    // The shared interface can contain only one definition per type name
+    let conflicting_type_names = owners_by_type
+        .keys()
+        .map(|key| key.name.clone())
+        .duplicates()
+        .collect::<Vec<_>>();
+    owners_by_type.retain(|key, _| !conflicting_type_names.contains(&key.name));
+
+    let report = SharedTypesReport {
+        package_name: package_name.clone(),
+        dry_run,
+        types: owners_by_type
+            .iter()
+            .map(|(key, owners)| SharedType {
+                name: key.name.clone(),
+                kind: key
+                    .signature
+                    .split_once(' ')
+                    .map(|(kind, _)| kind.to_string())
+                    .unwrap_or_default(),
+                owners: owners
+                    .iter()
+                    .map(|(component_name, interface_name)| SharedTypeOwner {
+                        component_name: component_name.to_string(),
+                        interface_name: interface_name.clone(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        conflicting_type_names,
+    };
+
+    if dry_run || owners_by_type.is_empty() {
+        return Ok(report);
+    }
+
+    // Moved type names per component and interface
+    let mut moved_types = BTreeMap::<AppComponentName, BTreeMap<String, Vec<String>>>::new();
+    for (key, owners) in &owners_by_type {
+        for (component_name, interface_name) in owners {
+            moved_types
+                .entry(component_name.clone())
+                .or_default()
+                .entry(interface_name.clone())
+                .or_default()
+                .push(key.name.clone());
+        }
+    }
+
+    log_action(
+        "Extracting",
+        format!(
+            "shared types to package {}",
+            package_name.log_color_highlight()
+        ),
+    );
+    let _indent = LogIndent::new();
+
+    let mut type_definitions = BTreeMap::<String, String>::new();
+    let mut component_sources = BTreeMap::<AppComponentName, BTreeMap<PathBuf, String>>::new();
+    for (component_name, interfaces) in &moved_types {
+        let (source_wit, _) = &component_packages[component_name];
+        let sources = component_sources.entry(component_name.clone()).or_default();
+        for path in wit_files(source_wit)? {
+            sources.insert(path.clone(), fs::read_to_string(&path)?);
+        }
+
+        for (interface_name, type_names) in interfaces {
+            let (path, content) = sources
+                .iter_mut()
+                .find(|(_, content)| find_interface_block(content, interface_name).is_some())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Failed to find the source of interface {} of component {}",
+                        interface_name.log_color_error_highlight(),
+                        component_name.as_str().log_color_highlight()
+                    )
+                })?;
+
+            let removed = remove_type_definitions(content, interface_name, type_names)
+                .with_context(|| anyhow!("Failed to update {}", path.log_color_highlight()))?;
+            for (type_name, definition) in removed {
+                type_definitions.entry(type_name).or_insert(definition);
+            }
+
+            add_use(
+                content,
+                interface_name,
+                &format!(
+                    "use {}/{}.{{{}}};",
+                    package_name,
+                    SHARED_TYPES_INTERFACE_NAME,
+                    type_names.iter().sorted().join(", ")
+                ),
+            );
+        }
+    }
+
+    let shared_package = format!(
+        "package {};\n\ninterface {} {{\n{}\n}}\n",
+        package_name,
+        SHARED_TYPES_INTERFACE_NAME,
+        type_definitions.values().join("\n\n")
+    );
+    let (namespace, name) = package_name.split_once(':').unwrap();
+    let shared_package_dir_name =
+        naming::wit::package_dep_dir_name_from_parser(&wit_parser::PackageName {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            version: None,
+        });
+
+    for (component_name, sources) in component_sources {
+        log_action(
+            "Updating",
+            format!(
+                "WIT sources of {}",
+                component_name.as_str().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        for (path, content) in sources {
+            if fs::read_to_string(&path)? != content {
+                log_action("Updating", path.log_color_highlight());
+                fs::write_str(&path, content)?;
+            }
+        }
+
+        let (source_wit, _) = &component_packages[&component_name];
+        let shared_package_path = source_wit
+            .join(naming::wit::DEPS_DIR)
+            .join(&shared_package_dir_name)
+            .join(format!("{SHARED_TYPES_INTERFACE_NAME}.wit"));
+        log_action("Writing", shared_package_path.log_color_highlight());
+        fs::write_str(&shared_package_path, &shared_package)?;
+    }
+
+    Ok(report)
+}
+
+/// Returns the structural signature of the type, if it is a record, variant, enum or flags
+/// definition, which only refers to primitive and anonymous types
+fn shared_type_signature(package: &UnresolvedPackage, kind: &TypeDefKind) -> Option<String> {
+    let signature = match kind {
+        TypeDefKind::Record(record) => format!(
+            "record {{ {} }}",
+            record
+                .fields
+                .iter()
+                .map(|field| Some(format!(
+                    "{}: {}",
+                    field.name,
+                    anonymous_type_signature(package, &field.ty)?
+                )))
+                .collect::<Option<Vec<_>>>()?
+                .join(", ")
+        ),
+        TypeDefKind::Variant(variant) => format!(
+            "variant {{ {} }}",
+            variant
+                .cases
+                .iter()
+                .map(|case| Some(format!(
+                    "{}({})",
+                    case.name,
+                    opt_anonymous_type_signature(package, &case.ty)?
+                )))
+                .collect::<Option<Vec<_>>>()?
+                .join(", ")
+        ),
+        TypeDefKind::Enum(enum_) => format!(
+            "enum {{ {} }}",
+            enum_.cases.iter().map(|case| &case.name).join(", ")
+        ),
+        TypeDefKind::Flags(flags) => format!(
+            "flags {{ {} }}",
+            flags.flags.iter().map(|flag| &flag.name).join(", ")
+        ),
+        _ => return None,
+    };
+    Some(signature)
+}
+
+fn opt_anonymous_type_signature(package: &UnresolvedPackage, typ: &Option<Type>) -> Option<String> {
+    match typ {
+        Some(typ) => anonymous_type_signature(package, typ),
+        None => Some("_".to_string()),
+    }
+}
+
+fn anonymous_type_signature(package: &UnresolvedPackage, typ: &Type) -> Option<String> {
+    let Type::Id(type_id) = typ else {
+        return Some(format!("{typ:?}").to_lowercase());
+    };
+
+    let type_def = &package.types[*type_id];
+    if type_def.name.is_some() {
+        return None;
+    }
+
+    match &type_def.kind {
+        TypeDefKind::Option(typ) => Some(format!(
+            "option<{}>",
+            anonymous_type_signature(package, typ)?
+        )),
+        TypeDefKind::List(typ) => {
+            Some(format!("list<{}>", anonymous_type_signature(package, typ)?))
+        }
+        TypeDefKind::Tuple(tuple) => Some(format!(
+            "tuple<{}>",
+            tuple
+                .types
+                .iter()
+                .map(|typ| anonymous_type_signature(package, typ))
+                .collect::<Option<Vec<_>>>()?
+                .join(", ")
+        )),
+        TypeDefKind::Result(result) => Some(format!(
+            "result<{}, {}>",
+            opt_anonymous_type_signature(package, &result.ok)?,
+            opt_anonymous_type_signature(package, &result.err)?
+        )),
+        _ => None,
+    }
+}
+
+fn wit_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = std::fs::read_dir(dir)
+        .with_context(|| anyhow!("Failed to read directory {}", dir.log_color_highlight()))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "wit"))
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '%'
+}
+
+/// Finds the start of the `<keyword> <name>` item in the range, skipping comments
+fn find_item(content: &str, range: Range<usize>, keyword: &str, name: &str) -> Option<usize> {
+    let mut offset = range.start;
+    while offset < range.end {
+        let rest = &content[offset..range.end];
+        if rest.starts_with("//") {
+            offset += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if rest.starts_with("/*") {
+            offset += rest.find("*/").map(|idx| idx + 2).unwrap_or(rest.len());
+            continue;
+        }
+
+        let at_boundary = !content[..offset]
+            .chars()
+            .next_back()
+            .is_some_and(is_ident_char);
+        if at_boundary {
+            if let Some(after_keyword) = rest.strip_prefix(keyword) {
+                let after_whitespace = after_keyword.trim_start();
+                if after_whitespace.len() < after_keyword.len() {
+                    let after_name = after_whitespace
+                        .strip_prefix('%')
+                        .unwrap_or(after_whitespace)
+                        .strip_prefix(name);
+                    if after_name.is_some_and(|after_name| {
+                        !after_name.chars().next().is_some_and(is_ident_char)
+                    }) {
+                        return Some(offset);
+                    }
+                }
+            }
+        }
+
+        offset += rest.chars().next().map(|c| c.len_utf8()).unwrap_or(1);
+    }
+    None
+}
+
+/// Returns the range of the `{ ... }` block starting at the first '{' after the offset, including
+/// the braces, skipping comments
+fn find_block(content: &str, from: usize) -> Option<Range<usize>> {
+    let start = from + content[from..].find('{')?;
+    let mut depth = 0;
+    let mut offset = start;
+    while offset < content.len() {
+        let rest = &content[offset..];
+        if rest.starts_with("//") {
+            offset += rest.find('\n').unwrap_or(rest.len());
+            continue;
+        }
+        if rest.starts_with("/*") {
+            offset += rest.find("*/").map(|idx| idx + 2).unwrap_or(rest.len());
+            continue;
+        }
+
+        let c = rest.chars().next()?;
+        if c == '{' {
+            depth += 1;
+        } else if c == '}' {
+            depth -= 1;
+            if depth == 0 {
+                return Some(start..offset + 1);
+            }
+        }
+        offset += c.len_utf8();
+    }
+    None
+}
+
+fn find_interface_block(content: &str, interface_name: &str) -> Option<Range<usize>> {
+    let start = find_item(content, 0..content.len(), "interface", interface_name)?;
+    find_block(content, start)
+}
+
+/// Removes the type definitions from the interface, together with their doc comments, and returns
+/// the removed definitions dedented
+fn remove_type_definitions(
+    content: &mut String,
+    interface_name: &str,
+    type_names: &[String],
+) -> anyhow::Result<Vec<(String, String)>> {
+    let mut removed = Vec::new();
+    for type_name in type_names {
+        let interface_block = find_interface_block(content, interface_name)
+            .ok_or_else(|| anyhow!("Interface {} not found", interface_name))?;
+
+        let start = SHARED_TYPE_KINDS
+            .iter()
+            .find_map(|kind| find_item(content, interface_block.clone(), kind, type_name))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Type {} not found in interface {}",
+                    type_name,
+                    interface_name
+                )
+            })?;
+        let block = find_block(content, start)
+            .ok_or_else(|| anyhow!("Unterminated definition of type {}", type_name))?;
+
+        // Extend the range to whole lines, including the preceding doc comments
+        let mut start = content[..start].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+        while start > 0 {
+            let prev_line_start = content[..start - 1]
+                .rfind('\n')
+                .map(|idx| idx + 1)
+                .unwrap_or(0);
+            if content[prev_line_start..start]
+                .trim_start()
+                .starts_with("///")
+            {
+                start = prev_line_start;
+            } else {
+                break;
+            }
+        }
+        let end = match content[block.end..].find('\n') {
+            Some(idx) if content[block.end..block.end + idx].trim().is_empty() => {
+                block.end + idx + 1
+            }
+            _ => block.end,
+        };
+
+        removed.push((type_name.clone(), dedent(&content[start..end])));
+        content.replace_range(start..end, "");
+    }
+    Ok(removed)
+}
+
+/// Reindents the lines with two spaces, keeping the relative indentation
+fn dedent(definition: &str) -> String {
+    let lines = definition.trim_end().lines().collect::<Vec<_>>();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                String::new()
+            } else {
+                format!("  {}", &line[indent..])
+            }
+        })
+        .join("\n")
+}
+
+/// Adds the use statement as the first item of the interface, with the indentation of the
+/// following item
+fn add_use(content: &mut String, interface_name: &str, use_statement: &str) {
+    let Some(interface_block) = find_interface_block(content, interface_name) else {
+        return;
+    };
+    let body = &content[interface_block.start + 1..interface_block.end - 1];
+    let indent = body
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .unwrap_or("  ")
+        .to_string();
+    content.insert_str(
+        interface_block.start + 1,
+        &format!("\n{indent}{use_statement}"),
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::shared_types::{add_use, remove_type_definitions};
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn remove_type_definitions_and_add_use() {
+        let mut content = r#"package test:a;
+
+interface api {
+  /// A point
+  record point {
+    x: s32,
+    y: s32,
+  }
+
+  // record color is not a definition
+  enum color { red, green }
+
+  get-point: func() -> point;
+}
+"#
+        .to_string();
+
+        let removed = remove_type_definitions(
+            &mut content,
+            "api",
+            &["point".to_string(), "color".to_string()],
+        )
+        .unwrap();
+        add_use(
+            &mut content,
+            "api",
+            "use test:shared-types/types.{color, point};",
+        );
+
+        assert!(
+            removed
+                == vec![
+                    (
+                        "point".to_string(),
+                        "  /// A point\n  record point {\n    x: s32,\n    y: s32,\n  }"
+                            .to_string()
+                    ),
+                    (
+                        "color".to_string(),
+                        "  enum color { red, green }".to_string()
+                    ),
+                ]
+        );
+        assert!(
+            content
+                == r#"package test:a;
+
+interface api {
+  use test:shared-types/types.{color, point};
+
+  // record color is not a definition
+
+  get-point: func() -> point;
+}
+"#
+        );
+    }
+}
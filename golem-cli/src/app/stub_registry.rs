@@ -0,0 +1,522 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::fs;
+use crate::log::{log_action, log_skipping_up_to_date, log_warn_action, LogColorize, LogIndent};
+use crate::model::app::{AppComponentName, Application};
+use anyhow::{anyhow, bail, Context};
+use fs_extra::dir::CopyOptions;
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+const ORAS: &str = "oras";
+const OCI_SCHEME: &str = "oci://";
+
+const STUB_ARTIFACT_TYPE: &str = "application/vnd.golem.wasm-rpc-stub.v1";
+const STUB_WIT_MEDIA_TYPE: &str = "application/vnd.golem.wasm-rpc-stub.wit.v1.tar+gzip";
+const STUB_INFO_MEDIA_TYPE: &str = "application/vnd.golem.wasm-rpc-stub.info.v1+yaml";
+
+const STUB_WIT_DIR: &str = "wit";
+const STUB_INFO_FILE: &str = "golem-stub.yaml";
+
+/// Published together with the WIT of a stub, the content hash is checked when fetching the stub
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StubInfo {
+    pub component_name: String,
+    pub version: String,
+    pub ephemeral: bool,
+    pub content_hash: String,
+}
+
+impl StubInfo {
+    pub fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        serde_yaml::from_str(&fs::read_to_string(path)?)
+            .map(Some)
+            .with_context(|| anyhow!("Failed to parse stub info {}", path.log_color_highlight()))
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        fs::write_str(
+            path,
+            serde_yaml::to_string(self).context("Failed to serialize stub info")?,
+        )
+    }
+}
+
+pub fn stub_wit_dir(stub_dir: &Path) -> PathBuf {
+    stub_dir.join(STUB_WIT_DIR)
+}
+
+pub fn stub_info_file(stub_dir: &Path) -> PathBuf {
+    stub_dir.join(STUB_INFO_FILE)
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct StubsLock {
+    #[serde(default)]
+    stubs: BTreeMap<String, LockedStub>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockedStub {
+    registry: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version_req: Option<String>,
+    version: String,
+    digest: String,
+    content_hash: String,
+}
+
+impl LockedStub {
+    fn is_locking(&self, registry: &str, version_req: Option<&str>) -> bool {
+        self.registry == registry && self.version_req.as_deref() == version_req
+    }
+}
+
+/// Packages the WIT of a component, which is used as the source of its generated client, and
+/// publishes it to an OCI registry using oras, tagged with the version.
+///
+/// Published versions are immutable, so publishing an existing version fails. Returns the digest
+/// of the published stub.
+pub fn publish_stub(
+    component_name: &AppComponentName,
+    wit_dir: &Path,
+    ephemeral: bool,
+    registry: &str,
+    version: &Version,
+) -> anyhow::Result<String> {
+    let repository = oci_repository(registry)?;
+
+    // Listing fails for repositories which do not exist before the first publish
+    if let Ok(tags) = list_tags(repository) {
+        if tags
+            .iter()
+            .any(|tag| parse_tag(tag).as_ref() == Some(version))
+        {
+            bail!(
+                "Stub version {} is already published to {}, published versions cannot be changed",
+                version.to_string().log_color_error_highlight(),
+                registry.log_color_highlight()
+            );
+        }
+    }
+
+    let staging_dir = tempfile::Builder::new()
+        .prefix("golem-cli-stub")
+        .tempdir()
+        .with_context(|| "Error creating temporary dir for stub")?;
+
+    let staged_wit_dir = stub_wit_dir(staging_dir.path());
+    fs::create_dir_all(&staged_wit_dir)?;
+    fs_extra::dir::copy(
+        wit_dir,
+        &staged_wit_dir,
+        &CopyOptions::new().content_only(true),
+    )
+    .with_context(|| {
+        anyhow!(
+            "Failed to copy stub WIT from {}",
+            wit_dir.log_color_highlight()
+        )
+    })?;
+
+    StubInfo {
+        component_name: component_name.to_string(),
+        version: version.to_string(),
+        ephemeral,
+        content_hash: content_hash(&staged_wit_dir)?,
+    }
+    .save(&stub_info_file(staging_dir.path()))?;
+
+    let reference = format!("{repository}:{version}");
+    log_action(
+        "Publishing",
+        format!(
+            "stub of {} to {}",
+            component_name.as_str().log_color_highlight(),
+            reference.log_color_highlight()
+        ),
+    );
+
+    oras(
+        Some(staging_dir.path()),
+        &[
+            "push",
+            &reference,
+            "--artifact-type",
+            STUB_ARTIFACT_TYPE,
+            &format!("{STUB_WIT_DIR}:{STUB_WIT_MEDIA_TYPE}"),
+            &format!("{STUB_INFO_FILE}:{STUB_INFO_MEDIA_TYPE}"),
+        ],
+    )?;
+
+    oras(None, &["resolve", &reference])
+}
+
+/// Fetches the stubs of the registry dependency targets, and pins them in the stub lockfile.
+///
+/// Without update, stubs which are already locked and fetched are skipped if their content
+/// matches the locked hash, and missing ones are fetched using the locked digest if available.
+/// With update, the versions are resolved again using the semver requirements. Fetched stubs are
+/// checked against the published content hash, and against the locked one if available.
+pub fn fetch_stubs(application: &Application, offline: bool, update: bool) -> anyhow::Result<()> {
+    let targets = application.registry_dependency_targets();
+    let lock_file = application.registry_stubs_lock_file();
+    if targets.is_empty() && !lock_file.exists() {
+        return Ok(());
+    }
+
+    let mut lock = load_lock(&lock_file)?;
+    let mut lock_changed = false;
+
+    for (component_name, (registry, version_req)) in &targets {
+        let stub_dir = application.registry_stub_dir(component_name);
+        let locked = lock
+            .stubs
+            .get(component_name.as_str())
+            .filter(|locked| locked.is_locking(registry, *version_req))
+            .cloned();
+
+        if let Some(locked) = locked.as_ref().filter(|_| !update && stub_dir.exists()) {
+            if is_intact(&stub_dir, locked)? {
+                log_skipping_up_to_date(format!(
+                    "fetching stub {}@{}",
+                    component_name.as_str().log_color_highlight(),
+                    locked.version.log_color_highlight()
+                ));
+                continue;
+            }
+            log_warn_action(
+                "Detected",
+                format!(
+                    "changes in the fetched stub of {}, fetching it again",
+                    component_name.as_str().log_color_highlight()
+                ),
+            );
+        }
+
+        if offline {
+            bail!(
+                "Offline mode is enabled, but the stub of {} is not fetched yet",
+                component_name.as_str().log_color_error_highlight()
+            );
+        }
+
+        let repository = oci_repository(registry)?;
+        let (version, digest) = match locked.as_ref().filter(|_| !update) {
+            Some(locked) => (locked.version.clone(), locked.digest.clone()),
+            None => {
+                let version_req = version_req
+                    .map(VersionReq::parse)
+                    .transpose()
+                    .with_context(|| anyhow!("Invalid semver requirement for {}", registry))?;
+                let tags = list_tags(repository)?;
+                let Some(version) = resolve_version(&tags, version_req.as_ref()) else {
+                    bail!(
+                        "No published stub version of {} matches {}, available versions: {}",
+                        registry.log_color_highlight(),
+                        version_req
+                            .as_ref()
+                            .map(|req| req.to_string())
+                            .unwrap_or_else(|| "*".to_string())
+                            .log_color_error_highlight(),
+                        tags.join(", ")
+                    );
+                };
+                let digest = oras(None, &["resolve", &format!("{repository}:{version}")])?;
+                (version.to_string(), digest)
+            }
+        };
+
+        log_action(
+            "Fetching",
+            format!(
+                "stub of {} from {}@{}",
+                component_name.as_str().log_color_highlight(),
+                registry.log_color_highlight(),
+                version.log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let info = pull_stub(repository, &digest, &stub_dir)?;
+        if info.component_name != component_name.as_str() {
+            bail!(
+                "The stub published to {} belongs to component {}, expected {}",
+                registry.log_color_highlight(),
+                info.component_name.log_color_error_highlight(),
+                component_name.as_str().log_color_highlight()
+            );
+        }
+        if let Some(locked) = locked.as_ref().filter(|_| !update) {
+            if locked.content_hash != info.content_hash {
+                bail!(
+                    "Integrity check failed for the stub of {}: content hash {} does not match the locked {}",
+                    component_name.as_str().log_color_highlight(),
+                    info.content_hash.log_color_error_highlight(),
+                    locked.content_hash.log_color_highlight()
+                );
+            }
+        }
+
+        let new_locked = LockedStub {
+            registry: registry.to_string(),
+            version_req: version_req.map(|req| req.to_string()),
+            version,
+            digest,
+            content_hash: info.content_hash,
+        };
+        if locked.as_ref() != Some(&new_locked) {
+            lock.stubs.insert(component_name.to_string(), new_locked);
+            lock_changed = true;
+        }
+    }
+
+    let stale_stubs = lock
+        .stubs
+        .keys()
+        .filter(|name| {
+            !targets
+                .keys()
+                .any(|component_name| component_name.as_str() == name.as_str())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    for name in stale_stubs {
+        log_warn_action(
+            "Removing",
+            format!("stub {} from the lockfile", name.log_color_highlight()),
+        );
+        lock.stubs.remove(&name);
+        fs::remove(application.registry_stub_dir(&name.as_str().into()))?;
+        lock_changed = true;
+    }
+
+    if lock_changed {
+        log_action(
+            "Updating",
+            format!("stub lockfile {}", lock_file.log_color_highlight()),
+        );
+        fs::write_str(
+            &lock_file,
+            toml::to_string(&lock).context("Failed to serialize stub lockfile")?,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn load_lock(lock_file: &Path) -> anyhow::Result<StubsLock> {
+    if !lock_file.exists() {
+        return Ok(StubsLock::default());
+    }
+
+    toml::from_str(&fs::read_to_string(lock_file)?).with_context(|| {
+        anyhow!(
+            "Failed to parse stub lockfile {}",
+            lock_file.log_color_highlight()
+        )
+    })
+}
+
+fn is_intact(stub_dir: &Path, locked: &LockedStub) -> anyhow::Result<bool> {
+    let Some(info) = StubInfo::load(&stub_info_file(stub_dir))? else {
+        return Ok(false);
+    };
+    let wit_dir = stub_wit_dir(stub_dir);
+    Ok(info.content_hash == locked.content_hash
+        && wit_dir.is_dir()
+        && content_hash(&wit_dir)? == locked.content_hash)
+}
+
+/// Pulls the stub by digest into a temporary directory, checks its content against the
+/// published content hash, then replaces the stub directory with it
+fn pull_stub(repository: &str, digest: &str, stub_dir: &Path) -> anyhow::Result<StubInfo> {
+    let pull_dir = tempfile::Builder::new()
+        .prefix("golem-cli-stub")
+        .tempdir()
+        .with_context(|| "Error creating temporary dir for stub")?;
+
+    oras(
+        None,
+        &[
+            "pull",
+            &format!("{repository}@{digest}"),
+            "--output",
+            &pull_dir.path().to_string_lossy(),
+        ],
+    )?;
+
+    let Some(info) = StubInfo::load(&stub_info_file(pull_dir.path()))? else {
+        bail!(
+            "The artifact {}@{} is not a stub, {} is missing",
+            repository.log_color_highlight(),
+            digest.log_color_highlight(),
+            STUB_INFO_FILE.log_color_error_highlight()
+        );
+    };
+
+    let pulled_content_hash = content_hash(&stub_wit_dir(pull_dir.path()))?;
+    if pulled_content_hash != info.content_hash {
+        bail!(
+            "Integrity check failed for {}@{}: content hash {} does not match the published {}",
+            repository.log_color_highlight(),
+            digest.log_color_highlight(),
+            pulled_content_hash.log_color_error_highlight(),
+            info.content_hash.log_color_highlight()
+        );
+    }
+
+    fs::remove(stub_dir)?;
+    fs::create_dir_all(stub_dir)?;
+    fs_extra::dir::copy(
+        pull_dir.path(),
+        stub_dir,
+        &CopyOptions::new().content_only(true),
+    )
+    .with_context(|| anyhow!("Failed to copy stub to {}", stub_dir.log_color_highlight()))?;
+
+    Ok(info)
+}
+
+/// Hash of the relative paths and contents of the files in the WIT directory
+pub fn content_hash(wit_dir: &Path) -> anyhow::Result<String> {
+    if !wit_dir.is_dir() {
+        bail!(
+            "Stub WIT directory {} not found",
+            wit_dir.log_color_error_highlight()
+        );
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    for entry in WalkDir::new(wit_dir).sort_by_file_name() {
+        let entry = entry.with_context(|| {
+            anyhow!("Failed to read directory {}", wit_dir.log_color_highlight())
+        })?;
+        if entry.file_type().is_file() {
+            let relative_path = entry
+                .path()
+                .strip_prefix(wit_dir)
+                .context("Failed to strip stub WIT directory prefix")?
+                .to_string_lossy()
+                .replace('\\', "/");
+            let content = fs::read(entry.path())?;
+            hasher.update(relative_path.as_bytes());
+            hasher.update(&(content.len() as u64).to_le_bytes());
+            hasher.update(&content);
+        }
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+fn oci_repository(registry: &str) -> anyhow::Result<&str> {
+    registry.strip_prefix(OCI_SCHEME).ok_or_else(|| {
+        anyhow!(
+            "Invalid registry reference {}, only OCI references starting with {} are supported",
+            registry.log_color_error_highlight(),
+            OCI_SCHEME.log_color_highlight()
+        )
+    })
+}
+
+fn list_tags(repository: &str) -> anyhow::Result<Vec<String>> {
+    Ok(oras(None, &["repo", "tags", repository])?
+        .lines()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect())
+}
+
+fn parse_tag(tag: &str) -> Option<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
+/// Selects the highest version matching the requirement from the tags, tags which are not
+/// semantic versions are ignored, and pre-releases are only selected if explicitly required
+pub fn resolve_version(tags: &[String], version_req: Option<&VersionReq>) -> Option<Version> {
+    tags.iter()
+        .filter_map(|tag| parse_tag(tag))
+        .filter(|version| match version_req {
+            Some(version_req) => version_req.matches(version),
+            None => version.pre.is_empty(),
+        })
+        .max()
+}
+
+fn oras(dir: Option<&Path>, args: &[&str]) -> anyhow::Result<String> {
+    let mut command = Command::new(ORAS);
+    command.args(args);
+    if let Some(dir) = dir {
+        command.current_dir(dir);
+    }
+    let output = command.output().with_context(|| {
+        anyhow!(
+            "Failed to execute {}, make sure it is installed and available in PATH",
+            ORAS.log_color_error_highlight()
+        )
+    })?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(anyhow!(
+            "{} {} failed with exit code: {}\n{}",
+            ORAS,
+            args.join(" "),
+            output
+                .status
+                .code()
+                .map(|code| code.to_string().log_color_error_highlight().to_string())
+                .unwrap_or_else(|| "?".to_string()),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::stub_registry::resolve_version;
+    use assert2::assert;
+    use semver::{Version, VersionReq};
+    use test_r::test;
+
+    #[test]
+    fn resolve_version_selects_highest_matching_tag() {
+        let tags = ["1.0.0", "v1.2.0", "1.3.0-rc.1", "2.0.0", "latest"]
+            .into_iter()
+            .map(|tag| tag.to_string())
+            .collect::<Vec<_>>();
+
+        assert!(resolve_version(&tags, None) == Some(Version::new(2, 0, 0)));
+        assert!(
+            resolve_version(&tags, Some(&VersionReq::parse("^1").unwrap()))
+                == Some(Version::new(1, 2, 0))
+        );
+        assert!(
+            resolve_version(&tags, Some(&VersionReq::parse(">=1.3.0-rc.1, <2").unwrap()))
+                == Some(Version::parse("1.3.0-rc.1").unwrap())
+        );
+        assert!(resolve_version(&tags, Some(&VersionReq::parse("^3").unwrap())).is_none());
+    }
+}
@@ -0,0 +1,421 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::wit_deps::checkout;
+use crate::fs;
+use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::template::{template_env, Template};
+use anyhow::{anyhow, bail, Context};
+use golem_templates::model::PackageName;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use walkdir::WalkDir;
+
+/// Optional metadata file in the root of a template, it is not copied into the application
+pub const TEMPLATE_METADATA_FILE_NAME: &str = "metadata.json";
+
+/// Suffix of the template files whose content is rendered, the suffix is removed from the
+/// created files
+pub const TEMPLATE_FILE_SUFFIX: &str = ".j2";
+
+/// Source of a component template repository: a git repository, optionally with a revision
+/// after '#', or a local directory. The github:org/repo form is a shorthand for GitHub.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateRepoSource {
+    Git { url: String, rev: Option<String> },
+    Local { path: PathBuf },
+}
+
+impl FromStr for TemplateRepoSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err("empty template repository".to_string());
+        }
+
+        let is_git = s.starts_with("github:")
+            || s.starts_with("https://")
+            || s.starts_with("http://")
+            || s.starts_with("ssh://")
+            || s.starts_with("git@")
+            || s.split('#').next().is_some_and(|url| url.ends_with(".git"));
+        if !is_git {
+            return Ok(TemplateRepoSource::Local {
+                path: PathBuf::from(s),
+            });
+        }
+
+        let (url, rev) = match s.split_once('#') {
+            Some((url, rev)) if !rev.is_empty() => (url, Some(rev.to_string())),
+            Some((url, _)) => (url, None),
+            None => (s, None),
+        };
+        let url = match url.strip_prefix("github:") {
+            Some(repo) => format!("https://github.com/{}.git", repo.trim_end_matches(".git")),
+            None => url.to_string(),
+        };
+
+        Ok(TemplateRepoSource::Git { url, rev })
+    }
+}
+
+impl Display for TemplateRepoSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateRepoSource::Git {
+                url,
+                rev: Some(rev),
+            } => write!(f, "{url}#{rev}"),
+            TemplateRepoSource::Git { url, rev: None } => write!(f, "{url}"),
+            TemplateRepoSource::Local { path } => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TemplateMetadata {
+    #[serde(default)]
+    post_generate: Vec<String>,
+    /// Relative paths of further template files whose content is rendered, without having the
+    /// template file suffix
+    #[serde(default)]
+    render: Vec<String>,
+}
+
+/// Repository of component templates, where every non-hidden top level directory is a template.
+///
+/// The files of a template are created relative to the application root. Their paths are
+/// rendered with the application manifest template environment, and so are the contents of the
+/// files with the [TEMPLATE_FILE_SUFFIX] suffix or listed in the template metadata. Other files
+/// are copied as is.
+pub struct TemplateRepo {
+    root: PathBuf,
+}
+
+impl TemplateRepo {
+    /// Opens a local template repository, or fetches a git one into the checkout directory
+    pub fn fetch(
+        source: &TemplateRepoSource,
+        checkout_dir: &Path,
+        offline: bool,
+    ) -> anyhow::Result<Self> {
+        match source {
+            TemplateRepoSource::Local { path } => {
+                if !path.is_dir() {
+                    bail!(
+                        "Template repository directory {} not found",
+                        path.log_color_error_highlight()
+                    );
+                }
+                Ok(Self {
+                    root: path.to_path_buf(),
+                })
+            }
+            TemplateRepoSource::Git { url, rev } => {
+                let repo_checkout_dir = checkout_dir.join(checkout_dir_name(url));
+                if offline {
+                    if !repo_checkout_dir.join(".git").exists() {
+                        bail!(
+                            "Offline mode is enabled, but the template repository {} is not fetched yet",
+                            url.log_color_error_highlight()
+                        );
+                    }
+                    log_action(
+                        "Using",
+                        format!(
+                            "previously fetched template repository {}",
+                            url.log_color_highlight()
+                        ),
+                    );
+                } else {
+                    log_action(
+                        "Fetching",
+                        format!(
+                            "template repository {}",
+                            source.to_string().log_color_highlight()
+                        ),
+                    );
+                    let _indent = LogIndent::new();
+                    checkout(&repo_checkout_dir, url, rev.as_deref().unwrap_or("HEAD"))?;
+                }
+                Ok(Self {
+                    root: repo_checkout_dir,
+                })
+            }
+        }
+    }
+
+    pub fn template_names(&self) -> anyhow::Result<Vec<String>> {
+        let entries = std::fs::read_dir(&self.root).with_context(|| {
+            anyhow!(
+                "Failed to read template repository {}",
+                self.root.log_color_highlight()
+            )
+        })?;
+
+        let mut template_names = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type()?.is_dir() && !name.starts_with('.') {
+                template_names.push(name);
+            }
+        }
+        template_names.sort();
+        Ok(template_names)
+    }
+
+    /// Renders the template into the target directory, fails without changes if any of the
+    /// rendered files already exists. Returns the post-generate commands of the template.
+    pub fn instantiate(
+        &self,
+        template_name: &str,
+        target_dir: &Path,
+        package_name: &PackageName,
+    ) -> anyhow::Result<Vec<String>> {
+        let template_names = self.template_names()?;
+        if !template_names.iter().any(|name| name == template_name) {
+            bail!(
+                "Template {} not found in the template repository, available templates: {}",
+                template_name.log_color_error_highlight(),
+                template_names
+                    .iter()
+                    .map(|name| name.log_color_highlight().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+
+        let template_dir = self.root.join(template_name);
+        let env = template_env();
+        let ctx = new_component_template_context(package_name);
+
+        let metadata_path = template_dir.join(TEMPLATE_METADATA_FILE_NAME);
+        let metadata = if metadata_path.is_file() {
+            serde_json::from_str::<TemplateMetadata>(&fs::read_to_string(&metadata_path)?)
+                .with_context(|| {
+                    anyhow!(
+                        "Failed to parse template metadata {}",
+                        metadata_path.log_color_highlight()
+                    )
+                })?
+        } else {
+            TemplateMetadata::default()
+        };
+
+        let mut files = Vec::<(PathBuf, Vec<u8>)>::new();
+        for entry in WalkDir::new(&template_dir).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() || entry.path() == metadata_path {
+                continue;
+            }
+
+            let relative_path = entry
+                .path()
+                .strip_prefix(&template_dir)
+                .expect("Failed to strip template dir prefix")
+                .to_string_lossy()
+                .replace('\\', "/");
+            let (relative_path, render_content) =
+                match relative_path.strip_suffix(TEMPLATE_FILE_SUFFIX) {
+                    Some(relative_path) => (relative_path.to_string(), true),
+                    None => {
+                        let render_content = metadata.render.contains(&relative_path);
+                        (relative_path, render_content)
+                    }
+                };
+
+            let target_path = relative_path
+                .render(&env, &ctx)
+                .with_context(|| anyhow!("Failed to render template path {relative_path}"))?;
+            let target_path = checked_template_target_path(&relative_path, &target_path)?;
+
+            let content = fs::read(entry.path())?;
+            let content = if render_content {
+                String::from_utf8(content)
+                    .with_context(|| {
+                        anyhow!("Failed to read template file {relative_path} as UTF-8")
+                    })?
+                    .render(&env, &ctx)
+                    .with_context(|| anyhow!("Failed to render template file {relative_path}"))?
+                    .into_bytes()
+            } else {
+                content
+            };
+
+            files.push((target_dir.join(target_path), content));
+        }
+
+        if let Some((existing, _)) = files.iter().find(|(path, _)| path.exists()) {
+            bail!(
+                "Cannot instantiate template {}, file {} already exists",
+                template_name.log_color_error_highlight(),
+                existing.log_color_error_highlight()
+            );
+        }
+
+        for (path, content) in files {
+            log_action("Creating", format!("{}", path.log_color_highlight()));
+            fs::write(&path, content)?;
+        }
+
+        Ok(metadata.post_generate)
+    }
+}
+
+/// Template context of a newly created component, used for template files and post-generate
+/// commands
+pub fn new_component_template_context(package_name: &PackageName) -> impl Serialize {
+    let component_name = package_name.to_string_with_colon();
+    let name = component_name
+        .split_once(':')
+        .map(|(_, name)| name.to_string())
+        .unwrap_or_default();
+    minijinja::context! {
+        componentName => component_name,
+        component_name => component_name,
+        package_namespace => package_name.namespace(),
+        package_name => name,
+    }
+}
+
+/// Rendered template paths must stay inside the target directory
+fn checked_template_target_path(relative_path: &str, target_path: &str) -> anyhow::Result<PathBuf> {
+    let path = PathBuf::from(target_path);
+    let is_inside_target = path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !is_inside_target {
+        bail!(
+            "Template file {} is rendered to {}, which is not a relative path inside the target directory",
+            relative_path.log_color_error_highlight(),
+            target_path.log_color_error_highlight()
+        );
+    }
+    Ok(path)
+}
+
+fn checkout_dir_name(url: &str) -> String {
+    url.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::template_repo::{TemplateRepo, TemplateRepoSource};
+    use assert2::{assert, let_assert};
+    use golem_templates::model::PackageName;
+    use std::path::{Path, PathBuf};
+    use test_r::test;
+
+    fn template_repo(files: &[(&str, &str)]) -> (tempfile::TempDir, TemplateRepo) {
+        let repo_dir = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            let path = repo_dir.path().join("starter").join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let repo = TemplateRepo {
+            root: repo_dir.path().to_path_buf(),
+        };
+        (repo_dir, repo)
+    }
+
+    fn read(dir: &Path, path: &str) -> String {
+        std::fs::read_to_string(dir.join(path)).unwrap()
+    }
+
+    #[test]
+    fn parse_template_repo_source() {
+        assert!(
+            "github:org/templates#v1".parse::<TemplateRepoSource>()
+                == Ok(TemplateRepoSource::Git {
+                    url: "https://github.com/org/templates.git".to_string(),
+                    rev: Some("v1".to_string()),
+                })
+        );
+        assert!(
+            "git@example.com:org/templates.git".parse::<TemplateRepoSource>()
+                == Ok(TemplateRepoSource::Git {
+                    url: "git@example.com:org/templates.git".to_string(),
+                    rev: None,
+                })
+        );
+        assert!(
+            "../templates".parse::<TemplateRepoSource>()
+                == Ok(TemplateRepoSource::Local {
+                    path: PathBuf::from("../templates"),
+                })
+        );
+    }
+
+    #[test]
+    fn instantiate_renders_opted_in_files_only() {
+        let (_repo_dir, repo) = template_repo(&[
+            (
+                "metadata.json",
+                r#"{"render": ["config.yaml"], "postGenerate": ["npm install"]}"#,
+            ),
+            ("{{package_name}}/main.rs.j2", "// {{ component_name }}"),
+            (
+                "{{package_name}}/README.md",
+                "Use {{ component_name }} in docs",
+            ),
+            ("config.yaml", "name: {{ package_name }}"),
+        ]);
+        let target_dir = tempfile::tempdir().unwrap();
+
+        let post_generate = repo
+            .instantiate(
+                "starter",
+                target_dir.path(),
+                &PackageName::from_string("app:shop").unwrap(),
+            )
+            .unwrap();
+
+        assert!(post_generate == vec!["npm install".to_string()]);
+        assert!(read(target_dir.path(), "shop/main.rs") == "// app:shop");
+        assert!(read(target_dir.path(), "shop/README.md") == "Use {{ component_name }} in docs");
+        assert!(read(target_dir.path(), "config.yaml") == "name: shop");
+        assert!(!target_dir.path().join("shop/main.rs.j2").exists());
+        assert!(!target_dir.path().join("metadata.json").exists());
+    }
+
+    #[test]
+    fn instantiate_rejects_paths_outside_the_target_dir() {
+        // Rendered to "../escaped.txt" and "/escaped.txt"
+        for dir in [r#"{{ ".." }}"#, r#"{{ "" }}"#] {
+            let (_repo_dir, repo) =
+                template_repo(&[(format!("{dir}/escaped.txt").as_str(), "escaped")]);
+            let target_dir = tempfile::tempdir().unwrap();
+
+            let_assert!(
+                Err(error) = repo.instantiate(
+                    "starter",
+                    target_dir.path(),
+                    &PackageName::from_string("app:shop").unwrap(),
+                )
+            );
+            assert!(error
+                .to_string()
+                .contains("not a relative path inside the target directory"));
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::context::ApplicationContext;
+use crate::fs;
+use crate::log::LogColorize;
+use crate::model::app::{BinaryComponentSource, DependencyType};
+use crate::model::app_raw;
+use crate::model::dependency_graph::find_cycles;
+use crate::model::template::{
+    contains_template, template_env, COMPONENT_TEMPLATE_VARIABLES, EXTERNAL_COMMAND_VARIABLES,
+};
+use crate::validation::{ValidatedResult, ValidationBuilder};
+use itertools::Itertools;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+/// Checks of an already loaded application, which are not required for loading it, but catch
+/// manifest mistakes which would otherwise only surface during building or deploying:
+/// undefined template variables, missing referenced paths and cycles in WASM dependencies.
+pub fn validate_app(ctx: &ApplicationContext) -> ValidatedResult<()> {
+    let mut validation = ValidationBuilder::new();
+
+    validate_template_variables(ctx, &mut validation);
+    validate_referenced_paths(ctx, &mut validation);
+    validate_wasm_dependency_cycles(ctx, &mut validation);
+
+    validation.build(())
+}
+
+fn validate_template_variables(ctx: &ApplicationContext, validation: &mut ValidationBuilder) {
+    let env = template_env();
+    let available_variables = COMPONENT_TEMPLATE_VARIABLES
+        .iter()
+        .chain(EXTERNAL_COMMAND_VARIABLES)
+        .copied()
+        .chain(ctx.application.variables().keys().map(|name| name.as_str()))
+        .collect::<BTreeSet<_>>();
+
+    for source in ctx.application.all_sources() {
+        // Sources which cannot be read or parsed are already reported when loading the application
+        let Ok(content) = fs::read_to_string(source) else {
+            continue;
+        };
+        let Ok(raw_app) = app_raw::Application::from_yaml_str(&content) else {
+            continue;
+        };
+
+        for (template_name, template) in raw_app.templates.iter().sorted_by_key(|(name, _)| *name) {
+            let Ok(template) = serde_json::to_value(template) else {
+                continue;
+            };
+
+            let mut template_strings = Vec::new();
+            collect_template_strings(&template, &mut template_strings);
+
+            for template_string in template_strings {
+                let mut context = vec![
+                    ("source", source.log_color_highlight().to_string()),
+                    ("template", template_name.clone()),
+                ];
+                if let Some(line) = line_of(&content, &template_string) {
+                    context.push(("line", line.to_string()));
+                }
+
+                validation.with_context(context, |validation| {
+                    match env.template_from_str(&template_string) {
+                        Ok(compiled) => {
+                            let undefined_variables = compiled
+                                .undeclared_variables(false)
+                                .into_iter()
+                                .filter(|variable| !available_variables.contains(variable.as_str()))
+                                .sorted()
+                                .collect::<Vec<_>>();
+                            if !undefined_variables.is_empty() {
+                                validation.add_error(format!(
+                                    "Undefined template variable(s) {} in {}, available variables: {}",
+                                    undefined_variables
+                                        .iter()
+                                        .map(|variable| variable.log_color_error_highlight())
+                                        .join(", "),
+                                    template_string.log_color_highlight(),
+                                    available_variables
+                                        .iter()
+                                        .map(|variable| variable.log_color_highlight())
+                                        .join(", ")
+                                ));
+                            }
+                        }
+                        Err(err) => validation.add_error(format!(
+                            "Invalid template expression {}: {}",
+                            template_string.log_color_highlight(),
+                            err.to_string().log_color_error_highlight()
+                        )),
+                    }
+                });
+            }
+        }
+    }
+}
+
+fn collect_template_strings(value: &serde_json::Value, template_strings: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(string) => {
+            if contains_template(string) {
+                template_strings.push(string.clone());
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_template_strings(value, template_strings);
+            }
+        }
+        serde_json::Value::Object(values) => {
+            for value in values.values() {
+                collect_template_strings(value, template_strings);
+            }
+        }
+        serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {}
+    }
+}
+
+/// Returns the 1-based line number of the first line containing the first line of the value
+fn line_of(content: &str, value: &str) -> Option<usize> {
+    let first_line = value.lines().next()?;
+    content
+        .lines()
+        .position(|line| line.contains(first_line))
+        .map(|idx| idx + 1)
+}
+
+fn validate_referenced_paths(ctx: &ApplicationContext, validation: &mut ValidationBuilder) {
+    let all_profiles = ctx.application.all_option_build_profiles();
+
+    for component_name in ctx.application.component_names() {
+        let mut paths = BTreeSet::<(&'static str, PathBuf)>::new();
+        for profile in &all_profiles {
+            paths.insert((
+                "Source WIT directory",
+                ctx.application
+                    .component_source_wit(component_name, profile.as_ref()),
+            ));
+
+            let properties = ctx
+                .application
+                .component_properties(component_name, profile.as_ref());
+            paths.extend(properties.files.iter().filter_map(|file| {
+                let url = file.source.as_url();
+                (url.scheme() == "file")
+                    .then(|| url.to_file_path().ok())
+                    .flatten()
+                    .map(|path| ("Initial file source", path))
+            }));
+        }
+
+        validation.with_context(
+            vec![
+                (
+                    "source",
+                    ctx.application
+                        .component_source(component_name)
+                        .log_color_highlight()
+                        .to_string(),
+                ),
+                ("component", component_name.to_string()),
+            ],
+            |validation| {
+                for (kind, path) in paths {
+                    if !path.exists() {
+                        validation.add_error(format!(
+                            "{} not found: {}",
+                            kind,
+                            path.log_color_error_highlight()
+                        ));
+                    }
+                }
+            },
+        );
+    }
+}
+
+/// WASM RPC dependencies can form cycles, but components composed together cannot
+fn validate_wasm_dependency_cycles(ctx: &ApplicationContext, validation: &mut ValidationBuilder) {
+    let mut adjacency = BTreeMap::<String, BTreeSet<String>>::new();
+    for component_name in ctx.application.component_names() {
+        adjacency.insert(
+            component_name.to_string(),
+            ctx.application
+                .component_dependencies(component_name)
+                .iter()
+                .filter(|dep| dep.dep_type == DependencyType::Wasm)
+                .filter_map(|dep| match &dep.source {
+                    BinaryComponentSource::AppComponent { name } => Some(name.to_string()),
+                    BinaryComponentSource::LocalFile { .. }
+                    | BinaryComponentSource::Url { .. }
+                    | BinaryComponentSource::Deployed { .. }
+                    | BinaryComponentSource::Registry { .. } => None,
+                })
+                .collect(),
+        );
+    }
+
+    for cycle in find_cycles(&adjacency) {
+        validation.add_error(format!(
+            "Cycle in WASM component dependencies: {}",
+            cycle
+                .iter()
+                .map(|component_name| component_name.log_color_error_highlight())
+                .join(", ")
+        ));
+    }
+}
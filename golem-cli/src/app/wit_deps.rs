@@ -0,0 +1,431 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::fs;
+use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
+use crate::model::app::Application;
+use crate::model::app_raw::FetchWitDep;
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use walkdir::WalkDir;
+
+const DEFAULT_WIT_PATH: &str = "wit";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct WitDepsLock {
+    #[serde(default)]
+    deps: BTreeMap<String, LockedWitDep>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct LockedWitDep {
+    git: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    commit: String,
+}
+
+impl LockedWitDep {
+    fn is_locking(&self, dep: &FetchWitDep) -> bool {
+        self.git == dep.git && self.path == dep.path
+    }
+}
+
+/// Fetches the WIT dependencies declared in the fetchWitDeps section of the application manifest,
+/// and pins them in the WIT dependency lockfile.
+///
+/// Without update, dependencies which are already locked and fetched are skipped, and missing ones
+/// are fetched using the locked commit if available. With update, every dependency is fetched
+/// again using the requested revision, and the lockfile is updated.
+///
+/// Only git sources are supported, fetching WIT packages from warg registries is not: registry
+/// sources are rejected when validating the application manifest.
+pub fn fetch_wit_deps(
+    application: &Application,
+    offline: bool,
+    update: bool,
+) -> anyhow::Result<()> {
+    let deps = application.fetch_wit_deps();
+    if deps.is_empty() {
+        return Ok(());
+    }
+
+    fetch_wit_deps_into(
+        deps,
+        &application.wit_deps_lock_file(),
+        &application.fetched_wit_deps_dir(),
+        &application.fetched_wit_deps_checkout_dir(),
+        offline,
+        update,
+    )
+}
+
+fn fetch_wit_deps_into(
+    deps: &BTreeMap<String, FetchWitDep>,
+    lock_file: &Path,
+    fetched_dir: &Path,
+    checkout_dir: &Path,
+    offline: bool,
+    update: bool,
+) -> anyhow::Result<()> {
+    let mut lock = load_lock(lock_file)?;
+    let mut lock_changed = false;
+
+    for (name, dep) in deps {
+        let target_dir = fetched_dir.join(name);
+        let locked = lock.deps.get(name).filter(|locked| locked.is_locking(dep));
+
+        if !update && locked.is_some() && target_dir.exists() {
+            log_action(
+                "Skipping",
+                format!(
+                    "fetching WIT dependency {}, using previously fetched version",
+                    name.log_color_highlight()
+                ),
+            );
+            continue;
+        }
+
+        if offline {
+            bail!(
+                "Offline mode is enabled, but the WIT dependency {} is not fetched yet",
+                name.log_color_error_highlight()
+            );
+        }
+
+        let rev = match locked {
+            Some(locked) if !update => locked.commit.clone(),
+            _ => dep.rev.clone().unwrap_or_else(|| "HEAD".to_string()),
+        };
+
+        log_action(
+            "Fetching",
+            format!(
+                "WIT dependency {} from {} at {}",
+                name.log_color_highlight(),
+                dep.git.log_color_highlight(),
+                rev.log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let dep_checkout_dir = checkout_dir.join(name);
+        let commit = checkout(&dep_checkout_dir, &dep.git, &rev)?;
+
+        let source_dir = dep_checkout_dir.join(dep.path.as_deref().unwrap_or(DEFAULT_WIT_PATH));
+        copy_wit_files(&source_dir, &target_dir)?;
+
+        if locked.map(|locked| &locked.commit) != Some(&commit) {
+            lock.deps.insert(
+                name.clone(),
+                LockedWitDep {
+                    git: dep.git.clone(),
+                    path: dep.path.clone(),
+                    commit,
+                },
+            );
+            lock_changed = true;
+        }
+    }
+
+    let stale_deps = lock
+        .deps
+        .keys()
+        .filter(|name| !deps.contains_key(*name))
+        .cloned()
+        .collect::<Vec<_>>();
+    for name in stale_deps {
+        log_warn_action(
+            "Removing",
+            format!(
+                "WIT dependency {} from the lockfile",
+                name.log_color_highlight()
+            ),
+        );
+        lock.deps.remove(&name);
+        fs::remove(fetched_dir.join(&name))?;
+        lock_changed = true;
+    }
+
+    if lock_changed {
+        log_action(
+            "Updating",
+            format!(
+                "WIT dependency lockfile {}",
+                lock_file.log_color_highlight()
+            ),
+        );
+        fs::write_str(
+            lock_file,
+            toml::to_string(&lock).context("Failed to serialize WIT dependency lockfile")?,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn load_lock(lock_file: &Path) -> anyhow::Result<WitDepsLock> {
+    if !lock_file.exists() {
+        return Ok(WitDepsLock::default());
+    }
+
+    toml::from_str(&fs::read_to_string(lock_file)?).with_context(|| {
+        anyhow!(
+            "Failed to parse WIT dependency lockfile {}",
+            lock_file.log_color_highlight()
+        )
+    })
+}
+
+/// Clones or fetches the repository, then checks out the requested revision in detached mode.
+/// Returns the commit hash of the checked out revision.
+pub fn checkout(checkout_dir: &Path, git_url: &str, rev: &str) -> anyhow::Result<String> {
+    if checkout_dir.join(".git").exists() {
+        git(checkout_dir, &["remote", "set-url", "origin", git_url])?;
+        git(checkout_dir, &["fetch", "--quiet", "--tags", "origin"])?;
+    } else {
+        fs::remove(checkout_dir)?;
+        fs::create_dir_all(checkout_dir)?;
+        git(checkout_dir, &["clone", "--quiet", git_url, "."])?;
+    }
+
+    // Prefer the remote version of branches, so updates are not shadowed by stale local branches
+    let remote_rev = if rev == "HEAD" {
+        "origin/HEAD".to_string()
+    } else {
+        format!("origin/{rev}")
+    };
+    let commit = git(
+        checkout_dir,
+        &[
+            "rev-parse",
+            "--verify",
+            "--quiet",
+            &format!("{remote_rev}^{{commit}}"),
+        ],
+    )
+    .or_else(|_| {
+        git(
+            checkout_dir,
+            &["rev-parse", "--verify", &format!("{rev}^{{commit}}")],
+        )
+    })
+    .with_context(|| anyhow!("Unknown revision: {}", rev.log_color_error_highlight()))?;
+
+    git(checkout_dir, &["checkout", "--quiet", "--detach", &commit])?;
+
+    Ok(commit)
+}
+
+fn git(dir: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .with_context(|| "Failed to execute git".to_string())?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(anyhow!(
+            "git {} failed with exit code: {}\n{}",
+            args.join(" "),
+            output
+                .status
+                .code()
+                .map(|code| code.to_string().log_color_error_highlight().to_string())
+                .unwrap_or_else(|| "?".to_string()),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Copies the WIT files of the source directory, and the ones of its deps directory, keeping
+/// the directory structure of the deps
+fn copy_wit_files(source_dir: &Path, target_dir: &Path) -> anyhow::Result<()> {
+    if !source_dir.is_dir() {
+        bail!(
+            "WIT directory {} not found in the fetched repository",
+            source_dir.log_color_error_highlight()
+        );
+    }
+
+    fs::remove(target_dir)?;
+    fs::create_dir_all(target_dir)?;
+
+    let entries = WalkDir::new(source_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() != 1 || entry.file_type().is_file() || entry.file_name() == "deps"
+        });
+    for entry in entries {
+        let entry = entry.with_context(|| {
+            anyhow!(
+                "Failed to read WIT directory {}",
+                source_dir.log_color_highlight()
+            )
+        })?;
+        let path = entry.path();
+        if entry.file_type().is_file() && path.extension().is_some_and(|ext| ext == "wit") {
+            let target_path = target_dir.join(
+                path.strip_prefix(source_dir)
+                    .expect("Failed to strip WIT directory prefix"),
+            );
+            log_action(
+                "Copying",
+                format!(
+                    "WIT source {} to {}",
+                    path.log_color_highlight(),
+                    target_path.log_color_highlight()
+                ),
+            );
+            fs::copy(path, target_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::wit_deps::{copy_wit_files, fetch_wit_deps_into, load_lock};
+    use crate::model::app_raw::FetchWitDep;
+    use assert2::{assert, let_assert};
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use std::process::Command;
+    use test_r::test;
+
+    fn write(path: &Path, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(path: &Path) -> String {
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    fn commit_all(repo_dir: &Path) -> String {
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+                .args(args)
+                .current_dir(repo_dir)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            String::from_utf8(output.stdout).unwrap().trim().to_string()
+        };
+        if !repo_dir.join(".git").exists() {
+            git(&["init", "--quiet"]);
+        }
+        git(&["add", "-A"]);
+        git(&["commit", "--quiet", "-m", "update"]);
+        git(&["rev-parse", "HEAD"])
+    }
+
+    #[test]
+    fn copy_wit_files_with_deps() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source_dir = temp_dir.path().join("source");
+        let target_dir = temp_dir.path().join("target");
+        write(&source_dir.join("api.wit"), "package a:api;");
+        write(&source_dir.join("README.md"), "readme");
+        write(&source_dir.join("deps/io/streams.wit"), "package wasi:io;");
+        write(
+            &source_dir.join("deps/io/nested/error.wit"),
+            "interface error {}",
+        );
+        write(&source_dir.join("deps/clocks.wit"), "package wasi:clocks;");
+        write(
+            &source_dir.join("other/skipped.wit"),
+            "package other:skipped;",
+        );
+        write(&target_dir.join("stale.wit"), "package stale:stale;");
+
+        copy_wit_files(&source_dir, &target_dir).unwrap();
+
+        assert!(read(&target_dir.join("api.wit")) == "package a:api;");
+        assert!(read(&target_dir.join("deps/io/streams.wit")) == "package wasi:io;");
+        assert!(read(&target_dir.join("deps/io/nested/error.wit")) == "interface error {}");
+        assert!(read(&target_dir.join("deps/clocks.wit")) == "package wasi:clocks;");
+        assert!(!target_dir.join("README.md").exists());
+        assert!(!target_dir.join("other").exists());
+        assert!(!target_dir.join("stale.wit").exists());
+
+        let_assert!(Err(_) = copy_wit_files(&temp_dir.path().join("missing"), &target_dir));
+    }
+
+    #[test]
+    fn fetch_locks_and_updates_deps() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        let lock_file = temp_dir.path().join("golem-wit-deps.lock");
+        let fetched_dir = temp_dir.path().join("wit-deps");
+        let checkout_dir = temp_dir.path().join("wit-deps-checkout");
+        let fetch = |deps: &BTreeMap<String, FetchWitDep>, offline: bool, update: bool| {
+            fetch_wit_deps_into(
+                deps,
+                &lock_file,
+                &fetched_dir,
+                &checkout_dir,
+                offline,
+                update,
+            )
+        };
+
+        write(&repo_dir.join("wit/api.wit"), "package a:api@1.0.0;");
+        let first_commit = commit_all(&repo_dir);
+
+        let deps = BTreeMap::from([(
+            "api".to_string(),
+            FetchWitDep {
+                git: repo_dir.to_string_lossy().to_string(),
+                rev: None,
+                path: None,
+                registry: None,
+            },
+        )]);
+
+        let_assert!(Err(_) = fetch(&deps, true, false));
+
+        fetch(&deps, false, false).unwrap();
+        assert!(read(&fetched_dir.join("api/api.wit")) == "package a:api@1.0.0;");
+        assert!(load_lock(&lock_file).unwrap().deps["api"].commit == first_commit);
+
+        // Missing fetched deps are fetched again using the locked commit
+        write(&repo_dir.join("wit/api.wit"), "package a:api@2.0.0;");
+        let second_commit = commit_all(&repo_dir);
+        std::fs::remove_dir_all(&fetched_dir).unwrap();
+        fetch(&deps, false, false).unwrap();
+        assert!(read(&fetched_dir.join("api/api.wit")) == "package a:api@1.0.0;");
+        assert!(load_lock(&lock_file).unwrap().deps["api"].commit == first_commit);
+
+        // Fetched and locked deps are used as is, even offline
+        fetch(&deps, true, false).unwrap();
+
+        fetch(&deps, false, true).unwrap();
+        assert!(read(&fetched_dir.join("api/api.wit")) == "package a:api@2.0.0;");
+        assert!(load_lock(&lock_file).unwrap().deps["api"].commit == second_commit);
+
+        // Deps removed from the manifest are removed from the lockfile
+        fetch(&BTreeMap::new(), false, false).unwrap();
+        assert!(load_lock(&lock_file).unwrap().deps.is_empty());
+        assert!(!fetched_dir.join("api").exists());
+    }
+}
@@ -17,6 +17,7 @@ use crate::log::{log_warn_action, LogColorize};
 use crate::model::app::{
     AppComponentName, Application, BinaryComponentSource, DependencyType, HttpApiDefinitionName,
 };
+use crate::model::app_raw;
 use anyhow::{anyhow, Context};
 use nondestructive::yaml::{Document, Id, MappingMut, Separator, SequenceMut, Value, ValueMut};
 use std::collections::HashMap;
@@ -68,30 +69,66 @@ impl<'a> AppYamlEditor<'a> {
             })?;
 
             if let Some(target) = dep.get("target") {
-                let target_value = target.as_str_with_comments_workaround().ok_or_else(|| {
-                    anyhow!(
-                        "expected target field for dependency {} - {},  in {}",
-                        component_name.as_str(),
-                        target_component_source.to_string(),
-                        path.display()
-                    )
-                })?;
-                if let BinaryComponentSource::AppComponent { name } = target_component_source {
-                    if target_value == name.as_str() {
-                        dep_type_id = Some(
-                            dep.get("type")
-                                .ok_or_else(|| {
-                                    anyhow!(
-                                        "expected type field for dependency {} - {},  in {}",
-                                        component_name.as_str(),
-                                        target_component_source.to_string(),
-                                        path.display()
-                                    )
-                                })?
-                                .id(),
-                        );
-                        break;
+                let is_same_target = match target.as_mapping() {
+                    Some(remote_target) => {
+                        let component = remote_target
+                            .get("component")
+                            .and_then(|component| component.as_str_with_comments_workaround());
+                        let registry = remote_target
+                            .get("registry")
+                            .and_then(|registry| registry.as_str_with_comments_workaround());
+                        match target_component_source {
+                            BinaryComponentSource::Deployed { name, version } => {
+                                component == Some(name.as_str())
+                                    && registry.is_none()
+                                    && remote_target.get("version").and_then(|version| {
+                                        version.as_i64_with_comments_workaround()
+                                    }) == version.map(|version| version as i64)
+                            }
+                            BinaryComponentSource::Registry {
+                                name,
+                                registry: target_registry,
+                                version,
+                            } => {
+                                component == Some(name.as_str())
+                                    && registry == Some(target_registry.as_str())
+                                    && remote_target.get("version").and_then(|version| {
+                                        version.as_str_with_comments_workaround()
+                                    }) == version.as_deref()
+                            }
+                            _ => false,
+                        }
                     }
+                    None => {
+                        let target_value =
+                            target.as_str_with_comments_workaround().ok_or_else(|| {
+                                anyhow!(
+                                    "expected target field for dependency {} - {},  in {}",
+                                    component_name.as_str(),
+                                    target_component_source.to_string(),
+                                    path.display()
+                                )
+                            })?;
+                        matches!(
+                            target_component_source,
+                            BinaryComponentSource::AppComponent { name } if target_value == name.as_str()
+                        )
+                    }
+                };
+                if is_same_target {
+                    dep_type_id = Some(
+                        dep.get("type")
+                            .ok_or_else(|| {
+                                anyhow!(
+                                    "expected type field for dependency {} - {},  in {}",
+                                    component_name.as_str(),
+                                    target_component_source.to_string(),
+                                    path.display()
+                                )
+                            })?
+                            .id(),
+                    );
+                    break;
                 }
             } else if let Some(path_field) = dep.get("path") {
                 let path_value = path_field
@@ -184,6 +221,25 @@ impl<'a> AppYamlEditor<'a> {
                     BinaryComponentSource::Url { url } => {
                         dep.insert_str("url", url);
                     }
+                    BinaryComponentSource::Deployed { name, version } => {
+                        let mut target = dep.insert("target", Separator::Auto).make_mapping();
+                        target.insert_str("component", name.as_str());
+                        if let Some(version) = version {
+                            target.insert_u64("version", *version);
+                        }
+                    }
+                    BinaryComponentSource::Registry {
+                        name,
+                        registry,
+                        version,
+                    } => {
+                        let mut target = dep.insert("target", Separator::Auto).make_mapping();
+                        target.insert_str("component", name.as_str());
+                        target.insert_str("registry", registry);
+                        if let Some(version) = version {
+                            target.insert_str("version", version);
+                        }
+                    }
                 }
                 dep.insert_str("type", dependency_type.as_str());
 
@@ -198,6 +254,37 @@ impl<'a> AppYamlEditor<'a> {
         Ok(insert)
     }
 
+    /// Marks the component as the test component of the target component
+    pub fn set_component_test_for(
+        &mut self,
+        component_name: &AppComponentName,
+        target_component_name: &AppComponentName,
+    ) -> anyhow::Result<()> {
+        let path = self.document_path_for_component(component_name);
+
+        let document = self.document_mut(&path)?;
+        let root_value = document.as_mut();
+
+        let mut component = root_value
+            .into_mapping_key_insert_missing("components")?
+            .into_mapping_key_insert_missing(component_name.as_str())?
+            .into_mapping_replace_empty()
+            .with_context(|| {
+                anyhow!(
+                    "expected mapping for component {} in {}",
+                    component_name.as_str(),
+                    path.display()
+                )
+            })?;
+
+        match component.get_mut("testFor") {
+            Some(test_for) => test_for.set_string(target_component_name.as_str()),
+            None => component.insert_str("testFor", target_component_name.as_str()),
+        }
+
+        Ok(())
+    }
+
     pub fn update_api_definition_version(
         &mut self,
         api_definition_name: &HttpApiDefinitionName,
@@ -235,6 +322,144 @@ impl<'a> AppYamlEditor<'a> {
         Ok(())
     }
 
+    /// Returns true if the route was inserted and false if an existing route with the same method and path was replaced
+    pub fn insert_or_replace_api_definition_route(
+        &mut self,
+        api_definition_name: &HttpApiDefinitionName,
+        route: &app_raw::HttpApiDefinitionRoute,
+    ) -> anyhow::Result<bool> {
+        let existing_idx = self.remove_api_definition_route_by_method_and_path(
+            api_definition_name,
+            &route.method,
+            &route.path,
+        )?;
+
+        let path = self.document_path_for_api_definition(api_definition_name);
+        let document = self.document_mut(&path)?;
+        let mut routes = document
+            .as_mut()
+            .into_mapping_key_insert_missing("httpApi")?
+            .into_mapping_key_insert_missing("definitions")?
+            .into_mapping_key_insert_missing(api_definition_name.as_str())?
+            .into_mapping_key_insert_missing("routes")?
+            .into_sequence_replace_empty()?;
+
+        // See: field_of_sequence_of_mapping_ident_bug
+        let empty_on_start = routes.as_ref().is_empty();
+        if empty_on_start {
+            routes.push(Separator::Auto);
+        }
+
+        {
+            let mut route_mapping = routes.push(Separator::Auto).make_mapping();
+            route_mapping.insert_str("method", &route.method);
+            route_mapping.insert_str("path", &route.path);
+            if let Some(security) = &route.security {
+                route_mapping.insert_str("security", security);
+            }
+
+            let mut binding = route_mapping
+                .insert("binding", Separator::Auto)
+                .make_mapping();
+            if let Some(binding_type) = &route.binding.type_ {
+                binding.insert_str(
+                    "type",
+                    serde_json::to_value(binding_type)?
+                        .as_str()
+                        .unwrap_or_default(),
+                );
+            }
+            if let Some(component_name) = &route.binding.component_name {
+                binding.insert_str("componentName", component_name);
+            }
+            if let Some(component_version) = route.binding.component_version {
+                binding.insert_u64("componentVersion", component_version);
+            }
+            if let Some(idempotency_key) = &route.binding.idempotency_key {
+                binding.insert_str("idempotencyKey", idempotency_key);
+            }
+            if let Some(invocation_context) = &route.binding.invocation_context {
+                binding.insert_str("invocationContext", invocation_context);
+            }
+            if let Some(response) = &route.binding.response {
+                binding.insert_str("response", response);
+            }
+        }
+
+        if empty_on_start {
+            routes.remove(0);
+        }
+
+        Ok(existing_idx.is_none())
+    }
+
+    /// Returns true if the route was found and removed
+    pub fn remove_api_definition_route(
+        &mut self,
+        api_definition_name: &HttpApiDefinitionName,
+        method: &str,
+        route_path: &str,
+    ) -> anyhow::Result<bool> {
+        Ok(self
+            .remove_api_definition_route_by_method_and_path(
+                api_definition_name,
+                method,
+                route_path,
+            )?
+            .is_some())
+    }
+
+    fn remove_api_definition_route_by_method_and_path(
+        &mut self,
+        api_definition_name: &HttpApiDefinitionName,
+        method: &str,
+        route_path: &str,
+    ) -> anyhow::Result<Option<usize>> {
+        let path = self.document_path_for_api_definition(api_definition_name);
+        let document = self.document_mut(&path)?;
+        let Some(mut routes) = document
+            .as_mut()
+            .into_mapping_key_insert_missing("httpApi")?
+            .into_mapping_key_insert_missing("definitions")?
+            .into_mapping_key_insert_missing(api_definition_name.as_str())?
+            .into_mapping_key_insert_missing("routes")?
+            .into_sequence_mut()
+        else {
+            return Ok(None);
+        };
+
+        let mut found_idx = None;
+        for (idx, route) in routes.as_ref().iter().enumerate() {
+            let route = route.as_mapping().ok_or_else(|| {
+                anyhow!(
+                    "expected mapping for route of HTTP API definition {} in {}",
+                    api_definition_name.as_str(),
+                    path.display()
+                )
+            })?;
+
+            let route_method = route
+                .get("method")
+                .and_then(|method| method.as_str_with_comments_workaround());
+            let route_path_value = route
+                .get("path")
+                .and_then(|path| path.as_str_with_comments_workaround());
+
+            if route_method.is_some_and(|route_method| route_method.eq_ignore_ascii_case(method))
+                && route_path_value == Some(route_path)
+            {
+                found_idx = Some(idx);
+                break;
+            }
+        }
+
+        if let Some(idx) = found_idx {
+            routes.remove(idx);
+        }
+
+        Ok(found_idx)
+    }
+
     fn document_mut(&mut self, path: &Path) -> anyhow::Result<&mut Document> {
         if !self.documents.contains_key(path) {
             self.documents.insert(
@@ -297,7 +522,6 @@ impl<'a> AppYamlEditor<'a> {
 
 trait ValueExtensions<'a> {
     fn as_str_with_comments_workaround(&self) -> Option<&str>;
-    #[allow(dead_code)]
     fn as_i64_with_comments_workaround(&self) -> Option<i64>;
 }
 
@@ -19,16 +19,26 @@ use crate::config::{Config, ProfileName};
 use crate::error::service::AnyhowMapServiceError;
 use crate::log::LogColorize;
 use crate::model::AccountId;
-use anyhow::{anyhow, bail, Context};
+use crate::poll::Poller;
+use anyhow::{anyhow, Context};
+use chrono::Utc;
 use colored::Colorize;
 use golem_client::api::{LoginClient, LoginClientLive, LoginOauth2WebFlowPollError};
-use golem_client::model::{Token, TokenSecret, UnsafeToken, WebFlowAuthorizeUrlResponse};
+use golem_client::model::{
+    OAuth2Data, Token, TokenSecret, UnsafeToken, WebFlowAuthorizeUrlResponse,
+};
 use golem_client::Security;
 use indoc::printdoc;
 use std::path::Path;
+use std::task::Poll;
+use std::time::Duration;
 use tracing::info;
 use uuid::Uuid;
 
+/// Default number of days before token expiry to start warning about it,
+/// can be overridden with GOLEM_TOKEN_EXPIRY_WARNING_DAYS
+pub const DEFAULT_TOKEN_EXPIRY_WARNING_DAYS: u64 = 7;
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct Authentication(pub UnsafeToken);
 
@@ -40,6 +50,12 @@ impl Authentication {
     pub fn account_id(&self) -> AccountId {
         AccountId(self.0.data.account_id.clone())
     }
+
+    pub fn to_oauth2_config(&self) -> AuthenticationConfig {
+        AuthenticationConfig::OAuth2(OAuth2AuthenticationConfig {
+            data: Some(unsafe_token_to_auth_data(&self.0)),
+        })
+    }
 }
 
 impl From<&OAuth2AuthenticationData> for Authentication {
@@ -58,6 +74,8 @@ impl From<&OAuth2AuthenticationData> for Authentication {
     }
 }
 
+const OAUTH2_WORKFLOW_TIMEOUT: Duration = Duration::from_secs(60);
+
 pub struct Auth {
     login_client: LoginClientLive,
 }
@@ -109,6 +127,19 @@ impl Auth {
         Ok(())
     }
 
+    /// Performs the OAuth2 device authorization flow and stores the resulting token in the profile
+    pub async fn login(
+        &self,
+        profile_name: &ProfileName,
+        config_dir: &Path,
+    ) -> anyhow::Result<Authentication> {
+        let data = self.start_device_flow().await?;
+        inform_user_device_flow(&data);
+        let token = self.complete_device_flow(&data).await?;
+        self.save_auth(&token, profile_name, config_dir)?;
+        Ok(Authentication(token))
+    }
+
     async fn oauth2(
         &self,
         profile_name: &ProfileName,
@@ -161,31 +192,49 @@ impl Auth {
             .map_service_error()
     }
 
-    async fn complete_oauth2(&self, state: String) -> anyhow::Result<UnsafeToken> {
-        use tokio::time::{sleep, Duration};
+    async fn start_device_flow(&self) -> anyhow::Result<OAuth2Data> {
+        info!("Start OAuth2 device flow");
+        self.login_client
+            .start_login_oauth_2()
+            .await
+            .map_service_error()
+    }
+
+    async fn complete_device_flow(&self, data: &OAuth2Data) -> anyhow::Result<UnsafeToken> {
+        info!("Complete OAuth2 device flow");
+        // The cloud service keeps polling the provider until the user code is confirmed,
+        // so we only have to wait until the code expires
+        let timeout = (data.expires - Utc::now()).to_std().unwrap_or_default();
 
+        tokio::time::timeout(
+            timeout,
+            self.login_client
+                .complete_login_oauth_2(&data.encoded_session),
+        )
+        .await
+        .map_err(|_| anyhow!("OAuth2 device flow timeout, the user code has expired"))?
+        .map_service_error()
+    }
+
+    async fn complete_oauth2(&self, state: String) -> anyhow::Result<UnsafeToken> {
         info!("Complete OAuth2 workflow");
-        let mut attempts = 0;
-        let max_attempts = 60;
-        let delay = Duration::from_secs(1);
-
-        loop {
-            let status = self.login_client.oauth_2_web_flow_poll(&state).await;
-            match status {
-                Ok(token) => return Ok(token),
-                Err(err) => match err {
-                    golem_client::Error::Item(LoginOauth2WebFlowPollError::Error202(_)) => {
-                        attempts += 1;
-                        if attempts >= max_attempts {
-                            bail!("OAuth2 workflow timeout")
-                        }
-
-                        sleep(delay).await;
+
+        // The user is waiting for the login to complete, so the interval is kept short
+        let poller = Poller {
+            max_interval: Duration::from_secs(2),
+            ..Poller::new(Some(OAUTH2_WORKFLOW_TIMEOUT))
+        };
+        poller
+            .poll("OAuth2 authentication", || async {
+                match self.login_client.oauth_2_web_flow_poll(&state).await {
+                    Ok(token) => Ok(Poll::Ready(token)),
+                    Err(golem_client::Error::Item(LoginOauth2WebFlowPollError::Error202(_))) => {
+                        Ok(Poll::Pending)
                     }
-                    _ => return Err(err).map_service_error(),
-                },
-            }
-        }
+                    Err(err) => Err(err).map_service_error(),
+                }
+            })
+            .await
     }
 }
 
@@ -208,6 +257,30 @@ fn inform_user(data: &WebFlowAuthorizeUrlResponse) {
     println!("Waiting for authentication...");
 }
 
+fn inform_user_device_flow(data: &OAuth2Data) {
+    let url = &data.url.underline();
+    let user_code = &data.user_code.bold();
+    let expires = data.expires.format("%Y-%m-%d %H:%M:%S UTC");
+
+    printdoc! {
+        "
+        ┌────────────────────────────────────────┐
+        │       Authenticate with GitHub         │
+        │                                        │
+        │  Visit the following URL in a browser  │
+        │  and enter the code below              │
+        │                                        │
+        └────────────────────────────────────────┘
+        {url}
+        Code: {user_code}
+        ──────────────────────────────────────────
+        The code expires at {expires}
+        "
+    }
+
+    println!("Waiting for authentication...");
+}
+
 fn token_header(secret: &TokenSecret) -> String {
     format!("bearer {}", secret.value)
 }
@@ -0,0 +1,138 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cancellation of bulk operations on Ctrl-C.
+//!
+//! While a bulk operation is running, the first Ctrl-C only cancels it: no new requests are
+//! started, the in-flight ones can still complete, then the command reports what completed and
+//! exits with [CANCELLED_EXIT_CODE]. Outside of bulk operations, or on a second Ctrl-C, the
+//! process exits immediately, as it would without the handler.
+
+use crate::error::NonSuccessfulExitWithCode;
+use crate::log::{log_warn_action, LogColorize};
+use crate::model::text::fmt::log_warn;
+use anyhow::bail;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Once};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Exit code of cancelled commands, the same as shells use for interrupted processes
+pub const CANCELLED_EXIT_CODE: u8 = 130;
+
+/// Time given to the in-flight requests to complete after cancellation
+pub const IN_FLIGHT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation {
+    inner: Arc<CancellationInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationInner {
+    cancelled: AtomicBool,
+    active_scopes: AtomicUsize,
+    ctrl_c_listener: Once,
+    notify: Notify,
+}
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks a running bulk operation, Ctrl-C cancels it instead of exiting while the returned
+    /// guard is alive
+    pub fn scope(&self) -> CancellationScope {
+        self.listen_for_ctrl_c();
+        self.inner.active_scopes.fetch_add(1, Ordering::SeqCst);
+        CancellationScope {
+            cancellation: self.clone(),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Completes once cancelled
+    pub async fn cancelled(&self) {
+        loop {
+            // Created before checking the flag, so a concurrent cancel cannot be missed
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Fails with the cancelled exit code if cancelled, used after reporting the completed
+    /// part of an operation
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.is_cancelled() {
+            bail!(NonSuccessfulExitWithCode(CANCELLED_EXIT_CODE));
+        }
+        Ok(())
+    }
+
+    /// Reports the completed part of a cancelled operation
+    pub fn log_cancelled(&self, operation: &str, completed: usize, total: usize) {
+        log_warn_action(
+            "Cancelled",
+            format!(
+                "{operation}, completed {} of {}",
+                completed.to_string().log_color_highlight(),
+                total.to_string().log_color_highlight()
+            ),
+        );
+    }
+
+    fn listen_for_ctrl_c(&self) {
+        self.inner.ctrl_c_listener.call_once(|| {
+            let cancellation = self.clone();
+            tokio::spawn(async move {
+                while tokio::signal::ctrl_c().await.is_ok() {
+                    if cancellation.is_cancelled()
+                        || cancellation.inner.active_scopes.load(Ordering::SeqCst) == 0
+                    {
+                        std::process::exit(CANCELLED_EXIT_CODE.into());
+                    }
+                    log_warn(
+                        "Cancelling, waiting for the in-flight requests to complete, press Ctrl-C again to exit immediately",
+                    );
+                    cancellation.cancel();
+                }
+            });
+        });
+    }
+}
+
+pub struct CancellationScope {
+    cancellation: Cancellation,
+}
+
+impl Drop for CancellationScope {
+    fn drop(&mut self) {
+        self.cancellation
+            .inner
+            .active_scopes
+            .fetch_sub(1, Ordering::SeqCst);
+    }
+}
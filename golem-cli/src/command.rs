@@ -16,29 +16,44 @@ use crate::command::api::ApiSubcommand;
 use crate::command::app::AppSubcommand;
 use crate::command::cloud::CloudSubcommand;
 use crate::command::component::ComponentSubcommand;
+use crate::command::events::EventsSubcommand;
+use crate::command::export::ExportSubcommand;
+use crate::command::extension::ExtensionSubcommand;
+use crate::command::introspect::IntrospectSubcommand;
 use crate::command::plugin::PluginSubcommand;
+use crate::command::profile::context::ContextSubcommand;
 use crate::command::profile::ProfileSubcommand;
+use crate::command::rib::RibSubcommand;
+use crate::command::sdk::SdkSubcommand;
 use crate::command::worker::WorkerSubcommand;
-use crate::config::{BuildProfileName, ProfileName};
+use crate::config::{BuildProfileName, Config, ProfileName};
 use crate::log::LogColorize;
-use crate::model::{Format, WorkerName};
+use crate::model::extension::find_extension;
+use crate::model::{ComponentName, Format, ProjectReference, WorkerName};
 use crate::{command_name, version};
-use anyhow::{anyhow, bail, Context as AnyhowContext};
+use anyhow::{anyhow, bail};
 use chrono::{DateTime, Utc};
 use clap::error::{ContextKind, ContextValue, ErrorKind};
 use clap::{self, Command, CommandFactory, Subcommand};
 use clap::{Args, Parser};
 use clap_verbosity_flag::{ErrorLevel, LogLevel};
 use golem_client::model::ScanCursor;
+use itertools::Itertools;
 use lenient_bool::LenientBool;
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::ffi::OsString;
+use std::fmt::Display;
+use std::num::NonZeroU32;
 use std::path::PathBuf;
+use std::str::FromStr;
+use url::Url;
 use uuid::Uuid;
 
+#[cfg(not(feature = "server-commands"))]
+use crate::command::server::LocalServerSubcommand;
 #[cfg(feature = "server-commands")]
 use crate::command::server::ServerSubcommand;
-use crate::command::shared_args::ComponentOptionalComponentName;
+use crate::command::shared_args::{ComponentOptionalComponentName, ProjectOptionalFlagArg};
 use crate::error::ShowClapHelpTarget;
 
 /// Golem Command Line Interface
@@ -85,6 +100,10 @@ pub struct Verbosity<L: LogLevel = ErrorLevel> {
 }
 
 impl Verbosity {
+    pub fn is_verbose(&self) -> bool {
+        self.verbose > 0
+    }
+
     pub fn as_clap_verbosity_flag(self) -> clap_verbosity_flag::Verbosity {
         clap_verbosity_flag::Verbosity::new(self.verbose, self.quiet)
     }
@@ -134,6 +153,67 @@ pub struct GolemCliGlobalFlags {
     #[arg(long, global = true, display_order = 110)]
     pub show_sensitive: bool,
 
+    /// Override the service URLs of the selected profile for the current invocation, can also be set with GOLEM_BASE_URL.
+    /// Use http+unix://<percent-encoded socket path>/<base path> for Unix sockets
+    #[arg(long, global = true, display_order = 111)]
+    pub base_url: Option<Url>,
+
+    /// Override the auth token of the selected profile for the current invocation, can also be set with GOLEM_TOKEN
+    #[arg(long = "token", global = true, display_order = 112)]
+    pub auth_token: Option<Uuid>,
+
+    /// Select project for project scoped commands, overrides the default project of the selected profile, can also be set with GOLEM_PROJECT
+    #[arg(long, global = true, display_order = 113)]
+    pub project: Option<ProjectReference>,
+
+    /// Variable for rendering application manifest and HTTP API definition templates, overrides the variables defined in the manifest, can be used multiple times
+    #[arg(long = "var", global = true, value_parser = parse_key_val, value_name = "KEY=VAL", display_order = 114)]
+    pub template_vars: Vec<(String, String)>,
+
+    /// Log every HTTP request and response made to Golem services to stderr, with credentials redacted unless --show-sensitive is used, can also be enabled with GOLEM_DEBUG_HTTP or in the profile config
+    #[arg(long, global = true, display_order = 115)]
+    pub debug_http: bool,
+
+    /// Append the bodies of the logged HTTP requests and responses to the given file, implies --debug-http. The bodies are written without redaction
+    #[arg(long, global = true, display_order = 116)]
+    pub debug_http_body_file: Option<PathBuf>,
+
+    /// Timeout of service calls (not including invocations), including the retries, e.g.: 30s, 2m
+    #[arg(long, global = true, value_parser = parse_duration, display_order = 117)]
+    pub http_timeout: Option<chrono::Duration>,
+
+    /// Connect timeout of service calls and invocations, e.g.: 500ms, 5s
+    #[arg(long, global = true, value_parser = parse_duration, display_order = 118)]
+    pub http_connect_timeout: Option<chrono::Duration>,
+
+    /// Number of retries for failed idempotent requests and requests with idempotency keys, on connection errors and 429, 502, 503 and 504 responses, defaults to 0 (3 for component uploads)
+    #[arg(long, global = true, display_order = 119)]
+    pub http_retries: Option<u32>,
+
+    /// Base delay of the exponential backoff between retries, a random jitter is added to it, e.g.: 200ms, 1s, defaults to 500ms
+    #[arg(long, global = true, value_parser = parse_duration, display_order = 120)]
+    pub http_retry_backoff: Option<chrono::Duration>,
+
+    /// Use a local cache of component and API definition metadata, which is revalidated with ETags, can also be enabled with GOLEM_HTTP_CACHE
+    #[arg(long, global = true, display_order = 121)]
+    pub http_cache: bool,
+
+    /// Maximum number of service requests per second, requests are also throttled automatically when the services report rate limits
+    #[arg(long, global = true, display_order = 122)]
+    pub max_rps: Option<NonZeroU32>,
+
+    /// Print a breakdown of where the time of the command was spent (argument parsing, auth, HTTP requests, rendering) to stderr
+    #[arg(long, global = true, display_order = 123)]
+    pub timings: bool,
+
+    /// Write the timings of the command to a file in the Chrome trace event format, which can be opened with chrome://tracing or Perfetto
+    #[arg(long, global = true, display_order = 124)]
+    pub trace_file: Option<PathBuf>,
+
+    /// Maximum time to wait for asynchronous operations to complete (e.g. worker updates), e.g.: 30s, 5m, waits without a limit by default
+    #[arg(long, global = true, value_parser = parse_duration, display_order = 125)]
+    pub wait_timeout: Option<chrono::Duration>,
+
     #[command(flatten)]
     pub verbosity: Verbosity,
 
@@ -152,14 +232,45 @@ pub struct GolemCliGlobalFlags {
     pub http_batch_size: Option<u64>,
 
     #[arg(skip)]
-    pub auth_token: Option<Uuid>,
+    pub http_concurrency: Option<usize>,
 
     #[arg(skip)]
     pub local_server_auto_start: bool,
+
+    #[arg(skip)]
+    pub token_expiry_warning_days: Option<u64>,
+
+    #[arg(skip)]
+    pub http_record: Option<PathBuf>,
+
+    #[arg(skip)]
+    pub http_replay: Option<PathBuf>,
+
+    #[arg(skip)]
+    pub env_override_errors: Vec<String>,
 }
 
 impl GolemCliGlobalFlags {
+    /// Fills the global flags that were not provided on the command line from environment variables.
+    ///
+    /// Profile related settings are resolved in the following order (first one wins):
+    ///   - command line flags (e.g. --base-url, --token, --profile, --project, --format)
+    ///   - environment variables (e.g. GOLEM_BASE_URL, GOLEM_TOKEN, GOLEM_PROFILE, GOLEM_PROJECT, GOLEM_FORMAT)
+    ///   - application manifest profile
+    ///   - global profile from the config directory
     pub fn with_env_overrides(mut self) -> GolemCliGlobalFlags {
+        if self.format.is_none() {
+            if let Ok(format) = std::env::var("GOLEM_FORMAT") {
+                self.format = self.parse_env_var("GOLEM_FORMAT", &format);
+            }
+        }
+
+        if self.project.is_none() {
+            if let Ok(project) = std::env::var("GOLEM_PROJECT") {
+                self.project = self.parse_env_var("GOLEM_PROJECT", &project);
+            }
+        }
+
         if self.profile.is_none() {
             if let Ok(profile) = std::env::var("GOLEM_PROFILE") {
                 self.profile = Some(profile.into());
@@ -172,6 +283,12 @@ impl GolemCliGlobalFlags {
             }
         }
 
+        if self.config_dir.is_none() {
+            if let Ok(config_dir) = std::env::var("GOLEM_CONFIG_DIR") {
+                self.config_dir = Some(PathBuf::from(config_dir));
+            }
+        }
+
         if !self.disable_app_manifest_discovery {
             if let Ok(disable) = std::env::var("GOLEM_DISABLE_APP_MANIFEST_DISCOVERY") {
                 self.disable_app_manifest_discovery = disable
@@ -206,22 +323,57 @@ impl GolemCliGlobalFlags {
             }
         }
 
+        if let Ok(concurrency) = std::env::var("GOLEM_HTTP_CONCURRENCY") {
+            self.http_concurrency = self.parse_env_var("GOLEM_HTTP_CONCURRENCY", &concurrency);
+        }
+
         if let Ok(batch_size) = std::env::var("GOLEM_HTTP_BATCH_SIZE") {
-            self.http_batch_size = Some(
-                batch_size
-                    .parse()
-                    .with_context(|| format!("Failed to parse GOLEM_HTTP_BATCH_SIZE: {batch_size}"))
-                    .unwrap(),
-            )
+            self.http_batch_size = self.parse_env_var("GOLEM_HTTP_BATCH_SIZE", &batch_size);
         }
 
-        if let Ok(auth_token) = std::env::var("GOLEM_AUTH_TOKEN") {
-            self.auth_token = Some(
-                auth_token
-                    .parse()
-                    .context("Failed to parse GOLEM_AUTH_TOKEN, expected uuid")
-                    .unwrap(),
-            );
+        if self.base_url.is_none() {
+            if let Ok(base_url) = std::env::var("GOLEM_BASE_URL") {
+                self.base_url = self.parse_env_var("GOLEM_BASE_URL", &base_url);
+            }
+        }
+
+        if self.auth_token.is_none() {
+            // NOTE: GOLEM_AUTH_TOKEN is kept for backward compatibility
+            for env_var in ["GOLEM_TOKEN", "GOLEM_AUTH_TOKEN"] {
+                if let Ok(auth_token) = std::env::var(env_var) {
+                    self.auth_token = self.parse_env_var(env_var, &auth_token);
+                    break;
+                }
+            }
+        }
+
+        if let Ok(warning_days) = std::env::var("GOLEM_TOKEN_EXPIRY_WARNING_DAYS") {
+            self.token_expiry_warning_days =
+                self.parse_env_var("GOLEM_TOKEN_EXPIRY_WARNING_DAYS", &warning_days);
+        }
+
+        if self.http_retries.is_none() {
+            if let Ok(retries) = std::env::var("GOLEM_HTTP_RETRIES") {
+                self.http_retries = self.parse_env_var("GOLEM_HTTP_RETRIES", &retries);
+            }
+        }
+
+        if !self.http_cache {
+            if let Ok(http_cache) = std::env::var("GOLEM_HTTP_CACHE") {
+                self.http_cache = http_cache
+                    .parse::<LenientBool>()
+                    .map(|b| b.into())
+                    .unwrap_or_default()
+            }
+        }
+
+        if !self.debug_http {
+            if let Ok(debug_http) = std::env::var("GOLEM_DEBUG_HTTP") {
+                self.debug_http = debug_http
+                    .parse::<LenientBool>()
+                    .map(|b| b.into())
+                    .unwrap_or_default()
+            }
         }
 
         if let Ok(auto_start) = std::env::var("GOLEM_LOCAL_SERVER_AUTO_START") {
@@ -231,9 +383,42 @@ impl GolemCliGlobalFlags {
                 .unwrap_or_default()
         }
 
+        if let Ok(path) = std::env::var("GOLEM_HTTP_RECORD") {
+            self.http_record = Some(PathBuf::from(path));
+        }
+
+        if let Ok(path) = std::env::var("GOLEM_HTTP_REPLAY") {
+            self.http_replay = Some(PathBuf::from(path));
+        }
+
         self
     }
 
+    fn parse_env_var<T>(&mut self, name: &str, value: &str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.env_override_errors
+                    .push(format!("Invalid value for {name}: {value:?}: {err}"));
+                None
+            }
+        }
+    }
+
+    /// Fails with all the environment variables that could not be parsed by
+    /// [Self::with_env_overrides]
+    pub fn check_env_overrides(&self) -> anyhow::Result<()> {
+        if self.env_override_errors.is_empty() {
+            Ok(())
+        } else {
+            bail!("{}", self.env_override_errors.join("\n"))
+        }
+    }
+
     pub fn config_dir(&self) -> PathBuf {
         self.config_dir
             .clone()
@@ -298,6 +483,12 @@ impl GolemCliCommand {
             .map(|arg| arg.into())
             .collect::<Vec<OsString>>();
 
+        let args = if with_env_overrides {
+            Self::with_profile_command_defaults(args)
+        } else {
+            args
+        };
+
         match GolemCliCommand::try_parse_from(&args) {
             Ok(mut command) => {
                 if with_env_overrides {
@@ -309,6 +500,28 @@ impl GolemCliCommand {
                 let fallback_command =
                     GolemCliFallbackCommand::try_parse_from(&args, with_env_overrides);
 
+                if error.kind() == ErrorKind::InvalidSubcommand {
+                    if let Some(extension_name) = fallback_command.positional_args.first() {
+                        if let Some(extension_path) = find_extension(
+                            &fallback_command.global_flags.config_dir(),
+                            extension_name,
+                        ) {
+                            let extension_args = args
+                                .iter()
+                                .skip(1)
+                                .skip_while(|arg| arg.as_os_str() != extension_name.as_str())
+                                .skip(1)
+                                .cloned()
+                                .collect();
+                            return GolemCliCommandParseResult::Extension {
+                                fallback_command,
+                                extension_path,
+                                args: extension_args,
+                            };
+                        }
+                    }
+                }
+
                 let partial_match = match error.kind() {
                     ErrorKind::DisplayHelp => {
                         let positional_args = fallback_command
@@ -368,6 +581,42 @@ impl GolemCliCommand {
         }
     }
 
+    /// Layers the command defaults of the selected global profile on the args, before parsing.
+    ///
+    /// Only global profiles are considered, as the application manifest is not loaded yet at
+    /// this point. Any problem with loading the profile is ignored here, as it will be reported
+    /// by the context initialization.
+    fn with_profile_command_defaults(args: Vec<OsString>) -> Vec<OsString> {
+        let fallback_command = GolemCliFallbackCommand::try_parse_from(&args, true);
+        let global_flags = &fallback_command.global_flags;
+
+        let Ok(config) = Config::from_dir(&global_flags.config_dir()) else {
+            return args;
+        };
+
+        let profile_name = if global_flags.local {
+            ProfileName::local()
+        } else if global_flags.cloud {
+            ProfileName::cloud()
+        } else {
+            global_flags
+                .profile
+                .clone()
+                .unwrap_or_else(|| config.default_profile_name())
+        };
+
+        let Ok(Some((profile, _))) = config.resolve_profile(&profile_name) else {
+            return args;
+        };
+
+        apply_command_defaults(
+            &GolemCliCommand::command(),
+            args,
+            &fallback_command.positional_args,
+            &profile.profile.config.command_defaults,
+        )
+    }
+
     fn invalid_arg_matchers() -> Vec<InvalidArgMatcher> {
         vec![
             InvalidArgMatcher {
@@ -376,16 +625,8 @@ impl GolemCliCommand {
                 missing_positional_arg: "worker_name",
                 to_partial_match: |_| GolemCliCommandPartialMatch::WorkerInvokeMissingWorkerName,
             },
-            InvalidArgMatcher {
-                subcommands: vec!["worker", "invoke"],
-                found_positional_args: vec!["worker_name"],
-                missing_positional_arg: "function_name",
-                to_partial_match: |args| {
-                    GolemCliCommandPartialMatch::WorkerInvokeMissingFunctionName {
-                        worker_name: args[0].clone().into(),
-                    }
-                },
-            },
+            // NOTE: the missing function name of "worker invoke" is handled by the worker command
+            //       handler, as it is optional when the worker is set in the profile context
             InvalidArgMatcher {
                 subcommands: vec!["profile", "switch"],
                 missing_positional_arg: "profile_name",
@@ -475,6 +716,11 @@ pub enum GolemCliCommandParseResult {
         error: clap::Error,
         fallback_command: GolemCliFallbackCommand,
     },
+    Extension {
+        fallback_command: GolemCliFallbackCommand,
+        extension_path: PathBuf,
+        args: Vec<OsString>,
+    },
 }
 
 #[derive(Debug)]
@@ -507,6 +753,29 @@ pub enum GolemCliSubcommand {
         #[clap(subcommand)]
         subcommand: WorkerSubcommand,
     },
+    /// Follow worker events, e.g. for alerting scripts
+    Events {
+        #[clap(subcommand)]
+        subcommand: EventsSubcommand,
+    },
+    /// Show the invocation, error and memory metrics reported by the metrics endpoints of the component and worker services
+    Metrics {
+        /// Only show the metrics of the given component
+        #[arg(long)]
+        component: Option<ComponentName>,
+        /// Only show the metrics of the given worker
+        #[arg(long)]
+        worker: Option<WorkerName>,
+        /// Show all metrics, not only the invocation, error and memory related ones
+        #[arg(long, default_value_t = false)]
+        all: bool,
+        /// Sample the metrics twice with the given delay in seconds, and show the per second rate of the counters
+        #[arg(long)]
+        rate_window: Option<u64>,
+        /// Print the selected metrics in the OpenMetrics text format, for piping into monitoring tools. Implies --all.
+        #[arg(long, default_value_t = false)]
+        openmetrics: bool,
+    },
     /// Manage API gateway objects
     Api {
         #[clap(subcommand)]
@@ -517,6 +786,35 @@ pub enum GolemCliSubcommand {
         #[clap(subcommand)]
         subcommand: PluginSubcommand,
     },
+    /// Converge the components, API definitions and API deployments of a project to the state described in a desired state file
+    Apply {
+        #[command(flatten)]
+        project: ProjectOptionalFlagArg,
+        /// Path to the desired state YAML file
+        #[arg(short, long, value_hint = clap::ValueHint::FilePath)]
+        file: PathBuf,
+        /// Delete the API deployments and API definitions which are not part of the desired state
+        #[arg(long, default_value_t = false)]
+        prune: bool,
+        /// Only show the planned changes, without applying them
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+    /// Export the resources of a project for other tools
+    Export {
+        #[clap(subcommand)]
+        subcommand: ExportSubcommand,
+    },
+    /// Generate client libraries for deployed APIs
+    Sdk {
+        #[clap(subcommand)]
+        subcommand: SdkSubcommand,
+    },
+    /// List and install CLI extensions, which are executables named golem-<NAME> providing the <NAME> subcommand
+    Extension {
+        #[clap(subcommand)]
+        subcommand: ExtensionSubcommand,
+    },
     /// Manage global CLI profiles
     Profile {
         #[clap(subcommand)]
@@ -528,11 +826,31 @@ pub enum GolemCliSubcommand {
         #[clap(subcommand)]
         subcommand: ServerSubcommand,
     },
+    /// Run and manage the local Golem services using docker compose
+    #[cfg(not(feature = "server-commands"))]
+    Server {
+        #[clap(subcommand)]
+        subcommand: LocalServerSubcommand,
+    },
     /// Manage Golem Cloud accounts and projects
     Cloud {
         #[clap(subcommand)]
         subcommand: CloudSubcommand,
     },
+    /// Log in to Golem Cloud using the OAuth2 device flow, and store the token in the selected profile
+    Login,
+    /// Manage the default component and worker of the selected profile
+    Context {
+        #[clap(subcommand)]
+        subcommand: ContextSubcommand,
+    },
+    /// Check connectivity, authentication, server compatibility and the application manifest, and suggest fixes
+    Doctor,
+    /// Check and evaluate Rib expressions
+    Rib {
+        #[clap(subcommand)]
+        subcommand: RibSubcommand,
+    },
     /// Start Rib REPL for a selected component
     Repl {
         #[command(flatten)]
@@ -540,14 +858,32 @@ pub enum GolemCliSubcommand {
         /// Optional component version to use, defaults to latest component version
         version: Option<u64>,
     },
+    /// Start an interactive console with a persistent component and worker selection, tab completion and history
+    Console,
+    /// Describe the CLI itself for tool integrations, use with --format json or yaml
+    Introspect {
+        #[clap(subcommand)]
+        subcommand: IntrospectSubcommand,
+    },
+    /// Start a language server on stdio, providing diagnostics, completion and hover docs for application manifests and desired state files
+    Lsp,
     /// Generate shell completion
     Completion {
         /// Selects shell
         shell: clap_complete::Shell,
     },
+    /// Print completion candidates for resource names, used by the generated completion scripts
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        /// Words of the command line after the command name, the last one being completed
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        words: Vec<String>,
+    },
 }
 
 pub mod shared_args {
+    use crate::command::parse_key_val;
+    use crate::config::ProfileName;
     use crate::model::app::AppBuildStep;
     use crate::model::{AccountId, PluginReference};
     use crate::model::{
@@ -555,6 +891,8 @@ pub mod shared_args {
     };
     use clap::Args;
     use golem_templates::model::GuestLanguage;
+    use std::collections::BTreeMap;
+    use std::num::NonZeroUsize;
 
     pub type ComponentTemplateName = String;
     pub type NewWorkerArgument = String;
@@ -585,6 +923,22 @@ pub mod shared_args {
         pub component_name: Option<ComponentName>,
     }
 
+    #[derive(Debug, Args)]
+    pub struct ProfilesArg {
+        /// Run the command against multiple profiles concurrently, e.g. --profiles staging,prod
+        #[arg(long, value_delimiter = ',', conflicts_with = "all_profiles")]
+        pub profiles: Vec<ProfileName>,
+        /// Run the command against all available profiles concurrently
+        #[arg(long)]
+        pub all_profiles: bool,
+    }
+
+    impl ProfilesArg {
+        pub fn is_multi_profile(&self) -> bool {
+            self.all_profiles || !self.profiles.is_empty()
+        }
+    }
+
     #[derive(Debug, Args)]
     pub struct ComponentOptionalComponentNames {
         // DO NOT ADD EMPTY LINES TO THE DOC COMMENT
@@ -614,20 +968,35 @@ pub mod shared_args {
         pub language: GuestLanguage,
     }
 
-    #[derive(Debug, Args)]
+    #[derive(Debug, Clone, Args)]
     pub struct ForceBuildArg {
-        /// When set to true will skip modification time based up-to-date checks, defaults to false
-        #[clap(long, default_value = "false")]
+        /// When set to true will skip content hash based up-to-date checks, defaults to false
+        #[clap(long, alias = "force-rebuild", default_value = "false")]
         pub force_build: bool,
     }
 
-    #[derive(Debug, Args)]
+    #[derive(Debug, Clone, Args)]
     pub struct BuildArgs {
         /// Select specific build step(s)
         #[clap(long, short)]
         pub step: Vec<AppBuildStep>,
         #[command(flatten)]
         pub force_build: ForceBuildArg,
+        /// Fail if generating RPC artifacts would change any file recorded in golem.lock
+        #[arg(long, default_value_t = false)]
+        pub frozen: bool,
+        /// Back up the files overwritten by generating RPC artifacts, restorable with app undo
+        #[arg(long, default_value_t = false)]
+        pub backup: bool,
+        /// Do not read from or write to the build cache configured in the manifest or profile
+        #[arg(long, default_value_t = false)]
+        pub no_cache: bool,
+        /// Number of components to build in parallel, defaults to 1
+        #[arg(long)]
+        pub jobs: Option<NonZeroUsize>,
+        /// Show the commands to be executed and the files to be written per build step, without building
+        #[arg(long, default_value_t = false)]
+        pub plan: bool,
     }
 
     #[derive(Debug, Args)]
@@ -668,6 +1037,10 @@ pub mod shared_args {
         #[clap(long, short, conflicts_with_all = ["update_workers", "redeploy_workers", "redeploy_http_api"]
         )]
         pub redeploy_all: bool,
+        // Only set from the manifest profile, the variables passed as arguments are handled by
+        // the global --var flag
+        #[clap(skip)]
+        pub http_api_vars: Vec<(String, String)>,
     }
 
     impl UpdateOrRedeployArgs {
@@ -677,6 +1050,7 @@ pub mod shared_args {
                 redeploy_workers: false,
                 redeploy_http_api: false,
                 redeploy_all: false,
+                http_api_vars: Vec::new(),
             }
         }
 
@@ -693,6 +1067,19 @@ pub mod shared_args {
                 || self.redeploy_all
                 || self.redeploy_http_api
         }
+
+        /// Profile variables, overridden by the ones set on the args
+        pub fn http_api_vars(
+            &self,
+            profile_args: &UpdateOrRedeployArgs,
+        ) -> BTreeMap<String, String> {
+            profile_args
+                .http_api_vars
+                .iter()
+                .chain(self.http_api_vars.iter())
+                .cloned()
+                .collect()
+        }
     }
 
     #[derive(Debug, Args)]
@@ -758,11 +1145,16 @@ pub mod shared_args {
 
 pub mod app {
     use crate::command::shared_args::{
-        AppOptionalComponentNames, BuildArgs, ForceBuildArg, UpdateOrRedeployArgs,
+        AppOptionalComponentNames, BuildArgs, ComponentOptionalComponentName, ForceBuildArg,
+        UpdateOrRedeployArgs,
     };
-    use crate::model::WorkerUpdateMode;
+    use crate::model::app::ClientLanguage;
+    use crate::model::ci::CiProvider;
+    use crate::model::dependency_graph::GraphFormat;
+    use crate::model::{ComponentName, WorkerUpdateMode};
     use clap::Subcommand;
-    use golem_templates::model::GuestLanguage;
+    use golem_templates::model::{GuestLanguage, PackageName};
+    use std::path::PathBuf;
 
     #[derive(Debug, Subcommand)]
     pub enum AppSubcommand {
@@ -779,6 +1171,12 @@ pub mod app {
             component_name: AppOptionalComponentNames,
             #[command(flatten)]
             build: BuildArgs,
+            /// Watch the component sources and the application manifests, and rebuild the affected components on changes
+            #[arg(long, short, default_value_t = false, conflicts_with = "plan")]
+            watch: bool,
+            /// Command to run after every successful build in watch mode
+            #[arg(long, requires = "watch")]
+            after_build: Option<String>,
         },
         /// Deploy all or selected components and HTTP APIs in the application, includes building
         Deploy {
@@ -789,6 +1187,39 @@ pub mod app {
             #[command(flatten)]
             update_or_redeploy: UpdateOrRedeployArgs,
         },
+        /// Validate, build and deploy the application in one step for CI pipelines, exits with 10 (validate), 11 (build), 12 (deploy components), 13 (update workers) or 14 (deploy HTTP APIs) on failure
+        Ci {
+            #[command(flatten)]
+            component_name: AppOptionalComponentNames,
+            #[command(flatten)]
+            force_build: ForceBuildArg,
+            #[command(flatten)]
+            update_or_redeploy: UpdateOrRedeployArgs,
+            /// Only validate, build and report the changes, without deploying anything
+            #[arg(long, default_value_t = false)]
+            dry_run: bool,
+            /// Write the JSON summary of the stages and changes to the given file
+            #[arg(long)]
+            summary_file: Option<PathBuf>,
+        },
+        /// Generate a CI workflow for building, testing and deploying the application
+        InitCi {
+            /// CI provider to generate the workflow for
+            #[arg(long)]
+            provider: CiProvider,
+            /// Custom minijinja template to render instead of the builtin one
+            #[arg(long, conflicts_with = "print_template")]
+            template: Option<PathBuf>,
+            /// Print the builtin template, which can be used as a base for a custom template
+            #[arg(long, default_value_t = false)]
+            print_template: bool,
+            /// Output file, defaults to .github/workflows/golem.yaml or .gitlab-ci.yml in the application root
+            #[arg(long)]
+            output: Option<PathBuf>,
+            /// Overwrite the output file if it already exists
+            #[arg(long, default_value_t = false)]
+            force: bool,
+        },
         /// Clean all components in the application or by selection
         Clean {
             #[command(flatten)]
@@ -815,6 +1246,96 @@ pub mod app {
             #[command(flatten)]
             component_name: AppOptionalComponentNames,
         },
+        /// Generate WASM RPC client bindings for all or selected components in the application
+        GenerateClient {
+            #[command(flatten)]
+            component_name: AppOptionalComponentNames,
+            /// Language of the generated client bindings
+            #[arg(long, default_value = "rust")]
+            language: ClientLanguage,
+            /// Output directory, bindings are generated into a subdirectory per component
+            #[arg(long)]
+            output: PathBuf,
+            /// Add the generated Rust client crates as members of the Cargo workspace containing the output directory, using workspace dependencies
+            #[arg(long, default_value_t = false)]
+            cargo_workspace: bool,
+        },
+        /// Show the component dependency graph of the application, highlighting dependency cycles
+        Graph {
+            /// Text format of the graph, use the global --format flag for JSON or YAML
+            #[arg(long, default_value = "dot")]
+            graph_format: GraphFormat,
+        },
+        /// Validate the application manifest, including templates, referenced paths and dependency cycles
+        Validate,
+        /// Fetch the WIT dependencies defined in the application manifest from git repositories (warg registries are not supported)
+        FetchWitDeps {
+            /// Fetch the requested revisions again and update the lockfile
+            #[arg(long, default_value_t = false)]
+            update: bool,
+        },
+        /// Fetch the stubs of the registry dependency targets defined in the application manifest
+        FetchStubs {
+            /// Resolve the versions again using the semver requirements and update the lockfile
+            #[arg(long, default_value_t = false)]
+            update: bool,
+        },
+        /// Publish the WIT of a component as a stub to an OCI registry, so other applications can use it as a WASM RPC dependency target without its sources
+        PublishStub {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+            /// OCI reference of the stub without tag, e.g. oci://ghcr.io/org/counter-stub
+            #[arg(long)]
+            registry: String,
+            /// Semantic version of the published stub, published versions cannot be changed
+            #[arg(long)]
+            version: semver::Version,
+        },
+        /// Copy a plain WIT package into the source WIT dependencies of a component, for sharing types between components without WASM RPC
+        AddWitDependency {
+            /// WIT directory of the package to add
+            source_wit_dir: PathBuf,
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentName,
+        },
+        /// Create a Rust test component which invokes every exported function of the target component through its WASM RPC client with default-valued arguments
+        NewTestComponent {
+            /// Target component of the test
+            #[arg(long = "for")]
+            target_component_name: ComponentName,
+            /// Name of the new test component in 'package:name' form, defaults to '<TARGET>-test'
+            #[arg(long)]
+            component_name: Option<PackageName>,
+        },
+        /// Build and deploy the components under test, then run the test components and the test cases declared in the manifest's tests section
+        Test {
+            #[command(flatten)]
+            component_name: AppOptionalComponentNames,
+            /// Name of the target worker invoked by the tests, the test workers use the same name. Test cases without a worker use '<WORKER_NAME>-<CASE>'
+            #[arg(long, default_value = "smoke-test")]
+            worker_name: String,
+            /// Only run the selected manifest test cases, can be used multiple times
+            #[arg(long = "case")]
+            case_names: Vec<String>,
+            /// Keep the workers of the test cases, instead of deleting them after the run
+            #[arg(long)]
+            keep_workers: bool,
+        },
+        /// Restore the files backed up by the last build with --backup
+        Undo,
+        /// Check the generated client WITs, client crates and copied WIT dependencies for manual edits made after generation
+        CheckGenerated,
+        /// Check that the types of the WASM RPC dependencies copied into the generated WIT of the callers are still compatible with the current WIT of the target components
+        CheckCompat,
+        /// Move the record, variant, enum and flags types defined identically in multiple components into a shared WIT package
+        ExtractSharedTypes {
+            /// Name of the shared types package, defaults to '<namespace>:shared-types' when all components use the same namespace
+            #[arg(long)]
+            package: Option<String>,
+            /// Only report the types to be moved, without changing any files
+            #[arg(long, default_value_t = false)]
+            dry_run: bool,
+        },
         /// Run custom command
         #[clap(external_subcommand)]
         CustomCommand(Vec<String>),
@@ -822,10 +1343,11 @@ pub mod app {
 }
 
 pub mod component {
+    use crate::app::template_repo::TemplateRepoSource;
     use crate::command::component::plugin::ComponentPluginSubcommand;
     use crate::command::shared_args::{
         BuildArgs, ComponentOptionalComponentName, ComponentOptionalComponentNames,
-        ComponentTemplateName, ForceBuildArg, UpdateOrRedeployArgs,
+        ComponentTemplateName, ForceBuildArg, ProfilesArg, UpdateOrRedeployArgs,
     };
     use crate::model::app::DependencyType;
     use crate::model::{ComponentName, WorkerUpdateMode};
@@ -842,6 +1364,9 @@ pub mod component {
             component_template: Option<ComponentTemplateName>,
             /// Name of the new component package in 'package:name' form
             component_name: Option<PackageName>,
+            /// Use templates from a git repository (URL or github:org/repo, optionally with #revision) or local directory instead of the built-in ones
+            #[arg(long)]
+            template_repo: Option<TemplateRepoSource>,
         },
         /// List or search component templates
         Templates {
@@ -855,6 +1380,11 @@ pub mod component {
             #[command(flatten)]
             build: BuildArgs,
         },
+        /// Link component(s) with their WASM dependencies and plugs, and show the composition graph
+        Compose {
+            #[command(flatten)]
+            component_name: ComponentOptionalComponentNames,
+        },
         /// Deploy component(s) and dependent HTTP APIs based on the current directory or by selection
         Deploy {
             #[command(flatten)]
@@ -891,6 +1421,8 @@ pub mod component {
         List {
             #[command(flatten)]
             component_name: ComponentOptionalComponentName,
+            #[command(flatten)]
+            profiles: ProfilesArg,
         },
         /// Get latest or selected version of deployed component metadata
         Get {
@@ -991,12 +1523,14 @@ pub mod worker {
     use crate::command::parse_cursor;
     use crate::command::parse_key_val;
     use crate::command::shared_args::{
-        ComponentOptionalComponentName, NewWorkerArgument, StreamArgs, WorkerFunctionArgument,
-        WorkerFunctionName, WorkerNameArg,
+        ComponentOptionalComponentName, NewWorkerArgument, ProfilesArg, StreamArgs,
+        WorkerFunctionArgument, WorkerFunctionName, WorkerNameArg,
     };
-    use crate::model::{IdempotencyKey, WorkerUpdateMode};
+    use crate::model::trace::TraceParent;
+    use crate::model::{IdempotencyKey, WorkerName, WorkerUpdateMode};
     use clap::Subcommand;
     use golem_client::model::ScanCursor;
+    use std::path::PathBuf;
 
     #[derive(Debug, Subcommand)]
     pub enum WorkerSubcommand {
@@ -1015,8 +1549,9 @@ pub mod worker {
         Invoke {
             #[command(flatten)]
             worker_name: WorkerNameArg,
-            /// Worker function name to invoke
-            function_name: WorkerFunctionName,
+            /// Worker function name to invoke, can be omitted when the worker is set in the profile context,
+            /// in which case the first argument is the function name
+            function_name: Option<WorkerFunctionName>,
             /// Worker function arguments in WAVE format
             arguments: Vec<WorkerFunctionArgument>,
             /// Enqueue invocation, and do not wait for it
@@ -1025,12 +1560,18 @@ pub mod worker {
             /// Set idempotency key for the call, use "-" for auto generated key
             #[clap(long, short)]
             idempotency_key: Option<IdempotencyKey>,
+            /// W3C trace context of the caller, the invocation is traced as its child span. When not set, a new trace is started.
+            #[clap(long)]
+            traceparent: Option<TraceParent>,
             #[clap(long, short)]
             /// Connect to the worker before invoke (the worker must already exist)
             /// and live stream its standard output, error and log channels
             stream: bool,
             #[command(flatten)]
             stream_args: StreamArgs,
+            /// Prompt for the missing arguments using an editor with WAVE value templates, bracket completion, validation and history per function
+            #[clap(long)]
+            interactive: bool,
         },
         /// Get worker metadata
         Get {
@@ -1057,7 +1598,7 @@ pub mod worker {
             /// Cursor can be used to get the next page of results, use the cursor returned
             /// in the previous response.
             /// The cursor has the format 'layer/position' where both layer and position are numbers.
-            #[arg(long, short, value_parser = parse_cursor)]
+            #[arg(long, short, value_parser = parse_cursor, conflicts_with_all = ["profiles", "all_profiles"])]
             scan_cursor: Option<ScanCursor>,
             /// The maximum the number of returned workers, returns all values is not specified.
             /// When multiple component is selected, then the limit it is applied separately
@@ -1066,6 +1607,8 @@ pub mod worker {
             /// When set to true it queries for most up-to-date status for each worker, default is false
             #[arg(long, default_value_t = false)]
             precise: bool,
+            #[command(flatten)]
+            profiles: ProfilesArg,
         },
         /// Connect to a worker and live stream its standard output, error and log channels
         Stream {
@@ -1132,6 +1675,46 @@ pub mod worker {
             /// Idempotency key of the invocation to be cancelled
             idempotency_key: IdempotencyKey,
         },
+        /// Export a worker's metadata, oplog and files into a portable worker archive
+        Export {
+            #[command(flatten)]
+            worker_name: WorkerNameArg,
+            /// Output archive, defaults to <WORKER_NAME>.golem-worker.gz in the current directory
+            #[arg(long, short)]
+            output: Option<PathBuf>,
+            /// Overwrite the output archive if it already exists
+            #[arg(long, default_value_t = false)]
+            force: bool,
+        },
+        /// Import a worker archive into the current environment by recreating the worker and replaying its invocations
+        Import {
+            /// Worker archive created by worker export
+            archive: PathBuf,
+            /// Name of the imported worker, defaults to the archived component and worker name
+            #[arg(long)]
+            worker: Option<WorkerName>,
+            /// Only create the worker with the archived arguments and environment, without replaying invocations
+            #[arg(long, default_value_t = false)]
+            no_replay: bool,
+        },
+        /// Replay a worker's last recorded invocations against a locally built component and report the result differences
+        ///
+        /// The invocations are replayed in a sandbox worker of a separate sandbox component, so the
+        /// original component and worker are not changed.
+        Replay {
+            #[command(flatten)]
+            worker_name: WorkerNameArg,
+            /// Component WASM to replay the invocations against
+            #[arg(long)]
+            against: PathBuf,
+            /// Number of the last recorded invocations to replay
+            #[arg(long, default_value_t = 10)]
+            last: usize,
+            /// Keep the sandbox worker after the replay, for further inspection. The sandbox
+            /// component is always kept, and updated by later replays.
+            #[arg(long, default_value_t = false)]
+            keep_sandbox: bool,
+        },
     }
 }
 
@@ -1174,9 +1757,14 @@ pub mod api {
 
     pub mod definition {
         use crate::command::shared_args::{ProjectOptionalFlagArg, UpdateOrRedeployArgs};
-        use crate::model::api::{ApiDefinitionId, ApiDefinitionVersion};
+        use crate::model::api::{
+            ApiDefinitionId, ApiDefinitionVersion, DEFAULT_CORS_ALLOW_HEADERS,
+            DEFAULT_CORS_ALLOW_METHODS, DEFAULT_CORS_ALLOW_ORIGIN,
+        };
         use crate::model::app::HttpApiDefinitionName;
+        use crate::model::gateway_import::GatewayConfigFormat;
         use clap::Subcommand;
+        use std::path::PathBuf;
 
         #[derive(Debug, Subcommand)]
         pub enum ApiDefinitionSubcommand {
@@ -1217,13 +1805,108 @@ pub mod api {
                 #[arg(long)]
                 version: ApiDefinitionVersion,
             },
+            /// Import routes from an HTTP gateway config as an API definition manifest, with placeholder bindings
+            Import {
+                /// Format of the gateway config
+                #[arg(long)]
+                format: GatewayConfigFormat,
+                /// Gateway config file
+                #[arg(value_hint = clap::ValueHint::FilePath)]
+                config_file: PathBuf,
+                /// Name of the API definition, defaults to the name of the config file
+                #[arg(long)]
+                name: Option<HttpApiDefinitionName>,
+                /// Version of the API definition
+                #[arg(long, default_value = "0.1.0")]
+                version: String,
+                /// Output manifest file, if not specified, the manifest is printed to the standard output
+                #[arg(long, value_hint = clap::ValueHint::FilePath)]
+                output: Option<PathBuf>,
+            },
+            /// Set the CORS policy of a route path in the application manifest, served as an OPTIONS preflight route
+            SetCors {
+                /// API definition name in the application manifest
+                http_api_definition_name: HttpApiDefinitionName,
+                /// Route path, e.g. /users/{id}
+                #[arg(long)]
+                route: String,
+                /// Value of the Access-Control-Allow-Origin header
+                #[arg(long, default_value = DEFAULT_CORS_ALLOW_ORIGIN)]
+                allow_origin: String,
+                /// Value of the Access-Control-Allow-Methods header
+                #[arg(long, default_value = DEFAULT_CORS_ALLOW_METHODS)]
+                allow_methods: String,
+                /// Value of the Access-Control-Allow-Headers header
+                #[arg(long, default_value = DEFAULT_CORS_ALLOW_HEADERS)]
+                allow_headers: String,
+                /// Value of the Access-Control-Expose-Headers header
+                #[arg(long)]
+                expose_headers: Option<String>,
+                /// Value of the Access-Control-Max-Age header, in seconds
+                #[arg(long)]
+                max_age: Option<u64>,
+                /// Value of the Access-Control-Allow-Credentials header
+                #[arg(long)]
+                allow_credentials: Option<bool>,
+            },
+            /// Remove the CORS policy of a route path from the application manifest
+            UnsetCors {
+                /// API definition name in the application manifest
+                http_api_definition_name: HttpApiDefinitionName,
+                /// Route path, e.g. /users/{id}
+                #[arg(long)]
+                route: String,
+            },
+            /// Preview API definitions with Swagger UI, served on a local HTTP server
+            Preview {
+                #[command(flatten)]
+                project: ProjectOptionalFlagArg,
+                /// API definition name from the application manifest, path of a manifest file, or a deployed API definition id
+                definition: String,
+                /// Version of the deployed API definition, required when previewing by API definition id
+                #[arg(long)]
+                version: Option<ApiDefinitionVersion>,
+                /// Local port of the preview server
+                #[arg(long, default_value_t = 9880)]
+                port: u16,
+            },
+            /// Lists all sites where an API definition is currently deployed
+            Deployments {
+                #[command(flatten)]
+                project: ProjectOptionalFlagArg,
+                /// API definition id
+                #[arg(short, long)]
+                id: ApiDefinitionId,
+                /// Version of the api definition, if not specified, deployments of all versions are listed
+                #[arg(long)]
+                version: Option<ApiDefinitionVersion>,
+            },
+            /// Clone an API definition version under a new version, and optionally deploy it in place of the old one
+            Promote {
+                #[command(flatten)]
+                project: ProjectOptionalFlagArg,
+                /// API definition id
+                #[arg(short, long)]
+                id: ApiDefinitionId,
+                /// Source version of the api definition
+                #[arg(long)]
+                from: ApiDefinitionVersion,
+                /// New version of the api definition
+                #[arg(long)]
+                to: ApiDefinitionVersion,
+                /// Deployment site, where the source version should be replaced with the new version
+                #[arg(long, value_name = "subdomain.host")]
+                deploy: Option<String>,
+            },
         }
     }
 
     pub mod deployment {
+        use crate::command::parse_key_val;
         use crate::command::shared_args::{ProjectOptionalFlagArg, UpdateOrRedeployArgs};
-        use crate::model::api::ApiDefinitionId;
+        use crate::model::api::{ApiDefinitionId, HttpRequestBody, HttpRouteSelector};
         use clap::Subcommand;
+        use std::path::PathBuf;
 
         #[derive(Debug, Subcommand)]
         pub enum ApiDeploymentSubcommand {
@@ -1249,7 +1932,7 @@ pub mod api {
                 /// API definition id
                 definition: Option<ApiDefinitionId>,
             },
-            /// Delete api deployment
+            /// Delete api deployment, requires typing the site name for confirmation unless --yes is used
             Delete {
                 #[command(flatten)]
                 project: ProjectOptionalFlagArg,
@@ -1257,6 +1940,40 @@ pub mod api {
                 #[arg(value_name = "subdomain.host")]
                 site: String,
             },
+            /// Converge API deployments to the state described in a deployments file
+            Apply {
+                #[command(flatten)]
+                project: ProjectOptionalFlagArg,
+                /// Path to the deployments YAML file
+                #[arg(value_hint = clap::ValueHint::FilePath)]
+                deployments_file: PathBuf,
+                /// Only show the planned changes, without applying them
+                #[arg(long)]
+                dry_run: bool,
+            },
+            /// Send a test request to a route of an API deployment and show the request and response
+            Test {
+                #[command(flatten)]
+                project: ProjectOptionalFlagArg,
+                /// Deployment site
+                #[arg(value_name = "subdomain.host")]
+                site: String,
+                /// Route to test, in the same form as defined in the API definition, e.g. "GET /users/{id}"
+                #[arg(long)]
+                route: HttpRouteSelector,
+                /// Route parameters (key-value pairs), parameters not used in the path are sent as query parameters
+                #[arg(long, value_parser = parse_key_val, value_name = "KEY=VAL")]
+                param: Vec<(String, String)>,
+                /// Request headers (key-value pairs)
+                #[arg(long, value_parser = parse_key_val, value_name = "KEY=VAL")]
+                header: Vec<(String, String)>,
+                /// Request body, use @<PATH> to read the body from a file
+                #[arg(long)]
+                body: Option<HttpRequestBody>,
+                /// URL scheme used for the request, defaults to "http" for the local profile, and "https" otherwise
+                #[arg(long)]
+                scheme: Option<String>,
+            },
         }
     }
 
@@ -1423,11 +2140,125 @@ pub mod plugin {
     }
 }
 
+pub mod sdk {
+    use crate::command::shared_args::ProjectOptionalFlagArg;
+    use crate::model::api::{ApiDefinitionId, ApiDefinitionVersion};
+    use crate::model::sdk::SdkLanguage;
+    use clap::Subcommand;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Subcommand)]
+    pub enum SdkSubcommand {
+        /// Generate a typed HTTP client library for a deployed API definition, based on its OpenAPI export and the WIT types of the bound component functions
+        Generate {
+            #[command(flatten)]
+            project: ProjectOptionalFlagArg,
+            /// API definition id
+            #[arg(long)]
+            api_definition: ApiDefinitionId,
+            /// Version of the API definition
+            #[arg(long)]
+            version: ApiDefinitionVersion,
+            /// Language of the generated client library
+            #[arg(long)]
+            language: SdkLanguage,
+            /// Target directory of the generated client library
+            #[arg(long)]
+            output: PathBuf,
+            /// Directory with custom templates, overriding the builtin templates with the same relative path, e.g. src/lib.rs
+            #[arg(long)]
+            template_dir: Option<PathBuf>,
+            /// Overwrite the existing files in the target directory
+            #[arg(long, default_value_t = false)]
+            force: bool,
+        },
+    }
+}
+
+pub mod events {
+    use crate::model::event::WorkerEventType;
+    use crate::model::ComponentName;
+    use clap::Subcommand;
+
+    #[derive(Debug, Subcommand)]
+    pub enum EventsSubcommand {
+        /// Print the worker events of the selected components until interrupted, as text or as JSON lines with --format json. Events are detected by polling the worker metadata.
+        Tail {
+            /// Component name, if not specified the components are selected based on the current application directory
+            #[arg(long)]
+            component: Option<ComponentName>,
+            /// Comma separated list of event types to print, defaults to all
+            #[arg(long, value_delimiter = ',')]
+            types: Vec<WorkerEventType>,
+            /// Polling interval in seconds
+            #[arg(long, default_value_t = 5)]
+            interval: u64,
+        },
+    }
+}
+
+pub mod export {
+    use crate::command::shared_args::ProjectOptionalFlagArg;
+    use crate::model::terraform::DEFAULT_TERRAFORM_PROVIDER_SOURCE;
+    use clap::Subcommand;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Subcommand)]
+    pub enum ExportSubcommand {
+        /// Print Terraform / OpenTofu resource definitions describing the components, API definitions and API deployments of the project, for adopting them into infrastructure as code
+        Terraform {
+            #[command(flatten)]
+            project: ProjectOptionalFlagArg,
+            /// Emit import blocks instead of resource definitions, the resource definitions can then be generated with terraform plan -generate-config-out=<FILE>
+            #[arg(long, default_value_t = false)]
+            import_blocks: bool,
+            /// Source address of the Golem Terraform provider
+            #[arg(long, default_value = DEFAULT_TERRAFORM_PROVIDER_SOURCE)]
+            provider_source: String,
+            /// Write the output to the given file instead of the standard output
+            #[arg(long)]
+            output: Option<PathBuf>,
+            /// Overwrite the output file if it already exists
+            #[arg(long, default_value_t = false)]
+            force: bool,
+        },
+    }
+}
+
+pub mod extension {
+    use clap::Subcommand;
+
+    #[derive(Debug, Subcommand)]
+    pub enum ExtensionSubcommand {
+        /// List the available extensions, from the config directory and from PATH
+        List,
+        /// Install an extension executable into the config directory
+        Install {
+            /// Local path or HTTP(S) URL of the extension executable
+            source: String,
+            /// Name of the extension, defaults to <NAME> when the source is named golem-<NAME>
+            #[arg(long)]
+            name: Option<String>,
+        },
+    }
+}
+
+pub mod introspect {
+    use clap::Subcommand;
+
+    #[derive(Debug, Subcommand)]
+    pub enum IntrospectSubcommand {
+        /// Print the full command tree with the args, their value types and help texts
+        Commands,
+    }
+}
+
 pub mod profile {
     use crate::command::profile::config::ProfileConfigSubcommand;
-    use crate::config::ProfileName;
+    use crate::config::{ProfileName, SecretStorage};
     use crate::model::Format;
     use clap::Subcommand;
+    use std::path::PathBuf;
     use url::Url;
     use uuid::Uuid;
 
@@ -1441,7 +2272,10 @@ pub mod profile {
             /// Switch to the profile after creation
             #[arg(long)]
             set_active: bool,
-            /// URL of Golem Component service
+            /// Base profile to inherit unset settings from (except auth)
+            #[arg(long)]
+            extends: Option<ProfileName>,
+            /// URL of Golem Component service, may include a base path, use http+unix://<percent-encoded socket path>/<base path> for Unix sockets
             #[arg(long)]
             component_url: Option<Url>,
             /// URL of Golem Worker service, if not provided defaults to component-url
@@ -1450,9 +2284,9 @@ pub mod profile {
             /// URL of Golem Cloud service, if not provided defaults to component-url
             #[arg(long)]
             cloud_url: Option<Url>,
-            /// Default output format
-            #[arg(long, default_value_t = Format::Text)]
-            default_format: Format,
+            /// Default output format, defaults to text, unless inherited from the base profile
+            #[arg(long)]
+            default_format: Option<Format>,
             /// Token to use for authenticating against Golem. If not provided an OAuth2 flow will be performed when authentication is needed for the first time.
             #[arg(long)]
             static_token: Option<Uuid>,
@@ -1464,6 +2298,9 @@ pub mod profile {
             /// This introduces significant vulnerabilities, and should only be used as a last resort.
             #[arg(long, hide = true)]
             allow_insecure: bool,
+            /// Storage of the profile's auth secrets
+            #[arg(long, default_value_t = SecretStorage::File)]
+            secret_storage: SecretStorage,
         },
         /// List global profiles
         List,
@@ -1482,6 +2319,36 @@ pub mod profile {
             /// Profile name to delete
             profile_name: ProfileName,
         },
+        /// Export a global profile to a sharable file
+        Export {
+            /// Name of profile to export, exports the active profile if not specified
+            profile_name: Option<ProfileName>,
+            /// Exclude auth secrets from the exported profile
+            #[arg(long)]
+            no_secrets: bool,
+            /// Output file, prints the exported profile if not specified
+            #[arg(long)]
+            output: Option<PathBuf>,
+        },
+        /// Import a global profile from a file created with 'profile export'
+        Import {
+            /// Path of the exported profile
+            file: PathBuf,
+            /// Name of the imported profile, defaults to the exported profile name
+            #[arg(long)]
+            name: Option<ProfileName>,
+            /// Switch to the profile after import
+            #[arg(long)]
+            set_active: bool,
+        },
+        /// Move auth secrets of global profiles between the config file and the OS keychain
+        MigrateSecrets {
+            /// Profile names to migrate, all profiles are migrated if not specified
+            profile_names: Vec<ProfileName>,
+            /// Target storage of the secrets
+            #[arg(long, default_value_t = SecretStorage::Keychain)]
+            to: SecretStorage,
+        },
         /// Configure global profile
         Config {
             /// Profile name
@@ -1491,9 +2358,31 @@ pub mod profile {
         },
     }
 
+    pub mod context {
+        use crate::command::parse_key_val;
+        use clap::Subcommand;
+
+        #[derive(Debug, Subcommand)]
+        pub enum ContextSubcommand {
+            /// Set the default component and worker, e.g.: set component=my-comp worker=my-worker, use an empty value to unset
+            Set {
+                /// Context entries, accepted keys: component, worker
+                #[arg(value_parser = parse_key_val, value_name = "KEY=VALUE", required = true)]
+                entries: Vec<(String, String)>,
+            },
+            /// Show the current context
+            Get,
+            /// Remove all context entries
+            Clear,
+        }
+    }
+
     pub mod config {
+        use crate::command::parse_duration;
+        use crate::config::ProfileName;
         use crate::model::Format;
         use clap::Subcommand;
+        use std::path::PathBuf;
 
         #[derive(Debug, Subcommand)]
         pub enum ProfileConfigSubcommand {
@@ -1502,10 +2391,84 @@ pub mod profile {
                 /// CLI output format
                 format: Format,
             },
+            /// Enable or disable HTTP debug logging for the requested profile, see --debug-http
+            SetDebugHttp {
+                /// Whether HTTP debug logging is enabled
+                #[arg(action = clap::ArgAction::Set)]
+                enabled: bool,
+            },
+            /// Set the HTTP timeouts and retries of the requested profile, unspecified settings are removed from the profile
+            SetHttp {
+                /// Timeout of service calls, including the retries, e.g.: 30s, 2m
+                #[arg(long, value_parser = parse_duration)]
+                timeout: Option<chrono::Duration>,
+                /// Connect timeout of service calls and invocations, e.g.: 500ms, 5s
+                #[arg(long, value_parser = parse_duration)]
+                connect_timeout: Option<chrono::Duration>,
+                /// Number of retries for failed idempotent requests and requests with idempotency keys
+                #[arg(long)]
+                retries: Option<u32>,
+                /// Base delay of the exponential backoff between retries, e.g.: 200ms, 1s
+                #[arg(long, value_parser = parse_duration)]
+                retry_backoff: Option<chrono::Duration>,
+            },
+            /// Set the TLS certificates of the requested profile, unspecified settings are removed from the profile
+            SetTls {
+                /// PEM file with additional CA certificates to trust, e.g. for a corporate proxy
+                #[arg(long)]
+                ca_certificate: Option<PathBuf>,
+                /// PEM file with the client certificate chain used for mutual TLS, requires --client-key
+                #[arg(long, requires = "client_key")]
+                client_certificate: Option<PathBuf>,
+                /// PEM file with the PKCS#8 private key of the client certificate, requires --client-certificate
+                #[arg(long, requires = "client_certificate")]
+                client_key: Option<PathBuf>,
+            },
+            /// Set the base profile of the requested profile, removes the base profile if not specified
+            SetExtends {
+                /// Base profile name
+                base_profile_name: Option<ProfileName>,
+            },
+            /// Set default flags for a command, removes the defaults if no flags are specified, e.g.: set-command-defaults "worker list" -- --format json
+            SetCommandDefaults {
+                /// Command path, without the binary name, e.g. "worker list"
+                command: String,
+                /// Default flags and their values
+                #[arg(last = true)]
+                flags: Vec<String>,
+            },
         }
     }
 }
 
+pub mod rib {
+    use crate::command::parse_key_val;
+    use crate::model::ComponentName;
+    use clap::Subcommand;
+
+    #[derive(Debug, Subcommand)]
+    pub enum RibSubcommand {
+        /// Parse and type-check a Rib expression against a component, and optionally evaluate it.
+        /// For an interactive session use the repl command.
+        Eval {
+            /// Rib expression, e.g. the response mapping of an API definition binding
+            expression: String,
+            /// Component to check the expression against, if not specified, the component is selected based on the current directory
+            #[arg(long)]
+            component: Option<ComponentName>,
+            /// Optional component version to use, defaults to latest component version
+            #[arg(long)]
+            version: Option<u64>,
+            /// Sample value for a global input variable (e.g. request) in WAVE format, can be used multiple times
+            #[arg(long = "input", value_parser = parse_key_val, value_name = "NAME=WAVE")]
+            inputs: Vec<(String, String)>,
+            /// Evaluate the expression using the provided inputs, expressions invoking workers are not supported
+            #[arg(long)]
+            evaluate: bool,
+        },
+    }
+}
+
 pub mod cloud {
     use crate::command::cloud::account::AccountSubcommand;
     use crate::command::cloud::project::ProjectSubcommand;
@@ -1532,26 +2495,40 @@ pub mod cloud {
     }
 
     pub mod token {
-        use crate::command::parse_instant;
+        use crate::command::shared_args::AccountIdOptionalArg;
+        use crate::command::{parse_duration, parse_instant};
         use crate::model::TokenId;
-        use chrono::{DateTime, Utc};
+        use chrono::{DateTime, Duration, Utc};
         use clap::Subcommand;
 
         #[derive(Debug, Subcommand)]
         pub enum TokenSubcommand {
             /// List tokens
-            List,
+            List {
+                #[command(flatten)]
+                account_id: AccountIdOptionalArg,
+            },
             /// Create new token
+            #[command(alias = "create")]
             New {
-                /// Expiration date of the generated token
-                #[arg(long, value_parser = parse_instant, default_value = "2100-01-01T00:00:00Z")]
-                expires_at: DateTime<Utc>,
+                #[command(flatten)]
+                account_id: AccountIdOptionalArg,
+                /// Expiration date of the generated token, defaults to 2100-01-01T00:00:00Z
+                #[arg(long, value_parser = parse_instant, conflicts_with = "expires_in")]
+                expires_at: Option<DateTime<Utc>>,
+                /// Expiration of the generated token relative to now, e.g.: 12h, 30d, 2w, 1y
+                #[arg(long, value_parser = parse_duration)]
+                expires_in: Option<Duration>,
             },
             /// Delete an existing token
             Delete {
+                #[command(flatten)]
+                account_id: AccountIdOptionalArg,
                 /// Token ID
                 token_id: TokenId,
             },
+            /// Show the token used by the selected profile, the secret is masked unless --show-sensitive is used
+            Current,
         }
     }
 
@@ -1629,24 +2606,29 @@ pub mod cloud {
 
         use crate::command::cloud::project::plugin::ProjectPluginSubcommand;
         use crate::command::cloud::project::policy::PolicySubcommand;
-        use crate::model::{ProjectName, ProjectPolicyId, ProjectReference};
+        use crate::model::project::ProjectRole;
+        use crate::model::{ProjectGrantId, ProjectName, ProjectPolicyId, ProjectReference};
         use clap::Subcommand;
         use golem_common::model::auth::ProjectPermission;
 
         #[derive(clap::Args, Debug)]
         #[group(required = true, multiple = false)]
         pub struct ProjectActionsOrPolicyId {
-            /// The sharing policy's identifier. If not provided, use `--action` instead
+            /// The sharing policy's identifier. If not provided, use `--action` or `--role` instead
             #[arg(long, required = true, group = "project_actions_or_policy")]
             pub policy_id: Option<ProjectPolicyId>,
-            /// A list of actions to be granted to the recipient account. If not provided, use `--policy-id` instead
+            /// A list of actions to be granted to the recipient account. If not provided, use `--policy-id` or `--role` instead
             #[arg(long, required = true, group = "project_actions_or_policy")]
             pub action: Option<Vec<ProjectPermission>>,
+            /// Predefined set of actions to be granted to the recipient account. If not provided, use `--policy-id` or `--action` instead
+            #[arg(long, required = true, group = "project_actions_or_policy")]
+            pub role: Option<ProjectRole>,
         }
 
         #[derive(Debug, Subcommand)]
         pub enum ProjectSubcommand {
             /// Create new project
+            #[command(alias = "create")]
             New {
                 /// The new project's name
                 project_name: ProjectName,
@@ -1659,8 +2641,26 @@ pub mod cloud {
                 /// Optionally filter projects by name
                 project_name: Option<ProjectName>,
             },
+            /// Delete a project
+            Delete {
+                // DO NOT ADD EMPTY LINES TO THE DOC COMMENT
+                /// Project to delete, accepted formats:
+                ///   - <PROJECT_NAME>
+                ///   - <ACCOUNT_EMAIL>/<PROJECT_NAME>
+                #[arg(verbatim_doc_comment)]
+                project_reference: ProjectReference,
+            },
             /// Gets the default project which is used when no explicit project is specified
             GetDefault,
+            /// Set the default project of the selected global profile, unsets it if no project is specified
+            SetDefault {
+                // DO NOT ADD EMPTY LINES TO THE DOC COMMENT
+                /// Project, accepted formats:
+                ///   - <PROJECT_NAME>
+                ///   - <ACCOUNT_EMAIL>/<PROJECT_NAME>
+                #[arg(verbatim_doc_comment)]
+                project_reference: Option<ProjectReference>,
+            },
             /// Share a project with another account
             Grant {
                 /// The project to be shared
@@ -1670,6 +2670,26 @@ pub mod cloud {
                 #[command(flatten)]
                 project_actions_or_policy_id: ProjectActionsOrPolicyId,
             },
+            /// List the accounts the project is shared with, and their granted actions
+            Members {
+                // DO NOT ADD EMPTY LINES TO THE DOC COMMENT
+                /// Project, accepted formats:
+                ///   - <PROJECT_NAME>
+                ///   - <ACCOUNT_EMAIL>/<PROJECT_NAME>
+                #[arg(verbatim_doc_comment)]
+                project_reference: ProjectReference,
+            },
+            /// Revoke a project grant
+            Revoke {
+                // DO NOT ADD EMPTY LINES TO THE DOC COMMENT
+                /// Project, accepted formats:
+                ///   - <PROJECT_NAME>
+                ///   - <ACCOUNT_EMAIL>/<PROJECT_NAME>
+                #[arg(verbatim_doc_comment)]
+                project_reference: ProjectReference,
+                /// Project grant ID, as shown by the members command
+                grant_id: ProjectGrantId,
+            },
             /// Manage project policies
             Policy {
                 #[command(subcommand)]
@@ -1765,6 +2785,7 @@ pub mod cloud {
 }
 
 pub mod server {
+    use crate::model::local_server::{ServerManifestTarget, ServerPersistence};
     use clap::{Args, Subcommand};
     use std::path::PathBuf;
 
@@ -1815,6 +2836,102 @@ pub mod server {
         /// Clean the local server data directory
         Clean,
     }
+
+    #[derive(Debug, Args, Default)]
+    pub struct ServerServiceArgs {
+        /// Port to serve the main API on, defaults to 9881
+        #[clap(long)]
+        pub router_port: Option<u16>,
+
+        /// Port to serve custom requests on, defaults to 9006
+        #[clap(long)]
+        pub custom_request_port: Option<u16>,
+
+        /// Tag of the Golem service images, defaults to latest
+        #[clap(long)]
+        pub image_tag: Option<String>,
+    }
+
+    impl ServerServiceArgs {
+        pub fn router_port(&self) -> u16 {
+            self.router_port.unwrap_or(9881)
+        }
+
+        pub fn custom_request_port(&self) -> u16 {
+            self.custom_request_port.unwrap_or(9006)
+        }
+
+        pub fn image_tag(&self) -> &str {
+            self.image_tag.as_deref().unwrap_or("latest")
+        }
+    }
+
+    #[derive(Debug, Args, Default)]
+    pub struct LocalServerArgs {
+        #[clap(flatten)]
+        pub services: ServerServiceArgs,
+
+        /// Path or HTTP(S) URL of a custom docker compose file, defaults to the builtin one. GOLEM_VERSION, GOLEM_ROUTER_PORT and GOLEM_CUSTOM_REQUEST_PORT are available as variables
+        #[clap(long)]
+        pub compose_file: Option<String>,
+    }
+
+    #[derive(Debug, Args)]
+    pub struct ServerManifestArgs {
+        /// Kind of the generated manifest
+        #[clap(long, value_enum, default_value_t = ServerManifestTarget::DockerCompose)]
+        pub target: ServerManifestTarget,
+
+        /// Number of worker executor replicas
+        #[clap(long, default_value_t = 1)]
+        pub worker_executors: u16,
+
+        /// Database used by the component and worker services
+        #[clap(long, value_enum, default_value_t = ServerPersistence::Sqlite)]
+        pub persistence: ServerPersistence,
+
+        /// Kubernetes namespace of the generated resources
+        #[clap(long, default_value = "golem")]
+        pub namespace: String,
+
+        /// Write the manifest to the given file instead of the standard output
+        #[clap(long)]
+        pub output: Option<PathBuf>,
+
+        #[clap(flatten)]
+        pub services: ServerServiceArgs,
+    }
+
+    #[derive(Debug, Subcommand)]
+    pub enum LocalServerSubcommand {
+        /// Run the local Golem services using docker compose in the foreground, and stream their logs
+        Run {
+            #[clap(flatten)]
+            args: LocalServerArgs,
+        },
+        /// Start the local Golem services using docker compose in the background
+        Start {
+            #[clap(flatten)]
+            args: LocalServerArgs,
+        },
+        /// Stop the local Golem services, keeping their data
+        Stop,
+        /// Show the state of the local Golem services
+        Status,
+        /// Show the logs of the local Golem services
+        Logs {
+            /// Follow the log output
+            #[clap(long, short)]
+            follow: bool,
+        },
+        /// Stop the local Golem services and delete their data
+        Clean,
+        /// Generate docker compose or Kubernetes manifests for self-hosting the Golem services
+        Manifest {
+            #[clap(flatten)]
+            args: ServerManifestArgs,
+        },
+    }
 }
 
 pub fn builtin_app_subcommands() -> BTreeSet<String> {
@@ -1851,6 +2968,174 @@ pub fn help_target_to_command(target: ShowClapHelpTarget) -> Command {
     command.clone()
 }
 
+/// Inserts the default flags of the commands matching the positional args right after
+/// the matched subcommand names. More specific commands take precedence, and flags which
+/// are already present (using any of their names) are skipped, so they can be overridden
+/// on the command line.
+fn apply_command_defaults(
+    command: &Command,
+    mut args: Vec<OsString>,
+    positional_args: &[String],
+    command_defaults: &BTreeMap<String, Vec<String>>,
+) -> Vec<OsString> {
+    let mut command = command.clone();
+    command.build();
+
+    let matching_defaults = command_defaults
+        .iter()
+        .map(|(command_path, flags)| (command_path.split_whitespace().collect::<Vec<_>>(), flags))
+        .filter(|(command_path, _)| {
+            !command_path.is_empty()
+                && command_path.len() <= positional_args.len()
+                && command_path
+                    .iter()
+                    .zip(positional_args)
+                    .all(|(name, arg)| name == arg)
+        })
+        .sorted_by_key(|(command_path, _)| std::cmp::Reverse(command_path.len()))
+        .collect::<Vec<_>>();
+
+    let Some((longest_command_path, _)) = matching_defaults.first() else {
+        return args;
+    };
+
+    // Subcommand of the most specific match, used for looking up all the names of the flags
+    let mut subcommand = &command;
+    for name in longest_command_path {
+        match subcommand.find_subcommand(name) {
+            Some(found) => subcommand = found,
+            None => break,
+        }
+    }
+
+    // Position right after the last subcommand name of the most specific match
+    let mut insert_idx = 1;
+    {
+        let mut command_path = longest_command_path.iter().peekable();
+        for (idx, arg) in args.iter().enumerate().skip(1) {
+            if arg.to_str() == Some("--") {
+                break;
+            }
+            if command_path
+                .peek()
+                .is_some_and(|name| arg.to_str() == Some(**name))
+            {
+                command_path.next();
+                insert_idx = idx + 1;
+            }
+        }
+    }
+
+    let flag_names = |flag: &str| -> Vec<String> {
+        let flag = flag.split_once('=').map(|(flag, _)| flag).unwrap_or(flag);
+        subcommand
+            .get_arguments()
+            .find(|arg| {
+                arg.get_long_and_visible_aliases()
+                    .unwrap_or_default()
+                    .iter()
+                    .any(|long| flag == format!("--{long}"))
+                    || arg
+                        .get_short_and_visible_aliases()
+                        .unwrap_or_default()
+                        .iter()
+                        .any(|short| flag == format!("-{short}"))
+            })
+            .map(|arg| {
+                arg.get_long_and_visible_aliases()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|long| format!("--{long}"))
+                    .chain(
+                        arg.get_short_and_visible_aliases()
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|short| format!("-{short}")),
+                    )
+                    .collect()
+            })
+            .unwrap_or_else(|| vec![flag.to_string()])
+    };
+
+    let takes_value = |short: char| {
+        subcommand
+            .get_arguments()
+            .find(|arg| {
+                arg.get_short_and_visible_aliases()
+                    .unwrap_or_default()
+                    .contains(&short)
+            })
+            .is_some_and(|arg| arg.get_action().takes_values())
+    };
+
+    // Short flags of a possibly combined short flag arg, e.g. "-y", "-yl" or "-fjson"
+    let short_flags = |arg: &str| -> Vec<char> {
+        let mut shorts = Vec::new();
+        if arg.starts_with("--") || is_negative_number(arg) {
+            return shorts;
+        }
+        if let Some(cluster) = arg.strip_prefix('-') {
+            for short in cluster.chars().take_while(|c| *c != '=') {
+                shorts.push(short);
+                // The rest of the arg is the value of the flag
+                if takes_value(short) {
+                    break;
+                }
+            }
+        }
+        shorts
+    };
+
+    let is_present = |args: &[OsString], names: &[String]| {
+        args.iter()
+            .filter_map(|arg| arg.to_str())
+            .take_while(|arg| *arg != "--")
+            .any(|arg| {
+                names.iter().any(|name| {
+                    if arg == name || arg.starts_with(&format!("{name}=")) {
+                        return true;
+                    }
+                    let mut short = name.strip_prefix('-').unwrap_or_default().chars();
+                    match (short.next(), short.next()) {
+                        (Some(short), None) if short != '-' => short_flags(arg).contains(&short),
+                        _ => false,
+                    }
+                })
+            })
+    };
+
+    for (_, flags) in matching_defaults {
+        // Group the flags with their values, negative numbers are values too
+        let mut groups = Vec::<Vec<&String>>::new();
+        for flag in flags {
+            match groups.last_mut() {
+                Some(group) if !flag.starts_with('-') || is_negative_number(flag) => {
+                    group.push(flag)
+                }
+                _ => groups.push(vec![flag]),
+            }
+        }
+
+        for group in groups {
+            if is_present(&args, &flag_names(group[0])) {
+                continue;
+            }
+            for arg in group {
+                args.insert(insert_idx, arg.into());
+                insert_idx += 1;
+            }
+        }
+    }
+
+    args
+}
+
+fn is_negative_number(arg: &str) -> bool {
+    arg.strip_prefix('-').is_some_and(|number| {
+        number.starts_with(|c: char| c.is_ascii_digit()) && number.parse::<f64>().is_ok()
+    })
+}
+
 fn parse_key_val(key_and_val: &str) -> anyhow::Result<(String, String)> {
     let pos = key_and_val.find('=').ok_or_else(|| {
         anyhow!(
@@ -1887,17 +3172,50 @@ fn parse_instant(
     }
 }
 
+fn parse_duration(
+    s: &str,
+) -> Result<chrono::Duration, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let unit_start = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("Missing time unit in duration: {s}"))?;
+    let (amount, unit) = s.split_at(unit_start);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("Invalid amount in duration: {s}"))?;
+
+    let duration = match unit {
+        "ms" => chrono::Duration::try_milliseconds(amount),
+        "s" => chrono::Duration::try_seconds(amount),
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        "y" => amount.checked_mul(365).and_then(chrono::Duration::try_days),
+        _ => {
+            return Err(format!(
+                "Unknown time unit in duration: {s}, expected one of: ms, s, m, h, d, w, y"
+            )
+            .into())
+        }
+    };
+
+    duration.ok_or_else(|| format!("Duration is out of range: {s}").into())
+}
+
 #[cfg(test)]
 mod test {
     use crate::command::{
-        builtin_app_subcommands, help_target_to_subcommand_names, GolemCliCommand,
+        apply_command_defaults, builtin_app_subcommands, help_target_to_subcommand_names,
+        parse_duration, GolemCliCommand, GolemCliGlobalFlags,
     };
     use crate::error::ShowClapHelpTarget;
-    use assert2::assert;
+    use crate::model::Format;
+    use assert2::{assert, let_assert};
     use clap::builder::StyledStr;
-    use clap::{Command, CommandFactory};
+    use clap::{Arg, ArgAction, Command, CommandFactory};
     use itertools::Itertools;
     use std::collections::{BTreeMap, BTreeSet};
+    use std::ffi::OsString;
     use strum::IntoEnumIterator;
     use test_r::test;
 
@@ -2131,4 +3449,163 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn parse_duration_units() {
+        assert!(parse_duration("250ms").unwrap() == chrono::Duration::milliseconds(250));
+        assert!(parse_duration("45s").unwrap() == chrono::Duration::seconds(45));
+        assert!(parse_duration("12h").unwrap() == chrono::Duration::hours(12));
+        assert!(parse_duration("30d").unwrap() == chrono::Duration::days(30));
+        assert!(parse_duration("2w").unwrap() == chrono::Duration::weeks(2));
+        assert!(parse_duration("1y").unwrap() == chrono::Duration::days(365));
+        assert!(parse_duration("30").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("30x").is_err());
+    }
+
+    #[test]
+    fn command_defaults_are_inserted_after_subcommands_and_overridable() {
+        let command = GolemCliCommand::command();
+        let command_defaults = BTreeMap::from([
+            (
+                "worker list".to_string(),
+                vec!["--format".to_string(), "json".to_string()],
+            ),
+            (
+                "worker".to_string(),
+                vec![
+                    "--format".to_string(),
+                    "yaml".to_string(),
+                    "--project".to_string(),
+                    "p1".to_string(),
+                ],
+            ),
+        ]);
+
+        let apply = |args: &[&str]| {
+            let args = args.iter().map(OsString::from).collect::<Vec<_>>();
+            let positional_args = args
+                .iter()
+                .skip(1)
+                .map(|arg| arg.to_string_lossy().to_string())
+                .filter(|arg| !arg.starts_with('-'))
+                .collect::<Vec<_>>();
+            apply_command_defaults(&command, args, &positional_args, &command_defaults)
+                .into_iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(
+            apply(&["golem", "worker", "list"])
+                == vec![
+                    "golem",
+                    "worker",
+                    "list",
+                    "--format",
+                    "json",
+                    "--project",
+                    "p1"
+                ]
+        );
+        assert!(
+            apply(&["golem", "worker", "list", "-f", "text"])
+                == vec!["golem", "worker", "list", "--project", "p1", "-f", "text"]
+        );
+        assert!(
+            apply(&["golem", "worker", "list", "--project=p2"])
+                == vec![
+                    "golem",
+                    "worker",
+                    "list",
+                    "--format",
+                    "json",
+                    "--project=p2"
+                ]
+        );
+        assert!(apply(&["golem", "component", "list"]) == vec!["golem", "component", "list"]);
+    }
+
+    #[test]
+    fn command_defaults_with_negative_numbers_and_combined_short_flags() {
+        let command = Command::new("golem")
+            .arg(
+                Arg::new("yes")
+                    .long("yes")
+                    .short('y')
+                    .action(ArgAction::SetTrue)
+                    .global(true),
+            )
+            .arg(Arg::new("format").long("format").short('f').global(true))
+            .subcommand(
+                Command::new("tail").arg(
+                    Arg::new("offset")
+                        .long("offset")
+                        .short('o')
+                        .allow_negative_numbers(true),
+                ),
+            );
+        let command_defaults = BTreeMap::from([(
+            "tail".to_string(),
+            vec![
+                "--offset".to_string(),
+                "-10".to_string(),
+                "--format".to_string(),
+                "json".to_string(),
+                "--yes".to_string(),
+            ],
+        )]);
+
+        let apply = |args: &[&str]| {
+            let args = args.iter().map(OsString::from).collect::<Vec<_>>();
+            apply_command_defaults(&command, args, &["tail".to_string()], &command_defaults)
+                .into_iter()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(
+            apply(&["golem", "tail"])
+                == vec!["golem", "tail", "--offset", "-10", "--format", "json", "--yes"]
+        );
+        assert!(
+            apply(&["golem", "tail", "-o", "-5"])
+                == vec!["golem", "tail", "--format", "json", "--yes", "-o", "-5"]
+        );
+        assert!(
+            apply(&["golem", "tail", "-yfyaml"])
+                == vec!["golem", "tail", "--offset", "-10", "-yfyaml"]
+        );
+        assert!(
+            apply(&["golem", "tail", "-fy"])
+                == vec!["golem", "tail", "--offset", "-10", "--yes", "-fy"]
+        );
+        assert!(
+            apply(&["golem", "tail", "--", "-y"])
+                == vec![
+                    "golem", "tail", "--offset", "-10", "--format", "json", "--yes", "--", "-y"
+                ]
+        );
+    }
+
+    #[test]
+    fn env_var_parse_errors_name_the_variable() {
+        let mut flags = GolemCliGlobalFlags::default();
+        assert!(flags.check_env_overrides().is_ok());
+
+        let format: Option<Format> = flags.parse_env_var("GOLEM_FORMAT", "json");
+        assert!(format == Some(Format::Json));
+
+        let retries: Option<u32> = flags.parse_env_var("GOLEM_HTTP_RETRIES", "many");
+        assert!(retries.is_none());
+        let token: Option<uuid::Uuid> = flags.parse_env_var("GOLEM_TOKEN", "not-a-uuid");
+        assert!(token.is_none());
+
+        let_assert!(Err(error) = flags.check_env_overrides());
+        let error = error.to_string();
+        assert!(error.contains("GOLEM_HTTP_RETRIES"));
+        assert!(error.contains("\"many\""));
+        assert!(error.contains("GOLEM_TOKEN"));
+        assert!(!error.contains("GOLEM_FORMAT"));
+    }
 }
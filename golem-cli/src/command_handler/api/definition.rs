@@ -16,30 +16,44 @@ use crate::app::yaml_edit::AppYamlEditor;
 use crate::command::api::definition::ApiDefinitionSubcommand;
 use crate::command::shared_args::{ProjectOptionalFlagArg, UpdateOrRedeployArgs};
 use crate::command_handler::Handlers;
+use crate::command_name;
 use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
 use crate::error::NonSuccessfulExit;
+use crate::fs;
 use crate::log::{
     log_action, log_skipping_up_to_date, log_warn_action, logln, LogColorize, LogIndent,
 };
-use crate::model::api::{ApiDefinitionId, ApiDefinitionVersion, HttpApiDeployMode};
+use crate::model::api::{ApiDefinitionId, ApiDefinitionVersion, ApiDeployment, HttpApiDeployMode};
 use crate::model::app::{
     ApplicationComponentSelectMode, DynamicHelpSections, HttpApiDefinitionName, WithSource,
 };
+use crate::model::app_raw;
 use crate::model::app_raw::HttpApiDefinition;
+use crate::model::ci::{CiChange, CiHttpApiDefinitionChange};
 use crate::model::component::Component;
 use crate::model::deploy_diff::api_definition::DiffableHttpApiDefinition;
+use crate::model::gateway_import::{import_routes, GatewayConfigFormat, BINDING_PLACEHOLDER};
+use crate::model::openapi::{swagger_ui_html, to_openapi_spec};
+use crate::model::template::{template_env, Template};
 use crate::model::text::api_definition::{
     ApiDefinitionGetView, ApiDefinitionNewView, ApiDefinitionUpdateView,
 };
 use crate::model::text::fmt::{log_deploy_diff, log_error, log_warn};
 use crate::model::{ComponentName, ProjectRefAndId};
-use anyhow::{bail, Context as AnyhowContext};
-use golem_client::api::ApiDefinitionClient;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use axum::extract::Path as AxumPath;
+use axum::http::{header, StatusCode};
+use axum::response::{Html, IntoResponse};
+use axum::routing::get;
+use axum::Router;
+use golem_client::api::{ApiDefinitionClient, ApiDeploymentClient};
 use golem_client::model::{HttpApiDefinitionRequest, HttpApiDefinitionResponseData};
+use heck::ToKebabCase;
 use itertools::Itertools;
 use serde::de::DeserializeOwned;
-use std::collections::{BTreeMap, BTreeSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 pub struct ApiDefinitionCommandHandler {
@@ -71,6 +85,62 @@ impl ApiDefinitionCommandHandler {
                 version,
             } => self.cmd_delete(project, id, version).await,
             ApiDefinitionSubcommand::List { project, id } => self.cmd_list(project, id).await,
+            ApiDefinitionSubcommand::Import {
+                format,
+                config_file,
+                name,
+                version,
+                output,
+            } => {
+                self.cmd_import(format, config_file, name, version, output)
+                    .await
+            }
+            ApiDefinitionSubcommand::SetCors {
+                http_api_definition_name,
+                route,
+                allow_origin,
+                allow_methods,
+                allow_headers,
+                expose_headers,
+                max_age,
+                allow_credentials,
+            } => {
+                self.cmd_set_cors(
+                    http_api_definition_name,
+                    route,
+                    HttpCorsPolicy {
+                        allow_origin,
+                        allow_methods,
+                        allow_headers,
+                        expose_headers,
+                        max_age,
+                        allow_credentials,
+                    },
+                )
+                .await
+            }
+            ApiDefinitionSubcommand::UnsetCors {
+                http_api_definition_name,
+                route,
+            } => self.cmd_unset_cors(http_api_definition_name, route).await,
+            ApiDefinitionSubcommand::Preview {
+                project,
+                definition,
+                version,
+                port,
+            } => self.cmd_preview(project, definition, version, port).await,
+            ApiDefinitionSubcommand::Deployments {
+                project,
+                id,
+                version,
+            } => self.cmd_deployments(project, id, version).await,
+            ApiDefinitionSubcommand::Promote {
+                project,
+                id,
+                from,
+                to,
+                deploy,
+            } => self.cmd_promote(project, id, from, to, deploy).await,
         }
     }
 
@@ -86,23 +156,7 @@ impl ApiDefinitionCommandHandler {
             .await?;
 
         if let Some(name) = name.as_ref() {
-            let app_ctx = self.ctx.app_context_lock().await;
-            let app_ctx = app_ctx.some_or_err()?;
-            if !app_ctx
-                .application
-                .http_api_definitions()
-                .keys()
-                .contains(name)
-            {
-                logln("");
-                log_error(format!(
-                    "HTTP API definition {} not found in the application manifest",
-                    name.as_str().log_color_highlight()
-                ));
-                logln("");
-                app_ctx.log_dynamic_help(&DynamicHelpSections::show_api_definitions())?;
-                bail!(NonSuccessfulExit)
-            }
+            self.check_manifest_api_definition_exists(name).await?;
         }
 
         let api_def_filter = name.as_ref().into_iter().cloned().collect::<BTreeSet<_>>();
@@ -223,33 +277,506 @@ impl ApiDefinitionCommandHandler {
             .opt_select_project(project.project.as_ref())
             .await?;
 
+        self.delete_api_definition(project.as_ref(), &api_def_id.0, &version.0)
+            .await?;
+
+        log_warn_action(
+            "Deleted",
+            format!(
+                "API definition: {}/{}",
+                api_def_id.0.log_color_highlight(),
+                version.0.log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_import(
+        &self,
+        format: GatewayConfigFormat,
+        config_file: PathBuf,
+        name: Option<HttpApiDefinitionName>,
+        version: String,
+        output: Option<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let name = match name {
+            Some(name) => name,
+            None => config_file
+                .file_stem()
+                .map(|stem| HttpApiDefinitionName::from(stem.to_string_lossy().to_kebab_case()))
+                .ok_or_else(|| anyhow!("Failed to derive API definition name from config file"))?,
+        };
+
+        if let Some(output) = &output {
+            if output.exists() {
+                log_error(format!(
+                    "Output file {} already exists",
+                    output.log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        log_action(
+            "Importing",
+            format!(
+                "HTTP API definition {} from {}",
+                name.as_str().log_color_highlight(),
+                config_file.log_color_highlight()
+            ),
+        );
+
+        let imported = import_routes(format, &fs::read_to_string(&config_file)?)?;
+
+        {
+            let _indent = LogIndent::new();
+            for warning in &imported.warnings {
+                log_warn(warning);
+            }
+        }
+
+        if imported.routes.is_empty() {
+            log_error("No routes found in the gateway config");
+            bail!(NonSuccessfulExit);
+        }
+
+        let application = app_raw::Application {
+            http_api: Some(app_raw::HttpApi {
+                definitions: HashMap::from([(
+                    name.to_string(),
+                    imported.to_http_api_definition(&version),
+                )]),
+                deployments: HashMap::new(),
+            }),
+            ..Default::default()
+        };
+        let manifest = application.to_yaml_string();
+
+        match &output {
+            Some(output) => {
+                fs::write_str(output, &manifest)?;
+                log_action(
+                    "Saved",
+                    format!(
+                        "API definition manifest to {}",
+                        output.log_color_highlight()
+                    ),
+                );
+            }
+            None => {
+                println!("{manifest}");
+            }
+        }
+
+        log_warn(format!(
+            "Route bindings are generated with {} placeholders, bind them to components before deploying",
+            BINDING_PLACEHOLDER.log_color_highlight()
+        ));
+
+        Ok(())
+    }
+
+    async fn cmd_set_cors(
+        &self,
+        name: HttpApiDefinitionName,
+        route: String,
+        cors_policy: HttpCorsPolicy,
+    ) -> anyhow::Result<()> {
+        self.check_manifest_api_definition_exists(&name).await?;
+
+        let preflight_route = app_raw::HttpApiDefinitionRoute {
+            method: "OPTIONS".to_string(),
+            path: route.clone(),
+            security: None,
+            binding: app_raw::HttpApiDefinitionBinding {
+                type_: Some(app_raw::HttpApiDefinitionBindingType::CorsPreflight),
+                component_name: None,
+                component_version: None,
+                idempotency_key: None,
+                invocation_context: None,
+                response: Some(cors_policy.to_rib()?),
+            },
+        };
+
+        let inserted = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+
+            let mut editor = AppYamlEditor::new(&app_ctx.application);
+            let inserted =
+                editor.insert_or_replace_api_definition_route(&name, &preflight_route)?;
+            editor.update_documents()?;
+            inserted
+        };
+
+        log_action(
+            if inserted { "Added" } else { "Updated" },
+            format!(
+                "CORS policy for route {} in HTTP API definition {}",
+                route.log_color_highlight(),
+                name.as_str().log_color_highlight()
+            ),
+        );
+        log_deploy_hint(&name);
+
+        Ok(())
+    }
+
+    async fn cmd_unset_cors(
+        &self,
+        name: HttpApiDefinitionName,
+        route: String,
+    ) -> anyhow::Result<()> {
+        self.check_manifest_api_definition_exists(&name).await?;
+
+        let is_cors_route = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            app_ctx
+                .application
+                .http_api_definitions()
+                .get(&name)
+                .map(|definition| {
+                    definition.value.routes.iter().any(|r| {
+                        r.method.eq_ignore_ascii_case("OPTIONS")
+                            && r.path == route
+                            && r.binding.type_
+                                == Some(app_raw::HttpApiDefinitionBindingType::CorsPreflight)
+                    })
+                })
+                .unwrap_or(false)
+        };
+
+        if !is_cors_route {
+            log_error(format!(
+                "No CORS policy found for route {} in HTTP API definition {}",
+                route.log_color_highlight(),
+                name.as_str().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+
+            let mut editor = AppYamlEditor::new(&app_ctx.application);
+            editor.remove_api_definition_route(&name, "OPTIONS", &route)?;
+            editor.update_documents()?;
+        }
+
+        log_warn_action(
+            "Removed",
+            format!(
+                "CORS policy for route {} in HTTP API definition {}",
+                route.log_color_highlight(),
+                name.as_str().log_color_highlight()
+            ),
+        );
+        log_deploy_hint(&name);
+
+        Ok(())
+    }
+
+    async fn cmd_preview(
+        &self,
+        project: ProjectOptionalFlagArg,
+        definition: String,
+        version: Option<ApiDefinitionVersion>,
+        port: u16,
+    ) -> anyhow::Result<()> {
+        let api_definitions = if Path::new(&definition).is_file() {
+            let app = app_raw::ApplicationWithSource::from_yaml_file(PathBuf::from(&definition))?;
+            let definitions = app
+                .application
+                .http_api
+                .map(|http_api| http_api.definitions)
+                .unwrap_or_default();
+            if definitions.is_empty() {
+                log_error(format!(
+                    "No HTTP API definitions found in {}",
+                    definition.log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+            definitions
+                .into_iter()
+                .sorted_by(|(a, _), (b, _)| a.cmp(b))
+                .map(|(name, api_definition)| {
+                    DiffableHttpApiDefinition::from_manifest(
+                        None,
+                        &HttpApiDefinitionName::from(name),
+                        &api_definition,
+                        &BTreeMap::new(),
+                    )
+                    .map(|api_definition| api_definition.0)
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        } else if let Some(version) = version {
+            let project = self
+                .ctx
+                .cloud_project_handler()
+                .opt_select_project(project.project.as_ref())
+                .await?;
+
+            match self
+                .api_definition(project.as_ref(), &definition, &version.0)
+                .await?
+            {
+                Some(api_definition) => {
+                    vec![DiffableHttpApiDefinition::from_server(api_definition)?.0]
+                }
+                None => {
+                    log_error(format!(
+                        "API definition {}/{} not found",
+                        definition.log_color_highlight(),
+                        version.0.log_color_highlight()
+                    ));
+                    bail!(NonSuccessfulExit);
+                }
+            }
+        } else {
+            let name = HttpApiDefinitionName::from(definition.as_str());
+            self.check_manifest_api_definition_exists(&name).await?;
+            let api_definition = {
+                let app_ctx = self.ctx.app_context_lock().await;
+                let app_ctx = app_ctx.some_or_err()?;
+                app_ctx
+                    .application
+                    .http_api_definitions()
+                    .get(&name)
+                    .unwrap()
+                    .value
+                    .clone()
+            };
+            vec![
+                DiffableHttpApiDefinition::from_manifest(
+                    None,
+                    &name,
+                    &api_definition,
+                    &BTreeMap::new(),
+                )?
+                .0,
+            ]
+        };
+
+        let specs = api_definitions
+            .iter()
+            .map(|api_definition| {
+                (
+                    format!("{}-{}.json", api_definition.id, api_definition.version),
+                    to_openapi_spec(api_definition).to_string(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        let spec_urls = api_definitions
+            .iter()
+            .map(|api_definition| {
+                (
+                    format!("{}/{}", api_definition.id, api_definition.version),
+                    format!(
+                        "/openapi/{}-{}.json",
+                        api_definition.id, api_definition.version
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        serve_api_definition_preview(port, swagger_ui_html(&spec_urls), specs).await
+    }
+
+    async fn cmd_deployments(
+        &self,
+        project: ProjectOptionalFlagArg,
+        api_def_id: ApiDefinitionId,
+        version: Option<ApiDefinitionVersion>,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(project.project.as_ref())
+            .await?;
+
         let clients = self.ctx.golem_clients().await?;
 
-        clients
-            .api_definition
-            .delete_definition(
+        let deployments = clients
+            .api_deployment
+            .list_deployments(
                 &self
                     .ctx
                     .cloud_project_handler()
                     .selected_project_id_or_default(project.as_ref())
                     .await?
                     .0,
-                &api_def_id.0,
-                &version.0,
+                Some(api_def_id.0.as_str()),
             )
             .await
-            .map_service_error()?;
+            .map_service_error()?
+            .into_iter()
+            .map(ApiDeployment::from)
+            .filter(|deployment| {
+                deployment.api_definitions.iter().any(|def| {
+                    def.id == api_def_id.0
+                        && version
+                            .as_ref()
+                            .map(|version| def.version == version.0)
+                            .unwrap_or(true)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if deployments.is_empty() {
+            log_warn(format!(
+                "API definition {} is not deployed to any site",
+                match &version {
+                    Some(version) => format!("{}@{}", api_def_id.0, version.0),
+                    None => api_def_id.0.clone(),
+                }
+                .log_color_highlight()
+            ));
+        }
 
-        log_warn_action(
-            "Deleted",
+        self.ctx.log_handler().log_view(&deployments);
+
+        Ok(())
+    }
+
+    async fn cmd_promote(
+        &self,
+        project: ProjectOptionalFlagArg,
+        api_def_id: ApiDefinitionId,
+        from_version: ApiDefinitionVersion,
+        to_version: ApiDefinitionVersion,
+        deploy_site: Option<String>,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(project.project.as_ref())
+            .await?;
+
+        let Some(source_api_definition) = self
+            .api_definition(project.as_ref(), &api_def_id.0, &from_version.0)
+            .await?
+        else {
+            log_error(format!(
+                "API definition {}@{} not found",
+                api_def_id.0.log_color_highlight(),
+                from_version.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit)
+        };
+
+        if self
+            .api_definition(project.as_ref(), &api_def_id.0, &to_version.0)
+            .await?
+            .is_some()
+        {
+            log_error(format!(
+                "API definition {}@{} already exists, please select a new version",
+                api_def_id.0.log_color_highlight(),
+                to_version.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit)
+        }
+
+        if let Some(site) = &deploy_site {
+            let Some(deployment) = self
+                .ctx
+                .api_deployment_handler()
+                .api_deployment(project.as_ref(), site)
+                .await?
+            else {
+                log_error(format!(
+                    "API deployment for site {} not found",
+                    site.log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit)
+            };
+
+            if !deployment
+                .api_definitions
+                .iter()
+                .any(|def| def.id == api_def_id.0 && def.version == from_version.0)
+            {
+                log_error(format!(
+                    "API definition {}@{} is not deployed to site {}",
+                    api_def_id.0.log_color_highlight(),
+                    from_version.0.log_color_highlight(),
+                    site.log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit)
+            }
+        }
+
+        let promoted_api_definition = {
+            let mut api_definition =
+                DiffableHttpApiDefinition::from_server(source_api_definition)?.0;
+            api_definition.version = to_version.0.clone();
+            api_definition.draft = true;
+            api_definition
+        };
+
+        log_action(
+            "Promoting",
             format!(
-                "API definition: {}/{}",
+                "HTTP API definition {} from version {} to {}",
                 api_def_id.0.log_color_highlight(),
-                version.0.log_color_highlight()
+                from_version.0.log_color_highlight(),
+                to_version.0.log_color_highlight()
             ),
         );
 
-        Ok(())
+        let result = self
+            .new_api_definition(project.as_ref(), &promoted_api_definition)
+            .await?;
+
+        self.ctx
+            .log_handler()
+            .log_view(&ApiDefinitionNewView(result));
+
+        let Some(site) = deploy_site else {
+            return Ok(());
+        };
+
+        let deploy_result = self
+            .ctx
+            .api_deployment_handler()
+            .replace_deployed_api_definition_version(
+                project.as_ref(),
+                &site,
+                &api_def_id.0,
+                &from_version.0,
+                &to_version.0,
+            )
+            .await;
+
+        match deploy_result {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                log_warn_action(
+                    "Rolling back",
+                    format!(
+                        "promoted HTTP API definition {}@{}",
+                        api_def_id.0.log_color_highlight(),
+                        to_version.0.log_color_highlight()
+                    ),
+                );
+                if let Err(rollback_error) = self
+                    .delete_api_definition(project.as_ref(), &api_def_id.0, &to_version.0)
+                    .await
+                {
+                    log_error(format!(
+                        "Failed to delete promoted HTTP API definition {}@{}: {:#}",
+                        api_def_id.0.log_color_highlight(),
+                        to_version.0.log_color_highlight(),
+                        rollback_error
+                    ));
+                }
+                Err(error)
+            }
+        }
     }
 
     pub async fn deploy(
@@ -292,6 +819,102 @@ impl ApiDefinitionCommandHandler {
         Ok(latest_api_definition_versions)
     }
 
+    /// Compares the HTTP API definitions of the application with the deployed ones without
+    /// deploying them, and logs the differences
+    pub async fn deploy_changes(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        deploy_mode: HttpApiDeployMode,
+        update_or_redeploy: &UpdateOrRedeployArgs,
+        latest_component_versions: &BTreeMap<String, Component>,
+    ) -> anyhow::Result<Vec<CiHttpApiDefinitionChange>> {
+        let (api_definitions, app_variables) = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            (
+                app_ctx.application.http_api_definitions().clone(),
+                app_ctx.application.variables().clone(),
+            )
+        };
+
+        let mut changes = Vec::with_capacity(api_definitions.len());
+        for (api_definition_name, api_definition) in api_definitions {
+            if !is_matched_by_deploy_mode(deploy_mode, &api_definition, latest_component_versions) {
+                continue;
+            }
+
+            let api_definition = self.render_api_definition_template(
+                app_variables.clone(),
+                update_or_redeploy,
+                latest_component_versions,
+                &api_definition_name,
+                &api_definition,
+            )?;
+            let version = api_definition.value.version.clone();
+
+            let server_diffable_api_definition = self
+                .api_definition(project, api_definition_name.as_str(), version.as_str())
+                .await?
+                .map(DiffableHttpApiDefinition::from_server)
+                .transpose()?;
+            let manifest_api_definition = DiffableHttpApiDefinition::from_manifest(
+                server_diffable_api_definition.as_ref(),
+                &api_definition_name,
+                &api_definition.value,
+                latest_component_versions,
+            )?;
+
+            let change = match server_diffable_api_definition {
+                None => {
+                    log_warn_action(
+                        "Found",
+                        format!(
+                            "new HTTP API definition {}@{}",
+                            api_definition_name.as_str().log_color_highlight(),
+                            version.log_color_highlight()
+                        ),
+                    );
+                    CiChange::New
+                }
+                Some(server_diffable_api_definition)
+                    if server_diffable_api_definition != manifest_api_definition =>
+                {
+                    log_warn_action(
+                        "Found",
+                        format!(
+                            "changes in HTTP API definition {}@{}",
+                            api_definition_name.as_str().log_color_highlight(),
+                            version.log_color_highlight()
+                        ),
+                    );
+                    let _indent = self.ctx.log_handler().nested_text_view_indent();
+                    log_deploy_diff(&server_diffable_api_definition, &manifest_api_definition)?;
+                    CiChange::Changed
+                }
+                Some(_) => {
+                    log_action(
+                        "Checked",
+                        format!(
+                            "HTTP API definition {}@{}, {}",
+                            api_definition_name.as_str().log_color_highlight(),
+                            version.log_color_highlight(),
+                            "UP-TO-DATE".log_color_ok_highlight()
+                        ),
+                    );
+                    CiChange::Unchanged
+                }
+            };
+
+            changes.push(CiHttpApiDefinitionChange {
+                name: api_definition_name.to_string(),
+                version,
+                change,
+            });
+        }
+
+        Ok(changes)
+    }
+
     pub async fn deploy_api_definition(
         &self,
         project: Option<&ProjectRefAndId>,
@@ -301,17 +924,7 @@ impl ApiDefinitionCommandHandler {
         api_definition_name: &HttpApiDefinitionName,
         api_definition: &WithSource<HttpApiDefinition>,
     ) -> anyhow::Result<Option<String>> {
-        let skip_by_component_filter = match deploy_mode {
-            HttpApiDeployMode::All => false,
-            HttpApiDeployMode::Matching => !api_definition.value.routes.iter().any(|route| {
-                match &route.binding.component_name {
-                    Some(component_name) => latest_component_versions.contains_key(component_name),
-                    None => false,
-                }
-            }),
-        };
-
-        if skip_by_component_filter {
+        if !is_matched_by_deploy_mode(deploy_mode, api_definition, latest_component_versions) {
             log_warn_action(
                 "Skipping",
                 format!(
@@ -322,6 +935,20 @@ impl ApiDefinitionCommandHandler {
             return Ok(None);
         };
 
+        let app_variables = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            app_ctx.application.variables().clone()
+        };
+
+        let api_definition = &self.render_api_definition_template(
+            app_variables,
+            update_or_redeploy,
+            latest_component_versions,
+            api_definition_name,
+            api_definition,
+        )?;
+
         let server_diffable_api_definition = self
             .api_definition(
                 project,
@@ -511,6 +1138,71 @@ impl ApiDefinitionCommandHandler {
         }
     }
 
+    /// Manifest variables are overridden by the profile and HTTP API variables, while variables
+    /// passed as arguments override all of them
+    fn render_api_definition_template(
+        &self,
+        app_variables: BTreeMap<String, String>,
+        update_or_redeploy: &UpdateOrRedeployArgs,
+        latest_component_versions: &BTreeMap<String, Component>,
+        api_definition_name: &HttpApiDefinitionName,
+        api_definition: &WithSource<HttpApiDefinition>,
+    ) -> anyhow::Result<WithSource<HttpApiDefinition>> {
+        let template_env = template_env();
+
+        let mut template_ctx = app_variables;
+        template_ctx.insert("profile".to_string(), self.ctx.profile_name().0.clone());
+        template_ctx.insert("env".to_string(), self.ctx.profile_name().0.clone());
+        template_ctx.extend(update_or_redeploy.http_api_vars(self.ctx.update_or_redeploy()));
+        template_ctx.extend(
+            self.ctx
+                .template_vars()
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+
+        let render_error_context = || {
+            format!(
+                "Failed to render HTTP API definition template {} in {}",
+                api_definition_name.as_str().log_color_highlight(),
+                api_definition.source.log_color_highlight()
+            )
+        };
+
+        let definition = &api_definition.value;
+        let rendered = HttpApiDefinition {
+            version: definition
+                .version
+                .render(&template_env, &template_ctx)
+                .with_context(render_error_context)?,
+            project: definition
+                .project
+                .render(&template_env, &template_ctx)
+                .with_context(render_error_context)?,
+            routes: definition
+                .routes
+                .iter()
+                .map(|route| {
+                    let mut route_ctx = template_ctx.clone();
+                    if let Some(component_name) = &route.binding.component_name {
+                        route_ctx.insert("component_name".to_string(), component_name.clone());
+                        if let Some(component) = latest_component_versions.get(component_name) {
+                            route_ctx.insert(
+                                "component_version".to_string(),
+                                component.versioned_component_id.version.to_string(),
+                            );
+                        }
+                    }
+                    route
+                        .render(&template_env, &route_ctx)
+                        .with_context(render_error_context)
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+
+        Ok(WithSource::new(api_definition.source.clone(), rendered))
+    }
+
     pub async fn deploy_required_components(
         &self,
         project: Option<&ProjectRefAndId>,
@@ -567,7 +1259,7 @@ impl ApiDefinitionCommandHandler {
         Ok(latest_components)
     }
 
-    async fn api_definition(
+    pub async fn api_definition(
         &self,
         project: Option<&ProjectRefAndId>,
         name: &str,
@@ -591,7 +1283,56 @@ impl ApiDefinitionCommandHandler {
             .map_service_error_not_found_as_opt()
     }
 
-    async fn update_api_definition(
+    async fn check_manifest_api_definition_exists(
+        &self,
+        name: &HttpApiDefinitionName,
+    ) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+        if !app_ctx
+            .application
+            .http_api_definitions()
+            .keys()
+            .contains(name)
+        {
+            logln("");
+            log_error(format!(
+                "HTTP API definition {} not found in the application manifest",
+                name.as_str().log_color_highlight()
+            ));
+            logln("");
+            app_ctx.log_dynamic_help(&DynamicHelpSections::show_api_definitions())?;
+            bail!(NonSuccessfulExit)
+        }
+        Ok(())
+    }
+
+    pub async fn delete_api_definition(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        name: &str,
+        version: &str,
+    ) -> anyhow::Result<()> {
+        let clients = self.ctx.golem_clients().await?;
+
+        clients
+            .api_definition
+            .delete_definition(
+                &self
+                    .ctx
+                    .cloud_project_handler()
+                    .selected_project_id_or_default(project)
+                    .await?
+                    .0,
+                name,
+                version,
+            )
+            .await
+            .map_service_error()
+            .map(|_| ())
+    }
+
+    pub async fn update_api_definition(
         &self,
         project: Option<&ProjectRefAndId>,
         manifest_api_definition: &HttpApiDefinitionRequest,
@@ -616,7 +1357,7 @@ impl ApiDefinitionCommandHandler {
             .map_service_error()
     }
 
-    async fn new_api_definition(
+    pub async fn new_api_definition(
         &self,
         project: Option<&ProjectRefAndId>,
         api_definition: &HttpApiDefinitionRequest,
@@ -643,3 +1384,74 @@ impl ApiDefinitionCommandHandler {
 fn parse_api_definition<T: DeserializeOwned>(input: &str) -> anyhow::Result<T> {
     serde_yaml::from_str(input).context("Failed to parse API definition")
 }
+
+fn is_matched_by_deploy_mode(
+    deploy_mode: HttpApiDeployMode,
+    api_definition: &WithSource<HttpApiDefinition>,
+    latest_component_versions: &BTreeMap<String, Component>,
+) -> bool {
+    match deploy_mode {
+        HttpApiDeployMode::All => true,
+        HttpApiDeployMode::Matching => api_definition.value.routes.iter().any(|route| match &route
+            .binding
+            .component_name
+        {
+            Some(component_name) => latest_component_versions.contains_key(component_name),
+            None => false,
+        }),
+    }
+}
+
+fn log_deploy_hint(name: &HttpApiDefinitionName) {
+    logln(format!(
+        "Use {} to apply the changes",
+        format!("{} api definition deploy {}", command_name(), name.as_str()).log_color_highlight()
+    ));
+}
+
+async fn serve_api_definition_preview(
+    port: u16,
+    index: String,
+    specs: BTreeMap<String, String>,
+) -> anyhow::Result<()> {
+    let specs = Arc::new(specs);
+
+    let router = Router::new()
+        .route(
+            "/",
+            get(move || {
+                let index = index.clone();
+                async move { Html(index) }
+            }),
+        )
+        .route(
+            "/openapi/:file",
+            get(move |AxumPath(file): AxumPath<String>| {
+                let specs = specs.clone();
+                async move {
+                    match specs.get(&file) {
+                        Some(spec) => ([(header::CONTENT_TYPE, "application/json")], spec.clone())
+                            .into_response(),
+                        None => StatusCode::NOT_FOUND.into_response(),
+                    }
+                }
+            }),
+        );
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .with_context(|| anyhow!("Failed to bind preview server to port {}", port))?;
+
+    log_action(
+        "Serving",
+        format!(
+            "API definition preview at {}",
+            format!("http://localhost:{port}").log_color_highlight()
+        ),
+    );
+    logln("Press Ctrl+C to stop the preview server");
+
+    axum::serve(listener, router)
+        .await
+        .context("Preview server failed")
+}
@@ -21,22 +21,29 @@ use crate::error::NonSuccessfulExit;
 use crate::log::{
     log_action, log_skipping_up_to_date, log_warn_action, logln, LogColorize, LogIndent,
 };
-use crate::model::api::{ApiDefinitionId, ApiDeployment, HttpApiDeployMode};
+use crate::model::api::{
+    render_route_path, ApiDefinitionId, ApiDeployment, HttpApiDeployMode, HttpApiDeploymentsFile,
+    HttpRequestBody, HttpRouteSelector,
+};
 use crate::model::app::{
     DynamicHelpSections, HttpApiDefinitionName, HttpApiDeploymentSite, WithSource,
 };
 use crate::model::app_raw::HttpApiDeployment;
 use crate::model::deploy_diff::api_deployment::DiffableHttpApiDeployment;
+use crate::model::text::api_deployment::{format_site, ApiDeploymentTestView};
 use crate::model::text::fmt::{log_deploy_diff, log_error, log_warn};
 use crate::model::ProjectRefAndId;
-use anyhow::bail;
-use golem_client::api::ApiDeploymentClient;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use golem_client::api::{ApiDefinitionClient, ApiDeploymentClient};
 use golem_client::model::{
     ApiDefinitionInfo as ApiDefinitionInfoCloud, ApiDeploymentRequest as ApiDeploymentRequestCloud,
     ApiSite as ApiSiteCloud,
 };
+use itertools::Itertools;
 use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
 use std::sync::Arc;
+use url::Url;
 
 pub struct ApiDeploymentCommandHandler {
     ctx: Arc<Context>,
@@ -61,6 +68,23 @@ impl ApiDeploymentCommandHandler {
             ApiDeploymentSubcommand::Delete { project, site } => {
                 self.cmd_delete(project, site).await
             }
+            ApiDeploymentSubcommand::Apply {
+                project,
+                deployments_file,
+                dry_run,
+            } => self.cmd_apply(project, deployments_file, dry_run).await,
+            ApiDeploymentSubcommand::Test {
+                project,
+                site,
+                route,
+                param,
+                header,
+                body,
+                scheme,
+            } => {
+                self.cmd_test(project, site, route, param, header, body, scheme)
+                    .await
+            }
         }
     }
 
@@ -200,28 +224,465 @@ impl ApiDeploymentCommandHandler {
             .opt_select_project(project.project.as_ref())
             .await?;
 
-        let clients = self.ctx.golem_clients().await?;
+        let Some(deployment) = self.api_deployment(project.as_ref(), &site).await? else {
+            log_error(format!(
+                "API deployment for site {} not found",
+                site.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
 
-        clients
-            .api_deployment
-            .delete_deployment(
-                &self
+        let other_deployments = self
+            .all_api_deployments(project.as_ref())
+            .await?
+            .into_iter()
+            .filter(|other| format_site(other) != format_site(&deployment))
+            .collect::<Vec<_>>();
+
+        log_warn_action(
+            "Deleting",
+            format!(
+                "site {} will stop serving the following API(s):",
+                site.log_color_highlight()
+            ),
+        );
+        {
+            let _indent = LogIndent::new();
+            for api_definition_info in &deployment.api_definitions {
+                let served_elsewhere = other_deployments
+                    .iter()
+                    .filter(|other| {
+                        other.api_definitions.iter().any(|def| {
+                            def.id == api_definition_info.id
+                                && def.version == api_definition_info.version
+                        })
+                    })
+                    .map(format_site)
+                    .collect::<Vec<_>>();
+
+                logln(format!(
+                    "{}/{}{}",
+                    api_definition_info.id.log_color_highlight(),
+                    api_definition_info.version.log_color_highlight(),
+                    if served_elsewhere.is_empty() {
+                        "".to_string()
+                    } else {
+                        format!(
+                            ", still served at: {}",
+                            served_elsewhere
+                                .iter()
+                                .map(|site| site.log_color_highlight())
+                                .join(", ")
+                        )
+                    }
+                ));
+
+                let Some(api_definition) = self
                     .ctx
-                    .cloud_project_handler()
-                    .selected_project_id_or_default(project.as_ref())
+                    .api_definition_handler()
+                    .api_definition(
+                        project.as_ref(),
+                        &api_definition_info.id,
+                        &api_definition_info.version,
+                    )
                     .await?
-                    .0,
-                &site,
-            )
-            .await
-            .map(|_| ())
-            .map_service_error()?;
+                else {
+                    continue;
+                };
+
+                let _indent = LogIndent::new();
+                for route in &api_definition.routes {
+                    logln(format!("{} {}", route.method, route.path));
+                }
+            }
+        }
+
+        let same_host_sites = other_deployments
+            .iter()
+            .filter(|other| other.site.host == deployment.site.host)
+            .map(format_site)
+            .collect::<Vec<_>>();
+        if same_host_sites.is_empty() {
+            log_warn(format!(
+                "No other deployments are using host {}",
+                deployment.site.host.log_color_highlight()
+            ));
+        } else {
+            logln(format!(
+                "Other deployments using host {}: {}",
+                deployment.site.host.log_color_highlight(),
+                same_host_sites
+                    .iter()
+                    .map(|site| site.log_color_highlight())
+                    .join(", ")
+            ));
+        }
+
+        if !self
+            .ctx
+            .interactive_handler()
+            .confirm_delete_api_deployment_by_site(&site)?
+        {
+            bail!(NonSuccessfulExit);
+        }
+
+        self.delete_api_deployment(project.as_ref(), &site).await?;
 
         log_warn_action("Deleted", format!("site {}", site.log_color_highlight()));
 
         Ok(())
     }
 
+    async fn cmd_apply(
+        &self,
+        project: ProjectOptionalFlagArg,
+        deployments_file: PathBuf,
+        dry_run: bool,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(project.project.as_ref())
+            .await?;
+
+        let deployments_file = HttpApiDeploymentsFile::from_file(&deployments_file)?;
+
+        if dry_run {
+            log_action("Planning", "HTTP API deployment changes");
+        } else {
+            log_action("Applying", "HTTP API deployment changes");
+        }
+        let changes = {
+            let _indent = LogIndent::new();
+            self.apply_api_deployments(
+                project.as_ref(),
+                &deployments_file.deployments,
+                dry_run,
+                true,
+            )
+            .await?
+        };
+
+        if changes == 0 {
+            log_skipping_up_to_date("applying HTTP API deployments, no changes found");
+        } else if dry_run {
+            logln("");
+            log_warn(format!(
+                "Dry run, {} change(s) were not applied",
+                changes.to_string().log_color_highlight()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Converges the API deployments to the given ones, and returns the number of applied
+    /// or planned changes. Deployments on other sites are only deleted when pruning.
+    pub async fn apply_api_deployments(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        deployments: &[HttpApiDeployment],
+        dry_run: bool,
+        prune: bool,
+    ) -> anyhow::Result<usize> {
+        let mut desired_deployments =
+            BTreeMap::<HttpApiDeploymentSite, DiffableHttpApiDeployment>::new();
+        for deployment in deployments {
+            let site = HttpApiDeploymentSite {
+                host: deployment.host.clone(),
+                subdomain: deployment.subdomain.clone(),
+            };
+            if desired_deployments.contains_key(&site) {
+                log_error(format!(
+                    "Site {} is defined multiple times in the deployments file",
+                    site.to_string().log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+            desired_deployments.insert(
+                site,
+                DiffableHttpApiDeployment::from_manifest(deployment, &BTreeMap::new())?,
+            );
+        }
+
+        let mut server_deployments = self
+            .all_api_deployments(project)
+            .await?
+            .into_iter()
+            .map(|deployment| {
+                let site = HttpApiDeploymentSite {
+                    host: deployment.site.host.clone(),
+                    subdomain: deployment.site.subdomain.clone(),
+                };
+                DiffableHttpApiDeployment::from_server(deployment).map(|dep| (site, dep))
+            })
+            .collect::<anyhow::Result<BTreeMap<_, _>>>()?;
+
+        let sites_to_delete = server_deployments
+            .keys()
+            .filter(|site| prune && !desired_deployments.contains_key(site))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut changes = 0;
+        for (site, desired_deployment) in &desired_deployments {
+            let site_as_str = site.to_string();
+            match server_deployments.remove(site) {
+                Some(server_deployment) if &server_deployment == desired_deployment => {
+                    log_skipping_up_to_date(format!(
+                        "HTTP API deployment {}",
+                        site_as_str.log_color_highlight()
+                    ));
+                }
+                Some(server_deployment) => {
+                    changes += 1;
+                    log_warn_action(
+                        if dry_run { "Would update" } else { "Updating" },
+                        format!("HTTP API deployment {}", site_as_str.log_color_highlight()),
+                    );
+                    {
+                        let _indent = self.ctx.log_handler().nested_text_view_indent();
+                        log_deploy_diff(&server_deployment, desired_deployment)?;
+                    }
+
+                    if dry_run {
+                        continue;
+                    }
+
+                    let plan = server_deployment.plan(desired_deployment);
+                    for (name, version) in plan.delete {
+                        self.undeploy_api_definition(project, site, &name, &version)
+                            .await?;
+                    }
+                    let result = self
+                        .create_or_update_api_deployment(project, site, desired_deployment)
+                        .await?;
+                    self.ctx.log_handler().log_view(&result);
+                }
+                None => {
+                    changes += 1;
+                    log_action(
+                        if dry_run { "Would create" } else { "Creating" },
+                        format!(
+                            "new HTTP API deployment {}",
+                            site_as_str.log_color_highlight()
+                        ),
+                    );
+                    {
+                        let _indent = self.ctx.log_handler().nested_text_view_indent();
+                        for (name, version) in desired_deployment.definitions() {
+                            logln(format!(
+                                "{}@{}",
+                                name.log_color_highlight(),
+                                version.log_color_highlight()
+                            ));
+                        }
+                    }
+
+                    if dry_run {
+                        continue;
+                    }
+
+                    let result = self
+                        .create_or_update_api_deployment(project, site, desired_deployment)
+                        .await?;
+                    self.ctx.log_handler().log_view(&result);
+                }
+            }
+        }
+
+        if !sites_to_delete.is_empty() {
+            changes += sites_to_delete.len();
+            let sites_to_delete = sites_to_delete
+                .iter()
+                .map(|site| site.to_string())
+                .collect::<Vec<_>>();
+
+            if dry_run {
+                for site in &sites_to_delete {
+                    log_warn_action(
+                        "Would delete",
+                        format!("HTTP API deployment {}", site.log_color_highlight()),
+                    );
+                }
+            } else {
+                if !self
+                    .ctx
+                    .interactive_handler()
+                    .confirm_delete_api_deployments(&sites_to_delete)?
+                {
+                    bail!(NonSuccessfulExit);
+                }
+
+                for site in &sites_to_delete {
+                    log_warn_action(
+                        "Deleting",
+                        format!("HTTP API deployment {}", site.log_color_highlight()),
+                    );
+                    self.delete_api_deployment(project, site).await?;
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn cmd_test(
+        &self,
+        project: ProjectOptionalFlagArg,
+        site: String,
+        route: HttpRouteSelector,
+        params: Vec<(String, String)>,
+        headers: Vec<(String, String)>,
+        body: Option<HttpRequestBody>,
+        scheme: Option<String>,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(project.project.as_ref())
+            .await?;
+
+        let Some(deployment) = self.api_deployment(project.as_ref(), &site).await? else {
+            log_error(format!(
+                "API deployment for site {} not found",
+                site.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        let mut matching_route = None;
+        for api_definition_info in &deployment.api_definitions {
+            let Some(api_definition) = self
+                .ctx
+                .api_definition_handler()
+                .api_definition(
+                    project.as_ref(),
+                    &api_definition_info.id,
+                    &api_definition_info.version,
+                )
+                .await?
+            else {
+                continue;
+            };
+
+            if let Some(route_data) = api_definition
+                .routes
+                .into_iter()
+                .find(|route_data| route.matches(&route_data.method.to_string(), &route_data.path))
+            {
+                matching_route = Some((api_definition_info, route_data));
+                break;
+            }
+        }
+
+        let Some((api_definition_info, route_data)) = matching_route else {
+            log_error(format!(
+                "Route {} is not served by site {}",
+                route.to_string().log_color_highlight(),
+                site.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        let (path, query) = render_route_path(&route_data.path, &params)?;
+
+        let scheme = scheme.unwrap_or_else(|| {
+            if self.ctx.profile_name().is_builtin_local() {
+                "http".to_string()
+            } else {
+                "https".to_string()
+            }
+        });
+        let mut url = Url::parse(&format!("{}://{}", scheme, format_site(&deployment)))
+            .with_context(|| anyhow!("Failed to construct URL for site {}", site))?;
+        url.set_path(&path);
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+
+        let method = reqwest::Method::from_bytes(route.method.as_bytes())
+            .with_context(|| anyhow!("Invalid HTTP method: {}", route.method))?;
+
+        let request_body = body.map(|body| body.read_to_string()).transpose()?;
+        let mut request_headers = headers.into_iter().collect::<BTreeMap<_, _>>();
+        if request_body.is_some()
+            && !request_headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case("content-type"))
+        {
+            request_headers.insert("Content-Type".to_string(), "application/json".to_string());
+        }
+
+        log_action(
+            "Sending",
+            format!(
+                "test request {} {}",
+                method.as_str().log_color_highlight(),
+                url.as_str().log_color_highlight()
+            ),
+        );
+
+        let mut request = self
+            .ctx
+            .file_download_client()
+            .request(method.clone(), url.clone());
+        for (name, value) in &request_headers {
+            request = request.header(name, value);
+        }
+        if let Some(request_body) = &request_body {
+            request = request.body(request_body.clone());
+        }
+
+        let response = request
+            .send()
+            .await
+            .with_context(|| anyhow!("Failed to send test request to {}", url))?;
+
+        let response_status = response.status().as_u16();
+        let response_headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).to_string(),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+        let response_body = response
+            .text()
+            .await
+            .context("Failed to read test response body")?;
+
+        self.ctx.log_handler().log_view(&ApiDeploymentTestView {
+            api_definition_id: api_definition_info.id.clone(),
+            api_definition_version: api_definition_info.version.clone(),
+            route: route.to_string(),
+            binding_type: route_data
+                .binding
+                .binding_type
+                .as_ref()
+                .map(|binding_type| binding_type.to_string()),
+            component_name: route_data
+                .binding
+                .component
+                .as_ref()
+                .map(|component| component.name.clone()),
+            request_method: method.to_string(),
+            request_url: url.to_string(),
+            request_headers,
+            request_body,
+            response_status,
+            response_headers,
+            response_body,
+            response_mapping: route_data.binding.response.clone(),
+        });
+
+        Ok(())
+    }
+
     pub async fn deploy(
         &self,
         project: Option<&ProjectRefAndId>,
@@ -467,7 +928,69 @@ impl ApiDeploymentCommandHandler {
         Ok(latest_api_definition_versions)
     }
 
-    async fn api_deployment(
+    pub async fn all_api_deployments(
+        &self,
+        project: Option<&ProjectRefAndId>,
+    ) -> anyhow::Result<Vec<ApiDeployment>> {
+        let clients = self.ctx.golem_clients().await?;
+        let project_id = self
+            .ctx
+            .cloud_project_handler()
+            .selected_project_id_or_default(project)
+            .await?
+            .0;
+
+        // NOTE: deployments can only be listed by API definition ids, so we collect them
+        //       for all the definitions, then deduplicate them by site
+        let api_definition_ids = clients
+            .api_definition
+            .list_definitions(&project_id, None)
+            .await
+            .map_service_error()?
+            .into_iter()
+            .map(|api_definition| api_definition.id)
+            .collect::<BTreeSet<_>>();
+
+        let mut deployments = BTreeMap::<String, ApiDeployment>::new();
+        for api_definition_id in api_definition_ids {
+            for deployment in clients
+                .api_deployment
+                .list_deployments(&project_id, Some(api_definition_id.as_str()))
+                .await
+                .map_service_error()?
+            {
+                let deployment = ApiDeployment::from(deployment);
+                deployments.insert(format_site(&deployment), deployment);
+            }
+        }
+
+        Ok(deployments.into_values().collect())
+    }
+
+    async fn delete_api_deployment(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        site: &str,
+    ) -> anyhow::Result<()> {
+        let clients = self.ctx.golem_clients().await?;
+
+        clients
+            .api_deployment
+            .delete_deployment(
+                &self
+                    .ctx
+                    .cloud_project_handler()
+                    .selected_project_id_or_default(project)
+                    .await?
+                    .0,
+                site,
+            )
+            .await
+            .map(|_| ())
+            .map_service_error()
+    }
+
+    pub async fn api_deployment(
         &self,
         project: Option<&ProjectRefAndId>,
         site: &str,
@@ -555,6 +1078,85 @@ impl ApiDeploymentCommandHandler {
             .map(|_| ())
     }
 
+    /// Replaces an API definition version deployed to the site with a new version.
+    ///
+    /// The deployment is updated with a single request, using the definition set of the site
+    /// where the old version is swapped for the new one, so the routes of the site are never
+    /// left undeployed. If the update fails, the old definition set is restored.
+    pub async fn replace_deployed_api_definition_version(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        site: &str,
+        id: &str,
+        old_version: &str,
+        new_version: &str,
+    ) -> anyhow::Result<()> {
+        let Some(deployment) = self.api_deployment(project, site).await? else {
+            bail!("API deployment for site {} not found", site);
+        };
+
+        let deployment_site = HttpApiDeploymentSite {
+            host: deployment.site.host.clone(),
+            subdomain: deployment.site.subdomain.clone(),
+        };
+        let old_diffable_deployment = DiffableHttpApiDeployment::from_server(deployment)?;
+        let new_diffable_deployment = {
+            let mut deployment = old_diffable_deployment.clone();
+            deployment
+                .definitions
+                .remove(&format!("{id}@{old_version}"));
+            deployment.definitions.insert(format!("{id}@{new_version}"));
+            deployment
+        };
+
+        log_action(
+            "Replacing",
+            format!(
+                "HTTP API definition {}@{} with {}@{} on {}",
+                id.log_color_highlight(),
+                old_version.log_color_highlight(),
+                id.log_color_highlight(),
+                new_version.log_color_highlight(),
+                site.log_color_highlight()
+            ),
+        );
+        match self
+            .create_or_update_api_deployment(project, &deployment_site, &new_diffable_deployment)
+            .await
+        {
+            Ok(result) => {
+                self.ctx.log_handler().log_view(&result);
+                Ok(())
+            }
+            Err(error) => {
+                log_warn_action(
+                    "Restoring",
+                    format!(
+                        "HTTP API definition {}@{} on {}",
+                        id.log_color_highlight(),
+                        old_version.log_color_highlight(),
+                        site.log_color_highlight()
+                    ),
+                );
+                if let Err(restore_error) = self
+                    .create_or_update_api_deployment(
+                        project,
+                        &deployment_site,
+                        &old_diffable_deployment,
+                    )
+                    .await
+                {
+                    log_error(format!(
+                        "Failed to restore HTTP API deployment {}: {:#}",
+                        site.log_color_highlight(),
+                        restore_error
+                    ));
+                }
+                Err(error)
+            }
+        }
+    }
+
     pub async fn undeploy_api_from_all_sites_for_redeploy(
         &self,
         project: Option<&ProjectRefAndId>,
@@ -12,35 +12,81 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::app::error::CustomCommandError;
+use crate::app::build::backup::restore_last_backup;
+use crate::app::build::plan::build_plan;
+use crate::app::build::watch::{
+    changed_paths, with_dependents, ChangeDebouncer, WatchedSources, WATCH_POLL_INTERVAL,
+};
+use crate::app::compat::check_compat;
+use crate::app::context::{find_manifest_sources, ApplicationContext};
+use crate::app::error::{format_errors, format_warns, CustomCommandError};
+use crate::app::remote_components::DeployedComponentInfo;
+use crate::app::shared_types::extract_shared_types;
+use crate::app::stub_registry::publish_stub;
+use crate::app::validate::validate_app;
+use crate::app::yaml_edit::AppYamlEditor;
 use crate::command::app::AppSubcommand;
 use crate::command::builtin_app_subcommands;
 use crate::command::shared_args::{
-    AppOptionalComponentNames, BuildArgs, ForceBuildArg, UpdateOrRedeployArgs,
+    AppOptionalComponentNames, BuildArgs, ComponentOptionalComponentName, ForceBuildArg,
+    UpdateOrRedeployArgs,
 };
+use crate::command_handler::worker::lenient_parse_type_annotated_value;
 use crate::command_handler::Handlers;
 use crate::context::Context;
 use crate::diagnose::diagnose;
-use crate::error::{HintError, NonSuccessfulExit, ShowClapHelpTarget};
+use crate::error::{HintError, NonSuccessfulExit, NonSuccessfulExitWithCode, ShowClapHelpTarget};
 use crate::fs;
 use crate::fuzzy::{Error, FuzzySearch};
-use crate::log::{log_action, logln, LogColorize, LogIndent, LogOutput, Output};
+use crate::log::{
+    log_action, log_skipping_up_to_date, log_warn_action, logln, LogColorize, LogIndent, LogOutput,
+    Output,
+};
 use crate::model::api::HttpApiDeployMode;
-use crate::model::app::{ApplicationComponentSelectMode, DynamicHelpSections};
-use crate::model::component::Component;
-use crate::model::text::fmt::{log_error, log_fuzzy_matches, log_text_view, log_warn};
+use crate::model::app::{
+    AppBuildStep, AppComponentName, ApplicationComponentSelectMode, BinaryComponentSource,
+    ClientLanguage, DependencyType, DynamicHelpSections,
+};
+use crate::model::app_raw;
+use crate::model::app_test::{AppTestReport, TestCaseView, TestStepOutcome, TestStepView};
+use crate::model::ci::{
+    CiChange, CiComponentChange, CiProvider, CiStage, CiStageResult, CiStageStatus, CiSummary,
+    CiToolchain, CiWorkflowConfig,
+};
+use crate::model::component::{function_params_types, Component};
+use crate::model::dependency_graph::{DependencyGraph, GraphFormat};
+use crate::model::invoke_result_view::InvokeResultView;
+use crate::model::template::template_env;
+use crate::model::text::fmt::{
+    format_export, log_error, log_fuzzy_matches, log_text_view, log_warn,
+};
 use crate::model::text::help::AvailableComponentNamesHelp;
-use crate::model::{ComponentName, WorkerUpdateMode};
-use anyhow::{anyhow, bail};
+use crate::model::worker::fuzzy_match_function_name;
+use crate::model::{
+    ComponentName, ComponentVersionSelection, IdempotencyKey, ProjectRefAndId, WorkerName,
+    WorkerUpdateMode,
+};
+use crate::wasm_rpc_stubgen::commands::dependencies::add_wit_dependency;
+use crate::wasm_rpc_stubgen::provenance::find_generated_files;
+use crate::wasm_rpc_stubgen::test_component::{
+    generate_test_component, test_function_name, test_package_name,
+};
+use crate::wasm_rpc_stubgen::wit_generate::extract_deployed_component_wit;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
 use colored::Colorize;
+use golem_client::model::ComponentType;
 use golem_templates::add_component_by_template;
 use golem_templates::model::{
     ComposableAppGroupName, GuestLanguage, PackageName, Template, TemplateName,
 };
+use golem_wasm_ast::analysis::analysed_type;
+use golem_wasm_rpc::json::OptionallyValueAndTypeJson;
+use golem_wasm_rpc::{Value, ValueAndType};
 use itertools::Itertools;
-use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Instant;
 use strum::IntoEnumIterator;
 
 pub struct AppCommandHandler {
@@ -61,7 +107,16 @@ impl AppCommandHandler {
             AppSubcommand::Build {
                 component_name,
                 build: build_args,
-            } => self.cmd_build(component_name, build_args).await,
+                watch,
+                after_build,
+            } => {
+                if watch {
+                    self.cmd_build_watch(component_name, build_args, after_build)
+                        .await
+                } else {
+                    self.cmd_build(component_name, build_args).await
+                }
+            }
             AppSubcommand::Deploy {
                 component_name,
                 force_build,
@@ -70,6 +125,32 @@ impl AppCommandHandler {
                 self.cmd_deploy(component_name, force_build, update_or_redeploy)
                     .await
             }
+            AppSubcommand::Ci {
+                component_name,
+                force_build,
+                update_or_redeploy,
+                dry_run,
+                summary_file,
+            } => {
+                self.cmd_ci(
+                    component_name,
+                    force_build,
+                    update_or_redeploy,
+                    dry_run,
+                    summary_file,
+                )
+                .await
+            }
+            AppSubcommand::InitCi {
+                provider,
+                template,
+                print_template,
+                output,
+                force,
+            } => {
+                self.cmd_init_ci(provider, template, print_template, output, force)
+                    .await
+            }
             AppSubcommand::Clean { component_name } => self.cmd_clean(component_name).await,
             AppSubcommand::UpdateWorkers {
                 component_name,
@@ -84,6 +165,56 @@ impl AppCommandHandler {
                     .await
             }
             AppSubcommand::Diagnose { component_name } => self.cmd_diagnose(component_name).await,
+            AppSubcommand::GenerateClient {
+                component_name,
+                language,
+                output,
+                cargo_workspace,
+            } => {
+                self.cmd_generate_client(component_name, language, output, cargo_workspace)
+                    .await
+            }
+            AppSubcommand::Graph { graph_format } => self.cmd_graph(graph_format).await,
+            AppSubcommand::Validate => self.cmd_validate().await,
+            AppSubcommand::FetchWitDeps { update } => self.cmd_fetch_wit_deps(update).await,
+            AppSubcommand::FetchStubs { update } => self.cmd_fetch_stubs(update).await,
+            AppSubcommand::PublishStub {
+                component_name,
+                registry,
+                version,
+            } => {
+                self.cmd_publish_stub(component_name, registry, version)
+                    .await
+            }
+            AppSubcommand::AddWitDependency {
+                source_wit_dir,
+                component_name,
+            } => {
+                self.cmd_add_wit_dependency(source_wit_dir, component_name)
+                    .await
+            }
+            AppSubcommand::NewTestComponent {
+                target_component_name,
+                component_name,
+            } => {
+                self.cmd_new_test_component(target_component_name, component_name)
+                    .await
+            }
+            AppSubcommand::Test {
+                component_name,
+                worker_name,
+                case_names,
+                keep_workers,
+            } => {
+                self.cmd_test(component_name, worker_name, case_names, keep_workers)
+                    .await
+            }
+            AppSubcommand::Undo => self.cmd_undo().await,
+            AppSubcommand::CheckGenerated => self.cmd_check_generated().await,
+            AppSubcommand::CheckCompat => self.cmd_check_compat().await,
+            AppSubcommand::ExtractSharedTypes { package, dry_run } => {
+                self.cmd_extract_shared_types(package, dry_run).await
+            }
             AppSubcommand::CustomCommand(command) => self.cmd_custom_command(command).await,
         }
     }
@@ -277,6 +408,132 @@ impl AppCommandHandler {
         .await
     }
 
+    async fn cmd_build_watch(
+        &self,
+        component_name: AppOptionalComponentNames,
+        build_args: BuildArgs,
+        after_build: Option<String>,
+    ) -> anyhow::Result<()> {
+        let mut component_names = component_name.component_name;
+        let mut watched_sources: Option<WatchedSources> = None;
+
+        loop {
+            let started = Instant::now();
+            let result = self
+                .build(
+                    component_names.clone(),
+                    Some(build_args.clone()),
+                    &ApplicationComponentSelectMode::All,
+                )
+                .await;
+            let elapsed = format!("{:.1}s", started.elapsed().as_secs_f64());
+
+            let built_components = if component_names.is_empty() {
+                "all components".to_string()
+            } else {
+                component_names
+                    .iter()
+                    .map(|name| name.0.log_color_highlight())
+                    .join(", ")
+            };
+            match result {
+                Ok(()) => {
+                    logln("");
+                    log_action(
+                        "Built",
+                        format!("{} in {}", built_components, elapsed.log_color_highlight()),
+                    );
+                    if let Some(after_build) = &after_build {
+                        run_after_build_command(after_build);
+                    }
+                }
+                Err(error) => {
+                    logln("");
+                    log_error(format!(
+                        "Failed to build {} in {}: {:#}",
+                        built_components, elapsed, error
+                    ));
+                }
+            }
+
+            let sources = {
+                let app_ctx = self.ctx.app_context_lock().await;
+                match (app_ctx.some_or_err(), watched_sources.take()) {
+                    (Ok(app_ctx), _) => WatchedSources::collect(app_ctx)?,
+                    (Err(_), Some(previous_sources)) => previous_sources,
+                    // Without a previously loaded application only the manifests are watched,
+                    // and loading is retried once they change
+                    (Err(_), None) => {
+                        let manifests = find_manifest_sources(&self.ctx.app_source_mode());
+                        if manifests.is_empty() {
+                            log_error(
+                                "Cannot watch the application, no application manifest found",
+                            );
+                            bail!(NonSuccessfulExit);
+                        }
+                        log_warn_action(
+                            "Watching",
+                            "only the application manifests, as the application failed to load",
+                        );
+                        WatchedSources::manifests_only(manifests)
+                    }
+                }
+            };
+            let sources = watched_sources.insert(sources);
+
+            log_action(
+                "Watching",
+                format!(
+                    "{} files for changes, press {} to stop",
+                    sources.file_count().to_string().log_color_highlight(),
+                    "Ctrl+C".log_color_highlight()
+                ),
+            );
+
+            let mut snapshot = sources.snapshot();
+            let mut debouncer = ChangeDebouncer::default();
+            let changed_paths = loop {
+                tokio::time::sleep(WATCH_POLL_INTERVAL).await;
+
+                let app_ctx = self.ctx.app_context_lock().await;
+                let current_sources = match app_ctx.some_or_err() {
+                    Ok(app_ctx) => WatchedSources::collect(app_ctx)?,
+                    Err(_) => sources.clone(),
+                };
+                let current_snapshot = current_sources.snapshot();
+                if let Some(changed_paths) =
+                    debouncer.poll(changed_paths(&snapshot, &current_snapshot))
+                {
+                    break changed_paths;
+                }
+                snapshot = current_snapshot;
+            };
+
+            logln("");
+            for path in &changed_paths {
+                log_action("Changed", path.log_color_highlight());
+            }
+
+            component_names = {
+                let app_ctx = self.ctx.app_context_lock().await;
+                match (
+                    sources.affected_components(&changed_paths),
+                    app_ctx.some_or_err(),
+                ) {
+                    (Some(affected), Ok(app_ctx)) => {
+                        with_dependents(&app_ctx.application, affected)
+                            .into_iter()
+                            .map(|name| ComponentName(name.to_string()))
+                            .collect()
+                    }
+                    _ => vec![],
+                }
+            };
+
+            self.ctx.unload_app_context().await;
+        }
+    }
+
     async fn cmd_clean(&self, component_name: AppOptionalComponentNames) -> anyhow::Result<()> {
         self.clean(
             component_name.component_name,
@@ -295,97 +552,1205 @@ impl AppCommandHandler {
             .await
     }
 
+    async fn cmd_ci(
+        &self,
+        component_name: AppOptionalComponentNames,
+        force_build: ForceBuildArg,
+        update_or_redeploy: UpdateOrRedeployArgs,
+        dry_run: bool,
+        summary_file: Option<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let mut summary = CiSummary {
+            dry_run,
+            ..CiSummary::default()
+        };
+
+        self.ci_pipeline(
+            &mut summary,
+            component_name,
+            force_build,
+            &update_or_redeploy,
+        )
+        .await;
+
+        for stage in [
+            CiStage::Validate,
+            CiStage::Build,
+            CiStage::DeployComponents,
+            CiStage::UpdateWorkers,
+            CiStage::DeployHttpApis,
+        ] {
+            if !summary.stages.iter().any(|result| result.stage == stage) {
+                summary.stages.push(CiStageResult {
+                    stage,
+                    status: CiStageStatus::Skipped,
+                    error: None,
+                });
+            }
+        }
+
+        self.ctx.log_handler().log_view(&summary);
+
+        if let Some(summary_file) = summary_file {
+            fs::write_str(
+                &summary_file,
+                serde_json::to_string_pretty(&summary).context("Failed to serialize CI summary")?,
+            )?;
+        }
+
+        match summary.failed_stage() {
+            Some(stage) => {
+                log_error(format!("CI pipeline failed in the {stage} stage"));
+                bail!(NonSuccessfulExitWithCode(stage.exit_code()))
+            }
+            None => Ok(()),
+        }
+    }
+
+    async fn cmd_init_ci(
+        &self,
+        provider: CiProvider,
+        template: Option<PathBuf>,
+        print_template: bool,
+        output: Option<PathBuf>,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        if print_template {
+            print!("{}", ci_workflow_template(provider));
+            return Ok(());
+        }
+
+        let config = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            ci_workflow_config(app_ctx)
+        };
+
+        let template = match &template {
+            Some(template) => fs::read_to_string(template)?,
+            None => ci_workflow_template(provider).to_string(),
+        };
+        let workflow = render_ci_workflow(&template, &config)?;
+
+        let output = output.unwrap_or_else(|| provider.default_workflow_path());
+        if output.exists() && !force {
+            log_error(format!(
+                "Workflow file {} already exists, use --force to overwrite it",
+                output.display().to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Writing",
+            format!(
+                "CI workflow to {}",
+                output.display().to_string().log_color_highlight()
+            ),
+        );
+        fs::write_str(&output, workflow)?;
+
+        logln("");
+        logln(format!(
+            "Configure {} as a secret and {} as a variable in the CI settings before running the workflow.",
+            "GOLEM_TOKEN".log_color_highlight(),
+            "GOLEM_BASE_URL".log_color_highlight(),
+        ));
+
+        Ok(())
+    }
+
+    /// Runs the stages of app ci until the first failing one, and records the results and
+    /// changes in the summary
+    async fn ci_pipeline(
+        &self,
+        summary: &mut CiSummary,
+        component_name: AppOptionalComponentNames,
+        force_build: ForceBuildArg,
+        update_or_redeploy: &UpdateOrRedeployArgs,
+    ) {
+        let dry_run = summary.dry_run;
+        let deploy_mode = if component_name.component_name.is_empty() {
+            HttpApiDeployMode::All
+        } else {
+            HttpApiDeployMode::Matching
+        };
+
+        let result = self.cmd_validate().await;
+        if ci_stage_result(summary, CiStage::Validate, result).is_none() {
+            return;
+        }
+
+        let result = self
+            .build(
+                component_name.component_name,
+                Some(BuildArgs {
+                    step: vec![],
+                    force_build,
+                    frozen: false,
+                    backup: false,
+                    no_cache: false,
+                    jobs: None,
+                    plan: false,
+                }),
+                &ApplicationComponentSelectMode::All,
+            )
+            .await;
+        if ci_stage_result(summary, CiStage::Build, result).is_none() {
+            return;
+        }
+
+        let result = self.ci_deploy_components(dry_run).await;
+        let Some((project, components, component_changes)) =
+            ci_stage_result(summary, CiStage::DeployComponents, result)
+        else {
+            return;
+        };
+        summary.components = component_changes;
+
+        if dry_run
+            || (update_or_redeploy.update_workers.is_none()
+                && !update_or_redeploy.redeploy_workers(self.ctx.update_or_redeploy()))
+        {
+            summary.stages.push(CiStageResult {
+                stage: CiStage::UpdateWorkers,
+                status: CiStageStatus::Skipped,
+                error: None,
+            });
+        } else {
+            let result = self
+                .ctx
+                .component_handler()
+                .update_or_redeploy_workers_by_components(&components, update_or_redeploy)
+                .await;
+            if ci_stage_result(summary, CiStage::UpdateWorkers, result).is_none() {
+                return;
+            }
+        }
+
+        let components = components
+            .into_iter()
+            .map(|component| (component.component_name.0.clone(), component))
+            .collect::<BTreeMap<_, _>>();
+        let result = async {
+            let changes = {
+                // The changes are logged when deploying, so they are only logged here for dry runs
+                let _log_output = (!dry_run).then(|| LogOutput::new(Output::TracingDebug));
+                self.ctx
+                    .api_definition_handler()
+                    .deploy_changes(
+                        project.as_ref(),
+                        deploy_mode,
+                        update_or_redeploy,
+                        &components,
+                    )
+                    .await?
+            };
+            if !dry_run {
+                self.ctx
+                    .api_handler()
+                    .deploy(
+                        project.as_ref(),
+                        deploy_mode,
+                        update_or_redeploy,
+                        &components,
+                    )
+                    .await?;
+            }
+            Ok::<_, anyhow::Error>(changes)
+        }
+        .await;
+        if let Some(changes) = ci_stage_result(summary, CiStage::DeployHttpApis, result) {
+            summary.http_api_definitions = changes;
+        }
+    }
+
+    /// Deploys the selected components, or only checks them for changes in dry run mode.
+    ///
+    /// Returns the selected project, the latest version of the deployed components, and the
+    /// changes of the selected components
+    async fn ci_deploy_components(
+        &self,
+        dry_run: bool,
+    ) -> anyhow::Result<(
+        Option<ProjectRefAndId>,
+        Vec<Component>,
+        Vec<CiComponentChange>,
+    )> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None)
+            .await?;
+        let component_handler = self.ctx.component_handler();
+        let component_names = component_handler
+            .selected_deployable_component_names()
+            .await?;
+
+        if dry_run {
+            log_action("Checking", "components for changes");
+            let _indent = LogIndent::new();
+
+            let mut components = Vec::new();
+            let mut changes = Vec::with_capacity(component_names.len());
+            for component_name in &component_names {
+                let change = component_handler
+                    .component_deploy_change(project.as_ref(), component_name)
+                    .await?;
+                if let Some(component) = component_handler
+                    .component(
+                        project.as_ref(),
+                        (&ComponentName::from(component_name.as_str())).into(),
+                        None,
+                    )
+                    .await?
+                {
+                    components.push(component);
+                }
+                changes.push(CiComponentChange {
+                    component_name: component_name.as_str().into(),
+                    change,
+                });
+            }
+
+            Ok((project, components, changes))
+        } else {
+            let mut versions_before_deploy = BTreeMap::new();
+            for component_name in &component_names {
+                if let Some(component) = component_handler
+                    .component(
+                        project.as_ref(),
+                        (&ComponentName::from(component_name.as_str())).into(),
+                        None,
+                    )
+                    .await?
+                {
+                    versions_before_deploy.insert(
+                        component.component_name.clone(),
+                        component.versioned_component_id.version,
+                    );
+                }
+            }
+
+            let components = component_handler
+                .deploy_selected_components(project.as_ref())
+                .await?;
+
+            let changes = components
+                .iter()
+                .map(|component| CiComponentChange {
+                    component_name: component.component_name.clone(),
+                    change: match versions_before_deploy.get(&component.component_name) {
+                        None => CiChange::New,
+                        Some(version) if *version != component.versioned_component_id.version => {
+                            CiChange::Changed
+                        }
+                        Some(_) => CiChange::Unchanged,
+                    },
+                })
+                .collect();
+
+            Ok((project, components, changes))
+        }
+    }
+
     async fn cmd_custom_command(&self, command: Vec<String>) -> anyhow::Result<()> {
         if command.len() != 1 {
             bail!(
                 "Expected exactly one custom subcommand, got: {}",
                 command.join(" ").log_color_error_highlight()
             );
-        }
+        }
+
+        let command = command[0].strip_prefix(":").unwrap_or(&command[0]);
+
+        self.fetch_deployed_dependencies().await?;
+
+        let mut app_ctx = self.ctx.app_context_lock_mut().await?;
+        let app_ctx = app_ctx.some_or_err_mut()?;
+        if let Err(error) = app_ctx.custom_command(command).await {
+            match error {
+                CustomCommandError::CommandNotFound => {
+                    logln("");
+                    log_error(format!(
+                        "Request command app command {} not found!",
+                        command.log_color_error_highlight()
+                    ));
+                    logln("");
+
+                    app_ctx.log_dynamic_help(&DynamicHelpSections::show_custom_commands(
+                        builtin_app_subcommands(),
+                    ))?;
+
+                    logln(
+                        "Available builtin commands:"
+                            .log_color_help_group()
+                            .to_string(),
+                    );
+                    let app_subcommands = builtin_app_subcommands();
+                    for subcommand in &app_subcommands {
+                        logln(format!("  {}", subcommand.bold()));
+                    }
+                    logln("");
+
+                    bail!(NonSuccessfulExit)
+                }
+                CustomCommandError::CommandError { error } => {
+                    bail!(
+                        "Command {} failed: {error}",
+                        command.log_color_error_highlight()
+                    )
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_update_workers(
+        &self,
+        component_names: Vec<ComponentName>,
+        update_mode: WorkerUpdateMode,
+        await_update: bool,
+    ) -> anyhow::Result<()> {
+        self.must_select_components(component_names, &ApplicationComponentSelectMode::All)
+            .await?;
+
+        let components = self.components_for_update_or_redeploy().await?;
+        self.ctx
+            .component_handler()
+            .update_workers_by_components(&components, update_mode, await_update)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn cmd_redeploy_workers(
+        &self,
+        component_names: Vec<ComponentName>,
+    ) -> anyhow::Result<()> {
+        self.must_select_components(component_names, &ApplicationComponentSelectMode::All)
+            .await?;
+
+        let components = self.components_for_update_or_redeploy().await?;
+        self.ctx
+            .component_handler()
+            .redeploy_workers_by_components(&components)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn cmd_diagnose(&self, component_names: AppOptionalComponentNames) -> anyhow::Result<()> {
+        self.diagnose(
+            component_names.component_name,
+            &ApplicationComponentSelectMode::All,
+        )
+        .await
+    }
+
+    async fn cmd_generate_client(
+        &self,
+        component_name: AppOptionalComponentNames,
+        language: ClientLanguage,
+        output: PathBuf,
+        cargo_workspace: bool,
+    ) -> anyhow::Result<()> {
+        if cargo_workspace && language != ClientLanguage::Rust {
+            log_error(format!(
+                "The {} flag is only supported for {} client bindings",
+                "--cargo-workspace".log_color_highlight(),
+                ClientLanguage::Rust.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        self.build(
+            component_name.component_name,
+            Some(BuildArgs {
+                step: vec![AppBuildStep::GenRpc],
+                force_build: ForceBuildArg { force_build: false },
+                frozen: false,
+                backup: false,
+                no_cache: false,
+                jobs: None,
+                plan: false,
+            }),
+            &ApplicationComponentSelectMode::All,
+        )
+        .await?;
+
+        let mut app_ctx = self.ctx.app_context_lock_mut().await?;
+        app_ctx
+            .some_or_err_mut()?
+            .generate_client_bindings(language, &output, cargo_workspace)
+    }
+
+    async fn cmd_graph(&self, graph_format: GraphFormat) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        self.ctx
+            .log_handler()
+            .log_view(&DependencyGraph::from_application(
+                &app_ctx.application,
+                graph_format,
+            ));
+
+        Ok(())
+    }
+
+    async fn cmd_extract_shared_types(
+        &self,
+        package: Option<String>,
+        dry_run: bool,
+    ) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let report = extract_shared_types(app_ctx, package.as_deref(), dry_run)?;
+        self.ctx.log_handler().log_view(&report);
+
+        Ok(())
+    }
+
+    async fn cmd_validate(&self) -> anyhow::Result<()> {
+        // Loading the application already reports parsing errors, unknown keys,
+        // duplicated components and invalid references
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let (_, warns, errors) = validate_app(app_ctx).into_product();
+        if !warns.is_empty() {
+            logln("");
+            for line in format_warns(&warns).lines() {
+                logln(line);
+            }
+        }
+        if !errors.is_empty() {
+            logln("");
+            for line in format_errors(&errors).lines() {
+                logln(line);
+            }
+            log_error("Application manifest validation failed");
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action("Validated", "application manifest, no problems found");
+
+        Ok(())
+    }
+
+    async fn cmd_fetch_wit_deps(&self, update: bool) -> anyhow::Result<()> {
+        let mut app_ctx = self.ctx.app_context_lock_mut().await?;
+        app_ctx.some_or_err_mut()?.fetch_wit_deps(update)
+    }
+
+    async fn cmd_fetch_stubs(&self, update: bool) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        app_ctx.some_or_err()?.fetch_registry_stubs(update)
+    }
+
+    async fn cmd_publish_stub(
+        &self,
+        component_name: ComponentOptionalComponentName,
+        registry: String,
+        version: semver::Version,
+    ) -> anyhow::Result<()> {
+        self.build(
+            component_name.component_name.into_iter().collect(),
+            Some(BuildArgs {
+                step: vec![AppBuildStep::GenRpc],
+                force_build: ForceBuildArg { force_build: false },
+                frozen: false,
+                backup: false,
+                no_cache: false,
+                jobs: None,
+                plan: false,
+            }),
+            &ApplicationComponentSelectMode::CurrentDir,
+        )
+        .await?;
+
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let Ok(component_name) = app_ctx.selected_component_names().iter().exactly_one() else {
+            log_error("Select exactly one component to publish its stub");
+            bail!(NonSuccessfulExit);
+        };
+
+        let digest = publish_stub(
+            component_name,
+            &app_ctx
+                .application
+                .component_generated_base_wit(component_name),
+            app_ctx
+                .application
+                .component_properties(component_name, app_ctx.build_profile())
+                .is_ephemeral(),
+            &registry,
+            &version,
+        )?;
+
+        log_action(
+            "Published",
+            format!(
+                "stub of {} as {}@{}, digest: {}",
+                component_name.as_str().log_color_highlight(),
+                registry.log_color_highlight(),
+                version.to_string().log_color_highlight(),
+                digest.log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_add_wit_dependency(
+        &self,
+        source_wit_dir: PathBuf,
+        component_name: ComponentOptionalComponentName,
+    ) -> anyhow::Result<()> {
+        // Resolving the source before loading the application, as loading switches to the
+        // application root directory
+        let source_wit_dir = source_wit_dir.canonicalize().with_context(|| {
+            anyhow!(
+                "Failed to resolve WIT directory {}",
+                source_wit_dir.log_color_highlight()
+            )
+        })?;
+
+        self.must_select_components(
+            component_name.component_name.into_iter().collect(),
+            &ApplicationComponentSelectMode::CurrentDir,
+        )
+        .await?;
+
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let Ok(component_name) = app_ctx.selected_component_names().iter().exactly_one() else {
+            log_error("Select exactly one component to add the WIT dependency to");
+            bail!(NonSuccessfulExit);
+        };
+
+        add_wit_dependency(
+            &source_wit_dir,
+            &app_ctx
+                .application
+                .component_source_wit(component_name, app_ctx.build_profile()),
+        )
+    }
+
+    async fn cmd_new_test_component(
+        &self,
+        target_component_name: ComponentName,
+        test_component_name: Option<PackageName>,
+    ) -> anyhow::Result<()> {
+        self.build(
+            vec![target_component_name],
+            Some(BuildArgs {
+                step: vec![AppBuildStep::GenRpc],
+                force_build: ForceBuildArg { force_build: false },
+                frozen: false,
+                backup: false,
+                no_cache: false,
+                jobs: None,
+                plan: false,
+            }),
+            &ApplicationComponentSelectMode::All,
+        )
+        .await?;
+
+        let (target_component_name, test_component_name, source) = {
+            let mut app_ctx = self.ctx.app_context_lock_mut().await?;
+            let app_ctx = app_ctx.some_or_err_mut()?;
+
+            let Ok(target_component_name) = app_ctx.selected_component_names().iter().exactly_one()
+            else {
+                log_error("Select exactly one target component for the test component");
+                bail!(NonSuccessfulExit);
+            };
+            let target_component_name = target_component_name.clone();
+
+            let test_component_name = match test_component_name {
+                Some(test_component_name) => test_component_name,
+                None => PackageName::from_string(format!("{target_component_name}-test"))
+                    .ok_or_else(|| {
+                        anyhow!("Invalid target component name: {target_component_name}")
+                    })?,
+            };
+
+            if app_ctx
+                .application
+                .contains_component(&AppComponentName::from(
+                    test_component_name.to_string_with_colon(),
+                ))
+            {
+                log_error(format!(
+                    "Component {} already exists",
+                    test_component_name.to_string_with_colon()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+
+            let is_ephemeral = app_ctx
+                .application
+                .component_properties(&target_component_name, app_ctx.build_profile())
+                .is_ephemeral();
+            let source = generate_test_component(
+                app_ctx.component_stub_def(&target_component_name, is_ephemeral)?,
+                &test_package_name(&test_component_name.to_string_with_colon())?,
+            )?;
+
+            (target_component_name, test_component_name, source)
+        };
+
+        {
+            let (common_template, component_template) = self.get_template("rust/minimal")?;
+
+            // Unloading app context, so we can reload after the new component is created
+            self.ctx.unload_app_context().await;
+
+            add_component_by_template(
+                common_template,
+                Some(component_template),
+                &PathBuf::from("."),
+                &test_component_name,
+            )
+            .map_err(|err| anyhow!("Failed to create new test component: {err}"))?;
+        }
+
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let component_name = AppComponentName::from(test_component_name.to_string_with_colon());
+        let source_wit_dir = app_ctx
+            .application
+            .component_source_wit(&component_name, app_ctx.build_profile());
+        for wit_file in std::fs::read_dir(&source_wit_dir)
+            .with_context(|| {
+                anyhow!(
+                    "Failed to read directory {}",
+                    source_wit_dir.log_color_highlight()
+                )
+            })?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "wit"))
+        {
+            fs::remove(&wit_file)?;
+        }
+        fs::write_str(
+            source_wit_dir.join(format!("{}.wit", test_component_name.to_kebab_case())),
+            &source.wit,
+        )?;
+        fs::write_str(
+            app_ctx
+                .application
+                .component_source_dir(&component_name)
+                .join("src")
+                .join("lib.rs"),
+            &source.lib_rs,
+        )?;
+
+        let mut editor = AppYamlEditor::new(&app_ctx.application);
+        editor.insert_or_update_dependency(
+            &component_name,
+            &BinaryComponentSource::AppComponent {
+                name: target_component_name.clone(),
+            },
+            DependencyType::DynamicWasmRpc,
+        )?;
+        editor.set_component_test_for(&component_name, &target_component_name)?;
+        editor.update_documents()?;
+
+        log_action(
+            "Added",
+            format!(
+                "test component {} for {}",
+                component_name.as_str().log_color_highlight(),
+                target_component_name.as_str().log_color_highlight()
+            ),
+        );
+
+        if !source.skipped_functions.is_empty() {
+            log_warn_action(
+                "Skipped",
+                "functions which cannot be invoked with default arguments:",
+            );
+            let _indent = LogIndent::new();
+            for (function_name, reason) in &source.skipped_functions {
+                log_warn_action(
+                    "Skipped",
+                    format!("{}: {}", function_name.log_color_highlight(), reason),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_test(
+        &self,
+        component_name: AppOptionalComponentNames,
+        worker_name: String,
+        case_names: Vec<String>,
+        keep_workers: bool,
+    ) -> anyhow::Result<()> {
+        let is_selected = |name: &str| {
+            component_name.component_name.is_empty()
+                || component_name
+                    .component_name
+                    .iter()
+                    .any(|selected| selected.0 == name)
+        };
+
+        let (tests, test_cases) = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+
+            let unknown_case_names = case_names
+                .iter()
+                .filter(|case_name| !app_ctx.application.tests().contains_key(*case_name))
+                .collect::<Vec<_>>();
+            if !unknown_case_names.is_empty() {
+                log_error(format!(
+                    "Unknown test case(s): {}, available test cases: {}",
+                    unknown_case_names
+                        .iter()
+                        .map(|name| name.log_color_error_highlight())
+                        .join(", "),
+                    app_ctx
+                        .application
+                        .tests()
+                        .keys()
+                        .map(|name| name.log_color_highlight())
+                        .join(", ")
+                ));
+                bail!(NonSuccessfulExit);
+            }
+
+            // Selecting test cases skips the test components
+            let tests = if case_names.is_empty() {
+                app_ctx
+                    .application
+                    .component_names()
+                    .filter_map(|test_component_name| {
+                        app_ctx
+                            .application
+                            .component_properties(test_component_name, app_ctx.build_profile())
+                            .test_for
+                            .clone()
+                            .map(|target_component_name| {
+                                (test_component_name.clone(), target_component_name)
+                            })
+                    })
+                    .filter(|(test_component_name, _)| is_selected(test_component_name.as_str()))
+                    .collect::<Vec<_>>()
+            } else {
+                vec![]
+            };
+
+            let test_cases = app_ctx
+                .application
+                .tests()
+                .iter()
+                .filter(|(case_name, test_case)| {
+                    (case_names.is_empty() || case_names.contains(case_name))
+                        && is_selected(&test_case.value.component)
+                })
+                .map(|(case_name, test_case)| (case_name.clone(), test_case.value.clone()))
+                .collect::<Vec<_>>();
+
+            (tests, test_cases)
+        };
+
+        if tests.is_empty() && test_cases.is_empty() {
+            log_error("No test components or test cases found, create a test component with 'app new-test-component', or declare test cases in the 'tests' section of the manifest");
+            bail!(NonSuccessfulExit);
+        }
+
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(None)
+            .await?;
+
+        let component_names = tests
+            .iter()
+            .flat_map(|(test_component_name, target_component_name)| {
+                [
+                    ComponentName::from(test_component_name.as_str()),
+                    ComponentName::from(target_component_name.as_str()),
+                ]
+            })
+            .chain(
+                test_cases
+                    .iter()
+                    .map(|(_, test_case)| ComponentName::from(test_case.component.as_str())),
+            )
+            .unique_by(|component_name| component_name.0.clone())
+            .collect();
+
+        let components = self
+            .ctx
+            .component_handler()
+            .deploy(
+                project.as_ref(),
+                component_names,
+                Some(ForceBuildArg { force_build: false }),
+                &ApplicationComponentSelectMode::All,
+                &UpdateOrRedeployArgs::none(),
+            )
+            .await?
+            .into_iter()
+            .map(|component| (component.component_name.0.clone(), component))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut failed_tests = Vec::new();
+        for (test_component_name, target_component_name) in &tests {
+            logln("");
+            log_action(
+                "Running",
+                format!(
+                    "test component {} against worker {} of {}",
+                    test_component_name.as_str().log_color_highlight(),
+                    worker_name.log_color_highlight(),
+                    target_component_name.as_str().log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
 
-        let command = command[0].strip_prefix(":").unwrap_or(&command[0]);
+            let Some(component) = components.get(test_component_name.as_str()) else {
+                log_error(format!(
+                    "Test component {} was not deployed",
+                    test_component_name.as_str().log_color_highlight()
+                ));
+                failed_tests.push(test_component_name);
+                continue;
+            };
+
+            let function_name =
+                test_function_name(&test_package_name(test_component_name.as_str())?);
+            let idempotency_key = IdempotencyKey::new();
+            let result = self
+                .ctx
+                .worker_handler()
+                .invoke_worker(
+                    component,
+                    Some(&WorkerName::from(worker_name.as_str())),
+                    &function_name,
+                    vec![ValueAndType::new(
+                        Value::String(worker_name.clone()),
+                        analysed_type::str(),
+                    )
+                    .try_into()
+                    .map_err(|err| anyhow!("Failed to encode test argument: {err}"))?],
+                    idempotency_key.clone(),
+                    false,
+                    None,
+                    None,
+                )
+                .await;
 
-        let app_ctx = self.ctx.app_context_lock().await;
-        let app_ctx = app_ctx.some_or_err()?;
-        if let Err(error) = app_ctx.custom_command(command) {
-            match error {
-                CustomCommandError::CommandNotFound => {
-                    logln("");
+            match result {
+                Ok(Some(result)) => {
+                    self.ctx
+                        .log_handler()
+                        .log_view(&InvokeResultView::new_invoke(
+                            idempotency_key,
+                            result,
+                            component,
+                            &function_name,
+                        ));
+                    log_action("Passed", test_component_name.as_str().log_color_highlight());
+                }
+                Ok(None) => {}
+                Err(err) => {
                     log_error(format!(
-                        "Request command app command {} not found!",
-                        command.log_color_error_highlight()
+                        "Test component {} failed: {:#}",
+                        test_component_name.as_str().log_color_highlight(),
+                        err
                     ));
-                    logln("");
+                    failed_tests.push(test_component_name);
+                }
+            }
+        }
 
-                    app_ctx.log_dynamic_help(&DynamicHelpSections::show_custom_commands(
-                        builtin_app_subcommands(),
-                    ))?;
+        let report = if test_cases.is_empty() {
+            None
+        } else {
+            let report = self
+                .run_test_cases(&components, &test_cases, &worker_name, keep_workers)
+                .await?;
+            logln("");
+            self.ctx.log_handler().log_view(&report);
+            Some(report)
+        };
 
-                    logln(
-                        "Available builtin commands:"
-                            .log_color_help_group()
-                            .to_string(),
-                    );
-                    let app_subcommands = builtin_app_subcommands();
-                    for subcommand in &app_subcommands {
-                        logln(format!("  {}", subcommand.bold()));
-                    }
-                    logln("");
+        if !failed_tests.is_empty() {
+            logln("");
+            log_error(format!(
+                "Failed test component(s): {}",
+                failed_tests
+                    .iter()
+                    .map(|name| name.as_str().log_color_error_highlight())
+                    .join(", ")
+            ));
+        }
+        if let Some(report) = &report {
+            if report.failed_count() > 0 {
+                logln("");
+                log_error(format!(
+                    "Failed test case(s): {}",
+                    report
+                        .cases
+                        .iter()
+                        .filter(|case| !case.passed())
+                        .map(|case| case.name.log_color_error_highlight())
+                        .join(", ")
+                ));
+            }
+        }
+        if !failed_tests.is_empty() || report.is_some_and(|report| report.failed_count() > 0) {
+            bail!(NonSuccessfulExit);
+        }
 
-                    bail!(NonSuccessfulExit)
+        Ok(())
+    }
+
+    /// Runs the steps of the test cases in order, each case using its own worker, and stops a
+    /// case at its first step which does not pass
+    async fn run_test_cases(
+        &self,
+        components: &BTreeMap<String, Component>,
+        test_cases: &[(String, app_raw::TestCase)],
+        default_worker_name: &str,
+        keep_workers: bool,
+    ) -> anyhow::Result<AppTestReport> {
+        let mut report = AppTestReport { cases: vec![] };
+
+        for (case_name, test_case) in test_cases {
+            let worker_name = WorkerName::from(
+                test_case
+                    .worker
+                    .clone()
+                    .unwrap_or_else(|| format!("{default_worker_name}-{case_name}")),
+            );
+
+            logln("");
+            log_action(
+                "Running",
+                format!(
+                    "test case {} against worker {} of {}",
+                    case_name.log_color_highlight(),
+                    worker_name.0.log_color_highlight(),
+                    test_case.component.log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
+
+            let mut steps = Vec::<TestStepView>::with_capacity(test_case.steps.len());
+            let component = components.get(&test_case.component);
+            for step in &test_case.steps {
+                let Some(component) = component else {
+                    steps.push(TestStepView::failed(
+                        step.function.clone(),
+                        step.expect.clone(),
+                        "Component was not deployed".to_string(),
+                    ));
+                    continue;
+                };
+                if steps
+                    .iter()
+                    .any(|step| step.outcome != TestStepOutcome::Passed)
+                {
+                    steps.push(TestStepView::skipped(
+                        step.function.clone(),
+                        step.expect.clone(),
+                    ));
+                    continue;
                 }
-                CustomCommandError::CommandError { error } => {
-                    bail!(
-                        "Command {} failed: {error}",
-                        command.log_color_error_highlight()
+
+                steps.push(
+                    match self.invoke_test_step(component, &worker_name, step).await {
+                        Ok((function_name, result)) => {
+                            TestStepView::new(function_name, step.expect.clone(), Ok(result))
+                        }
+                        Err(err) => TestStepView::failed(
+                            step.function.clone(),
+                            step.expect.clone(),
+                            format!("{err:#}"),
+                        ),
+                    },
+                );
+            }
+
+            if let Some(component) = component.filter(|_| !keep_workers) {
+                if let Err(err) = self
+                    .ctx
+                    .worker_handler()
+                    .delete(
+                        component.versioned_component_id.component_id,
+                        &worker_name.0,
                     )
+                    .await
+                {
+                    log_warn(format!(
+                        "Failed to delete test worker {}: {:#}",
+                        worker_name.0.log_color_highlight(),
+                        err
+                    ));
                 }
             }
+
+            report.cases.push(TestCaseView {
+                name: case_name.clone(),
+                component_name: test_case.component.clone(),
+                worker_name: worker_name.0,
+                steps,
+            });
         }
 
-        Ok(())
+        Ok(report)
     }
 
-    async fn cmd_update_workers(
+    /// Resolves the function and parses the WAVE arguments of the step, then invokes it and
+    /// returns the resolved function name together with the result
+    async fn invoke_test_step(
         &self,
-        component_names: Vec<ComponentName>,
-        update_mode: WorkerUpdateMode,
-        await_update: bool,
-    ) -> anyhow::Result<()> {
-        self.must_select_components(component_names, &ApplicationComponentSelectMode::All)
-            .await?;
+        component: &Component,
+        worker_name: &WorkerName,
+        step: &app_raw::TestStep,
+    ) -> anyhow::Result<(String, Option<ValueAndType>)> {
+        let function_name =
+            match fuzzy_match_function_name(&step.function, &component.metadata.exports) {
+                Ok(match_) => match_.option,
+                Err(Error::Ambiguous { raw_options, .. }) => bail!(
+                    "Ambiguous function name {}, candidates: {}",
+                    step.function,
+                    raw_options.join(", ")
+                ),
+                Err(Error::NotFound { .. }) => bail!("Function {} not found", step.function),
+            };
 
-        let components = self.components_for_update_or_redeploy().await?;
-        self.ctx
-            .component_handler()
-            .update_workers_by_components(&components, update_mode, await_update)
+        let types = function_params_types(component, &function_name)?;
+        if types.len() != step.args.len() {
+            bail!(
+                "Wrong number of arguments: expected {}, got {}",
+                types.len(),
+                step.args.len()
+            );
+        }
+        let arguments = step
+            .args
+            .iter()
+            .zip(types)
+            .map(|(arg, typ)| {
+                let value = lenient_parse_type_annotated_value(typ, arg)
+                    .map_err(|err| anyhow!("Failed to parse argument {arg}: {err}"))?;
+                let value: OptionallyValueAndTypeJson = value
+                    .try_into()
+                    .map_err(|err| anyhow!("Failed to encode argument {arg}: {err}"))?;
+                Ok(value)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        log_action("Invoking", format_export(&function_name));
+        let result = self
+            .ctx
+            .worker_handler()
+            .invoke_worker(
+                component,
+                Some(worker_name),
+                &function_name,
+                arguments,
+                IdempotencyKey::new(),
+                false,
+                None,
+                None,
+            )
             .await?;
 
+        Ok((function_name, result.and_then(|result| result.result)))
+    }
+
+    async fn cmd_undo(&self) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        if !restore_last_backup(app_ctx.some_or_err()?)? {
+            log_warn_action("Skipping", "undo, no backups found");
+        }
         Ok(())
     }
 
-    async fn cmd_redeploy_workers(
-        &self,
-        component_names: Vec<ComponentName>,
-    ) -> anyhow::Result<()> {
-        self.must_select_components(component_names, &ApplicationComponentSelectMode::All)
-            .await?;
+    async fn cmd_check_compat(&self) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
 
-        let components = self.components_for_update_or_redeploy().await?;
-        self.ctx
-            .component_handler()
-            .redeploy_workers_by_components(&components)
-            .await?;
+        let report = check_compat(app_ctx)?;
+        self.ctx.log_handler().log_view(&report);
+
+        if !report.is_compatible() {
+            logln("");
+            log_error(
+                "Incompatible or missing WASM RPC dependency types found, build the affected components to regenerate them",
+            );
+            bail!(NonSuccessfulExit);
+        }
 
         Ok(())
     }
 
-    async fn cmd_diagnose(&self, component_names: AppOptionalComponentNames) -> anyhow::Result<()> {
-        self.diagnose(
-            component_names.component_name,
-            &ApplicationComponentSelectMode::All,
-        )
-        .await
+    async fn cmd_check_generated(&self) -> anyhow::Result<()> {
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+
+        let mut dirs = Vec::new();
+        for component_name in app_ctx.application.component_names() {
+            dirs.push(
+                app_ctx
+                    .application
+                    .component_source_wit(component_name, app_ctx.build_profile()),
+            );
+            dirs.push(
+                app_ctx
+                    .application
+                    .component_generated_wit(component_name, app_ctx.build_profile()),
+            );
+        }
+        for dep in app_ctx.application.all_dependencies() {
+            if let Some(dep) = dep.as_dependent_app_component() {
+                if dep.dep_type.is_wasm_rpc() {
+                    dirs.push(app_ctx.application.client_wit(&dep.name));
+                    dirs.push(app_ctx.application.client_temp_build_dir(&dep.name));
+                }
+            }
+        }
+
+        let (generated, edited) = find_generated_files(dirs.into_iter().unique())?;
+        if !edited.is_empty() {
+            log_warn_action("Detected", "manual edits in generated files:");
+            {
+                let _indent = LogIndent::new();
+                for path in &edited {
+                    log_warn_action("Edited", path.log_color_highlight());
+                }
+            }
+            logln("");
+            log_error(
+                "Generated files were edited, the edits will be overwritten by the next build",
+            );
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Checked",
+            format!("{} generated files, no manual edits found", generated.len()),
+        );
+
+        Ok(())
     }
 
     async fn deploy(
@@ -442,6 +1807,7 @@ impl AppCommandHandler {
         build: Option<BuildArgs>,
         default_component_select_mode: &ApplicationComponentSelectMode,
     ) -> anyhow::Result<()> {
+        let plan = build.as_ref().is_some_and(|build| build.plan);
         if let Some(build) = build {
             self.ctx
                 .set_steps_filter(build.step.into_iter().collect())
@@ -449,11 +1815,162 @@ impl AppCommandHandler {
             self.ctx
                 .set_skip_up_to_date_checks(build.force_build.force_build)
                 .await;
+            self.ctx.set_frozen(build.frozen).await;
+            self.ctx.set_backup(build.backup).await;
+            self.ctx.set_no_cache(build.no_cache).await;
+            if let Some(jobs) = build.jobs {
+                self.ctx.set_build_jobs(jobs).await;
+            }
         }
         self.must_select_components(component_names, default_component_select_mode)
             .await?;
+
+        if plan {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let plan = build_plan(app_ctx.some_or_err()?)?;
+            self.ctx.log_handler().log_view(&plan);
+            return Ok(());
+        }
+
+        self.fetch_deployed_dependencies().await?;
+
         let mut app_ctx = self.ctx.app_context_lock_mut().await?;
-        app_ctx.some_or_err_mut()?.build().await
+        let app_ctx = app_ctx.some_or_err_mut()?;
+        app_ctx.fetch_registry_stubs(false)?;
+        app_ctx.build().await
+    }
+
+    /// Fetches the deployed components used as WASM RPC dependency targets, and reconstructs
+    /// their WIT, so their clients can be generated without having their sources
+    async fn fetch_deployed_dependencies(&self) -> anyhow::Result<()> {
+        let (deployed_deps, offline) = {
+            let app_ctx = self.ctx.app_context_lock().await;
+            let app_ctx = app_ctx.some_or_err()?;
+            let deployed_deps = app_ctx
+                .application
+                .all_dependencies()
+                .iter()
+                .filter_map(|dep| match &dep.source {
+                    BinaryComponentSource::Deployed { name, version } => Some((
+                        name.clone(),
+                        *version,
+                        app_ctx.application.deployed_component_wit(name),
+                        app_ctx.application.deployed_component_info(name),
+                    )),
+                    _ => None,
+                })
+                .collect::<BTreeSet<_>>();
+            (deployed_deps, app_ctx.config.offline)
+        };
+
+        if deployed_deps.is_empty() {
+            return Ok(());
+        }
+
+        log_action("Fetching", "deployed dependency components");
+        let _indent = LogIndent::new();
+
+        let project = if offline {
+            None
+        } else {
+            self.ctx
+                .cloud_project_handler()
+                .opt_select_project(None)
+                .await?
+        };
+
+        for (component_name, version, wit_dir, info_path) in deployed_deps {
+            let info = DeployedComponentInfo::load(&info_path)?;
+
+            if offline {
+                if info.is_none() || !wit_dir.exists() {
+                    log_error(format!(
+                        "Offline mode is enabled, but the deployed component {} is not fetched yet",
+                        component_name.as_str().log_color_error_highlight()
+                    ));
+                    bail!(NonSuccessfulExit);
+                }
+                continue;
+            }
+
+            let server_component_name = ComponentName::from(component_name.as_str());
+            let Some(component) = self
+                .ctx
+                .component_handler()
+                .component(
+                    project.as_ref(),
+                    (&server_component_name).into(),
+                    version.map(ComponentVersionSelection::ByExplicitVersion),
+                )
+                .await?
+            else {
+                log_error(format!(
+                    "Deployed component {} not found",
+                    component_name.as_str().log_color_error_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            };
+            let component_version = component.versioned_component_id.version;
+
+            if info
+                .as_ref()
+                .is_some_and(|info| info.version == component_version)
+                && wit_dir.exists()
+            {
+                log_skipping_up_to_date(format!(
+                    "fetching deployed component {}@{}",
+                    component_name.as_str().log_color_highlight(),
+                    component_version.to_string().log_color_highlight()
+                ));
+                continue;
+            }
+
+            log_action(
+                "Fetching",
+                format!(
+                    "WIT of deployed component {}@{}",
+                    component_name.as_str().log_color_highlight(),
+                    component_version.to_string().log_color_highlight()
+                ),
+            );
+
+            let component_bytes = self
+                .ctx
+                .golem_clients()
+                .await?
+                .component
+                .download_component(
+                    &component.versioned_component_id.component_id,
+                    Some(component_version),
+                )
+                .await?;
+
+            fs::remove(&wit_dir)?;
+            extract_deployed_component_wit(
+                &component_bytes,
+                component
+                    .metadata
+                    .root_package_name
+                    .as_deref()
+                    .unwrap_or(component_name.as_str()),
+                &wit_dir,
+            )
+            .with_context(|| {
+                anyhow!(
+                    "Failed to reconstruct the WIT of deployed component {}",
+                    component_name.as_str().log_color_highlight()
+                )
+            })?;
+
+            DeployedComponentInfo {
+                component_name: component_name.to_string(),
+                version: component_version,
+                ephemeral: matches!(component.component_type, ComponentType::Ephemeral),
+            }
+            .save(&info_path)?;
+        }
+
+        Ok(())
     }
 
     pub async fn clean(
@@ -794,3 +2311,214 @@ impl AppCommandHandler {
         Ok(())
     }
 }
+
+fn run_after_build_command(command: &str) {
+    log_action(
+        "Executing",
+        format!("after build command '{}'", command.log_color_highlight()),
+    );
+
+    let Some(command_tokens) = shlex::split(command).filter(|tokens| !tokens.is_empty()) else {
+        log_error(format!("Failed to parse after build command: {command}"));
+        return;
+    };
+
+    match std::process::Command::new(&command_tokens[0])
+        .args(command_tokens.iter().skip(1))
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        Ok(status) => log_error(format!(
+            "After build command failed with exit code: {}",
+            status
+                .code()
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "?".to_string())
+        )),
+        Err(err) => log_error(format!("Failed to execute after build command: {err}")),
+    }
+}
+
+/// Records the result of an app ci stage, returns None if the stage failed
+const GITHUB_WORKFLOW_TEMPLATE: &str = include_str!("ci/github.yaml");
+const GITLAB_WORKFLOW_TEMPLATE: &str = include_str!("ci/gitlab.yaml");
+
+fn ci_workflow_template(provider: CiProvider) -> &'static str {
+    match provider {
+        CiProvider::Github => GITHUB_WORKFLOW_TEMPLATE,
+        CiProvider::Gitlab => GITLAB_WORKFLOW_TEMPLATE,
+    }
+}
+
+fn render_ci_workflow(template: &str, config: &CiWorkflowConfig) -> anyhow::Result<String> {
+    template_env()
+        .render_str(template, config)
+        .context("Failed to render CI workflow template")
+}
+
+/// Collects the template variables from the application manifest. Paths are relative to the
+/// application root, which is the current dir when the application is loaded.
+fn ci_workflow_config(app_ctx: &ApplicationContext) -> CiWorkflowConfig {
+    let application = &app_ctx.application;
+    let relative = |path: &Path| -> String {
+        let path = std::env::current_dir()
+            .ok()
+            .and_then(|current_dir| path.strip_prefix(current_dir).ok())
+            .unwrap_or(path);
+        path.to_string_lossy().replace('\\', "/")
+    };
+
+    let mut toolchains = BTreeSet::new();
+    let mut cache_paths = BTreeSet::new();
+    cache_paths.insert(relative(&application.temp_dir()));
+    if let Some(build_cache) = application.build_cache() {
+        if let Some(dir) = &build_cache.value.dir {
+            cache_paths.insert(relative(&build_cache.source.join(dir)));
+        }
+    }
+    for component_name in application.component_names() {
+        let source_dir = application.component_source_dir(component_name);
+        let Some(toolchain) = CiToolchain::detect(source_dir) else {
+            continue;
+        };
+        toolchains.insert(toolchain);
+        match toolchain {
+            CiToolchain::Rust => {
+                // Rust components are usually members of a workspace in the application root
+                let workspace_dir = if Path::new("Cargo.toml").is_file() {
+                    Path::new("")
+                } else {
+                    source_dir
+                };
+                cache_paths.insert(relative(&workspace_dir.join("target")));
+            }
+            CiToolchain::Node => {
+                cache_paths.insert(relative(&source_dir.join("node_modules")));
+            }
+            CiToolchain::Go | CiToolchain::Python => {}
+        }
+    }
+
+    CiWorkflowConfig {
+        cli_version: crate::version().to_string(),
+        build_profile: app_ctx.build_profile().map(|profile| profile.to_string()),
+        components: application
+            .component_names()
+            .map(|component_name| component_name.to_string())
+            .collect(),
+        toolchains,
+        // The root manifest comes first, as some providers only support a single cache key file
+        manifests: application
+            .all_sources()
+            .iter()
+            .sorted_by_key(|source| source.components().count())
+            .map(|source| relative(source))
+            .collect(),
+        cache_paths: cache_paths.into_iter().collect(),
+        has_tests: application.component_names().any(|component_name| {
+            application
+                .component_properties(component_name, app_ctx.build_profile())
+                .test_for
+                .is_some()
+        }),
+    }
+}
+
+fn ci_stage_result<T>(
+    summary: &mut CiSummary,
+    stage: CiStage,
+    result: anyhow::Result<T>,
+) -> Option<T> {
+    match result {
+        Ok(value) => {
+            summary.stages.push(CiStageResult {
+                stage,
+                status: CiStageStatus::Succeeded,
+                error: None,
+            });
+            Some(value)
+        }
+        Err(error) => {
+            // NonSuccessfulExit errors are already reported on the command line
+            let error = error
+                .downcast_ref::<NonSuccessfulExit>()
+                .is_none()
+                .then(|| format!("{error:#}"));
+            if let Some(error) = &error {
+                logln("");
+                log_error(error);
+            }
+            summary.stages.push(CiStageResult {
+                stage,
+                status: CiStageStatus::Failed,
+                error,
+            });
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::app::{ci_workflow_template, render_ci_workflow};
+    use crate::model::ci::{CiProvider, CiToolchain, CiWorkflowConfig};
+    use assert2::assert;
+    use test_r::test;
+
+    fn config() -> CiWorkflowConfig {
+        CiWorkflowConfig {
+            cli_version: "1.2.4".to_string(),
+            build_profile: Some("release".to_string()),
+            components: vec!["app:counter".to_string()],
+            toolchains: [CiToolchain::Rust, CiToolchain::Node].into_iter().collect(),
+            manifests: vec![
+                "golem.yaml".to_string(),
+                "components-rust/app-counter/golem.yaml".to_string(),
+            ],
+            cache_paths: vec!["golem-temp".to_string(), "target".to_string()],
+            has_tests: true,
+        }
+    }
+
+    #[test]
+    fn github_workflow() {
+        let workflow =
+            render_ci_workflow(ci_workflow_template(CiProvider::Github), &config()).unwrap();
+        let workflow: serde_yaml::Value = serde_yaml::from_str(&workflow).unwrap();
+        let steps = workflow["jobs"]["golem"]["steps"].as_sequence().unwrap();
+
+        assert!(workflow["env"]["GOLEM_BUILD_PROFILE"].as_str() == Some("release"));
+        assert!(steps
+            .iter()
+            .any(|step| step["uses"].as_str() == Some("actions/setup-node@v4")));
+        assert!(steps
+            .iter()
+            .any(|step| step["run"].as_str() == Some("golem app test")));
+
+        let cache = steps
+            .iter()
+            .find(|step| step["uses"].as_str() == Some("actions/cache@v4"))
+            .unwrap();
+        assert!(cache["with"]["path"]
+            .as_str()
+            .unwrap()
+            .lines()
+            .any(|path| path.trim() == "golem-temp"));
+    }
+
+    #[test]
+    fn gitlab_workflow() {
+        let workflow =
+            render_ci_workflow(ci_workflow_template(CiProvider::Gitlab), &config()).unwrap();
+        let workflow: serde_yaml::Value = serde_yaml::from_str(&workflow).unwrap();
+
+        assert!(workflow["default"]["cache"]["key"]["files"][0].as_str() == Some("golem.yaml"));
+        assert!(workflow["default"]["cache"]["paths"][0].as_str() == Some("golem-temp"));
+        assert!(workflow["variables"]["GOLEM_BUILD_PROFILE"].as_str() == Some("release"));
+        assert!(
+            workflow["deploy"]["script"][0].as_str()
+                == Some("golem app ci --update-workers --summary-file golem-ci-summary.json")
+        );
+        assert!(workflow["test"]["script"][0].as_str() == Some("golem app test"));
+    }
+}
@@ -0,0 +1,528 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::shared_args::ProjectOptionalFlagArg;
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
+use crate::log::{
+    log_action, log_skipping_up_to_date, log_warn_action, logln, LogColorize, LogIndent,
+};
+use crate::model::app::{AppComponentName, HttpApiDefinitionName};
+use crate::model::component::Component;
+use crate::model::deploy_diff::api_definition::DiffableHttpApiDefinition;
+use crate::model::deploy_diff::component::DiffableComponent;
+use crate::model::desired_state::{DesiredComponent, DesiredStateFile};
+use crate::model::text::api_definition::{ApiDefinitionNewView, ApiDefinitionUpdateView};
+use crate::model::text::fmt::{log_deploy_diff, log_error, log_warn};
+use crate::model::{ComponentName, ProjectRefAndId};
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use golem_client::api::{ApiDefinitionClient, ComponentClient};
+use golem_client::model::{ComponentEnv as ComponentEnvCloud, ComponentQuery};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs::File;
+
+pub struct ApplyCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl ApplyCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn cmd_apply(
+        &self,
+        project: ProjectOptionalFlagArg,
+        file: PathBuf,
+        prune: bool,
+        dry_run: bool,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(project.project.as_ref())
+            .await?;
+
+        let desired_state = DesiredStateFile::from_file(&file)?;
+
+        log_action(
+            if dry_run { "Planning" } else { "Applying" },
+            format!(
+                "desired state from {}",
+                file.display().to_string().log_color_highlight()
+            ),
+        );
+        let changes = {
+            let _indent = LogIndent::new();
+
+            // Resources are created and updated in dependency order, and pruned in reverse
+            let mut changes = 0;
+            changes += self
+                .apply_components(project.as_ref(), &desired_state, prune, dry_run)
+                .await?;
+            changes += self
+                .apply_api_definitions(project.as_ref(), &desired_state, dry_run)
+                .await?;
+            changes += self
+                .ctx
+                .api_deployment_handler()
+                .apply_api_deployments(
+                    project.as_ref(),
+                    &desired_state.http_api_deployments,
+                    dry_run,
+                    prune,
+                )
+                .await?;
+            if prune {
+                changes += self
+                    .prune_api_definitions(project.as_ref(), &desired_state, dry_run)
+                    .await?;
+            }
+            changes
+        };
+
+        if changes == 0 {
+            log_skipping_up_to_date("applying desired state, no changes found");
+        } else if dry_run {
+            logln("");
+            log_warn(format!(
+                "Dry run, {} change(s) were not applied",
+                changes.to_string().log_color_highlight()
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn apply_components(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        desired_state: &DesiredStateFile,
+        prune: bool,
+        dry_run: bool,
+    ) -> anyhow::Result<usize> {
+        let mut changes = 0;
+
+        for (component_name, desired_component) in &desired_state.components {
+            let component_type = desired_component
+                .component_type
+                .as_deployable_component_type()
+                .ok_or_else(|| anyhow!("Component {component_name} is not deployable"))?;
+            let wasm_hash = wasm_hash(&desired_component.wasm)?;
+            if let Some(expected_hash) = &desired_component.hash {
+                if !expected_hash.eq_ignore_ascii_case(&wasm_hash) {
+                    log_error(format!(
+                        "The hash of {} does not match the expected hash of component {}, expected: {}, actual: {}",
+                        desired_component.wasm.display().to_string().log_color_highlight(),
+                        component_name.log_color_highlight(),
+                        expected_hash.log_color_highlight(),
+                        wasm_hash.log_color_highlight()
+                    ));
+                    bail!(NonSuccessfulExit);
+                }
+            }
+
+            let server_component = self
+                .ctx
+                .component_handler()
+                .component(
+                    project,
+                    (&ComponentName::from(component_name.as_str())).into(),
+                    None,
+                )
+                .await?;
+
+            let desired_env = desired_component
+                .env
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<HashMap<_, _>>();
+            let desired_diffable = DiffableComponent::from_manifest(
+                self.ctx.show_sensitive(),
+                &AppComponentName::from(component_name.as_str()),
+                wasm_hash.clone(),
+                component_type,
+                BTreeMap::new(),
+                None,
+                Some(&desired_env),
+            )?;
+
+            match &server_component {
+                Some(server_component) => {
+                    let server_hash = self.server_component_hash(server_component).await?;
+                    if server_hash == wasm_hash
+                        && server_component.component_type == component_type
+                        && server_component.env == desired_component.env
+                    {
+                        log_skipping_up_to_date(format!(
+                            "component {}",
+                            component_name.log_color_highlight()
+                        ));
+                        continue;
+                    }
+
+                    changes += 1;
+                    log_warn_action(
+                        if dry_run { "Would update" } else { "Updating" },
+                        format!("component {}", component_name.log_color_highlight()),
+                    );
+                    let server_diffable = DiffableComponent::from_server(
+                        self.ctx.show_sensitive(),
+                        server_component,
+                        server_hash,
+                        BTreeMap::new(),
+                    )?;
+                    {
+                        let _indent = self.ctx.log_handler().nested_text_view_indent();
+                        log_deploy_diff(&server_diffable, &desired_diffable)?;
+                    }
+                }
+                None => {
+                    changes += 1;
+                    log_action(
+                        if dry_run { "Would create" } else { "Creating" },
+                        format!("component {}", component_name.log_color_highlight()),
+                    );
+                }
+            }
+
+            if dry_run {
+                continue;
+            }
+
+            self.upsert_component(
+                project,
+                component_name,
+                desired_component,
+                server_component.as_ref(),
+            )
+            .await?;
+        }
+
+        if prune {
+            let clients = self.ctx.golem_clients().await?;
+            let mut undeclared_components = clients
+                .component
+                .get_components(project.map(|project| &project.project_id.0), None)
+                .await
+                .map_service_error()?
+                .into_iter()
+                .map(|component| component.component_name)
+                .filter(|component_name| !desired_state.components.contains_key(component_name))
+                .collect::<Vec<_>>();
+            undeclared_components.sort();
+            undeclared_components.dedup();
+
+            for component_name in undeclared_components {
+                log_warn(format!(
+                    "Component {} is not part of the desired state, but components cannot be deleted, skipping",
+                    component_name.log_color_highlight()
+                ));
+            }
+        }
+
+        Ok(changes)
+    }
+
+    async fn upsert_component(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        component_name: &str,
+        desired_component: &DesiredComponent,
+        server_component: Option<&Component>,
+    ) -> anyhow::Result<()> {
+        let component_type = desired_component
+            .component_type
+            .as_deployable_component_type()
+            .ok_or_else(|| anyhow!("Component {component_name} is not deployable"))?;
+        let wasm = File::open(&desired_component.wasm).await.with_context(|| {
+            anyhow!(
+                "Failed to open component WASM at {}",
+                desired_component
+                    .wasm
+                    .display()
+                    .to_string()
+                    .log_color_error_highlight()
+            )
+        })?;
+        let env = (!desired_component.env.is_empty()).then(|| ComponentEnvCloud {
+            key_values: desired_component
+                .env
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        });
+
+        let clients = self.ctx.golem_clients().await?;
+        let component = match server_component {
+            Some(server_component) => clients
+                .component
+                .update_component(
+                    &server_component.versioned_component_id.component_id,
+                    Some(&component_type),
+                    wasm,
+                    None,
+                    None,
+                    None,
+                    env.as_ref(),
+                    None,
+                )
+                .await
+                .map_service_error()?,
+            None => clients
+                .component
+                .create_component(
+                    &ComponentQuery {
+                        project_id: project.map(|project| project.project_id.0),
+                        component_name: component_name.to_string(),
+                    },
+                    wasm,
+                    Some(&component_type),
+                    None,
+                    None,
+                    None,
+                    env.as_ref(),
+                    None,
+                )
+                .await
+                .map_service_error()?,
+        };
+
+        log_action(
+            if server_component.is_some() {
+                "Updated"
+            } else {
+                "Created"
+            },
+            format!(
+                "component {}, version: {}",
+                component_name.log_color_highlight(),
+                component
+                    .versioned_component_id
+                    .version
+                    .to_string()
+                    .log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    async fn server_component_hash(&self, component: &Component) -> anyhow::Result<String> {
+        let clients = self.ctx.golem_clients().await?;
+        let component_bytes = clients
+            .component
+            .download_component(
+                &component.versioned_component_id.component_id,
+                Some(component.versioned_component_id.version),
+            )
+            .await?;
+
+        let mut component_hasher = blake3::Hasher::new();
+        component_hasher.update(&component_bytes);
+        Ok(component_hasher.finalize().to_hex().to_string())
+    }
+
+    async fn apply_api_definitions(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        desired_state: &DesiredStateFile,
+        dry_run: bool,
+    ) -> anyhow::Result<usize> {
+        if desired_state.http_api_definitions.is_empty() {
+            return Ok(0);
+        }
+
+        let api_definition_handler = self.ctx.api_definition_handler();
+        let latest_components = self
+            .ctx
+            .component_handler()
+            .latest_components_by_name(
+                project,
+                desired_state
+                    .components
+                    .keys()
+                    .map(|component_name| ComponentName::from(component_name.as_str()))
+                    .collect(),
+            )
+            .await?;
+
+        let mut changes = 0;
+        for (name, api_definition) in &desired_state.http_api_definitions {
+            let name = HttpApiDefinitionName::from(name.as_str());
+            let id_with_version = format!(
+                "{}@{}",
+                name.as_str().log_color_highlight(),
+                api_definition.version.log_color_highlight()
+            );
+
+            let server_api_definition = api_definition_handler
+                .api_definition(project, name.as_str(), &api_definition.version)
+                .await?
+                .map(DiffableHttpApiDefinition::from_server)
+                .transpose()?;
+            let desired_api_definition = DiffableHttpApiDefinition::from_manifest(
+                server_api_definition.as_ref(),
+                &name,
+                api_definition,
+                &latest_components,
+            )?;
+
+            match server_api_definition {
+                Some(server_api_definition) if server_api_definition == desired_api_definition => {
+                    log_skipping_up_to_date(format!("HTTP API definition {id_with_version}"));
+                }
+                Some(server_api_definition) => {
+                    if !server_api_definition.0.draft {
+                        log_error(format!(
+                            "HTTP API definition {id_with_version} is already published and cannot be changed, use a new version in the desired state file"
+                        ));
+                        {
+                            let _indent = self.ctx.log_handler().nested_text_view_indent();
+                            log_deploy_diff(&server_api_definition, &desired_api_definition)?;
+                        }
+                        bail!(NonSuccessfulExit);
+                    }
+
+                    changes += 1;
+                    log_warn_action(
+                        if dry_run { "Would update" } else { "Updating" },
+                        format!("HTTP API definition {id_with_version}"),
+                    );
+                    {
+                        let _indent = self.ctx.log_handler().nested_text_view_indent();
+                        log_deploy_diff(&server_api_definition, &desired_api_definition)?;
+                    }
+
+                    if !dry_run {
+                        let result = api_definition_handler
+                            .update_api_definition(project, &desired_api_definition.0)
+                            .await?;
+                        self.ctx
+                            .log_handler()
+                            .log_view(&ApiDefinitionUpdateView(result));
+                    }
+                }
+                None => {
+                    changes += 1;
+                    log_action(
+                        if dry_run { "Would create" } else { "Creating" },
+                        format!("HTTP API definition {id_with_version}"),
+                    );
+
+                    if !dry_run {
+                        let result = api_definition_handler
+                            .new_api_definition(project, &desired_api_definition.0)
+                            .await?;
+                        self.ctx
+                            .log_handler()
+                            .log_view(&ApiDefinitionNewView(result));
+                    }
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// Deletes the API definitions which are neither declared, nor used by the declared deployments
+    async fn prune_api_definitions(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        desired_state: &DesiredStateFile,
+        dry_run: bool,
+    ) -> anyhow::Result<usize> {
+        let clients = self.ctx.golem_clients().await?;
+        let project_id = self
+            .ctx
+            .cloud_project_handler()
+            .selected_project_id_or_default(project)
+            .await?;
+
+        let desired_api_definitions = desired_state.api_definition_versions();
+        let undeclared_api_definitions = clients
+            .api_definition
+            .list_definitions(&project_id.0, None)
+            .await
+            .map_service_error()?
+            .into_iter()
+            .map(|api_definition| (api_definition.id, api_definition.version))
+            .filter(|api_definition| !desired_api_definitions.contains(api_definition))
+            .collect::<Vec<_>>();
+
+        if undeclared_api_definitions.is_empty() {
+            return Ok(0);
+        }
+
+        let formatted = undeclared_api_definitions
+            .iter()
+            .map(|(id, version)| format!("{id}@{version}"))
+            .collect::<Vec<_>>();
+
+        if dry_run {
+            for api_definition in &formatted {
+                log_warn_action(
+                    "Would delete",
+                    format!(
+                        "HTTP API definition {}",
+                        api_definition.log_color_highlight()
+                    ),
+                );
+            }
+        } else {
+            if !self
+                .ctx
+                .interactive_handler()
+                .confirm_delete_api_definitions(&formatted)?
+            {
+                bail!(NonSuccessfulExit);
+            }
+
+            for ((id, version), api_definition) in undeclared_api_definitions.iter().zip(&formatted)
+            {
+                log_warn_action(
+                    "Deleting",
+                    format!(
+                        "HTTP API definition {}",
+                        api_definition.log_color_highlight()
+                    ),
+                );
+                self.ctx
+                    .api_definition_handler()
+                    .delete_api_definition(project, id, version)
+                    .await?;
+            }
+        }
+
+        Ok(undeclared_api_definitions.len())
+    }
+}
+
+fn wasm_hash(path: &Path) -> anyhow::Result<String> {
+    let file = std::fs::File::open(path).with_context(|| {
+        anyhow!(
+            "Failed to open component WASM at {}",
+            path.display().to_string().log_color_error_highlight()
+        )
+    })?;
+    let mut hasher = blake3::Hasher::new();
+    hasher
+        .update_reader(&file)
+        .context("Failed to hash component")?;
+    Ok(hasher.finalize().to_hex().to_string())
+}
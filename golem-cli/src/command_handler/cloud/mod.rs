@@ -15,6 +15,10 @@
 use crate::command::cloud::CloudSubcommand;
 use crate::command_handler::Handlers;
 use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, LogColorize};
+use crate::model::text::fmt::log_error;
+use anyhow::bail;
 use std::sync::Arc;
 
 pub mod account;
@@ -52,4 +56,33 @@ impl CloudCommandHandler {
             }
         }
     }
+
+    pub async fn cmd_login(&self) -> anyhow::Result<()> {
+        let profile_name = self.ctx.profile_name();
+
+        if profile_name.is_builtin_local() {
+            log_error(format!(
+                "Profile {} is using the local server, login is only available for cloud profiles",
+                profile_name.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let authentication = self
+            .ctx
+            .auth()?
+            .login(profile_name, self.ctx.config_dir())
+            .await?;
+
+        log_action(
+            "Logged in",
+            format!(
+                "with account {}, token saved to profile {}",
+                authentication.account_id().0.log_color_highlight(),
+                profile_name.0.log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
 }
@@ -14,20 +14,23 @@
 
 use crate::command::cloud::project::{ProjectActionsOrPolicyId, ProjectSubcommand};
 use crate::command_handler::Handlers;
+use crate::command_name;
+use crate::config::Config;
 use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
 use crate::error::NonSuccessfulExit;
-use crate::log::{logln, LogColorize};
-use crate::model::project::ProjectView;
-use crate::model::text::fmt::{log_error, log_text_view};
+use crate::log::{log_action, log_warn_action, logln, LogColorize};
+use crate::model::project::{ProjectMemberView, ProjectView};
+use crate::model::text::fmt::{log_error, log_text_view, log_warn};
 use crate::model::text::project::{
-    ProjectCreatedView, ProjectGetView, ProjectGrantView, ProjectListView,
+    ProjectCreatedView, ProjectGetView, ProjectGrantView, ProjectListView, ProjectMemberListView,
 };
 use crate::model::ProjectId;
-use crate::model::{ProjectName, ProjectRefAndId, ProjectReference};
+use crate::model::{AccountId, ProjectGrantId, ProjectName, ProjectRefAndId, ProjectReference};
 use anyhow::{anyhow, bail};
-use golem_client::api::{ProjectClient, ProjectGrantClient};
+use golem_client::api::{AccountClient, ProjectClient, ProjectGrantClient, ProjectPolicyClient};
 use golem_client::model::{Project, ProjectDataRequest, ProjectGrantDataRequest};
+use itertools::Itertools;
 use std::sync::Arc;
 
 pub mod plugin;
@@ -49,7 +52,13 @@ impl CloudProjectCommandHandler {
                 description,
             } => self.cmd_new(project_name, description).await,
             ProjectSubcommand::List { project_name } => self.cmd_list(project_name).await,
+            ProjectSubcommand::Delete { project_reference } => {
+                self.cmd_delete(project_reference).await
+            }
             ProjectSubcommand::GetDefault => self.cmd_get_default().await,
+            ProjectSubcommand::SetDefault { project_reference } => {
+                self.cmd_set_default(project_reference).await
+            }
             ProjectSubcommand::Grant {
                 project_reference,
                 recipient_email,
@@ -62,6 +71,13 @@ impl CloudProjectCommandHandler {
                 )
                 .await
             }
+            ProjectSubcommand::Members { project_reference } => {
+                self.cmd_members(project_reference).await
+            }
+            ProjectSubcommand::Revoke {
+                project_reference,
+                grant_id,
+            } => self.cmd_revoke(project_reference, grant_id).await,
             ProjectSubcommand::Policy { subcommand } => {
                 self.ctx
                     .cloud_project_policy_handler()
@@ -114,17 +130,103 @@ impl CloudProjectCommandHandler {
     }
 
     async fn cmd_get_default(&self) -> anyhow::Result<()> {
-        let project = self
+        let project = match self.ctx.profile_project() {
+            Some(project_reference) => self.project_by_reference(project_reference).await?,
+            None => self
+                .ctx
+                .golem_clients()
+                .await?
+                .project
+                .get_default_project()
+                .await
+                .map_service_error()?,
+        };
+        self.ctx
+            .log_handler()
+            .log_view(&ProjectGetView::from(project));
+        Ok(())
+    }
+
+    async fn cmd_set_default(
+        &self,
+        project_reference: Option<ProjectReference>,
+    ) -> anyhow::Result<()> {
+        let profile_name = self.ctx.profile_name().clone();
+        let Some(mut profile) = Config::get_profile(self.ctx.config_dir(), &profile_name)? else {
+            log_error(format!(
+                "Profile {} is not a global profile, set the project in the application manifest profile instead",
+                profile_name.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+
+        match &project_reference {
+            Some(project_reference) => {
+                // Validates that the project exists and is accessible
+                self.project_by_reference(project_reference).await?;
+
+                log_action(
+                    "Setting",
+                    format!(
+                        "default project of profile {} to {}",
+                        profile_name.0.log_color_highlight(),
+                        project_reference.to_string().log_color_highlight()
+                    ),
+                );
+            }
+            None => {
+                log_action(
+                    "Unsetting",
+                    format!(
+                        "default project of profile {}",
+                        profile_name.0.log_color_highlight()
+                    ),
+                );
+            }
+        }
+
+        profile.profile.config.default_project =
+            project_reference.map(|project_reference| project_reference.to_string());
+        Config::set_profile(profile_name, profile.profile, self.ctx.config_dir())?;
+
+        Ok(())
+    }
+
+    async fn cmd_delete(&self, project_reference: ProjectReference) -> anyhow::Result<()> {
+        let project = self.project_by_reference(&project_reference).await?;
+
+        if !self
             .ctx
+            .interactive_handler()
+            .confirm_delete_project(&project)?
+        {
+            bail!(NonSuccessfulExit);
+        }
+
+        self.ctx
             .golem_clients()
             .await?
             .project
-            .get_default_project()
+            .delete_project(&project.project_id)
             .await
             .map_service_error()?;
-        self.ctx
-            .log_handler()
-            .log_view(&ProjectGetView::from(project));
+
+        log_warn_action(
+            "Deleted",
+            format!(
+                "project {}",
+                project_reference.to_string().log_color_highlight()
+            ),
+        );
+
+        if self.ctx.profile_project() == Some(&project_reference) {
+            log_warn(format!(
+                "The deleted project was the default project of profile {}, use '{} cloud project set-default' to select a new one",
+                self.ctx.profile_name().0.log_color_highlight(),
+                command_name()
+            ));
+        }
+
         Ok(())
     }
 
@@ -261,11 +363,12 @@ impl CloudProjectCommandHandler {
                     grantee_account_id: None,
                     grantee_email: Some(account_email),
                     project_policy_id: actions_or_policy_id.policy_id.map(|id| id.0),
-                    project_actions: actions_or_policy_id
-                        .action
-                        .unwrap_or_default()
-                        .into_iter()
-                        .collect(),
+                    project_actions: match actions_or_policy_id.role {
+                        Some(role) => role.actions(),
+                        None => actions_or_policy_id.action.unwrap_or_default(),
+                    }
+                    .into_iter()
+                    .collect(),
                     project_policy_name: None,
                 },
             )
@@ -276,6 +379,82 @@ impl CloudProjectCommandHandler {
 
         Ok(())
     }
+
+    async fn cmd_members(&self, project_reference: ProjectReference) -> anyhow::Result<()> {
+        let project = self.select_project(&project_reference).await?;
+        let clients = self.ctx.golem_clients().await?;
+
+        let grants = clients
+            .project_grant
+            .get_project_grants(&project.project_id.0)
+            .await
+            .map_service_error()?;
+
+        let mut members = Vec::with_capacity(grants.len());
+        for grant in grants {
+            let policy = clients
+                .project_policy
+                .get_project_policies(&grant.data.project_policy_id)
+                .await
+                .map_service_error()?;
+
+            // The grantee's account is not necessarily visible for the current account,
+            // in that case only the account ID is shown
+            let account_email = clients
+                .account
+                .get_account(&grant.data.grantee_account_id)
+                .await
+                .ok()
+                .map(|account| account.email);
+
+            members.push(ProjectMemberView {
+                grant_id: grant.id,
+                account_id: AccountId(grant.data.grantee_account_id),
+                account_email,
+                policy_id: policy.id,
+                actions: policy
+                    .project_actions
+                    .actions
+                    .iter()
+                    .map(|action| action.to_string())
+                    .sorted()
+                    .collect(),
+            });
+        }
+
+        self.ctx
+            .log_handler()
+            .log_view(&ProjectMemberListView(members));
+
+        Ok(())
+    }
+
+    async fn cmd_revoke(
+        &self,
+        project_reference: ProjectReference,
+        grant_id: ProjectGrantId,
+    ) -> anyhow::Result<()> {
+        let project = self.select_project(&project_reference).await?;
+
+        self.ctx
+            .golem_clients()
+            .await?
+            .project_grant
+            .delete_project_grant(&project.project_id.0, &grant_id.0)
+            .await
+            .map_service_error()?;
+
+        log_warn_action(
+            "Revoked",
+            format!(
+                "project grant {} for project {}",
+                grant_id.0.to_string().log_color_highlight(),
+                project_reference.to_string().log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
 }
 
 fn project_not_found(project_reference: &ProjectReference) -> anyhow::Error {
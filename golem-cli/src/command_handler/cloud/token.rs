@@ -16,14 +16,18 @@ use crate::command::cloud::token::TokenSubcommand;
 use crate::command_handler::Handlers;
 use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
 use crate::log::{log_warn_action, LogColorize};
-use crate::model::text::token::{TokenListView, TokenNewView};
-use crate::model::TokenId;
-use chrono::{DateTime, Utc};
+use crate::model::text::token::{TokenCurrentView, TokenListView, TokenNewView};
+use crate::model::{AccountId, TokenId};
+use anyhow::{anyhow, bail};
+use chrono::{DateTime, Duration, Utc};
 use golem_client::api::TokenClient;
 use golem_client::model::CreateTokenDto;
 use std::sync::Arc;
 
+const DEFAULT_TOKEN_EXPIRES_AT: &str = "2100-01-01T00:00:00Z";
+
 pub struct CloudTokenCommandHandler {
     ctx: Arc<Context>,
 }
@@ -35,18 +39,35 @@ impl CloudTokenCommandHandler {
 
     pub async fn handle_command(&self, subcommand: TokenSubcommand) -> anyhow::Result<()> {
         match subcommand {
-            TokenSubcommand::List => self.cmd_list().await,
-            TokenSubcommand::New { expires_at } => self.cmd_new(expires_at).await,
-            TokenSubcommand::Delete { token_id } => self.cmd_delete(token_id).await,
+            TokenSubcommand::List { account_id } => self.cmd_list(account_id.account_id).await,
+            TokenSubcommand::New {
+                account_id,
+                expires_at,
+                expires_in,
+            } => {
+                self.cmd_new(account_id.account_id, expires_at, expires_in)
+                    .await
+            }
+            TokenSubcommand::Delete {
+                account_id,
+                token_id,
+            } => self.cmd_delete(account_id.account_id, token_id).await,
+            TokenSubcommand::Current => self.cmd_current().await,
         }
     }
 
-    async fn cmd_list(&self) -> anyhow::Result<()> {
+    async fn cmd_list(&self, account_id: Option<AccountId>) -> anyhow::Result<()> {
+        let account_id = self
+            .ctx
+            .cloud_account_handler()
+            .select_account_id_or_err(account_id)
+            .await?;
+
         let clients = self.ctx.golem_clients().await?;
 
         let tokens = clients
             .token
-            .get_tokens(&clients.account_id().0)
+            .get_tokens(&account_id.0)
             .await
             .map_service_error()?;
 
@@ -55,12 +76,31 @@ impl CloudTokenCommandHandler {
         Ok(())
     }
 
-    async fn cmd_new(&self, expires_at: DateTime<Utc>) -> anyhow::Result<()> {
+    async fn cmd_new(
+        &self,
+        account_id: Option<AccountId>,
+        expires_at: Option<DateTime<Utc>>,
+        expires_in: Option<Duration>,
+    ) -> anyhow::Result<()> {
+        let expires_at = match (expires_at, expires_in) {
+            (Some(expires_at), _) => expires_at,
+            (None, Some(expires_in)) => Utc::now()
+                .checked_add_signed(expires_in)
+                .ok_or_else(|| anyhow!("Token expiration is out of range"))?,
+            (None, None) => DEFAULT_TOKEN_EXPIRES_AT.parse()?,
+        };
+
+        let account_id = self
+            .ctx
+            .cloud_account_handler()
+            .select_account_id_or_err(account_id)
+            .await?;
+
         let clients = self.ctx.golem_clients().await?;
 
         let token = clients
             .token
-            .create_token(&clients.account_id().0, &CreateTokenDto { expires_at })
+            .create_token(&account_id.0, &CreateTokenDto { expires_at })
             .await
             .map_service_error()?;
 
@@ -69,12 +109,31 @@ impl CloudTokenCommandHandler {
         Ok(())
     }
 
-    async fn cmd_delete(&self, token_id: TokenId) -> anyhow::Result<()> {
+    async fn cmd_delete(
+        &self,
+        account_id: Option<AccountId>,
+        token_id: TokenId,
+    ) -> anyhow::Result<()> {
+        let account_id = self
+            .ctx
+            .cloud_account_handler()
+            .select_account_id_or_err(account_id)
+            .await?;
+
         let clients = self.ctx.golem_clients().await?;
 
+        if clients.auth_token_details().id == token_id.0
+            && !self
+                .ctx
+                .interactive_handler()
+                .confirm_delete_current_token(self.ctx.profile_name())?
+        {
+            bail!(NonSuccessfulExit);
+        }
+
         clients
             .token
-            .delete_token(&clients.account_id().0, &token_id.0)
+            .delete_token(&account_id.0, &token_id.0)
             .await
             .map_service_error()?;
 
@@ -85,4 +144,34 @@ impl CloudTokenCommandHandler {
 
         Ok(())
     }
+
+    async fn cmd_current(&self) -> anyhow::Result<()> {
+        let clients = self.ctx.golem_clients().await?;
+
+        self.ctx.log_handler().log_view(&TokenCurrentView {
+            profile: self.ctx.profile_name().0.clone(),
+            token: clients.auth_token_details().clone(),
+            secret: masked_secret(self.ctx.show_sensitive(), &clients.auth_token()),
+        });
+
+        Ok(())
+    }
+}
+
+fn masked_secret(show_sensitive: bool, secret: &str) -> String {
+    if show_sensitive {
+        return secret.to_string();
+    }
+
+    let visible_from = secret.len().saturating_sub(4);
+    secret
+        .char_indices()
+        .map(|(idx, c)| {
+            if idx >= visible_from || c == '-' {
+                c
+            } else {
+                '*'
+            }
+        })
+        .collect()
 }
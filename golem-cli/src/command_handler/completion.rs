@@ -0,0 +1,393 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::GolemCliCommand;
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
+use crate::fs;
+use crate::model::component::{Component, ComponentSelection};
+use crate::model::ComponentName;
+use anyhow::bail;
+use chrono::{DateTime, Utc};
+use clap::CommandFactory;
+use clap_complete::Shell;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const COMPLETION_CACHE_DIR_NAME: &str = "completion-cache";
+const COMPLETION_CACHE_TTL_SECONDS: i64 = 60;
+const MAX_COMPLETED_WORKERS: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceKind {
+    Component,
+    Worker,
+    ApiDefinition,
+    Profile,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompletionCacheEntry {
+    created_at: DateTime<Utc>,
+    values: Vec<String>,
+}
+
+pub struct CompletionHandler {
+    ctx: Arc<Context>,
+}
+
+impl CompletionHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    /// Prints the candidates for the last word, or fails if the last word is not a resource name,
+    /// so the completion scripts can fall back to the static completions
+    pub async fn cmd_complete(&self, words: Vec<String>) -> anyhow::Result<()> {
+        let Some((kind, prefix)) = completed_resource(&words) else {
+            bail!(NonSuccessfulExit);
+        };
+
+        let candidates = match kind {
+            ResourceKind::Component => self.cached("components", || self.components()).await?,
+            ResourceKind::Worker => match prefix.split_once('/') {
+                Some((component_name, _)) => self
+                    .cached(&format!("workers-{component_name}"), || {
+                        self.workers(component_name)
+                    })
+                    .await?
+                    .into_iter()
+                    .map(|worker_name| format!("{component_name}/{worker_name}"))
+                    .collect(),
+                None => self
+                    .cached("components", || self.components())
+                    .await?
+                    .into_iter()
+                    .map(|component_name| format!("{component_name}/"))
+                    .collect(),
+            },
+            ResourceKind::ApiDefinition => {
+                self.cached("api-definitions", || self.api_definitions())
+                    .await?
+            }
+            ResourceKind::Profile => self
+                .ctx
+                .available_profile_names()
+                .iter()
+                .map(|profile_name| profile_name.0.clone())
+                .collect(),
+        };
+
+        for candidate in candidates
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(prefix))
+        {
+            println!("{candidate}");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the cached values of the selected profile if they are recent enough,
+    /// otherwise fetches and caches them
+    async fn cached<F, Fut>(&self, key: &str, fetch: F) -> anyhow::Result<Vec<String>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<Vec<String>>>,
+    {
+        let cache_file = self.cache_file(key);
+        if let Some(entry) = fs::read_to_string(&cache_file)
+            .ok()
+            .and_then(|content| serde_json::from_str::<CompletionCacheEntry>(&content).ok())
+        {
+            if (Utc::now() - entry.created_at).num_seconds() < COMPLETION_CACHE_TTL_SECONDS {
+                return Ok(entry.values);
+            }
+        }
+
+        let values = fetch().await?;
+        // Failing to update the cache only makes the next completion slower
+        let _ = serde_json::to_string(&CompletionCacheEntry {
+            created_at: Utc::now(),
+            values: values.clone(),
+        })
+        .map_err(anyhow::Error::from)
+        .and_then(|content| fs::write_str(&cache_file, content));
+
+        Ok(values)
+    }
+
+    fn cache_file(&self, key: &str) -> PathBuf {
+        let sanitize = |name: &str| {
+            name.chars()
+                .map(|char| {
+                    if char.is_ascii_alphanumeric() || char == '-' {
+                        char
+                    } else {
+                        '_'
+                    }
+                })
+                .collect::<String>()
+        };
+        self.ctx
+            .config_dir()
+            .join(COMPLETION_CACHE_DIR_NAME)
+            .join(sanitize(&self.ctx.profile_name().0))
+            .join(format!("{}.json", sanitize(key)))
+    }
+
+    async fn components(&self) -> anyhow::Result<Vec<String>> {
+        let clients = self.ctx.golem_clients().await?;
+        Ok(clients
+            .component
+            .get_components(None, None)
+            .await
+            .map_service_error()?
+            .into_iter()
+            .map(|component| Component::from(component).component_name.0)
+            .sorted()
+            .dedup()
+            .collect())
+    }
+
+    async fn workers(&self, component_name: &str) -> anyhow::Result<Vec<String>> {
+        let component_name = ComponentName::from(component_name);
+        let Some(component) = self
+            .ctx
+            .component_handler()
+            .component(None, ComponentSelection::Name(&component_name), None)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+        let (workers, _) = self
+            .ctx
+            .worker_handler()
+            .list_component_workers(
+                &component_name,
+                component.versioned_component_id.component_id,
+                None,
+                None,
+                Some(MAX_COMPLETED_WORKERS),
+                false,
+            )
+            .await?;
+        Ok(workers
+            .into_iter()
+            .map(|worker| worker.worker_id.worker_name)
+            .sorted()
+            .collect())
+    }
+
+    async fn api_definitions(&self) -> anyhow::Result<Vec<String>> {
+        let project_id = self
+            .ctx
+            .cloud_project_handler()
+            .selected_project_id_or_default(None)
+            .await?;
+        let clients = self.ctx.golem_clients().await?;
+        Ok(clients
+            .api_definition
+            .list_definitions(&project_id.0, None)
+            .await
+            .map_service_error()?
+            .into_iter()
+            .map(|api_definition| api_definition.id)
+            .sorted()
+            .dedup()
+            .collect())
+    }
+}
+
+/// Finds the argument completed by the last word, and returns its resource kind with the
+/// already typed prefix
+fn completed_resource(words: &[String]) -> Option<(ResourceKind, &str)> {
+    let (current, previous) = words.split_last()?;
+    if current.starts_with('-') {
+        return None;
+    }
+
+    let mut command = GolemCliCommand::command();
+    command.build();
+
+    let mut command = &command;
+    let mut path = Vec::<&str>::new();
+    let mut positional_idx = 0;
+    let mut words = previous.iter();
+    while let Some(word) = words.next() {
+        if word == "--" {
+            continue;
+        }
+        let option = if let Some(long) = word.strip_prefix("--") {
+            (!long.contains('=')).then(|| {
+                command
+                    .get_arguments()
+                    .find(|arg| arg.get_long() == Some(long))
+            })
+        } else if let Some(short) = word.strip_prefix('-').filter(|short| short.len() == 1) {
+            let short = short.chars().next();
+            Some(command.get_arguments().find(|arg| arg.get_short() == short))
+        } else {
+            None
+        };
+
+        match option {
+            Some(Some(arg)) if arg.get_action().takes_values() => match words.next() {
+                Some(_) => {}
+                None => {
+                    return resource_kind(&path, arg.get_id().as_str())
+                        .map(|kind| (kind, current.as_str()))
+                }
+            },
+            Some(_) => {}
+            None if word.starts_with('-') => {}
+            None => match command.find_subcommand(word) {
+                Some(subcommand) => {
+                    command = subcommand;
+                    path.push(subcommand.get_name());
+                    positional_idx = 0;
+                }
+                None => positional_idx += 1,
+            },
+        }
+    }
+
+    if command.has_subcommands() {
+        return None;
+    }
+
+    let positionals = command.get_positionals().collect::<Vec<_>>();
+    let arg = match positionals.get(positional_idx) {
+        Some(arg) => *arg,
+        None => *positionals.last().filter(|arg| {
+            arg.get_num_args()
+                .is_some_and(|num_args| num_args.max_values() > 1)
+        })?,
+    };
+
+    resource_kind(&path, arg.get_id().as_str()).map(|kind| (kind, current.as_str()))
+}
+
+fn resource_kind(path: &[&str], arg_id: &str) -> Option<ResourceKind> {
+    match arg_id {
+        "component_name" => Some(ResourceKind::Component),
+        "worker_name" => Some(ResourceKind::Worker),
+        "profile" | "profile_name" | "base_profile_name" => Some(ResourceKind::Profile),
+        "id" | "definition" if path.first() == Some(&"api") => Some(ResourceKind::ApiDefinition),
+        _ => None,
+    }
+}
+
+/// Shell specific script, which completes resource names by calling the hidden complete
+/// command, and falls back to the static completions
+pub fn dynamic_completion_script(shell: Shell, command_name: &str) -> Option<String> {
+    let fn_prefix = command_name.replace(|char: char| !char.is_ascii_alphanumeric(), "_");
+    match shell {
+        Shell::Bash => Some(format!(
+            r#"
+_{fn_prefix}_static_completion=$(complete -p {command_name} 2>/dev/null | sed -E 's/.*-F ([^ ]+).*/\1/')
+
+_{fn_prefix}_dynamic_completion() {{
+    local cur words cword candidates
+    if declare -F _get_comp_words_by_ref >/dev/null 2>&1; then
+        _get_comp_words_by_ref -n := cur words cword
+    else
+        cur="${{COMP_WORDS[COMP_CWORD]}}"
+        words=("${{COMP_WORDS[@]}}")
+        cword=$COMP_CWORD
+    fi
+    if candidates=$("${{words[0]}}" __complete -- "${{words[@]:1:cword}}" 2>/dev/null); then
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$candidates" -- "$cur"))
+        if declare -F __ltrim_colon_completions >/dev/null 2>&1; then
+            __ltrim_colon_completions "$cur"
+        fi
+        return 0
+    fi
+    "$_{fn_prefix}_static_completion" "$@"
+}}
+
+complete -F _{fn_prefix}_dynamic_completion -o nosort -o bashdefault -o default {command_name}
+"#
+        )),
+        Shell::Zsh => Some(format!(
+            r#"
+_{fn_prefix}_dynamic_completion() {{
+    local output
+    if output="$("${{words[1]}}" __complete -- "${{(@)words[2,CURRENT]}}" 2>/dev/null)"; then
+        local -a candidates
+        candidates=("${{(@f)output}}")
+        compadd -- "${{candidates[@]}}"
+        return
+    fi
+    _{command_name} "$@"
+}}
+
+compdef _{fn_prefix}_dynamic_completion {command_name}
+"#
+        )),
+        Shell::Fish => Some(format!(
+            r#"
+function __{fn_prefix}_dynamic_completion
+    set -l words (commandline -opc) (commandline -ct)
+    {command_name} __complete -- $words[2..-1] 2>/dev/null
+end
+
+complete -c {command_name} -n '__{fn_prefix}_dynamic_completion >/dev/null' -f -a '(__{fn_prefix}_dynamic_completion)'
+"#
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::completion::{completed_resource, ResourceKind};
+    use assert2::assert;
+    use test_r::test;
+
+    fn complete(line: &str) -> Option<(ResourceKind, String)> {
+        let mut words = line
+            .split_whitespace()
+            .map(|word| word.to_string())
+            .collect::<Vec<_>>();
+        if line.ends_with(' ') {
+            words.push(String::new());
+        }
+        completed_resource(&words).map(|(kind, prefix)| (kind, prefix.to_string()))
+    }
+
+    #[test]
+    fn completes_resource_arguments() {
+        assert!(
+            complete("component get shop") == Some((ResourceKind::Component, "shop".to_string()))
+        );
+        assert!(complete("worker invoke ") == Some((ResourceKind::Worker, "".to_string())));
+        assert!(complete("--profile ") == Some((ResourceKind::Profile, "".to_string())));
+        assert!(complete("profile switch lo") == Some((ResourceKind::Profile, "lo".to_string())));
+    }
+
+    #[test]
+    fn does_not_complete_other_arguments() {
+        assert!(complete("comp").is_none());
+        assert!(complete("component ").is_none());
+        assert!(complete("worker invoke shop/cart-1 ").is_none());
+        assert!(complete("component get --").is_none());
+    }
+}
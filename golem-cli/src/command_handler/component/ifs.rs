@@ -48,6 +48,17 @@ pub struct ComponentFilesArchive {
     _temp_dir: TempDir, // archive_path is only valid as long as this is alive
 }
 
+impl ComponentFilesArchive {
+    pub async fn open(&self) -> anyhow::Result<File> {
+        File::open(&self.archive_path).await.with_context(|| {
+            anyhow!(
+                "Failed to open IFS archive: {}",
+                self.archive_path.display()
+            )
+        })
+    }
+}
+
 pub struct IfsFileManager {
     client: reqwest::Client,
 }
@@ -12,17 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app::build::command::execute_external_command;
 use crate::app::build::task_result_marker::{
     GetServerComponentHash, GetServerIfsFileHash, TaskResultMarker,
 };
 use crate::app::context::ApplicationContext;
+use crate::app::template_repo::{new_component_template_context, TemplateRepo, TemplateRepoSource};
 use crate::app::yaml_edit::AppYamlEditor;
 use crate::command::component::ComponentSubcommand;
 use crate::command::shared_args::{
-    BuildArgs, ComponentOptionalComponentNames, ComponentTemplateName, ForceBuildArg,
+    BuildArgs, ComponentOptionalComponentNames, ComponentTemplateName, ForceBuildArg, ProfilesArg,
     UpdateOrRedeployArgs,
 };
 use crate::command_handler::component::ifs::IfsFileManager;
+use crate::command_handler::component::upload::{upload_body, upload_with_retry, UploadProgress};
 use crate::command_handler::Handlers;
 use crate::context::Context;
 use crate::error::service::AnyhowMapServiceError;
@@ -31,12 +34,16 @@ use crate::log::{
     log_action, log_skipping_up_to_date, log_warn_action, logln, LogColorize, LogIndent,
 };
 use crate::model::app::{
-    AppComponentName, ApplicationComponentSelectMode, BuildProfileName, DynamicHelpSections,
+    AppBuildStep, AppComponentName, ApplicationComponentSelectMode, BuildProfileName,
+    DynamicHelpSections,
 };
 use crate::model::app::{DependencyType, InitialComponentFile};
-use crate::model::component::{Component, ComponentSelection, ComponentView};
+use crate::model::app_raw;
+use crate::model::ci::CiChange;
+use crate::model::component::{Component, ComponentSelection, ComponentView, ProfileComponentView};
 use crate::model::deploy::TryUpdateAllWorkersResult;
 use crate::model::deploy_diff::component::{DiffableComponent, DiffableComponentFile};
+use crate::model::template::{template_env, Template};
 use crate::model::text::component::{ComponentCreateView, ComponentGetView, ComponentUpdateView};
 use crate::model::text::fmt::{log_deploy_diff, log_error, log_text_view, log_warn};
 use crate::model::text::help::ComponentNameHelp;
@@ -60,15 +67,15 @@ use golem_templates::add_component_by_template;
 use golem_templates::model::{GuestLanguage, PackageName};
 use itertools::Itertools;
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::fs::File;
 use tracing::debug;
 use url::Url;
 
 pub mod ifs;
 pub mod plugin;
 pub mod plugin_installation;
+pub mod upload;
 
 pub struct ComponentCommandHandler {
     ctx: Arc<Context>,
@@ -84,7 +91,11 @@ impl ComponentCommandHandler {
             ComponentSubcommand::New {
                 component_template,
                 component_name,
-            } => self.cmd_new(component_template, component_name).await,
+                template_repo,
+            } => {
+                self.cmd_new(component_template, component_name, template_repo)
+                    .await
+            }
             ComponentSubcommand::Templates { filter } => {
                 self.cmd_templates(filter);
                 Ok(())
@@ -93,6 +104,9 @@ impl ComponentCommandHandler {
                 component_name,
                 build: build_args,
             } => self.cmd_build(component_name, build_args).await,
+            ComponentSubcommand::Compose { component_name } => {
+                self.cmd_compose(component_name).await
+            }
             ComponentSubcommand::Deploy {
                 component_name,
                 force_build,
@@ -118,8 +132,16 @@ impl ComponentCommandHandler {
                 )
                 .await
             }
-            ComponentSubcommand::List { component_name } => {
-                self.cmd_list(component_name.component_name).await
+            ComponentSubcommand::List {
+                component_name,
+                profiles,
+            } => {
+                if profiles.is_multi_profile() {
+                    self.cmd_list_multi_profile(component_name.component_name, profiles)
+                        .await
+                } else {
+                    self.cmd_list(component_name.component_name).await
+                }
             }
             ComponentSubcommand::Get {
                 component_name,
@@ -154,6 +176,7 @@ impl ComponentCommandHandler {
         &self,
         template: Option<ComponentTemplateName>,
         component_package_name: Option<PackageName>,
+        template_repo: Option<TemplateRepoSource>,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
 
@@ -176,6 +199,7 @@ impl ComponentCommandHandler {
                 (Some(template), Some(component_package_name)) => {
                     Some((template, component_package_name))
                 }
+                _ if template_repo.is_some() => None,
                 _ => self
                     .ctx
                     .interactive_handler()
@@ -205,19 +229,42 @@ impl ComponentCommandHandler {
             bail!(NonSuccessfulExit)
         }
 
-        let app_handler = self.ctx.app_handler();
-        let (common_template, component_template) = app_handler.get_template(&template)?;
+        let result = match &template_repo {
+            Some(template_repo) => {
+                let template_repo = {
+                    let app_ctx = self.ctx.app_context_lock().await;
+                    let app_ctx = app_ctx.some_or_err()?;
+                    TemplateRepo::fetch(
+                        template_repo,
+                        &app_ctx.application.temp_dir().join("template-repos"),
+                        app_ctx.config.offline,
+                    )?
+                };
+
+                // Unloading app context, so we can reload after the new component is created
+                self.ctx.unload_app_context().await;
+
+                template_repo.instantiate(&template, Path::new("."), &component_package_name)
+            }
+            None => {
+                let app_handler = self.ctx.app_handler();
+                let (common_template, component_template) = app_handler.get_template(&template)?;
+
+                // Unloading app context, so we can reload after the new component is created
+                self.ctx.unload_app_context().await;
 
-        // Unloading app context, so we can reload after the new component is created
-        self.ctx.unload_app_context().await;
+                add_component_by_template(
+                    common_template,
+                    Some(component_template),
+                    &PathBuf::from("."),
+                    &component_package_name,
+                )
+                .map(|()| component_template.post_generate.clone())
+            }
+        };
 
-        match add_component_by_template(
-            common_template,
-            Some(component_template),
-            &PathBuf::from("."),
-            &component_package_name,
-        ) {
-            Ok(()) => {
+        let post_generate_commands = match result {
+            Ok(post_generate_commands) => {
                 log_action(
                     "Added",
                     format!(
@@ -227,15 +274,87 @@ impl ComponentCommandHandler {
                             .log_color_highlight()
                     ),
                 );
+                post_generate_commands
             }
             Err(error) => {
                 bail!("Failed to create new app component: {}", error)
             }
-        }
+        };
+
+        let post_generate_commands = {
+            let template_env = template_env();
+            let template_ctx = new_component_template_context(&component_package_name);
+            post_generate_commands
+                .into_iter()
+                .map(|command| {
+                    command
+                        .render(&template_env, &template_ctx)
+                        .with_context(|| {
+                            anyhow!("Failed to render post-generate command {command}")
+                        })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?
+        };
+
+        // Commands of external template repositories are only executed after confirmation
+        let post_generate_commands = match &template_repo {
+            Some(template_repo)
+                if !post_generate_commands.is_empty()
+                    && !self
+                        .ctx
+                        .interactive_handler()
+                        .confirm_template_post_generate_commands(
+                            template_repo,
+                            &post_generate_commands,
+                        )? =>
+            {
+                log_warn_action(
+                    "Skipping",
+                    format!(
+                        "post-generate commands for {}",
+                        component_name.as_str().log_color_highlight()
+                    ),
+                );
+                vec![]
+            }
+            _ => post_generate_commands,
+        };
 
         let app_ctx = self.ctx.app_context_lock().await;
         let app_ctx = app_ctx.some_or_err()?;
 
+        if !post_generate_commands.is_empty() {
+            log_action(
+                "Executing",
+                format!(
+                    "post-generate commands for {}",
+                    component_name.as_str().log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
+
+            for command in post_generate_commands {
+                execute_external_command(
+                    app_ctx,
+                    app_ctx.application.component_source_dir(&component_name),
+                    Some(&component_name),
+                    &app_raw::ExternalCommand {
+                        command,
+                        dir: None,
+                        rmdirs: vec![],
+                        mkdirs: vec![],
+                        sources: vec![],
+                        targets: vec![],
+                        depends_on: vec![],
+                        env: HashMap::new(),
+                        when: None,
+                    },
+                    HashMap::new(),
+                )
+                .context("Failed to execute post-generate command")?;
+            }
+        }
+
         logln("");
         app_ctx.log_dynamic_help(&DynamicHelpSections::show_components())?;
 
@@ -257,6 +376,33 @@ impl ComponentCommandHandler {
             .await
     }
 
+    async fn cmd_compose(
+        &self,
+        component_name: ComponentOptionalComponentNames,
+    ) -> anyhow::Result<()> {
+        // Linking is forced, so the composition graph is always shown
+        self.ctx
+            .app_handler()
+            .build(
+                component_name.component_name,
+                Some(BuildArgs {
+                    step: vec![
+                        AppBuildStep::Link,
+                        AppBuildStep::Optimize,
+                        AppBuildStep::AddMetadata,
+                    ],
+                    force_build: ForceBuildArg { force_build: true },
+                    frozen: false,
+                    backup: false,
+                    no_cache: false,
+                    jobs: None,
+                    plan: false,
+                }),
+                &ApplicationComponentSelectMode::CurrentDir,
+            )
+            .await
+    }
+
     async fn cmd_clean(
         &self,
         component_name: ComponentOptionalComponentNames,
@@ -310,10 +456,86 @@ impl ComponentCommandHandler {
     }
 
     async fn cmd_list(&self, component_name: Option<ComponentName>) -> anyhow::Result<()> {
+        let component_views = self.list_components(component_name.as_ref()).await?;
+
+        if component_views.is_empty() && component_name.is_some() {
+            // Retry selection (this time with not allowing "not founds")
+            // so we get error messages for app component names.
+            self.ctx
+                .app_handler()
+                .opt_select_components(
+                    component_name.iter().cloned().collect(),
+                    &ApplicationComponentSelectMode::CurrentDir,
+                )
+                .await?;
+        }
+
+        if component_views.is_empty() {
+            bail!(NonSuccessfulExit)
+        } else {
+            self.ctx.log_handler().log_view(&component_views);
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_list_multi_profile(
+        &self,
+        component_name: Option<ComponentName>,
+        profiles: ProfilesArg,
+    ) -> anyhow::Result<()> {
+        let contexts = self.ctx.profile_contexts(&profiles).await?;
+
+        let results = self
+            .ctx
+            .request_executor()
+            .run(&contexts, |ctx| {
+                let component_name = component_name.as_ref();
+                async move {
+                    ctx.component_handler()
+                        .list_components(component_name)
+                        .await
+                }
+            })
+            .await;
+
+        let mut component_views = Vec::<ProfileComponentView>::new();
+        let mut any_failed = false;
+        for (ctx, result) in contexts.iter().zip(results) {
+            match result {
+                Ok(components) => component_views.extend(components.into_iter().map(|component| {
+                    ProfileComponentView {
+                        profile: ctx.profile_name().clone(),
+                        component,
+                    }
+                })),
+                Err(error) => {
+                    any_failed = true;
+                    log_error(format!(
+                        "Failed to list components for profile {}: {error:#}",
+                        ctx.profile_name().0.log_color_highlight()
+                    ));
+                }
+            }
+        }
+
+        self.ctx.log_handler().log_view(&component_views);
+
+        if any_failed {
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    async fn list_components(
+        &self,
+        component_name: Option<&ComponentName>,
+    ) -> anyhow::Result<Vec<ComponentView>> {
         let show_sensitive = self.ctx.show_sensitive();
 
         let selected_component_names = self
-            .opt_select_components_by_app_dir_or_name(component_name.as_ref())
+            .opt_select_components_by_app_dir_or_name(component_name)
             .await?;
 
         let mut component_views = Vec::<ComponentView>::new();
@@ -365,25 +587,7 @@ impl ComponentCommandHandler {
             }
         }
 
-        if component_views.is_empty() && component_name.is_some() {
-            // Retry selection (this time with not allowing "not founds")
-            // so we get error messages for app component names.
-            self.ctx
-                .app_handler()
-                .opt_select_components(
-                    component_name.iter().cloned().collect(),
-                    &ApplicationComponentSelectMode::CurrentDir,
-                )
-                .await?;
-        }
-
-        if component_views.is_empty() {
-            bail!(NonSuccessfulExit)
-        } else {
-            self.ctx.log_handler().log_view(&component_views);
-        }
-
-        Ok(())
+        Ok(component_views)
     }
 
     async fn cmd_get(
@@ -598,64 +802,163 @@ impl ComponentCommandHandler {
                 force_build.map(|force_build| BuildArgs {
                     step: vec![],
                     force_build,
+                    frozen: false,
+                    backup: false,
+                    no_cache: false,
+                    jobs: None,
+                    plan: false,
                 }),
                 default_component_select_mode,
             )
             .await?;
 
-        let selected_component_names = {
-            let app_ctx = self.ctx.app_context_lock().await;
-            app_ctx
-                .some_or_err()?
-                .selected_component_names()
-                .iter()
-                .cloned()
-                .collect::<Vec<_>>()
-        };
+        let components = self.deploy_selected_components(project).await?;
+
+        self.update_or_redeploy_workers_by_components(&components, update_or_redeploy)
+            .await?;
+
+        Ok(components)
+    }
+
+    /// Deploys the already built, selected and deployable components of the application
+    pub async fn deploy_selected_components(
+        &self,
+        project: Option<&ProjectRefAndId>,
+    ) -> anyhow::Result<Vec<Component>> {
+        let selected_component_names = self.selected_deployable_component_names().await?;
         let build_profile = self.ctx.build_profile().cloned();
 
         let plugin_installation_handler = self.ctx.plugin_installation_handler();
 
-        let components = {
-            log_action("Deploying", "components");
-            let _indent = LogIndent::new();
+        log_action("Deploying", "components");
+        let _indent = LogIndent::new();
 
-            let mut components = Vec::with_capacity(selected_component_names.len());
-            for component_name in &selected_component_names {
-                let app_ctx = self.ctx.app_context_lock().await;
-                if app_ctx
-                    .some_or_err()?
-                    .application
-                    .component_properties(component_name, build_profile.as_ref())
-                    .is_deployable()
-                {
-                    drop(app_ctx);
+        let cancellation = self.ctx.cancellation();
+        let _scope = cancellation.scope();
 
-                    let component = self
-                        .deploy_component(build_profile.as_ref(), project, component_name)
-                        .await?;
-                    let component = plugin_installation_handler
-                        .apply_plugin_installation_changes(
-                            component_name,
-                            build_profile.as_ref(),
-                            component,
-                        )
-                        .await?;
-                    components.push(component);
-                }
+        let mut components = Vec::with_capacity(selected_component_names.len());
+        for component_name in &selected_component_names {
+            if cancellation.is_cancelled() {
+                cancellation.log_cancelled(
+                    "deploying components",
+                    components.len(),
+                    selected_component_names.len(),
+                );
+                break;
             }
 
-            components
-        };
+            let component = self
+                .deploy_component(build_profile.as_ref(), project, component_name)
+                .await?;
+            let component = plugin_installation_handler
+                .apply_plugin_installation_changes(
+                    component_name,
+                    build_profile.as_ref(),
+                    component,
+                )
+                .await?;
+            components.push(component);
+        }
+        cancellation.check()?;
+
+        Ok(components)
+    }
 
+    pub async fn update_or_redeploy_workers_by_components(
+        &self,
+        components: &[Component],
+        update_or_redeploy: &UpdateOrRedeployArgs,
+    ) -> anyhow::Result<()> {
         if let Some(update) = update_or_redeploy.update_workers {
-            self.update_workers_by_components(&components, update, true)
-                .await?;
+            self.update_workers_by_components(components, update, true)
+                .await
         } else if update_or_redeploy.redeploy_workers(self.ctx.update_or_redeploy()) {
-            self.redeploy_workers_by_components(&components).await?;
+            self.redeploy_workers_by_components(components).await
+        } else {
+            Ok(())
         }
+    }
 
-        Ok(components)
+    pub async fn selected_deployable_component_names(
+        &self,
+    ) -> anyhow::Result<Vec<AppComponentName>> {
+        let build_profile = self.ctx.build_profile().cloned();
+        let app_ctx = self.ctx.app_context_lock().await;
+        let app_ctx = app_ctx.some_or_err()?;
+        Ok(app_ctx
+            .selected_component_names()
+            .iter()
+            .filter(|component_name| {
+                app_ctx
+                    .application
+                    .component_properties(component_name, build_profile.as_ref())
+                    .is_deployable()
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// Compares the built component with the deployed one without deploying it, and logs
+    /// the differences
+    pub async fn component_deploy_change(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        component_name: &AppComponentName,
+    ) -> anyhow::Result<CiChange> {
+        let build_profile = self.ctx.build_profile().cloned();
+        let deploy_properties = {
+            let mut app_ctx = self.ctx.app_context_lock_mut().await?;
+            let app_ctx = app_ctx.some_or_err_mut()?;
+            component_deploy_properties(app_ctx, component_name, build_profile.as_ref())?
+        };
+
+        let Some(server_component) = self
+            .component(
+                project,
+                (&ComponentName::from(component_name.as_str())).into(),
+                None,
+            )
+            .await?
+        else {
+            log_warn_action(
+                "Found",
+                format!(
+                    "new component {}",
+                    component_name.as_str().log_color_highlight()
+                ),
+            );
+            return Ok(CiChange::New);
+        };
+
+        let manifest_diffable_component = self
+            .manifest_diffable_component(component_name, &deploy_properties)
+            .await?;
+        let server_diffable_component = self
+            .server_diffable_component(project, &server_component)
+            .await?;
+
+        if server_diffable_component == manifest_diffable_component {
+            log_action(
+                "Checked",
+                format!(
+                    "component {}, {}",
+                    component_name.as_str().log_color_highlight(),
+                    "UP-TO-DATE".log_color_ok_highlight()
+                ),
+            );
+            Ok(CiChange::Unchanged)
+        } else {
+            log_warn_action(
+                "Found",
+                format!(
+                    "changes for component {}",
+                    component_name.as_str().log_color_highlight()
+                ),
+            );
+            let _indent = self.ctx.log_handler().nested_text_view_indent();
+            log_deploy_diff(&server_diffable_component, &manifest_diffable_component)?;
+            Ok(CiChange::Changed)
+        }
     }
 
     async fn deploy_component(
@@ -679,6 +982,9 @@ impl ComponentCommandHandler {
         let component_id = server_component
             .as_ref()
             .map(|c| c.versioned_component_id.component_id);
+        let server_component_version = server_component
+            .as_ref()
+            .map(|c| c.versioned_component_id.version);
 
         let manifest_diffable_component = self
             .manifest_diffable_component(component_name, &deploy_properties)
@@ -711,7 +1017,7 @@ impl ComponentCommandHandler {
             }
         }
 
-        let linked_wasm = File::open(&deploy_properties.linked_wasm_path)
+        let linked_wasm_size = tokio::fs::metadata(&deploy_properties.linked_wasm_path)
             .await
             .with_context(|| {
                 anyhow!(
@@ -722,7 +1028,8 @@ impl ComponentCommandHandler {
                         .to_string()
                         .log_color_error_highlight()
                 )
-            })?;
+            })?
+            .len();
 
         let ifs_files = {
             if !deploy_properties.files.is_empty() {
@@ -735,19 +1042,8 @@ impl ComponentCommandHandler {
                 None
             }
         };
-        let ifs_properties = ifs_files.as_ref().map(|f| &f.properties);
-        let ifs_archive = {
-            if let Some(files) = ifs_files.as_ref() {
-                Some(File::open(&files.archive_path).await.with_context(|| {
-                    anyhow!(
-                        "Failed to open IFS archive: {}",
-                        files.archive_path.display()
-                    )
-                })?)
-            } else {
-                None
-            }
-        };
+        let ifs_files = ifs_files.as_ref();
+        let ifs_properties = ifs_files.map(|f| &f.properties);
 
         // TODO: to be sent to component service
         let agent_types: Option<Vec<AgentType>> = {
@@ -766,6 +1062,35 @@ impl ComponentCommandHandler {
                 None
             }
         };
+        let agent_types = agent_types.map(|types| AgentTypes { types });
+        let agent_types = agent_types.as_ref();
+        let env = deploy_properties
+            .env
+            .clone()
+            .map(|env| ComponentEnvCloud { key_values: env });
+        let env = env.as_ref();
+        let linked_wasm_path = deploy_properties.linked_wasm_path.as_path();
+        let component_type = &deploy_properties.component_type;
+        let dynamic_linking = deploy_properties.dynamic_linking.as_ref();
+
+        let upload_progress = Arc::new(UploadProgress::new(
+            format!("Uploading {}", component_name.as_str()),
+            linked_wasm_size,
+        ));
+        let server_component_name = ComponentName::from(component_name.as_str());
+        let server_component_name = &server_component_name;
+        // A failed upload attempt might still have been committed by the server, which is
+        // recognized by a new component version with the uploaded size
+        let committed_upload = move || async move {
+            Ok::<_, anyhow::Error>(
+                self.component(project, server_component_name.into(), None)
+                    .await?
+                    .filter(|component| {
+                        Some(component.versioned_component_id.version) > server_component_version
+                            && component.component_size == linked_wasm_size
+                    }),
+            )
+        };
 
         let component = match component_id {
             Some(component_id) => {
@@ -778,28 +1103,42 @@ impl ComponentCommandHandler {
                 );
 
                 let clients = self.ctx.golem_clients().await?;
-
-                let component = {
-                    let component = clients
-                        .component
-                        .update_component(
-                            &component_id,
-                            Some(&deploy_properties.component_type),
-                            linked_wasm,
-                            ifs_properties,
-                            ifs_archive,
-                            deploy_properties.dynamic_linking.as_ref(),
-                            deploy_properties
-                                .env
-                                .map(|env| ComponentEnvCloud { key_values: env })
-                                .as_ref(),
-                            agent_types.map(|types| AgentTypes { types }).as_ref(),
-                        )
-                        .await
-                        .map_service_error()?;
-
-                    Component::from(component)
-                };
+                let component_id = &component_id;
+
+                let component = upload_with_retry(
+                    self.ctx.upload_retry_policy(),
+                    || {
+                        let upload_progress = upload_progress.clone();
+                        async move {
+                            let linked_wasm =
+                                upload_body(linked_wasm_path, upload_progress).await?;
+                            let ifs_archive = match ifs_files {
+                                Some(files) => Some(files.open().await?),
+                                None => None,
+                            };
+                            let component = clients
+                                .component
+                                .update_component(
+                                    component_id,
+                                    Some(component_type),
+                                    linked_wasm,
+                                    ifs_properties,
+                                    ifs_archive,
+                                    dynamic_linking,
+                                    env,
+                                    agent_types,
+                                )
+                                .await
+                                .map_service_error()?;
+
+                            Ok::<_, anyhow::Error>(Component::from(component))
+                        }
+                    },
+                    committed_upload,
+                )
+                .await;
+                upload_progress.finish();
+                let component = component?;
 
                 self.ctx
                     .log_handler()
@@ -818,30 +1157,46 @@ impl ComponentCommandHandler {
                     ),
                 );
                 let clients = self.ctx.golem_clients().await?;
-
-                let component = {
-                    let component = clients
-                        .component
-                        .create_component(
-                            &ComponentQuery {
-                                project_id: project.map(|p| p.project_id.0),
-                                component_name: component_name.to_string(),
-                            },
-                            linked_wasm,
-                            Some(&deploy_properties.component_type),
-                            ifs_properties,
-                            ifs_archive,
-                            deploy_properties.dynamic_linking.as_ref(),
-                            deploy_properties
-                                .env
-                                .map(|env| ComponentEnvCloud { key_values: env })
-                                .as_ref(),
-                            agent_types.map(|types| AgentTypes { types }).as_ref(),
-                        )
-                        .await
-                        .map_service_error()?;
-                    Component::from(component)
+                let component_query = ComponentQuery {
+                    project_id: project.map(|p| p.project_id.0),
+                    component_name: component_name.to_string(),
                 };
+                let component_query = &component_query;
+
+                let component = upload_with_retry(
+                    self.ctx.upload_retry_policy(),
+                    || {
+                        let upload_progress = upload_progress.clone();
+                        async move {
+                            let linked_wasm =
+                                upload_body(linked_wasm_path, upload_progress).await?;
+                            let ifs_archive = match ifs_files {
+                                Some(files) => Some(files.open().await?),
+                                None => None,
+                            };
+                            let component = clients
+                                .component
+                                .create_component(
+                                    component_query,
+                                    linked_wasm,
+                                    Some(component_type),
+                                    ifs_properties,
+                                    ifs_archive,
+                                    dynamic_linking,
+                                    env,
+                                    agent_types,
+                                )
+                                .await
+                                .map_service_error()?;
+
+                            Ok::<_, anyhow::Error>(Component::from(component))
+                        }
+                    },
+                    committed_upload,
+                )
+                .await;
+                upload_progress.finish();
+                let component = component?;
 
                 self.ctx
                     .log_handler()
@@ -914,8 +1269,15 @@ impl ComponentCommandHandler {
         log_action("Updating", format!("existing workers using {update} mode"));
         let _indent = LogIndent::new();
 
+        let cancellation = self.ctx.cancellation();
+        let _scope = cancellation.scope();
+
         let mut update_results = TryUpdateAllWorkersResult::default();
         for component in components {
+            if cancellation.is_cancelled() {
+                break;
+            }
+
             let result = self
                 .ctx
                 .worker_handler()
@@ -931,7 +1293,7 @@ impl ComponentCommandHandler {
         }
 
         self.ctx.log_handler().log_view(&update_results);
-        Ok(())
+        cancellation.check()
     }
 
     pub async fn redeploy_workers_by_components(
@@ -1638,7 +2000,7 @@ fn app_component_dynamic_linking(
         .component_dependencies(component_name)
         .iter()
         .filter(|dep| dep.dep_type == DependencyType::DynamicWasmRpc)
-        .filter_map(|dep| dep.as_dependent_app_component())
+        .filter_map(|dep| dep.as_rpc_client_target())
         .collect::<Vec<_>>();
 
     for wasm_rpc_dep in wasm_rpc_deps {
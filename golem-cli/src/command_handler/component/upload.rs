@@ -0,0 +1,223 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Component WASM uploads with progress reporting and whole-upload retries.
+//!
+//! Not supported, as the component service has no API for them:
+//! - chunked or resumable uploads: the service only accepts the WASM as a single multipart
+//!   field, and has no upload sessions or range requests, so a failed upload restarts from
+//!   zero. Instead the whole upload is retried, and before each retry the caller can check
+//!   whether the failed attempt was actually committed by the server, so a retry never creates
+//!   a duplicate component or component version.
+//! - compression (`--compression zstd`): the service does not decode a `Content-Encoding` on
+//!   requests, and does not advertise accepted encodings, so compression cannot be gated on
+//!   server support.
+
+use crate::error::service::ServiceError;
+use crate::http_proxy::{jitter, RetryPolicy};
+use crate::log::{log_warn_action, LogColorize};
+use anyhow::{anyhow, Context};
+use bytes::Bytes;
+use std::future::Future;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+pub const DEFAULT_UPLOAD_RETRIES: u32 = 3;
+
+const UPLOAD_CHUNK_SIZE: usize = 256 * 1024;
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+pub struct UploadProgress {
+    label: String,
+    total: u64,
+    sent: AtomicU64,
+    rendered_percent: AtomicU64,
+    enabled: bool,
+}
+
+impl UploadProgress {
+    pub fn new(label: String, total: u64) -> Self {
+        Self {
+            label,
+            total,
+            sent: AtomicU64::new(0),
+            rendered_percent: AtomicU64::new(u64::MAX),
+            enabled: std::io::stderr().is_terminal(),
+        }
+    }
+
+    fn reset(&self) {
+        self.sent.store(0, Ordering::Relaxed);
+        self.rendered_percent.store(u64::MAX, Ordering::Relaxed);
+    }
+
+    fn advance(&self, len: u64) {
+        let sent = self.sent.fetch_add(len, Ordering::Relaxed) + len;
+        if !self.enabled {
+            return;
+        }
+
+        // Only re-render when the displayed percentage changes
+        let percent = upload_percent(sent, self.total);
+        if self.rendered_percent.swap(percent, Ordering::Relaxed) != percent {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(
+                stderr,
+                "\r{}",
+                format_upload_progress(&self.label, sent, self.total)
+            );
+            let _ = stderr.flush();
+        }
+    }
+
+    /// Clears the progress bar, so it does not interleave with the following log lines
+    pub fn finish(&self) {
+        if self.enabled && self.rendered_percent.load(Ordering::Relaxed) != u64::MAX {
+            let mut stderr = std::io::stderr().lock();
+            let _ = write!(stderr, "\r\x1b[2K");
+            let _ = stderr.flush();
+        }
+    }
+}
+
+fn upload_percent(sent: u64, total: u64) -> u64 {
+    if total == 0 {
+        100
+    } else {
+        (sent.min(total) * 100) / total
+    }
+}
+
+pub fn format_upload_progress(label: &str, sent: u64, total: u64) -> String {
+    let percent = upload_percent(sent, total);
+    let filled = PROGRESS_BAR_WIDTH * percent as usize / 100;
+    format!(
+        "{} [{}{}] {} / {} ({}%)",
+        label,
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_BAR_WIDTH - filled),
+        humansize::format_size(sent.min(total), humansize::BINARY),
+        humansize::format_size(total, humansize::BINARY),
+        percent
+    )
+}
+
+/// Opens the file as a streaming request body, which reports the read bytes to the progress
+pub async fn upload_body(
+    path: &Path,
+    progress: Arc<UploadProgress>,
+) -> anyhow::Result<reqwest::Body> {
+    let file = File::open(path).await.with_context(|| {
+        anyhow!(
+            "Failed to open {}",
+            path.display().to_string().log_color_error_highlight()
+        )
+    })?;
+    progress.reset();
+
+    let stream =
+        futures_util::stream::try_unfold((file, progress), |(mut file, progress)| async move {
+            let mut buffer = vec![0u8; UPLOAD_CHUNK_SIZE];
+            let read = file.read(&mut buffer).await?;
+            if read == 0 {
+                return Ok::<_, std::io::Error>(None);
+            }
+            buffer.truncate(read);
+            progress.advance(read as u64);
+            Ok(Some((Bytes::from(buffer), (file, progress))))
+        });
+
+    Ok(reqwest::Body::wrap_stream(stream))
+}
+
+/// Runs the upload, and retries it on transient errors according to the retry policy.
+///
+/// Before every retry `committed` is called to check whether the failed attempt was still
+/// processed by the server, in which case its result is returned instead of uploading again.
+pub async fn upload_with_retry<T, Upload, UploadFuture, Committed, CommittedFuture>(
+    retry: RetryPolicy,
+    mut upload: Upload,
+    mut committed: Committed,
+) -> anyhow::Result<T>
+where
+    Upload: FnMut() -> UploadFuture,
+    UploadFuture: Future<Output = anyhow::Result<T>>,
+    Committed: FnMut() -> CommittedFuture,
+    CommittedFuture: Future<Output = anyhow::Result<Option<T>>>,
+{
+    let mut attempt = 0;
+    loop {
+        match upload().await {
+            Ok(result) => return Ok(result),
+            Err(err) if attempt < retry.max_retries && is_transient_upload_error(&err) => {
+                let delay = retry.delay(attempt, jitter());
+                attempt += 1;
+                log_warn_action(
+                    "Retrying",
+                    format!(
+                        "upload in {} ms (attempt {}/{}), previous attempt failed: {}",
+                        delay.as_millis(),
+                        attempt,
+                        retry.max_retries,
+                        err
+                    ),
+                );
+                tokio::time::sleep(delay).await;
+
+                if let Some(result) = committed().await? {
+                    return Ok(result);
+                }
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+fn is_transient_upload_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|err| {
+        err.downcast_ref::<ServiceError>()
+            .is_some_and(|err| err.is_transient())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::component::upload::format_upload_progress;
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn upload_progress_format() {
+        assert!(
+            format_upload_progress("app.wasm", 0, 2048)
+                == "app.wasm [------------------------------] 0 B / 2 KiB (0%)"
+        );
+        assert!(
+            format_upload_progress("app.wasm", 1024, 2048)
+                == "app.wasm [###############---------------] 1 KiB / 2 KiB (50%)"
+        );
+        assert!(
+            format_upload_progress("app.wasm", 4096, 2048)
+                == "app.wasm [##############################] 2 KiB / 2 KiB (100%)"
+        );
+        assert!(
+            format_upload_progress("empty.wasm", 0, 0)
+                == "empty.wasm [##############################] 0 B / 0 B (100%)"
+        );
+    }
+}
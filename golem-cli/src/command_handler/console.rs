@@ -0,0 +1,786 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::{GolemCliCommand, GolemCliSubcommand};
+use crate::command_handler::Handlers;
+use crate::command_name;
+use crate::context::Context;
+use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
+use crate::fs;
+use crate::log::{log_action, logln, LogColorize};
+use crate::model::component::{show_exported_functions, Component, ComponentSelection};
+use crate::model::text::fmt::log_error;
+use crate::model::ComponentName;
+use clap::Parser;
+use inquire::autocompletion::{Autocomplete, Replacement};
+use inquire::{CustomUserError, InquireError, Text};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Handle;
+use tracing::debug;
+
+const HISTORY_FILE_NAME: &str = "console_history";
+const MAX_HISTORY_SIZE: usize = 1000;
+const MAX_COMPLETED_WORKERS: u64 = 100;
+
+const BUILTIN_COMMANDS: &[(&str, &str)] = &[
+    (
+        "use component <COMPONENT>",
+        "Select the component of the session",
+    ),
+    ("use worker <WORKER>", "Select the worker of the session"),
+    ("use", "Clear the selected component and worker"),
+    ("components", "List components"),
+    ("workers", "List the workers of the selected component"),
+    (
+        "functions",
+        "List the exported functions of the selected component",
+    ),
+    (
+        "invoke <FUNCTION> [ARGS]..",
+        "Invoke a function of the selected worker",
+    ),
+    ("history", "Show the command history"),
+    (
+        "!!, !<N>",
+        "Run the last or the N-th command of the history again",
+    ),
+    ("help", "Show this help"),
+    ("exit, quit", "Leave the console"),
+];
+
+pub struct ConsoleHandler {
+    ctx: Arc<Context>,
+}
+
+impl ConsoleHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn cmd_console(&self) -> anyhow::Result<()> {
+        let profile_context = self.ctx.profile_context();
+        let mut session = ConsoleSession {
+            component: profile_context.component.clone(),
+            worker: profile_context.worker.clone(),
+        };
+        let history_file = self.ctx.config_dir().join(HISTORY_FILE_NAME);
+        let mut history = load_history(&history_file);
+        let completions = Arc::new(Mutex::new(ConsoleCompletions::default()));
+
+        log_action(
+            "Started",
+            format!(
+                "console for profile {}, type {} for the available commands",
+                self.ctx.profile_name().0.log_color_highlight(),
+                "help".log_color_highlight()
+            ),
+        );
+
+        loop {
+            let completer = ConsoleCompleter {
+                ctx: self.ctx.clone(),
+                runtime: Handle::current(),
+                completions: completions.clone(),
+                component: session.component.clone(),
+                history: history.clone(),
+            };
+            let Some(line) = self
+                .read_command(session.prompt(&self.ctx), completer)
+                .await?
+            else {
+                break;
+            };
+
+            let line = match expand_history(&line, &history) {
+                Ok(Some(line)) => {
+                    logln(&line);
+                    line
+                }
+                Ok(None) => line,
+                Err(error) => {
+                    log_error(error);
+                    continue;
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            add_to_history(&history_file, &mut history, &line);
+
+            match self.run_command(&mut session, &history, &line).await {
+                Ok(ConsoleAction::Continue) => {}
+                Ok(ConsoleAction::Exit) => break,
+                Err(error) => {
+                    if error.downcast_ref::<NonSuccessfulExit>().is_none() {
+                        log_error(format!("{error:#}"));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads a command, including its continuation lines while there are unclosed brackets
+    /// or quotes, returns None when the console should exit
+    async fn read_command(
+        &self,
+        prompt: String,
+        completer: ConsoleCompleter,
+    ) -> anyhow::Result<Option<String>> {
+        let mut line = match tokio::task::spawn_blocking(move || {
+            Text::new(&prompt).with_autocomplete(completer).prompt()
+        })
+        .await?
+        {
+            Ok(line) => line,
+            Err(InquireError::OperationCanceled) => return Ok(Some(String::new())),
+            Err(InquireError::OperationInterrupted) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        while needs_continuation(&line) {
+            if line.ends_with('\\') {
+                line.pop();
+            }
+            match tokio::task::spawn_blocking(|| Text::new("..").prompt()).await? {
+                Ok(next_line) => {
+                    line.push('\n');
+                    line.push_str(&next_line);
+                }
+                Err(InquireError::OperationCanceled) => return Ok(Some(String::new())),
+                Err(InquireError::OperationInterrupted) => return Ok(None),
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        Ok(Some(line))
+    }
+
+    async fn run_command(
+        &self,
+        session: &mut ConsoleSession,
+        history: &[String],
+        line: &str,
+    ) -> anyhow::Result<ConsoleAction> {
+        let args = split_args(line);
+        let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<_>>();
+
+        match args.as_slice() {
+            ["exit"] | ["quit"] => return Ok(ConsoleAction::Exit),
+            ["help"] => {
+                logln("Console commands:");
+                for (command, description) in BUILTIN_COMMANDS {
+                    logln(format!(
+                        "  {} {}",
+                        format!("{command:<28}").log_color_highlight(),
+                        description
+                    ));
+                }
+                logln("");
+                logln(format!(
+                    "Any other line is run as a {} component, worker or rib command, e.g. {}",
+                    command_name(),
+                    "worker list".log_color_highlight()
+                ));
+                logln("Arguments are separated by whitespace outside of brackets and quotes, double quotes are kept as in WAVE strings, single quotes are removed.");
+                logln("Lines with unclosed brackets or quotes, or ending with \\ continue on the next line.");
+            }
+            ["history"] => {
+                for (idx, line) in history.iter().enumerate() {
+                    logln(format!("{:>5}  {}", idx + 1, line));
+                }
+            }
+            ["use"] => {
+                session.component = None;
+                session.worker = None;
+            }
+            ["use", "component", component_name] => {
+                session.component = Some(component_name.to_string());
+                session.worker = None;
+            }
+            ["use", "worker", worker_name] => match worker_name.rsplit_once('/') {
+                Some((component_name, worker_name)) => {
+                    session.component = Some(component_name.to_string());
+                    session.worker = Some(worker_name.to_string());
+                }
+                None => {
+                    session.require_component()?;
+                    session.worker = Some(worker_name.to_string());
+                }
+            },
+            ["use", ..] => {
+                log_error(format!(
+                    "Expected {} or {}",
+                    "use component <COMPONENT>".log_color_highlight(),
+                    "use worker <WORKER>".log_color_highlight()
+                ));
+            }
+            ["components"] => self.run_cli_command(vec!["component", "list"]).await?,
+            ["workers"] => {
+                let component_name = session.require_component()?;
+                self.run_cli_command(vec!["worker", "list", component_name])
+                    .await?
+            }
+            ["functions"] => {
+                let component_name = session.require_component()?;
+                let component = self
+                    .ctx
+                    .component_handler()
+                    .component(
+                        None,
+                        ComponentSelection::Name(&ComponentName::from(component_name)),
+                        None,
+                    )
+                    .await?;
+                match component {
+                    Some(component) => {
+                        for function in show_exported_functions(&component.metadata.exports, true) {
+                            logln(function);
+                        }
+                    }
+                    None => log_error(format!(
+                        "Component {} not found",
+                        component_name.log_color_error_highlight()
+                    )),
+                }
+            }
+            ["invoke", function_name, arguments @ ..] => {
+                let worker_name = session.require_worker()?;
+                self.run_cli_command(
+                    [
+                        "worker",
+                        "invoke",
+                        "--interactive",
+                        worker_name.as_str(),
+                        function_name,
+                    ]
+                    .into_iter()
+                    .chain(arguments.iter().copied())
+                    .collect(),
+                )
+                .await?
+            }
+            args => self.run_cli_command(args.to_vec()).await?,
+        }
+
+        Ok(ConsoleAction::Continue)
+    }
+
+    async fn run_cli_command(&self, args: Vec<&str>) -> anyhow::Result<()> {
+        let command = match GolemCliCommand::try_parse_from(
+            std::iter::once(command_name()).chain(args.iter().copied()),
+        ) {
+            Ok(command) => command,
+            Err(error) => {
+                logln(error.render().ansi().to_string());
+                return Ok(());
+            }
+        };
+
+        match command.subcommand {
+            GolemCliSubcommand::Component { subcommand } => {
+                self.ctx
+                    .component_handler()
+                    .handle_command(subcommand)
+                    .await
+            }
+            GolemCliSubcommand::Worker { subcommand } => {
+                self.ctx.worker_handler().handle_command(subcommand).await
+            }
+            GolemCliSubcommand::Rib { subcommand } => {
+                self.ctx.rib_repl_handler().handle_command(subcommand).await
+            }
+            _ => {
+                log_error(format!(
+                    "Only component, worker and rib commands can be used in the console, use {} for the console commands",
+                    "help".log_color_highlight()
+                ));
+                Ok(())
+            }
+        }
+    }
+}
+
+enum ConsoleAction {
+    Continue,
+    Exit,
+}
+
+struct ConsoleSession {
+    component: Option<String>,
+    worker: Option<String>,
+}
+
+impl ConsoleSession {
+    fn prompt(&self, ctx: &Context) -> String {
+        let mut prompt = ctx.profile_name().0.clone();
+        if let Some(component) = &self.component {
+            prompt.push(' ');
+            prompt.push_str(component);
+            if let Some(worker) = &self.worker {
+                prompt.push('/');
+                prompt.push_str(worker);
+            }
+        }
+        prompt.push('>');
+        prompt
+    }
+
+    fn require_component(&self) -> anyhow::Result<&str> {
+        match &self.component {
+            Some(component) => Ok(component),
+            None => {
+                log_error(format!(
+                    "No component is selected, use {}",
+                    "use component <COMPONENT>".log_color_highlight()
+                ));
+                Err(NonSuccessfulExit.into())
+            }
+        }
+    }
+
+    fn require_worker(&self) -> anyhow::Result<String> {
+        let component = self.require_component()?;
+        match &self.worker {
+            Some(worker) => Ok(format!("{component}/{worker}")),
+            None => {
+                log_error(format!(
+                    "No worker is selected, use {}",
+                    "use worker <WORKER>".log_color_highlight()
+                ));
+                Err(NonSuccessfulExit.into())
+            }
+        }
+    }
+}
+
+/// Component names, worker names and exported functions, fetched on the first completion which
+/// needs them
+#[derive(Default)]
+struct ConsoleCompletions {
+    components: Option<Vec<String>>,
+    workers: HashMap<String, Vec<String>>,
+    functions: HashMap<String, Vec<String>>,
+}
+
+#[derive(Clone)]
+struct ConsoleCompleter {
+    ctx: Arc<Context>,
+    runtime: Handle,
+    completions: Arc<Mutex<ConsoleCompletions>>,
+    component: Option<String>,
+    history: Vec<String>,
+}
+
+impl ConsoleCompleter {
+    fn candidates(&self, kind: CompletionKind) -> Vec<String> {
+        match kind {
+            CompletionKind::Command => BUILTIN_COMMANDS
+                .iter()
+                .flat_map(|(command, _)| command.split(", "))
+                .filter_map(|command| command.split(' ').next())
+                .filter(|command| !command.starts_with('!'))
+                .chain(["component", "worker", "rib"])
+                .unique()
+                .map(|command| command.to_string())
+                .collect(),
+            CompletionKind::UseTarget => vec!["component".to_string(), "worker".to_string()],
+            CompletionKind::Component => {
+                let mut completions = self.completions.lock().unwrap();
+                completions
+                    .components
+                    .get_or_insert_with(|| self.fetch(self.fetch_components()))
+                    .clone()
+            }
+            CompletionKind::Worker => {
+                let Some(component) = &self.component else {
+                    return vec![];
+                };
+                let mut completions = self.completions.lock().unwrap();
+                completions
+                    .workers
+                    .entry(component.clone())
+                    .or_insert_with(|| self.fetch(self.fetch_workers(component)))
+                    .clone()
+            }
+            CompletionKind::Function => {
+                let Some(component) = &self.component else {
+                    return vec![];
+                };
+                let mut completions = self.completions.lock().unwrap();
+                completions
+                    .functions
+                    .entry(component.clone())
+                    .or_insert_with(|| self.fetch(self.fetch_functions(component)))
+                    .clone()
+            }
+            CompletionKind::History => vec![],
+        }
+    }
+
+    // NOTE: completions are requested by the prompt running on a blocking thread
+    fn fetch(
+        &self,
+        future: impl std::future::Future<Output = anyhow::Result<Vec<String>>>,
+    ) -> Vec<String> {
+        match self.runtime.block_on(future) {
+            Ok(candidates) => candidates,
+            Err(error) => {
+                debug!(error = %error, "Failed to fetch console completions");
+                vec![]
+            }
+        }
+    }
+
+    async fn fetch_components(&self) -> anyhow::Result<Vec<String>> {
+        let clients = self.ctx.golem_clients().await?;
+        Ok(clients
+            .component
+            .get_components(None, None)
+            .await
+            .map_service_error()?
+            .into_iter()
+            .map(|component| Component::from(component).component_name.0)
+            .sorted()
+            .dedup()
+            .collect())
+    }
+
+    async fn fetch_workers(&self, component_name: &str) -> anyhow::Result<Vec<String>> {
+        let component_name = ComponentName::from(component_name);
+        let Some(component) = self
+            .ctx
+            .component_handler()
+            .component(None, ComponentSelection::Name(&component_name), None)
+            .await?
+        else {
+            return Ok(vec![]);
+        };
+        let (workers, _) = self
+            .ctx
+            .worker_handler()
+            .list_component_workers(
+                &component_name,
+                component.versioned_component_id.component_id,
+                None,
+                None,
+                Some(MAX_COMPLETED_WORKERS),
+                false,
+            )
+            .await?;
+        Ok(workers
+            .into_iter()
+            .map(|worker| worker.worker_id.worker_name)
+            .sorted()
+            .collect())
+    }
+
+    async fn fetch_functions(&self, component_name: &str) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .ctx
+            .component_handler()
+            .component(
+                None,
+                ComponentSelection::Name(&ComponentName::from(component_name)),
+                None,
+            )
+            .await?
+            .map(|component| show_exported_functions(&component.metadata.exports, false))
+            .unwrap_or_default())
+    }
+}
+
+impl Autocomplete for ConsoleCompleter {
+    fn get_suggestions(&mut self, input: &str) -> Result<Vec<String>, CustomUserError> {
+        let (kind, completed, word) = completion_context(input);
+        if kind == CompletionKind::History {
+            return Ok(self
+                .history
+                .iter()
+                .rev()
+                .filter(|line| !input.is_empty() && line.starts_with(input) && *line != input)
+                .unique()
+                .cloned()
+                .collect());
+        }
+        Ok(self
+            .candidates(kind)
+            .into_iter()
+            .filter(|candidate| candidate.starts_with(word))
+            .map(|candidate| format!("{completed}{candidate}"))
+            .collect())
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted_suggestion: Option<String>,
+    ) -> Result<Replacement, CustomUserError> {
+        if highlighted_suggestion.is_some() {
+            return Ok(highlighted_suggestion);
+        }
+        let suggestions = self.get_suggestions(input)?;
+        Ok(common_prefix(&suggestions).filter(|prefix| prefix.len() > input.len()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompletionKind {
+    Command,
+    UseTarget,
+    Component,
+    Worker,
+    Function,
+    History,
+}
+
+/// Returns what is being completed, the already completed part of the input, and the word under
+/// completion
+fn completion_context(input: &str) -> (CompletionKind, &str, &str) {
+    let word_start = input
+        .rfind(char::is_whitespace)
+        .map(|idx| idx + 1)
+        .unwrap_or(0);
+    let (completed, word) = input.split_at(word_start);
+    let previous_words = completed.split_whitespace().collect::<Vec<_>>();
+
+    let kind = match previous_words.as_slice() {
+        [] => CompletionKind::Command,
+        ["use"] => CompletionKind::UseTarget,
+        ["use", "component"] => CompletionKind::Component,
+        ["use", "worker"] => CompletionKind::Worker,
+        ["invoke"] => CompletionKind::Function,
+        _ => CompletionKind::History,
+    };
+
+    (kind, completed, word)
+}
+
+fn common_prefix(values: &[String]) -> Option<String> {
+    let first = values.first()?;
+    let len = values.iter().skip(1).fold(first.len(), |len, value| {
+        first
+            .char_indices()
+            .zip(value.chars())
+            .take_while(|((idx, a), b)| *idx < len && a == b)
+            .map(|((idx, a), _)| idx + a.len_utf8())
+            .last()
+            .unwrap_or(0)
+    });
+    Some(first[..len].to_string())
+}
+
+/// Splits the line on whitespace outside of brackets and quotes, so WAVE values can be used
+/// without quoting. Double quotes are kept, as they are part of WAVE strings, while single
+/// quotes are removed.
+fn split_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut depth = 0usize;
+    let mut quote = None::<char>;
+    let mut escaped = false;
+
+    for char in line.chars() {
+        if let Some(quote_char) = quote {
+            if escaped {
+                escaped = false;
+            } else if char == '\\' && quote_char == '"' {
+                escaped = true;
+            } else if char == quote_char {
+                quote = None;
+                if quote_char == '\'' {
+                    continue;
+                }
+            }
+            current.push(char);
+            continue;
+        }
+
+        match char {
+            '"' | '\'' => {
+                quote = Some(char);
+                in_arg = true;
+                if char == '"' {
+                    current.push(char);
+                }
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                in_arg = true;
+                current.push(char);
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                in_arg = true;
+                current.push(char);
+            }
+            char if char.is_whitespace() && depth == 0 => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            char => {
+                in_arg = true;
+                current.push(char);
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+
+    args
+}
+
+fn needs_continuation(line: &str) -> bool {
+    if line.ends_with('\\') {
+        return true;
+    }
+
+    let mut depth = 0isize;
+    let mut quote = None::<char>;
+    let mut escaped = false;
+    for char in line.chars() {
+        match quote {
+            Some(quote_char) => {
+                if escaped {
+                    escaped = false;
+                } else if char == '\\' && quote_char == '"' {
+                    escaped = true;
+                } else if char == quote_char {
+                    quote = None;
+                }
+            }
+            None => match char {
+                '"' | '\'' => quote = Some(char),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+
+    depth > 0 || quote.is_some()
+}
+
+/// Expands !! and !<N> to the referenced history entry
+fn expand_history(line: &str, history: &[String]) -> Result<Option<String>, String> {
+    let line = line.trim();
+    let Some(reference) = line.strip_prefix('!') else {
+        return Ok(None);
+    };
+
+    let entry = if reference == "!" {
+        history.last()
+    } else {
+        reference
+            .parse::<usize>()
+            .ok()
+            .and_then(|idx| idx.checked_sub(1))
+            .and_then(|idx| history.get(idx))
+    };
+
+    match entry {
+        Some(entry) => Ok(Some(entry.clone())),
+        None => Err(format!(
+            "History entry not found: {}",
+            line.log_color_error_highlight()
+        )),
+    }
+}
+
+fn load_history(history_file: &PathBuf) -> Vec<String> {
+    if !history_file.exists() {
+        return vec![];
+    }
+    match fs::read_to_string(history_file) {
+        Ok(content) => content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.to_string())
+            .collect(),
+        Err(error) => {
+            debug!(error = %error, "Failed to load console history");
+            vec![]
+        }
+    }
+}
+
+fn add_to_history(history_file: &PathBuf, history: &mut Vec<String>, line: &str) {
+    // Multi-line commands are stored as one line, newlines are whitespace for all arguments
+    let line = line.lines().map(|line| line.trim()).join(" ");
+    if history.last() == Some(&line) {
+        return;
+    }
+    history.push(line);
+    if history.len() > MAX_HISTORY_SIZE {
+        history.drain(..history.len() - MAX_HISTORY_SIZE);
+    }
+    if let Err(error) = fs::write_str(history_file, history.join("\n")) {
+        debug!(error = %error, "Failed to save console history");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::console::{
+        completion_context, needs_continuation, split_args, CompletionKind,
+    };
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn split_args_keeps_wave_values_together() {
+        assert!(
+            split_args(r#"invoke add-item {product-id: "p 1", quantity: 2} 'a b' [1, 2]"#)
+                == vec![
+                    "invoke",
+                    "add-item",
+                    r#"{product-id: "p 1", quantity: 2}"#,
+                    "a b",
+                    "[1, 2]",
+                ]
+        );
+    }
+
+    #[test]
+    fn continuation_on_unclosed_brackets_and_quotes() {
+        assert!(needs_continuation("invoke add-item {product-id: \"p1\","));
+        assert!(needs_continuation("invoke echo \"multi"));
+        assert!(needs_continuation("invoke echo \\"));
+        assert!(!needs_continuation("invoke add-item {product-id: \"p}\"}"));
+    }
+
+    #[test]
+    fn completion_context_by_previous_words() {
+        assert!(completion_context("wo") == (CompletionKind::Command, "", "wo"));
+        assert!(completion_context("use comp") == (CompletionKind::UseTarget, "use ", "comp"));
+        assert!(
+            completion_context("use component sh")
+                == (CompletionKind::Component, "use component ", "sh")
+        );
+        assert!(completion_context("invoke ") == (CompletionKind::Function, "invoke ", ""));
+        assert!(
+            completion_context("worker list ") == (CompletionKind::History, "worker list ", "")
+        );
+    }
+}
@@ -0,0 +1,258 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command_handler::Handlers;
+use crate::command_name;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::model::doctor::{versions_compatible, DoctorCheck, DoctorCheckStatus, DoctorReport};
+use anyhow::bail;
+use chrono::Utc;
+use golem_client::api::{AccountClient, HealthCheckClient};
+use std::sync::Arc;
+use std::time::Instant;
+use url::Url;
+
+pub struct DoctorCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl DoctorCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn cmd_doctor(&self) -> anyhow::Result<()> {
+        let mut checks = Vec::new();
+
+        let client_config = self.ctx.client_config();
+        let services = [
+            ("Component service", &client_config.component_url),
+            ("Worker service", &client_config.worker_url),
+            ("Cloud service", &client_config.cloud_url),
+        ];
+
+        let mut any_service_reachable = false;
+        for (service_name, url) in services {
+            let (service_checks, reachable) = self.check_service(service_name, url).await?;
+            checks.extend(service_checks);
+            any_service_reachable |= reachable;
+        }
+
+        checks.push(self.check_authentication(any_service_reachable).await);
+        checks.push(self.check_app_manifest().await);
+
+        let report = DoctorReport {
+            profile: self.ctx.profile_name().0.clone(),
+            cli_version: crate::version().to_string(),
+            checks,
+        };
+
+        self.ctx.log_handler().log_view(&report);
+
+        if report.has_failures() {
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    async fn check_service(
+        &self,
+        service_name: &str,
+        url: &Url,
+    ) -> anyhow::Result<(Vec<DoctorCheck>, bool)> {
+        let client = self.ctx.health_check_client(url)?;
+
+        let started_at = Instant::now();
+        let healthcheck = client.healthcheck().await;
+        let latency_ms = started_at.elapsed().as_millis();
+
+        if let Err(err) = healthcheck {
+            let check = DoctorCheck::new(
+                service_name,
+                DoctorCheckStatus::Failed,
+                format!("{url} is not reachable: {err}"),
+            )
+            .with_fix(format!(
+                "Check that the server is running and reachable from this machine, or select the right server using '{} --profile <PROFILE>', '--base-url <URL>' or the GOLEM_BASE_URL environment variable",
+                command_name()
+            ));
+            return Ok((vec![check], false));
+        }
+
+        let mut checks = vec![DoctorCheck::new(
+            service_name,
+            DoctorCheckStatus::Ok,
+            format!("{url} is reachable"),
+        )
+        .with_latency_ms(latency_ms)];
+
+        let version_check_name = format!("{service_name} version");
+        let cli_version = crate::version();
+        checks.push(match client.version().await {
+            Ok(version_info) => match versions_compatible(cli_version, &version_info.version) {
+                Some(true) => DoctorCheck::new(
+                    version_check_name,
+                    DoctorCheckStatus::Ok,
+                    format!("server version {} is compatible", version_info.version),
+                ),
+                Some(false) => DoctorCheck::new(
+                    version_check_name,
+                    DoctorCheckStatus::Warning,
+                    format!(
+                        "server version {} does not match CLI version {}",
+                        version_info.version, cli_version
+                    ),
+                )
+                .with_fix(format!(
+                    "Install a {} version matching the server's major and minor version",
+                    command_name()
+                )),
+                None => DoctorCheck::new(
+                    version_check_name,
+                    DoctorCheckStatus::Warning,
+                    format!(
+                        "cannot compare server version {} with CLI version {}",
+                        version_info.version, cli_version
+                    ),
+                ),
+            },
+            Err(err) => DoctorCheck::new(
+                version_check_name,
+                DoctorCheckStatus::Warning,
+                format!("failed to get server version: {err}"),
+            ),
+        });
+
+        Ok((checks, true))
+    }
+
+    async fn check_authentication(&self, any_service_reachable: bool) -> DoctorCheck {
+        const CHECK_NAME: &str = "Authentication";
+
+        let login_fix = format!(
+            "Use '{} login', or create a token with '{} cloud token new' and pass it using '--token' or GOLEM_TOKEN",
+            command_name(),
+            command_name()
+        );
+
+        if !any_service_reachable {
+            return DoctorCheck::new(
+                CHECK_NAME,
+                DoctorCheckStatus::Skipped,
+                "no service is reachable",
+            );
+        }
+
+        if !self.ctx.has_auth_credentials() {
+            return DoctorCheck::new(
+                CHECK_NAME,
+                DoctorCheckStatus::Failed,
+                "the selected profile is not logged in",
+            )
+            .with_fix(login_fix);
+        }
+
+        let clients = match self.ctx.golem_clients().await {
+            Ok(clients) => clients,
+            Err(err) => {
+                return DoctorCheck::new(
+                    CHECK_NAME,
+                    DoctorCheckStatus::Failed,
+                    format!("failed to authenticate: {err}"),
+                )
+                .with_fix(login_fix)
+            }
+        };
+
+        let started_at = Instant::now();
+        let account = clients.account.get_account(&clients.account_id().0).await;
+        let latency_ms = started_at.elapsed().as_millis();
+
+        match account {
+            Ok(account) => {
+                let expires_at = clients.auth_token_details().expires_at;
+                if expires_at < Utc::now() {
+                    DoctorCheck::new(
+                        CHECK_NAME,
+                        DoctorCheckStatus::Failed,
+                        format!(
+                            "the token of account {} has expired at {expires_at}",
+                            account.email
+                        ),
+                    )
+                    .with_fix(login_fix)
+                } else {
+                    DoctorCheck::new(
+                        CHECK_NAME,
+                        DoctorCheckStatus::Ok,
+                        format!(
+                            "authenticated as {}, token expires at {expires_at}",
+                            account.email
+                        ),
+                    )
+                    .with_latency_ms(latency_ms)
+                }
+            }
+            Err(err) => DoctorCheck::new(
+                CHECK_NAME,
+                DoctorCheckStatus::Failed,
+                format!("the token was rejected: {err}"),
+            )
+            .with_fix(login_fix),
+        }
+    }
+
+    async fn check_app_manifest(&self) -> DoctorCheck {
+        const CHECK_NAME: &str = "Application manifest";
+
+        self.ctx.silence_app_context_init().await;
+        let app_ctx = self.ctx.app_context_lock().await;
+
+        match app_ctx.opt() {
+            Ok(None) => DoctorCheck::new(
+                CHECK_NAME,
+                DoctorCheckStatus::Skipped,
+                "no application manifest found",
+            ),
+            Ok(Some(app_ctx)) if app_ctx.loaded_with_warnings => DoctorCheck::new(
+                CHECK_NAME,
+                DoctorCheckStatus::Warning,
+                "the application manifest is valid, but was loaded with warnings",
+            )
+            .with_fix(format!(
+                "Run '{} app build' to see the warnings",
+                command_name()
+            )),
+            Ok(Some(app_ctx)) => DoctorCheck::new(
+                CHECK_NAME,
+                DoctorCheckStatus::Ok,
+                format!(
+                    "valid, with {} component(s)",
+                    app_ctx.application.component_names().count()
+                ),
+            ),
+            Err(err) => DoctorCheck::new(
+                CHECK_NAME,
+                DoctorCheckStatus::Failed,
+                format!("invalid: {err}"),
+            )
+            .with_fix(format!(
+                "Fix the reported errors, use '{} app build' to see them in detail",
+                command_name()
+            )),
+        }
+    }
+}
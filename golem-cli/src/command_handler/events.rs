@@ -0,0 +1,155 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::events::EventsSubcommand;
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, LogColorize};
+use crate::model::event::{
+    worker_events, WorkerEventSnapshot, WorkerEventSnapshots, WorkerEventType,
+};
+use crate::model::text::fmt::{log_error, log_warn};
+use crate::model::ComponentName;
+use anyhow::bail;
+use chrono::Utc;
+use itertools::Itertools;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+pub struct EventsCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl EventsCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: EventsSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            EventsSubcommand::Tail {
+                component,
+                types,
+                interval,
+            } => self.cmd_tail(component, types, interval).await,
+        }
+    }
+
+    async fn cmd_tail(
+        &self,
+        component_name: Option<ComponentName>,
+        types: Vec<WorkerEventType>,
+        interval: u64,
+    ) -> anyhow::Result<()> {
+        if interval == 0 {
+            log_error("The polling interval must be at least 1 second");
+            bail!(NonSuccessfulExit);
+        }
+
+        let selected_components = self
+            .ctx
+            .component_handler()
+            .must_select_components_by_app_dir_or_name(component_name.as_ref())
+            .await?;
+
+        let mut components = Vec::<(ComponentName, Uuid)>::new();
+        for component_name in selected_components.component_names {
+            match self
+                .ctx
+                .component_handler()
+                .component(
+                    selected_components.project.as_ref(),
+                    (&component_name).into(),
+                    None,
+                )
+                .await?
+            {
+                Some(component) => components.push((
+                    component_name,
+                    component.versioned_component_id.component_id,
+                )),
+                None => log_warn(format!(
+                    "Component not found: {}",
+                    component_name.0.log_color_error_highlight()
+                )),
+            }
+        }
+
+        if components.is_empty() {
+            log_error("No deployed components selected");
+            bail!(NonSuccessfulExit);
+        }
+
+        // The first poll is only used as the baseline, so existing workers are not reported
+        let mut snapshots = self.poll(&components).await?;
+
+        log_action(
+            "Tailing",
+            format!(
+                "worker events of {}, press Ctrl+C to stop",
+                components
+                    .iter()
+                    .map(|(component_name, _)| component_name.0.log_color_highlight())
+                    .join(", ")
+            ),
+        );
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+
+            let current = match self.poll(&components).await {
+                Ok(current) => current,
+                Err(error) => {
+                    log_warn(format!("Failed to poll workers, retrying: {error:#}"));
+                    continue;
+                }
+            };
+
+            for event in worker_events(&snapshots, &current, Utc::now()) {
+                if types.is_empty() || types.contains(&event.event_type) {
+                    self.ctx.log_handler().log_view(&event);
+                }
+            }
+
+            snapshots = current;
+        }
+    }
+
+    async fn poll(
+        &self,
+        components: &[(ComponentName, Uuid)],
+    ) -> anyhow::Result<WorkerEventSnapshots> {
+        let mut snapshots = WorkerEventSnapshots::new();
+        for (component_name, component_id) in components {
+            let (workers, _) = self
+                .ctx
+                .worker_handler()
+                .list_component_workers(component_name, *component_id, None, None, None, false)
+                .await?;
+            snapshots.extend(workers.iter().map(|worker| {
+                let snapshot = WorkerEventSnapshot::from(worker);
+                (
+                    (
+                        snapshot.component_name.clone(),
+                        snapshot.worker_name.clone(),
+                    ),
+                    snapshot,
+                )
+            }));
+        }
+        Ok(snapshots)
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::export::ExportSubcommand;
+use crate::command::shared_args::ProjectOptionalFlagArg;
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::error::service::AnyhowMapServiceError;
+use crate::error::NonSuccessfulExit;
+use crate::fs;
+use crate::log::{log_action, LogColorize};
+use crate::model::component::Component;
+use crate::model::deploy_diff::api_definition::DiffableHttpApiDefinition;
+use crate::model::terraform::{
+    TerraformApiDefinition, TerraformApiDeployment, TerraformComponent, TerraformExport,
+    TerraformRoute,
+};
+use crate::model::text::fmt::log_error;
+use anyhow::bail;
+use golem_client::api::{ApiDefinitionClient, ComponentClient};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub struct ExportCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl ExportCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: ExportSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            ExportSubcommand::Terraform {
+                project,
+                import_blocks,
+                provider_source,
+                output,
+                force,
+            } => {
+                self.cmd_terraform(project, import_blocks, provider_source, output, force)
+                    .await
+            }
+        }
+    }
+
+    async fn cmd_terraform(
+        &self,
+        project: ProjectOptionalFlagArg,
+        import_blocks: bool,
+        provider_source: String,
+        output: Option<PathBuf>,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        if let Some(output) = &output {
+            if output.exists() && !force {
+                log_error(format!(
+                    "Output file {} already exists, use --force to overwrite it",
+                    output.display().to_string().log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(project.project.as_ref())
+            .await?;
+        let project_id = self
+            .ctx
+            .cloud_project_handler()
+            .selected_project_id_or_default(project.as_ref())
+            .await?;
+
+        let clients = self.ctx.golem_clients().await?;
+
+        // Only the latest version of the components is exported, older versions can only
+        // be used by existing workers
+        let mut components = BTreeMap::<String, Component>::new();
+        for component in clients
+            .component
+            .get_components(Some(&project_id.0), None)
+            .await
+            .map_service_error()?
+            .into_iter()
+            .map(Component::from)
+        {
+            let is_latest = components
+                .get(&component.component_name.0)
+                .map(|latest| {
+                    latest.versioned_component_id.version < component.versioned_component_id.version
+                })
+                .unwrap_or(true);
+            if is_latest {
+                components.insert(component.component_name.0.clone(), component);
+            }
+        }
+
+        let api_definitions = clients
+            .api_definition
+            .list_definitions(&project_id.0, None)
+            .await
+            .map_service_error()?
+            .into_iter()
+            .map(|api_definition| {
+                DiffableHttpApiDefinition::from_server(api_definition)
+                    .map(|api_definition| api_definition.0)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let api_deployments = self
+            .ctx
+            .api_deployment_handler()
+            .all_api_deployments(project.as_ref())
+            .await?;
+
+        let export = TerraformExport {
+            provider_source,
+            project: project
+                .as_ref()
+                .map(|project| project.project_ref.to_string()),
+            components: components
+                .into_values()
+                .map(|component| TerraformComponent {
+                    name: component.component_name.0,
+                    component_id: component.versioned_component_id.component_id,
+                    version: component.versioned_component_id.version,
+                    component_type: component.component_type.to_string(),
+                    env: component.env,
+                })
+                .collect(),
+            api_definitions: api_definitions
+                .into_iter()
+                .map(|api_definition| TerraformApiDefinition {
+                    id: api_definition.id,
+                    version: api_definition.version,
+                    draft: api_definition.draft,
+                    routes: api_definition
+                        .routes
+                        .into_iter()
+                        .map(|route| TerraformRoute {
+                            method: route.method.to_string(),
+                            path: route.path,
+                            binding_type: route
+                                .binding
+                                .binding_type
+                                .map(|binding_type| binding_type.to_string()),
+                            component_name: route
+                                .binding
+                                .component
+                                .as_ref()
+                                .map(|component| component.name.clone()),
+                            component_version: route
+                                .binding
+                                .component
+                                .as_ref()
+                                .and_then(|component| component.version),
+                            worker_name: route.binding.worker_name,
+                            idempotency_key: route.binding.idempotency_key,
+                            response: route.binding.response,
+                            security: route.security,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            api_deployments: api_deployments
+                .into_iter()
+                .map(|api_deployment| TerraformApiDeployment {
+                    host: api_deployment.site.host,
+                    subdomain: api_deployment.site.subdomain,
+                    api_definitions: api_deployment
+                        .api_definitions
+                        .into_iter()
+                        .map(|api_definition| (api_definition.id, api_definition.version))
+                        .collect(),
+                })
+                .collect(),
+        };
+
+        let rendered = if import_blocks {
+            export.render_import_blocks()
+        } else {
+            export.render_resources()
+        };
+
+        match output {
+            Some(output) => {
+                log_action(
+                    "Writing",
+                    format!(
+                        "Terraform {} to {}",
+                        if import_blocks {
+                            "import blocks"
+                        } else {
+                            "resource definitions"
+                        },
+                        output.display().to_string().log_color_highlight()
+                    ),
+                );
+                fs::write_str(&output, rendered)?;
+            }
+            None => print!("{rendered}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::extension::ExtensionSubcommand;
+use crate::command::{GolemCliCommand, GolemCliGlobalFlags};
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, log_warn_action, LogColorize};
+use crate::model::extension::{
+    discover_extensions, extension_executable_name, extension_name, extensions_dir,
+    is_valid_extension_name,
+};
+use crate::model::text::fmt::log_error;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use clap::CommandFactory;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Arc;
+use tracing::debug;
+use url::Url;
+
+pub struct ExtensionCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl ExtensionCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: ExtensionSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            ExtensionSubcommand::List => self.cmd_list(),
+            ExtensionSubcommand::Install { source, name } => self.cmd_install(source, name).await,
+        }
+    }
+
+    fn cmd_list(&self) -> anyhow::Result<()> {
+        self.ctx
+            .log_handler()
+            .log_view(&discover_extensions(self.ctx.config_dir()));
+        Ok(())
+    }
+
+    async fn cmd_install(&self, source: String, name: Option<String>) -> anyhow::Result<()> {
+        let source_url = Url::parse(&source)
+            .ok()
+            .filter(|url| url.scheme() == "http" || url.scheme() == "https");
+
+        let name = match name {
+            Some(name) => name,
+            None => {
+                let file_name = match &source_url {
+                    Some(url) => url
+                        .path_segments()
+                        .and_then(|mut segments| segments.next_back())
+                        .unwrap_or_default()
+                        .to_string(),
+                    None => Path::new(&source)
+                        .file_name()
+                        .map(|file_name| file_name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                };
+                match extension_name(&file_name) {
+                    Some(name) => name.to_string(),
+                    None => {
+                        log_error(format!(
+                            "Cannot derive the extension name from {}, use --name to specify it",
+                            source.log_color_highlight()
+                        ));
+                        bail!(NonSuccessfulExit);
+                    }
+                }
+            }
+        };
+
+        if !is_valid_extension_name(&name) {
+            log_error(format!(
+                "Invalid extension name: {}, only lowercase letters, digits and dashes are allowed",
+                name.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        if is_builtin_subcommand(&name) {
+            log_error(format!(
+                "Extension name {} conflicts with a builtin command",
+                name.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let content = match &source_url {
+            Some(url) => {
+                log_action("Downloading", format!("extension from {}", url));
+                let response = self
+                    .ctx
+                    .file_download_client()
+                    .get(url.clone())
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .with_context(|| anyhow!("Failed to download extension from {url}"))?;
+                response
+                    .bytes()
+                    .await
+                    .with_context(|| anyhow!("Failed to download extension from {url}"))?
+                    .to_vec()
+            }
+            None => std::fs::read(&source)
+                .with_context(|| anyhow!("Failed to read extension from {source}"))?,
+        };
+
+        let dir = extensions_dir(self.ctx.config_dir());
+        std::fs::create_dir_all(&dir)
+            .with_context(|| anyhow!("Failed to create directory {}", dir.display()))?;
+        let target = dir.join(extension_executable_name(&name));
+        if target.exists() {
+            log_warn_action(
+                "Overwriting",
+                format!("extension {}", name.log_color_highlight()),
+            );
+        }
+        std::fs::write(&target, content)
+            .with_context(|| anyhow!("Failed to write {}", target.display()))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&target, std::fs::Permissions::from_mode(0o755))
+                .with_context(|| anyhow!("Failed to set permissions of {}", target.display()))?;
+        }
+
+        log_action(
+            "Installed",
+            format!(
+                "extension {} to {}",
+                name.log_color_highlight(),
+                target.display().to_string().log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Runs the extension executable with the remaining args. The selected profile and the
+    /// global flags are passed using the environment variables that the CLI itself accepts,
+    /// so extensions can call back into the CLI (using GOLEM_CLI) with the same settings.
+    pub fn run_extension(
+        &self,
+        global_flags: &GolemCliGlobalFlags,
+        path: &Path,
+        args: Vec<OsString>,
+    ) -> anyhow::Result<ExitCode> {
+        let client_config = self.ctx.client_config();
+
+        let mut command = std::process::Command::new(path);
+        command
+            .args(args)
+            .env("GOLEM_PROFILE", self.ctx.profile_name().to_string())
+            .env("GOLEM_FORMAT", self.ctx.format().to_string())
+            .env("GOLEM_CONFIG_DIR", self.ctx.config_dir())
+            .env("GOLEM_COMPONENT_URL", client_config.component_url.as_str())
+            .env("GOLEM_WORKER_URL", client_config.worker_url.as_str())
+            .env("GOLEM_CLOUD_URL", client_config.cloud_url.as_str());
+        if let Ok(current_exe) = std::env::current_exe() {
+            command.env("GOLEM_CLI", current_exe);
+        }
+        if let Some(app_manifest_path) = &global_flags.app_manifest_path {
+            command.env("GOLEM_APP_MANIFEST_PATH", app_manifest_path);
+        }
+        if let Some(build_profile) = &global_flags.build_profile {
+            command.env("GOLEM_BUILD_PROFILE", build_profile.to_string());
+        }
+        if let Some(base_url) = &global_flags.base_url {
+            command.env("GOLEM_BASE_URL", base_url.as_str());
+        }
+        if let Some(auth_token) = &global_flags.auth_token {
+            command.env("GOLEM_TOKEN", auth_token.to_string());
+        }
+        if let Some(project) = &global_flags.project {
+            command.env("GOLEM_PROJECT", project.to_string());
+        }
+        if global_flags.disable_app_manifest_discovery {
+            command.env("GOLEM_DISABLE_APP_MANIFEST_DISCOVERY", "true");
+        }
+
+        debug!(path = %path.display(), "Running extension");
+
+        let status = command.status().with_context(|| {
+            anyhow!(
+                "Failed to execute extension {}",
+                path.display().to_string().log_color_highlight()
+            )
+        })?;
+
+        Ok(match status.code() {
+            Some(0) => ExitCode::SUCCESS,
+            Some(code) => ExitCode::from(code.clamp(1, 255) as u8),
+            None => ExitCode::FAILURE,
+        })
+    }
+}
+
+fn is_builtin_subcommand(name: &str) -> bool {
+    GolemCliCommand::command().get_subcommands().any(|command| {
+        command.get_name() == name || command.get_all_aliases().any(|alias| alias == name)
+    })
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::app::template_repo::TemplateRepoSource;
 use crate::config::{AuthSecret, AuthenticationConfig, Profile, ProfileConfig, ProfileName};
 use crate::context::Context;
 use crate::error::NonSuccessfulExit;
@@ -24,8 +25,8 @@ use crate::model::text::fmt::{log_error, log_warn};
 use crate::model::{ComponentName, Format, NewInteractiveApp, WorkerName};
 use anyhow::bail;
 use colored::Colorize;
-use golem_client::model::Account;
 use golem_client::model::HttpApiDefinitionRequest;
+use golem_client::model::{Account, Project};
 use golem_common::model::ComponentVersion;
 use golem_templates::model::{ComposableAppGroupName, GuestLanguage, PackageName};
 use inquire::error::InquireResult;
@@ -83,6 +84,17 @@ impl InteractiveHandler {
         )
     }
 
+    // NOTE: static because happens during client initialization, and intentionally not
+    //       auto confirmed with --yes, as the device flow requires user interaction anyway
+    pub fn confirm_login_with_expired_token() -> anyhow::Result<bool> {
+        confirm(
+            false,
+            true,
+            "Do you want to log in again using the OAuth2 device flow?",
+            None,
+        )
+    }
+
     pub fn confirm_auto_deploy_component(
         &self,
         component_name: &ComponentName,
@@ -157,6 +169,30 @@ impl InteractiveHandler {
         )
     }
 
+    pub fn confirm_delete_current_token(&self, profile_name: &ProfileName) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "The requested token is used by the current profile ({}), deleting it will {} the profile. Do you want to continue?",
+                profile_name.0.log_color_highlight(),
+                "invalidate".log_color_warn()
+            ),
+            Some("Tip: create a new token and update the profile first when rotating credentials"),
+        )
+    }
+
+    pub fn confirm_delete_project(&self, project: &Project) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "Are you sure you want to {} project {}, including all of its components and workers?",
+                "delete".log_color_warn(),
+                project.project_data.name.log_color_highlight()
+            ),
+            None,
+        )
+    }
+
     pub fn confirm_plugin_installation_changes(
         &self,
         component: &AppComponentName,
@@ -192,6 +228,93 @@ impl InteractiveHandler {
         )
     }
 
+    pub fn confirm_template_post_generate_commands(
+        &self,
+        template_repo: &TemplateRepoSource,
+        commands: &[String],
+    ) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "The template from {} wants to execute the following post-generate command(s):\n{}\nDo you want to execute them?",
+                template_repo.to_string().log_color_highlight(),
+                commands
+                    .iter()
+                    .map(|command| format!(" - {}", command.log_color_highlight()))
+                    .join("\n")
+            ),
+            None,
+        )
+    }
+
+    pub fn confirm_delete_api_deployments(&self, sites: &[String]) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "The following API deployment(s) are not part of the deployments file and will be {}:\n{}\nDo you want to continue?",
+                "deleted".log_color_warn(),
+                sites
+                    .iter()
+                    .map(|site| format!(" - {}", site.log_color_highlight()))
+                    .join("\n")
+            ),
+            None,
+        )
+    }
+
+    pub fn confirm_delete_api_definitions(&self, definitions: &[String]) -> anyhow::Result<bool> {
+        self.confirm(
+            false,
+            format!(
+                "The following API definition(s) are not part of the desired state file and will be {}:\n{}\nDo you want to continue?",
+                "deleted".log_color_warn(),
+                definitions
+                    .iter()
+                    .map(|definition| format!(" - {}", definition.log_color_highlight()))
+                    .join("\n")
+            ),
+            None,
+        )
+    }
+
+    pub fn confirm_delete_api_deployment_by_site(&self, site: &str) -> anyhow::Result<bool> {
+        if self.ctx.yes() {
+            return Ok(true);
+        }
+
+        let message = format!(
+            "This action {} be undone. To confirm deleting the deployment, type the site name ({}):",
+            "cannot".log_color_warn(),
+            site.log_color_highlight()
+        );
+
+        let Some(typed_site) = Text::new(&message)
+            .prompt()
+            .none_if_not_interactive_logged()?
+        else {
+            return Ok(false);
+        };
+
+        if typed_site.trim() != site {
+            log_error("The typed site name does not match, aborting");
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    pub fn prompt_static_token(&self, profile_name: &ProfileName) -> anyhow::Result<Option<Uuid>> {
+        let message = format!(
+            "The static token of profile {} was not exported, please enter the token:",
+            profile_name.0.log_color_highlight()
+        );
+
+        CustomType::<Uuid>::new(&message)
+            .with_error_message("Please enter a valid token (UUID)")
+            .prompt()
+            .none_if_not_interactive_logged()
+    }
+
     pub fn create_profile(&self) -> anyhow::Result<(ProfileName, Profile, bool)> {
         if !self.confirm(
             true,
@@ -247,11 +370,15 @@ impl InteractiveHandler {
         };
 
         let profile = Profile {
+            extends: None,
             custom_url: Some(component_service_url),
             custom_cloud_url: cloud_service_url,
             custom_worker_url: worker_service_url,
             allow_insecure: false,
-            config: ProfileConfig { default_format },
+            config: ProfileConfig {
+                default_format: Some(default_format),
+                ..ProfileConfig::default()
+            },
             auth,
         };
 
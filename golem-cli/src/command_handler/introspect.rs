@@ -0,0 +1,46 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::introspect::IntrospectSubcommand;
+use crate::command::GolemCliCommand;
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::model::introspect::CommandCatalogView;
+use crate::version;
+use clap::CommandFactory;
+use std::sync::Arc;
+
+pub struct IntrospectCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl IntrospectCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub fn handle_command(&self, subcommand: IntrospectSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            IntrospectSubcommand::Commands => self.cmd_commands(),
+        }
+    }
+
+    fn cmd_commands(&self) -> anyhow::Result<()> {
+        self.ctx.log_handler().log_view(&CommandCatalogView::new(
+            &GolemCliCommand::command(),
+            version(),
+        ));
+        Ok(())
+    }
+}
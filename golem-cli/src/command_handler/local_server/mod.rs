@@ -0,0 +1,475 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::server::{
+    LocalServerArgs, LocalServerSubcommand, ServerManifestArgs, ServerServiceArgs,
+};
+use crate::command_handler::Handlers;
+use crate::command_name;
+use crate::config::{Config, Profile, ProfileName};
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, log_warn_action, logln, LogColorize};
+use crate::model::local_server::{
+    LocalServerServiceView, LocalServerStatusView, ServerManifestConfig, ServerManifestTarget,
+    ServerPersistence,
+};
+use crate::model::template::template_env;
+use crate::model::text::fmt::log_error;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use golem_client::api::HealthCheckClient;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use tracing::debug;
+use url::Url;
+
+const DOCKER_COMPOSE_TEMPLATE: &str = include_str!("docker-compose.yaml");
+const K8S_TEMPLATE: &str = include_str!("k8s.yaml");
+const DEFAULT_NAMESPACE: &str = "golem";
+const COMPOSE_PROJECT_NAME: &str = "golem-local";
+const ENV_ROUTER_PORT: &str = "GOLEM_ROUTER_PORT";
+
+pub struct LocalServerCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl LocalServerCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: LocalServerSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            LocalServerSubcommand::Run { args } => self.cmd_run(args).await,
+            LocalServerSubcommand::Start { args } => self.cmd_start(args).await,
+            LocalServerSubcommand::Stop => self.cmd_stop(),
+            LocalServerSubcommand::Status => self.cmd_status().await,
+            LocalServerSubcommand::Logs { follow } => self.cmd_logs(follow),
+            LocalServerSubcommand::Clean => self.cmd_clean(),
+            LocalServerSubcommand::Manifest { args } => self.cmd_manifest(args),
+        }
+    }
+
+    async fn cmd_run(&self, args: LocalServerArgs) -> anyhow::Result<()> {
+        self.prepare(&args).await?;
+
+        log_action(
+            "Running",
+            format!(
+                "local server at {}, press Ctrl-C to stop",
+                router_url(args.services.router_port())
+                    .as_str()
+                    .log_color_highlight()
+            ),
+        );
+        self.docker_compose(&["up", "--remove-orphans"])
+    }
+
+    async fn cmd_start(&self, args: LocalServerArgs) -> anyhow::Result<()> {
+        self.prepare(&args).await?;
+
+        log_action("Starting", "local server");
+        self.docker_compose(&["up", "--detach", "--remove-orphans"])?;
+        log_action(
+            "Started",
+            format!(
+                "local server at {}",
+                router_url(args.services.router_port())
+                    .as_str()
+                    .log_color_highlight()
+            ),
+        );
+        logln("");
+        logln(format!(
+            "Use '{}' to follow the logs of the services.",
+            format!("{} server logs --follow", command_name()).log_color_highlight()
+        ));
+
+        Ok(())
+    }
+
+    fn cmd_stop(&self) -> anyhow::Result<()> {
+        if !self.compose_file().exists() {
+            log_warn_action("Skipping", "stopping local server, it was never started");
+            return Ok(());
+        }
+
+        log_action("Stopping", "local server");
+        self.docker_compose(&["down"])?;
+        log_action("Stopped", "local server");
+
+        Ok(())
+    }
+
+    async fn cmd_status(&self) -> anyhow::Result<()> {
+        let router_port = self.router_port();
+        let router_url = router_url(router_port);
+
+        let services = if self.compose_file().exists() {
+            let output = self.docker_compose_output(&["ps", "--all", "--format", "json"])?;
+            LocalServerServiceView::from_compose_ps_output(&output)
+                .context("Failed to parse the output of docker compose ps")?
+        } else {
+            vec![]
+        };
+
+        let healthy = !services.is_empty()
+            && self
+                .ctx
+                .health_check_client(&router_url)?
+                .healthcheck()
+                .await
+                .is_ok();
+
+        self.ctx.log_handler().log_view(&LocalServerStatusView {
+            compose_file: self.compose_file(),
+            router_url: router_url.to_string(),
+            healthy,
+            services,
+        });
+
+        Ok(())
+    }
+
+    fn cmd_logs(&self, follow: bool) -> anyhow::Result<()> {
+        if !self.compose_file().exists() {
+            log_error("The local server was never started");
+            bail!(NonSuccessfulExit);
+        }
+
+        if follow {
+            self.docker_compose(&["logs", "--follow"])
+        } else {
+            self.docker_compose(&["logs"])
+        }
+    }
+
+    fn cmd_clean(&self) -> anyhow::Result<()> {
+        let dir = self.dir();
+        if !dir.exists() {
+            log_warn_action("Skipping", "cleaning local server, it was never started");
+            return Ok(());
+        }
+
+        log_warn_action("Deleting", "local server containers and data");
+        if self.compose_file().exists() {
+            self.docker_compose(&["down", "--volumes", "--remove-orphans"])?;
+        }
+        std::fs::remove_dir_all(&dir)
+            .with_context(|| anyhow!("Failed to delete {}", dir.display()))?;
+        log_action("Cleaned", "local server");
+
+        Ok(())
+    }
+
+    fn cmd_manifest(&self, args: ServerManifestArgs) -> anyhow::Result<()> {
+        if args.worker_executors == 0 {
+            log_error("At least one worker executor is required");
+            bail!(NonSuccessfulExit);
+        }
+
+        let manifest = render_manifest(
+            args.target,
+            &ServerManifestConfig {
+                image_tag: args.services.image_tag().to_string(),
+                router_port: args.services.router_port(),
+                custom_request_port: args.services.custom_request_port(),
+                worker_executors: args.worker_executors,
+                persistence: args.persistence,
+                namespace: args.namespace,
+            },
+        )?;
+
+        match args.output {
+            Some(output) => {
+                log_action(
+                    "Writing",
+                    format!(
+                        "manifest to {}",
+                        output.display().to_string().log_color_highlight()
+                    ),
+                );
+                std::fs::write(&output, manifest)
+                    .with_context(|| anyhow!("Failed to write {}", output.display()))?;
+            }
+            None => print!("{manifest}"),
+        }
+
+        Ok(())
+    }
+
+    /// Writes the compose and env files, and points the builtin local profile to the router
+    async fn prepare(&self, args: &LocalServerArgs) -> anyhow::Result<()> {
+        let compose_file_content = match &args.compose_file {
+            Some(source) => match Url::parse(source)
+                .ok()
+                .filter(|url| url.scheme() == "http" || url.scheme() == "https")
+            {
+                Some(url) => {
+                    log_action("Downloading", format!("compose file from {url}"));
+                    self.ctx
+                        .file_download_client()
+                        .get(url.clone())
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status())
+                        .with_context(|| anyhow!("Failed to download compose file from {url}"))?
+                        .text()
+                        .await
+                        .with_context(|| anyhow!("Failed to download compose file from {url}"))?
+                }
+                None => std::fs::read_to_string(source)
+                    .with_context(|| anyhow!("Failed to read compose file {source}"))?,
+            },
+            None => render_manifest(
+                ServerManifestTarget::DockerCompose,
+                &ServerManifestConfig {
+                    image_tag: args.services.image_tag().to_string(),
+                    router_port: args.services.router_port(),
+                    custom_request_port: args.services.custom_request_port(),
+                    worker_executors: 1,
+                    persistence: ServerPersistence::Sqlite,
+                    namespace: DEFAULT_NAMESPACE.to_string(),
+                },
+            )?,
+        };
+
+        let dir = self.dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| anyhow!("Failed to create directory {}", dir.display()))?;
+
+        let compose_file = self.compose_file();
+        std::fs::write(&compose_file, compose_file_content)
+            .with_context(|| anyhow!("Failed to write {}", compose_file.display()))?;
+
+        let env_file = self.env_file();
+        std::fs::write(
+            &env_file,
+            format!(
+                "GOLEM_VERSION={}\n{}={}\nGOLEM_CUSTOM_REQUEST_PORT={}\n",
+                args.services.image_tag(),
+                ENV_ROUTER_PORT,
+                args.services.router_port(),
+                args.services.custom_request_port(),
+            ),
+        )
+        .with_context(|| anyhow!("Failed to write {}", env_file.display()))?;
+
+        self.update_local_profile(args.services.router_port())
+    }
+
+    fn update_local_profile(&self, router_port: u16) -> anyhow::Result<()> {
+        let config_dir = self.ctx.config_dir();
+        let profile_name = ProfileName::local();
+
+        let mut profile = Config::get_profile(config_dir, &profile_name)?
+            .map(|profile| profile.profile)
+            .unwrap_or_else(Profile::default_local_profile);
+
+        let url = router_url(router_port);
+        if profile.custom_url.as_ref() == Some(&url) && profile.custom_worker_url.is_none() {
+            return Ok(());
+        }
+
+        log_action(
+            "Updating",
+            format!(
+                "profile {} to use {}",
+                profile_name.0.log_color_highlight(),
+                url.as_str().log_color_highlight()
+            ),
+        );
+        profile.custom_url = Some(url);
+        profile.custom_worker_url = None;
+        Config::set_profile(profile_name, profile, config_dir)
+    }
+
+    fn dir(&self) -> PathBuf {
+        self.ctx.config_dir().join("local-server")
+    }
+
+    fn compose_file(&self) -> PathBuf {
+        self.dir().join("docker-compose.yaml")
+    }
+
+    fn env_file(&self) -> PathBuf {
+        self.dir().join(".env")
+    }
+
+    fn router_port(&self) -> u16 {
+        std::fs::read_to_string(self.env_file())
+            .ok()
+            .and_then(|env| {
+                env.lines().find_map(|line| {
+                    line.strip_prefix(ENV_ROUTER_PORT)
+                        .and_then(|rest| rest.strip_prefix('='))
+                        .and_then(|port| port.trim().parse().ok())
+                })
+            })
+            .unwrap_or_else(|| ServerServiceArgs::default().router_port())
+    }
+
+    fn compose_command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new("docker");
+        command
+            .arg("compose")
+            .arg("--project-name")
+            .arg(COMPOSE_PROJECT_NAME)
+            .arg("--file")
+            .arg(self.compose_file())
+            .arg("--env-file")
+            .arg(self.env_file())
+            .args(args);
+        debug!(command = ?command, "docker compose");
+        command
+    }
+
+    /// Runs docker compose with inherited stdio, so the output of the services is streamed
+    fn docker_compose(&self, args: &[&str]) -> anyhow::Result<()> {
+        let status = self
+            .compose_command(args)
+            .status()
+            .context("Failed to execute docker, make sure it is installed and available in PATH")?;
+
+        if !status.success() {
+            log_error(format!(
+                "docker compose {} failed with exit code {}",
+                args.first().unwrap_or(&""),
+                status
+                    .code()
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "?".to_string())
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    fn docker_compose_output(&self, args: &[&str]) -> anyhow::Result<String> {
+        let output = self
+            .compose_command(args)
+            .stdin(Stdio::null())
+            .output()
+            .context("Failed to execute docker, make sure it is installed and available in PATH")?;
+
+        if !output.status.success() {
+            bail!(
+                "docker compose {} failed with exit code {}: {}",
+                args.first().unwrap_or(&""),
+                output
+                    .status
+                    .code()
+                    .map(|code| code.to_string())
+                    .unwrap_or_else(|| "?".to_string()),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+fn render_manifest(
+    target: ServerManifestTarget,
+    config: &ServerManifestConfig,
+) -> anyhow::Result<String> {
+    let template = match target {
+        ServerManifestTarget::DockerCompose => DOCKER_COMPOSE_TEMPLATE,
+        ServerManifestTarget::K8s => K8S_TEMPLATE,
+    };
+    template_env()
+        .render_str(template, config)
+        .context("Failed to render server manifest template")
+}
+
+fn router_url(router_port: u16) -> Url {
+    Url::parse(&format!("http://localhost:{router_port}")).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command_handler::local_server::render_manifest;
+    use crate::model::local_server::{
+        ServerManifestConfig, ServerManifestTarget, ServerPersistence,
+    };
+    use assert2::assert;
+    use test_r::test;
+
+    fn config(persistence: ServerPersistence) -> ServerManifestConfig {
+        ServerManifestConfig {
+            image_tag: "1.2.4".to_string(),
+            router_port: 9881,
+            custom_request_port: 9006,
+            worker_executors: 3,
+            persistence,
+            namespace: "golem-test".to_string(),
+        }
+    }
+
+    #[test]
+    fn docker_compose_manifest() {
+        for persistence in [ServerPersistence::Sqlite, ServerPersistence::Postgres] {
+            let manifest =
+                render_manifest(ServerManifestTarget::DockerCompose, &config(persistence)).unwrap();
+            let manifest: serde_yaml::Value = serde_yaml::from_str(&manifest).unwrap();
+            let services = manifest["services"].as_mapping().unwrap();
+
+            assert!(
+                manifest["services"]["golem-worker-executor"]["deploy"]["replicas"].as_u64()
+                    == Some(3)
+            );
+            assert!(
+                manifest["services"]["golem-router"]["image"].as_str()
+                    == Some("golemservices/golem-router:1.2.4")
+            );
+            assert!(
+                services.contains_key("postgres") == (persistence == ServerPersistence::Postgres)
+            );
+        }
+    }
+
+    #[test]
+    fn k8s_manifest() {
+        for persistence in [ServerPersistence::Sqlite, ServerPersistence::Postgres] {
+            let manifest =
+                render_manifest(ServerManifestTarget::K8s, &config(persistence)).unwrap();
+            let resources = serde_yaml::Deserializer::from_str(&manifest)
+                .map(|document| serde::Deserialize::deserialize(document).unwrap())
+                .collect::<Vec<serde_yaml::Value>>();
+
+            assert!(resources
+                .iter()
+                .all(
+                    |resource| resource["metadata"]["namespace"].as_str() == Some("golem-test")
+                        || resource["kind"].as_str() == Some("Namespace")
+                ));
+
+            let executor = resources
+                .iter()
+                .find(|resource| {
+                    resource["kind"].as_str() == Some("Deployment")
+                        && resource["metadata"]["name"].as_str() == Some("golem-worker-executor")
+                })
+                .unwrap();
+            assert!(executor["spec"]["replicas"].as_u64() == Some(3));
+
+            let has_postgres = resources
+                .iter()
+                .any(|resource| resource["metadata"]["name"].as_str() == Some("postgres"));
+            assert!(has_postgres == (persistence == ServerPersistence::Postgres));
+        }
+    }
+}
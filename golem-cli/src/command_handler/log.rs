@@ -17,7 +17,10 @@ use crate::model::text::fmt::{NestedTextViewIndent, TextView};
 use crate::model::Format;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use std::io::Write;
+use std::marker::PhantomData;
 use std::sync::Arc;
+use tracing::info_span;
 
 pub struct LogHandler {
     ctx: Arc<Context>,
@@ -29,6 +32,7 @@ impl LogHandler {
     }
 
     pub fn log_view<View: TextView + Serialize + DeserializeOwned>(&self, view: &View) {
+        let _span = info_span!("render").entered();
         match self.ctx.format() {
             Format::Json => {
                 println!("{}", serde_json::to_string(view).unwrap());
@@ -46,4 +50,111 @@ impl LogHandler {
     pub fn nested_text_view_indent(&self) -> NestedTextViewIndent {
         NestedTextViewIndent::new(self.ctx.format())
     }
+
+    pub fn paged_list_view<Item, Page>(
+        &self,
+        list_field: &'static str,
+    ) -> PagedListView<Item, Page> {
+        PagedListView {
+            format: self.ctx.format(),
+            list_field,
+            item_count: 0,
+            _page: PhantomData,
+        }
+    }
+}
+
+/// Logs a list view page by page, so only one page of a large list is held in memory. Every
+/// page is still received and deserialized as a whole, memory use is bounded by the page size
+/// (--http-batch-size), not by incremental parsing.
+///
+/// In JSON and YAML format the output is the same document as the one of the equivalent
+/// non-paged view: an object starting with the list field, followed by the fields of the
+/// view passed to [PagedListView::finish]. In text format every page is logged as a separate
+/// table.
+pub struct PagedListView<Item, Page> {
+    format: Format,
+    list_field: &'static str,
+    item_count: usize,
+    _page: PhantomData<(Item, Page)>,
+}
+
+impl<Item, Page> PagedListView<Item, Page>
+where
+    Item: Serialize,
+    Page: TextView + From<Vec<Item>>,
+{
+    pub fn log_page(&mut self, items: Vec<Item>) {
+        if items.is_empty() {
+            return;
+        }
+        let _span = info_span!("render").entered();
+
+        match self.format {
+            Format::Json => {
+                let mut output = String::new();
+                for item in &items {
+                    if self.item_count == 0 {
+                        output.push_str(&self.json_list_start());
+                    } else {
+                        output.push(',');
+                    }
+                    output.push_str(&serde_json::to_string(item).unwrap());
+                    self.item_count += 1;
+                }
+                print!("{output}");
+                let _ = std::io::stdout().flush();
+            }
+            Format::Yaml => {
+                if self.item_count == 0 {
+                    print!("---\n{}:\n", self.list_field);
+                }
+                print!("{}", serde_yaml::to_string(&items).unwrap());
+                self.item_count += items.len();
+            }
+            Format::Text => {
+                self.item_count += items.len();
+                Page::from(items).log();
+            }
+        }
+    }
+
+    pub fn finish<View: TextView + Serialize>(self, view: &View) {
+        match self.format {
+            Format::Json => {
+                let mut output = String::new();
+                if self.item_count == 0 {
+                    output.push_str(&self.json_list_start());
+                }
+                output.push(']');
+                if let serde_json::Value::Object(fields) = serde_json::to_value(view).unwrap() {
+                    for (name, value) in fields {
+                        output.push_str(&format!(
+                            ",{}:{}",
+                            serde_json::to_string(&name).unwrap(),
+                            value
+                        ));
+                    }
+                }
+                output.push('}');
+                println!("{output}");
+            }
+            Format::Yaml => {
+                if self.item_count == 0 {
+                    print!("---\n{}: []\n", self.list_field);
+                }
+                println!("{}", serde_yaml::to_string(view).unwrap());
+            }
+            Format::Text => {
+                if self.item_count == 0 {
+                    Page::from(Vec::new()).log();
+                }
+                view.log();
+            }
+        }
+    }
+
+    fn json_list_start(&self) -> String {
+        format!("{{{}:[", serde_json::to_string(self.list_field).unwrap())
+    }
 }
@@ -0,0 +1,283 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::context::Context;
+use crate::log::log_action;
+use crate::model::lsp::{completions, defined_component_names, diagnostics, hover, LspPosition};
+use crate::version;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufRead, Read, Write};
+use std::sync::Arc;
+use tracing::debug;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Minimal language server for application manifests and desired state files, speaking
+/// JSON-RPC over stdio. Documents are synchronized fully on every change.
+pub struct LspCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl LspCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn cmd_lsp(&self) -> anyhow::Result<()> {
+        let app_component_names = self.app_component_names().await;
+
+        log_action("Starting", "language server on stdio");
+
+        tokio::task::spawn_blocking(move || {
+            let stdin = std::io::stdin();
+            let stdout = std::io::stdout();
+            LanguageServer::new(app_component_names).run(&mut stdin.lock(), &mut stdout.lock())
+        })
+        .await?
+    }
+
+    /// Component names of the application in the current directory, if there is any. Manifest
+    /// errors are ignored here, as those are reported as diagnostics of the edited documents.
+    async fn app_component_names(&self) -> BTreeSet<String> {
+        self.ctx.silence_app_context_init().await;
+        let app_ctx = self.ctx.app_context_lock().await;
+        match app_ctx.opt() {
+            Ok(Some(app_ctx)) => app_ctx
+                .application
+                .component_names()
+                .map(|component_name| component_name.to_string())
+                .collect(),
+            Ok(None) | Err(_) => BTreeSet::new(),
+        }
+    }
+}
+
+struct LanguageServer {
+    app_component_names: BTreeSet<String>,
+    documents: BTreeMap<String, String>,
+    shutdown_requested: bool,
+}
+
+impl LanguageServer {
+    fn new(app_component_names: BTreeSet<String>) -> Self {
+        Self {
+            app_component_names,
+            documents: BTreeMap::new(),
+            shutdown_requested: false,
+        }
+    }
+
+    fn run(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> anyhow::Result<()> {
+        while let Some(message) = read_message(input)? {
+            let method = message["method"].as_str().unwrap_or_default().to_string();
+            let id = message.get("id").cloned();
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+            debug!(method = %method, "LSP message");
+
+            if method == "exit" {
+                if self.shutdown_requested {
+                    return Ok(());
+                }
+                bail!("Language server exited without shutdown request");
+            }
+
+            let result = self.handle(&method, params, output)?;
+            if let Some(id) = id {
+                let response = match result {
+                    Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    Err((code, message)) => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": code, "message": message }
+                    }),
+                };
+                write_message(output, &response)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles requests and notifications, for notifications the returned value is dropped
+    fn handle(
+        &mut self,
+        method: &str,
+        params: Value,
+        output: &mut impl Write,
+    ) -> anyhow::Result<Result<Value, (i64, String)>> {
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "completionProvider": { "triggerCharacters": ["{", " ", ":"] },
+                    "hoverProvider": true
+                },
+                "serverInfo": { "name": "golem", "version": version() }
+            }),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Value::Null
+            }
+            "textDocument/didOpen" => {
+                let (Some(uri), Some(text)) = (
+                    params["textDocument"]["uri"].as_str(),
+                    params["textDocument"]["text"].as_str(),
+                ) else {
+                    return Ok(Err((INVALID_PARAMS, "Missing text document".to_string())));
+                };
+                self.documents.insert(uri.to_string(), text.to_string());
+                self.publish_diagnostics(uri, output)?;
+                Value::Null
+            }
+            "textDocument/didChange" => {
+                let (Some(uri), Some(text)) = (
+                    params["textDocument"]["uri"].as_str(),
+                    params["contentChanges"]
+                        .as_array()
+                        .and_then(|changes| changes.last())
+                        .and_then(|change| change["text"].as_str()),
+                ) else {
+                    return Ok(Err((INVALID_PARAMS, "Missing content change".to_string())));
+                };
+                self.documents.insert(uri.to_string(), text.to_string());
+                self.publish_diagnostics(uri, output)?;
+                Value::Null
+            }
+            "textDocument/didClose" => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.documents.remove(uri);
+                    write_notification(
+                        output,
+                        "textDocument/publishDiagnostics",
+                        json!({ "uri": uri, "diagnostics": [] }),
+                    )?;
+                }
+                Value::Null
+            }
+            "textDocument/completion" => match self.document_position(&params) {
+                Some((text, position)) => {
+                    json!(completions(text, position, &self.component_names()))
+                }
+                None => Value::Null,
+            },
+            "textDocument/hover" => match self
+                .document_position(&params)
+                .and_then(|(text, position)| hover(text, position))
+            {
+                Some(doc) => json!({ "contents": { "kind": "markdown", "value": doc } }),
+                None => Value::Null,
+            },
+            method if method.starts_with("$/") || method == "initialized" => Value::Null,
+            method => return Ok(Err((METHOD_NOT_FOUND, format!("Unknown method: {method}")))),
+        };
+        Ok(Ok(result))
+    }
+
+    /// Known component names, from the application in the current directory and from the
+    /// open documents
+    fn component_names(&self) -> BTreeSet<String> {
+        let mut component_names = self.app_component_names.clone();
+        for text in self.documents.values() {
+            component_names.extend(defined_component_names(text));
+        }
+        component_names
+    }
+
+    fn document_position(&self, params: &Value) -> Option<(&str, LspPosition)> {
+        let text = self
+            .documents
+            .get(params["textDocument"]["uri"].as_str()?)?;
+        let position = LspPosition {
+            line: params["position"]["line"].as_u64()? as usize,
+            character: params["position"]["character"].as_u64()? as usize,
+        };
+        Some((text, position))
+    }
+
+    fn publish_diagnostics(&self, uri: &str, output: &mut impl Write) -> anyhow::Result<()> {
+        let diagnostics = if is_yaml_uri(uri) {
+            self.documents
+                .get(uri)
+                .map(|text| diagnostics(text, &self.component_names()))
+                .unwrap_or_default()
+        } else {
+            vec![]
+        };
+        write_notification(
+            output,
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        )
+    }
+}
+
+fn is_yaml_uri(uri: &str) -> bool {
+    uri.ends_with(".yaml") || uri.ends_with(".yml")
+}
+
+/// Reads a message with its Content-Length header, returns None at the end of the input
+fn read_message(input: &mut impl BufRead) -> anyhow::Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                content_length = Some(
+                    value
+                        .trim()
+                        .parse::<usize>()
+                        .with_context(|| anyhow!("Invalid Content-Length header: {header}"))?,
+                );
+            }
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        bail!("Missing Content-Length header");
+    };
+    let mut content = vec![0; content_length];
+    input.read_exact(&mut content)?;
+    Ok(Some(
+        serde_json::from_slice(&content).context("Failed to parse JSON-RPC message")?,
+    ))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> anyhow::Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(
+        output,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    output.flush()?;
+    Ok(())
+}
+
+fn write_notification(output: &mut impl Write, method: &str, params: Value) -> anyhow::Result<()> {
+    write_message(
+        output,
+        &json!({ "jsonrpc": "2.0", "method": method, "params": params }),
+    )
+}
@@ -0,0 +1,248 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, LogColorize};
+use crate::model::metrics::{
+    parse_metrics, render_openmetrics, MetricCategory, MetricFamily, MetricView,
+};
+use crate::model::text::fmt::{log_error, log_warn};
+use crate::model::{ComponentName, WorkerName};
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use url::Url;
+
+pub struct MetricsCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl MetricsCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn cmd_metrics(
+        &self,
+        component_name: Option<ComponentName>,
+        worker_name: Option<WorkerName>,
+        all: bool,
+        rate_window: Option<u64>,
+        openmetrics: bool,
+    ) -> anyhow::Result<()> {
+        // Metrics are matched by label values, components are usually labeled by their ID
+        let mut label_values = Vec::<Vec<String>>::new();
+        if let Some(component_name) = &component_name {
+            let project = self
+                .ctx
+                .cloud_project_handler()
+                .opt_select_project(None)
+                .await?;
+            let Some(component) = self
+                .ctx
+                .component_handler()
+                .component(project.as_ref(), component_name.into(), None)
+                .await?
+            else {
+                log_error(format!(
+                    "Component not found: {}",
+                    component_name.0.log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            };
+            label_values.push(vec![
+                component_name.0.clone(),
+                component.versioned_component_id.component_id.to_string(),
+            ]);
+        }
+        if let Some(worker_name) = &worker_name {
+            label_values.push(vec![worker_name.0.clone()]);
+        }
+
+        let endpoints = self.metrics_endpoints();
+
+        let first_sample = self.fetch_all(&endpoints).await?;
+        let second_sample = match rate_window {
+            Some(rate_window) => {
+                log_action(
+                    "Sampling",
+                    format!("metrics again in {rate_window} second(s) for calculating rates"),
+                );
+                let started_at = Instant::now();
+                tokio::time::sleep(Duration::from_secs(rate_window)).await;
+                Some((self.fetch_all(&endpoints).await?, started_at.elapsed()))
+            }
+            None => None,
+        };
+
+        let (families, previous_families, elapsed) = match second_sample {
+            Some((second_sample, elapsed)) => (second_sample, Some(first_sample), Some(elapsed)),
+            None => (first_sample, None, None),
+        };
+
+        let families = families
+            .into_iter()
+            .map(|(service, families)| {
+                (
+                    service,
+                    filter_families(families, &label_values, all || openmetrics),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        if openmetrics {
+            print!(
+                "{}",
+                render_openmetrics(
+                    &families
+                        .into_iter()
+                        .flat_map(|(_, families)| families)
+                        .collect::<Vec<_>>()
+                )
+            );
+            return Ok(());
+        }
+
+        let previous_values = previous_families
+            .iter()
+            .flatten()
+            .flat_map(|(service, families)| {
+                families.iter().flat_map(move |family| {
+                    family.samples.iter().map(move |sample| {
+                        (
+                            (service.clone(), sample.name.clone(), sample.labels.clone()),
+                            sample.value,
+                        )
+                    })
+                })
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut view = Vec::<MetricView>::new();
+        for (service, families) in families {
+            for family in families {
+                let is_counter = family.metric_type.as_deref() == Some("counter");
+                let category = MetricCategory::from_metric_name(&family.name);
+                for sample in family.samples {
+                    let rate = elapsed.filter(|_| is_counter).and_then(|elapsed| {
+                        previous_values
+                            .get(&(service.clone(), sample.name.clone(), sample.labels.clone()))
+                            .map(|previous| (sample.value - previous) / elapsed.as_secs_f64())
+                    });
+                    view.push(MetricView {
+                        service: service.clone(),
+                        category,
+                        name: sample.name,
+                        labels: sample.labels,
+                        value: sample.value,
+                        rate,
+                    });
+                }
+            }
+        }
+        view.sort_by(|a, b| {
+            (a.category, &a.service, &a.name).cmp(&(b.category, &b.service, &b.name))
+        });
+
+        self.ctx.log_handler().log_view(&view);
+
+        Ok(())
+    }
+
+    /// Metrics endpoints of the services, when all services are behind the same URL
+    /// (e.g. using the local router), they are only queried once
+    fn metrics_endpoints(&self) -> Vec<(String, Url)> {
+        let client_config = self.ctx.client_config();
+        let mut endpoints = Vec::<(String, Url)>::new();
+        for (service, url) in [
+            ("component-service", &client_config.component_url),
+            ("worker-service", &client_config.worker_url),
+        ] {
+            let mut url = url.clone();
+            url.set_query(None);
+            if let Ok(mut segments) = url.path_segments_mut() {
+                segments.pop_if_empty().push("metrics");
+            }
+            match endpoints.iter_mut().find(|(_, existing)| *existing == url) {
+                Some((existing_service, _)) => *existing_service = "golem".to_string(),
+                None => endpoints.push((service.to_string(), url)),
+            }
+        }
+        endpoints
+    }
+
+    async fn fetch_all(
+        &self,
+        endpoints: &[(String, Url)],
+    ) -> anyhow::Result<Vec<(String, Vec<MetricFamily>)>> {
+        let mut results = Vec::new();
+        for (service, url) in endpoints {
+            match self.fetch(url).await {
+                Ok(families) => results.push((service.clone(), families)),
+                Err(error) => log_warn(format!(
+                    "Failed to get metrics of {}: {error:#}",
+                    service.log_color_highlight()
+                )),
+            }
+        }
+
+        if results.is_empty() {
+            log_error("None of the metrics endpoints are available");
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(results)
+    }
+
+    async fn fetch(&self, url: &Url) -> anyhow::Result<Vec<MetricFamily>> {
+        let text = self
+            .ctx
+            .file_download_client()
+            .get(url.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .with_context(|| anyhow!("Failed to get metrics from {url}"))?
+            .text()
+            .await
+            .with_context(|| anyhow!("Failed to get metrics from {url}"))?;
+        Ok(parse_metrics(&text))
+    }
+}
+
+/// Keeps the samples which have a label for each of the label value groups, and optionally
+/// only the families of the invocation, error and memory categories
+fn filter_families(
+    families: Vec<MetricFamily>,
+    label_values: &[Vec<String>],
+    all: bool,
+) -> Vec<MetricFamily> {
+    families
+        .into_iter()
+        .filter(|family| {
+            all || MetricCategory::from_metric_name(&family.name) != MetricCategory::Other
+        })
+        .filter_map(|mut family| {
+            family.samples.retain(|sample| {
+                label_values
+                    .iter()
+                    .all(|values| sample.labels.values().any(|value| values.contains(value)))
+            });
+            (!family.samples.is_empty()).then_some(family)
+        })
+        .collect()
+}
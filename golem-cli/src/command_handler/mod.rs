@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::app::error::AppValidationError;
+use crate::cancellation::CANCELLED_EXIT_CODE;
 #[cfg(feature = "server-commands")]
 use crate::command::server::ServerSubcommand;
 use crate::command::{
@@ -27,6 +28,7 @@ use crate::command_handler::api::deployment::ApiDeploymentCommandHandler;
 use crate::command_handler::api::security_scheme::ApiSecuritySchemeCommandHandler;
 use crate::command_handler::api::ApiCommandHandler;
 use crate::command_handler::app::AppCommandHandler;
+use crate::command_handler::apply::ApplyCommandHandler;
 use crate::command_handler::cloud::account::grant::CloudAccountGrantCommandHandler;
 use crate::command_handler::cloud::account::CloudAccountCommandHandler;
 use crate::command_handler::cloud::project::plugin::CloudProjectPluginCommandHandler;
@@ -34,22 +36,36 @@ use crate::command_handler::cloud::project::policy::CloudProjectPolicyCommandHan
 use crate::command_handler::cloud::project::CloudProjectCommandHandler;
 use crate::command_handler::cloud::token::CloudTokenCommandHandler;
 use crate::command_handler::cloud::CloudCommandHandler;
+use crate::command_handler::completion::CompletionHandler;
 use crate::command_handler::component::plugin::ComponentPluginCommandHandler;
 use crate::command_handler::component::plugin_installation::PluginInstallationHandler;
 use crate::command_handler::component::ComponentCommandHandler;
+use crate::command_handler::console::ConsoleHandler;
+use crate::command_handler::doctor::DoctorCommandHandler;
+use crate::command_handler::events::EventsCommandHandler;
+use crate::command_handler::export::ExportCommandHandler;
+use crate::command_handler::extension::ExtensionCommandHandler;
 use crate::command_handler::interactive::InteractiveHandler;
+use crate::command_handler::introspect::IntrospectCommandHandler;
+use crate::command_handler::local_server::LocalServerCommandHandler;
 use crate::command_handler::log::LogHandler;
+use crate::command_handler::lsp::LspCommandHandler;
+use crate::command_handler::metrics::MetricsCommandHandler;
 use crate::command_handler::partial_match::ErrorHandler;
 use crate::command_handler::plugin::PluginCommandHandler;
 use crate::command_handler::profile::config::ProfileConfigCommandHandler;
+use crate::command_handler::profile::context::ProfileContextCommandHandler;
 use crate::command_handler::profile::ProfileCommandHandler;
 use crate::command_handler::rib_repl::RibReplHandler;
+use crate::command_handler::sdk::SdkCommandHandler;
 use crate::command_handler::worker::WorkerCommandHandler;
 use crate::context::Context;
-use crate::error::{ContextInitHintError, HintError, NonSuccessfulExit};
+use crate::error::service::ServiceError;
+use crate::error::{ContextInitHintError, HintError, NonSuccessfulExit, NonSuccessfulExitWithCode};
 use crate::log::{logln, set_log_output, Output};
 use crate::model::text::fmt::log_error;
-use crate::{command_name, init_tracing};
+use crate::timings::Timings;
+use crate::{command_name, init_tracing, init_tracing_with_timings};
 use anyhow::anyhow;
 use clap::CommandFactory;
 use clap_complete::Shell;
@@ -60,18 +76,31 @@ use futures_util::FutureExt;
 use std::ffi::OsString;
 use std::process::ExitCode;
 use std::sync::Arc;
-use tracing::{debug, Level};
+use std::time::Instant;
+use tracing::{debug, info_span, Instrument, Level};
 
 mod api;
 mod app;
+mod apply;
 mod cloud;
+mod completion;
 mod component;
+mod console;
+mod doctor;
+mod events;
+mod export;
+mod extension;
 pub(crate) mod interactive;
+mod introspect;
+mod local_server;
 mod log;
+mod lsp;
+mod metrics;
 mod partial_match;
 mod plugin;
 mod profile;
 mod rib_repl;
+mod sdk;
 mod worker;
 
 // NOTE: We are explicitly not using #[async_trait] here to be able to NOT have a Send bound
@@ -113,6 +142,8 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
         log_output_for_help: Option<Output>,
         hooks: Arc<Hooks>,
     ) -> anyhow::Result<Self> {
+        global_flags.check_env_overrides()?;
+
         let start_local_server_yes = Arc::new(tokio::sync::RwLock::new(global_flags.yes));
         Ok(Self {
             ctx: Arc::new(
@@ -184,8 +215,17 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
         I: IntoIterator<Item = T>,
         T: Into<OsString> + Clone,
     {
+        let parse_started_at = Instant::now();
         let result = match GolemCliCommand::try_parse_from_lenient(args_iterator, true) {
             GolemCliCommandParseResult::FullMatch(command) => {
+                let print_timings = command.global_flags.timings;
+                let trace_file = command.global_flags.trace_file.clone();
+                let timings = (print_timings || trace_file.is_some()).then(|| {
+                    let timings = Timings::new(parse_started_at);
+                    timings.record("parse_args", parse_started_at, Instant::now());
+                    timings
+                });
+
                 #[cfg(feature = "server-commands")]
                 let verbosity = if matches!(command.subcommand, GolemCliSubcommand::Server { .. }) {
                     Hooks::override_verbosity(command.global_flags.verbosity())
@@ -204,20 +244,43 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                 #[cfg(not(feature = "server-commands"))]
                 let pretty_mode = false;
 
-                init_tracing(verbosity, pretty_mode);
+                init_tracing_with_timings(verbosity, pretty_mode, timings.as_ref());
+
+                // Completion candidates are printed to stdout, so nothing else can be logged
+                if matches!(command.subcommand, GolemCliSubcommand::Complete { .. }) {
+                    set_log_output(Output::None);
+                }
+
+                // The language server uses stdio for the protocol messages, so logs have to go
+                // to stderr, and the manifest warning confirms have to be silenced
+                let log_output =
+                    matches!(command.subcommand, GolemCliSubcommand::Lsp).then_some(Output::Stderr);
 
                 match Self::new_with_init_hint_error_handler(
                     command.global_flags.clone(),
-                    None,
+                    log_output,
                     hooks,
                 )
+                .instrument(info_span!("context_init"))
                 .await
                 {
                     Ok(handler) => {
+                        // Cancelled bulk operations can complete with partial results
                         let result = handler
                             .handle_command(command)
+                            .instrument(info_span!("command"))
                             .await
-                            .map(|()| ExitCode::SUCCESS);
+                            .map(|()| {
+                                if handler.ctx.cancellation().is_cancelled() {
+                                    ExitCode::from(CANCELLED_EXIT_CODE)
+                                } else {
+                                    ExitCode::SUCCESS
+                                }
+                            });
+                        handler.ctx.log_http_cache_stats();
+                        if let Some(timings) = &timings {
+                            timings.report(print_timings, trace_file.as_deref());
+                        }
 
                         match result {
                             Ok(result) => Ok(result),
@@ -229,6 +292,25 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                                         .error_handler()
                                         .handle_hint_errors(hint_error)
                                         .map(|()| ExitCode::FAILURE)
+                                } else if error
+                                    .downcast_ref::<ServiceError>()
+                                    .is_some_and(|error| error.is_unauthorized())
+                                {
+                                    handler
+                                        .ctx
+                                        .error_handler()
+                                        .handle_unauthorized_error(&error)
+                                        .await
+                                        .map(|()| ExitCode::FAILURE)
+                                } else if error
+                                    .downcast_ref::<ServiceError>()
+                                    .is_some_and(|error| error.is_deserialization_error())
+                                {
+                                    handler
+                                        .ctx
+                                        .error_handler()
+                                        .handle_deserialization_error(&error)
+                                        .map(|()| ExitCode::FAILURE)
                                 } else {
                                     Err(error)
                                 }
@@ -277,6 +359,28 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                     Err(err) => Err(err),
                 }
             }
+            GolemCliCommandParseResult::Extension {
+                fallback_command,
+                extension_path,
+                args,
+            } => {
+                init_tracing(fallback_command.global_flags.verbosity(), false);
+
+                match Self::new_with_init_hint_error_handler(
+                    fallback_command.global_flags.clone(),
+                    None,
+                    hooks,
+                )
+                .await
+                {
+                    Ok(handler) => handler.ctx.extension_handler().run_extension(
+                        &fallback_command.global_flags,
+                        &extension_path,
+                        args,
+                    ),
+                    Err(error) => Err(error),
+                }
+            }
             GolemCliCommandParseResult::Error {
                 error,
                 fallback_command,
@@ -290,7 +394,9 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
         };
 
         result.unwrap_or_else(|error| {
-            if error.downcast_ref::<NonSuccessfulExit>().is_some() {
+            if let Some(NonSuccessfulExitWithCode(exit_code)) = error.downcast_ref() {
+                return ExitCode::from(*exit_code);
+            } else if error.downcast_ref::<NonSuccessfulExit>().is_some() {
                 // NOP
             } else if error
                 .downcast_ref::<Arc<anyhow::Error>>()
@@ -330,6 +436,44 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
             GolemCliSubcommand::Plugin { subcommand } => {
                 self.ctx.plugin_handler().handle_command(subcommand).await
             }
+            GolemCliSubcommand::Apply {
+                project,
+                file,
+                prune,
+                dry_run,
+            } => {
+                self.ctx
+                    .apply_handler()
+                    .cmd_apply(project, file, prune, dry_run)
+                    .await
+            }
+            GolemCliSubcommand::Export { subcommand } => {
+                self.ctx.export_handler().handle_command(subcommand).await
+            }
+            GolemCliSubcommand::Sdk { subcommand } => {
+                self.ctx.sdk_handler().handle_command(subcommand).await
+            }
+            GolemCliSubcommand::Events { subcommand } => {
+                self.ctx.events_handler().handle_command(subcommand).await
+            }
+            GolemCliSubcommand::Metrics {
+                component,
+                worker,
+                all,
+                rate_window,
+                openmetrics,
+            } => {
+                self.ctx
+                    .metrics_handler()
+                    .cmd_metrics(component, worker, all, rate_window, openmetrics)
+                    .await
+            }
+            GolemCliSubcommand::Extension { subcommand } => {
+                self.ctx
+                    .extension_handler()
+                    .handle_command(subcommand)
+                    .await
+            }
             GolemCliSubcommand::Profile { subcommand } => {
                 self.ctx.profile_handler().handle_command(subcommand).await
             }
@@ -339,9 +483,27 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                     .handler_server_commands(self.ctx.clone(), subcommand)
                     .await
             }
+            #[cfg(not(feature = "server-commands"))]
+            GolemCliSubcommand::Server { subcommand } => {
+                self.ctx
+                    .local_server_handler()
+                    .handle_command(subcommand)
+                    .await
+            }
             GolemCliSubcommand::Cloud { subcommand } => {
                 self.ctx.cloud_handler().handle_command(subcommand).await
             }
+            GolemCliSubcommand::Login => self.ctx.cloud_handler().cmd_login().await,
+            GolemCliSubcommand::Context { subcommand } => {
+                self.ctx
+                    .profile_context_handler()
+                    .handle_command(subcommand)
+                    .await
+            }
+            GolemCliSubcommand::Doctor => self.ctx.doctor_handler().cmd_doctor().await,
+            GolemCliSubcommand::Rib { subcommand } => {
+                self.ctx.rib_repl_handler().handle_command(subcommand).await
+            }
             GolemCliSubcommand::Repl {
                 component_name,
                 version,
@@ -351,7 +513,15 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
                     .cmd_repl(component_name.component_name, version)
                     .await
             }
+            GolemCliSubcommand::Console => self.ctx.console_handler().cmd_console().await,
+            GolemCliSubcommand::Introspect { subcommand } => {
+                self.ctx.introspect_handler().handle_command(subcommand)
+            }
+            GolemCliSubcommand::Lsp => self.ctx.lsp_handler().cmd_lsp().await,
             GolemCliSubcommand::Completion { shell } => self.cmd_completion(shell),
+            GolemCliSubcommand::Complete { words } => {
+                self.ctx.completion_handler().cmd_complete(words).await
+            }
         }
     }
 
@@ -360,6 +530,9 @@ impl<Hooks: CommandHandlerHooks + 'static> CommandHandler<Hooks> {
         let command_name = command_name();
         debug!(command_name, shell=%shell, "completion");
         clap_complete::generate(shell, &mut command, command_name, &mut std::io::stdout());
+        if let Some(script) = completion::dynamic_completion_script(shell, command_name) {
+            print!("{script}");
+        }
         Ok(())
     }
 }
@@ -376,6 +549,7 @@ pub trait Handlers {
     fn api_handler(&self) -> ApiCommandHandler;
     fn api_security_scheme_handler(&self) -> ApiSecuritySchemeCommandHandler;
     fn app_handler(&self) -> AppCommandHandler;
+    fn apply_handler(&self) -> ApplyCommandHandler;
     fn cloud_account_grant_handler(&self) -> CloudAccountGrantCommandHandler;
     fn cloud_account_handler(&self) -> CloudAccountCommandHandler;
     fn cloud_handler(&self) -> CloudCommandHandler;
@@ -384,15 +558,27 @@ pub trait Handlers {
     fn cloud_project_policy_handler(&self) -> CloudProjectPolicyCommandHandler;
     fn cloud_token_handler(&self) -> CloudTokenCommandHandler;
     fn component_handler(&self) -> ComponentCommandHandler;
+    fn completion_handler(&self) -> CompletionHandler;
     fn component_plugin_handler(&self) -> ComponentPluginCommandHandler;
+    fn console_handler(&self) -> ConsoleHandler;
+    fn doctor_handler(&self) -> DoctorCommandHandler;
     fn error_handler(&self) -> ErrorHandler;
+    fn events_handler(&self) -> EventsCommandHandler;
+    fn export_handler(&self) -> ExportCommandHandler;
+    fn extension_handler(&self) -> ExtensionCommandHandler;
     fn interactive_handler(&self) -> InteractiveHandler;
+    fn introspect_handler(&self) -> IntrospectCommandHandler;
+    fn local_server_handler(&self) -> LocalServerCommandHandler;
     fn log_handler(&self) -> LogHandler;
+    fn lsp_handler(&self) -> LspCommandHandler;
+    fn metrics_handler(&self) -> MetricsCommandHandler;
     fn plugin_installation_handler(&self) -> PluginInstallationHandler;
     fn plugin_handler(&self) -> PluginCommandHandler;
     fn profile_config_handler(&self) -> ProfileConfigCommandHandler;
+    fn profile_context_handler(&self) -> ProfileContextCommandHandler;
     fn profile_handler(&self) -> ProfileCommandHandler;
     fn rib_repl_handler(&self) -> RibReplHandler;
+    fn sdk_handler(&self) -> SdkCommandHandler;
     fn worker_handler(&self) -> WorkerCommandHandler;
 }
 
@@ -429,6 +615,10 @@ impl Handlers for Arc<Context> {
         AppCommandHandler::new(self.clone())
     }
 
+    fn apply_handler(&self) -> ApplyCommandHandler {
+        ApplyCommandHandler::new(self.clone())
+    }
+
     fn cloud_account_grant_handler(&self) -> CloudAccountGrantCommandHandler {
         CloudAccountGrantCommandHandler::new(self.clone())
     }
@@ -461,22 +651,62 @@ impl Handlers for Arc<Context> {
         ComponentCommandHandler::new(self.clone())
     }
 
+    fn completion_handler(&self) -> CompletionHandler {
+        CompletionHandler::new(self.clone())
+    }
+
     fn component_plugin_handler(&self) -> ComponentPluginCommandHandler {
         ComponentPluginCommandHandler::new(self.clone())
     }
 
+    fn console_handler(&self) -> ConsoleHandler {
+        ConsoleHandler::new(self.clone())
+    }
+
+    fn doctor_handler(&self) -> DoctorCommandHandler {
+        DoctorCommandHandler::new(self.clone())
+    }
+
     fn error_handler(&self) -> ErrorHandler {
         ErrorHandler::new(self.clone())
     }
 
+    fn events_handler(&self) -> EventsCommandHandler {
+        EventsCommandHandler::new(self.clone())
+    }
+
+    fn export_handler(&self) -> ExportCommandHandler {
+        ExportCommandHandler::new(self.clone())
+    }
+
+    fn extension_handler(&self) -> ExtensionCommandHandler {
+        ExtensionCommandHandler::new(self.clone())
+    }
+
     fn interactive_handler(&self) -> InteractiveHandler {
         InteractiveHandler::new(self.clone())
     }
 
+    fn introspect_handler(&self) -> IntrospectCommandHandler {
+        IntrospectCommandHandler::new(self.clone())
+    }
+
+    fn local_server_handler(&self) -> LocalServerCommandHandler {
+        LocalServerCommandHandler::new(self.clone())
+    }
+
     fn log_handler(&self) -> LogHandler {
         LogHandler::new(self.clone())
     }
 
+    fn lsp_handler(&self) -> LspCommandHandler {
+        LspCommandHandler::new(self.clone())
+    }
+
+    fn metrics_handler(&self) -> MetricsCommandHandler {
+        MetricsCommandHandler::new(self.clone())
+    }
+
     fn plugin_installation_handler(&self) -> PluginInstallationHandler {
         PluginInstallationHandler::new(self.clone())
     }
@@ -489,6 +719,10 @@ impl Handlers for Arc<Context> {
         ProfileConfigCommandHandler::new(self.clone())
     }
 
+    fn profile_context_handler(&self) -> ProfileContextCommandHandler {
+        ProfileContextCommandHandler::new(self.clone())
+    }
+
     fn profile_handler(&self) -> ProfileCommandHandler {
         ProfileCommandHandler::new(self.clone())
     }
@@ -497,6 +731,10 @@ impl Handlers for Arc<Context> {
         RibReplHandler::new(self.clone())
     }
 
+    fn sdk_handler(&self) -> SdkCommandHandler {
+        SdkCommandHandler::new(self.clone())
+    }
+
     fn worker_handler(&self) -> WorkerCommandHandler {
         WorkerCommandHandler::new(self.clone())
     }
@@ -16,7 +16,9 @@ use crate::command::{
     builtin_app_subcommands, help_target_to_command, GolemCliCommandPartialMatch,
     GolemCliGlobalFlags,
 };
+use crate::command_handler::interactive::InteractiveHandler;
 use crate::command_handler::Handlers;
+use crate::command_name;
 use crate::config::{Config, ProfileName};
 use crate::context::Context;
 use crate::error::{ContextInitHintError, HintError, ShowClapHelpTarget};
@@ -27,6 +29,9 @@ use crate::model::component::show_exported_functions;
 use crate::model::text::fmt::{log_error, log_text_view, NestedTextViewIndent};
 use crate::model::text::help::{AvailableFunctionNamesHelp, WorkerNameHelp};
 use crate::model::{ComponentNameMatchKind, Format};
+use crate::server_version::{
+    parse_version, server_version_compatibility, ServerVersionCompatibility,
+};
 use colored::Colorize;
 use std::collections::BTreeSet;
 use std::path::Path;
@@ -215,6 +220,82 @@ impl ErrorHandler {
         }
     }
 
+    /// Unauthorized errors usually mean that the token expired or was revoked since the
+    /// command started, for OAuth2 profiles we offer to log in again
+    pub async fn handle_unauthorized_error(&self, error: &anyhow::Error) -> anyhow::Result<()> {
+        logln("");
+        log_error(format!("{error:#}"));
+        logln("");
+
+        if !self.ctx.uses_oauth2_token() {
+            logln("The token was rejected, it might be expired or deleted.");
+            logln(format!(
+                "Create a new token with '{}' and use it with '--token', GOLEM_TOKEN, or in a new profile.",
+                format!("{} cloud token new", command_name()).log_color_highlight()
+            ));
+            return Ok(());
+        }
+
+        if InteractiveHandler::confirm_login_with_expired_token()? {
+            self.ctx
+                .auth()?
+                .login(self.ctx.profile_name(), self.ctx.config_dir())
+                .await?;
+            logln("");
+            log_action(
+                "Renewed",
+                format!(
+                    "token of profile {}, re-run the command to continue",
+                    self.ctx.profile_name().0.log_color_highlight()
+                ),
+            );
+        } else {
+            logln(format!(
+                "Use '{}' to log in again.",
+                format!("{} login", command_name()).log_color_highlight()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Responses the CLI cannot deserialize are usually caused by incompatible server versions
+    pub fn handle_deserialization_error(&self, error: &anyhow::Error) -> anyhow::Result<()> {
+        logln("");
+        log_error(format!("{error:#}"));
+        logln("");
+
+        let server_version = self.ctx.known_server_version();
+        let compatibility = parse_version(crate::version()).zip(server_version).map(
+            |(cli_version, server_version)| {
+                server_version_compatibility(&cli_version, server_version)
+            },
+        );
+
+        match (server_version, compatibility) {
+            (Some(server_version), Some(compatibility))
+                if compatibility != ServerVersionCompatibility::Supported =>
+            {
+                logln(format!(
+                    "The server version {} is not supported by {} {}, install a {} version matching the server.",
+                    server_version.to_string().log_color_highlight(),
+                    command_name(),
+                    crate::version().log_color_highlight(),
+                    command_name()
+                ));
+            }
+            _ => {
+                logln("The response of the server could not be processed, this is usually caused by a server version which is not supported by the CLI.");
+                logln(format!(
+                    "Use '{}' to check the versions of the CLI and the server.",
+                    format!("{} doctor", command_name()).log_color_highlight()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn handle_hint_errors(&self, hint_error: &HintError) -> anyhow::Result<()> {
         match hint_error {
             HintError::NoApplicationManifestFound => {
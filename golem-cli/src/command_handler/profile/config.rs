@@ -13,13 +13,16 @@
 // limitations under the License.
 
 use crate::command::profile::config::ProfileConfigSubcommand;
-use crate::config::{Config, ProfileName};
+use crate::command_handler::Handlers;
+use crate::config::{Config, ProfileHttpConfig, ProfileName, ProfileTlsConfig};
 use crate::context::Context;
 use crate::error::NonSuccessfulExit;
-use crate::log::log_action;
+use crate::log::{log_action, LogColorize};
 use crate::model::text::fmt::log_error;
 use crate::model::Format;
 use anyhow::bail;
+use itertools::Itertools;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub struct ProfileConfigCommandHandler {
@@ -40,13 +43,48 @@ impl ProfileConfigCommandHandler {
             ProfileConfigSubcommand::SetFormat { format } => {
                 self.cmd_set_format(profile_name, format)
             }
+            ProfileConfigSubcommand::SetDebugHttp { enabled } => {
+                self.cmd_set_debug_http(profile_name, enabled)
+            }
+            ProfileConfigSubcommand::SetHttp {
+                timeout,
+                connect_timeout,
+                retries,
+                retry_backoff,
+            } => self.cmd_set_http(
+                profile_name,
+                ProfileHttpConfig {
+                    timeout_ms: timeout.map(|d| d.num_milliseconds() as u64),
+                    connect_timeout_ms: connect_timeout.map(|d| d.num_milliseconds() as u64),
+                    retries,
+                    retry_backoff_ms: retry_backoff.map(|d| d.num_milliseconds() as u64),
+                },
+            ),
+            ProfileConfigSubcommand::SetTls {
+                ca_certificate,
+                client_certificate,
+                client_key,
+            } => self.cmd_set_tls(
+                profile_name,
+                ProfileTlsConfig {
+                    ca_certificate,
+                    client_certificate,
+                    client_key,
+                },
+            ),
+            ProfileConfigSubcommand::SetExtends { base_profile_name } => {
+                self.cmd_set_extends(profile_name, base_profile_name)
+            }
+            ProfileConfigSubcommand::SetCommandDefaults { command, flags } => {
+                self.cmd_set_command_defaults(profile_name, command, flags)
+            }
         }
     }
 
     fn cmd_set_format(&self, profile_name: ProfileName, format: Format) -> anyhow::Result<()> {
         match Config::get_profile(self.ctx.config_dir(), &profile_name)? {
             Some(mut profile) => {
-                profile.profile.config.default_format = format;
+                profile.profile.config.default_format = Some(format);
 
                 log_action(
                     "Updating",
@@ -67,4 +105,198 @@ impl ProfileConfigCommandHandler {
             }
         }
     }
+
+    fn cmd_set_debug_http(&self, profile_name: ProfileName, enabled: bool) -> anyhow::Result<()> {
+        match Config::get_profile(self.ctx.config_dir(), &profile_name)? {
+            Some(mut profile) => {
+                profile.profile.config.debug_http = Some(enabled);
+
+                log_action(
+                    "Updating",
+                    format!(
+                        "profile's HTTP debug logging for {} to {}",
+                        profile_name.0.log_color_highlight(),
+                        if enabled { "enabled" } else { "disabled" }
+                    ),
+                );
+                Config::set_profile(profile.name, profile.profile, self.ctx.config_dir())?;
+                log_action("Updated", "");
+
+                Ok(())
+            }
+            None => {
+                log_error(format!("Profile {profile_name} not found"));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+
+    fn cmd_set_http(
+        &self,
+        profile_name: ProfileName,
+        http: ProfileHttpConfig,
+    ) -> anyhow::Result<()> {
+        match Config::get_profile(self.ctx.config_dir(), &profile_name)? {
+            Some(mut profile) => {
+                profile.profile.config.http = http;
+
+                log_action(
+                    "Updating",
+                    format!(
+                        "profile's HTTP timeouts and retries for {}",
+                        profile_name.0.log_color_highlight()
+                    ),
+                );
+                Config::set_profile(profile.name, profile.profile, self.ctx.config_dir())?;
+                log_action("Updated", "");
+
+                Ok(())
+            }
+            None => {
+                log_error(format!("Profile {profile_name} not found"));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+
+    fn cmd_set_tls(&self, profile_name: ProfileName, tls: ProfileTlsConfig) -> anyhow::Result<()> {
+        // Paths are stored as absolute, so the profile works from any directory
+        let absolute = |path: Option<PathBuf>| -> anyhow::Result<Option<PathBuf>> {
+            match path {
+                Some(path) => match std::fs::canonicalize(&path) {
+                    Ok(path) => Ok(Some(path)),
+                    Err(err) => {
+                        log_error(format!(
+                            "Cannot access {}: {}",
+                            path.display().to_string().log_color_highlight(),
+                            err
+                        ));
+                        bail!(NonSuccessfulExit);
+                    }
+                },
+                None => Ok(None),
+            }
+        };
+        let tls = ProfileTlsConfig {
+            ca_certificate: absolute(tls.ca_certificate)?,
+            client_certificate: absolute(tls.client_certificate)?,
+            client_key: absolute(tls.client_key)?,
+        };
+
+        match Config::get_profile(self.ctx.config_dir(), &profile_name)? {
+            Some(mut profile) => {
+                profile.profile.config.tls = tls;
+
+                log_action(
+                    "Updating",
+                    format!(
+                        "profile's TLS certificates for {}",
+                        profile_name.0.log_color_highlight()
+                    ),
+                );
+                Config::set_profile(profile.name, profile.profile, self.ctx.config_dir())?;
+                log_action("Updated", "");
+
+                Ok(())
+            }
+            None => {
+                log_error(format!("Profile {profile_name} not found"));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+
+    fn cmd_set_extends(
+        &self,
+        profile_name: ProfileName,
+        base_profile_name: Option<ProfileName>,
+    ) -> anyhow::Result<()> {
+        match Config::get_profile(self.ctx.config_dir(), &profile_name)? {
+            Some(mut profile) => {
+                profile.profile.extends = base_profile_name.clone();
+
+                self.ctx
+                    .profile_handler()
+                    .check_profile_resolves(&profile.name, &profile.profile)?;
+
+                match &base_profile_name {
+                    Some(base_profile_name) => log_action(
+                        "Updating",
+                        format!(
+                            "profile {} to extend {}",
+                            profile_name.0.log_color_highlight(),
+                            base_profile_name.0.log_color_highlight()
+                        ),
+                    ),
+                    None => log_action(
+                        "Updating",
+                        format!(
+                            "profile {} to not extend any profile",
+                            profile_name.0.log_color_highlight()
+                        ),
+                    ),
+                }
+                Config::set_profile(profile.name, profile.profile, self.ctx.config_dir())?;
+                log_action("Updated", "");
+
+                Ok(())
+            }
+            None => {
+                log_error(format!("Profile {profile_name} not found"));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+
+    fn cmd_set_command_defaults(
+        &self,
+        profile_name: ProfileName,
+        command: String,
+        flags: Vec<String>,
+    ) -> anyhow::Result<()> {
+        let command = command.split_whitespace().join(" ");
+        if command.is_empty() {
+            log_error("The command path must not be empty");
+            bail!(NonSuccessfulExit);
+        }
+
+        match Config::get_profile(self.ctx.config_dir(), &profile_name)? {
+            Some(mut profile) => {
+                if flags.is_empty() {
+                    log_action(
+                        "Removing",
+                        format!(
+                            "default flags of command {} from profile {}",
+                            command.log_color_highlight(),
+                            profile_name.0.log_color_highlight()
+                        ),
+                    );
+                    profile.profile.config.command_defaults.remove(&command);
+                } else {
+                    log_action(
+                        "Updating",
+                        format!(
+                            "default flags of command {} for profile {} to {}",
+                            command.log_color_highlight(),
+                            profile_name.0.log_color_highlight(),
+                            flags.join(" ").log_color_highlight()
+                        ),
+                    );
+                    profile
+                        .profile
+                        .config
+                        .command_defaults
+                        .insert(command, flags);
+                }
+                Config::set_profile(profile.name, profile.profile, self.ctx.config_dir())?;
+                log_action("Updated", "");
+
+                Ok(())
+            }
+            None => {
+                log_error(format!("Profile {profile_name} not found"));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
 }
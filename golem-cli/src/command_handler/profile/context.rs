@@ -0,0 +1,113 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::profile::context::ContextSubcommand;
+use crate::command_handler::Handlers;
+use crate::config::{Config, NamedProfile, ProfileContext};
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::log::{log_action, log_warn_action, LogColorize};
+use crate::model::text::fmt::log_error;
+use anyhow::bail;
+use std::sync::Arc;
+
+pub struct ProfileContextCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl ProfileContextCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: ContextSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            ContextSubcommand::Set { entries } => self.cmd_set(entries),
+            ContextSubcommand::Get => self.cmd_get(),
+            ContextSubcommand::Clear => self.cmd_clear(),
+        }
+    }
+
+    fn cmd_set(&self, entries: Vec<(String, String)>) -> anyhow::Result<()> {
+        let mut profile = self.global_profile()?;
+        let context = &mut profile.profile.config.context;
+
+        for (key, value) in entries {
+            let value = (!value.is_empty()).then_some(value);
+            match key.as_str() {
+                "component" => context.component = value,
+                "worker" => context.worker = value,
+                _ => {
+                    log_error(format!(
+                        "Unknown context key: {}, accepted keys: {}, {}",
+                        key.log_color_error_highlight(),
+                        "component".log_color_highlight(),
+                        "worker".log_color_highlight()
+                    ));
+                    bail!(NonSuccessfulExit);
+                }
+            }
+        }
+
+        log_action(
+            "Updating",
+            format!(
+                "context of profile {}",
+                profile.name.0.log_color_highlight()
+            ),
+        );
+        let context = context.clone();
+        Config::set_profile(profile.name, profile.profile, self.ctx.config_dir())?;
+        self.ctx.log_handler().log_view(&context);
+
+        Ok(())
+    }
+
+    fn cmd_get(&self) -> anyhow::Result<()> {
+        self.ctx
+            .log_handler()
+            .log_view(&self.global_profile()?.profile.config.context);
+        Ok(())
+    }
+
+    fn cmd_clear(&self) -> anyhow::Result<()> {
+        let mut profile = self.global_profile()?;
+        profile.profile.config.context = ProfileContext::default();
+
+        log_warn_action(
+            "Clearing",
+            format!(
+                "context of profile {}",
+                profile.name.0.log_color_highlight()
+            ),
+        );
+        Config::set_profile(profile.name, profile.profile, self.ctx.config_dir())?;
+
+        Ok(())
+    }
+
+    fn global_profile(&self) -> anyhow::Result<NamedProfile> {
+        let profile_name = self.ctx.profile_name();
+        match Config::get_profile(self.ctx.config_dir(), profile_name)? {
+            Some(profile) => Ok(profile),
+            None => {
+                log_error(format!(
+                    "Profile {} is not a global profile, the context can only be stored in global profiles",
+                    profile_name.0.log_color_highlight()
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+    }
+}
@@ -13,19 +13,25 @@
 // limitations under the License.
 
 pub mod config;
+pub mod context;
 
 use crate::command::profile::ProfileSubcommand;
 use crate::command_handler::Handlers;
 use crate::config::{
-    AuthenticationConfig, Config, NamedProfile, Profile, ProfileConfig, ProfileName,
+    AuthSecret, AuthenticationConfig, Config, NamedProfile, Profile, ProfileConfig, ProfileName,
+    ProfileOrigins, SecretStorage,
 };
 use crate::context::Context;
 use crate::error::NonSuccessfulExit;
+use crate::fs;
+use crate::keychain;
 use crate::log::{log_action, log_warn_action, LogColorize};
-use crate::model::text::fmt::log_error;
+use crate::model::text::fmt::{log_error, log_warn};
 use crate::model::{Format, ProfileView};
-use anyhow::bail;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use itertools::Itertools;
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use url::Url;
 use uuid::Uuid;
@@ -50,20 +56,37 @@ impl ProfileCommandHandler {
                 default_format,
                 allow_insecure,
                 static_token,
+                secret_storage,
+                extends,
             } => self.cmd_new(
                 name,
                 set_active,
+                extends,
                 component_url,
                 worker_url,
                 cloud_url,
                 default_format,
                 allow_insecure,
                 static_token,
+                secret_storage,
             ),
             ProfileSubcommand::List => self.cmd_list(),
             ProfileSubcommand::Switch { profile_name } => self.cmd_switch(profile_name),
             ProfileSubcommand::Get { profile_name } => self.cmd_get(profile_name),
             ProfileSubcommand::Delete { profile_name } => self.cmd_delete(profile_name),
+            ProfileSubcommand::Export {
+                profile_name,
+                no_secrets,
+                output,
+            } => self.cmd_export(profile_name, no_secrets, output),
+            ProfileSubcommand::Import {
+                file,
+                name,
+                set_active,
+            } => self.cmd_import(file, name, set_active),
+            ProfileSubcommand::MigrateSecrets { profile_names, to } => {
+                self.cmd_migrate_secrets(profile_names, to)
+            }
             ProfileSubcommand::Config {
                 profile_name,
                 subcommand,
@@ -80,12 +103,14 @@ impl ProfileCommandHandler {
         &self,
         name: Option<ProfileName>,
         set_active: bool,
+        extends: Option<ProfileName>,
         component_url: Option<Url>,
         worker_url: Option<Url>,
         cloud_url: Option<Url>,
-        default_format: Format,
+        default_format: Option<Format>,
         allow_insecure: bool,
         static_token: Option<Uuid>,
+        secret_storage: SecretStorage,
     ) -> anyhow::Result<()> {
         let (name, profile, set_active) = match name {
             Some(name) => {
@@ -102,14 +127,21 @@ impl ProfileCommandHandler {
                 };
 
                 let profile = Profile {
+                    extends,
                     custom_url: component_url,
                     custom_cloud_url: cloud_url,
                     custom_worker_url: worker_url,
                     allow_insecure,
-                    config: ProfileConfig { default_format },
+                    config: ProfileConfig {
+                        default_format,
+                        secret_storage,
+                        ..ProfileConfig::default()
+                    },
                     auth,
                 };
 
+                self.check_profile_resolves(&name, &profile)?;
+
                 (name, profile, set_active)
             }
             None => self.ctx.interactive_handler().create_profile()?,
@@ -144,7 +176,11 @@ impl ProfileCommandHandler {
         let profiles = sorted_profiles
             .into_iter()
             .map(|(name, profile)| {
-                ProfileView::from_profile(&default_profile_name, NamedProfile { name, profile })
+                ProfileView::from_profile(
+                    &default_profile_name,
+                    NamedProfile { name, profile },
+                    ProfileOrigins::new(),
+                )
             })
             .collect::<Vec<_>>();
 
@@ -165,28 +201,26 @@ impl ProfileCommandHandler {
     }
 
     fn cmd_get(&self, profile_name: Option<ProfileName>) -> anyhow::Result<()> {
-        let default_profile = Config::get_default_profile(self.ctx.config_dir())?;
-        let default_profile_name = default_profile.name.clone();
+        let config = Config::from_dir(self.ctx.config_dir())?;
+        let default_profile_name = config.default_profile_name();
+        let profile_name = profile_name.unwrap_or_else(|| default_profile_name.clone());
 
-        let profile = match profile_name {
-            Some(profile_name) => {
-                match Config::get_profile(self.ctx.config_dir(), &profile_name)? {
-                    Some(profile) => profile,
-                    None => {
-                        log_error(format!(
-                            "Profile {} not found",
-                            profile_name.0.log_color_error_highlight()
-                        ));
-                        bail!(NonSuccessfulExit);
-                    }
-                }
+        let (profile, origins) = match config.resolve_profile(&profile_name)? {
+            Some(profile) => profile,
+            None => {
+                log_error(format!(
+                    "Profile {} not found",
+                    profile_name.0.log_color_error_highlight()
+                ));
+                bail!(NonSuccessfulExit);
             }
-            None => default_profile,
         };
 
-        self.ctx
-            .log_handler()
-            .log_view(&ProfileView::from_profile(&default_profile_name, profile));
+        self.ctx.log_handler().log_view(&ProfileView::from_profile(
+            &default_profile_name,
+            profile,
+            origins,
+        ));
 
         Ok(())
     }
@@ -212,6 +246,19 @@ impl ProfileCommandHandler {
             bail!(NonSuccessfulExit);
         }
 
+        let extending_profile_names = config.extending_profile_names(&profile_name);
+        if !extending_profile_names.is_empty() {
+            log_error(format!(
+                "Cannot delete profile {}, as it is extended by the following profile(s): {}",
+                profile_name.0.log_color_error_highlight(),
+                extending_profile_names
+                    .iter()
+                    .map(|name| name.0.log_color_highlight())
+                    .join(", ")
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
         Config::delete_profile(&profile_name, self.ctx.config_dir())?;
 
         log_warn_action(
@@ -221,4 +268,231 @@ impl ProfileCommandHandler {
 
         Ok(())
     }
+
+    fn cmd_export(
+        &self,
+        profile_name: Option<ProfileName>,
+        no_secrets: bool,
+        output: Option<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let mut profile = match profile_name {
+            Some(profile_name) => {
+                match Config::get_resolved_profile(self.ctx.config_dir(), &profile_name)? {
+                    Some((profile, _)) => profile,
+                    None => {
+                        log_error(format!(
+                            "Profile {} not found",
+                            profile_name.0.log_color_error_highlight()
+                        ));
+                        bail!(NonSuccessfulExit);
+                    }
+                }
+            }
+            None => Config::get_default_profile(self.ctx.config_dir())?,
+        };
+
+        // Inherited settings are already applied, so the exported profile is standalone
+        profile.profile.extends = None;
+
+        if no_secrets {
+            profile.profile.auth.redact_secrets();
+        }
+
+        let exported_profile = serde_json::to_string_pretty(&profile)?;
+
+        match output {
+            Some(output) => {
+                fs::write_str(&output, &exported_profile)?;
+                log_action(
+                    "Exported",
+                    format!(
+                        "profile {} to {}",
+                        profile.name.0.log_color_highlight(),
+                        output.log_color_highlight()
+                    ),
+                );
+            }
+            None => println!("{exported_profile}"),
+        }
+
+        if !no_secrets {
+            log_warn("The exported profile contains secrets, use --no-secrets for sharing");
+        }
+
+        Ok(())
+    }
+
+    fn cmd_import(
+        &self,
+        file: PathBuf,
+        name: Option<ProfileName>,
+        set_active: bool,
+    ) -> anyhow::Result<()> {
+        let imported_profile: NamedProfile = serde_json::from_str(&fs::read_to_string(&file)?)
+            .with_context(|| {
+                anyhow!(
+                    "Failed to parse exported profile: {}",
+                    file.log_color_highlight()
+                )
+            })?;
+
+        let name = name.unwrap_or(imported_profile.name);
+        let mut profile = imported_profile.profile;
+
+        if name.is_builtin() {
+            log_error(format!(
+                "The imported profile name {} is a builtin profile. Please choose another profile name with --name!",
+                name.0.log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        if Config::get_profile(self.ctx.config_dir(), &name)?.is_some() {
+            log_error(format!(
+                "Profile {} already exists. Please choose another profile name with --name, or delete the existing profile first!",
+                name.0.log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        if let AuthenticationConfig::Static(inner) = &mut profile.auth {
+            if inner.secret.is_stored_externally() {
+                match self.ctx.interactive_handler().prompt_static_token(&name)? {
+                    Some(token) => inner.secret = AuthSecret(token),
+                    None => bail!(NonSuccessfulExit),
+                }
+            }
+        }
+
+        log_action(
+            "Importing",
+            format!(
+                "profile {} from {}",
+                name.0.log_color_highlight(),
+                file.log_color_highlight()
+            ),
+        );
+        Config::set_profile(name.clone(), profile, self.ctx.config_dir())?;
+
+        if set_active {
+            log_action(
+                "Setting ",
+                format!(
+                    "imported profile {} as the active one",
+                    name.0.log_color_highlight()
+                ),
+            );
+            Config::set_active_profile_name(name, self.ctx.config_dir())?;
+        };
+
+        Ok(())
+    }
+
+    pub fn check_profile_resolves(
+        &self,
+        profile_name: &ProfileName,
+        profile: &Profile,
+    ) -> anyhow::Result<()> {
+        let mut config = Config::from_dir(self.ctx.config_dir())?;
+        config
+            .profiles
+            .insert(profile_name.clone(), profile.clone());
+
+        if let Err(err) = config.resolve_profile(profile_name) {
+            log_error(format!("{err:#}"));
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    fn cmd_migrate_secrets(
+        &self,
+        profile_names: Vec<ProfileName>,
+        to: SecretStorage,
+    ) -> anyhow::Result<()> {
+        // Secrets are loaded explicitly, so failing to read them from the keychain is detected
+        let config = Config::from_dir_with_external_secrets(self.ctx.config_dir())?;
+
+        let profile_names = if profile_names.is_empty() {
+            config.profiles.keys().cloned().sorted().collect::<Vec<_>>()
+        } else {
+            let unknown_profile_names = profile_names
+                .iter()
+                .filter(|name| !config.profiles.contains_key(name))
+                .collect::<Vec<_>>();
+            if !unknown_profile_names.is_empty() {
+                log_error(format!(
+                    "Profile(s) not found: {}",
+                    unknown_profile_names
+                        .iter()
+                        .map(|name| name.0.log_color_error_highlight())
+                        .join(", ")
+                ));
+                bail!(NonSuccessfulExit);
+            }
+            profile_names
+        };
+
+        let mut failed = false;
+        for profile_name in profile_names {
+            let mut profile = config.profiles.get(&profile_name).unwrap().clone();
+            let from = profile.config.secret_storage;
+
+            if from == to {
+                log_warn_action(
+                    "Skipping",
+                    format!(
+                        "profile {}, secrets are already using {} storage",
+                        profile_name.0.log_color_highlight(),
+                        to.to_string().log_color_highlight()
+                    ),
+                );
+                continue;
+            }
+
+            log_action(
+                "Migrating",
+                format!(
+                    "secrets of profile {} from {} to {}",
+                    profile_name.0.log_color_highlight(),
+                    from.to_string().log_color_highlight(),
+                    to.to_string().log_color_highlight()
+                ),
+            );
+
+            if from == SecretStorage::Keychain {
+                if let Some(secret) = profile.auth.secret_mut() {
+                    if secret.is_stored_externally() {
+                        match keychain::load_secret(&profile_name) {
+                            Ok(loaded_secret) => *secret = loaded_secret,
+                            Err(err) => {
+                                log_error(format!(
+                                    "Failed to load the secret of profile {} from the OS keychain, skipping the profile: {:#}",
+                                    profile_name.0.log_color_highlight(),
+                                    err
+                                ));
+                                failed = true;
+                                continue;
+                            }
+                        }
+                    }
+                }
+            }
+
+            profile.config.secret_storage = to;
+            Config::set_profile(profile_name.clone(), profile, self.ctx.config_dir())?;
+
+            // Only deleted once the secret is saved in the config file
+            if from == SecretStorage::Keychain {
+                keychain::delete_secret(&profile_name)?;
+            }
+        }
+
+        if failed {
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
 }
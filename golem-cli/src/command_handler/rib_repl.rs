@@ -12,23 +12,31 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::command::rib::RibSubcommand;
+use crate::command_handler::worker::lenient_parse_type_annotated_value;
 use crate::command_handler::Handlers;
 use crate::context::Context;
 use crate::error::NonSuccessfulExit;
-use crate::log::logln;
-use crate::model::component::ComponentView;
+use crate::log::{logln, LogColorize};
+use crate::model::component::{render_type, Component, ComponentView};
 use crate::model::text::component::ComponentReplStartedView;
-use crate::model::text::fmt::log_error;
+use crate::model::text::fmt::{format_rib_source_for_error, log_error};
+use crate::model::text::rib::RibEvalView;
 use crate::model::{ComponentName, ComponentNameMatchKind, IdempotencyKey, WorkerName};
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use async_trait::async_trait;
 use golem_rib_repl::{
     ReplComponentDependencies, RibDependencyManager, RibRepl, RibReplConfig, WorkerFunctionInvoke,
 };
 use golem_wasm_ast::analysis::AnalysedType;
 use golem_wasm_rpc::json::OptionallyValueAndTypeJson;
-use golem_wasm_rpc::ValueAndType;
-use rib::{ComponentDependency, ComponentDependencyKey};
+use golem_wasm_rpc::{print_value_and_type, ValueAndType};
+use itertools::Itertools;
+use rib::{
+    ComponentDependency, ComponentDependencyKey, RibCompiler, RibCompilerConfig, RibInput,
+    RibResult,
+};
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -43,53 +51,35 @@ impl RibReplHandler {
         Self { ctx }
     }
 
+    pub async fn handle_command(&self, command: RibSubcommand) -> anyhow::Result<()> {
+        match command {
+            RibSubcommand::Eval {
+                expression,
+                component,
+                version,
+                inputs,
+                evaluate,
+            } => {
+                self.cmd_eval(expression, component, version, inputs, evaluate)
+                    .await
+            }
+        }
+    }
+
     pub async fn cmd_repl(
         &self,
         component_name: Option<ComponentName>,
         component_version: Option<u64>,
     ) -> anyhow::Result<()> {
-        let selected_components = self
-            .ctx
-            .component_handler()
-            .must_select_components_by_app_dir_or_name(component_name.as_ref())
-            .await?;
-
-        let component_name = {
-            if selected_components.component_names.len() == 1 {
-                selected_components.component_names[0].clone()
-            } else {
-                self.ctx
-                    .interactive_handler()
-                    .select_component_for_repl(selected_components.component_names.clone())?
-            }
-        };
-
         // NOTE: we pre-create the ReplDependencies, because trying to do it in RibDependencyManager::get_dependencies
         //       results in thread safety errors on the path when cargo component could be called for client building
         let component = self
-            .ctx
-            .component_handler()
-            .component_by_name_with_auto_deploy(
-                selected_components.project.as_ref(),
-                ComponentNameMatchKind::App,
-                &component_name,
-                component_version.map(|v| v.into()),
-            )
+            .select_component(component_name, component_version)
             .await?;
 
-        let component_dependency_key = ComponentDependencyKey {
-            component_name: component.component_name.0.clone(),
-            component_id: component.versioned_component_id.component_id,
-            root_package_name: component.metadata.root_package_name.clone(),
-            root_package_version: component.metadata.root_package_version.clone(),
-        };
-
         self.ctx
             .set_rib_repl_dependencies(ReplComponentDependencies {
-                component_dependencies: vec![ComponentDependency::new(
-                    component_dependency_key,
-                    component.metadata.exports.clone(),
-                )],
+                component_dependencies: vec![component_dependency(&component)],
             })
             .await;
 
@@ -99,7 +89,7 @@ impl RibReplHandler {
             worker_function_invoke: Arc::new(self.clone()),
             printer: None,
             component_source: None,
-            prompt: None,
+            prompt: self.repl_prompt(),
             command_registry: None,
         })
         .await?;
@@ -118,6 +108,203 @@ impl RibReplHandler {
         repl.run().await;
         Ok(())
     }
+
+    async fn cmd_eval(
+        &self,
+        expression: String,
+        component_name: Option<ComponentName>,
+        component_version: Option<u64>,
+        inputs: Vec<(String, String)>,
+        evaluate: bool,
+    ) -> anyhow::Result<()> {
+        let expr = match rib::from_string(expression.as_str()) {
+            Ok(expr) => expr,
+            Err(err) => {
+                log_error(format!(
+                    "Failed to parse Rib expression:\n{}\n{}\n{}",
+                    format_rib_error(&err),
+                    "Rib source:".log_color_highlight(),
+                    format_rib_source_for_error(&expression, &err),
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        };
+
+        let component = self
+            .select_component(component_name, component_version)
+            .await?;
+
+        let compiler = RibCompiler::new(RibCompilerConfig::new(
+            vec![component_dependency(&component)],
+            vec![],
+            vec![],
+        ));
+
+        let compiled = match compiler.compile(expr) {
+            Ok(compiled) => compiled,
+            Err(err) => {
+                log_error(format!(
+                    "Failed to type-check Rib expression against component {}:\n{}",
+                    component.component_name.0.log_color_highlight(),
+                    format_rib_error(&err.to_string()),
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        };
+
+        let input_types = compiled.rib_input_type_info.types;
+
+        let mut input_values = HashMap::new();
+        for (name, wave) in inputs {
+            let Some(typ) = input_types.get(&name) else {
+                log_error(format!(
+                    "Unknown input variable {}, the expression uses the following inputs: {}",
+                    name.log_color_error_highlight(),
+                    if input_types.is_empty() {
+                        "-".to_string()
+                    } else {
+                        input_types
+                            .keys()
+                            .sorted()
+                            .map(|name| name.log_color_highlight())
+                            .join(", ")
+                    }
+                ));
+                bail!(NonSuccessfulExit);
+            };
+
+            let value = lenient_parse_type_annotated_value(typ, &wave).map_err(|err| {
+                anyhow!(
+                    "Failed to parse input variable {} as {}: {}",
+                    name.log_color_highlight(),
+                    render_type(typ).log_color_highlight(),
+                    err
+                )
+            })?;
+
+            input_values.insert(name, value);
+        }
+
+        let result = if evaluate {
+            let missing_inputs = input_types
+                .keys()
+                .filter(|name| !input_values.contains_key(*name))
+                .sorted()
+                .collect::<Vec<_>>();
+            if !missing_inputs.is_empty() {
+                log_error(format!(
+                    "Missing input variables for evaluation: {}",
+                    missing_inputs
+                        .into_iter()
+                        .map(|name| name.log_color_error_highlight())
+                        .join(", ")
+                ));
+                bail!(NonSuccessfulExit);
+            }
+
+            let result = rib::interpret_pure(compiled.byte_code, RibInput::new(input_values), None)
+                .await
+                .map_err(|err| anyhow!("Failed to evaluate Rib expression: {err}"))?;
+
+            match result {
+                RibResult::Unit => None,
+                RibResult::Val(value) => Some(
+                    print_value_and_type(&value)
+                        .map_err(|err| anyhow!("Failed to format result as WAVE: {err}"))?,
+                ),
+            }
+        } else {
+            None
+        };
+
+        self.ctx.log_handler().log_view(&RibEvalView {
+            component_name: component.component_name.0.clone(),
+            component_version: component.versioned_component_id.version,
+            inputs: input_types
+                .iter()
+                .map(|(name, typ)| (name.clone(), render_type(typ)))
+                .collect(),
+            output_type: compiled
+                .rib_output_type_info
+                .map(|info| render_type(&info.analysed_type)),
+            evaluated: evaluate,
+            result,
+        });
+
+        Ok(())
+    }
+
+    /// Shows the profile context in the prompt, uses the default prompt otherwise
+    fn repl_prompt(&self) -> Option<String> {
+        let context = self.ctx.profile_context();
+        let context = [
+            context
+                .component
+                .as_ref()
+                .map(|component| format!("component={component}")),
+            context
+                .worker
+                .as_ref()
+                .map(|worker| format!("worker={worker}")),
+        ]
+        .into_iter()
+        .flatten()
+        .join(" ");
+
+        (!context.is_empty()).then(|| format!("[{context}] >>> "))
+    }
+
+    async fn select_component(
+        &self,
+        component_name: Option<ComponentName>,
+        component_version: Option<u64>,
+    ) -> anyhow::Result<Component> {
+        let selected_components = self
+            .ctx
+            .component_handler()
+            .must_select_components_by_app_dir_or_name(component_name.as_ref())
+            .await?;
+
+        let component_name = {
+            if selected_components.component_names.len() == 1 {
+                selected_components.component_names[0].clone()
+            } else {
+                self.ctx
+                    .interactive_handler()
+                    .select_component_for_repl(selected_components.component_names.clone())?
+            }
+        };
+
+        self.ctx
+            .component_handler()
+            .component_by_name_with_auto_deploy(
+                selected_components.project.as_ref(),
+                ComponentNameMatchKind::App,
+                &component_name,
+                component_version.map(|v| v.into()),
+            )
+            .await
+    }
+}
+
+fn component_dependency(component: &Component) -> ComponentDependency {
+    ComponentDependency::new(
+        ComponentDependencyKey {
+            component_name: component.component_name.0.clone(),
+            component_id: component.versioned_component_id.component_id,
+            root_package_name: component.metadata.root_package_name.clone(),
+            root_package_version: component.metadata.root_package_version.clone(),
+        },
+        component.metadata.exports.clone(),
+    )
+}
+
+fn format_rib_error(err: &str) -> String {
+    err.lines()
+        .map(|line| format!("  {line}"))
+        .join("\n")
+        .log_color_warn()
+        .to_string()
 }
 
 #[async_trait]
@@ -179,6 +366,7 @@ impl WorkerFunctionInvoke for RibReplHandler {
                 IdempotencyKey::new(),
                 false,
                 None,
+                None,
             )
             .await?
             .unwrap();
@@ -0,0 +1,223 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command::sdk::SdkSubcommand;
+use crate::command::shared_args::ProjectOptionalFlagArg;
+use crate::command_handler::Handlers;
+use crate::context::Context;
+use crate::error::NonSuccessfulExit;
+use crate::fs;
+use crate::log::{log_action, logln, LogColorize};
+use crate::model::api::{ApiDefinitionId, ApiDefinitionVersion};
+use crate::model::component::ComponentVersionSelection;
+use crate::model::deploy_diff::api_definition::DiffableHttpApiDefinition;
+use crate::model::openapi::to_openapi_spec;
+use crate::model::sdk::{sdk_component_refs, SdkComponentExports, SdkConfig, SdkLanguage};
+use crate::model::template::template_env;
+use crate::model::text::fmt::{log_error, log_warn};
+use crate::model::ComponentName;
+use anyhow::{bail, Context as AnyhowContext};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const RUST_CARGO_TOML_TEMPLATE: &str = include_str!("sdk/rust/Cargo.toml.j2");
+const RUST_LIB_TEMPLATE: &str = include_str!("sdk/rust/lib.rs.j2");
+const TS_PACKAGE_JSON_TEMPLATE: &str = include_str!("sdk/ts/package.json.j2");
+const TS_TSCONFIG_TEMPLATE: &str = include_str!("sdk/ts/tsconfig.json.j2");
+const TS_INDEX_TEMPLATE: &str = include_str!("sdk/ts/index.ts.j2");
+
+pub struct SdkCommandHandler {
+    ctx: Arc<Context>,
+}
+
+impl SdkCommandHandler {
+    pub fn new(ctx: Arc<Context>) -> Self {
+        Self { ctx }
+    }
+
+    pub async fn handle_command(&self, subcommand: SdkSubcommand) -> anyhow::Result<()> {
+        match subcommand {
+            SdkSubcommand::Generate {
+                project,
+                api_definition,
+                version,
+                language,
+                output,
+                template_dir,
+                force,
+            } => {
+                self.cmd_generate(
+                    project,
+                    api_definition,
+                    version,
+                    language,
+                    output,
+                    template_dir,
+                    force,
+                )
+                .await
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn cmd_generate(
+        &self,
+        project: ProjectOptionalFlagArg,
+        api_definition_id: ApiDefinitionId,
+        version: ApiDefinitionVersion,
+        language: SdkLanguage,
+        output: PathBuf,
+        template_dir: Option<PathBuf>,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        let project = self
+            .ctx
+            .cloud_project_handler()
+            .opt_select_project(project.project.as_ref())
+            .await?;
+
+        let Some(api_definition) = self
+            .ctx
+            .api_definition_handler()
+            .api_definition(project.as_ref(), &api_definition_id.0, &version.0)
+            .await?
+        else {
+            log_error(format!(
+                "API definition {}/{} not found",
+                api_definition_id.0.log_color_highlight(),
+                version.0.log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        };
+        let spec = to_openapi_spec(&DiffableHttpApiDefinition::from_server(api_definition)?.0);
+
+        let mut component_exports = SdkComponentExports::new();
+        for (component_name, component_version) in sdk_component_refs(&spec) {
+            match self
+                .ctx
+                .component_handler()
+                .component(
+                    project.as_ref(),
+                    (&ComponentName::from(component_name.as_str())).into(),
+                    component_version.map(ComponentVersionSelection::ByExplicitVersion),
+                )
+                .await?
+            {
+                Some(component) => {
+                    component_exports.insert(
+                        (component_name, component_version),
+                        component.metadata.exports,
+                    );
+                }
+                None => {
+                    log_warn(format!(
+                        "Component {} not found, the responses of its bindings will be untyped",
+                        component_name.log_color_highlight()
+                    ));
+                }
+            }
+        }
+
+        let config = SdkConfig::new(language, &spec, &component_exports, crate::version());
+
+        let mut files = vec![(
+            PathBuf::from("openapi.json"),
+            serde_json::to_string_pretty(&spec).context("Failed to serialize OpenAPI spec")?,
+        )];
+        for (path, builtin_template) in sdk_templates(language) {
+            let template = match &template_dir {
+                Some(template_dir) if template_dir.join(path).is_file() => {
+                    fs::read_to_string(template_dir.join(path))?
+                }
+                _ => builtin_template.to_string(),
+            };
+            let content = template_env()
+                .render_str(&template, &config)
+                .with_context(|| format!("Failed to render SDK template {path}"))?;
+            files.push((PathBuf::from(path), content));
+        }
+
+        if !force {
+            let existing = files
+                .iter()
+                .map(|(path, _)| output.join(path))
+                .filter(|path| path.exists())
+                .collect::<Vec<_>>();
+            if !existing.is_empty() {
+                log_error(format!(
+                    "The target directory already contains generated files: {}, use --force to overwrite them",
+                    existing
+                        .iter()
+                        .map(|path| path.display().to_string().log_color_highlight())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        }
+
+        log_action(
+            "Generating",
+            format!(
+                "{} client for API definition {}/{} to {}",
+                sdk_language_name(language),
+                api_definition_id.0.log_color_highlight(),
+                version.0.log_color_highlight(),
+                output.display().to_string().log_color_highlight()
+            ),
+        );
+        for (path, content) in &files {
+            fs::write_str(output.join(path), content)?;
+        }
+
+        let untyped = config
+            .operations
+            .iter()
+            .filter(|operation| !operation.typed_response)
+            .map(|operation| format!("{} {}", operation.method, operation.path))
+            .collect::<Vec<_>>();
+        if !untyped.is_empty() {
+            logln("");
+            log_warn(format!(
+                "The response types of the following routes could not be resolved from the component exports, these return untyped JSON: {}",
+                untyped.join(", ")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Builtin templates of the generated files, by relative path in the target directory
+fn sdk_templates(language: SdkLanguage) -> Vec<(&'static str, &'static str)> {
+    match language {
+        SdkLanguage::Rust => vec![
+            ("Cargo.toml", RUST_CARGO_TOML_TEMPLATE),
+            ("src/lib.rs", RUST_LIB_TEMPLATE),
+        ],
+        SdkLanguage::Ts => vec![
+            ("package.json", TS_PACKAGE_JSON_TEMPLATE),
+            ("tsconfig.json", TS_TSCONFIG_TEMPLATE),
+            ("src/index.ts", TS_INDEX_TEMPLATE),
+        ],
+    }
+}
+
+fn sdk_language_name(language: SdkLanguage) -> &'static str {
+    match language {
+        SdkLanguage::Rust => "Rust",
+        SdkLanguage::Ts => "TypeScript",
+    }
+}
@@ -14,20 +14,27 @@
 
 mod stream;
 mod stream_output;
+mod wave_editor;
 
 use crate::command::shared_args::{
-    NewWorkerArgument, StreamArgs, WorkerFunctionArgument, WorkerFunctionName, WorkerNameArg,
+    NewWorkerArgument, ProfilesArg, StreamArgs, WorkerFunctionArgument, WorkerFunctionName,
+    WorkerNameArg,
 };
 use crate::command::worker::WorkerSubcommand;
+use crate::command::GolemCliCommandPartialMatch;
 use crate::command_handler::worker::stream::WorkerConnection;
+use crate::command_handler::worker::wave_editor::{WaveArgumentEditor, WaveParam};
 use crate::command_handler::Handlers;
+use crate::command_name;
 use crate::context::Context;
 use crate::error::service::{AnyhowMapServiceError, ServiceError};
 use crate::error::NonSuccessfulExit;
 use crate::fuzzy::{Error, FuzzySearch};
 use crate::log::{log_action, log_error_action, log_warn_action, logln, LogColorize, LogIndent};
 use crate::model::app::ApplicationComponentSelectMode;
-use crate::model::component::{function_params_types, show_exported_functions, Component};
+use crate::model::component::{
+    function_params, function_params_types, show_exported_functions, Component,
+};
 use crate::model::deploy::{TryUpdateAllWorkersResult, WorkerUpdateAttempt};
 use crate::model::invoke_result_view::InvokeResultView;
 use crate::model::text::fmt::{
@@ -38,17 +45,33 @@ use crate::model::text::help::{
     ParameterErrorTableView, WorkerNameHelp,
 };
 use crate::model::text::worker::{WorkerCreateView, WorkerGetView};
+use crate::model::trace::{
+    otlp_headers, otlp_service_name, otlp_traces_endpoint, ClientSpan, TraceParent,
+};
 use crate::model::worker::fuzzy_match_function_name;
+use crate::model::worker_archive::{
+    WorkerArchiveHeader, WorkerArchiveReader, WorkerArchiveRecord, WorkerArchiveWriter,
+    WORKER_ARCHIVE_EXTENSION, WORKER_ARCHIVE_FORMAT_VERSION,
+};
+use crate::model::worker_replay::{
+    format_result, oplog_windows_backwards, recorded_invocations, started_invocation_count,
+    RecordedInvocation, ReplayedInvocationView, WorkerReplayReport,
+};
 use crate::model::{
-    ComponentName, ComponentNameMatchKind, IdempotencyKey, ProjectName, ProjectReference,
-    WorkerMetadata, WorkerMetadataView, WorkerName, WorkerNameMatch, WorkerUpdateMode,
-    WorkersMetadataResponseView,
+    ComponentName, ComponentNameMatchKind, IdempotencyKey, ProfileWorkerMetadataView,
+    ProfileWorkersMetadataResponseView, ProjectName, ProjectRefAndId, ProjectReference,
+    SelectedComponents, WorkerMetadata, WorkerMetadataPageView, WorkerMetadataView, WorkerName,
+    WorkerNameMatch, WorkerUpdateMode, WorkersMetadataCursorsView, WorkersMetadataResponseView,
 };
-use anyhow::{anyhow, bail};
+use crate::server_version::ServerFeature;
+use anyhow::{anyhow, bail, Context as AnyhowContext};
+use chrono::Utc;
 use colored::Colorize;
-use golem_client::api::WorkerClient;
+use futures_util::{pin_mut, stream, Stream, TryStreamExt};
+use golem_client::api::{ComponentClient, WorkerClient};
 use golem_client::model::{
-    ComponentType, InvokeResult, PublicOplogEntry, ScanCursor, UpdateRecord,
+    ComponentEnv as ComponentEnvCloud, ComponentFileSystemNodeKind, ComponentQuery, ComponentType,
+    InvokeResult, PublicOplogEntry, ScanCursor, UpdateRecord,
 };
 use golem_client::model::{
     InvokeParameters as InvokeParametersCloud, RevertLastInvocations as RevertLastInvocationsCloud,
@@ -61,10 +84,15 @@ use golem_wasm_ast::analysis::AnalysedType;
 use golem_wasm_rpc::json::OptionallyValueAndTypeJson;
 use golem_wasm_rpc::{parse_value_and_type, ValueAndType};
 use itertools::{EitherOrBoth, Itertools};
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::task::Poll;
+use std::time::{Duration, SystemTime};
+use tokio::fs::File;
 use tokio::time::timeout;
+use tracing::debug;
 use uuid::Uuid;
 
 pub struct WorkerCommandHandler {
@@ -89,17 +117,25 @@ impl WorkerCommandHandler {
                 arguments,
                 enqueue,
                 idempotency_key,
+                traceparent,
                 stream,
                 stream_args,
+                interactive,
             } => {
+                let (worker_name, function_name, arguments, resolved) = self
+                    .with_context_worker(worker_name, function_name, arguments)
+                    .await?;
                 self.cmd_invoke(
                     worker_name,
+                    resolved,
                     &function_name,
                     arguments,
                     enqueue,
                     idempotency_key,
+                    traceparent,
                     stream,
                     stream_args,
+                    interactive,
                 )
                 .await
             }
@@ -111,15 +147,27 @@ impl WorkerCommandHandler {
                 scan_cursor,
                 max_count,
                 precise,
+                profiles,
             } => {
-                self.cmd_list(
-                    component_name.component_name,
-                    filters,
-                    scan_cursor,
-                    max_count,
-                    precise,
-                )
-                .await
+                if profiles.is_multi_profile() {
+                    self.cmd_list_multi_profile(
+                        component_name.component_name,
+                        filters,
+                        max_count,
+                        precise,
+                        profiles,
+                    )
+                    .await
+                } else {
+                    self.cmd_list(
+                        component_name.component_name,
+                        filters,
+                        scan_cursor,
+                        max_count,
+                        precise,
+                    )
+                    .await
+                }
             }
             WorkerSubcommand::Stream {
                 worker_name,
@@ -164,6 +212,25 @@ impl WorkerCommandHandler {
                 self.cmd_cancel_invocation(worker_name, idempotency_key)
                     .await
             }
+            WorkerSubcommand::Export {
+                worker_name,
+                output,
+                force,
+            } => self.cmd_export(worker_name, output, force).await,
+            WorkerSubcommand::Import {
+                archive,
+                worker,
+                no_replay,
+            } => self.cmd_import(archive, worker, no_replay).await,
+            WorkerSubcommand::Replay {
+                worker_name,
+                against,
+                last,
+                keep_sandbox,
+            } => {
+                self.cmd_replay(worker_name, against, last, keep_sandbox)
+                    .await
+            }
         }
     }
 
@@ -231,12 +298,15 @@ impl WorkerCommandHandler {
     async fn cmd_invoke(
         &self,
         worker_name: WorkerNameArg,
+        resolved: Option<(WorkerNameMatch, Component)>,
         function_name: &WorkerFunctionName,
         arguments: Vec<WorkerFunctionArgument>,
         enqueue: bool,
         idempotency_key: Option<IdempotencyKey>,
+        traceparent: Option<TraceParent>,
         stream: bool,
         stream_args: StreamArgs,
+        interactive: bool,
     ) -> anyhow::Result<()> {
         self.ctx.silence_app_context_init().await;
 
@@ -264,18 +334,23 @@ impl WorkerCommandHandler {
             None => new_idempotency_key(),
         };
 
-        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
-
-        let component = self
-            .ctx
-            .component_handler()
-            .component_by_name_with_auto_deploy(
-                worker_name_match.project.as_ref(),
-                worker_name_match.component_name_match_kind,
-                &worker_name_match.component_name,
-                worker_name_match.worker_name.as_ref().map(|wn| wn.into()),
-            )
-            .await?;
+        let (worker_name_match, component) = match resolved {
+            Some(resolved) => resolved,
+            None => {
+                let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+                let component = self
+                    .ctx
+                    .component_handler()
+                    .component_by_name_with_auto_deploy(
+                        worker_name_match.project.as_ref(),
+                        worker_name_match.component_name_match_kind,
+                        &worker_name_match.component_name,
+                        worker_name_match.worker_name.as_ref().map(|wn| wn.into()),
+                    )
+                    .await?;
+                (worker_name_match, component)
+            }
+        };
 
         let matched_function_name =
             fuzzy_match_function_name(function_name, &component.metadata.exports);
@@ -330,6 +405,13 @@ impl WorkerCommandHandler {
             }
         };
 
+        let arguments = if interactive {
+            self.prompt_missing_arguments(&component, &function_name, arguments)
+                .await?
+        } else {
+            arguments
+        };
+
         if enqueue {
             log_action(
                 "Enqueueing",
@@ -352,6 +434,17 @@ impl WorkerCommandHandler {
 
         let arguments = wave_args_to_invoke_args(&component, &function_name, arguments)?;
 
+        // The context of the CLI side span, which is the parent of the invocation on the server
+        let trace_context = match &traceparent {
+            Some(traceparent) => traceparent.child(),
+            None => TraceParent::new(),
+        };
+        log_action(
+            "Using",
+            format!("trace ID: {}", trace_context.trace_id.log_color_highlight()),
+        );
+
+        let started_at = SystemTime::now();
         let result = self
             .invoke_worker(
                 &component,
@@ -361,32 +454,87 @@ impl WorkerCommandHandler {
                 idempotency_key.clone(),
                 enqueue,
                 stream.then_some(stream_args),
+                Some(&trace_context),
             )
-            .await?;
+            .await;
+
+        self.export_client_span(&ClientSpan {
+            name: format!("invoke {function_name}"),
+            context: trace_context.clone(),
+            parent_span_id: traceparent.map(|traceparent| traceparent.parent_id),
+            start: started_at,
+            end: SystemTime::now(),
+            attributes: vec![
+                (
+                    "golem.component.name".to_string(),
+                    component.component_name.0.clone(),
+                ),
+                (
+                    "golem.worker.name".to_string(),
+                    worker_name_match
+                        .worker_name
+                        .as_ref()
+                        .map(|worker_name| worker_name.0.clone())
+                        .unwrap_or_default(),
+                ),
+                ("golem.function.name".to_string(), function_name.clone()),
+                (
+                    "golem.idempotency_key".to_string(),
+                    idempotency_key.0.clone(),
+                ),
+            ],
+            error: result.as_ref().err().map(|err| format!("{err:#}")),
+        })
+        .await;
 
-        match result {
+        match result? {
             Some(result) => {
                 logln("");
-                self.ctx
-                    .log_handler()
-                    .log_view(&InvokeResultView::new_invoke(
+                self.ctx.log_handler().log_view(
+                    &InvokeResultView::new_invoke(
                         idempotency_key,
                         result,
                         &component,
                         &function_name,
-                    ));
+                    )
+                    .with_trace_id(trace_context.trace_id),
+                );
             }
             None => {
                 log_action("Enqueued", "invocation");
-                self.ctx
-                    .log_handler()
-                    .log_view(&InvokeResultView::new_enqueue(idempotency_key));
+                self.ctx.log_handler().log_view(
+                    &InvokeResultView::new_enqueue(idempotency_key)
+                        .with_trace_id(trace_context.trace_id),
+                );
             }
         }
 
         Ok(())
     }
 
+    async fn prompt_missing_arguments(
+        &self,
+        component: &Component,
+        function_name: &str,
+        arguments: Vec<WorkerFunctionArgument>,
+    ) -> anyhow::Result<Vec<WorkerFunctionArgument>> {
+        let params = function_params(component, function_name)?
+            .into_iter()
+            .map(|param| WaveParam {
+                name: param.name.clone(),
+                typ: param.typ.clone(),
+            })
+            .collect::<Vec<_>>();
+        let editor = WaveArgumentEditor::new(
+            self.ctx.config_dir(),
+            &component.component_name.0,
+            function_name,
+        );
+
+        tokio::task::spawn_blocking(move || editor.prompt_missing_arguments(&params, arguments))
+            .await?
+    }
+
     async fn cmd_stream(
         &self,
         worker_name: WorkerNameArg,
@@ -409,7 +557,7 @@ impl WorkerCommandHandler {
             component.versioned_component_id.component_id,
             worker_name.0.clone(),
             stream_args.into(),
-            self.ctx.allow_insecure(),
+            &self.ctx.client_config().service_http_client_config,
             self.ctx.format(),
             None,
         )
@@ -523,6 +671,10 @@ impl WorkerCommandHandler {
             bail!(NonSuccessfulExit)
         }
 
+        self.ctx
+            .require_server_version(ServerFeature::WorkerRevert)
+            .await?;
+
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
         let (component, worker_name) = self
@@ -576,6 +728,10 @@ impl WorkerCommandHandler {
         worker_name: WorkerNameArg,
         idempotency_key: IdempotencyKey,
     ) -> anyhow::Result<()> {
+        self.ctx
+            .require_server_version(ServerFeature::CancelInvocation)
+            .await?;
+
         self.ctx.silence_app_context_init().await;
         let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
         let (component, worker_name) = self
@@ -614,60 +770,783 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
-    async fn cmd_list(
+    async fn cmd_export(
+        &self,
+        worker_name: WorkerNameArg,
+        output: Option<PathBuf>,
+        force: bool,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        let output = output.unwrap_or_else(|| {
+            PathBuf::from(format!("{}.{}", worker_name.0, WORKER_ARCHIVE_EXTENSION))
+        });
+        if output.exists() && !force {
+            log_error(format!(
+                "Output archive {} already exists, use {} to overwrite it",
+                output.display().to_string().log_color_highlight(),
+                "--force".log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        log_action(
+            "Exporting",
+            format!(
+                "worker {} to {}",
+                format_worker_name_match(&worker_name_match),
+                output.display().to_string().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        let component_id = component.versioned_component_id.component_id;
+        let metadata = self
+            .worker_metadata(component_id, &component.component_name, &worker_name)
+            .await?;
+
+        let mut writer = WorkerArchiveWriter::create(
+            &output,
+            WorkerArchiveHeader {
+                format_version: WORKER_ARCHIVE_FORMAT_VERSION,
+                cli_version: crate::version().to_string(),
+                exported_at: Utc::now(),
+                component_name: component.component_name.0.clone(),
+                component_id,
+                component_version: metadata.component_version,
+                worker_name: worker_name.0.clone(),
+                args: metadata.args,
+                env: metadata.env.into_iter().collect(),
+            },
+        )?;
+
+        let clients = self.ctx.golem_clients().await?;
+
+        let batch_size = self.ctx.http_batch_size();
+        let mut cursor = Option::<OplogCursor>::None;
+        loop {
+            let result = clients
+                .worker
+                .get_oplog(
+                    &component_id,
+                    &worker_name.0,
+                    None,
+                    batch_size,
+                    cursor.as_ref(),
+                    None,
+                )
+                .await
+                .map_service_error()?;
+
+            for entry in result.entries {
+                writer.write_oplog_entry(entry.oplog_index, entry.entry)?;
+            }
+
+            cursor = result.next;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let mut directories = vec!["/".to_string()];
+        while let Some(directory) = directories.pop() {
+            let nodes = clients
+                .worker
+                .get_files(&component_id, &worker_name.0, &directory)
+                .await
+                .map_service_error()?
+                .nodes;
+
+            for node in nodes {
+                let path = format!("{}/{}", directory.trim_end_matches('/'), node.name);
+                match node.kind {
+                    ComponentFileSystemNodeKind::Directory => directories.push(path),
+                    ComponentFileSystemNodeKind::File => {
+                        let content = clients
+                            .worker
+                            .get_file_content(&component_id, &worker_name.0, &path)
+                            .await
+                            .map_service_error()?;
+                        writer.write_file(&path, &content)?;
+                    }
+                }
+            }
+        }
+
+        let footer = writer.finish()?;
+
+        log_action(
+            "Exported",
+            format!(
+                "{} oplog entries and {} files",
+                footer.oplog_entry_count.to_string().log_color_highlight(),
+                footer.file_count.to_string().log_color_highlight()
+            ),
+        );
+
+        Ok(())
+    }
+
+    async fn cmd_import(
+        &self,
+        archive: PathBuf,
+        worker: Option<WorkerName>,
+        no_replay: bool,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+
+        // Checking the whole archive first, so truncated archives do not result in partially
+        // imported workers
+        let validation = WorkerArchiveReader::open(&archive).and_then(|reader| reader.validate());
+        let footer = match validation {
+            Ok(footer) => footer,
+            Err(err) => {
+                log_error(format!(
+                    "Failed to read worker archive {}: {:#}",
+                    archive.display().to_string().log_color_highlight(),
+                    err
+                ));
+                bail!(NonSuccessfulExit);
+            }
+        };
+
+        let mut reader = WorkerArchiveReader::open(&archive)?;
+        let header = reader.header().clone();
+
+        let worker_name = worker
+            .unwrap_or_else(|| format!("{}/{}", header.component_name, header.worker_name).into());
+        let worker_name_match = self.match_worker_name(worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        if component.versioned_component_id.version != header.component_version {
+            log_warn(format!(
+                "The archived worker used component version {}, the worker will be created using version {}",
+                header.component_version.to_string().log_color_highlight(),
+                component
+                    .versioned_component_id
+                    .version
+                    .to_string()
+                    .log_color_highlight()
+            ));
+        }
+
+        log_action(
+            "Importing",
+            format!(
+                "worker {} from {}, exported at {}",
+                format_worker_name_match(&worker_name_match),
+                archive.display().to_string().log_color_highlight(),
+                header.exported_at.to_string().log_color_highlight()
+            ),
+        );
+        let _indent = LogIndent::new();
+
+        self.new_worker(
+            component.versioned_component_id.component_id,
+            worker_name.0.clone(),
+            header.args,
+            header.env.into_iter().collect(),
+        )
+        .await?;
+        log_action(
+            "Created",
+            format!("worker {}", format_worker_name_match(&worker_name_match)),
+        );
+
+        if no_replay {
+            log_warn_action("Skipping", "replaying invocations");
+            return Ok(());
+        }
+
+        let mut replayed_invocations = 0;
+        while let Some(record) = reader.next_record()? {
+            let WorkerArchiveRecord::OplogEntry {
+                entry: PublicOplogEntry::ExportedFunctionInvoked(params),
+                ..
+            } = record
+            else {
+                continue;
+            };
+
+            let arguments = params
+                .request
+                .into_iter()
+                .map(|value| value.try_into())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| anyhow!("Failed to convert archived argument: {err}"))?;
+
+            log_action(
+                "Replaying",
+                format!(
+                    "invocation {} using idempotency key: {}",
+                    format_export(&params.function_name),
+                    params.idempotency_key.to_string().log_color_highlight()
+                ),
+            );
+
+            if let Err(err) = self
+                .invoke_worker(
+                    &component,
+                    Some(&worker_name),
+                    &params.function_name,
+                    arguments,
+                    IdempotencyKey(params.idempotency_key.to_string()),
+                    false,
+                    None,
+                    None,
+                )
+                .await
+            {
+                log_error(format!(
+                    "Failed to replay invocation {}, stopping the import after {} replayed invocations: {:#}",
+                    format_export(&params.function_name),
+                    replayed_invocations,
+                    err
+                ));
+                bail!(NonSuccessfulExit);
+            }
+            replayed_invocations += 1;
+        }
+
+        log_action(
+            "Imported",
+            format!(
+                "worker by replaying {} invocations",
+                replayed_invocations.to_string().log_color_highlight()
+            ),
+        );
+        if footer.file_count > 0 {
+            log_warn(format!(
+                "The archive contains {} files, these are not uploaded, as the replayed invocations recreate the worker's files",
+                footer.file_count.to_string().log_color_highlight()
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn cmd_replay(
+        &self,
+        worker_name: WorkerNameArg,
+        against: PathBuf,
+        last: usize,
+        keep_sandbox: bool,
+    ) -> anyhow::Result<()> {
+        self.ctx.silence_app_context_init().await;
+        let worker_name_match = self.match_worker_name(worker_name.worker_name).await?;
+        let (component, worker_name) = self
+            .component_by_worker_name_match(&worker_name_match)
+            .await?;
+
+        if !against.is_file() {
+            log_error(format!(
+                "Component WASM {} not found",
+                against.display().to_string().log_color_error_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let component_id = component.versioned_component_id.component_id;
+        let metadata = self
+            .worker_metadata(component_id, &component.component_name, &worker_name)
+            .await?;
+
+        let invocations = self
+            .fetch_recorded_invocations(component_id, &worker_name, last)
+            .await?;
+
+        let sandbox_component_name = ComponentName(format!("{}-replay", component.component_name));
+        let sandbox_worker_name = WorkerName(format!(
+            "{}-replay-{}",
+            worker_name.0,
+            &Uuid::new_v4().to_string()[..8]
+        ));
+
+        let mut report = WorkerReplayReport {
+            component_name: component.component_name.0.clone(),
+            worker_name: worker_name.0.clone(),
+            sandbox_component_name: sandbox_component_name.0.clone(),
+            sandbox_worker_name: sandbox_worker_name.0.clone(),
+            invocations: vec![],
+        };
+
+        if !invocations.is_empty() {
+            let sandbox_component = self
+                .upload_sandbox_component(
+                    worker_name_match.project.as_ref(),
+                    &component,
+                    &sandbox_component_name,
+                    &against,
+                )
+                .await?;
+            let sandbox_component_id = sandbox_component.versioned_component_id.component_id;
+
+            log_action(
+                "Replaying",
+                format!(
+                    "the last {} invocations of worker {} in sandbox worker {}/{}",
+                    invocations.len().to_string().log_color_highlight(),
+                    format_worker_name_match(&worker_name_match),
+                    sandbox_component_name.0.log_color_highlight(),
+                    sandbox_worker_name.0.log_color_highlight()
+                ),
+            );
+            let _indent = LogIndent::new();
+
+            let replayed = self
+                .replay_in_sandbox(
+                    &sandbox_component,
+                    &sandbox_worker_name,
+                    metadata.args,
+                    metadata.env,
+                    &invocations,
+                )
+                .await;
+
+            // The sandbox component is kept, as components cannot be deleted through the API,
+            // it is updated with the new WASM by the next replay instead
+            let cleanup = if keep_sandbox {
+                Ok(())
+            } else {
+                self.delete(sandbox_component_id, &sandbox_worker_name.0)
+                    .await
+            };
+
+            report.invocations = replayed?;
+            cleanup?;
+        }
+
+        logln("");
+        self.ctx.log_handler().log_view(&report);
+
+        if report.has_regressions() {
+            logln("");
+            log_error("The replayed invocations differ from the recorded ones");
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    /// Fetches the last `last` recorded invocations, paging the oplog backwards from its end,
+    /// and stopping once enough invocations are started in the fetched entries
+    async fn fetch_recorded_invocations(
+        &self,
+        component_id: Uuid,
+        worker_name: &WorkerName,
+        last: usize,
+    ) -> anyhow::Result<Vec<RecordedInvocation>> {
+        let Some(last_oplog_index) = self.last_oplog_index(component_id, worker_name).await? else {
+            return Ok(vec![]);
+        };
+
+        let clients = self.ctx.golem_clients().await?;
+        let mut entries = Vec::<(u64, PublicOplogEntry)>::new();
+        for (from, count) in oplog_windows_backwards(last_oplog_index, self.ctx.http_batch_size()) {
+            let to = from + count - 1;
+            let mut window = Vec::<(u64, PublicOplogEntry)>::new();
+            let mut cursor = Option::<OplogCursor>::None;
+            loop {
+                let result = clients
+                    .worker
+                    .get_oplog(
+                        &component_id,
+                        &worker_name.0,
+                        Some(from),
+                        count,
+                        cursor.as_ref(),
+                        None,
+                    )
+                    .await
+                    .map_service_error()?;
+
+                window.extend(
+                    result
+                        .entries
+                        .into_iter()
+                        .map(|entry| (entry.oplog_index, entry.entry))
+                        .filter(|(oplog_index, _)| *oplog_index <= to),
+                );
+
+                cursor = result.next;
+                let window_complete = window
+                    .last()
+                    .is_some_and(|(oplog_index, _)| *oplog_index >= to);
+                if cursor.is_none() || window_complete {
+                    break;
+                }
+            }
+
+            window.append(&mut entries);
+            entries = window;
+
+            if started_invocation_count(&entries) >= last {
+                break;
+            }
+        }
+
+        Ok(recorded_invocations(entries, last))
+    }
+
+    /// Finds the last oplog index by probing the oplog exponentially, then bisecting,
+    /// as the worker metadata does not contain it
+    async fn last_oplog_index(
+        &self,
+        component_id: Uuid,
+        worker_name: &WorkerName,
+    ) -> anyhow::Result<Option<u64>> {
+        let clients = self.ctx.golem_clients().await?;
+        let has_entry = async |oplog_index: u64| -> anyhow::Result<bool> {
+            clients
+                .worker
+                .get_oplog(
+                    &component_id,
+                    &worker_name.0,
+                    Some(oplog_index),
+                    1,
+                    None,
+                    None,
+                )
+                .await
+                .map(|result| !result.entries.is_empty())
+                .map_service_error()
+        };
+
+        if !has_entry(1).await? {
+            return Ok(None);
+        }
+
+        let (mut present, mut missing) = (1u64, 2u64);
+        while has_entry(missing).await? {
+            present = missing;
+            missing *= 2;
+        }
+        while missing - present > 1 {
+            let middle = present + (missing - present) / 2;
+            if has_entry(middle).await? {
+                present = middle;
+            } else {
+                missing = middle;
+            }
+        }
+
+        Ok(Some(present))
+    }
+
+    async fn replay_in_sandbox(
+        &self,
+        sandbox_component: &Component,
+        sandbox_worker_name: &WorkerName,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+        invocations: &[RecordedInvocation],
+    ) -> anyhow::Result<Vec<ReplayedInvocationView>> {
+        self.new_worker(
+            sandbox_component.versioned_component_id.component_id,
+            sandbox_worker_name.0.clone(),
+            args,
+            env,
+        )
+        .await?;
+
+        let mut replayed_invocations = Vec::with_capacity(invocations.len());
+        for invocation in invocations {
+            log_action(
+                "Invoking",
+                format!(
+                    "{} recorded at oplog index {}",
+                    format_export(&invocation.function_name),
+                    invocation.oplog_index.to_string().log_color_highlight()
+                ),
+            );
+
+            let arguments = invocation
+                .request
+                .iter()
+                .cloned()
+                .map(|value| value.try_into())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| anyhow!("Failed to convert recorded argument: {err}"))?;
+
+            let replayed = self
+                .invoke_worker(
+                    sandbox_component,
+                    Some(sandbox_worker_name),
+                    &invocation.function_name,
+                    arguments,
+                    IdempotencyKey::new(),
+                    false,
+                    None,
+                    None,
+                )
+                .await
+                .map(|result| format_result(result.and_then(|result| result.result).as_ref()))
+                .map_err(|err| format!("{err:#}"));
+
+            replayed_invocations.push(ReplayedInvocationView::new(invocation, replayed));
+        }
+
+        Ok(replayed_invocations)
+    }
+
+    async fn upload_sandbox_component(
+        &self,
+        project: Option<&ProjectRefAndId>,
+        component: &Component,
+        sandbox_component_name: &ComponentName,
+        wasm: &Path,
+    ) -> anyhow::Result<Component> {
+        let existing_sandbox_component = self
+            .ctx
+            .component_handler()
+            .component(project, sandbox_component_name.into(), None)
+            .await?;
+
+        log_action(
+            "Uploading",
+            format!(
+                "{} as sandbox component {}",
+                wasm.display().to_string().log_color_highlight(),
+                sandbox_component_name.0.log_color_highlight()
+            ),
+        );
+
+        let wasm_file = File::open(wasm)
+            .await
+            .with_context(|| anyhow!("Failed to open component WASM: {}", wasm.display()))?;
+        let env = ComponentEnvCloud {
+            key_values: component.env.clone().into_iter().collect(),
+        };
+
+        let clients = self.ctx.golem_clients().await?;
+
+        let sandbox_component = match existing_sandbox_component {
+            Some(sandbox_component) => clients
+                .component
+                .update_component(
+                    &sandbox_component.versioned_component_id.component_id,
+                    Some(&component.component_type),
+                    wasm_file,
+                    None,
+                    None::<File>,
+                    None,
+                    Some(&env),
+                    None,
+                )
+                .await
+                .map_service_error()?,
+            None => clients
+                .component
+                .create_component(
+                    &ComponentQuery {
+                        project_id: project.map(|p| p.project_id.0),
+                        component_name: sandbox_component_name.0.clone(),
+                    },
+                    wasm_file,
+                    Some(&component.component_type),
+                    None,
+                    None::<File>,
+                    None,
+                    Some(&env),
+                    None,
+                )
+                .await
+                .map_service_error()?,
+        };
+
+        Ok(Component::from(sandbox_component))
+    }
+
+    async fn cmd_list(
+        &self,
+        component_name: Option<ComponentName>,
+        filters: Vec<String>,
+        scan_cursor: Option<ScanCursor>,
+        max_count: Option<u64>,
+        precise: bool,
+    ) -> anyhow::Result<()> {
+        let selected_components = self
+            .select_components_for_list(component_name.as_ref(), scan_cursor.as_ref())
+            .await?;
+        let components = self.list_components(&selected_components).await;
+
+        let mut component_ids = Vec::with_capacity(components.len());
+        for (component_name, component) in
+            selected_components.component_names.iter().zip(components)
+        {
+            match component? {
+                Some(component) => component_ids.push((
+                    component_name,
+                    component.versioned_component_id.component_id,
+                )),
+                None => {
+                    log_warn(format!(
+                        "Component not found: {}",
+                        component_name.0.log_color_error_highlight()
+                    ));
+                }
+            }
+        }
+
+        // Workers are logged page by page as they arrive, so listing all workers of large
+        // accounts only holds one page per component in memory. The pages of the components
+        // are fetched concurrently.
+        let mut paged_view = self
+            .ctx
+            .log_handler()
+            .paged_list_view::<WorkerMetadataView, WorkerMetadataPageView>("workers");
+        let mut view = WorkersMetadataCursorsView::default();
+
+        let pages = self
+            .ctx
+            .request_executor()
+            .merge_streams(component_ids.iter().map(|(component_name, component_id)| {
+                Box::pin(
+                    self.component_worker_pages(
+                        component_name,
+                        *component_id,
+                        Some(&filters),
+                        scan_cursor.as_ref(),
+                        max_count,
+                        precise,
+                    )
+                    .map_ok(move |page| (*component_name, page)),
+                )
+            }));
+        pin_mut!(pages);
+
+        while let Some((component_name, page)) = pages.try_next().await? {
+            paged_view.log_page(
+                page.workers
+                    .into_iter()
+                    .map(WorkerMetadataView::from)
+                    .collect(),
+            );
+            page.next_cursor.into_iter().for_each(|scan_cursor| {
+                view.cursors.insert(
+                    component_name.to_string(),
+                    scan_cursor_to_string(&scan_cursor),
+                );
+            });
+        }
+
+        paged_view.finish(&view);
+
+        Ok(())
+    }
+
+    async fn cmd_list_multi_profile(
         &self,
         component_name: Option<ComponentName>,
         filters: Vec<String>,
-        scan_cursor: Option<ScanCursor>,
         max_count: Option<u64>,
         precise: bool,
+        profiles: ProfilesArg,
     ) -> anyhow::Result<()> {
-        let selected_components = self
+        let contexts = self.ctx.profile_contexts(&profiles).await?;
+
+        let results = self
             .ctx
-            .component_handler()
-            .must_select_components_by_app_dir_or_name(component_name.as_ref())
-            .await?;
+            .request_executor()
+            .run(&contexts, |ctx| {
+                let component_name = component_name.as_ref();
+                let filters = filters.as_slice();
+                async move {
+                    ctx.worker_handler()
+                        .list_workers(component_name, filters, None, max_count, precise)
+                        .await
+                }
+            })
+            .await;
 
-        if scan_cursor.is_some() && selected_components.component_names.len() != 1 {
-            log_error(format!(
-                "Cursor cannot be used with multiple components selected! ({})",
-                selected_components
-                    .component_names
-                    .iter()
-                    .map(|cn| cn.0.log_color_highlight())
-                    .join(", ")
-            ));
-            logln("");
-            logln("Switch to an application directory with only one component or explicitly specify the requested component name.");
-            logln("");
+        let mut view = ProfileWorkersMetadataResponseView::default();
+        let mut any_failed = false;
+        for (ctx, result) in contexts.iter().zip(results) {
+            let profile = ctx.profile_name();
+            match result {
+                Ok(workers) => {
+                    view.workers
+                        .extend(workers.workers.into_iter().map(|worker| {
+                            ProfileWorkerMetadataView {
+                                profile: profile.clone(),
+                                worker,
+                            }
+                        }));
+                    if !workers.cursors.is_empty() {
+                        view.truncated_profiles.insert(profile.clone());
+                    }
+                }
+                Err(error) => {
+                    any_failed = true;
+                    log_error(format!(
+                        "Failed to list workers for profile {}: {error:#}",
+                        profile.0.log_color_highlight()
+                    ));
+                }
+            }
+        }
+
+        self.ctx.log_handler().log_view(&view);
+
+        if any_failed {
             bail!(NonSuccessfulExit);
         }
 
-        let mut view = WorkersMetadataResponseView::default();
+        Ok(())
+    }
 
-        for component_name in &selected_components.component_names {
-            match self
-                .ctx
-                .component_handler()
-                .component(
-                    selected_components.project.as_ref(),
-                    component_name.into(),
-                    None,
-                )
-                .await?
-            {
-                Some(component) => {
-                    let (workers, scan_cursor) = self
-                        .list_component_workers(
-                            component_name,
-                            component.versioned_component_id.component_id,
-                            Some(filters.as_slice()),
-                            scan_cursor.as_ref(),
-                            max_count,
-                            precise,
-                        )
+    async fn list_workers(
+        &self,
+        component_name: Option<&ComponentName>,
+        filters: &[String],
+        scan_cursor: Option<&ScanCursor>,
+        max_count: Option<u64>,
+        precise: bool,
+    ) -> anyhow::Result<WorkersMetadataResponseView> {
+        let selected_components = self
+            .select_components_for_list(component_name, scan_cursor)
+            .await?;
+
+        let results = self
+            .ctx
+            .request_executor()
+            .run(&selected_components.component_names, |component_name| {
+                let project = selected_components.project.as_ref();
+                async move {
+                    let component = self
+                        .ctx
+                        .component_handler()
+                        .component(project, component_name.into(), None)
                         .await?;
+                    match component {
+                        Some(component) => self
+                            .list_component_workers(
+                                component_name,
+                                component.versioned_component_id.component_id,
+                                Some(filters),
+                                scan_cursor,
+                                max_count,
+                                precise,
+                            )
+                            .await
+                            .map(Some),
+                        None => Ok(None),
+                    }
+                }
+            })
+            .await;
+
+        let mut view = WorkersMetadataResponseView::default();
 
+        for (component_name, result) in selected_components.component_names.iter().zip(results) {
+            match result? {
+                Some((workers, scan_cursor)) => {
                     view.workers
                         .extend(workers.into_iter().map(WorkerMetadataView::from));
                     scan_cursor.into_iter().for_each(|scan_cursor| {
@@ -686,9 +1565,55 @@ impl WorkerCommandHandler {
             }
         }
 
-        self.ctx.log_handler().log_view(&view);
+        Ok(view)
+    }
 
-        Ok(())
+    async fn select_components_for_list(
+        &self,
+        component_name: Option<&ComponentName>,
+        scan_cursor: Option<&ScanCursor>,
+    ) -> anyhow::Result<SelectedComponents> {
+        let selected_components = self
+            .ctx
+            .component_handler()
+            .must_select_components_by_app_dir_or_name(component_name)
+            .await?;
+
+        if scan_cursor.is_some() && selected_components.component_names.len() != 1 {
+            log_error(format!(
+                "Cursor cannot be used with multiple components selected! ({})",
+                selected_components
+                    .component_names
+                    .iter()
+                    .map(|cn| cn.0.log_color_highlight())
+                    .join(", ")
+            ));
+            logln("");
+            logln("Switch to an application directory with only one component or explicitly specify the requested component name.");
+            logln("");
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(selected_components)
+    }
+
+    /// Looks up the selected components concurrently
+    async fn list_components(
+        &self,
+        selected_components: &SelectedComponents,
+    ) -> Vec<anyhow::Result<Option<Component>>> {
+        self.ctx
+            .request_executor()
+            .run(&selected_components.component_names, |component_name| {
+                let project = selected_components.project.as_ref();
+                async move {
+                    self.ctx
+                        .component_handler()
+                        .component(project, component_name.into(), None)
+                        .await
+                }
+            })
+            .await
     }
 
     async fn cmd_interrupt(&self, worker_name: WorkerNameArg) -> anyhow::Result<()> {
@@ -877,6 +1802,7 @@ impl WorkerCommandHandler {
         idempotency_key: IdempotencyKey,
         enqueue: bool,
         stream_args: Option<StreamArgs>,
+        trace_context: Option<&TraceParent>,
     ) -> anyhow::Result<Option<InvokeResult>> {
         let mut connect_handle = match &worker_name {
             Some(worker_name) => match stream_args {
@@ -887,7 +1813,7 @@ impl WorkerCommandHandler {
                         component.versioned_component_id.component_id,
                         worker_name.0.clone(),
                         stream_args.into(),
-                        self.ctx.allow_insecure(),
+                        &self.ctx.client_config().service_http_client_config,
                         self.ctx.format(),
                         if enqueue {
                             None
@@ -909,11 +1835,27 @@ impl WorkerCommandHandler {
 
         let clients = self.ctx.golem_clients().await?;
 
+        let traced_worker_client = match trace_context {
+            Some(trace_context) => {
+                let mut headers = HeaderMap::new();
+                headers.insert(
+                    "traceparent",
+                    HeaderValue::from_str(&trace_context.to_string())?,
+                );
+                Some(clients.worker_with_headers(!enqueue, headers)?)
+            }
+            None => None,
+        };
+        let worker_client = match &traced_worker_client {
+            Some(worker_client) => worker_client,
+            None if enqueue => &clients.worker,
+            None => &clients.worker_invoke,
+        };
+
         let result = match &worker_name {
             Some(worker_name) => {
                 if enqueue {
-                    clients
-                        .worker
+                    worker_client
                         .invoke_function(
                             &component.versioned_component_id.component_id,
                             &worker_name.0,
@@ -926,8 +1868,7 @@ impl WorkerCommandHandler {
                     None
                 } else {
                     Some(
-                        clients
-                            .worker_invoke
+                        worker_client
                             .invoke_and_await_function(
                                 &component.versioned_component_id.component_id,
                                 &worker_name.0,
@@ -942,8 +1883,7 @@ impl WorkerCommandHandler {
             }
             None => {
                 if enqueue {
-                    clients
-                        .worker
+                    worker_client
                         .invoke_function_without_name(
                             &component.versioned_component_id.component_id,
                             Some(&idempotency_key.0),
@@ -955,8 +1895,7 @@ impl WorkerCommandHandler {
                     None
                 } else {
                     Some(
-                        clients
-                            .worker_invoke
+                        worker_client
                             .invoke_and_await_function_without_name(
                                 &component.versioned_component_id.component_id,
                                 Some(&idempotency_key.0),
@@ -987,6 +1926,36 @@ impl WorkerCommandHandler {
         Ok(result)
     }
 
+    /// Exports the span using OTLP/HTTP when an OpenTelemetry exporter endpoint is configured,
+    /// export failures are only reported as warnings
+    async fn export_client_span(&self, span: &ClientSpan) {
+        let Some(endpoint) = otlp_traces_endpoint() else {
+            return;
+        };
+
+        let mut request = self
+            .ctx
+            .file_download_client()
+            .post(&endpoint)
+            .header(CONTENT_TYPE, "application/json")
+            .body(span.to_otlp_json(&otlp_service_name()).to_string());
+        for (name, value) in otlp_headers() {
+            request = request.header(name, value);
+        }
+
+        match request
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+        {
+            Ok(_) => debug!(endpoint = %endpoint, "Exported client span"),
+            Err(err) => log_warn(format!(
+                "Failed to export trace span to {}: {err}",
+                endpoint.log_color_highlight()
+            )),
+        }
+    }
+
     pub async fn worker_metadata(
         &self,
         component_id: Uuid,
@@ -1004,7 +1973,7 @@ impl WorkerCommandHandler {
         Ok(WorkerMetadata::from_cloud(component_name.clone(), result))
     }
 
-    async fn delete(&self, component_id: Uuid, worker_name: &str) -> anyhow::Result<()> {
+    pub async fn delete(&self, component_id: Uuid, worker_name: &str) -> anyhow::Result<()> {
         let clients = self.ctx.golem_clients().await?;
 
         clients
@@ -1046,10 +2015,11 @@ impl WorkerCommandHandler {
         );
         let _indent = LogIndent::new();
 
-        let mut update_results = TryUpdateAllWorkersResult::default();
-        for worker in &workers {
-            let result = self
-                .update_worker(
+        let results = self
+            .ctx
+            .request_executor()
+            .run(&workers, |worker| {
+                self.update_worker(
                     component_name,
                     worker.worker_id.component_id.0,
                     &worker.worker_id.worker_name,
@@ -1057,8 +2027,19 @@ impl WorkerCommandHandler {
                     target_version,
                     false,
                 )
-                .await;
+            })
+            .await;
+
+        if results.len() < workers.len() {
+            self.ctx.cancellation().log_cancelled(
+                &format!("updating workers for component {component_name}"),
+                results.len(),
+                workers.len(),
+            );
+        }
 
+        let mut update_results = TryUpdateAllWorkersResult::default();
+        for (worker, result) in workers.iter().zip(results) {
             match result {
                 Ok(_) => {
                     update_results.triggered.push(WorkerUpdateAttempt {
@@ -1080,15 +2061,16 @@ impl WorkerCommandHandler {
         }
 
         if await_update {
-            for worker in workers {
-                let _ = self
-                    .await_update_result(
+            self.ctx
+                .request_executor()
+                .run(&workers, |worker| {
+                    self.await_update_result(
                         &worker.worker_id.component_id.0,
                         &worker.worker_id.worker_name,
                         target_version,
                     )
-                    .await;
-            }
+                })
+                .await;
         }
 
         Ok(update_results)
@@ -1162,86 +2144,91 @@ impl WorkerCommandHandler {
         target_version: u64,
     ) -> anyhow::Result<()> {
         let clients = self.ctx.golem_clients().await?;
-        loop {
-            let metadata = clients
-                .worker
-                .get_worker_metadata(component_id, worker_name)
-                .await?;
-            for update_record in metadata.updates {
-                let mut latest_success = None;
-                let mut latest_failure = None;
-                let mut pending_count = 0;
-                match update_record {
-                    UpdateRecord::PendingUpdate(details)
-                        if details.target_version == target_version =>
-                    {
-                        pending_count += 1;
-                    }
-                    UpdateRecord::SuccessfulUpdate(details)
-                        if details.target_version == target_version =>
-                    {
-                        match latest_success {
-                            None => latest_success = Some(details),
-                            Some(previous_success)
-                                if previous_success.timestamp < details.timestamp =>
+        self.ctx
+            .poller()
+            .poll(
+                &format!(
+                    "update of worker {} to version {}",
+                    worker_name.log_color_highlight(),
+                    target_version.to_string().log_color_highlight()
+                ),
+                || async {
+                    let metadata = clients
+                        .worker
+                        .get_worker_metadata(component_id, worker_name)
+                        .await?;
+
+                    let mut latest_success = None;
+                    let mut latest_failure = None;
+                    let mut pending_count = 0;
+                    for update_record in metadata.updates {
+                        match update_record {
+                            UpdateRecord::PendingUpdate(details)
+                                if details.target_version == target_version =>
                             {
-                                latest_success = Some(details);
+                                pending_count += 1;
                             }
-                            _ => {}
-                        }
-                    }
-                    UpdateRecord::FailedUpdate(details)
-                        if details.target_version == target_version =>
-                    {
-                        match latest_failure {
-                            None => latest_failure = Some(details),
-                            Some(previous_failure)
-                                if previous_failure.timestamp < details.timestamp =>
+                            UpdateRecord::SuccessfulUpdate(details)
+                                if details.target_version == target_version =>
+                            {
+                                match &latest_success {
+                                    None => latest_success = Some(details),
+                                    Some(previous_success)
+                                        if previous_success.timestamp < details.timestamp =>
+                                    {
+                                        latest_success = Some(details);
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            UpdateRecord::FailedUpdate(details)
+                                if details.target_version == target_version =>
                             {
-                                latest_failure = Some(details);
+                                match &latest_failure {
+                                    None => latest_failure = Some(details),
+                                    Some(previous_failure)
+                                        if previous_failure.timestamp < details.timestamp =>
+                                    {
+                                        latest_failure = Some(details);
+                                    }
+                                    _ => {}
+                                }
                             }
                             _ => {}
                         }
                     }
-                    _ => {}
-                }
 
-                if pending_count > 0 {
-                    log_action("Worker update", "is still pending");
-                    tokio::time::sleep(Duration::from_secs(2)).await;
-                } else if let Some(success) = latest_success {
-                    log_action(
-                        "Worker update",
-                        format!(
-                            "to version {} succeeded at {}",
-                            success.target_version.to_string().log_color_highlight(),
-                            success.timestamp.to_string().log_color_highlight()
-                        ),
-                    );
-                    return Ok(());
-                } else if let Some(failure) = latest_failure {
-                    let error = failure.details.unwrap_or("unknown reason".to_string());
-                    log_error_action(
-                        "Worker update",
-                        format!(
-                            "to version {} succeeded at {}: {}",
-                            failure.target_version.to_string().log_color_highlight(),
-                            failure.timestamp.to_string().log_color_highlight(),
-                            error
-                        ),
-                    );
-                    return Err(anyhow!(error));
-                } else {
-                    log_error_action(
-                        "Worker update",
-                        "is not pending anymore, but no outcome has been found",
-                    );
-                    return Err(anyhow ! (
-                "Unexpected worker state: update is not pending anymore, but no outcome has been found"
-                ));
-                }
-            }
-        }
+                    if pending_count > 0 {
+                        Ok(Poll::Pending)
+                    } else if let Some(success) = latest_success {
+                        log_action(
+                            "Worker update",
+                            format!(
+                                "to version {} succeeded at {}",
+                                success.target_version.to_string().log_color_highlight(),
+                                success.timestamp.to_string().log_color_highlight()
+                            ),
+                        );
+                        Ok(Poll::Ready(()))
+                    } else if let Some(failure) = latest_failure {
+                        let error = failure.details.unwrap_or("unknown reason".to_string());
+                        log_error_action(
+                            "Worker update",
+                            format!(
+                                "to version {} failed at {}: {}",
+                                failure.target_version.to_string().log_color_highlight(),
+                                failure.timestamp.to_string().log_color_highlight(),
+                                error
+                            ),
+                        );
+                        Err(anyhow!(error))
+                    } else {
+                        // The update is not registered yet
+                        Ok(Poll::Pending)
+                    }
+                },
+            )
+            .await
     }
 
     pub async fn redeploy_component_workers(
@@ -1279,11 +2266,23 @@ impl WorkerCommandHandler {
             bail!(NonSuccessfulExit);
         }
 
-        for worker in workers {
+        let cancellation = self.ctx.cancellation();
+        let _scope = cancellation.scope();
+
+        let worker_count = workers.len();
+        for (idx, worker) in workers.into_iter().enumerate() {
+            if cancellation.is_cancelled() {
+                cancellation.log_cancelled(
+                    &format!("redeploying workers for component {component_name}"),
+                    idx,
+                    worker_count,
+                );
+                break;
+            }
             self.redeploy_worker(component_name, worker).await?;
         }
 
-        Ok(())
+        cancellation.check()
     }
 
     async fn redeploy_worker(
@@ -1345,52 +2344,83 @@ impl WorkerCommandHandler {
         max_count: Option<u64>,
         precise: bool,
     ) -> anyhow::Result<(Vec<WorkerMetadata>, Option<ScanCursor>)> {
-        let clients = self.ctx.golem_clients().await?;
-        let mut workers = Vec::<WorkerMetadata>::new();
-        let mut final_result_cursor = Option::<ScanCursor>::None;
+        let pages = self.component_worker_pages(
+            component_name,
+            component_id,
+            filters,
+            start_scan_cursor,
+            max_count,
+            precise,
+        );
+        pin_mut!(pages);
 
-        let start_scan_cursor = start_scan_cursor.map(scan_cursor_to_string);
-        let mut current_scan_cursor = start_scan_cursor.clone();
-        loop {
-            let result_cursor = {
-                let results = clients
-                    .worker
-                    .get_workers_metadata(
-                        &component_id,
-                        filters,
-                        current_scan_cursor.as_deref(),
-                        max_count.or(Some(self.ctx.http_batch_size())),
-                        Some(precise),
-                    )
-                    .await
-                    .map_service_error()?;
+        let mut workers = Vec::<WorkerMetadata>::new();
+        let mut scan_cursor = None;
+        while let Some(page) = pages.try_next().await? {
+            workers.extend(page.workers);
+            scan_cursor = page.next_cursor;
+        }
 
-                workers.extend(
-                    results
-                        .workers
-                        .into_iter()
-                        .map(|meta| WorkerMetadata::from_cloud(component_name.clone(), meta)),
-                );
+        Ok((workers, scan_cursor))
+    }
 
-                results.cursor
+    /// Lists the workers of the component page by page. With max_count only one page is
+    /// fetched, and it has the cursor for more results.
+    ///
+    /// The pages of a single component are fetched one after the other, only the listings of
+    /// different components run concurrently. A cursor (layer/position) can only be obtained from
+    /// the previous page, and the API has no end bound for a scan, so neither cursor prediction
+    /// nor parallel ranges (e.g. one scan per layer) are possible without overlapping results.
+    pub fn component_worker_pages<'a>(
+        &'a self,
+        component_name: &'a ComponentName,
+        component_id: Uuid,
+        filters: Option<&'a [String]>,
+        start_scan_cursor: Option<&ScanCursor>,
+        max_count: Option<u64>,
+        precise: bool,
+    ) -> impl Stream<Item = anyhow::Result<WorkerPage>> + 'a {
+        // The state is the cursor of the next page, or None after the last page
+        let start_scan_cursor = Some(start_scan_cursor.map(scan_cursor_to_string));
+        stream::try_unfold(start_scan_cursor, move |scan_cursor| async move {
+            let Some(scan_cursor) = scan_cursor else {
+                return Ok(None);
             };
 
-            match result_cursor {
-                Some(next_cursor) => {
-                    if max_count.is_none() {
-                        current_scan_cursor = Some(scan_cursor_to_string(&next_cursor));
-                    } else {
-                        final_result_cursor = Some(next_cursor);
-                        break;
-                    }
-                }
-                None => {
-                    break;
+            let clients = self.ctx.golem_clients().await?;
+            let results = clients
+                .worker
+                .get_workers_metadata(
+                    &component_id,
+                    filters,
+                    scan_cursor.as_deref(),
+                    max_count.or(Some(self.ctx.http_batch_size())),
+                    Some(precise),
+                )
+                .await
+                .map_service_error()?;
+
+            let workers = results
+                .workers
+                .into_iter()
+                .map(|meta| WorkerMetadata::from_cloud(component_name.clone(), meta))
+                .collect();
+
+            let (next_cursor, next_state) = match results.cursor {
+                Some(next_cursor) if max_count.is_none() => {
+                    (None, Some(Some(scan_cursor_to_string(&next_cursor))))
                 }
-            }
-        }
+                next_cursor => (next_cursor, None),
+            };
 
-        Ok((workers, final_result_cursor))
+            Ok::<_, anyhow::Error>(Some((
+                WorkerPage {
+                    workers,
+                    next_cursor,
+                },
+                next_state,
+            )))
+        })
     }
 
     async fn component_by_worker_name_match(
@@ -1472,6 +2502,125 @@ impl WorkerCommandHandler {
         Ok(())
     }
 
+    /// Uses the worker of the profile context when the worker name is omitted, in that case
+    /// the worker name arg is the function name, and the function name arg is the first argument.
+    ///
+    /// When the component of the context worker had to be resolved to decide that, it is
+    /// returned too, so the invocation does not have to fetch it again.
+    async fn with_context_worker(
+        &self,
+        worker_name: WorkerNameArg,
+        function_name: Option<WorkerFunctionName>,
+        arguments: Vec<WorkerFunctionArgument>,
+    ) -> anyhow::Result<(
+        WorkerNameArg,
+        WorkerFunctionName,
+        Vec<WorkerFunctionArgument>,
+        Option<(WorkerNameMatch, Component)>,
+    )> {
+        let context_worker_name = self
+            .ctx
+            .profile_context()
+            .worker
+            .as_ref()
+            .map(|worker_name| WorkerName::from(worker_name.as_str()));
+
+        fn use_context_worker(
+            context_worker_name: WorkerName,
+            worker_name: WorkerNameArg,
+            function_name: Option<WorkerFunctionName>,
+            arguments: Vec<WorkerFunctionArgument>,
+            resolved: Option<(WorkerNameMatch, Component)>,
+        ) -> (
+            WorkerNameArg,
+            WorkerFunctionName,
+            Vec<WorkerFunctionArgument>,
+            Option<(WorkerNameMatch, Component)>,
+        ) {
+            log_action(
+                "Using",
+                format!(
+                    "worker {} from the profile context",
+                    context_worker_name.0.log_color_highlight()
+                ),
+            );
+            (
+                WorkerNameArg {
+                    worker_name: context_worker_name,
+                },
+                worker_name.worker_name.0,
+                function_name.into_iter().chain(arguments).collect(),
+                resolved,
+            )
+        }
+
+        match (context_worker_name, function_name) {
+            (None, Some(function_name)) => Ok((worker_name, function_name, arguments, None)),
+            (None, None) => {
+                log_error("Missing function name");
+                logln(format!(
+                    "Specify both the worker and the function name, or set the worker in the profile context using '{} context set worker=<WORKER>'",
+                    command_name()
+                ));
+                self.ctx
+                    .error_handler()
+                    .handle_partial_match(
+                        GolemCliCommandPartialMatch::WorkerInvokeMissingFunctionName {
+                            worker_name: worker_name.worker_name,
+                        },
+                    )
+                    .await?;
+                bail!(NonSuccessfulExit);
+            }
+            (Some(context_worker_name), None) => Ok(use_context_worker(
+                context_worker_name,
+                worker_name,
+                None,
+                arguments,
+                None,
+            )),
+            (Some(context_worker_name), Some(function_name)) => {
+                match self
+                    .component_exporting_function(&context_worker_name, &worker_name.worker_name.0)
+                    .await?
+                {
+                    Some(resolved) => Ok(use_context_worker(
+                        context_worker_name,
+                        worker_name,
+                        Some(function_name),
+                        arguments,
+                        Some(resolved),
+                    )),
+                    None => Ok((worker_name, function_name, arguments, None)),
+                }
+            }
+        }
+    }
+
+    /// Returns the matched worker name and its component, if the component exports the function
+    async fn component_exporting_function(
+        &self,
+        worker_name: &WorkerName,
+        function_name: &str,
+    ) -> anyhow::Result<Option<(WorkerNameMatch, Component)>> {
+        let worker_name_match = self.match_worker_name(worker_name.clone()).await?;
+        let component = self
+            .ctx
+            .component_handler()
+            .component(
+                worker_name_match.project.as_ref(),
+                (&worker_name_match.component_name).into(),
+                None,
+            )
+            .await?;
+
+        Ok(component
+            .filter(|component| {
+                fuzzy_match_function_name(function_name, &component.metadata.exports).is_ok()
+            })
+            .map(|component| (worker_name_match, component)))
+    }
+
     pub async fn match_worker_name(
         &self,
         worker_name: WorkerName,
@@ -1480,6 +2629,29 @@ impl WorkerCommandHandler {
             (worker_name != "-").then(|| worker_name.into())
         }
 
+        let context_component_match = |worker_name: &str| {
+            self.ctx
+                .profile_context()
+                .component
+                .as_ref()
+                .map(|component_name| {
+                    log_action(
+                        "Using",
+                        format!(
+                            "component {} from the profile context",
+                            component_name.log_color_highlight()
+                        ),
+                    );
+                    WorkerNameMatch {
+                        account: None,
+                        project: None,
+                        component_name_match_kind: ComponentNameMatchKind::Unknown,
+                        component_name: component_name.as_str().into(),
+                        worker_name: to_opt_worker_name(worker_name.to_string()),
+                    }
+                })
+        };
+
         let segments = worker_name.0.split("/").collect::<Vec<&str>>();
         match segments.len() {
             // <WORKER>
@@ -1498,6 +2670,10 @@ impl WorkerCommandHandler {
                         let selected_component_names = app_ctx.selected_component_names();
 
                         if selected_component_names.len() != 1 {
+                            if let Some(worker_name_match) = context_component_match(&worker_name) {
+                                return Ok(worker_name_match);
+                            }
+
                             logln("");
                             log_error(
                                     format!("Multiple components were selected based on the current directory: {}",
@@ -1530,6 +2706,10 @@ impl WorkerCommandHandler {
                         })
                     }
                     None => {
+                        if let Some(worker_name_match) = context_component_match(&worker_name) {
+                            return Ok(worker_name_match);
+                        }
+
                         logln("");
                         log_error("Cannot infer the component name for the worker as the current directory is not part of an application.");
                         logln("");
@@ -1815,6 +2995,13 @@ pub fn lenient_parse_type_annotated_value(
     parse_value_and_type(analysed_type, input)
 }
 
+/// Page of workers of a component
+pub struct WorkerPage {
+    pub workers: Vec<WorkerMetadata>,
+    /// Cursor for more results, only set on the last page when max_count is used
+    pub next_cursor: Option<ScanCursor>,
+}
+
 fn scan_cursor_to_string(cursor: &ScanCursor) -> String {
     format!("{}/{}", cursor.layer, cursor.cursor)
 }
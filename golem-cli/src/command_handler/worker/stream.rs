@@ -14,6 +14,8 @@
 
 use crate::command_handler::worker::parse_worker_error;
 use crate::command_handler::worker::stream_output::WorkerStreamOutput;
+use crate::config::HttpClientConfig;
+use crate::http_network::{connect_through_proxy, native_tls_connector, proxy_for_url};
 use crate::model::{Format, WorkerConnectOptions};
 use anyhow::{anyhow, Context};
 use bytes::Bytes;
@@ -21,18 +23,19 @@ use futures_util::future::Either;
 use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{future, pin_mut, SinkExt, StreamExt, TryStreamExt};
 use golem_common::model::{IdempotencyKey, Timestamp, WorkerEvent};
-use native_tls::TlsConnector;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::net::TcpStream;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::Mutex;
 use tokio::{task, time};
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::handshake::client::Request;
 use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::{
-    connect_async_tls_with_config, tungstenite, Connector, MaybeTlsStream, WebSocketStream,
+    client_async_tls_with_config, connect_async_tls_with_config, tungstenite, Connector,
+    WebSocketStream,
 };
 use tracing::{debug, error, info, trace};
 use url::Url;
@@ -41,6 +44,9 @@ use uuid::Uuid;
 pub struct WorkerConnection {
     request: Request,
     connector: Option<Connector>,
+    proxy: Option<Url>,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    unix_socket: Option<PathBuf>,
     output: WorkerStreamOutput,
     idempotency_key: Option<IdempotencyKey>,
     last_seen_idempotency_key: Arc<Mutex<Option<IdempotencyKey>>>,
@@ -55,16 +61,28 @@ impl WorkerConnection {
         component_id: Uuid,
         worker_name: String,
         connect_options: WorkerConnectOptions,
-        allow_insecure: bool,
+        http_client_config: &HttpClientConfig,
         format: Format,
         idempotency_key: Option<IdempotencyKey>,
     ) -> anyhow::Result<WorkerConnection> {
+        let proxy = if http_client_config.no_proxy {
+            None
+        } else {
+            proxy_for_url(&worker_service_url)
+        };
+        if let Some(proxy) = &proxy {
+            debug!(
+                proxy = proxy.as_str(),
+                "Worker stream connect through proxy"
+            );
+        }
+
         let (request, connector) = Self::create_request(
             worker_service_url,
             auth_token,
             component_id,
             worker_name,
-            allow_insecure,
+            http_client_config,
         )?;
         let output = WorkerStreamOutput::new(connect_options, format);
 
@@ -74,6 +92,8 @@ impl WorkerConnection {
         Ok(Self {
             request,
             connector,
+            proxy,
+            unix_socket: http_client_config.unix_socket.clone(),
             output,
             idempotency_key,
             last_seen_idempotency_key,
@@ -94,14 +114,49 @@ impl WorkerConnection {
 
     /// Connects to the worker event stream and outputs incoming messages until the connection is dropped
     async fn run(&self) -> anyhow::Result<()> {
-        let (ws_stream, _) = connect_async_tls_with_config(
-            self.request.clone(),
-            None,
-            false,
-            self.connector.clone(),
-        )
-        .await
-        .map_err(|e| match e {
+        #[cfg(unix)]
+        if let Some(unix_socket) = &self.unix_socket {
+            let stream = tokio::net::UnixStream::connect(unix_socket)
+                .await
+                .with_context(|| {
+                    anyhow!("Failed to connect to Unix socket {}", unix_socket.display())
+                })?;
+            let (ws_stream, _) =
+                tokio_tungstenite::client_async_with_config(self.request.clone(), stream, None)
+                    .await
+                    .map_err(Self::connect_error)?;
+            return self.run_connected(ws_stream).await;
+        }
+
+        let connected = match &self.proxy {
+            Some(proxy) => {
+                let stream =
+                    connect_through_proxy(proxy, &Url::parse(&self.request.uri().to_string())?)
+                        .await?;
+                client_async_tls_with_config(
+                    self.request.clone(),
+                    stream,
+                    None,
+                    self.connector.clone(),
+                )
+                .await
+            }
+            None => {
+                connect_async_tls_with_config(
+                    self.request.clone(),
+                    None,
+                    false,
+                    self.connector.clone(),
+                )
+                .await
+            }
+        };
+        let (ws_stream, _) = connected.map_err(Self::connect_error)?;
+        self.run_connected(ws_stream).await
+    }
+
+    fn connect_error(error: tungstenite::Error) -> anyhow::Error {
+        match error {
             tungstenite::error::Error::Http(http_error_response) => {
                 let status = http_error_response.status().as_u16();
                 match http_error_response.body().clone() {
@@ -109,9 +164,14 @@ impl WorkerConnection {
                     None => anyhow!("Websocket connect failed, HTTP error: {}", status),
                 }
             }
-            _ => anyhow!("Websocket connect failed, error: {}", e),
-        })?;
+            _ => anyhow!("Websocket connect failed, error: {}", error),
+        }
+    }
 
+    async fn run_connected<S>(&self, ws_stream: WebSocketStream<S>) -> anyhow::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
         let (write, read) = ws_stream.split();
 
         let pings = task::spawn(async move { Self::ping_loop(write).await });
@@ -145,7 +205,7 @@ impl WorkerConnection {
         auth_token: String,
         component_id: Uuid,
         worker_name: String,
-        allow_insecure: bool,
+        http_client_config: &HttpClientConfig,
     ) -> anyhow::Result<(Request, Option<Connector>)> {
         let mut url = worker_service_url;
 
@@ -155,6 +215,7 @@ impl WorkerConnection {
             .map_err(|()| anyhow!("Failed to set ws url schema".to_string()))?;
         url.path_segments_mut()
             .map_err(|()| anyhow!("Failed to get url path for ws url".to_string()))?
+            .pop_if_empty()
             .push("v1")
             .push("components")
             .push(&component_id.to_string())
@@ -174,23 +235,17 @@ impl WorkerConnection {
             headers.insert("Authorization", format!("Bearer {auth_token}").parse()?);
         }
 
-        let connector = if allow_insecure {
-            Some(Connector::NativeTls(
-                TlsConnector::builder()
-                    .danger_accept_invalid_certs(true)
-                    .danger_accept_invalid_hostnames(true)
-                    .build()?,
-            ))
-        } else {
-            None
-        };
+        let connector =
+            native_tls_connector(http_client_config.allow_insecure, &http_client_config.tls)?
+                .map(Connector::NativeTls);
 
         Ok((request, connector))
     }
 
-    async fn ping_loop(
-        mut write: SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
-    ) -> anyhow::Error {
+    async fn ping_loop<S>(mut write: SplitSink<WebSocketStream<S>, Message>) -> anyhow::Error
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
         let mut interval = time::interval(Duration::from_secs(1)); // TODO configure
         let mut cnt: i64 = 1;
 
@@ -210,13 +265,15 @@ impl WorkerConnection {
         }
     }
 
-    async fn read_loop(
-        read: SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>,
+    async fn read_loop<S>(
+        read: SplitStream<WebSocketStream<S>>,
         output: WorkerStreamOutput,
         last_seen_idempotency_key: Arc<Mutex<Option<IdempotencyKey>>>,
         idempotency_key_to_look_for: Option<IdempotencyKey>,
         goal_reached: Arc<AtomicBool>,
-    ) {
+    ) where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
         let result = read
             .try_for_each(|message| {
                 let output = output.clone();
@@ -0,0 +1,215 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::command_handler::worker::lenient_parse_type_annotated_value;
+use crate::error::NonSuccessfulExit;
+use crate::fs;
+use crate::log::log_warn_action;
+use crate::model::component::render_type;
+use crate::model::wave::{
+    check_wave_brackets, highlight_wave, wave_case_names, wave_closing_suffix, wave_skeleton,
+};
+use anyhow::bail;
+use golem_wasm_ast::analysis::AnalysedType;
+use inquire::autocompletion::{Autocomplete, Replacement};
+use inquire::validator::{ErrorMessage, Validation};
+use inquire::{CustomUserError, InquireError, Text};
+use itertools::Itertools;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+const HISTORY_FILE_NAME: &str = "invoke_argument_history.json";
+const MAX_HISTORY_SIZE: usize = 50;
+
+#[derive(Debug, Clone)]
+pub struct WaveParam {
+    pub name: String,
+    pub typ: AnalysedType,
+}
+
+/// Prompts for invoke arguments, with value templates, bracket completion and per function
+/// parameter history as suggestions, and validates the values against the parameter types
+/// before accepting them.
+pub struct WaveArgumentEditor {
+    history_file: PathBuf,
+    history_key: String,
+}
+
+impl WaveArgumentEditor {
+    pub fn new(config_dir: &Path, component_name: &str, function_name: &str) -> Self {
+        Self {
+            history_file: config_dir.join(HISTORY_FILE_NAME),
+            history_key: format!("{component_name}/{function_name}"),
+        }
+    }
+
+    /// Prompts for the parameters which do not have an argument yet, and returns all arguments
+    pub fn prompt_missing_arguments(
+        &self,
+        params: &[WaveParam],
+        mut arguments: Vec<String>,
+    ) -> anyhow::Result<Vec<String>> {
+        if arguments.len() >= params.len() {
+            return Ok(arguments);
+        }
+
+        let mut history = self.load_history();
+        for param in &params[arguments.len()..] {
+            let history_key = format!("{}/{}", self.history_key, param.name);
+            let completer = WaveCompleter {
+                typ: param.typ.clone(),
+                history: history.get(&history_key).cloned().unwrap_or_default(),
+            };
+            let validator = {
+                let typ = param.typ.clone();
+                move |input: &str| -> Result<Validation, CustomUserError> {
+                    Ok(validate_wave(&typ, input))
+                }
+            };
+            let prompt = format!("{} ({}):", param.name, render_type(&param.typ));
+
+            let argument = match Text::new(&prompt)
+                .with_help_message("tab: complete, ↑↓: templates, cases and history")
+                .with_autocomplete(completer)
+                .with_validator(validator)
+                .with_formatter(&highlight_wave)
+                .prompt()
+            {
+                Ok(argument) => argument,
+                Err(InquireError::OperationCanceled | InquireError::OperationInterrupted) => {
+                    log_warn_action("Canceled", "invocation");
+                    bail!(NonSuccessfulExit);
+                }
+                Err(error) => return Err(error.into()),
+            };
+
+            let entries = history.entry(history_key).or_default();
+            entries.retain(|entry| entry != &argument);
+            entries.push(argument.clone());
+            if entries.len() > MAX_HISTORY_SIZE {
+                entries.drain(..entries.len() - MAX_HISTORY_SIZE);
+            }
+
+            arguments.push(argument);
+        }
+        self.save_history(&history);
+
+        Ok(arguments)
+    }
+
+    fn load_history(&self) -> BTreeMap<String, Vec<String>> {
+        if !self.history_file.exists() {
+            return BTreeMap::new();
+        }
+        match fs::read_to_string(&self.history_file)
+            .and_then(|content| Ok(serde_json::from_str(&content)?))
+        {
+            Ok(history) => history,
+            Err(error) => {
+                debug!(error = %error, "Failed to load invoke argument history");
+                BTreeMap::new()
+            }
+        }
+    }
+
+    fn save_history(&self, history: &BTreeMap<String, Vec<String>>) {
+        let result = serde_json::to_string_pretty(history)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| fs::write_str(&self.history_file, content));
+        if let Err(error) = result {
+            debug!(error = %error, "Failed to save invoke argument history");
+        }
+    }
+}
+
+fn validate_wave(typ: &AnalysedType, input: &str) -> Validation {
+    if let Err(error) = check_wave_brackets(input) {
+        return Validation::Invalid(ErrorMessage::Custom(format!(
+            "{}: {}",
+            error.message,
+            highlight_wave(input)
+        )));
+    }
+    match lenient_parse_type_annotated_value(typ, input) {
+        Ok(_) => Validation::Valid,
+        Err(error) => Validation::Invalid(ErrorMessage::Custom(error)),
+    }
+}
+
+#[derive(Clone)]
+struct WaveCompleter {
+    typ: AnalysedType,
+    history: Vec<String>,
+}
+
+impl Autocomplete for WaveCompleter {
+    fn get_suggestions(&mut self, input: &str) -> Result<Vec<String>, CustomUserError> {
+        let mut suggestions = Vec::new();
+
+        if input.trim().is_empty() {
+            suggestions.extend(wave_skeleton(&self.typ));
+        }
+
+        let closing_suffix = wave_closing_suffix(input);
+        if !closing_suffix.is_empty() {
+            suggestions.push(format!("{input}{closing_suffix}"));
+        }
+
+        // Case names are only completed for the top level value
+        if !input.is_empty()
+            && input
+                .chars()
+                .all(|char| char.is_alphanumeric() || char == '-')
+        {
+            suggestions.extend(
+                wave_case_names(&self.typ)
+                    .into_iter()
+                    .filter(|case| case.starts_with(input) && case != input),
+            );
+        }
+
+        suggestions.extend(
+            self.history
+                .iter()
+                .rev()
+                .filter(|entry| entry.starts_with(input) && *entry != input)
+                .cloned(),
+        );
+
+        Ok(suggestions.into_iter().unique().collect())
+    }
+
+    fn get_completion(
+        &mut self,
+        input: &str,
+        highlighted_suggestion: Option<String>,
+    ) -> Result<Replacement, CustomUserError> {
+        if highlighted_suggestion.is_some() {
+            return Ok(highlighted_suggestion);
+        }
+
+        let closing_suffix = wave_closing_suffix(input);
+        if !closing_suffix.is_empty() {
+            return Ok(Some(format!("{input}{closing_suffix}")));
+        }
+
+        let suggestions = self.get_suggestions(input)?;
+        if suggestions.len() == 1 {
+            Ok(suggestions.into_iter().next())
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -12,13 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::keychain;
+use crate::log::LogColorize;
+use crate::model::text::fmt::log_warn;
 use crate::model::Format;
 use anyhow::{anyhow, bail, Context};
 use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use golem_client::model::TokenSecret;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt::Debug;
 use std::fmt::{Display, Formatter};
 use std::fs::{create_dir_all, File, OpenOptions};
@@ -116,6 +120,9 @@ pub struct NamedProfile {
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Profile {
+    /// Base profile, unset settings are inherited from it (except auth)
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub extends: Option<ProfileName>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub custom_url: Option<Url>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
@@ -133,6 +140,7 @@ impl Profile {
     pub fn default_local_profile() -> Self {
         let url = Url::parse(DEFAULT_OSS_URL).unwrap();
         Self {
+            extends: None,
             custom_url: Some(url),
             custom_worker_url: None,
             custom_cloud_url: None,
@@ -143,12 +151,260 @@ impl Profile {
             }),
         }
     }
+
+    fn inherit_from(
+        &mut self,
+        base_name: &ProfileName,
+        base: &Profile,
+        origins: &mut ProfileOrigins,
+    ) {
+        fn inherit<T: Clone>(
+            key: &str,
+            value: &mut Option<T>,
+            base_value: &Option<T>,
+            base_name: &ProfileName,
+            origins: &mut ProfileOrigins,
+        ) {
+            if value.is_none() && base_value.is_some() {
+                *value = base_value.clone();
+                origins.insert(key.to_string(), base_name.clone());
+            }
+        }
+
+        inherit(
+            PROFILE_KEY_URL,
+            &mut self.custom_url,
+            &base.custom_url,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_CLOUD_URL,
+            &mut self.custom_cloud_url,
+            &base.custom_cloud_url,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_WORKER_URL,
+            &mut self.custom_worker_url,
+            &base.custom_worker_url,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_DEFAULT_FORMAT,
+            &mut self.config.default_format,
+            &base.config.default_format,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_DEFAULT_PROJECT,
+            &mut self.config.default_project,
+            &base.config.default_project,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_DEBUG_HTTP,
+            &mut self.config.debug_http,
+            &base.config.debug_http,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_HTTP_TIMEOUT,
+            &mut self.config.http.timeout_ms,
+            &base.config.http.timeout_ms,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_HTTP_CONNECT_TIMEOUT,
+            &mut self.config.http.connect_timeout_ms,
+            &base.config.http.connect_timeout_ms,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_HTTP_RETRIES,
+            &mut self.config.http.retries,
+            &base.config.http.retries,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_HTTP_RETRY_BACKOFF,
+            &mut self.config.http.retry_backoff_ms,
+            &base.config.http.retry_backoff_ms,
+            base_name,
+            origins,
+        );
+        inherit(
+            PROFILE_KEY_TLS_CA_CERTIFICATE,
+            &mut self.config.tls.ca_certificate,
+            &base.config.tls.ca_certificate,
+            base_name,
+            origins,
+        );
+        // The client certificate and key are only inherited together
+        if self.config.tls.client_certificate.is_none() && self.config.tls.client_key.is_none() {
+            inherit(
+                PROFILE_KEY_TLS_CLIENT_CERTIFICATE,
+                &mut self.config.tls.client_certificate,
+                &base.config.tls.client_certificate,
+                base_name,
+                origins,
+            );
+            inherit(
+                PROFILE_KEY_TLS_CLIENT_KEY,
+                &mut self.config.tls.client_key,
+                &base.config.tls.client_key,
+                base_name,
+                origins,
+            );
+        }
+
+        for (command, flags) in &base.config.command_defaults {
+            if !self.config.command_defaults.contains_key(command) {
+                self.config
+                    .command_defaults
+                    .insert(command.clone(), flags.clone());
+                origins.insert(
+                    format!("{PROFILE_KEY_COMMAND_DEFAULTS}.{command}"),
+                    base_name.clone(),
+                );
+            }
+        }
+
+        // Insecure mode can only be turned on, to keep it explicit in the base profile
+        if !self.allow_insecure && base.allow_insecure {
+            self.allow_insecure = true;
+            origins.insert(PROFILE_KEY_ALLOW_INSECURE.to_string(), base_name.clone());
+        }
+    }
 }
 
+pub const PROFILE_KEY_URL: &str = "url";
+pub const PROFILE_KEY_CLOUD_URL: &str = "cloudUrl";
+pub const PROFILE_KEY_WORKER_URL: &str = "workerUrl";
+pub const PROFILE_KEY_ALLOW_INSECURE: &str = "allowInsecure";
+pub const PROFILE_KEY_DEFAULT_FORMAT: &str = "defaultFormat";
+pub const PROFILE_KEY_DEFAULT_PROJECT: &str = "defaultProject";
+pub const PROFILE_KEY_COMMAND_DEFAULTS: &str = "commandDefaults";
+pub const PROFILE_KEY_DEBUG_HTTP: &str = "debugHttp";
+pub const PROFILE_KEY_HTTP_TIMEOUT: &str = "http.timeoutMs";
+pub const PROFILE_KEY_HTTP_CONNECT_TIMEOUT: &str = "http.connectTimeoutMs";
+pub const PROFILE_KEY_HTTP_RETRIES: &str = "http.retries";
+pub const PROFILE_KEY_HTTP_RETRY_BACKOFF: &str = "http.retryBackoffMs";
+pub const PROFILE_KEY_TLS_CA_CERTIFICATE: &str = "tls.caCertificate";
+pub const PROFILE_KEY_TLS_CLIENT_CERTIFICATE: &str = "tls.clientCertificate";
+pub const PROFILE_KEY_TLS_CLIENT_KEY: &str = "tls.clientKey";
+
+/// Origin (base profile name) of inherited profile settings, by setting key
+pub type ProfileOrigins = BTreeMap<String, ProfileName>;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq)]
 pub struct ProfileConfig {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default_format: Option<Format>,
     #[serde(default)]
-    pub default_format: Format,
+    pub secret_storage: SecretStorage,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub default_project: Option<String>,
+    /// Default flags by command path (e.g. "worker list"), used when not provided on the command line
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub command_defaults: BTreeMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "ProfileContext::is_empty", default)]
+    pub context: ProfileContext,
+    /// Log HTTP requests and responses, same as using --debug-http
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub debug_http: Option<bool>,
+    #[serde(skip_serializing_if = "ProfileHttpConfig::is_empty", default)]
+    pub http: ProfileHttpConfig,
+    #[serde(skip_serializing_if = "ProfileTlsConfig::is_empty", default)]
+    pub tls: ProfileTlsConfig,
+}
+
+/// Timeouts and retries of service calls, overridden by the --http-* flags
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileHttpConfig {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub timeout_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub connect_timeout_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub retries: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub retry_backoff_ms: Option<u64>,
+}
+
+impl ProfileHttpConfig {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Custom CA certificates and client certificate (mTLS) of service calls, all files are PEM
+/// encoded, the client key in PKCS #8 format
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileTlsConfig {
+    /// CA certificate bundle, trusted in addition to the system certificates
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ca_certificate: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub client_certificate: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub client_key: Option<PathBuf>,
+}
+
+impl ProfileTlsConfig {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Default component and worker for commands, set using the context command.
+///
+/// Unlike other profile settings, the context is not inherited from base profiles.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileContext {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub component: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub worker: Option<String>,
+}
+
+impl ProfileContext {
+    pub fn is_empty(&self) -> bool {
+        self.component.is_none() && self.worker.is_none()
+    }
+}
+
+/// Where the auth secrets of a profile are stored
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, Eq, PartialEq, ValueEnum)]
+pub enum SecretStorage {
+    /// Plaintext in the global config file
+    #[default]
+    #[serde(alias = "file")]
+    File,
+    /// OS keychain: macOS Keychain, Windows Credential Manager or Secret Service
+    #[serde(alias = "keychain")]
+    Keychain,
+}
+
+impl Display for SecretStorage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::File => "file",
+            Self::Keychain => "keychain",
+        };
+        Display::fmt(&s, f)
+    }
 }
 
 impl Config {
@@ -163,6 +419,14 @@ impl Config {
     }
 
     pub fn from_dir(config_dir: &Path) -> anyhow::Result<Config> {
+        let mut config = Self::from_dir_with_external_secrets(config_dir)?;
+        config.load_secrets(keychain::load_secret);
+        Ok(config)
+    }
+
+    /// Loads the config without reading the secrets from the OS keychain, so they keep their
+    /// [AuthSecret::stored_externally] placeholders
+    pub fn from_dir_with_external_secrets(config_dir: &Path) -> anyhow::Result<Config> {
         let config_path = Self::config_path(config_dir);
 
         if !config_path
@@ -216,6 +480,68 @@ impl Config {
         Ok(config.with_local_and_cloud_profiles())
     }
 
+    fn load_secrets(&mut self, load_secret: impl Fn(&ProfileName) -> anyhow::Result<AuthSecret>) {
+        for (profile_name, profile) in &mut self.profiles {
+            if profile.config.secret_storage != SecretStorage::Keychain {
+                continue;
+            }
+
+            let Some(secret) = profile.auth.secret_mut() else {
+                continue;
+            };
+
+            // Secrets that are present in the config file are the result of a fallback on save
+            if !secret.is_stored_externally() {
+                continue;
+            }
+
+            match load_secret(profile_name) {
+                Ok(loaded_secret) => *secret = loaded_secret,
+                Err(err) => {
+                    log_warn(format!(
+                        "Failed to load the secret of profile {} from the OS keychain: {:#}",
+                        profile_name.0.log_color_highlight(),
+                        err
+                    ));
+                    // Forces a new login for OAuth2 profiles instead of using an invalid token
+                    if let AuthenticationConfig::OAuth2(inner) = &mut profile.auth {
+                        inner.data = None;
+                    }
+                }
+            }
+        }
+    }
+
+    fn move_secrets(
+        &mut self,
+        store_secret: impl Fn(&ProfileName, &AuthSecret) -> anyhow::Result<()>,
+    ) {
+        for (profile_name, profile) in &mut self.profiles {
+            if profile.config.secret_storage != SecretStorage::Keychain {
+                continue;
+            }
+
+            let Some(secret) = profile.auth.secret_mut() else {
+                continue;
+            };
+
+            if secret.is_stored_externally() {
+                continue;
+            }
+
+            match store_secret(profile_name, secret) {
+                Ok(()) => *secret = AuthSecret::stored_externally(),
+                Err(err) => {
+                    log_warn(format!(
+                        "Failed to store the secret of profile {} in the OS keychain, falling back to the config file: {:#}",
+                        profile_name.0.log_color_highlight(),
+                        err
+                    ));
+                }
+            }
+        }
+    }
+
     fn with_local_and_cloud_profiles(mut self) -> Self {
         self.profiles
             .entry(ProfileName::local())
@@ -243,7 +569,10 @@ impl Config {
             .map_err(|err| anyhow!("Can't open config file: {err}"))?;
         let writer = BufWriter::new(file);
 
-        serde_json::to_writer_pretty(writer, self)
+        let mut config = self.clone();
+        config.move_secrets(keychain::store_secret);
+
+        serde_json::to_writer_pretty(writer, &config)
             .map_err(|err| anyhow!("Can't save config to file: {err}"))
     }
 
@@ -267,14 +596,77 @@ impl Config {
         Ok(())
     }
 
+    /// Returns the default profile with inherited settings applied
     pub fn get_default_profile(config_dir: &Path) -> anyhow::Result<NamedProfile> {
-        let mut config = Self::from_dir(config_dir)?;
-        let profile_name = config.default_profile.unwrap_or_else(ProfileName::local);
-        let profile = config.profiles.remove(&profile_name).unwrap();
-        Ok(NamedProfile {
-            name: profile_name.clone(),
-            profile: profile.clone(),
-        })
+        let config = Self::from_dir(config_dir)?;
+        let profile_name = config.default_profile_name();
+        Ok(config.resolve_profile(&profile_name)?.unwrap().0)
+    }
+
+    /// Returns the profile with inherited settings applied
+    pub fn get_resolved_profile(
+        config_dir: &Path,
+        name: &ProfileName,
+    ) -> anyhow::Result<Option<(NamedProfile, ProfileOrigins)>> {
+        Self::from_dir(config_dir)?.resolve_profile(name)
+    }
+
+    /// Applies the settings of the base profiles, the closest base profile wins
+    pub fn resolve_profile(
+        &self,
+        name: &ProfileName,
+    ) -> anyhow::Result<Option<(NamedProfile, ProfileOrigins)>> {
+        let Some(profile) = self.profiles.get(name) else {
+            return Ok(None);
+        };
+
+        let mut profile = profile.clone();
+        let mut origins = ProfileOrigins::new();
+        let mut visited = vec![name.clone()];
+        let mut base_name = profile.extends.clone();
+
+        while let Some(current_base_name) = base_name {
+            if visited.contains(&current_base_name) {
+                bail!(
+                    "Profile inheritance cycle detected: {}",
+                    visited
+                        .iter()
+                        .chain([&current_base_name])
+                        .map(|name| name.0.as_str())
+                        .join(" -> ")
+                );
+            }
+
+            let Some(base) = self.profiles.get(&current_base_name) else {
+                bail!(
+                    "Profile {} extends profile {}, which does not exist",
+                    visited.last().unwrap().0.log_color_highlight(),
+                    current_base_name.0.log_color_highlight()
+                );
+            };
+
+            profile.inherit_from(&current_base_name, base, &mut origins);
+            base_name = base.extends.clone();
+            visited.push(current_base_name);
+        }
+
+        Ok(Some((
+            NamedProfile {
+                name: name.clone(),
+                profile,
+            },
+            origins,
+        )))
+    }
+
+    /// Names of the profiles directly extending the given profile
+    pub fn extending_profile_names(&self, name: &ProfileName) -> Vec<ProfileName> {
+        self.profiles
+            .iter()
+            .filter(|(_, profile)| profile.extends.as_ref() == Some(name))
+            .map(|(profile_name, _)| profile_name.clone())
+            .sorted()
+            .collect()
     }
 
     pub fn get_profile(
@@ -300,8 +692,16 @@ impl Config {
 
     pub fn delete_profile(name: &ProfileName, config_dir: &Path) -> anyhow::Result<()> {
         let mut config = Self::from_dir(config_dir)?;
-        config.profiles.remove(name);
-        config.store_file(config_dir)
+        let profile = config.profiles.remove(name);
+        config.store_file(config_dir)?;
+
+        if let Some(profile) = profile {
+            if profile.config.secret_storage == SecretStorage::Keychain {
+                keychain::delete_secret(name)?;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -331,27 +731,58 @@ impl From<&Profile> for ClientConfig {
             .unwrap_or_else(|| component_url.clone());
 
         let allow_insecure = profile.allow_insecure;
+        let tls = &profile.config.tls;
 
         ClientConfig {
             component_url,
             worker_url,
             cloud_url,
-            service_http_client_config: HttpClientConfig::new_for_service_calls(allow_insecure),
-            invoke_http_client_config: HttpClientConfig::new_for_invoke(allow_insecure),
-            health_check_http_client_config: HttpClientConfig::new_for_health_check(allow_insecure),
+            service_http_client_config: HttpClientConfig::new_for_service_calls(allow_insecure)
+                .with_tls(tls),
+            invoke_http_client_config: HttpClientConfig::new_for_invoke(allow_insecure)
+                .with_tls(tls),
+            health_check_http_client_config: HttpClientConfig::new_for_health_check(allow_insecure)
+                .with_tls(tls),
             file_download_http_client_config: HttpClientConfig::new_for_file_download(
                 allow_insecure,
-            ),
+            )
+            .with_tls(tls),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+impl ClientConfig {
+    /// Applies the timeouts requested with flags or profile settings. Invocations keep having
+    /// no total timeout, so long-running invocations are not cut.
+    pub fn with_http_overrides(
+        mut self,
+        timeout: Option<Duration>,
+        connect_timeout: Option<Duration>,
+    ) -> Self {
+        self.service_http_client_config = self
+            .service_http_client_config
+            .with_overrides(timeout, connect_timeout);
+        self.invoke_http_client_config = self
+            .invoke_http_client_config
+            .with_overrides(None, connect_timeout);
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct HttpClientConfig {
     pub allow_insecure: bool,
     pub timeout: Option<Duration>,
     pub connect_timeout: Option<Duration>,
     pub read_timeout: Option<Duration>,
+    pub tls: ProfileTlsConfig,
+    /// Ignore the proxies set by the HTTPS_PROXY and similar environment variables, used for
+    /// clients connecting to the local client proxy
+    pub no_proxy: bool,
+    /// Sent to the local client proxy with every request, selecting the upstream client
+    pub local_proxy_key: Option<String>,
+    /// Unix socket of the services, all requests are sent through it
+    pub unix_socket: Option<PathBuf>,
 }
 
 impl HttpClientConfig {
@@ -361,6 +792,10 @@ impl HttpClientConfig {
             timeout: Some(Duration::from_secs(10)),
             connect_timeout: Some(Duration::from_secs(10)),
             read_timeout: Some(Duration::from_secs(10)),
+            tls: ProfileTlsConfig::default(),
+            no_proxy: false,
+            local_proxy_key: None,
+            unix_socket: None,
         }
         .with_env_overrides("GOLEM_HTTP")
     }
@@ -371,6 +806,10 @@ impl HttpClientConfig {
             timeout: None,
             connect_timeout: None,
             read_timeout: None,
+            tls: ProfileTlsConfig::default(),
+            no_proxy: false,
+            local_proxy_key: None,
+            unix_socket: None,
         }
         .with_env_overrides("GOLEM_HTTP_INVOKE")
     }
@@ -381,6 +820,10 @@ impl HttpClientConfig {
             timeout: Some(Duration::from_secs(2)),
             connect_timeout: Some(Duration::from_secs(1)),
             read_timeout: Some(Duration::from_secs(1)),
+            tls: ProfileTlsConfig::default(),
+            no_proxy: false,
+            local_proxy_key: None,
+            unix_socket: None,
         }
         .with_env_overrides("GOLEM_HTTP_HEALTHCHECK")
     }
@@ -391,10 +834,34 @@ impl HttpClientConfig {
             timeout: Some(Duration::from_secs(60)),
             connect_timeout: Some(Duration::from_secs(10)),
             read_timeout: Some(Duration::from_secs(60)),
+            tls: ProfileTlsConfig::default(),
+            no_proxy: false,
+            local_proxy_key: None,
+            unix_socket: None,
         }
         .with_env_overrides("GOLEM_HTTP_FILE_DOWNLOAD")
     }
 
+    fn with_tls(mut self, tls: &ProfileTlsConfig) -> Self {
+        self.tls = tls.clone();
+        self
+    }
+
+    fn with_overrides(
+        mut self,
+        timeout: Option<Duration>,
+        connect_timeout: Option<Duration>,
+    ) -> Self {
+        if let Some(timeout) = timeout {
+            self.timeout = Some(timeout);
+            self.read_timeout = Some(timeout);
+        }
+        if let Some(connect_timeout) = connect_timeout {
+            self.connect_timeout = Some(connect_timeout);
+        }
+        self
+    }
+
     fn with_env_overrides(mut self, prefix: &str) -> Self {
         fn env_duration(name: &str) -> Option<Duration> {
             let duration_str = std::env::var(name).ok()?;
@@ -433,6 +900,21 @@ impl AuthenticationConfig {
             secret: AuthSecret(token),
         })
     }
+
+    /// Removes secrets, so the config can be shared, OAuth2 profiles will require a new login
+    pub fn redact_secrets(&mut self) {
+        match self {
+            Self::OAuth2(inner) => inner.data = None,
+            Self::Static(inner) => inner.secret = AuthSecret::stored_externally(),
+        }
+    }
+
+    pub fn secret_mut(&mut self) -> Option<&mut AuthSecret> {
+        match self {
+            Self::OAuth2(inner) => inner.data.as_mut().map(|data| &mut data.secret),
+            Self::Static(inner) => Some(&mut inner.secret),
+        }
+    }
 }
 
 impl Default for AuthenticationConfig {
@@ -454,18 +936,31 @@ pub struct OAuth2AuthenticationData {
     pub account_id: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "AuthSecret::is_stored_externally")]
     pub secret: AuthSecret,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StaticAuthenticationConfig {
+    #[serde(default, skip_serializing_if = "AuthSecret::is_stored_externally")]
     pub secret: AuthSecret,
 }
 
-#[derive(Clone, Copy, Serialize, Deserialize)]
+#[derive(Clone, Copy, Serialize, Deserialize, Default)]
 pub struct AuthSecret(pub Uuid);
 
+impl AuthSecret {
+    /// Placeholder for secrets that are not stored in the config file, but in the OS keychain
+    pub fn stored_externally() -> Self {
+        Self(Uuid::nil())
+    }
+
+    pub fn is_stored_externally(&self) -> bool {
+        self.0.is_nil()
+    }
+}
+
 impl Display for AuthSecret {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str("*******")
@@ -483,3 +978,240 @@ impl From<AuthSecret> for TokenSecret {
         Self { value: value.0 }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::config::{
+        AuthSecret, AuthenticationConfig, Config, OAuth2AuthenticationConfig,
+        OAuth2AuthenticationData, Profile, ProfileConfig, ProfileName, SecretStorage,
+        PROFILE_KEY_DEFAULT_FORMAT, PROFILE_KEY_URL, PROFILE_KEY_WORKER_URL,
+    };
+    use crate::model::Format;
+    use anyhow::anyhow;
+    use assert2::{assert, let_assert};
+    use chrono::Utc;
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+    use test_r::test;
+    use url::Url;
+    use uuid::Uuid;
+
+    fn profile(extends: Option<&str>, url: Option<&str>, format: Option<Format>) -> Profile {
+        Profile {
+            extends: extends.map(ProfileName::from),
+            custom_url: url.map(|url| Url::parse(url).unwrap()),
+            config: ProfileConfig {
+                default_format: format,
+                ..ProfileConfig::default()
+            },
+            ..Profile::default()
+        }
+    }
+
+    #[test]
+    fn resolve_profile_with_inheritance() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "base".into(),
+            profile(None, Some("http://base:9881"), Some(Format::Json)),
+        );
+        config.profiles.insert(
+            "staging".into(),
+            Profile {
+                custom_worker_url: Some(Url::parse("http://staging-worker:9881").unwrap()),
+                ..profile(Some("base"), None, None)
+            },
+        );
+        config.profiles.insert(
+            "dev".into(),
+            profile(Some("staging"), None, Some(Format::Yaml)),
+        );
+
+        let (profile, origins) = config.resolve_profile(&"dev".into()).unwrap().unwrap();
+
+        assert!(profile.profile.custom_url == Some(Url::parse("http://base:9881").unwrap()));
+        assert!(
+            profile.profile.custom_worker_url
+                == Some(Url::parse("http://staging-worker:9881").unwrap())
+        );
+        assert!(profile.profile.config.default_format == Some(Format::Yaml));
+        assert!(origins.get(PROFILE_KEY_URL) == Some(&"base".into()));
+        assert!(origins.get(PROFILE_KEY_WORKER_URL) == Some(&"staging".into()));
+        assert!(origins.get(PROFILE_KEY_DEFAULT_FORMAT).is_none());
+    }
+
+    #[test]
+    fn resolve_profile_with_inheritance_errors() {
+        let mut config = Config::default();
+        config
+            .profiles
+            .insert("a".into(), profile(Some("b"), None, None));
+        config
+            .profiles
+            .insert("b".into(), profile(Some("a"), None, None));
+        config
+            .profiles
+            .insert("c".into(), profile(Some("missing"), None, None));
+
+        assert!(config.resolve_profile(&"a".into()).is_err());
+        assert!(config.resolve_profile(&"c".into()).is_err());
+        assert!(config.resolve_profile(&"missing".into()).unwrap().is_none());
+    }
+
+    fn secret_profile(auth: AuthenticationConfig, secret_storage: SecretStorage) -> Profile {
+        Profile {
+            config: ProfileConfig {
+                secret_storage,
+                ..ProfileConfig::default()
+            },
+            auth,
+            ..Profile::default()
+        }
+    }
+
+    fn oauth2(secret: AuthSecret) -> AuthenticationConfig {
+        AuthenticationConfig::OAuth2(OAuth2AuthenticationConfig {
+            data: Some(OAuth2AuthenticationData {
+                id: Uuid::new_v4(),
+                account_id: "account".to_string(),
+                created_at: Utc::now(),
+                expires_at: Utc::now(),
+                secret,
+            }),
+        })
+    }
+
+    fn secret(config: &mut Config, profile_name: &str) -> Option<Uuid> {
+        config
+            .profiles
+            .get_mut(&ProfileName::from(profile_name))
+            .unwrap()
+            .auth
+            .secret_mut()
+            .map(|secret| secret.0)
+    }
+
+    #[test]
+    fn secret_storage_serialization() {
+        for storage in [SecretStorage::File, SecretStorage::Keychain] {
+            let json = serde_json::to_string(&storage).unwrap();
+            assert!(serde_json::from_str::<SecretStorage>(&json).unwrap() == storage);
+        }
+        assert!(
+            serde_json::from_str::<SecretStorage>(r#""keychain""#).unwrap()
+                == SecretStorage::Keychain
+        );
+        assert!(serde_json::from_str::<SecretStorage>(r#""file""#).unwrap() == SecretStorage::File);
+
+        // Profiles saved before the secret storage setting use the config file
+        let profile: Profile = serde_json::from_str(
+            r#"{"auth":{"Static":{"secret":"6b0b7d2e-8c0e-4bd4-9a3f-2f4a4d5c9e10"}}}"#,
+        )
+        .unwrap();
+        assert!(profile.config.secret_storage == SecretStorage::File);
+    }
+
+    #[test]
+    fn keychain_secrets_round_trip() {
+        let static_secret = Uuid::new_v4();
+        let oauth2_secret = Uuid::new_v4();
+        let file_secret = Uuid::new_v4();
+
+        let mut config = Config::default();
+        config.profiles.insert(
+            "static".into(),
+            secret_profile(
+                AuthenticationConfig::static_token(static_secret),
+                SecretStorage::Keychain,
+            ),
+        );
+        config.profiles.insert(
+            "oauth2".into(),
+            secret_profile(oauth2(AuthSecret(oauth2_secret)), SecretStorage::Keychain),
+        );
+        config.profiles.insert(
+            "file".into(),
+            secret_profile(
+                AuthenticationConfig::static_token(file_secret),
+                SecretStorage::File,
+            ),
+        );
+
+        let keychain = RefCell::new(BTreeMap::<ProfileName, AuthSecret>::new());
+        config.move_secrets(|profile_name, secret| {
+            keychain.borrow_mut().insert(profile_name.clone(), *secret);
+            Ok(())
+        });
+        assert!(keychain.borrow().len() == 2);
+
+        // Only the placeholders of the keychain secrets are left, which are not serialized
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains(&static_secret.to_string()));
+        assert!(!json.contains(&oauth2_secret.to_string()));
+        assert!(json.contains(&file_secret.to_string()));
+
+        let mut config: Config = serde_json::from_str(&json).unwrap();
+        assert!(secret(&mut config, "static") == Some(Uuid::nil()));
+        assert!(secret(&mut config, "oauth2") == Some(Uuid::nil()));
+
+        config.load_secrets(|profile_name| {
+            keychain
+                .borrow()
+                .get(profile_name)
+                .copied()
+                .ok_or_else(|| anyhow!("no entry"))
+        });
+        assert!(secret(&mut config, "static") == Some(static_secret));
+        assert!(secret(&mut config, "oauth2") == Some(oauth2_secret));
+        assert!(secret(&mut config, "file") == Some(file_secret));
+    }
+
+    #[test]
+    fn keychain_secrets_fallbacks() {
+        let fallback_secret = Uuid::new_v4();
+
+        let mut config = Config::default();
+        config.profiles.insert(
+            "static".into(),
+            secret_profile(
+                AuthenticationConfig::static_token(Uuid::nil()),
+                SecretStorage::Keychain,
+            ),
+        );
+        config.profiles.insert(
+            "oauth2".into(),
+            secret_profile(
+                oauth2(AuthSecret::stored_externally()),
+                SecretStorage::Keychain,
+            ),
+        );
+        config.profiles.insert(
+            "fallback".into(),
+            secret_profile(
+                AuthenticationConfig::static_token(fallback_secret),
+                SecretStorage::Keychain,
+            ),
+        );
+
+        // Secrets falling back to the config file are kept there
+        config.move_secrets(|_, _| Err(anyhow!("keychain is not available")));
+        assert!(secret(&mut config, "fallback") == Some(fallback_secret));
+
+        // Secrets that cannot be loaded keep the placeholder, and OAuth2 profiles need a new
+        // login, while secrets in the config file are not looked up
+        config.load_secrets(|profile_name| {
+            assert!(profile_name.0 != "fallback");
+            Err(anyhow!("keychain is not available"))
+        });
+        assert!(secret(&mut config, "static") == Some(Uuid::nil()));
+        assert!(secret(&mut config, "fallback") == Some(fallback_secret));
+        let_assert!(
+            AuthenticationConfig::OAuth2(oauth2) = &config
+                .profiles
+                .get(&ProfileName::from("oauth2"))
+                .unwrap()
+                .auth
+        );
+        assert!(oauth2.data.is_none());
+    }
+}
@@ -13,22 +13,41 @@
 // limitations under the License.
 
 use crate::app::context::ApplicationContext;
-use crate::auth::{Auth, Authentication};
-use crate::command::shared_args::UpdateOrRedeployArgs;
+use crate::auth::{Auth, Authentication, DEFAULT_TOKEN_EXPIRY_WARNING_DAYS};
+use crate::cancellation::Cancellation;
+use crate::command::shared_args::{ProfilesArg, UpdateOrRedeployArgs};
 use crate::command::GolemCliGlobalFlags;
+use crate::command_handler::component::upload::DEFAULT_UPLOAD_RETRIES;
 use crate::command_handler::interactive::InteractiveHandler;
-use crate::config::AuthenticationConfig;
-use crate::config::{ClientConfig, Config, HttpClientConfig, NamedProfile, Profile, ProfileName};
+use crate::command_name;
+use crate::config::{AuthenticationConfig, OAuth2AuthenticationConfig};
+use crate::config::{ClientConfig, Config, NamedProfile, Profile, ProfileContext, ProfileName};
 use crate::error::{ContextInitHintError, HintError, NonSuccessfulExit};
+use crate::http_cache::{HttpCache, HTTP_CACHE_DIR};
+use crate::http_cassette::HttpCassette;
+use crate::http_debug::HttpDebug;
+use crate::http_pool::{
+    new_reqwest_client_with_headers, HttpClientPool, RequestExecutor, DEFAULT_HTTP_CONCURRENCY,
+};
+use crate::http_proxy::{HttpProxy, RetryPolicy};
+use crate::http_rate_limit::RateLimiter;
+use crate::http_transport::resolve_service_transports;
 use crate::log::{log_action, set_log_output, LogColorize, LogOutput, Output};
 use crate::model::app::{AppBuildStep, ApplicationSourceMode};
 use crate::model::app::{ApplicationConfig, BuildProfileName as AppBuildProfileName};
-use crate::model::text::fmt::log_error;
+use crate::model::text::fmt::{log_error, log_warn};
 use crate::model::{app_raw, Format, ProjectReference};
 use crate::model::{AccountDetails, AccountId, PluginReference};
+use crate::poll::Poller;
+use crate::server_version::{
+    parse_version, server_version_compatibility, supported_server_versions, ServerFeature,
+    ServerVersionCache, ServerVersionCompatibility, SERVER_VERSION_CACHE_DIR,
+};
 use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
 use anyhow::{anyhow, bail, Context as AnyhowContext};
+use chrono::Utc;
 use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
 use golem_client::api::ApiCertificateClientLive as ApiCertificateClientCloud;
 use golem_client::api::ApiDefinitionClientLive as ApiDefinitionClientCloud;
 use golem_client::api::ApiDeploymentClientLive as ApiDeploymentClientCloud;
@@ -47,16 +66,22 @@ use golem_client::api::TokenClientLive as TokenClientCloud;
 use golem_client::api::WorkerClientLive as WorkerClientCloud;
 use golem_client::api::{AccountClient, AccountSummaryClientLive as AccountSummaryClientCloud};
 use golem_client::api::{AccountClientLive as AccountClientCloud, LoginClientLive};
+use golem_client::model::Token;
 use golem_client::{Context as ContextCloud, Security};
 use golem_rib_repl::ReplComponentDependencies;
 use golem_templates::model::{ComposableAppGroupName, GuestLanguage};
 use golem_templates::ComposableAppTemplate;
+use itertools::Itertools;
+use reqwest::header::HeaderMap;
+use semver::Version;
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
-use tracing::debug;
+use std::time::Duration;
+use tracing::{debug, info_span, Instrument};
 use url::Url;
 use uuid::Uuid;
 
@@ -64,6 +89,7 @@ use uuid::Uuid;
 // but NOT responsible for producing CLI output, those should be part of the CommandHandler(s)
 pub struct Context {
     // Readonly
+    global_flags: GolemCliGlobalFlags,
     config_dir: PathBuf,
     format: Format,
     local_server_auto_start: bool,
@@ -73,7 +99,11 @@ pub struct Context {
     available_profile_names: BTreeSet<ProfileName>,
     app_context_config: ApplicationContextConfig,
     http_batch_size: u64,
+    request_executor: RequestExecutor,
+    cancellation: Cancellation,
+    poller: Poller,
     auth_token_override: Option<Uuid>,
+    token_expiry_warning_days: u64,
     project: Option<ProjectReference>,
     client_config: ClientConfig,
     yes: bool,
@@ -82,9 +112,14 @@ pub struct Context {
     start_local_server: Box<dyn Fn() -> BoxFuture<'static, anyhow::Result<()>> + Send + Sync>,
 
     file_download_client: reqwest::Client,
+    http_proxy: Arc<HttpProxy>,
+    upload_retry_policy: RetryPolicy,
+    http_client_pool: Arc<HttpClientPool>,
+    server_version_cache: ServerVersionCache,
 
     // Lazy initialized
     golem_clients: tokio::sync::OnceCell<GolemClients>,
+    server_version: std::sync::OnceLock<Option<Version>>,
     templates: std::sync::OnceLock<
         BTreeMap<GuestLanguage, BTreeMap<ComposableAppGroupName, ComposableAppTemplate>>,
     >,
@@ -101,12 +136,47 @@ impl Context {
         start_local_server_yes: Arc<tokio::sync::RwLock<bool>>,
         start_local_server: Box<dyn Fn() -> BoxFuture<'static, anyhow::Result<()>> + Send + Sync>,
     ) -> anyhow::Result<Self> {
+        Self::new_with_http_client_pool(
+            global_flags,
+            log_output_for_help,
+            start_local_server_yes,
+            start_local_server,
+            Arc::new(HttpClientPool::default()),
+        )
+        .await
+    }
+
+    async fn new_with_http_client_pool(
+        global_flags: GolemCliGlobalFlags,
+        log_output_for_help: Option<Output>,
+        start_local_server_yes: Arc<tokio::sync::RwLock<bool>>,
+        start_local_server: Box<dyn Fn() -> BoxFuture<'static, anyhow::Result<()>> + Send + Sync>,
+        http_client_pool: Arc<HttpClientPool>,
+    ) -> anyhow::Result<Self> {
+        let original_global_flags = global_flags.clone();
         let format = global_flags.format;
         let http_batch_size = global_flags.http_batch_size;
+        let http_concurrency = global_flags.http_concurrency;
         let auth_token = global_flags.auth_token;
+        let token_expiry_warning_days = global_flags
+            .token_expiry_warning_days
+            .unwrap_or(DEFAULT_TOKEN_EXPIRY_WARNING_DAYS);
         let config_dir = global_flags.config_dir();
         let local_server_auto_start = global_flags.local_server_auto_start;
         let show_sensitive = global_flags.show_sensitive;
+        let debug_http = global_flags.debug_http;
+        let debug_http_body_file = global_flags.debug_http_body_file.clone();
+        let http_timeout = global_flags.http_timeout;
+        let http_connect_timeout = global_flags.http_connect_timeout;
+        let http_retries = global_flags.http_retries;
+        let http_retry_backoff = global_flags.http_retry_backoff;
+        let http_cache = global_flags.http_cache;
+        let http_record = global_flags.http_record.clone();
+        let http_replay = global_flags.http_replay.clone();
+        let max_rps = global_flags.max_rps;
+        let wait_timeout = global_flags.wait_timeout;
+        let base_url = global_flags.base_url.clone();
+        let project_override = global_flags.project.clone();
 
         let mut yes = global_flags.yes;
         let mut update_or_redeploy = UpdateOrRedeployArgs::none();
@@ -127,7 +197,7 @@ impl Context {
         let app_source_mode = preloaded_app.source_mode;
         let manifest_profiles = preloaded_app.profiles.unwrap_or_default();
 
-        let (available_profile_names, profile, manifest_profile) = load_merged_profiles(
+        let (available_profile_names, mut profile, manifest_profile) = load_merged_profiles(
             &config_dir,
             app_context_config.requested_profile_name.as_ref(),
             manifest_profiles,
@@ -159,6 +229,16 @@ impl Context {
             if manifest_profile.redeploy_all == Some(true) {
                 update_or_redeploy.redeploy_all = true;
             }
+
+            if manifest_profile.build_cache.is_some() {
+                app_context_config.build_cache = manifest_profile.build_cache.clone();
+            }
+
+            update_or_redeploy.http_api_vars = manifest_profile
+                .http_api_vars
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
         }
 
         let project = match manifest_profile.as_ref().and_then(|m| m.project.as_ref()) {
@@ -172,10 +252,31 @@ impl Context {
                         )
                     })?,
             ),
-            None => None,
+            None => match &profile.profile.config.default_project {
+                Some(project) => Some(
+                    ProjectReference::from_str(project.as_str())
+                        .map_err(|err| anyhow!("{}", err))
+                        .with_context(|| {
+                            anyhow!(
+                                "Failed to parse default project for profile {}",
+                                profile.name.0.log_color_highlight()
+                            )
+                        })?,
+                ),
+                None => None,
+            },
         };
+        let project = project_override.or(project);
+
+        if let Some(base_url) = &base_url {
+            profile.profile.custom_url = Some(base_url.clone());
+            profile.profile.custom_cloud_url = None;
+            profile.profile.custom_worker_url = None;
+        }
 
-        let format = format.unwrap_or(profile.profile.config.default_format);
+        let format = format
+            .or(profile.profile.config.default_format)
+            .unwrap_or_default();
 
         let log_output = log_output_for_help.unwrap_or(match format {
             Format::Json => Output::Stderr,
@@ -188,7 +289,7 @@ impl Context {
         log_action(
             "Selected",
             format!(
-                "profile: {}{}",
+                "profile: {}{}{}",
                 profile.name.0.log_color_highlight(),
                 project
                     .as_ref()
@@ -196,15 +297,72 @@ impl Context {
                         ", project: {}",
                         project.to_string().log_color_highlight()
                     ))
+                    .unwrap_or_else(|| "".to_string()),
+                base_url
+                    .as_ref()
+                    .map(|base_url| format!(
+                        ", base URL: {}",
+                        base_url.to_string().log_color_highlight()
+                    ))
                     .unwrap_or_else(|| "".to_string())
             ),
         );
 
-        let client_config = ClientConfig::from(&profile.profile);
+        let http_config = &profile.profile.config.http;
+        let flag_or_profile_duration = |duration: Option<chrono::Duration>, ms: Option<u64>| {
+            duration
+                .and_then(|duration| duration.to_std().ok())
+                .or(ms.map(Duration::from_millis))
+        };
+
+        let client_config = ClientConfig::from(&profile.profile).with_http_overrides(
+            flag_or_profile_duration(http_timeout, http_config.timeout_ms),
+            flag_or_profile_duration(http_connect_timeout, http_config.connect_timeout_ms),
+        );
+        let client_config = resolve_service_transports(client_config)?;
         let file_download_client =
-            new_reqwest_client(&client_config.file_download_http_client_config)?;
+            http_client_pool.client(&client_config.file_download_http_client_config)?;
+
+        let http_debug = (debug_http
+            || debug_http_body_file.is_some()
+            || profile.profile.config.debug_http == Some(true))
+        .then(|| HttpDebug::new(show_sensitive, debug_http_body_file.as_deref()))
+        .transpose()?;
+        let http_retries = http_retries.or(http_config.retries);
+        let retry_policy = RetryPolicy {
+            max_retries: http_retries.unwrap_or(0),
+            backoff: flag_or_profile_duration(http_retry_backoff, http_config.retry_backoff_ms)
+                .unwrap_or(RetryPolicy::DEFAULT_BACKOFF),
+        };
+        // Component uploads are checked for being committed before retrying, so they are
+        // retried by default
+        let upload_retry_policy = RetryPolicy {
+            max_retries: http_retries.unwrap_or(DEFAULT_UPLOAD_RETRIES),
+            ..retry_policy
+        };
+        let cancellation = Cancellation::new();
+        let http_cassette = match (&http_replay, &http_record) {
+            (Some(path), _) => Some(HttpCassette::replay(path)?),
+            (None, Some(path)) => Some(HttpCassette::record(path)),
+            (None, None) => None,
+        };
+        // Cached responses would make the recordings depend on the state of the cache
+        let http_cache = (http_cache && http_cassette.is_none())
+            .then(|| HttpCache::new(config_dir.join(HTTP_CACHE_DIR)));
+        let server_version_cache =
+            ServerVersionCache::new(config_dir.join(SERVER_VERSION_CACHE_DIR));
+        // The proxy is only used when an opt-in request level feature is enabled, otherwise the
+        // clients connect to the services directly
+        let http_proxy = Arc::new(HttpProxy::new(
+            http_debug,
+            retry_policy,
+            http_cache,
+            RateLimiter::new(max_rps.map(NonZeroU32::get)),
+            http_cassette,
+        ));
 
         Ok(Self {
+            global_flags: original_global_flags,
             config_dir,
             format,
             local_server_auto_start,
@@ -214,13 +372,25 @@ impl Context {
             available_profile_names,
             app_context_config,
             http_batch_size: http_batch_size.unwrap_or(50),
+            request_executor: RequestExecutor::new(
+                http_concurrency.unwrap_or(DEFAULT_HTTP_CONCURRENCY),
+                cancellation.clone(),
+            ),
+            cancellation,
+            poller: Poller::new(wait_timeout.and_then(|timeout| timeout.to_std().ok())),
             auth_token_override: auth_token,
+            token_expiry_warning_days,
             project,
             yes,
             show_sensitive,
             start_local_server,
             client_config,
+            http_proxy,
+            upload_retry_policy,
+            http_client_pool,
+            server_version_cache,
             golem_clients: tokio::sync::OnceCell::new(),
+            server_version: std::sync::OnceLock::new(),
             file_download_client,
             templates: std::sync::OnceLock::new(),
             app_context_state: tokio::sync::RwLock::new(ApplicationContextState::new(
@@ -264,6 +434,15 @@ impl Context {
         &self.update_or_redeploy
     }
 
+    /// Template variables passed as arguments
+    pub fn template_vars(&self) -> &BTreeMap<String, String> {
+        &self.app_context_config.template_vars
+    }
+
+    pub fn app_source_mode(&self) -> ApplicationSourceMode {
+        self.app_context_config.app_source_mode()
+    }
+
     pub async fn silence_app_context_init(&self) {
         let mut state = self.app_context_state.write().await;
         state.silent_init = true;
@@ -285,31 +464,282 @@ impl Context {
         self.project.as_ref()
     }
 
+    /// Creates a separate context for each requested profile, used for running read-only
+    /// commands against multiple profiles. Local server auto start is not supported for these.
+    pub async fn profile_contexts(
+        &self,
+        profiles: &ProfilesArg,
+    ) -> anyhow::Result<Vec<Arc<Context>>> {
+        let profile_names = if profiles.all_profiles {
+            self.available_profile_names.iter().cloned().collect()
+        } else {
+            profiles
+                .profiles
+                .iter()
+                .cloned()
+                .unique()
+                .collect::<Vec<_>>()
+        };
+
+        let mut contexts = Vec::with_capacity(profile_names.len());
+        for profile_name in profile_names {
+            let mut global_flags = self.global_flags.clone();
+            global_flags.profile = Some(profile_name);
+            global_flags.local = false;
+            global_flags.cloud = false;
+            global_flags.local_server_auto_start = false;
+
+            contexts.push(Arc::new(
+                Context::new_with_http_client_pool(
+                    global_flags,
+                    None,
+                    Arc::new(tokio::sync::RwLock::new(self.yes)),
+                    Box::new(|| async { Ok(()) }.boxed()),
+                    self.http_client_pool.clone(),
+                )
+                .await?,
+            ));
+        }
+
+        Ok(contexts)
+    }
+
+    pub fn profile_context(&self) -> &ProfileContext {
+        &self.profile.config.context
+    }
+
     pub fn http_batch_size(&self) -> u64 {
         self.http_batch_size
     }
 
+    /// Executor for the requests of bulk operations, limited by GOLEM_HTTP_CONCURRENCY
+    pub fn request_executor(&self) -> RequestExecutor {
+        self.request_executor.clone()
+    }
+
+    /// Cancellation of bulk operations on Ctrl-C
+    pub fn cancellation(&self) -> &Cancellation {
+        &self.cancellation
+    }
+
+    /// Poller of asynchronous operations, limited by --wait-timeout
+    pub fn poller(&self) -> Poller {
+        self.poller
+    }
+
+    pub fn upload_retry_policy(&self) -> RetryPolicy {
+        self.upload_retry_policy
+    }
+
+    pub fn log_http_cache_stats(&self) {
+        self.http_proxy.log_cache_stats();
+    }
+
     pub async fn golem_clients(&self) -> anyhow::Result<&GolemClients> {
         self.golem_clients
             .get_or_try_init(|| async {
+                let renewed_auth = self.renew_expired_oauth2_token().await?;
+
+                let client_config = if self.http_proxy.is_needed() {
+                    self.http_proxy
+                        .proxy_client_config(self.client_config.clone())
+                        .await?
+                } else {
+                    self.client_config.clone()
+                };
+
                 let clients = GolemClients::new(
-                    self.client_config.clone(),
+                    client_config,
                     self.auth_token_override,
                     &self.profile_name,
-                    &self.profile.auth,
+                    renewed_auth.as_ref().unwrap_or(&self.profile.auth),
                     self.config_dir(),
+                    &self.http_client_pool,
                 )
+                .instrument(info_span!("auth"))
                 .await?;
 
+                self.warn_if_token_expires_soon(clients.auth_token_details());
+
                 if self.local_server_auto_start {
                     self.start_local_server_if_needed(&clients).await?;
                 }
 
+                self.check_server_version(&clients).await;
+
                 Ok(clients)
             })
             .await
     }
 
+    /// Reads the server version on the first contact with the services of the profile, and
+    /// warns if it is not supported. Failing to get the version is not an error, as the
+    /// version is only used for warnings and feature checks.
+    async fn check_server_version(&self, clients: &GolemClients) {
+        let url = &self.client_config.component_url;
+        let version = match self.server_version_cache.get(&self.profile_name, url) {
+            Some(version) => Some(version),
+            None => {
+                // NOTE: explicitly calling the trait method to avoid unused imports when
+                //       compiling with default features
+                match golem_client::api::HealthCheckClient::version(&clients.component_healthcheck)
+                    .await
+                {
+                    Ok(version_info) => {
+                        self.server_version_cache.put(
+                            &self.profile_name,
+                            url,
+                            &version_info.version,
+                        );
+                        self.warn_if_server_version_unsupported(&version_info.version);
+                        Some(version_info.version)
+                    }
+                    Err(err) => {
+                        debug!("Failed to get server version: {}", err);
+                        None
+                    }
+                }
+            }
+        };
+
+        let _ = self
+            .server_version
+            .set(version.as_deref().and_then(parse_version));
+    }
+
+    fn warn_if_server_version_unsupported(&self, server_version: &str) {
+        let (Some(cli_version), Some(parsed_server_version)) = (
+            parse_version(crate::version()),
+            parse_version(server_version),
+        ) else {
+            return;
+        };
+
+        let (min_version, max_version) = supported_server_versions(&cli_version);
+        match server_version_compatibility(&cli_version, &parsed_server_version) {
+            ServerVersionCompatibility::Supported => {}
+            ServerVersionCompatibility::TooOld => {
+                log_warn(format!(
+                    "The server of profile {} has version {}, which is older than the oldest supported version {}. Some commands might fail, upgrade the server or use an older version of {}.",
+                    self.profile_name.0.log_color_highlight(),
+                    server_version.log_color_highlight(),
+                    min_version.to_string().log_color_highlight(),
+                    command_name()
+                ));
+            }
+            ServerVersionCompatibility::TooNew => {
+                log_warn(format!(
+                    "The server of profile {} has version {}, which is newer than the versions supported by {} {} (below {}). Some commands might fail, install a {} version matching the server.",
+                    self.profile_name.0.log_color_highlight(),
+                    server_version.log_color_highlight(),
+                    command_name(),
+                    crate::version().log_color_highlight(),
+                    max_version.to_string().log_color_highlight(),
+                    command_name()
+                ));
+            }
+        }
+    }
+
+    /// The server version of the profile, if it could be determined
+    pub async fn server_version(&self) -> anyhow::Result<Option<&Version>> {
+        self.golem_clients().await?;
+        Ok(self.known_server_version())
+    }
+
+    /// The server version of the profile without contacting the server, available only after
+    /// the clients were initialized
+    pub fn known_server_version(&self) -> Option<&Version> {
+        self.server_version.get().and_then(Option::as_ref)
+    }
+
+    /// Fails with a clear error if the server is known to be older than required by the
+    /// feature, unknown server versions are assumed to support it
+    pub async fn require_server_version(&self, feature: ServerFeature) -> anyhow::Result<()> {
+        let Some(server_version) = self.server_version().await? else {
+            return Ok(());
+        };
+
+        let min_version = feature.min_server_version();
+        if *server_version < min_version {
+            log_error(format!(
+                "{} requires server >= {}, but the server of profile {} has version {}",
+                feature,
+                min_version.to_string().log_color_highlight(),
+                self.profile_name.0.log_color_highlight(),
+                server_version.to_string().log_color_highlight()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        Ok(())
+    }
+
+    /// OAuth2 tokens cannot be refreshed, so for expired ones the device flow is re-run
+    /// when the user confirms it, otherwise the command fails early instead of getting
+    /// unauthorized errors from the services.
+    async fn renew_expired_oauth2_token(&self) -> anyhow::Result<Option<AuthenticationConfig>> {
+        if self.auth_token_override.is_some() {
+            return Ok(None);
+        }
+
+        let AuthenticationConfig::OAuth2(OAuth2AuthenticationConfig { data: Some(data) }) =
+            &self.profile.auth
+        else {
+            return Ok(None);
+        };
+
+        if data.expires_at > Utc::now() {
+            return Ok(None);
+        }
+
+        log_warn(format!(
+            "The token of profile {} has expired at {}",
+            self.profile_name.0.log_color_highlight(),
+            data.expires_at.to_string().log_color_highlight()
+        ));
+
+        if !InteractiveHandler::confirm_login_with_expired_token()? {
+            log_error(format!(
+                "Use '{} login' to log in again, or pass a valid token using '--token' or GOLEM_TOKEN",
+                command_name()
+            ));
+            bail!(NonSuccessfulExit);
+        }
+
+        let authentication = self
+            .auth()?
+            .login(&self.profile_name, &self.config_dir)
+            .await?;
+        log_action(
+            "Renewed",
+            format!(
+                "token of profile {}",
+                self.profile_name.0.log_color_highlight()
+            ),
+        );
+
+        Ok(Some(authentication.to_oauth2_config()))
+    }
+
+    fn warn_if_token_expires_soon(&self, token: &Token) {
+        if self.token_expiry_warning_days == 0 {
+            return;
+        }
+
+        let remaining = token.expires_at - Utc::now();
+        if remaining < chrono::Duration::days(self.token_expiry_warning_days as i64) {
+            log_warn(format!(
+                "The token of profile {} expires in {} day(s), at {}. Use '{} login' or create a new token with '{} cloud token new' to avoid authentication failures.",
+                self.profile_name.0.log_color_highlight(),
+                remaining.num_days().max(0),
+                token.expires_at.to_string().log_color_highlight(),
+                command_name(),
+                command_name()
+            ));
+        }
+    }
+
     #[cfg(feature = "server-commands")]
     async fn start_local_server_if_needed(&self, clients: &GolemClients) -> anyhow::Result<()> {
         if !self.profile_name.is_builtin_local() {
@@ -343,10 +773,55 @@ impl Context {
         &self.client_config.worker_url
     }
 
+    pub fn client_config(&self) -> &ClientConfig {
+        &self.client_config
+    }
+
+    /// Returns false when using the selected profile would require an interactive login
+    pub fn has_auth_credentials(&self) -> bool {
+        self.auth_token_override.is_some()
+            || match &self.profile.auth {
+                AuthenticationConfig::Static(_) => true,
+                AuthenticationConfig::OAuth2(inner) => inner.data.is_some(),
+            }
+    }
+
+    /// Returns true if the token of the selected profile was acquired using OAuth2, and was not overridden
+    pub fn uses_oauth2_token(&self) -> bool {
+        self.auth_token_override.is_none()
+            && matches!(self.profile.auth, AuthenticationConfig::OAuth2(_))
+    }
+
+    /// Unauthenticated health check client for any of the services of the selected profile
+    pub fn health_check_client(&self, base_url: &Url) -> anyhow::Result<HealthCheckClientLive> {
+        Ok(HealthCheckClientLive {
+            context: ContextCloud {
+                client: self
+                    .http_client_pool
+                    .client(&self.client_config.health_check_http_client_config)?,
+                base_url: base_url.clone(),
+                security_token: Security::Empty,
+            },
+        })
+    }
+
     pub fn allow_insecure(&self) -> bool {
         self.client_config.service_http_client_config.allow_insecure
     }
 
+    /// Unauthenticated access to the login API of the selected profile, used for explicit login
+    pub fn auth(&self) -> anyhow::Result<Auth> {
+        Ok(Auth::new(LoginClientLive {
+            context: ContextCloud {
+                client: self
+                    .http_client_pool
+                    .client(&self.client_config.service_http_client_config)?,
+                base_url: self.client_config.cloud_url.clone(),
+                security_token: Security::Empty,
+            },
+        }))
+    }
+
     pub async fn account_id(&self) -> anyhow::Result<AccountId> {
         Ok(self.golem_clients().await?.account_id())
     }
@@ -417,6 +892,46 @@ impl Context {
         .await
     }
 
+    pub async fn set_frozen(&self, frozen: bool) {
+        self.set_app_ctx_init_config(
+            "frozen",
+            |ctx| &mut ctx.frozen,
+            |ctx| &mut ctx.frozen_was_set,
+            frozen,
+        )
+        .await
+    }
+
+    pub async fn set_no_cache(&self, no_cache: bool) {
+        self.set_app_ctx_init_config(
+            "no_cache",
+            |ctx| &mut ctx.no_cache,
+            |ctx| &mut ctx.no_cache_was_set,
+            no_cache,
+        )
+        .await
+    }
+
+    pub async fn set_backup(&self, backup: bool) {
+        self.set_app_ctx_init_config(
+            "backup",
+            |ctx| &mut ctx.backup,
+            |ctx| &mut ctx.backup_was_set,
+            backup,
+        )
+        .await
+    }
+
+    pub async fn set_build_jobs(&self, build_jobs: NonZeroUsize) {
+        self.set_app_ctx_init_config(
+            "build_jobs",
+            |ctx| &mut ctx.build_jobs,
+            |ctx| &mut ctx.build_jobs_was_set,
+            build_jobs,
+        )
+        .await
+    }
+
     pub async fn set_steps_filter(&self, steps_filter: HashSet<AppBuildStep>) {
         self.set_app_ctx_init_config(
             "steps_filter",
@@ -500,6 +1015,8 @@ impl Context {
 
 pub struct GolemClients {
     authentication: Authentication,
+    config: ClientConfig,
+    security_token: Security,
 
     pub account: AccountClientCloud,
     pub account_summary: AccountSummaryClientCloud,
@@ -529,11 +1046,13 @@ impl GolemClients {
         profile_name: &ProfileName,
         auth_config: &AuthenticationConfig,
         config_dir: &Path,
+        http_client_pool: &HttpClientPool,
     ) -> anyhow::Result<Self> {
-        let healthcheck_http_client = new_reqwest_client(&config.health_check_http_client_config)?;
+        let healthcheck_http_client =
+            http_client_pool.client(&config.health_check_http_client_config)?;
 
-        let service_http_client = new_reqwest_client(&config.service_http_client_config)?;
-        let invoke_http_client = new_reqwest_client(&config.invoke_http_client_config)?;
+        let service_http_client = http_client_pool.client(&config.service_http_client_config)?;
+        let invoke_http_client = http_client_pool.client(&config.invoke_http_client_config)?;
 
         let auth = Auth::new(LoginClientLive {
             context: ContextCloud {
@@ -644,6 +1163,29 @@ impl GolemClients {
             worker_invoke: WorkerClientCloud {
                 context: worker_invoke_context(),
             },
+            config,
+            security_token,
+        })
+    }
+
+    /// Worker client which sends the given headers with every request, used for propagating
+    /// the trace context of invocations
+    pub fn worker_with_headers(
+        &self,
+        invoke: bool,
+        headers: HeaderMap,
+    ) -> anyhow::Result<WorkerClientCloud> {
+        let http_client_config = if invoke {
+            &self.config.invoke_http_client_config
+        } else {
+            &self.config.service_http_client_config
+        };
+        Ok(WorkerClientCloud {
+            context: ContextCloud {
+                client: new_reqwest_client_with_headers(http_client_config, headers)?,
+                base_url: self.config.worker_url.clone(),
+                security_token: self.security_token.clone(),
+            },
         })
     }
 
@@ -654,6 +1196,10 @@ impl GolemClients {
     pub fn auth_token(&self) -> String {
         self.authentication.0.secret.value.to_string()
     }
+
+    pub fn auth_token_details(&self) -> &Token {
+        &self.authentication.0.data
+    }
 }
 
 struct ApplicationContextConfig {
@@ -663,6 +1209,9 @@ struct ApplicationContextConfig {
     disable_app_manifest_discovery: bool,
     golem_rust_override: RustDependencyOverride,
     wasm_rpc_client_build_offline: bool,
+    template_vars: BTreeMap<String, String>,
+    build_cache: Option<app_raw::BuildCache>,
+    verbose: bool,
 }
 
 impl ApplicationContextConfig {
@@ -685,6 +1234,9 @@ impl ApplicationContextConfig {
                 version_override: global_flags.golem_rust_version,
             },
             wasm_rpc_client_build_offline: global_flags.wasm_rpc_offline,
+            template_vars: global_flags.template_vars.into_iter().collect(),
+            build_cache: None,
+            verbose: global_flags.verbosity.is_verbose(),
         }
     }
 
@@ -709,6 +1261,14 @@ pub struct ApplicationContextState {
     pub silent_init: bool,
     pub skip_up_to_date_checks: bool,
     skip_up_to_date_checks_was_set: bool,
+    pub frozen: bool,
+    frozen_was_set: bool,
+    pub backup: bool,
+    backup_was_set: bool,
+    pub no_cache: bool,
+    no_cache_was_set: bool,
+    pub build_jobs: NonZeroUsize,
+    build_jobs_was_set: bool,
     pub build_steps_filter: HashSet<AppBuildStep>,
     build_steps_filter_was_set: bool,
 
@@ -723,6 +1283,14 @@ impl ApplicationContextState {
             silent_init: false,
             skip_up_to_date_checks: false,
             skip_up_to_date_checks_was_set: false,
+            frozen: false,
+            frozen_was_set: false,
+            backup: false,
+            backup_was_set: false,
+            no_cache: false,
+            no_cache_was_set: false,
+            build_jobs: NonZeroUsize::MIN,
+            build_jobs_was_set: false,
             build_steps_filter: HashSet::new(),
             build_steps_filter_was_set: false,
             app_context: None,
@@ -745,10 +1313,17 @@ impl ApplicationContextState {
 
         let app_config = ApplicationConfig {
             skip_up_to_date_checks: self.skip_up_to_date_checks,
+            frozen: self.frozen,
+            backup: self.backup,
+            build_jobs: self.build_jobs,
             build_profile: config.build_profile.as_ref().map(|p| p.to_string().into()),
             offline: config.wasm_rpc_client_build_offline,
             steps_filter: self.build_steps_filter.clone(),
             golem_rust_override: config.golem_rust_override.clone(),
+            template_vars: config.template_vars.clone(),
+            build_cache: config.build_cache.clone(),
+            no_cache: self.no_cache,
+            verbose: config.verbose,
         };
 
         debug!(app_config = ?app_config, "Initializing application context");
@@ -829,26 +1404,6 @@ impl Default for RibReplState {
     }
 }
 
-fn new_reqwest_client(config: &HttpClientConfig) -> anyhow::Result<reqwest::Client> {
-    let mut builder = reqwest::Client::builder();
-
-    if config.allow_insecure {
-        builder = builder.danger_accept_invalid_certs(true);
-    }
-
-    if let Some(timeout) = config.timeout {
-        builder = builder.timeout(timeout);
-    }
-    if let Some(connect_timeout) = config.connect_timeout {
-        builder = builder.connect_timeout(connect_timeout);
-    }
-    if let Some(read_timeout) = config.read_timeout {
-        builder = builder.read_timeout(read_timeout);
-    }
-
-    Ok(builder.connection_verbose(true).build()?)
-}
-
 /// Finds the requested or the default profile in the global CLI config
 /// and in the application manifest. The global config gets overrides applied from
 /// the manifest profile.
@@ -867,7 +1422,7 @@ fn load_merged_profiles(
 )> {
     let mut available_profile_names = BTreeSet::new();
 
-    let mut config = Config::from_dir(config_dir)?;
+    let config = Config::from_dir(config_dir)?;
     available_profile_names.extend(config.profiles.keys().cloned());
     available_profile_names.extend(manifest_profiles.keys().cloned());
 
@@ -884,12 +1439,8 @@ fn load_merged_profiles(
 
     let global_profile = match profile_name {
         Some(profile_name) => config
-            .profiles
-            .remove(profile_name)
-            .map(|profile| NamedProfile {
-                name: profile_name.clone(),
-                profile,
-            }),
+            .resolve_profile(profile_name)?
+            .map(|(profile, _)| profile),
         None => Some(Config::get_default_profile(config_dir)?),
     };
 
@@ -911,7 +1462,7 @@ fn load_merged_profiles(
                 let profile = &mut profile.profile;
 
                 if let Some(format) = &manifest_profile.format {
-                    profile.config.default_format = *format;
+                    profile.config.default_format = Some(*format);
                 }
 
                 // TODO: should we allow these? or show only warn logs?
@@ -941,7 +1492,7 @@ fn load_merged_profiles(
                 let mut profile = Profile::default_local_profile();
 
                 if let Some(format) = &manifest_profile.format {
-                    profile.config.default_format = *format
+                    profile.config.default_format = Some(*format)
                 }
 
                 if let Some(url) = &manifest_profile.url {
@@ -18,6 +18,20 @@ impl Display for NonSuccessfulExit {
 
 impl Error for NonSuccessfulExit {}
 
+// Same as NonSuccessfulExit, but with a specific exit code, for commands where the exit code
+// signals the kind of the failure (e.g. app ci).
+#[derive(Debug)]
+pub struct NonSuccessfulExitWithCode(pub u8);
+
+impl Display for NonSuccessfulExitWithCode {
+    fn fmt(&self, _f: &mut Formatter<'_>) -> std::fmt::Result {
+        //NOP
+        Ok(())
+    }
+}
+
+impl Error for NonSuccessfulExitWithCode {}
+
 #[derive(Clone, Copy, Debug, Display, EnumIter)]
 pub enum ShowClapHelpTarget {
     AppNew,
@@ -164,6 +178,43 @@ pub mod service {
         }
     }
 
+    impl ServiceError {
+        pub fn is_unauthorized(&self) -> bool {
+            match &self.kind {
+                ServiceErrorKind::ErrorResponse(response) => response.status_code == 401,
+                ServiceErrorKind::UnexpectedResponse { status_code, .. } => *status_code == 401,
+                _ => false,
+            }
+        }
+
+        /// Responses which could not be deserialized, usually caused by a server version
+        /// which is not supported by the CLI
+        pub fn is_deserialization_error(&self) -> bool {
+            matches!(self.kind, ServiceErrorKind::SerdeError(_))
+        }
+
+        /// Connection failures, timeouts and overloaded or unavailable services
+        pub fn is_transient(&self) -> bool {
+            let is_transient_status =
+                |status_code: u16| matches!(status_code, 429 | 502 | 503 | 504);
+            match &self.kind {
+                ServiceErrorKind::ErrorResponse(response) => {
+                    is_transient_status(response.status_code)
+                }
+                ServiceErrorKind::ReqwestError(error) => {
+                    error.is_connect()
+                        || error.is_timeout()
+                        || error.is_request()
+                        || error.is_body()
+                }
+                ServiceErrorKind::UnexpectedResponse { status_code, .. } => {
+                    is_transient_status(*status_code)
+                }
+                _ => false,
+            }
+        }
+    }
+
     impl Error for ServiceError {}
 
     impl<T> From<golem_client::Error<T>> for ServiceError
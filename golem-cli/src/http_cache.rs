@@ -0,0 +1,190 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local cache of service responses, revalidated with ETags.
+//!
+//! Only the responses of metadata lookups are cached (components and API definitions), which
+//! are repeated by many commands, e.g. every invocation looks up the component metadata for
+//! the function signatures. Cached responses are always revalidated with If-None-Match, so
+//! stale metadata is never used, but identical metadata is not downloaded again.
+//!
+//! The cache is opt-in (--http-cache), as it is served by the local proxy.
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, info};
+
+pub const HTTP_CACHE_DIR: &str = "http-cache";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpCacheEntry {
+    pub etag: String,
+    pub content_type: Option<String>,
+    /// Base64 encoded response body
+    pub body: String,
+}
+
+impl HttpCacheEntry {
+    pub fn body(&self) -> Option<Vec<u8>> {
+        BASE64_STANDARD.decode(&self.body).ok()
+    }
+}
+
+pub struct HttpCache {
+    dir: PathBuf,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    stored: AtomicU64,
+}
+
+impl HttpCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            stored: AtomicU64::new(0),
+        }
+    }
+
+    /// Component and API definition lookups, excluding WASM downloads, worker operations and
+    /// invocations
+    pub fn is_cacheable_path(path: &str) -> bool {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        matches!(
+            segments.as_slice(),
+            ["v1", "components"]
+                | ["v1", "components", _]
+                | ["v1", "components", _, "latest"]
+                | ["v1", "components", _, "versions"]
+                | ["v1", "components", _, "versions", _]
+                | ["v1", "api", "definitions", ..]
+        )
+    }
+
+    /// Responses depend on the caller, so the credentials are part of the key
+    pub fn key(url: &str, authorization: Option<&[u8]>) -> String {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(url.as_bytes());
+        hasher.update(b"\n");
+        hasher.update(authorization.unwrap_or_default());
+        hasher.finalize().to_hex().to_string()
+    }
+
+    pub fn get(&self, key: &str) -> Option<HttpCacheEntry> {
+        let path = self.dir.join(key);
+        let content = std::fs::read(&path).ok()?;
+        match serde_json::from_slice(&content) {
+            Ok(entry) => Some(entry),
+            Err(err) => {
+                debug!(
+                    "Ignoring invalid HTTP cache entry {}: {}",
+                    path.display(),
+                    err
+                );
+                None
+            }
+        }
+    }
+
+    /// Failing to write the cache is not an error, the response is simply not cached
+    pub fn put(&self, key: &str, etag: &str, content_type: Option<&str>, body: &[u8]) {
+        let entry = HttpCacheEntry {
+            etag: etag.to_string(),
+            content_type: content_type.map(|content_type| content_type.to_string()),
+            body: BASE64_STANDARD.encode(body),
+        };
+
+        // Entries are written to a temporary file first, so concurrent readers never see
+        // partial entries
+        let path = self.dir.join(key);
+        let temp_path = self.dir.join(format!("{key}.{}.tmp", std::process::id()));
+        let result = std::fs::create_dir_all(&self.dir)
+            .and_then(|()| std::fs::write(&temp_path, serde_json::to_vec(&entry)?))
+            .and_then(|()| std::fs::rename(&temp_path, &path));
+
+        match result {
+            Ok(()) => {
+                self.stored.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(err) => {
+                let _ = std::fs::remove_file(&temp_path);
+                debug!(
+                    "Failed to write HTTP cache entry {}: {}",
+                    path.display(),
+                    err
+                );
+            }
+        }
+    }
+
+    pub fn record_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn log_stats(&self) {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        if hits + misses > 0 {
+            info!(
+                hits,
+                misses,
+                stored = self.stored.load(Ordering::Relaxed),
+                dir = %self.dir.display(),
+                "HTTP cache statistics"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http_cache::HttpCache;
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn cacheable_paths() {
+        assert!(HttpCache::is_cacheable_path("/v1/components"));
+        assert!(HttpCache::is_cacheable_path("/v1/components/"));
+        assert!(HttpCache::is_cacheable_path("/v1/components/id"));
+        assert!(HttpCache::is_cacheable_path("/v1/components/id/latest"));
+        assert!(HttpCache::is_cacheable_path("/v1/components/id/versions/3"));
+        assert!(HttpCache::is_cacheable_path(
+            "/v1/api/definitions/api/1.0.0"
+        ));
+        assert!(!HttpCache::is_cacheable_path("/v1/components/id/download"));
+        assert!(!HttpCache::is_cacheable_path("/v1/components/id/workers"));
+        assert!(!HttpCache::is_cacheable_path(
+            "/v1/components/id/workers/w1"
+        ));
+        assert!(!HttpCache::is_cacheable_path("/v1/api/deployments"));
+    }
+
+    #[test]
+    fn cache_key_depends_on_credentials() {
+        let url = "http://localhost:9881/v1/components/id/latest";
+        assert!(HttpCache::key(url, Some(b"Bearer a")) == HttpCache::key(url, Some(b"Bearer a")));
+        assert!(HttpCache::key(url, Some(b"Bearer a")) != HttpCache::key(url, Some(b"Bearer b")));
+        assert!(HttpCache::key(url, None) != HttpCache::key(url, Some(b"Bearer a")));
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recording and replaying of service responses ("cassettes"), used by the client proxy.
+//!
+//! Only meant for testing the CLI without a running server, so it can only be enabled with the
+//! GOLEM_HTTP_RECORD and GOLEM_HTTP_REPLAY env vars, both expecting the path of the cassette
+//! file. Recorded requests are matched by method, path and query, in the order of recording.
+//! When all recorded responses of a request are used up, the last one is replayed again, so
+//! polling loops do not depend on the number of polls during recording.
+
+use anyhow::Context;
+use axum::body::Bytes;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::debug;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpCassetteFile {
+    pub interactions: Vec<HttpInteraction>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpInteraction {
+    pub method: String,
+    /// Path and query of the request
+    pub path: String,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// Body of the response, if it is valid UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Base64 encoded body of the response, if it is not valid UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+impl HttpInteraction {
+    fn new(
+        method: &Method,
+        path: &str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Self {
+        let (body, body_base64) = match std::str::from_utf8(body) {
+            Ok(body) => (Some(body.to_string()), None),
+            Err(_) => (None, Some(BASE64_STANDARD.encode(body))),
+        };
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    value
+                        .to_str()
+                        .ok()
+                        .map(|value| (name.to_string(), value.to_string()))
+                })
+                .collect(),
+            body,
+            body_base64,
+        }
+    }
+
+    fn key(&self) -> String {
+        interaction_key(&self.method, &self.path)
+    }
+
+    pub fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    pub fn headers(&self) -> HeaderMap {
+        self.headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::try_from(name.as_str()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect()
+    }
+
+    pub fn body(&self) -> Bytes {
+        match (&self.body, &self.body_base64) {
+            (Some(body), _) => Bytes::from(body.clone()),
+            (None, Some(body)) => Bytes::from(BASE64_STANDARD.decode(body).unwrap_or_default()),
+            (None, None) => Bytes::new(),
+        }
+    }
+}
+
+fn interaction_key(method: &str, path: &str) -> String {
+    format!("{method} {path}")
+}
+
+enum HttpCassetteMode {
+    Record(Mutex<HttpCassetteFile>),
+    Replay(Mutex<HashMap<String, Vec<HttpInteraction>>>),
+}
+
+pub struct HttpCassette {
+    path: PathBuf,
+    mode: HttpCassetteMode,
+}
+
+impl HttpCassette {
+    /// Starts a new cassette, the file is written after every recorded interaction
+    pub fn record(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            mode: HttpCassetteMode::Record(Mutex::new(HttpCassetteFile::default())),
+        }
+    }
+
+    pub fn replay(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read(path)
+            .with_context(|| format!("Failed to read HTTP cassette: {}", path.display()))?;
+        let file: HttpCassetteFile = serde_json::from_slice(&content)
+            .with_context(|| format!("Failed to parse HTTP cassette: {}", path.display()))?;
+
+        let mut interactions = HashMap::<String, Vec<HttpInteraction>>::new();
+        // Stored in reverse, so the next interaction can be popped
+        for interaction in file.interactions.into_iter().rev() {
+            interactions
+                .entry(interaction.key())
+                .or_default()
+                .push(interaction);
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mode: HttpCassetteMode::Replay(Mutex::new(interactions)),
+        })
+    }
+
+    pub fn is_replay(&self) -> bool {
+        matches!(self.mode, HttpCassetteMode::Replay(_))
+    }
+
+    /// Returns the next recorded response of the request, the last one is kept for repeated
+    /// requests
+    pub fn next(&self, method: &Method, path: &str) -> Option<HttpInteraction> {
+        let HttpCassetteMode::Replay(interactions) = &self.mode else {
+            return None;
+        };
+        let mut interactions = interactions.lock().unwrap();
+        let interactions = interactions.get_mut(&interaction_key(method.as_str(), path))?;
+        if interactions.len() > 1 {
+            interactions.pop()
+        } else {
+            interactions.last().cloned()
+        }
+    }
+
+    /// Failing to write the cassette is not an error, as it does not affect the command
+    pub fn record_interaction(
+        &self,
+        method: &Method,
+        path: &str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) {
+        let HttpCassetteMode::Record(file) = &self.mode else {
+            return;
+        };
+        let mut file = file.lock().unwrap();
+        file.interactions
+            .push(HttpInteraction::new(method, path, status, headers, body));
+
+        let result = self
+            .path
+            .parent()
+            .map(std::fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .and_then(|()| {
+                std::fs::write(
+                    &self.path,
+                    serde_json::to_vec_pretty(&*file).map_err(std::io::Error::other)?,
+                )
+            });
+        if let Err(err) = result {
+            debug!(
+                "Failed to write HTTP cassette {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http_cassette::HttpCassette;
+    use assert2::{assert, let_assert};
+    use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+    use test_r::test;
+
+    #[test]
+    fn record_and_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cassette.json");
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        let recorder = HttpCassette::record(&path);
+        recorder.record_interaction(
+            &Method::GET,
+            "/v1/components?name=a",
+            StatusCode::OK,
+            &headers,
+            b"[]",
+        );
+        recorder.record_interaction(
+            &Method::GET,
+            "/v1/components?name=a",
+            StatusCode::OK,
+            &headers,
+            br#"[{"name":"a"}]"#,
+        );
+        recorder.record_interaction(
+            &Method::POST,
+            "/v1/components",
+            StatusCode::OK,
+            &HeaderMap::new(),
+            &[0xff, 0x00],
+        );
+
+        let_assert!(Ok(player) = HttpCassette::replay(&path));
+        assert!(player.is_replay());
+
+        let_assert!(Some(first) = player.next(&Method::GET, "/v1/components?name=a"));
+        assert!(first.body() == "[]");
+        assert!(first.headers().get("content-type").unwrap() == "application/json");
+        let_assert!(Some(second) = player.next(&Method::GET, "/v1/components?name=a"));
+        assert!(second.body() == r#"[{"name":"a"}]"#);
+        let_assert!(Some(repeated) = player.next(&Method::GET, "/v1/components?name=a"));
+        assert!(repeated.body() == r#"[{"name":"a"}]"#);
+
+        let_assert!(Some(binary) = player.next(&Method::POST, "/v1/components"));
+        assert!(binary.status() == StatusCode::OK);
+        assert!(binary.body().to_vec() == vec![0xff, 0x00]);
+
+        assert!(player.next(&Method::DELETE, "/v1/components").is_none());
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! HTTP request and response debug logging, used by the client proxy.
+
+use anyhow::Context;
+use axum::body::Bytes;
+use axum::http::{HeaderMap, Method, StatusCode};
+use colored::Colorize;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+const SENSITIVE_HEADER_PARTS: &[&str] = &["token", "secret", "api-key", "password"];
+
+pub struct HttpDebug {
+    show_sensitive: bool,
+    body_file: Option<Mutex<File>>,
+}
+
+impl HttpDebug {
+    pub fn new(show_sensitive: bool, body_file: Option<&Path>) -> anyhow::Result<Self> {
+        let body_file = match body_file {
+            Some(path) => Some(Mutex::new(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| {
+                        format!("Failed to open HTTP debug body file: {}", path.display())
+                    })?,
+            )),
+            None => None,
+        };
+
+        Ok(Self {
+            show_sensitive,
+            body_file,
+        })
+    }
+
+    pub fn log_request(
+        &self,
+        id: u64,
+        method: &Method,
+        url: &str,
+        headers: &HeaderMap,
+        body: &Bytes,
+    ) {
+        eprintln!(
+            "{} {} {}",
+            format!("> #{id}").cyan().bold(),
+            method.as_str().bold(),
+            url
+        );
+        self.log_headers(">", headers);
+        self.write_body(&format!("> #{id} {method} {url}"), body);
+    }
+
+    pub fn log_response(
+        &self,
+        id: u64,
+        status: StatusCode,
+        headers: &HeaderMap,
+        elapsed: Duration,
+        body: &Bytes,
+    ) {
+        let status_line = status.to_string();
+        eprintln!(
+            "{} {} {}",
+            format!("< #{id}").cyan().bold(),
+            if status.is_success() {
+                status_line.green()
+            } else if status.is_redirection() {
+                status_line.yellow()
+            } else {
+                status_line.red()
+            },
+            format!("({} ms)", elapsed.as_millis()).dimmed()
+        );
+        self.log_headers("<", headers);
+        self.write_body(&format!("< #{id} {status}"), body);
+    }
+
+    pub fn log_error(&self, id: u64, url: &str, elapsed: Duration, error: &str) {
+        eprintln!(
+            "{} {} {}",
+            format!("< #{id}").cyan().bold(),
+            format!("request to {url} failed: {error}").red(),
+            format!("({} ms)", elapsed.as_millis()).dimmed()
+        );
+    }
+
+    fn log_headers(&self, prefix: &str, headers: &HeaderMap) {
+        for (name, value) in headers {
+            let value = value.to_str().unwrap_or("<non-ascii value>");
+            eprintln!(
+                "{}   {}: {}",
+                prefix.cyan(),
+                name.as_str().dimmed(),
+                header_value_for_log(name.as_str(), value, self.show_sensitive)
+            );
+        }
+    }
+
+    fn write_body(&self, title: &str, body: &Bytes) {
+        let Some(body_file) = &self.body_file else {
+            return;
+        };
+
+        let body = match std::str::from_utf8(body) {
+            Ok(body) => body.to_string(),
+            Err(_) => format!("<{} bytes of binary data>", body.len()),
+        };
+        let mut body_file = body_file.lock().unwrap();
+        // Failing to write the debug file should not fail the actual requests
+        let _ = writeln!(body_file, "{title}\n{body}\n");
+    }
+}
+
+/// Returns the header value for logging, with credentials redacted unless sensitive values
+/// are requested to be shown
+pub fn header_value_for_log(name: &str, value: &str, show_sensitive: bool) -> String {
+    let name = name.to_ascii_lowercase();
+    let sensitive = SENSITIVE_HEADERS.contains(&name.as_str())
+        || SENSITIVE_HEADER_PARTS
+            .iter()
+            .any(|part| name.contains(part));
+
+    if !sensitive || show_sensitive {
+        return value.to_string();
+    }
+
+    // Keeping the auth scheme helps debugging auth issues without revealing the credentials
+    match (name.as_str(), value.split_once(' ')) {
+        ("authorization" | "proxy-authorization", Some((scheme, _))) => {
+            format!("{scheme} <redacted>")
+        }
+        _ => "<redacted>".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http_debug::header_value_for_log;
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn header_redaction() {
+        assert!(
+            header_value_for_log("Authorization", "Bearer 1234-abcd", false) == "Bearer <redacted>"
+        );
+        assert!(header_value_for_log("cookie", "session=1234", false) == "<redacted>");
+        assert!(header_value_for_log("X-Api-Key", "1234", false) == "<redacted>");
+        assert!(header_value_for_log("x-golem-token", "1234", false) == "<redacted>");
+        assert!(
+            header_value_for_log("content-type", "application/json", false) == "application/json"
+        );
+        assert!(
+            header_value_for_log("authorization", "Bearer 1234-abcd", true) == "Bearer 1234-abcd"
+        );
+    }
+}
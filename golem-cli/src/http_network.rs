@@ -0,0 +1,327 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Network settings shared by the HTTP and WebSocket clients: custom CA certificates, client
+//! certificates (mTLS) and outbound proxies.
+//!
+//! The HTTP clients use the proxies set by the HTTPS_PROXY, HTTP_PROXY, ALL_PROXY and NO_PROXY
+//! environment variables through reqwest, the same variables are resolved here for the
+//! WebSocket connections, which are tunneled through the proxy with HTTP CONNECT.
+
+use crate::config::ProfileTlsConfig;
+use anyhow::{anyhow, bail, Context};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use native_tls::TlsConnector;
+use std::path::Path;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use url::Url;
+
+const MAX_PROXY_RESPONSE_HEADER_SIZE: usize = 16 * 1024;
+
+/// Adds the configured CA certificates and client identity to the reqwest client
+pub fn configure_reqwest_tls(
+    mut builder: reqwest::ClientBuilder,
+    tls: &ProfileTlsConfig,
+) -> anyhow::Result<reqwest::ClientBuilder> {
+    if let Some(ca_certificate) = &tls.ca_certificate {
+        for pem in read_pem_certificates(ca_certificate)? {
+            builder = builder.add_root_certificate(
+                reqwest::Certificate::from_pem(pem.as_bytes()).with_context(|| {
+                    anyhow!("Invalid CA certificate in {}", ca_certificate.display())
+                })?,
+            );
+        }
+    }
+    if let Some((certificate, key)) = read_client_identity(tls)? {
+        builder = builder.identity(
+            reqwest::Identity::from_pkcs8_pem(&certificate, &key)
+                .context("Invalid client certificate or key")?,
+        );
+    }
+    Ok(builder)
+}
+
+/// TLS connector for the WebSocket connections, returns None if the default connector can be
+/// used
+pub fn native_tls_connector(
+    allow_insecure: bool,
+    tls: &ProfileTlsConfig,
+) -> anyhow::Result<Option<TlsConnector>> {
+    if !allow_insecure && tls.is_empty() {
+        return Ok(None);
+    }
+
+    let mut builder = TlsConnector::builder();
+    if allow_insecure {
+        builder
+            .danger_accept_invalid_certs(true)
+            .danger_accept_invalid_hostnames(true);
+    }
+    if let Some(ca_certificate) = &tls.ca_certificate {
+        for pem in read_pem_certificates(ca_certificate)? {
+            builder.add_root_certificate(
+                native_tls::Certificate::from_pem(pem.as_bytes()).with_context(|| {
+                    anyhow!("Invalid CA certificate in {}", ca_certificate.display())
+                })?,
+            );
+        }
+    }
+    if let Some((certificate, key)) = read_client_identity(tls)? {
+        builder.identity(
+            native_tls::Identity::from_pkcs8(&certificate, &key)
+                .context("Invalid client certificate or key")?,
+        );
+    }
+    Ok(Some(builder.build()?))
+}
+
+fn read_pem_certificates(path: &Path) -> anyhow::Result<Vec<String>> {
+    let pem = std::fs::read_to_string(path)
+        .with_context(|| anyhow!("Failed to read CA certificate: {}", path.display()))?;
+    let certificates = split_pem_certificates(&pem);
+    if certificates.is_empty() {
+        bail!("No PEM certificates found in {}", path.display());
+    }
+    Ok(certificates)
+}
+
+fn read_client_identity(tls: &ProfileTlsConfig) -> anyhow::Result<Option<(Vec<u8>, Vec<u8>)>> {
+    match (&tls.client_certificate, &tls.client_key) {
+        (Some(certificate), Some(key)) => Ok(Some((
+            std::fs::read(certificate).with_context(|| {
+                anyhow!(
+                    "Failed to read client certificate: {}",
+                    certificate.display()
+                )
+            })?,
+            std::fs::read(key)
+                .with_context(|| anyhow!("Failed to read client key: {}", key.display()))?,
+        ))),
+        (None, None) => Ok(None),
+        _ => bail!("The client certificate and the client key have to be configured together"),
+    }
+}
+
+/// Splits a PEM bundle into the individual certificates
+pub fn split_pem_certificates(pem: &str) -> Vec<String> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+
+    let mut certificates = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = rest.find(BEGIN) {
+        let Some(end) = rest[start..].find(END) else {
+            break;
+        };
+        let end = start + end + END.len();
+        certificates.push(format!("{}\n", &rest[start..end]));
+        rest = &rest[end..];
+    }
+    certificates
+}
+
+/// The proxy to use for the URL, based on the proxy environment variables
+pub fn proxy_for_url(url: &Url) -> Option<Url> {
+    proxy_for_url_with_env(url, |name| std::env::var(name).ok())
+}
+
+fn proxy_for_url_with_env(url: &Url, env: impl Fn(&str) -> Option<String>) -> Option<Url> {
+    let env = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|name| env(name).filter(|value| !value.trim().is_empty()))
+    };
+
+    let host = url.host_str()?;
+    if env(&["NO_PROXY", "no_proxy"]).is_some_and(|no_proxy| no_proxy_matches(&no_proxy, host)) {
+        return None;
+    }
+
+    let proxy = if matches!(url.scheme(), "https" | "wss") {
+        env(&["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"])
+    } else {
+        env(&["HTTP_PROXY", "http_proxy", "ALL_PROXY", "all_proxy"])
+    }?;
+    let proxy = proxy.trim();
+
+    if proxy.contains("://") {
+        Url::parse(proxy).ok()
+    } else {
+        Url::parse(&format!("http://{proxy}")).ok()
+    }
+}
+
+/// NO_PROXY is a comma separated list of hosts and domains, where domains also match their
+/// subdomains, and "*" matches every host
+fn no_proxy_matches(no_proxy: &str, host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            let domain = entry.trim_start_matches("*.").trim_start_matches('.');
+            entry == "*"
+                || host.eq_ignore_ascii_case(domain)
+                || host
+                    .to_ascii_lowercase()
+                    .ends_with(&format!(".{}", domain.to_ascii_lowercase()))
+        })
+}
+
+/// Opens a tunnel to the target URL through an HTTP proxy, using HTTP CONNECT
+pub async fn connect_through_proxy(proxy: &Url, target: &Url) -> anyhow::Result<TcpStream> {
+    if proxy.scheme() != "http" {
+        bail!(
+            "Unsupported proxy scheme for WebSocket connections: {}",
+            proxy.scheme()
+        );
+    }
+    let proxy_host = proxy
+        .host_str()
+        .ok_or_else(|| anyhow!("Missing proxy host: {proxy}"))?;
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+    let target_host = target
+        .host_str()
+        .ok_or_else(|| anyhow!("Missing host: {target}"))?;
+    let target_port = target
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("Missing port: {target}"))?;
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .with_context(|| anyhow!("Failed to connect to proxy {proxy_host}:{proxy_port}"))?;
+
+    let mut request = format!(
+        "CONNECT {target_host}:{target_port} HTTP/1.1\r\nHost: {target_host}:{target_port}\r\n"
+    );
+    if !proxy.username().is_empty() {
+        let credentials = format!(
+            "{}:{}",
+            percent_decode(proxy.username()),
+            percent_decode(proxy.password().unwrap_or_default())
+        );
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            BASE64_STANDARD.encode(credentials)
+        ));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // The response is read byte by byte, so nothing is consumed from the tunneled stream
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > MAX_PROXY_RESPONSE_HEADER_SIZE {
+            bail!("Invalid response from proxy {proxy_host}:{proxy_port}");
+        }
+        response.push(
+            stream.read_u8().await.with_context(|| {
+                anyhow!("Proxy {proxy_host}:{proxy_port} closed the connection")
+            })?,
+        );
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(stream),
+        _ => bail!("Proxy {proxy_host}:{proxy_port} refused the tunnel: {status_line}"),
+    }
+}
+
+/// Decodes the percent encoded user info of URLs
+pub(crate) fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let hex = bytes
+            .get(idx + 1..idx + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[idx], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                idx += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http_network::{
+        no_proxy_matches, percent_decode, proxy_for_url_with_env, split_pem_certificates,
+    };
+    use assert2::assert;
+    use std::collections::HashMap;
+    use test_r::test;
+    use url::Url;
+
+    #[test]
+    fn proxy_resolution() {
+        let env = HashMap::from([
+            ("HTTPS_PROXY", "proxy.corp:3128"),
+            ("http_proxy", "http://plain-proxy.corp:8080"),
+            ("NO_PROXY", "localhost, .internal.corp,10.0.0.1"),
+        ]);
+        let proxy = |url: &str| {
+            proxy_for_url_with_env(&Url::parse(url).unwrap(), |name| {
+                env.get(name).map(|value| value.to_string())
+            })
+            .map(|url| url.to_string())
+        };
+
+        assert!(
+            proxy("https://release.api.golem.cloud") == Some("http://proxy.corp:3128/".to_string())
+        );
+        assert!(
+            proxy("wss://release.api.golem.cloud") == Some("http://proxy.corp:3128/".to_string())
+        );
+        assert!(
+            proxy("ws://golem.example.com") == Some("http://plain-proxy.corp:8080/".to_string())
+        );
+        assert!(proxy("http://localhost:9881").is_none());
+        assert!(proxy("https://golem.internal.corp").is_none());
+        assert!(proxy("http://10.0.0.1:9881").is_none());
+
+        assert!(no_proxy_matches("*", "golem.cloud"));
+        assert!(no_proxy_matches("golem.cloud", "api.golem.cloud"));
+        assert!(!no_proxy_matches("golem.cloud", "notgolem.cloud"));
+
+        assert!(percent_decode("user%40corp") == "user@corp");
+        assert!(percent_decode("100%") == "100%");
+    }
+
+    #[test]
+    fn pem_bundle_split() {
+        let bundle = "# comment\n-----BEGIN CERTIFICATE-----\nAAA\n-----END CERTIFICATE-----\n\n-----BEGIN CERTIFICATE-----\nBBB\n-----END CERTIFICATE-----\n";
+        assert!(
+            split_pem_certificates(bundle)
+                == vec![
+                    "-----BEGIN CERTIFICATE-----\nAAA\n-----END CERTIFICATE-----\n".to_string(),
+                    "-----BEGIN CERTIFICATE-----\nBBB\n-----END CERTIFICATE-----\n".to_string(),
+                ]
+        );
+        assert!(split_pem_certificates("no certificates").is_empty());
+    }
+}
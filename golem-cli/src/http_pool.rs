@@ -0,0 +1,285 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shared HTTP clients and bounded request execution.
+//!
+//! reqwest clients own their connection pools, so clients are shared by their config: all
+//! service calls of a command, including the ones made for multiple profiles, reuse the same
+//! connections (and with that the TLS sessions), using HTTP/2 when the server supports it.
+
+use crate::cancellation::{Cancellation, IN_FLIGHT_GRACE_PERIOD};
+use crate::config::HttpClientConfig;
+use crate::http_network::configure_reqwest_tls;
+use crate::http_proxy::LOCAL_PROXY_KEY_HEADER;
+use futures_util::{future, pin_mut, stream, Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::ClientBuilder;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub const DEFAULT_HTTP_CONCURRENCY: usize = 8;
+
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+pub struct HttpClientPool {
+    clients: Mutex<HashMap<HttpClientConfig, reqwest::Client>>,
+}
+
+impl HttpClientPool {
+    /// Returns the shared client for the config, cloned clients share the connection pool
+    pub fn client(&self, config: &HttpClientConfig) -> anyhow::Result<reqwest::Client> {
+        let mut clients = self.clients.lock().unwrap();
+        match clients.get(config) {
+            Some(client) => Ok(client.clone()),
+            None => {
+                let client = new_reqwest_client_with_headers(config, HeaderMap::new())?;
+                clients.insert(config.clone(), client.clone());
+                Ok(client)
+            }
+        }
+    }
+}
+
+/// Creates a new client with its own connection pool, only used when the client needs
+/// request specific default headers, otherwise use [HttpClientPool::client]
+pub fn new_reqwest_client_with_headers(
+    config: &HttpClientConfig,
+    mut headers: HeaderMap,
+) -> anyhow::Result<reqwest::Client> {
+    if let Some(local_proxy_key) = &config.local_proxy_key {
+        headers.insert(
+            LOCAL_PROXY_KEY_HEADER,
+            HeaderValue::from_str(local_proxy_key)?,
+        );
+    }
+    Ok(reqwest_client_builder(config)?
+        .default_headers(headers)
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .http2_adaptive_window(true)
+        .build()?)
+}
+
+/// Client builder with the TLS, proxy and timeout settings of the config
+pub fn reqwest_client_builder(config: &HttpClientConfig) -> anyhow::Result<ClientBuilder> {
+    let mut builder = configure_reqwest_tls(reqwest::Client::builder(), &config.tls)?;
+
+    if config.allow_insecure {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    if config.no_proxy {
+        builder = builder.no_proxy();
+    }
+    #[cfg(unix)]
+    if let Some(unix_socket) = &config.unix_socket {
+        builder = builder.unix_socket(unix_socket.clone());
+    }
+
+    if let Some(timeout) = config.timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(connect_timeout) = config.connect_timeout {
+        builder = builder.connect_timeout(connect_timeout);
+    }
+    if let Some(read_timeout) = config.read_timeout {
+        builder = builder.read_timeout(read_timeout);
+    }
+
+    Ok(builder)
+}
+
+/// Runs requests of bulk operations with bounded concurrency
+#[derive(Debug, Clone)]
+pub struct RequestExecutor {
+    concurrency: usize,
+    cancellation: Cancellation,
+}
+
+impl RequestExecutor {
+    pub fn new(concurrency: usize, cancellation: Cancellation) -> Self {
+        Self {
+            concurrency: concurrency.max(1),
+            cancellation,
+        }
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Runs the request for every item, with at most `concurrency` requests in flight. The
+    /// results are returned in the order of the items.
+    ///
+    /// On cancellation no new requests are started, and the in-flight ones get
+    /// [IN_FLIGHT_GRACE_PERIOD] to complete, so only the results for a prefix of the items
+    /// are returned.
+    pub async fn run<I, F, Fut, T>(&self, items: I, request: F) -> Vec<T>
+    where
+        I: IntoIterator,
+        F: FnMut(I::Item) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let _scope = self.cancellation.scope();
+
+        let cancellation = &self.cancellation;
+        let results = stream::iter(items)
+            .take_while(|_| future::ready(!cancellation.is_cancelled()))
+            .map(request)
+            .buffered(self.concurrency);
+        pin_mut!(results);
+
+        let mut collected = Vec::new();
+        loop {
+            let next = tokio::select! {
+                next = results.next() => next,
+                _ = cancellation.cancelled() => break,
+            };
+            match next {
+                Some(result) => collected.push(result),
+                None => return collected,
+            }
+        }
+
+        let deadline = tokio::time::Instant::now() + IN_FLIGHT_GRACE_PERIOD;
+        while let Ok(Some(result)) = tokio::time::timeout_at(deadline, results.next()).await {
+            collected.push(result);
+        }
+        collected
+    }
+
+    /// Merges the streams (e.g. the pages of paginated requests), with at most `concurrency`
+    /// of them polled at the same time. The items are returned in the order they arrive.
+    /// On cancellation no new streams are started.
+    pub fn merge_streams<I, S>(&self, streams: I) -> impl Stream<Item = S::Item>
+    where
+        I: IntoIterator<Item = S>,
+        S: Stream + Unpin,
+    {
+        let cancellation = self.cancellation.clone();
+        stream::iter(streams)
+            .take_while(move |_| future::ready(!cancellation.is_cancelled()))
+            .flatten_unordered(self.concurrency)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cancellation::Cancellation;
+    use crate::http_pool::RequestExecutor;
+    use assert2::assert;
+    use futures_util::{stream, StreamExt};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use test_r::test;
+
+    #[test]
+    fn request_executor_bounds_concurrency_and_keeps_order() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let executor = RequestExecutor::new(3, Cancellation::new());
+        let results = runtime.block_on(executor.run(0..10u64, |idx| {
+            let in_flight = &in_flight;
+            let max_in_flight = &max_in_flight;
+            async move {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                // Later items finish first, the results still have to keep the item order
+                tokio::time::sleep(Duration::from_millis(10 - idx)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                idx * 2
+            }
+        }));
+
+        assert!(results == (0..10u64).map(|idx| idx * 2).collect::<Vec<_>>());
+        assert!(max_in_flight.load(Ordering::SeqCst) == 3);
+    }
+
+    #[test]
+    fn request_executor_stops_starting_requests_on_cancellation() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let cancellation = Cancellation::new();
+        let executor = RequestExecutor::new(2, cancellation.clone());
+        let results = runtime.block_on(executor.run(0..10u64, |idx| {
+            let cancellation = cancellation.clone();
+            async move {
+                if idx == 3 {
+                    cancellation.cancel();
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                idx
+            }
+        }));
+
+        // The in-flight requests complete, but no new ones are started
+        assert!(results.len() >= 4);
+        assert!(results.len() < 10);
+        assert!(results == (0..results.len() as u64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn request_executor_merges_streams_with_bounded_concurrency() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+
+        let active = AtomicUsize::new(0);
+        let max_active = AtomicUsize::new(0);
+
+        let executor = RequestExecutor::new(3, Cancellation::new());
+        let mut items = runtime.block_on(
+            executor
+                .merge_streams((0..10u64).map(|idx| {
+                    let active = &active;
+                    let max_active = &max_active;
+                    Box::pin(stream::unfold(0u64, move |page| async move {
+                        if page == 2 {
+                            active.fetch_sub(1, Ordering::SeqCst);
+                            return None;
+                        }
+                        if page == 0 {
+                            let current = active.fetch_add(1, Ordering::SeqCst) + 1;
+                            max_active.fetch_max(current, Ordering::SeqCst);
+                        }
+                        tokio::time::sleep(Duration::from_millis(5)).await;
+                        Some((idx * 10 + page, page + 1))
+                    }))
+                }))
+                .collect::<Vec<_>>(),
+        );
+        items.sort();
+
+        assert!(
+            items
+                == (0..10u64)
+                    .flat_map(|idx| [idx * 10, idx * 10 + 1])
+                    .collect::<Vec<_>>()
+        );
+        assert!(max_active.load(Ordering::SeqCst) == 3);
+    }
+}
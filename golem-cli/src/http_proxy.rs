@@ -0,0 +1,818 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local client proxy, used as middleware for the generated API clients.
+//!
+//! The generated API clients only accept a plain reqwest client, so request level features
+//! (debug logging, retries, response caching, throttling and recording) are implemented as a
+//! local reverse proxy: every service URL is replaced with the URL of an in-process proxy,
+//! which forwards the requests to the original service.
+//!
+//! The proxy is only started when one of these features is enabled, and it only accepts
+//! requests carrying a secret key generated for the process, as the forwarded requests use
+//! the TLS identity of the profile.
+
+use crate::config::{ClientConfig, HttpClientConfig};
+use crate::http_cache::{HttpCache, HttpCacheEntry};
+use crate::http_cassette::HttpCassette;
+use crate::http_debug::HttpDebug;
+use crate::http_pool::reqwest_client_builder;
+use crate::http_rate_limit::{RateLimiter, MAX_RATE_LIMITED_RETRIES};
+use crate::log::{log_warn_action, LogColorize};
+use anyhow::Context;
+use axum::body::{Body, Bytes};
+use axum::extract::Request;
+use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{field, info_span, Instrument};
+use url::Url;
+use uuid::Uuid;
+
+/// Header of the proxied requests, carrying the secret key of the upstream client
+pub const LOCAL_PROXY_KEY_HEADER: &str = "x-golem-cli-proxy-key";
+
+/// Headers which are managed by the HTTP clients on both sides of the proxy, or only meant
+/// for the proxy
+const NOT_FORWARDED_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "content-length",
+    "transfer-encoding",
+    "keep-alive",
+    LOCAL_PROXY_KEY_HEADER,
+];
+
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Largest request body which is buffered, so it can be resent when rate limited
+const MAX_RESENT_BODY_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    /// Base delay of the exponential backoff
+    pub backoff: Duration,
+}
+
+impl RetryPolicy {
+    pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: Self::DEFAULT_BACKOFF,
+        }
+    }
+
+    /// Only idempotent requests, or requests carrying an idempotency key are retried, so
+    /// retrying cannot duplicate side effects
+    pub fn is_retryable_request(method: &Method, headers: &HeaderMap) -> bool {
+        matches!(
+            *method,
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+        ) || headers.contains_key("idempotency-key")
+    }
+
+    pub fn is_retryable_status(status: StatusCode) -> bool {
+        matches!(
+            status,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Exponential backoff with "equal jitter": half of the delay is fixed, the other half is
+    /// scaled by the jitter, which is expected to be in the [0, 1] range
+    pub fn delay(&self, attempt: u32, jitter: f64) -> Duration {
+        let delay = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_RETRY_DELAY);
+        delay / 2 + (delay / 2).mul_f64(jitter.clamp(0.0, 1.0))
+    }
+}
+
+pub struct HttpProxy {
+    debug: Option<HttpDebug>,
+    retry: RetryPolicy,
+    cache: Option<HttpCache>,
+    rate_limiter: RateLimiter,
+    cassette: Option<HttpCassette>,
+    next_request_id: AtomicU64,
+}
+
+impl HttpProxy {
+    pub fn new(
+        debug: Option<HttpDebug>,
+        retry: RetryPolicy,
+        cache: Option<HttpCache>,
+        rate_limiter: RateLimiter,
+        cassette: Option<HttpCassette>,
+    ) -> Self {
+        Self {
+            debug,
+            retry,
+            cache,
+            rate_limiter,
+            cassette,
+            next_request_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Whether any request level feature is enabled, otherwise the clients connect directly.
+    /// All of them are opt-in (--debug-http, --http-retries, --http-cache, --max-rps and the
+    /// cassettes), so by default no proxy is started.
+    pub fn is_needed(&self) -> bool {
+        self.debug.is_some()
+            || self.retry.max_retries > 0
+            || self.cache.is_some()
+            || self.rate_limiter.has_max_rps()
+            || self.cassette.is_some()
+    }
+
+    /// Starts a proxy for every distinct service URL, and returns the client config pointing
+    /// to the proxies
+    pub async fn proxy_client_config(
+        self: &Arc<Self>,
+        mut config: ClientConfig,
+    ) -> anyhow::Result<ClientConfig> {
+        // Every kind of client gets its own upstream client, which applies the timeouts to
+        // every attempt, so hung or unreachable services fail inside the proxy, and can be
+        // retried. The original clients only keep the connect timeout, as their requests span
+        // all the attempts. Redirects are returned to the original clients as is.
+        //
+        // The upstream clients are selected by random keys, so other local processes cannot
+        // send requests through the proxy.
+        let mut upstream_clients = HashMap::<String, reqwest::Client>::new();
+        for http_client_config in [
+            &mut config.service_http_client_config,
+            &mut config.invoke_http_client_config,
+            &mut config.health_check_http_client_config,
+        ] {
+            let key = Uuid::new_v4().to_string();
+            let upstream_client = reqwest_client_builder(http_client_config)?
+                .redirect(reqwest::redirect::Policy::none())
+                .build()?;
+            upstream_clients.insert(key.clone(), upstream_client);
+
+            // The proxy environment variables and the Unix socket only apply to the upstream
+            // clients
+            *http_client_config = HttpClientConfig {
+                timeout: None,
+                read_timeout: None,
+                no_proxy: true,
+                local_proxy_key: Some(key),
+                unix_socket: None,
+                ..http_client_config.clone()
+            };
+        }
+        let upstream_clients = Arc::new(upstream_clients);
+
+        let mut proxies = HashMap::<Url, Url>::new();
+        for url in [
+            &mut config.component_url,
+            &mut config.worker_url,
+            &mut config.cloud_url,
+        ] {
+            let proxy_url = match proxies.get(url) {
+                Some(proxy_url) => proxy_url.clone(),
+                None => {
+                    let proxy_url = self
+                        .start_proxy(upstream_clients.clone(), url.clone())
+                        .await?;
+                    proxies.insert(url.clone(), proxy_url.clone());
+                    proxy_url
+                }
+            };
+            *url = proxy_url;
+        }
+
+        Ok(config)
+    }
+
+    async fn start_proxy(
+        self: &Arc<Self>,
+        upstream_clients: Arc<HashMap<String, reqwest::Client>>,
+        target: Url,
+    ) -> anyhow::Result<Url> {
+        let proxy = self.clone();
+        let router = Router::new().fallback(move |request: Request| {
+            let proxy = proxy.clone();
+            let client = request
+                .headers()
+                .get(LOCAL_PROXY_KEY_HEADER)
+                .and_then(|key| key.to_str().ok())
+                .and_then(|key| upstream_clients.get(key))
+                .cloned();
+            let target = target.clone();
+            async move {
+                let Some(client) = client else {
+                    return (StatusCode::FORBIDDEN, "Unknown proxy client").into_response();
+                };
+
+                let span = info_span!(
+                    "http_request",
+                    method = %request.method(),
+                    path = %request.uri().path(),
+                    status = field::Empty,
+                );
+                let response = proxy
+                    .forward(&client, &target, request)
+                    .instrument(span.clone())
+                    .await;
+                span.record("status", response.status().as_u16());
+                response
+            }
+        });
+
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .context("Failed to bind HTTP client proxy")?;
+        let port = listener.local_addr()?.port();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        Ok(Url::parse(&format!("http://127.0.0.1:{port}/"))?)
+    }
+
+    async fn forward(&self, client: &reqwest::Client, target: &Url, request: Request) -> Response {
+        let id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        let (parts, body) = request.into_parts();
+
+        let path = parts
+            .uri
+            .path_and_query()
+            .map(|path_and_query| path_and_query.as_str())
+            .unwrap_or("/");
+        let url = format!("{}{}", target.as_str().trim_end_matches('/'), path);
+
+        // Replayed requests never reach the services
+        if let Some(cassette) = self
+            .cassette
+            .as_ref()
+            .filter(|cassette| cassette.is_replay())
+        {
+            return match cassette.next(&parts.method, path) {
+                Some(interaction) => Self::response(
+                    interaction.status(),
+                    interaction.headers(),
+                    Body::from(interaction.body()),
+                ),
+                None => self.proxy_error(
+                    id,
+                    &url,
+                    Duration::ZERO,
+                    format!("No recorded response for {} {}", parts.method, path),
+                ),
+            };
+        }
+
+        let max_retries = if RetryPolicy::is_retryable_request(&parts.method, &parts.headers) {
+            self.retry.max_retries
+        } else {
+            0
+        };
+
+        let cache = self.cache.as_ref().filter(|_| {
+            parts.method == Method::GET && HttpCache::is_cacheable_path(parts.uri.path())
+        });
+        let cache_key = cache.map(|_| {
+            HttpCache::key(
+                &url,
+                parts
+                    .headers
+                    .get(header::AUTHORIZATION)
+                    .map(|value| value.as_bytes()),
+            )
+        });
+        let cached = cache
+            .zip(cache_key.as_ref())
+            .and_then(|(cache, key)| cache.get(key));
+
+        // Small bodies are buffered, so rate limited requests can be resent
+        let small_body = match parts.headers.get(header::CONTENT_LENGTH) {
+            Some(content_length) => content_length
+                .to_str()
+                .ok()
+                .and_then(|content_length| content_length.parse::<usize>().ok())
+                .is_some_and(|content_length| content_length <= MAX_RESENT_BODY_SIZE),
+            None => !matches!(parts.method, Method::POST | Method::PUT | Method::PATCH),
+        };
+
+        let mut headers = parts.headers;
+        for name in NOT_FORWARDED_HEADERS {
+            headers.remove(*name);
+        }
+
+        // Large and unknown size bodies are streamed, unless the request is logged, retried,
+        // cached or recorded, so large uploads are not held in memory
+        if self.debug.is_none()
+            && max_retries == 0
+            && cache.is_none()
+            && self.cassette.is_none()
+            && !small_body
+        {
+            return self
+                .forward_streaming(client, id, &parts.method, &url, headers, body)
+                .await;
+        }
+
+        // Bodies are buffered, so they can be logged and resent on retries
+        let body = match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(body) => body,
+            Err(err) => return self.proxy_error(id, &url, Duration::ZERO, err.to_string()),
+        };
+
+        if let Some(cached) = &cached {
+            if let Ok(etag) = HeaderValue::from_str(&cached.etag) {
+                headers.insert(header::IF_NONE_MATCH, etag);
+            }
+        }
+        if let Some(debug) = &self.debug {
+            debug.log_request(id, &parts.method, &url, &headers, &body);
+        }
+
+        let started_at = Instant::now();
+        let mut attempt = 0;
+        let mut rate_limited_attempt = 0;
+        loop {
+            self.rate_limiter.acquire().await;
+            let result = self
+                .send(client, &parts.method, &url, &headers, body.clone())
+                .await;
+
+            // Rate limited requests were not processed, so they are retried regardless of the
+            // method and the retry policy
+            let rate_limited_delay = match &result {
+                Ok((status, headers, _)) => self.rate_limiter.observe(*status, headers),
+                Err(_) => None,
+            };
+            if let Some(delay) = rate_limited_delay {
+                if rate_limited_attempt < MAX_RATE_LIMITED_RETRIES {
+                    rate_limited_attempt += 1;
+                    log_rate_limited(&parts.method, &url, delay);
+                    continue;
+                }
+            }
+
+            let retry_reason = match &result {
+                Ok((status, _, _)) if RetryPolicy::is_retryable_status(*status) => {
+                    Some(status.to_string())
+                }
+                Ok(_) => None,
+                Err(err) if err.is_connect() || err.is_timeout() => Some(err.to_string()),
+                Err(_) => None,
+            };
+
+            match retry_reason {
+                Some(reason) if attempt < max_retries => {
+                    let delay = self.retry.delay(attempt, jitter());
+                    attempt += 1;
+                    log_warn_action(
+                        "Retrying",
+                        format!(
+                            "{} {} after {}, in {} ms (retry {}/{})",
+                            parts.method,
+                            url.log_color_highlight(),
+                            reason,
+                            delay.as_millis(),
+                            attempt,
+                            max_retries
+                        ),
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                _ => {
+                    return match result {
+                        Ok((status, headers, body)) => {
+                            if let Some(debug) = &self.debug {
+                                debug.log_response(
+                                    id,
+                                    status,
+                                    &headers,
+                                    started_at.elapsed(),
+                                    &body,
+                                );
+                            }
+                            if let Some(cassette) = &self.cassette {
+                                cassette.record_interaction(
+                                    &parts.method,
+                                    path,
+                                    status,
+                                    &headers,
+                                    &body,
+                                );
+                            }
+                            match cache.zip(cache_key.as_ref()) {
+                                Some((cache, key)) => {
+                                    Self::cached_response(cache, key, cached, status, headers, body)
+                                }
+                                None => Self::response(status, headers, Body::from(body)),
+                            }
+                        }
+                        Err(err) => {
+                            self.proxy_error(id, &url, started_at.elapsed(), err.to_string())
+                        }
+                    };
+                }
+            }
+        }
+    }
+
+    async fn forward_streaming(
+        &self,
+        client: &reqwest::Client,
+        id: u64,
+        method: &Method,
+        url: &str,
+        headers: HeaderMap,
+        body: Body,
+    ) -> Response {
+        let started_at = Instant::now();
+        self.rate_limiter.acquire().await;
+        let result = client
+            .request(method.clone(), url)
+            .headers(headers)
+            .body(reqwest::Body::wrap_stream(body.into_data_stream()))
+            .send()
+            .await;
+
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                let mut headers = response.headers().clone();
+                for name in NOT_FORWARDED_HEADERS {
+                    headers.remove(*name);
+                }
+                // Streamed requests cannot be resent, but the following requests are throttled
+                self.rate_limiter.observe(status, &headers);
+                Self::response(status, headers, Body::from_stream(response.bytes_stream()))
+            }
+            Err(err) => self.proxy_error(id, url, started_at.elapsed(), err.to_string()),
+        }
+    }
+
+    /// Serves the cached body if the entry was not modified, and stores the response if it
+    /// has an ETag
+    fn cached_response(
+        cache: &HttpCache,
+        key: &str,
+        cached: Option<HttpCacheEntry>,
+        status: StatusCode,
+        mut headers: HeaderMap,
+        body: Bytes,
+    ) -> Response {
+        if status == StatusCode::NOT_MODIFIED {
+            if let Some((cached, cached_body)) =
+                cached.and_then(|cached| cached.body().map(|body| (cached, body)))
+            {
+                cache.record_hit();
+                if let Some(content_type) = cached
+                    .content_type
+                    .as_deref()
+                    .and_then(|content_type| HeaderValue::from_str(content_type).ok())
+                {
+                    headers.insert(header::CONTENT_TYPE, content_type);
+                }
+                return Self::response(StatusCode::OK, headers, Body::from(cached_body));
+            }
+        }
+
+        cache.record_miss();
+        if status == StatusCode::OK {
+            if let Some(etag) = headers
+                .get(header::ETAG)
+                .and_then(|etag| etag.to_str().ok())
+            {
+                cache.put(
+                    key,
+                    etag,
+                    headers
+                        .get(header::CONTENT_TYPE)
+                        .and_then(|content_type| content_type.to_str().ok()),
+                    &body,
+                );
+            }
+        }
+        Self::response(status, headers, Body::from(body))
+    }
+
+    fn response(status: StatusCode, headers: HeaderMap, body: Body) -> Response {
+        let mut response = Response::new(body);
+        *response.status_mut() = status;
+        *response.headers_mut() = headers;
+        response
+    }
+
+    pub fn log_cache_stats(&self) {
+        if let Some(cache) = &self.cache {
+            cache.log_stats();
+        }
+    }
+
+    async fn send(
+        &self,
+        client: &reqwest::Client,
+        method: &Method,
+        url: &str,
+        headers: &HeaderMap,
+        body: Bytes,
+    ) -> reqwest::Result<(StatusCode, HeaderMap, Bytes)> {
+        let response = client
+            .request(method.clone(), url)
+            .headers(headers.clone())
+            .body(body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let mut headers = response.headers().clone();
+        for name in NOT_FORWARDED_HEADERS {
+            headers.remove(*name);
+        }
+        let body = response.bytes().await?;
+
+        Ok((status, headers, body))
+    }
+
+    fn proxy_error(&self, id: u64, url: &str, elapsed: Duration, error: String) -> Response {
+        if let Some(debug) = &self.debug {
+            debug.log_error(id, url, elapsed, &error);
+        }
+        (StatusCode::BAD_GATEWAY, error).into_response()
+    }
+}
+
+fn log_rate_limited(method: &Method, url: &str, delay: Duration) {
+    log_warn_action(
+        "Rate limited",
+        format!(
+            "{} {}, retrying in {}s",
+            method,
+            url.log_color_highlight(),
+            delay.as_secs_f64().ceil() as u64
+        ),
+    );
+}
+
+/// Random value in the [0, 1] range, UUIDs are used as the source of randomness
+pub fn jitter() -> f64 {
+    (Uuid::new_v4().as_u64_pair().0 >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::{ClientConfig, Profile};
+    use crate::http_cache::HttpCache;
+    use crate::http_pool::new_reqwest_client_with_headers;
+    use crate::http_proxy::{HttpProxy, RetryPolicy, LOCAL_PROXY_KEY_HEADER};
+    use crate::http_rate_limit::RateLimiter;
+    use assert2::assert;
+    use axum::http::{header, HeaderMap, HeaderValue, Method, StatusCode};
+    use axum::response::IntoResponse;
+    use axum::Router;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use test_r::test;
+    use url::Url;
+
+    #[test]
+    fn retry_policy() {
+        let mut idempotency_key = HeaderMap::new();
+        idempotency_key.insert("Idempotency-Key", HeaderValue::from_static("key"));
+
+        assert!(RetryPolicy::is_retryable_request(
+            &Method::GET,
+            &HeaderMap::new()
+        ));
+        assert!(!RetryPolicy::is_retryable_request(
+            &Method::POST,
+            &HeaderMap::new()
+        ));
+        assert!(RetryPolicy::is_retryable_request(
+            &Method::POST,
+            &idempotency_key
+        ));
+        assert!(RetryPolicy::is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!RetryPolicy::is_retryable_status(
+            StatusCode::INTERNAL_SERVER_ERROR
+        ));
+
+        let policy = RetryPolicy {
+            max_retries: 3,
+            backoff: Duration::from_millis(200),
+        };
+        assert!(policy.delay(0, 0.0) == Duration::from_millis(100));
+        assert!(policy.delay(0, 1.0) == Duration::from_millis(200));
+        assert!(policy.delay(2, 0.5) == Duration::from_millis(600));
+        assert!(policy.delay(20, 1.0) == Duration::from_secs(30));
+    }
+
+    #[test]
+    async fn hung_attempts_are_retried() {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let upstream = Router::new().fallback({
+            let attempts = attempts.clone();
+            move || {
+                let attempts = attempts.clone();
+                async move {
+                    if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                    "ok"
+                }
+            }
+        });
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let upstream_url =
+            Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+        let proxy = Arc::new(HttpProxy::new(
+            None,
+            RetryPolicy {
+                max_retries: 1,
+                backoff: Duration::from_millis(1),
+            },
+            None,
+            RateLimiter::new(None),
+            None,
+        ));
+        let config = ClientConfig::from(&Profile {
+            custom_url: Some(upstream_url),
+            ..Profile::default()
+        })
+        .with_http_overrides(Some(Duration::from_millis(200)), None);
+        let config = proxy.proxy_client_config(config).await.unwrap();
+        assert!(config.service_http_client_config.timeout.is_none());
+
+        let client =
+            new_reqwest_client_with_headers(&config.service_http_client_config, HeaderMap::new())
+                .unwrap();
+        let response = client
+            .get(config.component_url.join("v1/components").unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status() == StatusCode::OK);
+        assert!(attempts.load(Ordering::SeqCst) == 2);
+
+        let unknown_client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let response = unknown_client
+            .get(config.component_url.clone())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status() == StatusCode::FORBIDDEN);
+
+        let mut wrong_key = HeaderMap::new();
+        wrong_key.insert(LOCAL_PROXY_KEY_HEADER, HeaderValue::from_static("service"));
+        let response = unknown_client
+            .get(config.component_url.clone())
+            .headers(wrong_key)
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status() == StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    async fn cached_responses_are_revalidated() {
+        // The upstream answers with a body and ETag per token, and with 304 when the ETag
+        // of the token is sent back
+        let if_none_match = Arc::new(Mutex::new(Vec::<Option<String>>::new()));
+        let upstream = Router::new().fallback({
+            let if_none_match = if_none_match.clone();
+            move |headers: HeaderMap| {
+                let if_none_match = if_none_match.clone();
+                async move {
+                    let token = headers
+                        .get(header::AUTHORIZATION)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default()
+                        .trim_start_matches("Bearer ")
+                        .to_string();
+                    let etag = format!("\"{token}\"");
+                    let sent_etag = headers
+                        .get(header::IF_NONE_MATCH)
+                        .and_then(|value| value.to_str().ok())
+                        .map(|value| value.to_string());
+                    if_none_match.lock().unwrap().push(sent_etag.clone());
+
+                    if sent_etag.as_deref() == Some(etag.as_str()) {
+                        StatusCode::NOT_MODIFIED.into_response()
+                    } else {
+                        (
+                            [
+                                (header::ETAG, etag),
+                                (header::CONTENT_TYPE, "application/json".to_string()),
+                            ],
+                            format!("{{\"token\":\"{token}\"}}"),
+                        )
+                            .into_response()
+                    }
+                }
+            }
+        });
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .unwrap();
+        let upstream_url =
+            Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        tokio::spawn(async move { axum::serve(listener, upstream).await });
+
+        let cache_dir = tempfile::tempdir().unwrap();
+        let proxy = Arc::new(HttpProxy::new(
+            None,
+            RetryPolicy::none(),
+            Some(HttpCache::new(cache_dir.path().to_path_buf())),
+            RateLimiter::new(None),
+            None,
+        ));
+        assert!(proxy.is_needed());
+        let config = ClientConfig::from(&Profile {
+            custom_url: Some(upstream_url),
+            ..Profile::default()
+        });
+        let config = proxy.proxy_client_config(config).await.unwrap();
+        let url = config
+            .component_url
+            .join("v1/components/id/latest")
+            .unwrap();
+
+        let get = |token: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+            );
+            let client =
+                new_reqwest_client_with_headers(&config.service_http_client_config, headers)
+                    .unwrap();
+            let url = url.clone();
+            async move {
+                let response = client.get(url).send().await.unwrap();
+                let status = response.status();
+                let content_type = response.headers().get(header::CONTENT_TYPE).cloned();
+                let body = response.text().await.unwrap();
+                (status, content_type, body)
+            }
+        };
+
+        // Stored on the first request, then served from the cache on 304
+        for _ in 0..2 {
+            let (status, content_type, body) = get("a").await;
+            assert!(status == StatusCode::OK);
+            assert!(content_type == Some(HeaderValue::from_static("application/json")));
+            assert!(body == r#"{"token":"a"}"#);
+        }
+
+        // Another token does not revalidate or get the entry of the first one
+        let (status, _, body) = get("b").await;
+        assert!(status == StatusCode::OK);
+        assert!(body == r#"{"token":"b"}"#);
+
+        assert!(*if_none_match.lock().unwrap() == vec![None, Some("\"a\"".to_string()), None]);
+    }
+
+    #[test]
+    fn proxy_is_opt_in() {
+        let cache_dir = tempfile::tempdir().unwrap();
+        let cache = || Some(HttpCache::new(cache_dir.path().to_path_buf()));
+        let proxy = |retry: RetryPolicy, cache: Option<HttpCache>, max_rps: Option<u32>| {
+            HttpProxy::new(None, retry, cache, RateLimiter::new(max_rps), None)
+        };
+        assert!(!proxy(RetryPolicy::none(), None, None).is_needed());
+        assert!(proxy(RetryPolicy::none(), None, Some(10)).is_needed());
+        assert!(proxy(RetryPolicy::none(), cache(), None).is_needed());
+        assert!(proxy(
+            RetryPolicy {
+                max_retries: 2,
+                backoff: RetryPolicy::DEFAULT_BACKOFF
+            },
+            None,
+            None
+        )
+        .is_needed());
+    }
+}
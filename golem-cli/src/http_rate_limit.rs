@@ -0,0 +1,230 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client side throttling of service requests.
+//!
+//! Requests are paced by an explicit --max-rps limit and by the rate limits reported by the
+//! services: 429 responses and exhausted RateLimit-Remaining headers pause all requests until
+//! the limit resets, and repeated 429 responses slow down the request rate adaptively, so bulk
+//! operations stay under the limits instead of failing.
+//!
+//! The limiter runs in the local client proxy, so the limits reported by the services are only
+//! respected when the proxy is started for some request level feature.
+
+use axum::http::{HeaderMap, StatusCode};
+use chrono::{DateTime, Utc};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Number of times a rate limited request is retried, independently of --http-retries
+pub const MAX_RATE_LIMITED_RETRIES: u32 = 5;
+
+const DEFAULT_RATE_LIMITED_DELAY: Duration = Duration::from_secs(1);
+const MAX_RATE_LIMITED_DELAY: Duration = Duration::from_secs(60);
+const MIN_ADAPTIVE_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_ADAPTIVE_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct RateLimiter {
+    min_interval: Option<Duration>,
+    state: Mutex<RateLimiterState>,
+}
+
+struct RateLimiterState {
+    next_request_at: Instant,
+    blocked_until: Instant,
+    adaptive_interval: Duration,
+}
+
+impl RateLimiter {
+    pub fn new(max_rps: Option<u32>) -> Self {
+        let now = Instant::now();
+        Self {
+            min_interval: max_rps.map(|max_rps| Duration::from_secs(1) / max_rps.max(1)),
+            state: Mutex::new(RateLimiterState {
+                next_request_at: now,
+                blocked_until: now,
+                adaptive_interval: Duration::ZERO,
+            }),
+        }
+    }
+
+    pub fn has_max_rps(&self) -> bool {
+        self.min_interval.is_some()
+    }
+
+    /// Waits until the next request can be sent
+    pub async fn acquire(&self) {
+        let send_at = {
+            let mut state = self.state.lock().unwrap();
+            let interval = self
+                .min_interval
+                .unwrap_or_default()
+                .max(state.adaptive_interval);
+            let send_at = Instant::now()
+                .max(state.blocked_until)
+                .max(state.next_request_at);
+            state.next_request_at = send_at + interval;
+            send_at
+        };
+        tokio::time::sleep_until(send_at.into()).await;
+    }
+
+    /// Updates the limits based on the response, and returns the delay before retrying if the
+    /// request was rate limited
+    pub fn observe(&self, status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+        let limits = RateLimitHeaders::from_headers(headers, Utc::now());
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let delay = limits
+                .retry_after
+                .or(limits.reset)
+                .unwrap_or(DEFAULT_RATE_LIMITED_DELAY)
+                .min(MAX_RATE_LIMITED_DELAY);
+            state.blocked_until = state.blocked_until.max(now + delay);
+            state.adaptive_interval =
+                (state.adaptive_interval * 2).clamp(MIN_ADAPTIVE_INTERVAL, MAX_ADAPTIVE_INTERVAL);
+            return Some(delay);
+        }
+
+        match (limits.remaining, limits.reset) {
+            (Some(0), Some(reset)) => {
+                state.blocked_until = state
+                    .blocked_until
+                    .max(now + reset.min(MAX_RATE_LIMITED_DELAY));
+            }
+            // Spreads the remaining requests over the rest of the window
+            (Some(remaining), Some(reset)) => {
+                state.adaptive_interval = (reset / remaining).min(MAX_ADAPTIVE_INTERVAL);
+            }
+            _ => {
+                state.adaptive_interval = state.adaptive_interval * 9 / 10;
+                if state.adaptive_interval < MIN_ADAPTIVE_INTERVAL / 5 {
+                    state.adaptive_interval = Duration::ZERO;
+                }
+            }
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RateLimitHeaders {
+    pub retry_after: Option<Duration>,
+    pub remaining: Option<u32>,
+    /// Time until the rate limit window resets
+    pub reset: Option<Duration>,
+}
+
+impl RateLimitHeaders {
+    /// Supports Retry-After, and both the RateLimit-* and the X-RateLimit-* headers
+    pub fn from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Self {
+        let header = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                headers
+                    .get(*name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| value.trim().to_string())
+            })
+        };
+
+        Self {
+            retry_after: header(&["retry-after"]).and_then(|value| parse_retry_after(&value, now)),
+            remaining: header(&["ratelimit-remaining", "x-ratelimit-remaining"])
+                .and_then(|value| value.parse().ok()),
+            reset: header(&["ratelimit-reset", "x-ratelimit-reset"])
+                .and_then(|value| parse_reset(&value, now)),
+        }
+    }
+}
+
+/// Retry-After is either a number of seconds or an HTTP date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => DateTime::parse_from_rfc2822(value).ok().map(|date| {
+            (date.with_timezone(&Utc) - now)
+                .to_std()
+                .unwrap_or_default()
+        }),
+    }
+}
+
+/// Reset is either a number of seconds, or a unix timestamp in the X-RateLimit-Reset header of
+/// some services
+fn parse_reset(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let seconds = value.parse::<u64>().ok()?;
+    let now_seconds = now.timestamp().max(0) as u64;
+    if seconds > now_seconds / 2 {
+        Some(Duration::from_secs(seconds.saturating_sub(now_seconds)))
+    } else {
+        Some(Duration::from_secs(seconds))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::http_rate_limit::RateLimitHeaders;
+    use assert2::assert;
+    use axum::http::{HeaderMap, HeaderValue};
+    use chrono::{DateTime, Utc};
+    use std::time::Duration;
+    use test_r::test;
+
+    #[test]
+    fn rate_limit_headers() {
+        let now = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let headers = |values: &[(&'static str, &'static str)]| {
+            let mut headers = HeaderMap::new();
+            for (name, value) in values {
+                headers.insert(*name, HeaderValue::from_static(value));
+            }
+            RateLimitHeaders::from_headers(&headers, now)
+        };
+
+        assert!(headers(&[]) == RateLimitHeaders::default());
+        assert!(
+            headers(&[("retry-after", "3")])
+                == RateLimitHeaders {
+                    retry_after: Some(Duration::from_secs(3)),
+                    ..RateLimitHeaders::default()
+                }
+        );
+        assert!(
+            headers(&[("retry-after", "Wed, 01 Jan 2025 00:00:10 GMT")]).retry_after
+                == Some(Duration::from_secs(10))
+        );
+        assert!(
+            headers(&[("ratelimit-remaining", "5"), ("ratelimit-reset", "20")])
+                == RateLimitHeaders {
+                    retry_after: None,
+                    remaining: Some(5),
+                    reset: Some(Duration::from_secs(20)),
+                }
+        );
+        assert!(
+            headers(&[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "1735689630")
+            ]) == RateLimitHeaders {
+                retry_after: None,
+                remaining: Some(0),
+                reset: Some(Duration::from_secs(30)),
+            }
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Transports of the service endpoints.
+//!
+//! Besides plain TCP, services can be reached through a Unix domain socket, using URLs like
+//! `http+unix://%2Frun%2Fgolem.sock/base/path`, where the host is the percent-encoded path of
+//! the socket. The HTTP clients of the services connect to the socket directly, so the
+//! permissions of the socket keep applying, and the service URLs are rewritten to plain HTTP
+//! URLs, keeping the base path.
+
+use crate::config::ClientConfig;
+use crate::http_network::percent_decode;
+use anyhow::{anyhow, bail};
+use std::path::PathBuf;
+use url::Url;
+
+pub const UNIX_SOCKET_URL_SCHEME: &str = "http+unix";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceTransport {
+    Tcp,
+    UnixSocket(PathBuf),
+}
+
+impl ServiceTransport {
+    pub fn from_url(url: &Url) -> anyhow::Result<Self> {
+        if url.scheme() != UNIX_SOCKET_URL_SCHEME {
+            return Ok(Self::Tcp);
+        }
+
+        match url.host_str().map(percent_decode) {
+            Some(socket_path) if !socket_path.is_empty() => {
+                Ok(Self::UnixSocket(PathBuf::from(socket_path)))
+            }
+            _ => bail!(
+                "Missing socket path in {url}, expected a percent-encoded path, e.g.: {UNIX_SOCKET_URL_SCHEME}://%2Frun%2Fgolem.sock"
+            ),
+        }
+    }
+}
+
+/// Configures the service clients to connect to the Unix socket of the service URLs. As a
+/// client sends all of its requests through the same socket, the services must either all use
+/// TCP, or the same socket.
+pub fn resolve_service_transports(mut config: ClientConfig) -> anyhow::Result<ClientConfig> {
+    let mut unix_socket = None::<PathBuf>;
+    let mut has_tcp_services = false;
+    for url in [
+        &mut config.component_url,
+        &mut config.worker_url,
+        &mut config.cloud_url,
+    ] {
+        match ServiceTransport::from_url(url)? {
+            ServiceTransport::Tcp => has_tcp_services = true,
+            ServiceTransport::UnixSocket(socket_path) => {
+                if let Some(unix_socket) = &unix_socket {
+                    if unix_socket != &socket_path {
+                        bail!(
+                            "All service URLs must use the same Unix socket, found {} and {}",
+                            unix_socket.display(),
+                            socket_path.display()
+                        );
+                    }
+                }
+                *url = unix_socket_service_url(url)?;
+                unix_socket = Some(socket_path);
+            }
+        }
+    }
+
+    let Some(unix_socket) = unix_socket else {
+        return Ok(config);
+    };
+    if has_tcp_services {
+        bail!("Service URLs cannot mix Unix sockets and TCP");
+    }
+    if cfg!(not(unix)) {
+        bail!(
+            "Unix socket {} is not supported on this platform",
+            unix_socket.display()
+        );
+    }
+
+    // Proxies are meant for remote services, the socket is local
+    for http_client_config in [
+        &mut config.service_http_client_config,
+        &mut config.invoke_http_client_config,
+        &mut config.health_check_http_client_config,
+    ] {
+        http_client_config.unix_socket = Some(unix_socket.clone());
+        http_client_config.no_proxy = true;
+    }
+
+    Ok(config)
+}
+
+/// Plain HTTP URL of a service reached through a Unix socket, the host is only used for the
+/// Host header
+fn unix_socket_service_url(url: &Url) -> anyhow::Result<Url> {
+    let mut service_url = Url::parse("http://localhost")
+        .map_err(|err| anyhow!("Failed to create service URL for {url}: {err}"))?;
+    service_url.set_path(url.path());
+    service_url.set_query(url.query());
+    Ok(service_url)
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::config::{ClientConfig, Profile};
+    use crate::http_transport::{
+        resolve_service_transports, unix_socket_service_url, ServiceTransport,
+    };
+    use assert2::assert;
+    use std::path::PathBuf;
+    use url::Url;
+
+    #[test]
+    fn service_transport_from_url() {
+        let url = Url::parse("http+unix://%2Frun%2Fgolem.sock/golem/api").unwrap();
+        assert!(
+            ServiceTransport::from_url(&url).unwrap()
+                == ServiceTransport::UnixSocket(PathBuf::from("/run/golem.sock"))
+        );
+        assert!(unix_socket_service_url(&url).unwrap().as_str() == "http://localhost/golem/api");
+
+        let url = Url::parse("https://release.api.golem.cloud/base").unwrap();
+        assert!(ServiceTransport::from_url(&url).unwrap() == ServiceTransport::Tcp);
+
+        assert!(ServiceTransport::from_url(&Url::parse("http+unix:///golem").unwrap()).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_service_transports() {
+        let profile = |worker_url: &str| Profile {
+            custom_url: Some(Url::parse("http+unix://%2Frun%2Fgolem.sock/golem").unwrap()),
+            custom_worker_url: Some(Url::parse(worker_url).unwrap()),
+            ..Profile::default()
+        };
+
+        let config = resolve_service_transports(ClientConfig::from(&profile(
+            "http+unix://%2Frun%2Fgolem.sock/worker",
+        )))
+        .unwrap();
+        assert!(config.component_url.as_str() == "http://localhost/golem");
+        assert!(config.worker_url.as_str() == "http://localhost/worker");
+        assert!(
+            config.service_http_client_config.unix_socket == Some(PathBuf::from("/run/golem.sock"))
+        );
+        assert!(config
+            .file_download_http_client_config
+            .unix_socket
+            .is_none());
+
+        assert!(resolve_service_transports(ClientConfig::from(&profile(
+            "http+unix://%2Frun%2Fother.sock/worker"
+        )))
+        .is_err());
+        assert!(
+            resolve_service_transports(ClientConfig::from(&profile("http://localhost:9881")))
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::config::{AuthSecret, ProfileName};
+use anyhow::{anyhow, bail, Context};
+use keyring::Entry;
+use uuid::Uuid;
+
+const KEYCHAIN_SERVICE: &str = "golem-cli";
+
+fn entry_user(profile_name: &ProfileName) -> String {
+    format!("profile:{}", profile_name.0)
+}
+
+fn entry(profile_name: &ProfileName) -> anyhow::Result<Entry> {
+    Entry::new(KEYCHAIN_SERVICE, &entry_user(profile_name))
+        .with_context(|| anyhow!("Failed to access OS keychain"))
+}
+
+fn format_secret(secret: &AuthSecret) -> String {
+    secret.0.to_string()
+}
+
+fn parse_secret(secret: &str) -> anyhow::Result<AuthSecret> {
+    let secret =
+        Uuid::parse_str(secret).with_context(|| anyhow!("Invalid secret in OS keychain"))?;
+    // The placeholder would be mistaken for a secret that still has to be loaded
+    if secret.is_nil() {
+        bail!("Invalid secret in OS keychain");
+    }
+    Ok(AuthSecret(secret))
+}
+
+pub fn load_secret(profile_name: &ProfileName) -> anyhow::Result<AuthSecret> {
+    let secret = entry(profile_name)?
+        .get_password()
+        .with_context(|| anyhow!("Failed to read secret from OS keychain"))?;
+
+    parse_secret(&secret)
+}
+
+pub fn store_secret(profile_name: &ProfileName, secret: &AuthSecret) -> anyhow::Result<()> {
+    let entry = entry(profile_name)?;
+
+    // Avoid unnecessary writes, as those can trigger OS level confirmations
+    if let Ok(stored_secret) = entry.get_password() {
+        if stored_secret == format_secret(secret) {
+            return Ok(());
+        }
+    }
+
+    entry
+        .set_password(&format_secret(secret))
+        .with_context(|| anyhow!("Failed to write secret to OS keychain"))
+}
+
+pub fn delete_secret(profile_name: &ProfileName) -> anyhow::Result<()> {
+    match entry(profile_name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(err).with_context(|| anyhow!("Failed to delete secret from OS keychain")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::{AuthSecret, ProfileName};
+    use crate::keychain::{entry_user, format_secret, parse_secret};
+    use assert2::assert;
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn secret_round_trip() {
+        let secret = AuthSecret(Uuid::new_v4());
+        assert!(parse_secret(&format_secret(&secret)).unwrap().0 == secret.0);
+
+        assert!(parse_secret("not-a-uuid").is_err());
+        assert!(parse_secret(&format_secret(&AuthSecret::stored_externally())).is_err());
+    }
+
+    #[test]
+    fn entries_are_per_profile() {
+        assert!(entry_user(&ProfileName::from("local")) == "profile:local");
+        assert!(entry_user(&ProfileName::from("local")) != entry_user(&ProfileName::from("cloud")));
+    }
+}
@@ -12,14 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::timings::Timings;
 use clap_verbosity_flag::Verbosity;
 use golem_common::tracing::directive;
 use golem_common::tracing::directive::warn;
 use shadow_rs::shadow;
-use tracing_subscriber::{EnvFilter, FmtSubscriber};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{EnvFilter, Layer, Registry};
 
 pub mod app;
 pub mod auth;
+pub mod cancellation;
 pub mod command;
 pub mod command_handler;
 pub mod config;
@@ -28,8 +31,20 @@ pub mod diagnose;
 pub mod error;
 pub mod fs;
 pub mod fuzzy;
+pub mod http_cache;
+pub mod http_cassette;
+pub mod http_debug;
+pub mod http_network;
+pub mod http_pool;
+pub mod http_proxy;
+pub mod http_rate_limit;
+pub mod http_transport;
+pub mod keychain;
 pub mod log;
 pub mod model;
+pub mod poll;
+pub mod server_version;
+pub mod timings;
 pub mod validation;
 pub mod wasm_rpc_stubgen;
 
@@ -60,9 +75,17 @@ pub fn version() -> &'static str {
 }
 
 pub fn init_tracing(verbosity: Verbosity, pretty_mode: bool) {
-    if let Some(level) = verbosity.tracing_level() {
-        let subscriber = FmtSubscriber::builder();
+    init_tracing_with_timings(verbosity, pretty_mode, None)
+}
 
+/// Initializes tracing, the spans of the CLI are collected for the timings regardless of
+/// the verbosity
+pub fn init_tracing_with_timings(
+    verbosity: Verbosity,
+    pretty_mode: bool,
+    timings: Option<&Timings>,
+) {
+    let fmt_layer = verbosity.tracing_level().map(|level| {
         let mut filter = EnvFilter::builder().parse_lossy(level.as_str());
         for directive in directive::default_deps() {
             filter = filter.add_directive(directive);
@@ -71,27 +94,24 @@ pub fn init_tracing(verbosity: Verbosity, pretty_mode: bool) {
         filter = filter.add_directive(warn("opentelemetry"));
         filter = filter.add_directive(warn("poem"));
 
-        if pretty_mode {
-            let subscriber = subscriber
-                .pretty()
-                .with_max_level(level)
-                .with_writer(std::io::stderr)
-                .with_env_filter(filter)
-                .finish();
-
-            tracing::subscriber::set_global_default(subscriber)
-                .expect("setting default subscriber failed");
+        let layer = tracing_subscriber::fmt::layer().with_writer(std::io::stderr);
+        let layer: Box<dyn Layer<Registry> + Send + Sync> = if pretty_mode {
+            layer.pretty().with_filter(filter).boxed()
         } else {
-            let subscriber = subscriber
-                .with_max_level(level)
-                .with_writer(std::io::stderr)
-                .with_env_filter(filter)
-                .finish();
-
-            tracing::subscriber::set_global_default(subscriber)
-                .expect("setting default subscriber failed");
+            layer.with_filter(filter).boxed()
         };
+        layer
+    });
+    let timings_layer = timings.map(Timings::layer);
+
+    if fmt_layer.is_none() && timings_layer.is_none() {
+        return;
     }
+
+    let subscriber = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(timings_layer);
+    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
 }
 
 #[cfg(test)]
@@ -14,8 +14,11 @@
 
 use crate::fs::{OverwriteSafeAction, OverwriteSafeActionPlan, PathExtra};
 use camino::{Utf8Path, Utf8PathBuf};
+use colored::control::SHOULD_COLORIZE;
 use colored::{ColoredString, Colorize};
+use regex::Regex;
 use std::borrow::Cow;
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 use std::sync::{LazyLock, OnceLock, RwLock};
 use terminal_size::terminal_size;
@@ -26,6 +29,11 @@ static LOG_STATE: LazyLock<RwLock<LogState>> = LazyLock::new(RwLock::default);
 static TERMINAL_WIDTH: OnceLock<Option<usize>> = OnceLock::new();
 static WRAP_PADDING: usize = 2;
 
+thread_local! {
+    static THREAD_LOG_STATE: RefCell<Option<ThreadLogState>> = const { RefCell::new(None) };
+    static THREAD_LOG_CAPTURE: RefCell<Option<LogCaptureState>> = const { RefCell::new(None) };
+}
+
 fn terminal_width() -> Option<usize> {
     *TERMINAL_WIDTH.get_or_init(|| terminal_size().map(|(width, _)| width.0 as usize))
 }
@@ -85,16 +93,131 @@ impl Default for LogState {
     }
 }
 
+struct ThreadLogState {
+    prefix: String,
+    indents: Vec<Option<String>>,
+}
+
+impl ThreadLogState {
+    fn indent(&self) -> String {
+        let mut indent = self.prefix.clone();
+        for custom_indent in &self.indents {
+            indent.push_str(custom_indent.as_deref().unwrap_or("  "))
+        }
+        indent
+    }
+}
+
+/// Returns None if the current thread does not use a thread specific log state
+fn with_thread_log_state<R, F: FnOnce(&mut ThreadLogState) -> R>(f: F) -> Option<R> {
+    THREAD_LOG_STATE.with_borrow_mut(|state| state.as_mut().map(f))
+}
+
+/// Prefixes all log lines of the current thread, so the interleaved output of tasks running
+/// in parallel stays attributable. While active, indents are tracked per thread, starting from
+/// the global indent at the time of creation.
+pub struct LogThreadPrefix;
+
+impl LogThreadPrefix {
+    pub fn new<S: AsRef<str>>(prefix: S) -> Self {
+        let base_indent = LOG_STATE.read().unwrap().calculated_indent.clone();
+        THREAD_LOG_STATE.with_borrow_mut(|state| {
+            *state = Some(ThreadLogState {
+                prefix: format!("{}{} ", base_indent, prefix.as_ref()),
+                indents: Vec::new(),
+            })
+        });
+        Self
+    }
+
+    pub fn is_active() -> bool {
+        THREAD_LOG_STATE.with_borrow(|state| state.is_some())
+    }
+}
+
+impl Drop for LogThreadPrefix {
+    fn drop(&mut self) {
+        THREAD_LOG_STATE.with_borrow_mut(|state| *state = None);
+    }
+}
+
+static ANSI_COLOR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").expect("Failed to compile ANSI color pattern"));
+
+struct LogCaptureState {
+    max_width: Option<usize>,
+    colors: bool,
+    output: String,
+}
+
+/// Captures the log lines of the current thread instead of writing them to the log output,
+/// wrapped as if the terminal had the given width. Used for testing the rendering of views,
+/// indents are tracked per thread while active.
+///
+/// Colors are decided by the capture instead of the process-wide setting of `colored`: without
+/// colors, the color codes are stripped from the captured lines, and [should_colorize] is false
+/// on the capturing thread.
+pub struct LogCapture;
+
+impl LogCapture {
+    pub fn new(terminal_width: Option<usize>, colors: bool) -> Self {
+        THREAD_LOG_STATE.with_borrow_mut(|state| {
+            *state = Some(ThreadLogState {
+                prefix: String::new(),
+                indents: Vec::new(),
+            })
+        });
+        THREAD_LOG_CAPTURE.with_borrow_mut(|capture| {
+            *capture = Some(LogCaptureState {
+                max_width: terminal_width.map(|width| width.saturating_sub(WRAP_PADDING)),
+                colors,
+                output: String::new(),
+            })
+        });
+        Self
+    }
+
+    pub fn output(&self) -> String {
+        THREAD_LOG_CAPTURE.with_borrow(|capture| {
+            capture
+                .as_ref()
+                .map(|capture| capture.output.clone())
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        THREAD_LOG_CAPTURE.with_borrow_mut(|capture| *capture = None);
+        THREAD_LOG_STATE.with_borrow_mut(|state| *state = None);
+    }
+}
+
+/// Whether colors and color-only decorations should be used, log captures decide it for
+/// their thread
+pub fn should_colorize() -> bool {
+    THREAD_LOG_CAPTURE
+        .with_borrow(|capture| capture.as_ref().map(|capture| capture.colors))
+        .unwrap_or_else(|| SHOULD_COLORIZE.should_colorize())
+}
+
 pub struct LogIndent;
 
 impl LogIndent {
     pub fn new() -> Self {
-        LOG_STATE.write().unwrap().inc_indent(None);
+        if with_thread_log_state(|state| state.indents.push(None)).is_none() {
+            LOG_STATE.write().unwrap().inc_indent(None);
+        }
         Self
     }
 
     pub fn prefix<S: AsRef<str>>(prefix: S) -> Self {
-        LOG_STATE.write().unwrap().inc_indent(Some(prefix.as_ref()));
+        if with_thread_log_state(|state| state.indents.push(Some(prefix.as_ref().to_string())))
+            .is_none()
+        {
+            LOG_STATE.write().unwrap().inc_indent(Some(prefix.as_ref()));
+        }
         Self
     }
 }
@@ -107,8 +230,9 @@ impl Default for LogIndent {
 
 impl Drop for LogIndent {
     fn drop(&mut self) {
-        let mut state = LOG_STATE.write().unwrap();
-        state.dec_indent();
+        if with_thread_log_state(|state| state.indents.pop()).is_none() {
+            LOG_STATE.write().unwrap().dec_indent();
+        }
     }
 }
 
@@ -162,7 +286,32 @@ pub fn logln<T: AsRef<str>>(message: T) {
 pub fn logln_internal(message: &str) {
     let state = LOG_STATE.read().unwrap();
 
-    let lines = match state.max_width {
+    let thread_indent = with_thread_log_state(|state| state.indent());
+    let indent = thread_indent
+        .as_deref()
+        .unwrap_or(state.calculated_indent.as_str());
+
+    let capture = THREAD_LOG_CAPTURE.with_borrow(|capture| {
+        capture.as_ref().map(|capture| {
+            (
+                capture
+                    .max_width
+                    .map(|width| width.saturating_sub(indent.len())),
+                capture.colors,
+            )
+        })
+    });
+    let max_width = match capture {
+        Some((capture_max_width, _)) => capture_max_width,
+        None => state.max_width,
+    };
+    let message = match capture {
+        Some((_, false)) => ANSI_COLOR.replace_all(message, ""),
+        _ => Cow::from(message),
+    };
+    let message = message.as_ref();
+
+    let lines = match max_width {
         Some(width) if width <= message.len() && !message.contains("\n") => {
             textwrap::wrap(
                 message,
@@ -178,16 +327,27 @@ pub fn logln_internal(message: &str) {
     };
 
     for line in lines {
+        let captured = THREAD_LOG_CAPTURE.with_borrow_mut(|capture| match capture {
+            Some(capture) => {
+                capture.output.push_str(&format!("{indent}{line}\n"));
+                true
+            }
+            None => false,
+        });
+        if captured {
+            continue;
+        }
+
         match state.output {
             Output::Stdout => {
-                println!("{}{}", state.calculated_indent, line)
+                println!("{indent}{line}")
             }
             Output::Stderr => {
-                eprintln!("{}{}", state.calculated_indent, line)
+                eprintln!("{indent}{line}")
             }
             Output::None => {}
             Output::TracingDebug => {
-                debug!("{}{}", state.calculated_indent, line);
+                debug!("{indent}{line}");
             }
         }
     }
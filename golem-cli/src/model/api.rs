@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use anyhow::bail;
+use crate::fs;
+use crate::model::app_raw::HttpApiDeployment;
+use anyhow::{anyhow, bail, Context};
 use chrono::{DateTime, Utc};
 use golem_client::model::{ApiDefinitionInfo, ApiSite, MethodPattern, Provider};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use uuid::Uuid;
 
@@ -169,6 +173,40 @@ impl From<golem_client::model::ApiDeployment> for ApiDeployment {
     }
 }
 
+/// Desired state of API deployments, as used by `api deployment apply`.
+///
+/// Uses the same deployment entry format as the application manifest, but every
+/// definition must be referenced with an explicit version (`<name>@<version>`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct HttpApiDeploymentsFile {
+    #[serde(default)]
+    pub deployments: Vec<HttpApiDeployment>,
+}
+
+impl HttpApiDeploymentsFile {
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let file: Self = serde_yaml::from_str(&content)
+            .with_context(|| anyhow!("Failed to parse deployments file: {}", path.display()))?;
+
+        for deployment in &file.deployments {
+            for definition in &deployment.definitions {
+                if ApiDefinitionIdWithVersion::from_str(definition).is_err() {
+                    bail!(
+                        "Invalid API definition reference {} for host {} in {}, expected format: <name>@<version>",
+                        definition,
+                        deployment.host,
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        Ok(file)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ApiSecurityScheme {
     #[serde(rename = "schemeIdentifier")]
@@ -208,3 +246,304 @@ pub fn to_method_pattern(method: &str) -> anyhow::Result<MethodPattern> {
         _ => bail!("Invalid method: {}", method),
     })
 }
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HttpRouteSelector {
+    pub method: String,
+    pub path: String,
+}
+
+impl HttpRouteSelector {
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        self.method.eq_ignore_ascii_case(method) && self.path == path
+    }
+}
+
+impl Display for HttpRouteSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+impl FromStr for HttpRouteSelector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((method, path)) = s.trim().split_once(char::is_whitespace) else {
+            return Err(format!(
+                "Invalid route: {s}. Expected format: \"<METHOD> <PATH>\", e.g. \"GET /users/{{id}}\""
+            ));
+        };
+
+        let method = method.to_uppercase();
+        to_method_pattern(&method).map_err(|err| err.to_string())?;
+
+        Ok(HttpRouteSelector {
+            method,
+            path: path.trim().to_string(),
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum HttpRequestBody {
+    Inline(String),
+    File(PathBuf),
+}
+
+impl HttpRequestBody {
+    pub fn read_to_string(&self) -> anyhow::Result<String> {
+        match self {
+            HttpRequestBody::Inline(body) => Ok(body.clone()),
+            HttpRequestBody::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+impl FromStr for HttpRequestBody {
+    type Err = core::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('@') {
+            Some(path) => Ok(HttpRequestBody::File(PathBuf::from(path))),
+            None => Ok(HttpRequestBody::Inline(s.to_string())),
+        }
+    }
+}
+
+/// Renders a route path pattern (e.g. `/users/{id}?{filter}`) using the provided parameters.
+///
+/// Returns the rendered path and the query parameters. Parameters that are not used
+/// in the path pattern are returned as additional query parameters.
+///
+/// Path parameter values are percent-encoded, so values containing e.g. '/', '?' or '#' cannot
+/// change the target route. Catch-all parameters (`{+rest}`) can span multiple segments, so
+/// their '/' separators are kept, and only the segments are encoded.
+pub fn render_route_path(
+    pattern: &str,
+    params: &[(String, String)],
+) -> anyhow::Result<(String, Vec<(String, String)>)> {
+    let mut unused_params = params.iter().cloned().collect::<BTreeMap<_, _>>();
+
+    let (path_pattern, query_pattern) = match pattern.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (pattern, None),
+    };
+
+    let mut path_segments = Vec::<String>::new();
+    for segment in path_pattern.split('/') {
+        match route_pattern_var(segment) {
+            Some(var) => match unused_params.remove(var) {
+                Some(value) if segment.starts_with("{+") => path_segments.push(
+                    value
+                        .split('/')
+                        .map(encode_path_segment)
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                ),
+                Some(value) => path_segments.push(encode_path_segment(&value)),
+                None => bail!("Missing value for path parameter {}", var),
+            },
+            None => path_segments.push(segment.to_string()),
+        }
+    }
+
+    let mut query = Vec::<(String, String)>::new();
+    for query_part in query_pattern.into_iter().flat_map(|query| query.split('&')) {
+        if let Some(var) = route_pattern_var(query_part) {
+            if let Some(value) = unused_params.remove(var) {
+                query.push((var.to_string(), value));
+            }
+        }
+    }
+    query.extend(unused_params);
+
+    Ok((path_segments.join("/"), query))
+}
+
+/// Percent-encodes everything except the unreserved and sub-delimiter characters, and ':' and
+/// '@', which are allowed in path segments (RFC 3986)
+fn encode_path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~!$&'()*+,;=:@".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+pub fn route_pattern_var(segment: &str) -> Option<&str> {
+    segment
+        .strip_prefix('{')
+        .and_then(|segment| segment.strip_suffix('}'))
+        .map(|var| var.trim_start_matches('+'))
+}
+
+pub const DEFAULT_CORS_ALLOW_ORIGIN: &str = "*";
+pub const DEFAULT_CORS_ALLOW_METHODS: &str = "GET, POST, PUT, DELETE, OPTIONS";
+pub const DEFAULT_CORS_ALLOW_HEADERS: &str = "Content-Type, Authorization";
+
+/// CORS policy, served by a `cors-preflight` binding on the OPTIONS route of a path
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpCorsPolicy {
+    pub allow_origin: String,
+    pub allow_methods: String,
+    pub allow_headers: String,
+    pub expose_headers: Option<String>,
+    pub max_age: Option<u64>,
+    pub allow_credentials: Option<bool>,
+}
+
+impl HttpCorsPolicy {
+    pub fn to_rib(&self) -> anyhow::Result<String> {
+        let mut fields = vec![
+            format!(
+                "Access-Control-Allow-Origin: {}",
+                rib_string(&self.allow_origin)?
+            ),
+            format!(
+                "Access-Control-Allow-Methods: {}",
+                rib_string(&self.allow_methods)?
+            ),
+            format!(
+                "Access-Control-Allow-Headers: {}",
+                rib_string(&self.allow_headers)?
+            ),
+        ];
+        if let Some(expose_headers) = &self.expose_headers {
+            fields.push(format!(
+                "Access-Control-Expose-Headers: {}",
+                rib_string(expose_headers)?
+            ));
+        }
+        if let Some(max_age) = self.max_age {
+            fields.push(format!("Access-Control-Max-Age: {max_age}u64"));
+        }
+        if let Some(allow_credentials) = self.allow_credentials {
+            fields.push(format!(
+                "Access-Control-Allow-Credentials: {allow_credentials}"
+            ));
+        }
+        Ok(format!("{{ {} }}", fields.join(", ")))
+    }
+}
+
+/// Quoted Rib string literal. Rib string literals have no escape sequences, quotes end the
+/// literal and `${` starts an interpolation, so values containing those (or braces, backslashes
+/// and control characters) cannot be represented and are rejected.
+pub fn rib_string(value: &str) -> anyhow::Result<String> {
+    if let Some(char) = value
+        .chars()
+        .find(|char| matches!(char, '"' | '\\' | '$' | '{' | '}') || char.is_control())
+    {
+        bail!(
+            "Value '{}' cannot be used in a Rib string literal, as it contains {:?}",
+            value,
+            char
+        );
+    }
+    Ok(format!("\"{value}\""))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::api::{render_route_path, rib_string, HttpCorsPolicy, HttpRouteSelector};
+    use assert2::{assert, let_assert};
+    use std::str::FromStr;
+    use test_r::test;
+
+    #[test]
+    fn parse_route_selector() {
+        let_assert!(Ok(route) = HttpRouteSelector::from_str("get /users/{id}"));
+        assert!(route.method == "GET");
+        assert!(route.path == "/users/{id}");
+        assert!(route.matches("Get", "/users/{id}"));
+
+        assert!(HttpRouteSelector::from_str("/users/{id}").is_err());
+        assert!(HttpRouteSelector::from_str("FETCH /users/{id}").is_err());
+    }
+
+    #[test]
+    fn render_route_path_with_path_and_query_params() {
+        let params = vec![
+            ("id".to_string(), "42".to_string()),
+            ("filter".to_string(), "active".to_string()),
+            ("extra".to_string(), "1".to_string()),
+        ];
+
+        let_assert!(Ok((path, query)) = render_route_path("/users/{id}?{filter}", &params));
+        assert!(path == "/users/42");
+        assert!(
+            query
+                == vec![
+                    ("filter".to_string(), "active".to_string()),
+                    ("extra".to_string(), "1".to_string()),
+                ]
+        );
+    }
+
+    #[test]
+    fn render_route_path_encodes_path_params() {
+        let params = vec![
+            ("id".to_string(), "a/b?c#d e%".to_string()),
+            ("rest".to_string(), "docs/a b/c?d".to_string()),
+        ];
+
+        let_assert!(Ok((path, query)) = render_route_path("/users/{id}/files/{+rest}", &params));
+        assert!(path == "/users/a%2Fb%3Fc%23d%20e%25/files/docs/a%20b/c%3Fd");
+        assert!(query.is_empty());
+    }
+
+    #[test]
+    fn render_route_path_missing_path_param() {
+        assert!(render_route_path("/users/{id}", &[]).is_err());
+    }
+
+    #[test]
+    fn cors_policy_to_rib() {
+        let policy = HttpCorsPolicy {
+            allow_origin: "https://app.example.com".to_string(),
+            allow_methods: "GET, POST".to_string(),
+            allow_headers: "Content-Type".to_string(),
+            expose_headers: None,
+            max_age: Some(3600),
+            allow_credentials: Some(true),
+        };
+
+        let_assert!(Ok(rib_expr) = policy.to_rib());
+        assert!(
+            rib_expr
+                == concat!(
+                    r#"{ Access-Control-Allow-Origin: "https://app.example.com", "#,
+                    r#"Access-Control-Allow-Methods: "GET, POST", "#,
+                    r#"Access-Control-Allow-Headers: "Content-Type", "#,
+                    r#"Access-Control-Max-Age: 3600u64, "#,
+                    r#"Access-Control-Allow-Credentials: true }"#
+                )
+        );
+        assert!(rib::from_string(rib_expr.as_str()).is_ok());
+
+        let policy = HttpCorsPolicy {
+            allow_origin: r#"https://a.example.com", Injected: "x"#.to_string(),
+            ..policy
+        };
+        assert!(policy.to_rib().is_err());
+    }
+
+    #[test]
+    fn rib_string_literals() {
+        assert!(
+            rib_string("Content-Type, Authorization").unwrap()
+                == r#""Content-Type, Authorization""#
+        );
+        assert!(rib_string("*").unwrap() == r#""*""#);
+        assert!(rib_string(r#"a"b"#).is_err());
+        assert!(rib_string(r"a\b").is_err());
+        assert!(rib_string("${request.path.user}").is_err());
+        assert!(rib_string("{x}").is_err());
+        assert!(rib_string("a\nb").is_err());
+    }
+}
@@ -9,6 +9,7 @@ use crate::validation::{ValidatedResult, ValidationBuilder};
 use crate::wasm_rpc_stubgen::naming;
 use crate::wasm_rpc_stubgen::naming::wit::package_dep_dir_name_from_parser;
 use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
+use crate::wasm_rpc_stubgen::wit_generate::PackageRename;
 use golem_common::model::{ComponentFilePathWithPermissions, ComponentFilePermissions};
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
@@ -16,6 +17,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt::Formatter;
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use strum::IntoEnumIterator;
@@ -28,10 +30,18 @@ pub const DEFAULT_CONFIG_FILE_NAME: &str = "golem.yaml";
 #[derive(Clone, Debug)]
 pub struct ApplicationConfig {
     pub skip_up_to_date_checks: bool,
+    pub frozen: bool,
+    pub backup: bool,
+    pub build_jobs: NonZeroUsize,
     pub build_profile: Option<BuildProfileName>,
     pub offline: bool,
     pub steps_filter: HashSet<AppBuildStep>,
     pub golem_rust_override: RustDependencyOverride,
+    pub template_vars: BTreeMap<String, String>,
+    /// Build cache selected by the manifest profile, overriding the application level one
+    pub build_cache: Option<app_raw::BuildCache>,
+    pub no_cache: bool,
+    pub verbose: bool,
 }
 
 impl ApplicationConfig {
@@ -183,9 +193,67 @@ pub enum AppBuildStep {
     GenRpc,
     Componentize,
     Link,
+    Optimize,
     AddMetadata,
 }
 
+/// Dependency declared in the dependsOn section of a command: either the whole build,
+/// a single build step, or another custom command
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommandDependency {
+    Build,
+    BuildStep(AppBuildStep),
+    CustomCommand(String),
+}
+
+impl CommandDependency {
+    pub fn parse(name: &str) -> Self {
+        if name == "build" {
+            return Self::Build;
+        }
+        match <AppBuildStep as clap::ValueEnum>::from_str(name, false) {
+            Ok(step) => Self::BuildStep(step),
+            Err(_) => Self::CustomCommand(name.to_string()),
+        }
+    }
+}
+
+impl Display for CommandDependency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandDependency::Build => write!(f, "build"),
+            CommandDependency::BuildStep(step) => write!(
+                f,
+                "{}",
+                clap::ValueEnum::to_possible_value(step)
+                    .expect("Missing build step name")
+                    .get_name()
+            ),
+            CommandDependency::CustomCommand(command_name) => write!(f, "{command_name}"),
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[clap(rename_all = "kebab_case")]
+pub enum ClientLanguage {
+    Rust,
+    #[value(alias = "typescript")]
+    Ts,
+    #[value(alias = "golang")]
+    Go,
+}
+
+impl Display for ClientLanguage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientLanguage::Rust => write!(f, "Rust"),
+            ClientLanguage::Ts => write!(f, "TypeScript"),
+            ClientLanguage::Go => write!(f, "Go"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AppComponentName(String);
 
@@ -309,12 +377,14 @@ impl From<&str> for TemplateName {
     }
 }
 
-pub fn includes_from_yaml_file(source: &Path) -> Vec<String> {
+/// Returns the include patterns of a manifest, relative to the directory of the manifest. Without
+/// explicit includes the root manifest includes all the manifests in its directory tree.
+pub fn includes_from_yaml_file(source: &Path, is_root: bool) -> Vec<String> {
     fs::read_to_string(source)
         .ok()
         .and_then(|source| app_raw::Application::from_yaml_str(source.as_str()).ok())
         .map(|app| {
-            if app.includes.is_empty() {
+            if app.includes.is_empty() && is_root {
                 vec!["**/golem.yaml".to_string()]
             } else {
                 app.includes
@@ -438,9 +508,27 @@ impl Display for DependencyType {
 
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum BinaryComponentSource {
-    AppComponent { name: AppComponentName },
-    LocalFile { path: PathBuf },
-    Url { url: Url },
+    AppComponent {
+        name: AppComponentName,
+    },
+    LocalFile {
+        path: PathBuf,
+    },
+    Url {
+        url: Url,
+    },
+    /// Component deployed to the server, the latest version is used if the version is not set
+    Deployed {
+        name: AppComponentName,
+        version: Option<u64>,
+    },
+    /// Stub published to an OCI registry, the latest version is used if the semver requirement
+    /// is not set
+    Registry {
+        name: AppComponentName,
+        registry: String,
+        version: Option<String>,
+    },
 }
 
 impl Display for BinaryComponentSource {
@@ -449,6 +537,16 @@ impl Display for BinaryComponentSource {
             BinaryComponentSource::AppComponent { name } => write!(f, "{name}"),
             BinaryComponentSource::LocalFile { path } => write!(f, "{}", path.display()),
             BinaryComponentSource::Url { url } => write!(f, "{url}"),
+            BinaryComponentSource::Deployed { name, version } => match version {
+                Some(version) => write!(f, "{name}@{version}"),
+                None => write!(f, "{name}"),
+            },
+            BinaryComponentSource::Registry {
+                registry, version, ..
+            } => match version {
+                Some(version) => write!(f, "{registry}@{version}"),
+                None => write!(f, "{registry}"),
+            },
         }
     }
 }
@@ -469,6 +567,23 @@ impl DependentComponent {
             _ => None,
         }
     }
+
+    /// Returns the target component of a WASM RPC dependency, for which a client is generated,
+    /// for application, deployed and registry components
+    pub fn as_rpc_client_target(&self) -> Option<DependentAppComponent> {
+        if !self.dep_type.is_wasm_rpc() {
+            return None;
+        }
+        match &self.source {
+            BinaryComponentSource::AppComponent { name }
+            | BinaryComponentSource::Deployed { name, .. }
+            | BinaryComponentSource::Registry { name, .. } => Some(DependentAppComponent {
+                name: name.clone(),
+                dep_type: self.dep_type,
+            }),
+            _ => None,
+        }
+    }
 }
 
 impl PartialOrd for DependentComponent {
@@ -503,11 +618,20 @@ impl Ord for DependentAppComponent {
 #[derive(Clone, Debug)]
 pub struct Application {
     all_sources: BTreeSet<PathBuf>,
+    variables: BTreeMap<String, String>,
+    version: Option<String>,
     temp_dir: Option<WithSource<String>>,
     wit_deps: WithSource<Vec<String>>,
+    fetch_wit_deps: WithSource<BTreeMap<String, app_raw::FetchWitDep>>,
+    client_naming: naming::wit::ClientNaming,
+    build_cache: Option<WithSource<app_raw::BuildCache>>,
     components: BTreeMap<AppComponentName, Component>,
     dependencies: BTreeMap<AppComponentName, BTreeSet<DependentComponent>>,
     dependency_sources: BTreeMap<AppComponentName, BTreeMap<AppComponentName, PathBuf>>,
+    dependency_golem_rust_overrides:
+        BTreeMap<AppComponentName, WithSource<app_raw::GolemRustOverride>>,
+    dependency_package_renames:
+        BTreeMap<AppComponentName, BTreeMap<AppComponentName, Vec<PackageRename>>>,
     no_dependencies: BTreeSet<DependentComponent>,
     custom_commands: HashMap<String, WithSource<Vec<app_raw::ExternalCommand>>>,
     clean: Vec<WithSource<String>>,
@@ -516,6 +640,7 @@ pub struct Application {
         ProfileName,
         BTreeMap<HttpApiDeploymentSite, WithSource<app_raw::HttpApiDeployment>>,
     >,
+    tests: BTreeMap<String, WithSource<app_raw::TestCase>>,
 }
 
 impl Application {
@@ -528,14 +653,24 @@ impl Application {
     pub fn from_raw_apps(
         available_profiles: &BTreeSet<ProfileName>,
         apps: Vec<app_raw::ApplicationWithSource>,
+        variable_overrides: &BTreeMap<String, String>,
     ) -> ValidatedResult<Self> {
-        build_application(available_profiles, apps)
+        build_application(available_profiles, apps, variable_overrides)
     }
 
     pub fn all_sources(&self) -> &BTreeSet<PathBuf> {
         &self.all_sources
     }
 
+    /// Manifest variables, already overridden by the ones passed as arguments
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
     pub fn component_names(&self) -> impl Iterator<Item = &AppComponentName> {
         self.components.keys()
     }
@@ -559,12 +694,33 @@ impl Application {
     }
 
     pub fn wit_deps(&self) -> Vec<PathBuf> {
-        self.wit_deps
+        let mut wit_deps = self
+            .wit_deps
             .value
             .iter()
             .cloned()
             .map(|path| self.wit_deps.source.join(path))
-            .collect()
+            .collect::<Vec<_>>();
+        if !self.fetch_wit_deps.value.is_empty() {
+            wit_deps.push(self.fetched_wit_deps_dir());
+        }
+        wit_deps
+    }
+
+    pub fn fetch_wit_deps(&self) -> &BTreeMap<String, app_raw::FetchWitDep> {
+        &self.fetch_wit_deps.value
+    }
+
+    pub fn fetched_wit_deps_dir(&self) -> PathBuf {
+        self.temp_dir().join("wit-deps")
+    }
+
+    pub fn fetched_wit_deps_checkout_dir(&self) -> PathBuf {
+        self.temp_dir().join("wit-deps-checkout")
+    }
+
+    pub fn wit_deps_lock_file(&self) -> PathBuf {
+        self.fetch_wit_deps.source.join("golem-wit-deps.lock")
     }
 
     pub fn all_dependencies(&self) -> BTreeSet<DependentComponent> {
@@ -599,6 +755,38 @@ impl Application {
         custom_commands
     }
 
+    /// Collects the dependencies declared by the common and component level steps of a custom command
+    pub fn custom_command_dependencies(
+        &self,
+        command_name: &str,
+        profile: Option<&BuildProfileName>,
+    ) -> Vec<CommandDependency> {
+        let common_steps = self
+            .custom_commands
+            .get(command_name)
+            .into_iter()
+            .flat_map(|command| command.value.iter());
+        let component_steps = self.component_names().flat_map(|component_name| {
+            self.component_properties(component_name, profile)
+                .custom_commands
+                .get(command_name)
+                .into_iter()
+                .flatten()
+        });
+
+        let mut dependencies = Vec::<CommandDependency>::new();
+        for dependency in common_steps
+            .chain(component_steps)
+            .flat_map(|step| step.depends_on.iter())
+            .map(|name| CommandDependency::parse(name))
+        {
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+            }
+        }
+        dependencies
+    }
+
     pub fn all_custom_commands_for_all_build_profiles(
         &self,
     ) -> BTreeMap<Option<BuildProfileName>, BTreeSet<String>> {
@@ -636,6 +824,18 @@ impl Application {
         }
     }
 
+    pub fn backup_dir(&self) -> PathBuf {
+        self.temp_dir().join("backups")
+    }
+
+    pub fn client_naming(&self) -> naming::wit::ClientNaming {
+        self.client_naming.clone()
+    }
+
+    pub fn build_cache(&self) -> Option<&WithSource<app_raw::BuildCache>> {
+        self.build_cache.as_ref()
+    }
+
     pub fn task_result_marker_dir(&self) -> PathBuf {
         self.temp_dir().join("task-results")
     }
@@ -835,6 +1035,92 @@ impl Application {
         )
     }
 
+    /// Additional WASM libraries composed into the component during linking
+    pub fn component_plugs(
+        &self,
+        component_name: &AppComponentName,
+        profile: Option<&BuildProfileName>,
+    ) -> Vec<PathBuf> {
+        let component = self.component(component_name);
+        self.component_properties(component_name, profile)
+            .plugs
+            .iter()
+            .map(|plug| component.source_dir().join(plug))
+            .collect()
+    }
+
+    /// Returns the golem-rust dependency override used for the client of the component.
+    ///
+    /// Overrides defined on the dependencies targeting the component take precedence over the
+    /// override defined in the component properties, and both take precedence over the global one.
+    /// Override paths are relative to the manifest they are defined in.
+    pub fn client_golem_rust_override(
+        &self,
+        component_name: &AppComponentName,
+        profile: Option<&BuildProfileName>,
+        global_override: &RustDependencyOverride,
+    ) -> RustDependencyOverride {
+        let (source_dir, golem_rust_override) =
+            match self.dependency_golem_rust_overrides.get(component_name) {
+                Some(golem_rust_override) => (
+                    golem_rust_override
+                        .source
+                        .parent()
+                        .unwrap_or(Path::new(".")),
+                    &golem_rust_override.value,
+                ),
+                // Deployed and registry dependency targets have no component properties
+                None if !self.contains_component(component_name) => return global_override.clone(),
+                None => {
+                    match &self
+                        .component_properties(component_name, profile)
+                        .golem_rust_override
+                    {
+                        Some(golem_rust_override) => (
+                            self.component(component_name).source_dir(),
+                            golem_rust_override,
+                        ),
+                        None => return global_override.clone(),
+                    }
+                }
+            };
+
+        RustDependencyOverride {
+            path_override: golem_rust_override
+                .path
+                .as_ref()
+                .map(|path| source_dir.join(path)),
+            version_override: golem_rust_override.version.clone(),
+        }
+    }
+
+    /// Package renames applied to the WIT files copied from the client of the target component
+    /// into the component
+    pub fn dependency_package_renames(
+        &self,
+        component_name: &AppComponentName,
+        target_name: &AppComponentName,
+    ) -> Vec<PackageRename> {
+        self.dependency_package_renames
+            .get(component_name)
+            .and_then(|renames| renames.get(target_name))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// WASI preview1 adapter used for turning a core WASM module into a component during linking
+    pub fn component_wasi_adapter(
+        &self,
+        component_name: &AppComponentName,
+        profile: Option<&BuildProfileName>,
+    ) -> Option<PathBuf> {
+        let component = self.component(component_name);
+        self.component_properties(component_name, profile)
+            .wasi_adapter
+            .as_ref()
+            .map(|adapter| component.source_dir().join(adapter))
+    }
+
     /// Temporary target of the component composition (linking) step
     pub fn component_temp_linked_wasm(&self, component_name: &AppComponentName) -> PathBuf {
         self.temp_dir()
@@ -842,6 +1128,30 @@ impl Application {
             .join(format!("{}.wasm", component_name.as_str()))
     }
 
+    pub fn component_temp_optimized_wasm(&self, component_name: &AppComponentName) -> PathBuf {
+        self.temp_dir()
+            .join("temp-optimized-wasm")
+            .join(format!("{}.wasm", component_name.as_str()))
+    }
+
+    /// The input of adding metadata: the optimized WASM if optimization is enabled for the
+    /// component, otherwise the linked one
+    pub fn component_temp_metadata_input_wasm(
+        &self,
+        component_name: &AppComponentName,
+        profile: Option<&BuildProfileName>,
+    ) -> PathBuf {
+        if self
+            .component_properties(component_name, profile)
+            .optimize
+            .is_some()
+        {
+            self.component_temp_optimized_wasm(component_name)
+        } else {
+            self.component_temp_linked_wasm(component_name)
+        }
+    }
+
     fn client_build_dir(&self) -> PathBuf {
         self.temp_dir().join("client")
     }
@@ -858,6 +1168,54 @@ impl Application {
             .join("client.wasm")
     }
 
+    /// Stores the hash of the source WIT the client was generated from
+    pub fn client_source_wit_hash(&self, component_name: &AppComponentName) -> PathBuf {
+        self.client_build_dir()
+            .join(self.component_name_as_safe_path_elem(component_name))
+            .join("source-wit.hash")
+    }
+
+    /// WIT reconstructed from a deployed component, used as the source of its client
+    pub fn deployed_component_wit(&self, component_name: &AppComponentName) -> PathBuf {
+        self.client_build_dir()
+            .join(self.component_name_as_safe_path_elem(component_name))
+            .join("deployed-wit")
+    }
+
+    /// Version and type of a deployed component, recorded when fetching its WIT
+    pub fn deployed_component_info(&self, component_name: &AppComponentName) -> PathBuf {
+        self.client_build_dir()
+            .join(self.component_name_as_safe_path_elem(component_name))
+            .join("deployed.yaml")
+    }
+
+    /// Stub fetched from a registry, containing its WIT and stub info
+    pub fn registry_stub_dir(&self, component_name: &AppComponentName) -> PathBuf {
+        self.client_build_dir()
+            .join(self.component_name_as_safe_path_elem(component_name))
+            .join("registry-stub")
+    }
+
+    /// Lockfile pinning the resolved versions and digests of the registry stubs
+    pub fn registry_stubs_lock_file(&self) -> PathBuf {
+        PathBuf::from("golem-stubs.lock")
+    }
+
+    /// Returns the registry dependency targets, deduplicated by component name
+    pub fn registry_dependency_targets(&self) -> BTreeMap<&AppComponentName, (&str, Option<&str>)> {
+        self.all_dependencies()
+            .iter()
+            .filter_map(|dep| match &dep.source {
+                BinaryComponentSource::Registry {
+                    name,
+                    registry,
+                    version,
+                } => Some((name, (registry.as_str(), version.as_deref()))),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn client_wit(&self, component_name: &AppComponentName) -> PathBuf {
         self.client_build_dir()
             .join(self.component_name_as_safe_path_elem(component_name))
@@ -919,6 +1277,10 @@ impl Application {
     ) -> Option<&BTreeMap<HttpApiDeploymentSite, WithSource<app_raw::HttpApiDeployment>>> {
         self.http_api_deployments.get(profile)
     }
+
+    pub fn tests(&self) -> &BTreeMap<String, WithSource<app_raw::TestCase>> {
+        &self.tests
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -949,6 +1311,8 @@ pub struct ComponentProperties {
     pub generated_wit: String,
     pub component_wasm: String,
     pub linked_wasm: Option<String>,
+    pub plugs: Vec<String>,
+    pub wasi_adapter: Option<String>,
     pub build: Vec<app_raw::BuildCommand>,
     pub custom_commands: HashMap<String, Vec<app_raw::ExternalCommand>>,
     pub clean: Vec<String>,
@@ -956,6 +1320,9 @@ pub struct ComponentProperties {
     pub files: Vec<InitialComponentFile>,
     pub plugins: Vec<PluginInstallation>,
     pub env: HashMap<String, String>,
+    pub golem_rust_override: Option<app_raw::GolemRustOverride>,
+    pub optimize: Option<app_raw::Optimize>,
+    pub test_for: Option<AppComponentName>,
 }
 
 impl ComponentProperties {
@@ -972,6 +1339,8 @@ impl ComponentProperties {
             generated_wit: raw.generated_wit.unwrap_or_default(),
             component_wasm: raw.component_wasm.unwrap_or_default(),
             linked_wasm: raw.linked_wasm,
+            plugs: raw.plugs,
+            wasi_adapter: raw.wasi_adapter,
             build: raw.build,
             custom_commands: raw.custom_commands,
             clean: raw.clean,
@@ -979,6 +1348,9 @@ impl ComponentProperties {
             files,
             plugins,
             env: Self::validate_and_normalize_env(validation, raw.env),
+            golem_rust_override: raw.golem_rust_override,
+            optimize: raw.optimize,
+            test_for: raw.test_for.map(AppComponentName::from),
         })
     }
 
@@ -1020,6 +1392,14 @@ impl ComponentProperties {
             self.linked_wasm = overrides.linked_wasm;
         }
 
+        if !overrides.plugs.is_empty() {
+            self.plugs = overrides.plugs;
+        }
+
+        if overrides.wasi_adapter.is_some() {
+            self.wasi_adapter = overrides.wasi_adapter;
+        }
+
         if !overrides.build.is_empty() {
             self.build = overrides.build;
         }
@@ -1059,6 +1439,18 @@ impl ComponentProperties {
                 .extend(Self::validate_and_normalize_env(validation, overrides.env));
         }
 
+        if overrides.golem_rust_override.is_some() {
+            self.golem_rust_override = overrides.golem_rust_override;
+        }
+
+        if overrides.optimize.is_some() {
+            self.optimize = overrides.optimize;
+        }
+
+        if overrides.test_for.is_some() {
+            self.test_for = overrides.test_for.map(AppComponentName::from);
+        }
+
         Ok((!any_errors).then_some(self))
     }
 
@@ -1254,13 +1646,15 @@ mod app_builder {
     };
     use crate::model::app_raw;
     use crate::model::deploy_diff::api_definition::normalize_http_api_binding_path;
+    use crate::model::template::{
+        contains_template, template_env, Template, COMPONENT_TEMPLATE_VARIABLES,
+        EXTERNAL_COMMAND_VARIABLES,
+    };
     use crate::model::text::fmt::format_rib_source_for_error;
     use crate::validation::{ValidatedResult, ValidationBuilder};
+    use crate::wasm_rpc_stubgen::naming;
+    use crate::wasm_rpc_stubgen::wit_generate::PackageRename;
     use colored::Colorize;
-    use heck::{
-        ToKebabCase, ToLowerCamelCase, ToPascalCase, ToShoutyKebabCase, ToShoutySnakeCase,
-        ToSnakeCase, ToTitleCase, ToTrainCase, ToUpperCamelCase,
-    };
     use itertools::Itertools;
     use serde::Serialize;
     use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
@@ -1273,8 +1667,9 @@ mod app_builder {
     pub fn build_application(
         available_profiles: &BTreeSet<ProfileName>,
         apps: Vec<app_raw::ApplicationWithSource>,
+        variable_overrides: &BTreeMap<String, String>,
     ) -> ValidatedResult<Application> {
-        AppBuilder::build_app(available_profiles, apps)
+        AppBuilder::build_app(available_profiles, apps, variable_overrides)
     }
 
     // Load only profiles
@@ -1286,9 +1681,14 @@ mod app_builder {
 
     #[derive(Debug, PartialEq, Eq, Hash)]
     enum UniqueSourceCheckedEntityKey {
-        Include,
+        Version,
         TempDir,
         WitDeps,
+        FetchWitDeps,
+        Variable(String),
+        StubSuffix,
+        StubFileName,
+        BuildCache,
         CustomCommand(String),
         Template(TemplateName),
         Dependency((AppComponentName, DependentComponent)),
@@ -1304,15 +1704,21 @@ mod app_builder {
             site: HttpApiDeploymentSite,
         },
         Profile(ProfileName),
+        Test(String),
     }
 
     impl UniqueSourceCheckedEntityKey {
         fn entity_kind(&self) -> &'static str {
             let property = "Property";
             match self {
-                UniqueSourceCheckedEntityKey::Include => property,
+                UniqueSourceCheckedEntityKey::Version => property,
                 UniqueSourceCheckedEntityKey::TempDir => property,
                 UniqueSourceCheckedEntityKey::WitDeps => property,
+                UniqueSourceCheckedEntityKey::FetchWitDeps => property,
+                UniqueSourceCheckedEntityKey::Variable(_) => "Variable",
+                UniqueSourceCheckedEntityKey::StubSuffix => property,
+                UniqueSourceCheckedEntityKey::StubFileName => property,
+                UniqueSourceCheckedEntityKey::BuildCache => property,
                 UniqueSourceCheckedEntityKey::CustomCommand(_) => "Custom command",
                 UniqueSourceCheckedEntityKey::Template(_) => "Template",
                 UniqueSourceCheckedEntityKey::Dependency(_) => "Dependency",
@@ -1323,13 +1729,14 @@ mod app_builder {
                 }
                 UniqueSourceCheckedEntityKey::HttpApiDeployment { .. } => "HTTP API Deployment",
                 UniqueSourceCheckedEntityKey::Profile(_) => "Profile",
+                UniqueSourceCheckedEntityKey::Test(_) => "Test case",
             }
         }
 
         fn entity_name(self) -> String {
             match self {
-                UniqueSourceCheckedEntityKey::Include => {
-                    "include".log_color_highlight().to_string()
+                UniqueSourceCheckedEntityKey::Version => {
+                    "version".log_color_highlight().to_string()
                 }
                 UniqueSourceCheckedEntityKey::TempDir => {
                     "tempDir".log_color_highlight().to_string()
@@ -1337,6 +1744,21 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::WitDeps => {
                     "witDeps".log_color_highlight().to_string()
                 }
+                UniqueSourceCheckedEntityKey::FetchWitDeps => {
+                    "fetchWitDeps".log_color_highlight().to_string()
+                }
+                UniqueSourceCheckedEntityKey::Variable(name) => {
+                    name.log_color_highlight().to_string()
+                }
+                UniqueSourceCheckedEntityKey::StubSuffix => {
+                    "stubSuffix".log_color_highlight().to_string()
+                }
+                UniqueSourceCheckedEntityKey::StubFileName => {
+                    "stubFileName".log_color_highlight().to_string()
+                }
+                UniqueSourceCheckedEntityKey::BuildCache => {
+                    "buildCache".log_color_highlight().to_string()
+                }
                 UniqueSourceCheckedEntityKey::CustomCommand(command_name) => {
                     command_name.log_color_highlight().to_string()
                 }
@@ -1386,17 +1808,28 @@ mod app_builder {
                 UniqueSourceCheckedEntityKey::Profile(profile_name) => {
                     profile_name.0.log_color_highlight().to_string()
                 }
+                UniqueSourceCheckedEntityKey::Test(test_name) => {
+                    test_name.log_color_highlight().to_string()
+                }
             }
         }
     }
 
     #[derive(Default)]
     struct AppBuilder {
-        include: Vec<String>,
+        variables: BTreeMap<String, String>,
+        version: Option<String>,
         temp_dir: Option<WithSource<String>>,
         wit_deps: WithSource<Vec<String>>,
+        fetch_wit_deps: WithSource<BTreeMap<String, app_raw::FetchWitDep>>,
+        client_naming: naming::wit::ClientNaming,
+        build_cache: Option<WithSource<app_raw::BuildCache>>,
         templates: HashMap<TemplateName, app_raw::ComponentTemplate>,
         dependencies: BTreeMap<AppComponentName, BTreeSet<DependentComponent>>,
+        dependency_golem_rust_overrides:
+            BTreeMap<AppComponentName, WithSource<app_raw::GolemRustOverride>>,
+        dependency_package_renames:
+            BTreeMap<AppComponentName, BTreeMap<AppComponentName, Vec<PackageRename>>>,
         custom_commands: HashMap<String, WithSource<Vec<app_raw::ExternalCommand>>>,
         clean: Vec<WithSource<String>>,
         http_api_definitions:
@@ -1405,6 +1838,7 @@ mod app_builder {
             ProfileName,
             BTreeMap<HttpApiDeploymentSite, WithSource<app_raw::HttpApiDeployment>>,
         >,
+        tests: BTreeMap<String, WithSource<app_raw::TestCase>>,
 
         // NOTE: raw component names are available (for validation) even after component resolving
         raw_component_names: HashSet<String>,
@@ -1425,10 +1859,22 @@ mod app_builder {
         fn build_app(
             available_profiles: &BTreeSet<ProfileName>,
             apps: Vec<app_raw::ApplicationWithSource>,
+            variable_overrides: &BTreeMap<String, String>,
         ) -> ValidatedResult<Application> {
             let mut builder = Self::default();
             let mut validation = ValidationBuilder::default();
 
+            // Variables are collected first, as they are used for rendering the templated
+            // properties while adding the apps
+            for app in &apps {
+                builder.add_raw_app_variables(&mut validation, app);
+            }
+            builder.variables.extend(
+                variable_overrides
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+
             for app in apps {
                 builder.add_raw_app(&mut validation, app);
             }
@@ -1437,6 +1883,7 @@ mod app_builder {
             builder.validate_unique_sources(&mut validation);
             builder.validate_http_api_definitions(&mut validation);
             builder.validate_http_api_deployments(&mut validation, available_profiles);
+            builder.validate_tests(&mut validation);
 
             let dependency_sources = {
                 let mut dependency_sources =
@@ -1467,16 +1914,24 @@ mod app_builder {
 
             validation.build(Application {
                 all_sources: builder.all_sources,
+                variables: builder.variables,
+                version: builder.version,
                 temp_dir: builder.temp_dir,
                 wit_deps: builder.wit_deps,
+                fetch_wit_deps: builder.fetch_wit_deps,
+                client_naming: builder.client_naming,
+                build_cache: builder.build_cache,
                 components: builder.resolved_components,
                 dependencies: builder.dependencies,
                 dependency_sources,
+                dependency_golem_rust_overrides: builder.dependency_golem_rust_overrides,
+                dependency_package_renames: builder.dependency_package_renames,
                 no_dependencies: BTreeSet::new(),
                 custom_commands: builder.custom_commands,
                 clean: builder.clean,
                 http_api_definitions: builder.http_api_definitions,
                 http_api_deployments: builder.http_api_deployments,
+                tests: builder.tests,
             })
         }
 
@@ -1504,6 +1959,38 @@ mod app_builder {
             is_first
         }
 
+        fn add_raw_app_variables(
+            &mut self,
+            validation: &mut ValidationBuilder,
+            app: &app_raw::ApplicationWithSource,
+        ) {
+            validation.with_context(
+                vec![("source", app.source.to_string_lossy().to_string())],
+                |validation| {
+                    for (name, value) in &app.application.variables {
+                        if COMPONENT_TEMPLATE_VARIABLES
+                            .iter()
+                            .chain(EXTERNAL_COMMAND_VARIABLES)
+                            .any(|reserved| reserved == name)
+                        {
+                            validation.add_error(format!(
+                                "Variable {} is reserved, available for all templates by default",
+                                name.log_color_error_highlight()
+                            ));
+                            continue;
+                        }
+
+                        if self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::Variable(name.clone()),
+                            &app.source,
+                        ) {
+                            self.variables.insert(name.clone(), value.clone());
+                        }
+                    }
+                },
+            );
+        }
+
         fn add_raw_app(
             &mut self,
             validation: &mut ValidationBuilder,
@@ -1516,6 +2003,14 @@ mod app_builder {
                     let app_source_dir = app_source.parent().unwrap();
                     self.all_sources.insert(app_source.to_path_buf());
 
+                    if let Some(version) = app.application.version {
+                        if self
+                            .add_entity_source(UniqueSourceCheckedEntityKey::Version, &app.source)
+                        {
+                            self.version = Some(version);
+                        }
+                    }
+
                     if let Some(dir) = app.application.temp_dir {
                         if self
                             .add_entity_source(UniqueSourceCheckedEntityKey::TempDir, &app.source)
@@ -1525,13 +2020,6 @@ mod app_builder {
                         }
                     }
 
-                    if !app.application.includes.is_empty()
-                        && self
-                            .add_entity_source(UniqueSourceCheckedEntityKey::Include, &app.source)
-                    {
-                        self.include = app.application.includes;
-                    }
-
                     if !app.application.wit_deps.is_empty()
                         && self
                             .add_entity_source(UniqueSourceCheckedEntityKey::WitDeps, &app.source)
@@ -1540,6 +2028,91 @@ mod app_builder {
                             WithSource::new(app_source_dir.to_path_buf(), app.application.wit_deps);
                     }
 
+                    if !app.application.fetch_wit_deps.is_empty()
+                        && self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::FetchWitDeps,
+                            &app.source,
+                        )
+                    {
+                        for (name, dep) in &app.application.fetch_wit_deps {
+                            if !is_valid_fetch_wit_dep_name(name) {
+                                validation.add_error(format!(
+                                    "Invalid {} name: {}, expected a non-empty name using only alphanumeric characters, '-' and '_'",
+                                    "fetchWitDeps".log_color_highlight(),
+                                    name.log_color_error_highlight(),
+                                ));
+                            }
+                            if dep.registry.is_some() {
+                                validation.add_error(format!(
+                                    "Unsupported {} source for {} {}, fetching WIT packages from warg registries is not supported, use a {} source or vendor the package into {}",
+                                    "registry".log_color_highlight(),
+                                    "fetchWitDeps".log_color_highlight(),
+                                    name.log_color_error_highlight(),
+                                    "git".log_color_highlight(),
+                                    "wit/deps".log_color_highlight(),
+                                ));
+                            } else if dep.git.trim().is_empty() {
+                                validation.add_error(format!(
+                                    "Empty {} for {} {}",
+                                    "git".log_color_highlight(),
+                                    "fetchWitDeps".log_color_highlight(),
+                                    name.log_color_error_highlight(),
+                                ));
+                            }
+                        }
+                        self.fetch_wit_deps = WithSource::new(
+                            app_source_dir.to_path_buf(),
+                            app.application.fetch_wit_deps.into_iter().collect(),
+                        );
+                    }
+
+                    if let Some(stub_suffix) = app.application.stub_suffix {
+                        if self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::StubSuffix,
+                            &app.source,
+                        ) {
+                            if is_valid_stub_suffix(&stub_suffix) {
+                                self.client_naming.suffix = stub_suffix;
+                            } else {
+                                validation.add_error(format!(
+                                    "Invalid {}: {}, expected a kebab-case suffix starting with '-', e.g.: {}",
+                                    "stubSuffix".log_color_highlight(),
+                                    stub_suffix.log_color_error_highlight(),
+                                    naming::wit::DEFAULT_CLIENT_SUFFIX.log_color_highlight(),
+                                ));
+                            }
+                        }
+                    }
+
+                    if let Some(stub_file_name) = app.application.stub_file_name {
+                        if self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::StubFileName,
+                            &app.source,
+                        ) {
+                            if is_valid_stub_file_name(&stub_file_name) {
+                                self.client_naming.wit_file_name = stub_file_name;
+                            } else {
+                                validation.add_error(format!(
+                                    "Invalid {}: {}, expected a file name with .wit extension, e.g.: {}",
+                                    "stubFileName".log_color_highlight(),
+                                    stub_file_name.log_color_error_highlight(),
+                                    naming::wit::DEFAULT_CLIENT_WIT_FILE_NAME.log_color_highlight(),
+                                ));
+                            }
+                        }
+                    }
+
+                    if let Some(build_cache) = app.application.build_cache {
+                        if self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::BuildCache,
+                            &app.source,
+                        ) {
+                            validate_build_cache(validation, &build_cache);
+                            self.build_cache =
+                                Some(WithSource::new(app_source_dir.to_path_buf(), build_cache));
+                        }
+                    }
+
                     for (template_name, template) in app.application.templates {
                         self.add_and_resolve_raw_template(
                             validation,
@@ -1655,6 +2228,18 @@ mod app_builder {
                             }
                         }
                     }
+
+                    for (test_name, test_case) in app.application.tests {
+                        if self.add_entity_source(
+                            UniqueSourceCheckedEntityKey::Test(test_name.clone()),
+                            &app.source,
+                        ) {
+                            self.tests.insert(
+                                test_name,
+                                WithSource::new(app.source.to_path_buf(), test_case),
+                            );
+                        }
+                    }
                 },
             );
         }
@@ -1717,6 +2302,9 @@ mod app_builder {
                                             &profile.worker_url,
                                         );
                                     }
+                                    if let Some(build_cache) = &profile.build_cache {
+                                        validate_build_cache(validation, build_cache);
+                                    }
                                     if is_builtin_local && is_cloud {
                                         validation.add_error(format!(
                                             "Builtin profile '{}' cannot be used as Cloud profile, using 'cloud:true' or project are not allowed!",
@@ -1806,11 +2394,48 @@ mod app_builder {
                     let dep_type = DependencyType::from_str(&dependency.type_);
                     if let Ok(dep_type) = dep_type {
                         let binary_component_source = match (dependency.target, dependency.path, dependency.url) {
-                            (Some(target_name), None, None) => {
+                            (Some(app_raw::DependencyTarget::AppComponent(target_name)), None, None) => {
                                 Some(BinaryComponentSource::AppComponent {
                                     name: target_name.into(),
                                 })
                             }
+                            (Some(app_raw::DependencyTarget::Deployed(target)), None, None) => {
+                                Some(BinaryComponentSource::Deployed {
+                                    name: target.component.into(),
+                                    version: target.version,
+                                })
+                            }
+                            (Some(app_raw::DependencyTarget::Registry(target)), None, None) => {
+                                let valid_registry = target.registry.starts_with("oci://");
+                                if !valid_registry {
+                                    validation.add_error(format!(
+                                        "Invalid registry reference for component dependency: {}, only OCI references starting with {} are supported",
+                                        target.registry.log_color_error_highlight(),
+                                        "oci://".log_color_highlight()
+                                    ));
+                                }
+                                let valid_version = match &target.version {
+                                    Some(version) => match semver::VersionReq::parse(version) {
+                                        Ok(_) => true,
+                                        Err(err) => {
+                                            validation.add_error(format!(
+                                                "Invalid semver requirement for component dependency: {}, {}",
+                                                version.log_color_error_highlight(),
+                                                err
+                                            ));
+                                            false
+                                        }
+                                    },
+                                    None => true,
+                                };
+                                (valid_registry && valid_version).then(|| {
+                                    BinaryComponentSource::Registry {
+                                        name: target.component.into(),
+                                        registry: target.registry,
+                                        version: target.version,
+                                    }
+                                })
+                            }
                             (None, Some(path), None) => {
                                 Some(BinaryComponentSource::LocalFile { path: Path::new(&path).to_path_buf() })
                             }
@@ -1848,6 +2473,45 @@ mod app_builder {
                             }
                         };
 
+                        if let (
+                            Some(golem_rust_override),
+                            Some(
+                                BinaryComponentSource::AppComponent { name }
+                                | BinaryComponentSource::Deployed { name, .. }
+                                | BinaryComponentSource::Registry { name, .. },
+                            ),
+                        ) = (dependency.golem_rust_override, &binary_component_source)
+                        {
+                            self.add_dependency_golem_rust_override(
+                                validation,
+                                source,
+                                &component_name,
+                                name,
+                                golem_rust_override,
+                            );
+                        }
+
+                        if !dependency.package_renames.is_empty() {
+                            match &binary_component_source {
+                                Some(
+                                    BinaryComponentSource::AppComponent { name }
+                                    | BinaryComponentSource::Deployed { name, .. }
+                                    | BinaryComponentSource::Registry { name, .. },
+                                ) if dep_type.is_wasm_rpc() => {
+                                    self.add_dependency_package_renames(
+                                        validation,
+                                        &component_name,
+                                        name,
+                                        dependency.package_renames,
+                                    );
+                                }
+                                _ => validation.add_error(format!(
+                                    "Property {} is only supported for WASM RPC dependencies",
+                                    "packageRenames".log_color_error_highlight()
+                                )),
+                            }
+                        }
+
                         if let Some(binary_component_source) = binary_component_source {
                             let dependent_component = DependentComponent {
                                 source: binary_component_source,
@@ -1875,6 +2539,80 @@ mod app_builder {
             });
         }
 
+        /// The client of a component is shared by all of its dependents, so the overrides defined
+        /// on the dependencies targeting the same component must be the same
+        fn add_dependency_golem_rust_override(
+            &mut self,
+            validation: &mut ValidationBuilder,
+            source: &Path,
+            component_name: &str,
+            target_name: &AppComponentName,
+            golem_rust_override: app_raw::GolemRustOverride,
+        ) {
+            let golem_rust_override = match golem_rust_override.render(
+                &template_env(),
+                &self.template_context(&component_name.into(), None),
+            ) {
+                Ok(golem_rust_override) => golem_rust_override,
+                Err(err) => {
+                    validation.add_error(format!(
+                        "Failed to render golem-rust override for dependency {}: {}",
+                        target_name.as_str().log_color_highlight(),
+                        err.to_string().log_color_error_highlight()
+                    ));
+                    return;
+                }
+            };
+
+            match self.dependency_golem_rust_overrides.get(target_name) {
+                Some(existing)
+                    if existing.value != golem_rust_override || existing.source != source =>
+                {
+                    validation.add_error(format!(
+                        "Conflicting golem-rust overrides for dependencies targeting {}, defined in {} and {}",
+                        target_name.as_str().log_color_error_highlight(),
+                        existing.source.log_color_highlight(),
+                        source.log_color_highlight()
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    self.dependency_golem_rust_overrides.insert(
+                        target_name.clone(),
+                        WithSource::new(source.to_path_buf(), golem_rust_override),
+                    );
+                }
+            }
+        }
+
+        fn add_dependency_package_renames(
+            &mut self,
+            validation: &mut ValidationBuilder,
+            component_name: &str,
+            target_name: &AppComponentName,
+            package_renames: Vec<app_raw::PackageRename>,
+        ) {
+            let package_renames = package_renames
+                .into_iter()
+                .filter_map(
+                    |rename| match PackageRename::new(&rename.from, &rename.to) {
+                        Ok(rename) => Some(rename),
+                        Err(err) => {
+                            validation.add_error(err.to_string());
+                            None
+                        }
+                    },
+                )
+                .collect::<Vec<_>>();
+
+            self.dependency_package_renames
+                .entry(component_name.into())
+                .or_default()
+                .entry(target_name.clone())
+                .or_default()
+                .extend(package_renames);
+        }
+
         fn validate_unique_sources(&mut self, validation: &mut ValidationBuilder) {
             let entity_sources = std::mem::take(&mut self.entity_sources);
             entity_sources
@@ -1905,6 +2643,15 @@ mod app_builder {
                             !std::fs::exists(path).unwrap_or(false)
                         }
                         BinaryComponentSource::Url { .. } => false,
+                        BinaryComponentSource::Deployed { .. } => false,
+                        BinaryComponentSource::Registry { .. } => false,
+                    };
+                    let deployed_app_component = match &target.source {
+                        BinaryComponentSource::Deployed { name, .. }
+                        | BinaryComponentSource::Registry { name, .. } => {
+                            self.raw_component_names.contains(&name.0)
+                        }
+                        _ => false,
                     };
                     let invalid_target_source = match (&target.dep_type, &target.source) {
                         (
@@ -1919,13 +2666,36 @@ mod app_builder {
                         ) => {
                             false // valid
                         }
+                        (
+                            DependencyType::DynamicWasmRpc,
+                            BinaryComponentSource::Deployed { .. },
+                        ) => {
+                            false // valid
+                        }
+                        (
+                            DependencyType::DynamicWasmRpc,
+                            BinaryComponentSource::Registry { .. },
+                        ) => {
+                            false // valid
+                        }
+                        (
+                            _,
+                            BinaryComponentSource::Deployed { .. }
+                            | BinaryComponentSource::Registry { .. },
+                        ) => {
+                            true // deployed and registry components are only supported as WASM RPC dependencies
+                        }
                         (DependencyType::Wasm, _) => {
                             false // valid
                         }
                         _ => true,
                     };
 
-                    if invalid_source || invalid_target || invalid_target_source {
+                    if invalid_source
+                        || invalid_target
+                        || invalid_target_source
+                        || deployed_app_component
+                    {
                         let source = self
                             .entity_sources
                             .get(&UniqueSourceCheckedEntityKey::Dependency((
@@ -1967,6 +2737,14 @@ mod app_builder {
                                         target.source.to_string().log_color_highlight(),
                                     ))
                                 }
+                                if deployed_app_component {
+                                    validation.add_error(format!(
+                                        "{} {} - {}: the deployed or registry target component is also defined in the application, use its name as target instead\n",
+                                        target.dep_type.describe(),
+                                        component.as_str().log_color_highlight(),
+                                        target.source.to_string().log_color_error_highlight(),
+                                    ))
+                                }
                             },
                         );
                     }
@@ -1974,37 +2752,33 @@ mod app_builder {
             }
         }
 
-        fn template_env<'a>() -> minijinja::Environment<'a> {
-            let mut env = minijinja::Environment::new();
-
-            env.add_filter("to_snake_case", |str: &str| str.to_snake_case());
-
-            env.add_filter("to_kebab_case", |str: &str| str.to_kebab_case());
-            env.add_filter("to_lower_camel_case", |str: &str| str.to_lower_camel_case());
-            env.add_filter("to_pascal_case", |str: &str| str.to_pascal_case());
-            env.add_filter("to_shouty_kebab_case", |str: &str| {
-                str.to_shouty_kebab_case()
-            });
-            env.add_filter("to_shouty_snake_case", |str: &str| {
-                str.to_shouty_snake_case()
-            });
-            env.add_filter("to_snake_case", |str: &str| str.to_snake_case());
-            env.add_filter("to_title_case", |str: &str| str.to_title_case());
-            env.add_filter("to_train_case", |str: &str| str.to_train_case());
-            env.add_filter("to_upper_camel_case", |str: &str| str.to_upper_camel_case());
-
-            env
-        }
-
-        fn template_context(component_name: &AppComponentName) -> impl Serialize {
+        /// For profiled templates the profile is exposed in the context, otherwise it is resolved
+        /// only when executing the commands, based on the selected build profile
+        fn template_context(
+            &self,
+            component_name: &AppComponentName,
+            profile: Option<&str>,
+        ) -> impl Serialize {
+            // The external command variables are rendered as themselves, so they are kept
+            // for the rendering done when executing the commands
+            let mut variables = self.variables.clone();
+            variables.extend(
+                EXTERNAL_COMMAND_VARIABLES
+                    .iter()
+                    .map(|name| (name.to_string(), format!("{{{{ {name} }}}}"))),
+            );
+            if let Some(profile) = profile {
+                variables.insert("profile".to_string(), profile.to_string());
+            }
             minijinja::context! {
                 componentName => component_name.as_str(),
-                component_name => component_name.as_str()
+                component_name => component_name.as_str(),
+                ..minijinja::Value::from_serialize(variables)
             }
         }
 
         fn resolve_components(&mut self, validation: &mut ValidationBuilder) {
-            let template_env = Self::template_env();
+            let template_env = template_env();
 
             let components = std::mem::take(&mut self.raw_components);
 
@@ -2125,6 +2899,7 @@ mod app_builder {
                 &template_name,
                 &template.component_properties,
                 &component_name,
+                None,
                 Some(component_properties),
             )
             .map(|properties| ResolvedComponentProperties::NonProfiled {
@@ -2161,6 +2936,7 @@ mod app_builder {
                                 &template_name,
                                 template_component_properties,
                                 &component_name,
+                                Some(profile_name.as_str()),
                                 component_properties,
                             )
                             .into_iter()
@@ -2317,13 +3093,14 @@ mod app_builder {
             template_name: &TemplateName,
             template_properties: &app_raw::ComponentProperties,
             component_name: &AppComponentName,
+            profile: Option<&str>,
             component_properties: Option<app_raw::ComponentProperties>,
         ) -> Option<ComponentProperties> {
             ComponentProperties::from_raw_template(
                 validation,
                 source,
                 template_env,
-                &Self::template_context(component_name),
+                &self.template_context(component_name, profile),
                 template_properties,
             )
             .inspect_err(|err| {
@@ -2467,7 +3244,9 @@ mod app_builder {
                                         match rib_script.as_ref().map(|s| s.as_str()) {
                                             Some(rib) => {
                                                 check_not_empty(validation, property_name, rib);
-                                                if let Some(err) = rib::from_string(rib).err() {
+                                                if contains_template(rib) {
+                                                    // Templated scripts are only checked after rendering, during deploy
+                                                } else if let Some(err) = rib::from_string(rib).err() {
                                                     validation.add_error(
                                                         format!(
                                                             "Failed to parse property {} as Rib:\n{}\n{}\n{}",
@@ -2606,6 +3385,43 @@ mod app_builder {
             }
         }
 
+        fn validate_tests(&self, validation: &mut ValidationBuilder) {
+            for (name, test_case) in &self.tests {
+                validation.with_context(
+                    vec![
+                        ("source", test_case.source.to_string_lossy().to_string()),
+                        ("test", name.clone()),
+                    ],
+                    |validation| {
+                        let test_case = &test_case.value;
+
+                        if check_not_empty(validation, "component", &test_case.component)
+                            && !self.raw_component_names.contains(&test_case.component)
+                        {
+                            validation.add_error(format!(
+                                "Property {} contains unknown component name: {}\n\n{}",
+                                "component".log_color_highlight(),
+                                test_case.component.log_color_error_highlight(),
+                                self.available_components(&test_case.component)
+                            ));
+                        }
+                        if let Some(worker) = &test_case.worker {
+                            check_not_empty(validation, "worker", worker);
+                        }
+                        if test_case.steps.is_empty() {
+                            validation.add_error(format!(
+                                "Property {} is empty",
+                                "steps".log_color_highlight()
+                            ));
+                        }
+                        for step in &test_case.steps {
+                            check_not_empty(validation, "function", &step.function);
+                        }
+                    },
+                );
+            }
+        }
+
         fn available_profiles<'a, I: IntoIterator<Item = &'a str>>(
             &self,
             available_profiles: I,
@@ -2701,15 +3517,73 @@ mod app_builder {
         }
         !is_empty
     }
+
+    fn is_valid_stub_suffix(suffix: &str) -> bool {
+        suffix
+            .strip_prefix('-')
+            .map(|name| {
+                !name.is_empty()
+                    && name.split('-').all(|part| {
+                        part.chars().next().is_some_and(|c| c.is_ascii_lowercase())
+                            && part
+                                .chars()
+                                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    })
+            })
+            .unwrap_or(false)
+    }
+
+    fn is_valid_stub_file_name(file_name: &str) -> bool {
+        file_name.len() > ".wit".len()
+            && file_name.ends_with(".wit")
+            && !file_name.contains(['/', '\\'])
+            && file_name != naming::wit::EXPORTS_WIT_FILE_NAME
+    }
+
+    fn validate_build_cache(validation: &mut ValidationBuilder, build_cache: &app_raw::BuildCache) {
+        match (&build_cache.dir, &build_cache.s3) {
+            (Some(_), Some(_)) | (None, None) => validation.add_error(format!(
+                "Property {} must define exactly one of {} and {}",
+                "buildCache".log_color_highlight(),
+                "dir".log_color_highlight(),
+                "s3".log_color_highlight(),
+            )),
+            (None, Some(s3)) if !s3.starts_with("s3://") => validation.add_error(format!(
+                "Invalid {}: {}, expected an S3 URL, e.g.: {}",
+                "buildCache.s3".log_color_highlight(),
+                s3.log_color_error_highlight(),
+                "s3://bucket/prefix".log_color_highlight(),
+            )),
+            (Some(_), None) if build_cache.endpoint.is_some() => validation.add_error(format!(
+                "Property {} is only allowed together with {}",
+                "buildCache.endpoint".log_color_highlight(),
+                "buildCache.s3".log_color_highlight(),
+            )),
+            _ => {}
+        }
+    }
+
+    fn is_valid_fetch_wit_dep_name(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::model::app::{AppComponentName, Application, BuildProfileName};
+    use crate::model::app::{
+        AppComponentName, Application, BinaryComponentSource, BuildProfileName,
+    };
     use crate::model::app_raw;
     use crate::model::component::AppComponentType;
+    use crate::wasm_rpc_stubgen::stub::RustDependencyOverride;
+    use crate::wasm_rpc_stubgen::wit_generate::PackageRename;
     use assert2::{assert, check};
     use indoc::indoc;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
     use test_r::test;
 
     #[test]
@@ -2765,6 +3639,7 @@ mod test {
                 manifest.to_string(),
             )
             .unwrap()],
+            &Default::default(),
         );
 
         let (app, warns, errors) = app.into_product();
@@ -2800,4 +3675,413 @@ mod test {
         check!(release_props.component_wasm == "comp-a-component-wasm");
         check!(release_custom_props.component_wasm == "release-comp-a-component-wasm");
     }
+
+    #[test]
+    fn stub_naming_from_manifest() {
+        let manifest = indoc! {"
+            stubSuffix: -rpc
+            stubFileName: rpc.wit
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (app, _warns, errors) = app.into_product();
+        assert!(errors.is_empty(), "\n{}", errors.join("\n\n"));
+        let client_naming = app.unwrap().client_naming();
+
+        check!(client_naming.suffix == "-rpc");
+        check!(client_naming.wit_file_name == "rpc.wit");
+
+        let invalid_manifest = indoc! {"
+            stubSuffix: Rpc
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                invalid_manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (_app, _warns, errors) = app.into_product();
+        check!(errors.len() == 1);
+    }
+
+    #[test]
+    fn golem_rust_override_precedence() {
+        let manifest = indoc! {"
+            templates:
+              template:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                golemRustOverride:
+                  path: ../golem-rust-{{ componentName }}
+
+            components:
+              app:a:
+                template: template
+              app:b:
+                template: template
+              app:c:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+
+            dependencies:
+              app:c:
+              - type: wasm-rpc
+                target: app:a
+              - type: wasm-rpc
+                target: app:b
+                golemRustOverride:
+                  version: 1.6.0
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "app/golem.yaml".into(),
+                manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (app, _warns, errors) = app.into_product();
+        assert!(errors.is_empty(), "\n{}", errors.join("\n\n"));
+        let app = app.unwrap();
+
+        let global_override = RustDependencyOverride {
+            path_override: None,
+            version_override: Some("1.5.0".to_string()),
+        };
+
+        let a_override = app.client_golem_rust_override(&"app:a".into(), None, &global_override);
+        check!(a_override.path_override == Some(PathBuf::from("app/../golem-rust-app:a")));
+        check!(a_override.version_override.is_none());
+
+        let b_override = app.client_golem_rust_override(&"app:b".into(), None, &global_override);
+        check!(b_override.path_override.is_none());
+        check!(b_override.version_override == Some("1.6.0".to_string()));
+
+        let c_override = app.client_golem_rust_override(&"app:c".into(), None, &global_override);
+        check!(c_override.version_override == Some("1.5.0".to_string()));
+    }
+
+    #[test]
+    fn dependency_package_renames() {
+        let manifest = indoc! {"
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+              app:b:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+
+            dependencies:
+              app:a:
+              - type: wasm-rpc
+                target: app:b
+                packageRenames:
+                - from: test:main
+                  to: other:main
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "golem.yaml".into(),
+                manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (app, _warns, errors) = app.into_product();
+        assert!(errors.is_empty(), "\n{}", errors.join("\n\n"));
+        let app = app.unwrap();
+
+        check!(
+            app.dependency_package_renames(&"app:a".into(), &"app:b".into())
+                == vec![PackageRename::new("test:main", "other:main").unwrap()]
+        );
+        check!(app
+            .dependency_package_renames(&"app:b".into(), &"app:a".into())
+            .is_empty());
+
+        let invalid_manifest = indoc! {"
+            dependencies:
+              app:a:
+              - type: wasm-rpc
+                target: app:b
+                packageRenames:
+                - from: test
+                  to: other:main
+              - type: wasm
+                path: lib.wasm
+                packageRenames:
+                - from: test:lib
+                  to: other:lib
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "golem.yaml".into(),
+                invalid_manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (_app, _warns, errors) = app.into_product();
+        check!(errors
+            .iter()
+            .any(|error| error.contains("Invalid package name for renaming")));
+        check!(errors
+            .iter()
+            .any(|error| error.contains("only supported for WASM RPC dependencies")));
+    }
+
+    #[test]
+    fn variables_in_templates() {
+        let manifest = indoc! {"
+            variables:
+              witRoot: wit
+              region: eu
+
+            templates:
+              template:
+                sourceWit: '{{ witRoot }}/{{ componentName | to_snake_case }}'
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+                env:
+                  REGION: '{{ region }}'
+
+            components:
+              app:a:
+                template: template
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                manifest.to_string(),
+            )
+            .unwrap()],
+            &BTreeMap::from([("region".to_string(), "us".to_string())]),
+        );
+
+        let (app, _warns, errors) = app.into_product();
+        assert!(errors.is_empty(), "\n{}", errors.join("\n\n"));
+        let app = app.unwrap();
+
+        let props = app.component_properties(&"app:a".into(), None);
+        check!(props.source_wit == "wit/app_a");
+        check!(props.env.get("REGION") == Some(&"us".to_string()));
+
+        let reserved_manifest = indoc! {"
+            variables:
+              componentName: a
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                reserved_manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (_app, _warns, errors) = app.into_product();
+        check!(errors.len() == 1);
+    }
+
+    #[test]
+    fn deployed_dependency_targets() {
+        let manifest = indoc! {"
+            components:
+              app:a:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+
+            dependencies:
+              app:a:
+                - type: wasm-rpc
+                  target:
+                    component: remote:b
+                    version: 3
+                - type: wasm
+                  target:
+                    component: remote:c
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (_app, _warns, errors) = app.into_product();
+        check!(errors.len() == 1);
+        check!(errors[0].contains("remote:c"));
+
+        let manifest = manifest.replace("type: wasm\n", "type: wasm-rpc\n");
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![
+                app_raw::ApplicationWithSource::from_yaml_string("dummy-source".into(), manifest)
+                    .unwrap(),
+            ],
+            &Default::default(),
+        );
+
+        let (app, _warns, errors) = app.into_product();
+        assert!(errors.is_empty(), "\n{}", errors.join("\n\n"));
+        let app = app.unwrap();
+
+        let targets = app
+            .component_dependencies(&"app:a".into())
+            .iter()
+            .map(|dep| dep.source.clone())
+            .collect::<Vec<_>>();
+        check!(
+            targets
+                == vec![
+                    BinaryComponentSource::Deployed {
+                        name: "remote:b".into(),
+                        version: Some(3),
+                    },
+                    BinaryComponentSource::Deployed {
+                        name: "remote:c".into(),
+                        version: None,
+                    },
+                ]
+        );
+    }
+
+    #[test]
+    fn test_cases() {
+        let manifest = indoc! {r#"
+            components:
+              app:counter:
+                sourceWit: wit
+                generatedWit: wit-generated
+                componentWasm: component.wasm
+
+            tests:
+              increments:
+                component: app:counter
+                steps:
+                  - function: increment
+                    args: ["2"]
+                  - function: get
+                    expect: "2"
+              unknown-component:
+                component: app:missing
+                worker: counter-1
+                steps: []
+        "#};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (_app, _warns, errors) = app.into_product();
+        check!(errors.len() == 2);
+        check!(errors.iter().any(|error| error.contains("app:missing")));
+        check!(errors.iter().any(|error| error.contains("steps")));
+
+        let (valid_manifest, _) = manifest.split_once("  unknown-component:").unwrap();
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                valid_manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (app, _warns, errors) = app.into_product();
+        assert!(errors.is_empty(), "\n{}", errors.join("\n\n"));
+        let app = app.unwrap();
+
+        let test_case = &app.tests()["increments"].value;
+        check!(test_case.component == "app:counter");
+        check!(test_case.worker.is_none());
+        check!(
+            test_case.steps
+                == vec![
+                    app_raw::TestStep {
+                        function: "increment".to_string(),
+                        args: vec!["2".to_string()],
+                        expect: None,
+                    },
+                    app_raw::TestStep {
+                        function: "get".to_string(),
+                        args: vec![],
+                        expect: Some("2".to_string()),
+                    },
+                ]
+        );
+    }
+
+    #[test]
+    fn fetch_wit_deps_registry_sources_are_rejected() {
+        let manifest = indoc! {"
+            fetchWitDeps:
+              wasi-http:
+                git: https://github.com/WebAssembly/wasi-http
+                rev: v0.2.0
+              wasi-io:
+                registry: wasi.dev
+        "};
+
+        let app = Application::from_raw_apps(
+            &Default::default(),
+            vec![app_raw::ApplicationWithSource::from_yaml_string(
+                "dummy-source".into(),
+                manifest.to_string(),
+            )
+            .unwrap()],
+            &Default::default(),
+        );
+
+        let (_, _, errors) = app.into_product();
+        check!(errors
+            .iter()
+            .any(|error| error.contains("wasi-io")
+                && error.contains("warg registries is not supported")));
+        check!(!errors.iter().any(|error| error.contains("wasi-http")));
+    }
 }
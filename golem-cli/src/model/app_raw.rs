@@ -34,16 +34,26 @@ impl ApplicationWithSource {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Application {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub includes: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub temp_dir: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub wit_deps: Vec<String>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fetch_wit_deps: HashMap<String, FetchWitDep>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stub_suffix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stub_file_name: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub templates: HashMap<String, ComponentTemplate>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub components: HashMap<String, Component>,
@@ -55,8 +65,12 @@ pub struct Application {
     pub clean: Vec<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub http_api: Option<HttpApi>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_cache: Option<BuildCache>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub profiles: HashMap<ProfileName, Profile>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub tests: HashMap<String, TestCase>,
 }
 
 impl Application {
@@ -144,6 +158,25 @@ impl Component {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TestCase {
+    pub component: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker: Option<String>,
+    pub steps: Vec<TestStep>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TestStep {
+    pub function: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct HttpApi {
@@ -233,6 +266,25 @@ pub struct Profile {
     pub redeploy_http_api: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub redeploy_all: Option<bool>,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub http_api_vars: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub build_cache: Option<BuildCache>,
+}
+
+/// Shared cache of compiled and linked component WASMs, either a directory (e.g. a mounted
+/// network share) or an S3-compatible bucket, accessed using the aws CLI
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct BuildCache {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub s3: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -255,6 +307,10 @@ pub struct ComponentProperties {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub linked_wasm: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasi_adapter: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub build: Vec<BuildCommand>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub custom_commands: HashMap<String, Vec<ExternalCommand>>,
@@ -268,6 +324,12 @@ pub struct ComponentProperties {
     pub plugins: Vec<PluginInstallation>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub env: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub golem_rust_override: Option<GolemRustOverride>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimize: Option<Optimize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_for: Option<String>,
 }
 
 impl ComponentProperties {
@@ -288,6 +350,14 @@ impl ComponentProperties {
             self.linked_wasm = overrides.linked_wasm;
         }
 
+        if !overrides.plugs.is_empty() {
+            self.plugs = overrides.plugs;
+        }
+
+        if overrides.wasi_adapter.is_some() {
+            self.wasi_adapter = overrides.wasi_adapter;
+        }
+
         if !overrides.build.is_empty() {
             self.build = overrides.build;
         }
@@ -312,6 +382,18 @@ impl ComponentProperties {
             self.env.extend(overrides.env);
         }
 
+        if overrides.golem_rust_override.is_some() {
+            self.golem_rust_override = overrides.golem_rust_override;
+        }
+
+        if overrides.optimize.is_some() {
+            self.optimize = overrides.optimize;
+        }
+
+        if overrides.test_for.is_some() {
+            self.test_for = overrides.test_for;
+        }
+
         self
     }
 }
@@ -342,6 +424,21 @@ impl BuildCommand {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FetchWitDep {
+    #[serde(default)]
+    pub git: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Warg registry source, only parsed to reject it with a clear error, as fetching from
+    /// registries is not supported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ExternalCommand {
@@ -356,6 +453,12 @@ pub struct ExternalCommand {
     pub sources: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub targets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -383,11 +486,99 @@ pub struct Dependency {
     #[serde(rename = "type")]
     pub type_: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub target: Option<String>,
+    pub target: Option<DependencyTarget>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub golem_rust_override: Option<GolemRustOverride>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub package_renames: Vec<PackageRename>,
+}
+
+/// Renames a package in the WIT files copied from the client of the dependency target, so targets
+/// using the same package name as an other component can still be depended on
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PackageRename {
+    pub from: String,
+    pub to: String,
+}
+
+/// Target of a dependency, either an application component by name, a component already
+/// deployed to the server, or a stub published to a registry, in the latter two cases the
+/// WASM RPC client is generated from their WIT
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DependencyTarget {
+    AppComponent(String),
+    Deployed(DeployedDependencyTarget),
+    Registry(RegistryDependencyTarget),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DeployedDependencyTarget {
+    pub component: String,
+    /// Uses the latest version if not specified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RegistryDependencyTarget {
+    pub component: String,
+    /// Registry reference of the published stub, e.g. oci://ghcr.io/org/counter-stub
+    pub registry: String,
+    /// Semver requirement, uses the latest published version if not specified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GolemRustOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Optimize {
+    #[serde(default)]
+    pub level: WasmOptLevel,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub passes: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WasmOptLevel {
+    O0,
+    O1,
+    #[default]
+    O2,
+    O3,
+    O4,
+    Os,
+    Oz,
+}
+
+impl WasmOptLevel {
+    pub fn as_arg(&self) -> &'static str {
+        match self {
+            WasmOptLevel::O0 => "-O0",
+            WasmOptLevel::O1 => "-O1",
+            WasmOptLevel::O2 => "-O2",
+            WasmOptLevel::O3 => "-O3",
+            WasmOptLevel::O4 => "-O4",
+            WasmOptLevel::Os => "-Os",
+            WasmOptLevel::Oz => "-Oz",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -0,0 +1,226 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::worker_replay::format_result;
+use golem_wasm_rpc::{parse_value_and_type, ValueAndType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TestStepOutcome {
+    Passed,
+    /// The invocation succeeded, but returned a different result than expected
+    Mismatch,
+    /// The invocation failed, or could not be prepared
+    Failed,
+    /// Not invoked, as a previous step of the same test case did not pass
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestStepView {
+    pub function_name: String,
+    pub outcome: TestStepOutcome,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub actual: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
+
+impl TestStepView {
+    pub fn new(
+        function_name: String,
+        expected: Option<String>,
+        result: Result<Option<ValueAndType>, String>,
+    ) -> Self {
+        let actual = match result {
+            Ok(actual) => actual,
+            Err(error) => return Self::failed(function_name, expected, error),
+        };
+
+        let (outcome, error) = match &expected {
+            Some(expected) => match expected_result_matches(expected, actual.as_ref()) {
+                Ok(true) => (TestStepOutcome::Passed, None),
+                Ok(false) => (TestStepOutcome::Mismatch, None),
+                Err(error) => (TestStepOutcome::Failed, Some(error)),
+            },
+            None => (TestStepOutcome::Passed, None),
+        };
+
+        Self {
+            function_name,
+            outcome,
+            expected,
+            actual: Some(format_result(actual.as_ref())),
+            error,
+        }
+    }
+
+    pub fn failed(function_name: String, expected: Option<String>, error: String) -> Self {
+        Self {
+            function_name,
+            outcome: TestStepOutcome::Failed,
+            expected,
+            actual: None,
+            error: Some(error),
+        }
+    }
+
+    pub fn skipped(function_name: String, expected: Option<String>) -> Self {
+        Self {
+            function_name,
+            outcome: TestStepOutcome::Skipped,
+            expected,
+            actual: None,
+            error: None,
+        }
+    }
+}
+
+/// Parses the expected WAVE value using the type of the actual result, so the values are
+/// compared instead of their formatting. Functions without a result are expected as "()".
+pub fn expected_result_matches(
+    expected: &str,
+    actual: Option<&ValueAndType>,
+) -> Result<bool, String> {
+    match actual {
+        Some(actual) => parse_value_and_type(&actual.typ, expected)
+            .map(|expected| expected.value == actual.value)
+            .map_err(|err| format!("Failed to parse expected result: {err}")),
+        None => Ok(expected.trim() == "()"),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestCaseView {
+    pub name: String,
+    pub component_name: String,
+    pub worker_name: String,
+    pub steps: Vec<TestStepView>,
+}
+
+impl TestCaseView {
+    pub fn passed(&self) -> bool {
+        self.steps
+            .iter()
+            .all(|step| step.outcome == TestStepOutcome::Passed)
+    }
+}
+
+/// Results of the test cases declared in the tests section of the application manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppTestReport {
+    pub cases: Vec<TestCaseView>,
+}
+
+impl AppTestReport {
+    pub fn passed_count(&self) -> usize {
+        self.cases.iter().filter(|case| case.passed()).count()
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.cases.len() - self.passed_count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::app_test::{
+        expected_result_matches, AppTestReport, TestCaseView, TestStepOutcome, TestStepView,
+    };
+    use assert2::{assert, let_assert};
+    use golem_wasm_ast::analysis::analysed_type;
+    use golem_wasm_rpc::{Value, ValueAndType};
+    use test_r::test;
+
+    fn cart_item() -> ValueAndType {
+        ValueAndType::new(
+            Value::Record(vec![Value::String("p-1".to_string()), Value::U32(2)]),
+            analysed_type::record(vec![
+                analysed_type::field("product-id", analysed_type::str()),
+                analysed_type::field("quantity", analysed_type::u32()),
+            ]),
+        )
+    }
+
+    #[test]
+    fn expected_results() {
+        let item = cart_item();
+        assert!(
+            expected_result_matches(r#"{product-id: "p-1", quantity: 2}"#, Some(&item)) == Ok(true)
+        );
+        assert!(
+            expected_result_matches(r#"{ product-id: "p-1",  quantity: 2 }"#, Some(&item))
+                == Ok(true)
+        );
+        assert!(
+            expected_result_matches(r#"{product-id: "p-1", quantity: 3}"#, Some(&item))
+                == Ok(false)
+        );
+        let_assert!(Err(_) = expected_result_matches("not wave", Some(&item)));
+
+        assert!(expected_result_matches("()", None) == Ok(true));
+        assert!(expected_result_matches("1", None) == Ok(false));
+    }
+
+    #[test]
+    fn step_outcomes() {
+        let count = || Some(ValueAndType::new(Value::U64(3), analysed_type::u64()));
+
+        let passed = TestStepView::new("get".to_string(), Some("3".to_string()), Ok(count()));
+        assert!(passed.outcome == TestStepOutcome::Passed);
+        assert!(passed.actual.as_deref() == Some("3"));
+
+        let unchecked = TestStepView::new("get".to_string(), None, Ok(count()));
+        assert!(unchecked.outcome == TestStepOutcome::Passed);
+
+        let mismatch = TestStepView::new("get".to_string(), Some("4".to_string()), Ok(count()));
+        assert!(mismatch.outcome == TestStepOutcome::Mismatch);
+
+        let failed = TestStepView::new(
+            "get".to_string(),
+            Some("3".to_string()),
+            Err("worker trapped".to_string()),
+        );
+        assert!(failed.outcome == TestStepOutcome::Failed);
+        assert!(failed.error.as_deref() == Some("worker trapped"));
+
+        let report = AppTestReport {
+            cases: vec![
+                TestCaseView {
+                    name: "passing".to_string(),
+                    component_name: "app:counter".to_string(),
+                    worker_name: "smoke-test-passing".to_string(),
+                    steps: vec![passed, unchecked],
+                },
+                TestCaseView {
+                    name: "failing".to_string(),
+                    component_name: "app:counter".to_string(),
+                    worker_name: "smoke-test-failing".to_string(),
+                    steps: vec![
+                        mismatch,
+                        TestStepView::skipped("get".to_string(), Some("3".to_string())),
+                    ],
+                },
+            ],
+        };
+        assert!(report.passed_count() == 1);
+        assert!(report.failed_count() == 1);
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildPlanFileAction {
+    Create,
+    Overwrite,
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildPlanFile {
+    pub path: PathBuf,
+    pub action: BuildPlanFileAction,
+}
+
+impl BuildPlanFile {
+    /// Files of up-to-date tasks are skipped, otherwise they are created or overwritten
+    pub fn new(path: PathBuf, up_to_date: bool) -> Self {
+        let action = if up_to_date {
+            BuildPlanFileAction::Skip
+        } else if path.exists() {
+            BuildPlanFileAction::Overwrite
+        } else {
+            BuildPlanFileAction::Create
+        };
+        Self { path, action }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildPlanCommand {
+    pub command: String,
+    pub dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildPlanTask {
+    pub step: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_name: Option<String>,
+    pub description: String,
+    pub up_to_date: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<BuildPlanCommand>,
+    pub files: Vec<BuildPlanFile>,
+}
+
+/// Tasks of the selected build steps in execution order, with the commands they would execute
+/// and the files they would write.
+///
+/// Up-to-date checks are only evaluated for tasks with content hash based checks, tasks
+/// generating RPC artifacts are checked during the build, and are planned as if they would run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildPlan {
+    pub tasks: Vec<BuildPlanTask>,
+}
@@ -0,0 +1,171 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::ComponentName;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CiStage {
+    Validate,
+    Build,
+    DeployComponents,
+    UpdateWorkers,
+    DeployHttpApis,
+}
+
+impl CiStage {
+    /// Exit code of app ci when failing in the stage, kept stable for CI scripts
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CiStage::Validate => 10,
+            CiStage::Build => 11,
+            CiStage::DeployComponents => 12,
+            CiStage::UpdateWorkers => 13,
+            CiStage::DeployHttpApis => 14,
+        }
+    }
+}
+
+impl Display for CiStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let stage = match self {
+            CiStage::Validate => "validate",
+            CiStage::Build => "build",
+            CiStage::DeployComponents => "deploy components",
+            CiStage::UpdateWorkers => "update workers",
+            CiStage::DeployHttpApis => "deploy HTTP APIs",
+        };
+        write!(f, "{stage}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CiStageStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CiChange {
+    New,
+    Changed,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiStageResult {
+    pub stage: CiStage,
+    pub status: CiStageStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiComponentChange {
+    pub component_name: ComponentName,
+    pub change: CiChange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiHttpApiDefinitionChange {
+    pub name: String,
+    pub version: String,
+    pub change: CiChange,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiSummary {
+    pub dry_run: bool,
+    pub stages: Vec<CiStageResult>,
+    pub components: Vec<CiComponentChange>,
+    pub http_api_definitions: Vec<CiHttpApiDefinitionChange>,
+}
+
+impl CiSummary {
+    pub fn failed_stage(&self) -> Option<CiStage> {
+        self.stages
+            .iter()
+            .find(|result| result.status == CiStageStatus::Failed)
+            .map(|result| result.stage)
+    }
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[clap(rename_all = "kebab_case")]
+pub enum CiProvider {
+    Github,
+    Gitlab,
+}
+
+impl CiProvider {
+    /// Path of the generated workflow file, relative to the application root
+    pub fn default_workflow_path(&self) -> PathBuf {
+        match self {
+            CiProvider::Github => Path::new(".github").join("workflows").join("golem.yaml"),
+            CiProvider::Gitlab => PathBuf::from(".gitlab-ci.yml"),
+        }
+    }
+}
+
+/// Toolchains that have to be installed in CI for building the components
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CiToolchain {
+    Rust,
+    Node,
+    Go,
+    Python,
+}
+
+impl CiToolchain {
+    /// Detects the toolchain based on the project files in the component source directory
+    pub fn detect(component_source_dir: &Path) -> Option<Self> {
+        let project_files = [
+            ("Cargo.toml", CiToolchain::Rust),
+            ("package.json", CiToolchain::Node),
+            ("go.mod", CiToolchain::Go),
+            ("pyproject.toml", CiToolchain::Python),
+            ("requirements.txt", CiToolchain::Python),
+        ];
+        project_files
+            .into_iter()
+            .find(|(file_name, _)| component_source_dir.join(file_name).is_file())
+            .map(|(_, toolchain)| toolchain)
+    }
+}
+
+/// Variables for rendering the CI workflow templates
+#[derive(Debug, Clone, Serialize)]
+pub struct CiWorkflowConfig {
+    pub cli_version: String,
+    pub build_profile: Option<String>,
+    pub components: Vec<String>,
+    pub toolchains: BTreeSet<CiToolchain>,
+    /// Application manifest files, used as the cache key
+    pub manifests: Vec<String>,
+    /// Directories of the build steps, including the generated WASM RPC client builds
+    pub cache_paths: Vec<String>,
+    pub has_tests: bool,
+}
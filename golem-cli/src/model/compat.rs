@@ -0,0 +1,60 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatDifference {
+    pub interface_name: String,
+    pub item: String,
+    pub change: String,
+    /// Additions in the target are compatible with the existing callers
+    pub breaking: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatDependency {
+    pub component_name: String,
+    pub dependency_name: String,
+    pub package_name: String,
+    /// False if the stub types of the target are not yet copied into the caller's generated WIT
+    pub vendored: bool,
+    pub differences: Vec<CompatDifference>,
+}
+
+impl CompatDependency {
+    pub fn is_compatible(&self) -> bool {
+        self.vendored
+            && !self
+                .differences
+                .iter()
+                .any(|difference| difference.breaking)
+    }
+}
+
+/// Differences between the target types vendored in the callers' generated WIT and the current
+/// WIT of the targets, for all the WASM RPC dependencies of the application.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatReport {
+    pub dependencies: Vec<CompatDependency>,
+}
+
+impl CompatReport {
+    pub fn is_compatible(&self) -> bool {
+        self.dependencies.iter().all(|dep| dep.is_compatible())
+    }
+}
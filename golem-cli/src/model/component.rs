@@ -12,9 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::config::ProfileName;
 use crate::model::wave::function_wave_compatible;
 use crate::model::ComponentName;
 use crate::model::ProjectId;
+use crate::wasm_rpc_stubgen::commands::metadata::BUILD_METADATA_PRODUCERS_FIELD;
 use anyhow::{anyhow, bail};
 use chrono::{DateTime, Utc};
 use golem_client::model::{
@@ -24,8 +26,9 @@ use golem_common::model::component_metadata::DynamicLinkedInstance;
 use golem_common::model::trim_date::TrimDateTime;
 use golem_wasm_ast::analysis::wave::DisplayNamedFunc;
 use golem_wasm_ast::analysis::{
-    AnalysedExport, AnalysedFunction, AnalysedInstance, AnalysedResourceMode, NameOptionTypePair,
-    NameTypePair, TypeEnum, TypeFlags, TypeRecord, TypeTuple, TypeVariant,
+    AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedInstance,
+    AnalysedResourceMode, NameOptionTypePair, NameTypePair, TypeEnum, TypeFlags, TypeRecord,
+    TypeTuple, TypeVariant,
 };
 use rib::{ParsedFunctionName, ParsedFunctionSite};
 use serde::{Deserialize, Serialize};
@@ -147,6 +150,15 @@ impl ComponentUpsertResult {
     }
 }
 
+/// Component view tagged with the profile it was queried with, used for multi-profile commands
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileComponentView {
+    pub profile: ProfileName,
+    #[serde(flatten)]
+    pub component: ComponentView,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ComponentView {
@@ -166,6 +178,8 @@ pub struct ComponentView {
     pub dynamic_linking: BTreeMap<String, BTreeMap<String, String>>,
     pub files: Vec<InitialComponentFile>,
     pub env: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub build_metadata: BTreeMap<String, String>,
 }
 
 impl ComponentView {
@@ -180,6 +194,15 @@ impl ComponentView {
             created_at: value.created_at,
             project_id: value.project_id,
             exports: show_exported_functions(&value.metadata.exports, true),
+            build_metadata: value
+                .metadata
+                .producers
+                .iter()
+                .flat_map(|producers| producers.fields.iter())
+                .filter(|field| field.name == BUILD_METADATA_PRODUCERS_FIELD)
+                .flat_map(|field| field.values.iter())
+                .map(|value| (value.name.clone(), value.version.clone()))
+                .collect(),
             dynamic_linking: value
                 .metadata
                 .dynamic_linking
@@ -405,19 +428,29 @@ pub fn function_result_types<'t>(
     Ok(func.result.iter().map(|r| &r.typ).collect())
 }
 
-pub fn function_params_types<'t>(
+pub fn function_params<'t>(
     component: &'t Component,
     function: &str,
-) -> anyhow::Result<Vec<&'t AnalysedType>> {
+) -> anyhow::Result<Vec<&'t AnalysedFunctionParameter>> {
     let (func, parsed) = resolve_function(component, function)?;
 
     if parsed.function().is_indexed_resource() {
-        Ok(func.parameters.iter().skip(1).map(|r| &r.typ).collect())
+        Ok(func.parameters.iter().skip(1).collect())
     } else {
-        Ok(func.parameters.iter().map(|r| &r.typ).collect())
+        Ok(func.parameters.iter().collect())
     }
 }
 
+pub fn function_params_types<'t>(
+    component: &'t Component,
+    function: &str,
+) -> anyhow::Result<Vec<&'t AnalysedType>> {
+    Ok(function_params(component, function)?
+        .into_iter()
+        .map(|param| &param.typ)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use test_r::test;
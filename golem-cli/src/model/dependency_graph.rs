@@ -0,0 +1,232 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::app::{Application, BinaryComponentSource};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[clap(rename_all = "kebab_case")]
+pub enum GraphFormat {
+    #[default]
+    Dot,
+    Mermaid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyGraphNodeKind {
+    AppComponent,
+    LocalFile,
+    Url,
+    Deployed,
+    Registry,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphNode {
+    pub name: String,
+    pub kind: DependencyGraphNodeKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraphEdge {
+    pub from: String,
+    pub to: String,
+    pub dependency_type: String,
+    pub in_cycle: bool,
+}
+
+/// Component dependency graph of an application, cycles are reported as the list of components
+/// participating in them, including self dependencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyGraph {
+    pub nodes: Vec<DependencyGraphNode>,
+    pub edges: Vec<DependencyGraphEdge>,
+    pub cycles: Vec<Vec<String>>,
+    #[serde(skip)]
+    pub format: GraphFormat,
+}
+
+impl DependencyGraph {
+    pub fn from_application(application: &Application, format: GraphFormat) -> Self {
+        let mut nodes = BTreeMap::<String, DependencyGraphNodeKind>::new();
+        let mut adjacency = BTreeMap::<String, BTreeSet<String>>::new();
+        let mut edges = Vec::new();
+
+        for component_name in application.component_names() {
+            nodes.insert(
+                component_name.to_string(),
+                DependencyGraphNodeKind::AppComponent,
+            );
+            adjacency.entry(component_name.to_string()).or_default();
+
+            for dependency in application.component_dependencies(component_name) {
+                let kind = match &dependency.source {
+                    BinaryComponentSource::AppComponent { .. } => {
+                        DependencyGraphNodeKind::AppComponent
+                    }
+                    BinaryComponentSource::LocalFile { .. } => DependencyGraphNodeKind::LocalFile,
+                    BinaryComponentSource::Url { .. } => DependencyGraphNodeKind::Url,
+                    BinaryComponentSource::Deployed { .. } => DependencyGraphNodeKind::Deployed,
+                    BinaryComponentSource::Registry { .. } => DependencyGraphNodeKind::Registry,
+                };
+                let target = dependency.source.to_string();
+                nodes.entry(target.clone()).or_insert(kind);
+                adjacency
+                    .entry(component_name.to_string())
+                    .or_default()
+                    .insert(target.clone());
+                edges.push(DependencyGraphEdge {
+                    from: component_name.to_string(),
+                    to: target,
+                    dependency_type: dependency.dep_type.as_str().to_string(),
+                    in_cycle: false,
+                });
+            }
+        }
+
+        let cycles = find_cycles(&adjacency);
+        for edge in &mut edges {
+            edge.in_cycle = cycles
+                .iter()
+                .any(|cycle| cycle.contains(&edge.from) && cycle.contains(&edge.to));
+        }
+
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|(name, kind)| DependencyGraphNode { name, kind })
+                .collect(),
+            edges,
+            cycles,
+            format,
+        }
+    }
+}
+
+/// Returns the strongly connected components which form a cycle, using Tarjan's algorithm
+pub fn find_cycles(adjacency: &BTreeMap<String, BTreeSet<String>>) -> Vec<Vec<String>> {
+    struct State<'a> {
+        adjacency: &'a BTreeMap<String, BTreeSet<String>>,
+        next_index: usize,
+        indices: BTreeMap<&'a str, usize>,
+        low_links: BTreeMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        cycles: Vec<Vec<String>>,
+    }
+
+    impl<'a> State<'a> {
+        fn visit(&mut self, node: &'a str) {
+            self.indices.insert(node, self.next_index);
+            self.low_links.insert(node, self.next_index);
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            let adjacency = self.adjacency;
+            for target in adjacency.get(node).into_iter().flatten() {
+                let target = target.as_str();
+                if !self.indices.contains_key(target) {
+                    self.visit(target);
+                    let low_link = self.low_links[node].min(self.low_links[target]);
+                    self.low_links.insert(node, low_link);
+                } else if self.on_stack.contains(target) {
+                    let low_link = self.low_links[node].min(self.indices[target]);
+                    self.low_links.insert(node, low_link);
+                }
+            }
+
+            if self.low_links[node] == self.indices[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+
+                let is_self_dependency = adjacency
+                    .get(node)
+                    .is_some_and(|targets| targets.contains(node));
+                if component.len() > 1 || is_self_dependency {
+                    component.sort();
+                    self.cycles.push(component);
+                }
+            }
+        }
+    }
+
+    let mut state = State {
+        adjacency,
+        next_index: 0,
+        indices: BTreeMap::new(),
+        low_links: BTreeMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        cycles: Vec::new(),
+    };
+
+    for node in adjacency.keys() {
+        if !state.indices.contains_key(node.as_str()) {
+            state.visit(node);
+        }
+    }
+
+    state.cycles.sort();
+    state.cycles
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::dependency_graph::find_cycles;
+    use assert2::assert;
+    use std::collections::{BTreeMap, BTreeSet};
+    use test_r::test;
+
+    #[test]
+    fn find_cycles_reports_loops_and_self_dependencies() {
+        let adjacency = [
+            ("a", vec!["b"]),
+            ("b", vec!["c"]),
+            ("c", vec!["a", "d"]),
+            ("d", vec![]),
+            ("e", vec!["e"]),
+        ]
+        .into_iter()
+        .map(|(node, targets)| {
+            (
+                node.to_string(),
+                targets
+                    .into_iter()
+                    .map(|target| target.to_string())
+                    .collect::<BTreeSet<_>>(),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+        assert!(
+            find_cycles(&adjacency)
+                == vec![
+                    vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                    vec!["e".to_string()],
+                ]
+        );
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::api::ApiDefinitionIdWithVersion;
+use crate::model::app_raw::{HttpApiDefinition, HttpApiDeployment};
+use crate::model::component::AppComponentType;
+use anyhow::{anyhow, bail, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Desired state of a whole project, as used by `golem apply`.
+///
+/// Components are described by their already built WASM, while API definitions and
+/// deployments use the same format as the application manifest. Deployments must reference
+/// the definitions with an explicit version (`<name>@<version>`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DesiredStateFile {
+    #[serde(default)]
+    pub components: BTreeMap<String, DesiredComponent>,
+    #[serde(default)]
+    pub http_api_definitions: BTreeMap<String, HttpApiDefinition>,
+    #[serde(default)]
+    pub http_api_deployments: Vec<HttpApiDeployment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DesiredComponent {
+    /// Path of the component WASM, relative to the desired state file
+    pub wasm: PathBuf,
+    /// Expected BLAKE3 hash of the WASM in hex, for making sure the expected build is deployed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(default)]
+    pub component_type: AppComponentType,
+    /// Default environment variables of the workers
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+}
+
+impl DesiredStateFile {
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| anyhow!("Failed to read desired state file: {}", path.display()))?;
+        let mut file: Self = serde_yaml::from_str(&content)
+            .with_context(|| anyhow!("Failed to parse desired state file: {}", path.display()))?;
+
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        for (name, component) in &mut file.components {
+            if component.component_type == AppComponentType::Library {
+                bail!(
+                    "Component {} in {} is a library, only deployable components can be applied",
+                    name,
+                    path.display()
+                );
+            }
+            component.wasm = base_dir.join(&component.wasm);
+        }
+
+        for deployment in &file.http_api_deployments {
+            for definition in &deployment.definitions {
+                if ApiDefinitionIdWithVersion::from_str(definition).is_err() {
+                    bail!(
+                        "Invalid API definition reference {} for host {} in {}, expected format: <name>@<version>",
+                        definition,
+                        deployment.host,
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        Ok(file)
+    }
+
+    /// API definition names and versions declared in the file, or referenced by its deployments
+    pub fn api_definition_versions(&self) -> BTreeSet<(String, String)> {
+        self.http_api_definitions
+            .iter()
+            .map(|(name, definition)| (name.clone(), definition.version.clone()))
+            .chain(self.http_api_deployments.iter().flat_map(|deployment| {
+                deployment.definitions.iter().filter_map(|definition| {
+                    ApiDefinitionIdWithVersion::from_str(definition)
+                        .ok()
+                        .map(|definition| (definition.id.0, definition.version.0))
+                })
+            }))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::component::AppComponentType;
+    use crate::model::desired_state::DesiredStateFile;
+    use assert2::assert;
+    use std::collections::BTreeSet;
+    use test_r::test;
+
+    #[test]
+    fn desired_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("golem.yaml");
+        std::fs::write(
+            &path,
+            r#"
+components:
+  shop:cart:
+    wasm: target/cart.wasm
+    componentType: ephemeral
+    env:
+      LOG_LEVEL: debug
+httpApiDefinitions:
+  shop-api:
+    version: 0.1.0
+    routes:
+      - method: GET
+        path: /carts/{user}
+        binding:
+          componentName: shop:cart
+          response: 'let w = instance(); w.get()'
+httpApiDeployments:
+  - host: localhost:9006
+    definitions:
+      - shop-api@0.1.0
+      - legacy-api@0.0.1
+"#,
+        )
+        .unwrap();
+
+        let file = DesiredStateFile::from_file(&path).unwrap();
+        let component = &file.components["shop:cart"];
+        assert!(component.wasm == dir.path().join("target/cart.wasm"));
+        assert!(component.component_type == AppComponentType::Ephemeral);
+        assert!(
+            file.api_definition_versions()
+                == BTreeSet::from([
+                    ("legacy-api".to_string(), "0.0.1".to_string()),
+                    ("shop-api".to_string(), "0.1.0".to_string()),
+                ])
+        );
+
+        std::fs::write(
+            &path,
+            "httpApiDeployments:\n  - host: localhost:9006\n    definitions: [shop-api]\n",
+        )
+        .unwrap();
+        assert!(DesiredStateFile::from_file(&path).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DoctorCheckStatus {
+    Ok,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    pub name: String,
+    pub status: DoctorCheckStatus,
+    pub details: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl DoctorCheck {
+    pub fn new(
+        name: impl Into<String>,
+        status: DoctorCheckStatus,
+        details: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            status,
+            details: details.into(),
+            latency_ms: None,
+            fix: None,
+        }
+    }
+
+    pub fn with_latency_ms(mut self, latency_ms: u128) -> Self {
+        self.latency_ms = Some(latency_ms);
+        self
+    }
+
+    pub fn with_fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub profile: String,
+    pub cli_version: String,
+    pub checks: Vec<DoctorCheck>,
+}
+
+impl DoctorReport {
+    pub fn has_failures(&self) -> bool {
+        self.checks
+            .iter()
+            .any(|check| check.status == DoctorCheckStatus::Failed)
+    }
+}
+
+/// Compares the CLI and server versions, only the major and minor versions have to match,
+/// as patch releases are expected to be compatible
+pub fn versions_compatible(cli_version: &str, server_version: &str) -> Option<bool> {
+    let parse = |version: &str| semver::Version::parse(version.trim_start_matches('v')).ok();
+    let cli_version = parse(cli_version)?;
+    let server_version = parse(server_version)?;
+    Some(cli_version.major == server_version.major && cli_version.minor == server_version.minor)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::doctor::versions_compatible;
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn versions_compatible_ignores_patch_and_pre_release() {
+        assert!(versions_compatible("1.3.0", "1.3.2") == Some(true));
+        assert!(versions_compatible("v1.3.0-dev.21", "1.3.1") == Some(true));
+        assert!(versions_compatible("1.3.0", "1.2.9") == Some(false));
+        assert!(versions_compatible("2.0.0", "1.3.0") == Some(false));
+        assert!(versions_compatible("1.3.0", "unknown").is_none());
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::{ComponentName, WorkerMetadata};
+use chrono::{DateTime, Utc};
+use golem_client::model::{UpdateRecord, WorkerStatus};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(
+    clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[clap(rename_all = "kebab_case")]
+#[serde(rename_all = "kebab-case")]
+pub enum WorkerEventType {
+    WorkerCreated,
+    WorkerDeleted,
+    StatusChanged,
+    WorkerFailed,
+    InvocationFailed,
+    UpdateSucceeded,
+    UpdateFailed,
+}
+
+impl Display for WorkerEventType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let event_type = match self {
+            WorkerEventType::WorkerCreated => "worker-created",
+            WorkerEventType::WorkerDeleted => "worker-deleted",
+            WorkerEventType::StatusChanged => "status-changed",
+            WorkerEventType::WorkerFailed => "worker-failed",
+            WorkerEventType::InvocationFailed => "invocation-failed",
+            WorkerEventType::UpdateSucceeded => "update-succeeded",
+            WorkerEventType::UpdateFailed => "update-failed",
+        };
+        write!(f, "{event_type}")
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerEventView {
+    pub timestamp: DateTime<Utc>,
+    pub event_type: WorkerEventType,
+    pub component_name: ComponentName,
+    pub worker_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<WorkerStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component_version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The parts of the worker metadata which are compared between polls for detecting events
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkerEventSnapshot {
+    pub component_name: ComponentName,
+    pub worker_name: String,
+    pub status: WorkerStatus,
+    pub component_version: u64,
+    pub last_error: Option<String>,
+    pub successful_updates: usize,
+    pub failed_updates: Vec<Option<String>>,
+}
+
+impl From<&WorkerMetadata> for WorkerEventSnapshot {
+    fn from(value: &WorkerMetadata) -> Self {
+        WorkerEventSnapshot {
+            component_name: value.component_name.clone(),
+            worker_name: value.worker_id.worker_name.clone(),
+            status: value.status.clone(),
+            component_version: value.component_version,
+            last_error: value.last_error.clone(),
+            successful_updates: value
+                .updates
+                .iter()
+                .filter(|update| matches!(update, UpdateRecord::SuccessfulUpdate(_)))
+                .count(),
+            failed_updates: value
+                .updates
+                .iter()
+                .filter_map(|update| match update {
+                    UpdateRecord::FailedUpdate(update) => Some(update.details.clone()),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Workers of the tailed components, keyed by component and worker name
+pub type WorkerEventSnapshots = BTreeMap<(ComponentName, String), WorkerEventSnapshot>;
+
+/// Compares two polls of the worker metadata and returns the detected events
+pub fn worker_events(
+    previous: &WorkerEventSnapshots,
+    current: &WorkerEventSnapshots,
+    timestamp: DateTime<Utc>,
+) -> Vec<WorkerEventView> {
+    let event = |event_type: WorkerEventType, worker: &WorkerEventSnapshot| WorkerEventView {
+        timestamp,
+        event_type,
+        component_name: worker.component_name.clone(),
+        worker_name: worker.worker_name.clone(),
+        status: Some(worker.status.clone()),
+        component_version: Some(worker.component_version),
+        error: None,
+    };
+
+    let mut events = Vec::new();
+
+    for (key, worker) in current {
+        let Some(previous) = previous.get(key) else {
+            events.push(event(WorkerEventType::WorkerCreated, worker));
+            if worker.status == WorkerStatus::Failed {
+                events.push(WorkerEventView {
+                    error: worker.last_error.clone(),
+                    ..event(WorkerEventType::WorkerFailed, worker)
+                });
+            }
+            continue;
+        };
+
+        if worker.status != previous.status {
+            events.push(event(WorkerEventType::StatusChanged, worker));
+            if worker.status == WorkerStatus::Failed {
+                events.push(WorkerEventView {
+                    error: worker.last_error.clone(),
+                    ..event(WorkerEventType::WorkerFailed, worker)
+                });
+            }
+        }
+
+        if worker.last_error.is_some() && worker.last_error != previous.last_error {
+            events.push(WorkerEventView {
+                error: worker.last_error.clone(),
+                ..event(WorkerEventType::InvocationFailed, worker)
+            });
+        }
+
+        if worker.successful_updates > previous.successful_updates {
+            events.push(event(WorkerEventType::UpdateSucceeded, worker));
+        }
+
+        for details in worker
+            .failed_updates
+            .iter()
+            .skip(previous.failed_updates.len())
+        {
+            events.push(WorkerEventView {
+                error: details.clone(),
+                ..event(WorkerEventType::UpdateFailed, worker)
+            });
+        }
+    }
+
+    for (key, worker) in previous {
+        if !current.contains_key(key) {
+            events.push(WorkerEventView {
+                status: None,
+                ..event(WorkerEventType::WorkerDeleted, worker)
+            });
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::event::{
+        worker_events, WorkerEventSnapshot, WorkerEventSnapshots, WorkerEventType,
+    };
+    use crate::model::ComponentName;
+    use assert2::assert;
+    use chrono::Utc;
+    use golem_client::model::WorkerStatus;
+    use test_r::test;
+
+    fn snapshots(workers: Vec<WorkerEventSnapshot>) -> WorkerEventSnapshots {
+        workers
+            .into_iter()
+            .map(|worker| {
+                (
+                    (worker.component_name.clone(), worker.worker_name.clone()),
+                    worker,
+                )
+            })
+            .collect()
+    }
+
+    fn worker(name: &str, status: WorkerStatus) -> WorkerEventSnapshot {
+        WorkerEventSnapshot {
+            component_name: ComponentName("app:counter".to_string()),
+            worker_name: name.to_string(),
+            status,
+            component_version: 0,
+            last_error: None,
+            successful_updates: 0,
+            failed_updates: vec![],
+        }
+    }
+
+    #[test]
+    fn detect_worker_events() {
+        let previous = snapshots(vec![
+            worker("w1", WorkerStatus::Idle),
+            worker("w2", WorkerStatus::Running),
+            worker("w3", WorkerStatus::Idle),
+        ]);
+        let current = snapshots(vec![
+            worker("w1", WorkerStatus::Idle),
+            WorkerEventSnapshot {
+                last_error: Some("trap".to_string()),
+                ..worker("w2", WorkerStatus::Failed)
+            },
+            WorkerEventSnapshot {
+                failed_updates: vec![Some("incompatible".to_string())],
+                ..worker("w3", WorkerStatus::Idle)
+            },
+            worker("w4", WorkerStatus::Idle),
+        ]);
+
+        let events = worker_events(&previous, &current, Utc::now())
+            .into_iter()
+            .map(|event| (event.worker_name, event.event_type, event.error))
+            .collect::<Vec<_>>();
+
+        assert!(
+            events
+                == vec![
+                    ("w2".to_string(), WorkerEventType::StatusChanged, None),
+                    (
+                        "w2".to_string(),
+                        WorkerEventType::WorkerFailed,
+                        Some("trap".to_string())
+                    ),
+                    (
+                        "w2".to_string(),
+                        WorkerEventType::InvocationFailed,
+                        Some("trap".to_string())
+                    ),
+                    (
+                        "w3".to_string(),
+                        WorkerEventType::UpdateFailed,
+                        Some("incompatible".to_string())
+                    ),
+                    ("w4".to_string(), WorkerEventType::WorkerCreated, None),
+                ]
+        );
+        assert!(worker_events(&current, &current, Utc::now()).is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the CLI extension executables, e.g. golem-costs provides the costs subcommand
+pub const EXTENSION_EXECUTABLE_PREFIX: &str = "golem-";
+
+const EXTENSIONS_DIR_NAME: &str = "extensions";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtensionSource {
+    Installed,
+    Path,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtensionView {
+    pub name: String,
+    pub path: PathBuf,
+    pub source: ExtensionSource,
+}
+
+/// Extensions installed with the extension install command, these take precedence over
+/// the ones found on PATH
+pub fn extensions_dir(config_dir: &Path) -> PathBuf {
+    config_dir.join(EXTENSIONS_DIR_NAME)
+}
+
+pub fn extension_executable_name(name: &str) -> String {
+    format!(
+        "{}{}{}",
+        EXTENSION_EXECUTABLE_PREFIX,
+        name,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+/// Returns the extension name for executable file names like golem-<NAME>
+pub fn extension_name(file_name: &str) -> Option<&str> {
+    let name = file_name.strip_prefix(EXTENSION_EXECUTABLE_PREFIX)?;
+    let name = name
+        .strip_suffix(std::env::consts::EXE_SUFFIX)
+        .filter(|_| !std::env::consts::EXE_SUFFIX.is_empty())
+        .unwrap_or(name);
+    is_valid_extension_name(name).then_some(name)
+}
+
+pub fn is_valid_extension_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+}
+
+fn search_dirs(config_dir: &Path) -> Vec<(ExtensionSource, PathBuf)> {
+    std::iter::once((ExtensionSource::Installed, extensions_dir(config_dir)))
+        .chain(
+            std::env::var_os("PATH")
+                .map(|path| std::env::split_paths(&path).collect::<Vec<_>>())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|dir| (ExtensionSource::Path, dir)),
+        )
+        .collect()
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+            .unwrap_or(false)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+pub fn find_extension(config_dir: &Path, name: &str) -> Option<PathBuf> {
+    if !is_valid_extension_name(name) {
+        return None;
+    }
+    let executable_name = extension_executable_name(name);
+    search_dirs(config_dir)
+        .into_iter()
+        .map(|(_, dir)| dir.join(&executable_name))
+        .find(|path| is_executable(path))
+}
+
+/// Lists the available extensions, for extensions with the same name only the one used
+/// for running is listed
+pub fn discover_extensions(config_dir: &Path) -> Vec<ExtensionView> {
+    let mut extensions = BTreeMap::<String, ExtensionView>::new();
+    for (source, dir) in search_dirs(config_dir) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let Some(name) = extension_name(&file_name) else {
+                continue;
+            };
+            if !extensions.contains_key(name) && is_executable(&path) {
+                extensions.insert(
+                    name.to_string(),
+                    ExtensionView {
+                        name: name.to_string(),
+                        path,
+                        source,
+                    },
+                );
+            }
+        }
+    }
+    extensions.into_values().collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::extension::extension_name;
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn extension_name_from_executable_name() {
+        let exe_suffix = std::env::consts::EXE_SUFFIX;
+        assert!(extension_name(&format!("golem-costs{exe_suffix}")) == Some("costs"));
+        assert!(extension_name(&format!("golem-team-tools{exe_suffix}")) == Some("team-tools"));
+        assert!(extension_name("golem-").is_none());
+        assert!(extension_name("golem-Costs").is_none());
+        assert!(extension_name("cargo-golem").is_none());
+    }
+}
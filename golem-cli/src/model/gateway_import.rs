@@ -0,0 +1,539 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::api::to_method_pattern;
+use crate::model::app_raw;
+use anyhow::{anyhow, bail, Context};
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+pub const BINDING_PLACEHOLDER: &str = "TODO";
+
+/// Name of the catch-all path variable used for importing prefix matched paths
+const PREFIX_REST_VAR: &str = "rest";
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[clap(rename_all = "kebab_case")]
+pub enum GatewayConfigFormat {
+    /// Kong declarative configuration (services and routes)
+    Kong,
+    /// Nginx configuration (location blocks)
+    Nginx,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedRoute {
+    pub method: String,
+    pub path: String,
+    pub upstream: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportedRoutes {
+    pub routes: Vec<ImportedRoute>,
+    pub warnings: Vec<String>,
+}
+
+impl ImportedRoutes {
+    /// Prefix matched paths are imported as the exact path and the path with a trailing
+    /// catch-all segment, as API definition routes only match whole paths
+    fn add(
+        &mut self,
+        methods: &[String],
+        path: &str,
+        prefix: bool,
+        upstream: Option<&str>,
+        source: &str,
+    ) {
+        let paths = if prefix {
+            prefix_route_paths(path)
+        } else {
+            vec![path.to_string()]
+        };
+
+        let methods = if methods.is_empty() {
+            self.warnings.push(format!(
+                "{source} does not restrict HTTP methods, imported as GET only"
+            ));
+            vec!["GET".to_string()]
+        } else {
+            methods.iter().map(|method| method.to_uppercase()).collect()
+        };
+
+        for method in methods {
+            if let Err(err) = to_method_pattern(&method) {
+                self.warnings
+                    .push(format!("{source}: skipping method {method}: {err}"));
+                continue;
+            }
+
+            for path in &paths {
+                self.routes.push(ImportedRoute {
+                    method: method.clone(),
+                    path: path.clone(),
+                    upstream: upstream.map(|upstream| upstream.to_string()),
+                })
+            }
+        }
+    }
+
+    pub fn to_http_api_definition(&self, version: &str) -> app_raw::HttpApiDefinition {
+        let mut seen = BTreeSet::new();
+        app_raw::HttpApiDefinition {
+            version: version.to_string(),
+            project: None,
+            routes: self
+                .routes
+                .iter()
+                .filter(|route| seen.insert((route.method.clone(), route.path.clone())))
+                .map(|route| app_raw::HttpApiDefinitionRoute {
+                    method: route.method.clone(),
+                    path: route.path.clone(),
+                    security: None,
+                    binding: app_raw::HttpApiDefinitionBinding {
+                        type_: None,
+                        component_name: Some(BINDING_PLACEHOLDER.to_string()),
+                        component_version: None,
+                        idempotency_key: None,
+                        invocation_context: None,
+                        response: Some(match &route.upstream {
+                            Some(upstream) => format!(
+                                "{BINDING_PLACEHOLDER}: bind to a component, previously proxied to {upstream}"
+                            ),
+                            None => format!("{BINDING_PLACEHOLDER}: bind to a component"),
+                        }),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+fn prefix_route_paths(path: &str) -> Vec<String> {
+    let is_catch_all = path
+        .rsplit('/')
+        .next()
+        .is_some_and(|segment| segment.starts_with("{+") && segment.ends_with('}'));
+    if is_catch_all {
+        return vec![path.to_string()];
+    }
+
+    let trimmed = path.trim_end_matches('/');
+    let wildcard = format!("{trimmed}/{{+{PREFIX_REST_VAR}}}");
+    if path.ends_with('/') && !trimmed.is_empty() {
+        vec![wildcard]
+    } else if trimmed.is_empty() {
+        vec!["/".to_string(), wildcard]
+    } else {
+        vec![trimmed.to_string(), wildcard]
+    }
+}
+
+pub fn import_routes(format: GatewayConfigFormat, config: &str) -> anyhow::Result<ImportedRoutes> {
+    match format {
+        GatewayConfigFormat::Kong => import_kong_routes(config),
+        GatewayConfigFormat::Nginx => import_nginx_routes(config),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KongConfig {
+    #[serde(default)]
+    services: Vec<KongService>,
+    #[serde(default)]
+    routes: Vec<KongRoute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct KongService {
+    name: Option<String>,
+    url: Option<String>,
+    protocol: Option<String>,
+    host: Option<String>,
+    port: Option<u16>,
+    path: Option<String>,
+    #[serde(default)]
+    routes: Vec<KongRoute>,
+}
+
+impl KongService {
+    fn upstream(&self) -> Option<String> {
+        match (&self.url, &self.host) {
+            (Some(url), _) => Some(url.clone()),
+            (None, Some(host)) => Some(format!(
+                "{}://{}{}{}",
+                self.protocol.as_deref().unwrap_or("http"),
+                host,
+                self.port.map(|port| format!(":{port}")).unwrap_or_default(),
+                self.path.as_deref().unwrap_or_default()
+            )),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct KongRoute {
+    name: Option<String>,
+    #[serde(default)]
+    paths: Vec<String>,
+    #[serde(default)]
+    methods: Vec<String>,
+    service: Option<KongServiceRef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KongServiceRef {
+    Name(String),
+    Object { name: Option<String> },
+}
+
+impl KongServiceRef {
+    fn name(&self) -> Option<&str> {
+        match self {
+            KongServiceRef::Name(name) => Some(name),
+            KongServiceRef::Object { name } => name.as_deref(),
+        }
+    }
+}
+
+fn import_kong_routes(config: &str) -> anyhow::Result<ImportedRoutes> {
+    let config: KongConfig =
+        serde_yaml::from_str(config).context("Failed to parse Kong declarative config")?;
+
+    let mut result = ImportedRoutes::default();
+
+    let add_route = |result: &mut ImportedRoutes, route: &KongRoute, upstream: Option<&str>| {
+        let source = format!(
+            "Kong route {}",
+            route.name.as_deref().unwrap_or("<unnamed>")
+        );
+        if route.paths.is_empty() {
+            result
+                .warnings
+                .push(format!("{source} has no paths, skipping"));
+        }
+        for path in &route.paths {
+            if path.starts_with('~') {
+                result
+                    .warnings
+                    .push(format!("{source}: skipping regex path {path}"));
+                continue;
+            }
+            // Kong route paths are prefix matched
+            result.add(&route.methods, path, true, upstream, &source);
+        }
+    };
+
+    for service in &config.services {
+        let upstream = service.upstream();
+        for route in &service.routes {
+            add_route(&mut result, route, upstream.as_deref());
+        }
+    }
+
+    for route in &config.routes {
+        let upstream = route
+            .service
+            .as_ref()
+            .and_then(|service| service.name())
+            .and_then(|name| {
+                config
+                    .services
+                    .iter()
+                    .find(|service| service.name.as_deref() == Some(name))
+            })
+            .and_then(|service| service.upstream());
+        add_route(&mut result, route, upstream.as_deref());
+    }
+
+    Ok(result)
+}
+
+#[derive(Debug)]
+struct NginxDirective {
+    name: String,
+    args: Vec<String>,
+    block: Option<Vec<NginxDirective>>,
+}
+
+fn import_nginx_routes(config: &str) -> anyhow::Result<ImportedRoutes> {
+    let tokens = nginx_tokens(config)?;
+    let mut pos = 0;
+    let directives = parse_nginx_block(&tokens, &mut pos, false)?;
+
+    let mut result = ImportedRoutes::default();
+    collect_nginx_locations(&directives, &mut result);
+    Ok(result)
+}
+
+fn nginx_tokens(config: &str) -> anyhow::Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = config.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '{' | '}' | ';' => tokens.push(c.to_string()),
+            '"' | '\'' => {
+                let quote = c;
+                let mut token = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            if let Some(c) = chars.next() {
+                                token.push(c)
+                            }
+                        }
+                        Some(c) if c == quote => break,
+                        Some(c) => token.push(c),
+                        None => bail!("Unterminated quoted string in nginx config"),
+                    }
+                }
+                tokens.push(token);
+            }
+            c => {
+                let mut token = c.to_string();
+                while let Some(c) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '{' | '}' | ';') {
+                        break;
+                    }
+                    token.push(*c);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn parse_nginx_block(
+    tokens: &[String],
+    pos: &mut usize,
+    nested: bool,
+) -> anyhow::Result<Vec<NginxDirective>> {
+    let mut directives = Vec::new();
+
+    while *pos < tokens.len() {
+        let name = tokens[*pos].clone();
+        *pos += 1;
+
+        if name == "}" {
+            if nested {
+                return Ok(directives);
+            }
+            bail!("Unexpected '}}' in nginx config");
+        }
+
+        let mut args = Vec::new();
+        loop {
+            let Some(token) = tokens.get(*pos) else {
+                return Err(anyhow!(
+                    "Unexpected end of nginx config in directive {name}"
+                ));
+            };
+            *pos += 1;
+            match token.as_str() {
+                ";" => {
+                    directives.push(NginxDirective {
+                        name,
+                        args,
+                        block: None,
+                    });
+                    break;
+                }
+                "{" => {
+                    let block = parse_nginx_block(tokens, pos, true)?;
+                    directives.push(NginxDirective {
+                        name,
+                        args,
+                        block: Some(block),
+                    });
+                    break;
+                }
+                _ => args.push(token.clone()),
+            }
+        }
+    }
+
+    if nested {
+        bail!("Unexpected end of nginx config, missing '}}'");
+    }
+
+    Ok(directives)
+}
+
+fn collect_nginx_locations(directives: &[NginxDirective], result: &mut ImportedRoutes) {
+    for directive in directives {
+        let Some(block) = &directive.block else {
+            continue;
+        };
+
+        if directive.name == "location" {
+            let path = match directive.args.as_slice() {
+                [path] => Some((path, true)),
+                [modifier, path] if modifier == "^~" => Some((path, true)),
+                [modifier, path] if modifier == "=" => Some((path, false)),
+                _ => None,
+            };
+
+            match path {
+                Some((path, _)) if path.starts_with('@') => {}
+                Some((path, prefix)) => {
+                    let source = format!("nginx location {path}");
+                    let methods = block
+                        .iter()
+                        .find(|directive| directive.name == "limit_except")
+                        .map(|directive| directive.args.clone())
+                        .unwrap_or_default();
+                    let upstream = block
+                        .iter()
+                        .find(|directive| directive.name == "proxy_pass")
+                        .and_then(|directive| directive.args.first());
+                    result.add(
+                        &methods,
+                        path,
+                        prefix,
+                        upstream.map(|s| s.as_str()),
+                        &source,
+                    );
+                }
+                None => result.warnings.push(format!(
+                    "skipping regex nginx location {}",
+                    directive.args.join(" ")
+                )),
+            }
+        }
+
+        collect_nginx_locations(block, result);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::model::gateway_import::{
+        import_routes, prefix_route_paths, GatewayConfigFormat, ImportedRoute,
+    };
+    use assert2::{assert, let_assert};
+
+    fn route(method: &str, path: &str, upstream: Option<&str>) -> ImportedRoute {
+        ImportedRoute {
+            method: method.to_string(),
+            path: path.to_string(),
+            upstream: upstream.map(|upstream| upstream.to_string()),
+        }
+    }
+
+    #[test]
+    fn import_kong_services_and_routes() {
+        let config = r#"
+_format_version: "3.0"
+services:
+  - name: users
+    url: http://users.internal:8080
+    routes:
+      - name: list-users
+        paths: ["/users"]
+        methods: ["GET", "post"]
+  - name: orders
+    host: orders.internal
+    port: 9000
+routes:
+  - name: get-order
+    paths: ["/orders/{id}", "~/orders/\\d+$"]
+    methods: ["GET"]
+    service:
+      name: orders
+"#;
+
+        let_assert!(Ok(imported) = import_routes(GatewayConfigFormat::Kong, config));
+
+        assert!(
+            imported.routes
+                == vec![
+                    route("GET", "/users", Some("http://users.internal:8080")),
+                    route("GET", "/users/{+rest}", Some("http://users.internal:8080")),
+                    route("POST", "/users", Some("http://users.internal:8080")),
+                    route("POST", "/users/{+rest}", Some("http://users.internal:8080")),
+                    route("GET", "/orders/{id}", Some("http://orders.internal:9000")),
+                    route(
+                        "GET",
+                        "/orders/{id}/{+rest}",
+                        Some("http://orders.internal:9000")
+                    ),
+                ]
+        );
+        assert!(imported.warnings.len() == 1);
+    }
+
+    #[test]
+    fn import_nginx_locations() {
+        let config = r#"
+http {
+    server {
+        listen 80; # comment
+        location = /health {
+            return 200;
+        }
+        location /api/items {
+            limit_except GET PUT { deny all; }
+            proxy_pass http://items:3000;
+        }
+        location ~* \.(png|jpg)$ {
+            root /var/www;
+        }
+    }
+}
+"#;
+
+        let_assert!(Ok(imported) = import_routes(GatewayConfigFormat::Nginx, config));
+
+        assert!(
+            imported.routes
+                == vec![
+                    route("GET", "/health", None),
+                    route("GET", "/api/items", Some("http://items:3000")),
+                    route("GET", "/api/items/{+rest}", Some("http://items:3000")),
+                    route("PUT", "/api/items", Some("http://items:3000")),
+                    route("PUT", "/api/items/{+rest}", Some("http://items:3000")),
+                ]
+        );
+        assert!(imported.warnings.len() == 2);
+    }
+
+    #[test]
+    fn prefix_paths() {
+        assert!(prefix_route_paths("/") == vec!["/", "/{+rest}"]);
+        assert!(prefix_route_paths("/api/") == vec!["/api/{+rest}"]);
+        assert!(prefix_route_paths("/api") == vec!["/api", "/api/{+rest}"]);
+        assert!(prefix_route_paths("/files/{+path}") == vec!["/files/{+path}"]);
+    }
+
+    #[test]
+    fn import_nginx_unbalanced_block() {
+        let_assert!(Err(_) = import_routes(GatewayConfigFormat::Nginx, "server { location / {"));
+    }
+}
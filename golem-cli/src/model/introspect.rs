@@ -0,0 +1,258 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use clap::{Arg, ArgAction, Command, ValueHint};
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable description of the command tree, for tools driving the CLI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandCatalogView {
+    pub cli_version: String,
+    pub command: CommandView,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandView {
+    pub name: String,
+    /// Full command path, starting with the binary name
+    pub path: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub about: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_about: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    pub hidden: bool,
+    /// Global args are only listed on the command defining them, but also apply to all
+    /// of its subcommands
+    pub args: Vec<ArgView>,
+    pub subcommands: Vec<CommandView>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArgValueType {
+    /// Flag without value
+    Boolean,
+    /// Flag which can be repeated, e.g. -vvv
+    Count,
+    String,
+    Path,
+    Url,
+    /// One of the possible values
+    Enum,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArgView {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub short: Option<char>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub long_help: Option<String>,
+    pub positional: bool,
+    pub value_type: ArgValueType,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub value_names: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub possible_values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub default_values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    pub required: bool,
+    pub multiple: bool,
+    pub global: bool,
+    pub hidden: bool,
+}
+
+impl CommandCatalogView {
+    pub fn new(command: &Command, cli_version: &str) -> Self {
+        Self {
+            cli_version: cli_version.to_string(),
+            command: CommandView::new(command, &[]),
+        }
+    }
+}
+
+impl CommandView {
+    fn new(command: &Command, parent_path: &[String]) -> Self {
+        let mut path = parent_path.to_vec();
+        path.push(command.get_name().to_string());
+
+        Self {
+            name: command.get_name().to_string(),
+            about: command.get_about().map(|about| about.to_string()),
+            long_about: command
+                .get_long_about()
+                .map(|long_about| long_about.to_string()),
+            aliases: command
+                .get_all_aliases()
+                .map(|alias| alias.to_string())
+                .collect(),
+            hidden: command.is_hide_set(),
+            args: command
+                .get_arguments()
+                .filter(|arg| {
+                    !matches!(
+                        arg.get_action(),
+                        ArgAction::Help
+                            | ArgAction::HelpShort
+                            | ArgAction::HelpLong
+                            | ArgAction::Version
+                    )
+                })
+                .map(ArgView::from)
+                .collect(),
+            subcommands: command
+                .get_subcommands()
+                .map(|subcommand| CommandView::new(subcommand, &path))
+                .collect(),
+            path,
+        }
+    }
+
+    /// Returns the command and all of its subcommands, depth first
+    pub fn flatten(&self) -> Vec<&CommandView> {
+        std::iter::once(self)
+            .chain(
+                self.subcommands
+                    .iter()
+                    .flat_map(|subcommand| subcommand.flatten()),
+            )
+            .collect()
+    }
+}
+
+impl From<&Arg> for ArgView {
+    fn from(arg: &Arg) -> Self {
+        let possible_values = arg
+            .get_possible_values()
+            .into_iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| value.get_name().to_string())
+            .collect::<Vec<_>>();
+
+        let value_type = match arg.get_action() {
+            ArgAction::SetTrue | ArgAction::SetFalse => ArgValueType::Boolean,
+            ArgAction::Count => ArgValueType::Count,
+            _ if !possible_values.is_empty() => ArgValueType::Enum,
+            _ => match arg.get_value_hint() {
+                ValueHint::AnyPath
+                | ValueHint::FilePath
+                | ValueHint::DirPath
+                | ValueHint::ExecutablePath => ArgValueType::Path,
+                ValueHint::Url => ArgValueType::Url,
+                _ => ArgValueType::String,
+            },
+        };
+
+        let multiple = matches!(arg.get_action(), ArgAction::Append)
+            || arg
+                .get_num_args()
+                .is_some_and(|num_args| num_args.max_values() > 1);
+
+        Self {
+            id: arg.get_id().to_string(),
+            long: arg.get_long().map(|long| long.to_string()),
+            short: arg.get_short(),
+            aliases: arg
+                .get_all_aliases()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|alias| alias.to_string())
+                .collect(),
+            help: arg.get_help().map(|help| help.to_string()),
+            long_help: arg.get_long_help().map(|long_help| long_help.to_string()),
+            positional: arg.is_positional(),
+            value_type,
+            value_names: arg
+                .get_value_names()
+                .unwrap_or_default()
+                .iter()
+                .map(|value_name| value_name.to_string())
+                .collect(),
+            possible_values,
+            default_values: arg
+                .get_default_values()
+                .iter()
+                .map(|value| value.to_string_lossy().to_string())
+                .collect(),
+            env: arg.get_env().map(|env| env.to_string_lossy().to_string()),
+            required: arg.is_required_set(),
+            multiple,
+            global: arg.is_global_set(),
+            hidden: arg.is_hide_set(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::GolemCliCommand;
+    use crate::model::introspect::{ArgValueType, CommandCatalogView};
+    use assert2::assert;
+    use clap::CommandFactory;
+    use test_r::test;
+
+    #[test]
+    fn command_catalog_contains_commands_and_args() {
+        let catalog = CommandCatalogView::new(&GolemCliCommand::command(), "1.0.0");
+
+        let format = catalog
+            .command
+            .args
+            .iter()
+            .find(|arg| arg.id == "format")
+            .unwrap();
+        assert!(format.global);
+        assert!(format.short == Some('f'));
+
+        let commands = catalog.command.flatten();
+        let shell = commands
+            .iter()
+            .find(|command| command.path[1..] == ["completion"])
+            .and_then(|command| command.args.iter().find(|arg| arg.id == "shell"))
+            .unwrap();
+        assert!(shell.positional);
+        assert!(shell.value_type == ArgValueType::Enum);
+        assert!(shell.possible_values.contains(&"bash".to_string()));
+
+        let introspect_commands = commands
+            .iter()
+            .find(|command| command.path[1..] == ["introspect", "commands"])
+            .unwrap();
+        assert!(introspect_commands.about.is_some());
+
+        let complete = commands
+            .iter()
+            .find(|command| command.path[1..] == ["__complete"])
+            .unwrap();
+        assert!(complete.hidden);
+
+        assert!(commands
+            .iter()
+            .flat_map(|command| command.args.iter())
+            .all(|arg| arg.help.is_some()));
+    }
+}
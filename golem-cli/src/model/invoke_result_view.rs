@@ -28,6 +28,8 @@ pub struct InvokeResultView {
     pub result_json: Option<ValueAndType>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub result_wave: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub trace_id: Option<String>,
 }
 
 impl InvokeResultView {
@@ -49,6 +51,7 @@ impl InvokeResultView {
             idempotency_key: idempotency_key.0,
             result_json: result.result,
             result_wave: wave,
+            trace_id: None,
         }
     }
 
@@ -57,6 +60,14 @@ impl InvokeResultView {
             idempotency_key: idempotency_key.0,
             result_json: None,
             result_wave: None,
+            trace_id: None,
+        }
+    }
+
+    pub fn with_trace_id(self, trace_id: String) -> Self {
+        Self {
+            trace_id: Some(trace_id),
+            ..self
         }
     }
 
@@ -0,0 +1,125 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[clap(rename_all = "kebab_case")]
+pub enum ServerManifestTarget {
+    #[default]
+    DockerCompose,
+    K8s,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[clap(rename_all = "kebab_case")]
+#[serde(rename_all = "kebab-case")]
+pub enum ServerPersistence {
+    #[default]
+    Sqlite,
+    Postgres,
+}
+
+/// Variables for rendering the docker compose and Kubernetes manifest templates
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerManifestConfig {
+    pub image_tag: String,
+    pub router_port: u16,
+    pub custom_request_port: u16,
+    pub worker_executors: u16,
+    pub persistence: ServerPersistence,
+    pub namespace: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalServerStatusView {
+    pub compose_file: PathBuf,
+    pub router_url: String,
+    pub healthy: bool,
+    pub services: Vec<LocalServerServiceView>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalServerServiceView {
+    pub name: String,
+    pub state: String,
+    pub status: String,
+}
+
+impl LocalServerServiceView {
+    /// Parses the output of "docker compose ps --format json", which is a JSON array in older
+    /// compose versions, and one JSON object per line in newer ones
+    pub fn from_compose_ps_output(output: &str) -> anyhow::Result<Vec<Self>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "PascalCase")]
+        struct ComposePsEntry {
+            service: String,
+            state: String,
+            #[serde(default)]
+            status: String,
+        }
+
+        let output = output.trim();
+        let entries: Vec<ComposePsEntry> = if output.starts_with('[') {
+            serde_json::from_str(output)?
+        } else {
+            output
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect::<Result<_, _>>()?
+        };
+
+        let mut services = entries
+            .into_iter()
+            .map(|entry| Self {
+                name: entry.service,
+                state: entry.state,
+                status: entry.status,
+            })
+            .collect::<Vec<_>>();
+        services.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(services)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::local_server::LocalServerServiceView;
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn parse_compose_ps_output() {
+        let array = r#"[{"Service":"redis","State":"running","Status":"Up 2 minutes"},{"Service":"golem-router","State":"exited","Status":"Exited (1)"}]"#;
+        let lines = "{\"Service\":\"redis\",\"State\":\"running\",\"Status\":\"Up 2 minutes\"}\n{\"Service\":\"golem-router\",\"State\":\"exited\",\"Status\":\"Exited (1)\"}\n";
+
+        for output in [array, lines] {
+            let services = LocalServerServiceView::from_compose_ps_output(output).unwrap();
+            assert!(services.len() == 2);
+            assert!(services[0].name == "golem-router");
+            assert!(services[0].state == "exited");
+            assert!(services[1].name == "redis");
+            assert!(services[1].status == "Up 2 minutes");
+        }
+
+        assert!(LocalServerServiceView::from_compose_ps_output("")
+            .unwrap()
+            .is_empty());
+    }
+}
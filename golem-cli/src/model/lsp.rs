@@ -0,0 +1,748 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::app_raw;
+use crate::model::desired_state::DesiredStateFile;
+use crate::model::template::{
+    contains_template, COMPONENT_TEMPLATE_VARIABLES, EXTERNAL_COMMAND_VARIABLES,
+};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Documentation of the manifest properties, shown on hover and as completion details.
+///
+/// Patterns are matched against the suffix of the key path of the hovered property, `*` matches
+/// any single key (e.g. component names), and a leading `/` anchors the pattern to the root.
+/// The more specific patterns have to come first. The descriptions are based on the
+/// application manifest schema.
+const PROPERTY_DOCS: &[(&str, &str)] = &[
+    ("/includes", "Include paths or globs for searching for application manifest documents, relative to the including document."),
+    ("/tempDir", "Temporary directory used for generating and building WIT and WASM artifacts. Default location is golem-temp in the application root."),
+    ("/witDeps", "List of source directories for common WIT dependency packages."),
+    ("/version", "Version of the application, embedded into the build metadata of the built components."),
+    ("/variables", "Variables available in all templated properties, and in HTTP API definition templates. Can be overridden with --var."),
+    ("/fetchWitDeps", "WIT dependency packages fetched from git repositories, keyed by dependency name."),
+    ("/stubSuffix", "Suffix used for naming the generated WASM RPC client packages and interfaces, must be kebab-case."),
+    ("/stubFileName", "File name of the generated WASM RPC client WIT file. Defaults to client.wit."),
+    ("/buildCache", "Shared build cache used for restoring and storing the outputs of the build steps."),
+    ("/templates", "Component definition templates."),
+    ("/components", "Components by component names."),
+    ("/dependencies", "Component dependencies by component names."),
+    ("/customCommands", "User defined custom commands."),
+    ("/clean", "User defined extra paths used in the clean command."),
+    ("/httpApi", "HTTP API definitions and deployments."),
+    ("/profiles", "Custom or customized profiles for the application."),
+    ("/httpApiDefinitions", "HTTP API definitions of the desired state by name, each with an explicit version."),
+    ("/httpApiDeployments", "HTTP API deployments of the desired state."),
+    ("buildCache.dir", "Cache directory, e.g. a mounted network share, relative to the manifest."),
+    ("buildCache.s3", "S3 bucket URL with optional prefix, e.g. s3://bucket/golem-cache, accessed using the aws CLI."),
+    ("buildCache.endpoint", "Custom endpoint URL for S3-compatible storages."),
+    ("buildCache.readOnly", "Only restore from the cache, without storing new entries, defaults to false."),
+    ("httpApi.definitions", "HTTP API definitions by name."),
+    ("httpApi.deployments", "HTTP API deployments by profile."),
+    ("routes.method", "HTTP method for the route."),
+    ("routes.path", "HTTP path pattern for the route."),
+    ("routes.security", "Optional ID of the required HTTP API security."),
+    ("routes.binding", "HTTP API route binding."),
+    ("binding.type", "Binding type, defaults to simple worker binding."),
+    ("binding.componentName", "Component to be used in the binding."),
+    ("binding.componentVersion", "Component version to be used in the binding, defaults to latest version."),
+    ("binding.idempotencyKey", "Rib script for calculating the idempotency key."),
+    ("binding.invocationContext", "Rib script for calculating the invocation context."),
+    ("binding.response", "Rib script for creating the response."),
+    ("definitions.*.version", "Version for the HTTP API definition."),
+    ("definitions.*.project", "Optional owner project of the HTTP API definition."),
+    ("definitions.*.routes", "Routes of the HTTP API definition."),
+    ("httpApiDefinitions.*.version", "Version for the HTTP API definition."),
+    ("httpApiDefinitions.*.project", "Optional owner project of the HTTP API definition."),
+    ("httpApiDefinitions.*.routes", "Routes of the HTTP API definition."),
+    ("host", "Host for the HTTP API deployment."),
+    ("subdomain", "Optional subdomain of the HTTP API deployment."),
+    ("deployments.*.definitions", "HTTP API definitions to be used in the deployment. Accepted formats: <name> or <name>@<version>."),
+    ("httpApiDeployments.definitions", "HTTP API definitions to be used in the deployment. Accepted formats: <name> or <name>@<version>."),
+    ("dependencies.*.type", "Type of the dependency: wasm-rpc, static-wasm-rpc or wasm."),
+    ("dependencies.*.target", "Target component name, a deployed component, or a stub published to a registry."),
+    ("dependencies.*.path", "Target component WASM path."),
+    ("dependencies.*.url", "Target component remote URL."),
+    ("dependencies.*.packageRenames", "Renames packages in the WIT files copied from the client of the target component."),
+    ("files.sourcePath", "Source path for the component file: either a local file or an URL."),
+    ("files.targetPath", "Target path for the component file, must be an absolute path."),
+    ("files.permissions", "Permission for the component file."),
+    ("plugins.name", "Name of the plugin."),
+    ("plugins.version", "Version of the plugin."),
+    ("plugins.parameters", "Key-value pairs for configuring the plugin installation."),
+    ("optimize.level", "Optimization level."),
+    ("optimize.passes", "Additional wasm-opt passes or flags, the leading dashes are optional, e.g. strip-debug."),
+    ("golemRustOverride.path", "Local path of the golem-rust crate."),
+    ("golemRustOverride.version", "Version of the golem-rust crate, ignored when path is specified."),
+    ("build.env", "Environment variables for the external command. The values and the working directory can use the command template variables."),
+    ("customCommands.*.env", "Environment variables for the external command. The values and the working directory can use the command template variables."),
+    ("/components.*.wasm", "Path of the component WASM, relative to the desired state file."),
+    ("/components.*.hash", "Expected BLAKE3 hash of the WASM in hex, for making sure the expected build is deployed."),
+    ("template", "Component template to be used for defining this component."),
+    ("sourceWit", "Source WIT directory for the user defined component WIT source(s)."),
+    ("generatedWit", "Generated WIT directory created by the golem tooling, which handles exported interface extraction and includes the WASM RPC dependencies."),
+    ("componentWasm", "File path for the built WASM component."),
+    ("linkedWasm", "File path for the linked WASM component which is ready to be uploaded to Golem."),
+    ("plugs", "File paths of additional WASM libraries which are composed into the component during linking."),
+    ("wasiAdapter", "File path of the WASI preview1 adapter used for turning the built WASM into a component during linking."),
+    ("build", "Commands used for creating component WASM."),
+    ("customCommands", "User defined custom commands."),
+    ("clean", "User defined extra paths used in the clean command."),
+    ("componentType", "Optional component type, defaults to durable."),
+    ("files", "Initial component files system."),
+    ("plugins", "Installed plugins for the component."),
+    ("env", "Environment variables for the component."),
+    ("golemRustOverride", "Overrides the golem-rust dependency used by the generated WASM RPC client of the component."),
+    ("optimize", "Enables the built-in optimize build step, which runs wasm-opt on the linked component."),
+    ("testFor", "Marks the component as the generated test component of the given app component."),
+    ("profiles", "Component definition profiles."),
+    ("defaultProfile", "Default profile."),
+    ("command", "External command to execute."),
+    ("dir", "Working directory for the command, defaults to the directory of golem.yaml in which the component is defined."),
+    ("rmdirs", "List of directories that should be deleted before running the command, runs before mkdirs."),
+    ("mkdirs", "List of directories that should be created before running the command, runs after rmdirs."),
+    ("sources", "Inputs (paths and globs) for the external command."),
+    ("targets", "Output (paths and globs) for the external command."),
+    ("dependsOn", "Custom commands or build steps that have to run before the command."),
+    ("when", "Condition as a template expression, the command is skipped if it evaluates to false."),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(into = "u8")]
+pub enum LspDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+impl From<LspDiagnosticSeverity> for u8 {
+    fn from(severity: LspDiagnosticSeverity) -> Self {
+        match severity {
+            LspDiagnosticSeverity::Error => 1,
+            LspDiagnosticSeverity::Warning => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(into = "u8")]
+pub enum LspCompletionItemKind {
+    Variable,
+    Property,
+    Value,
+    EnumMember,
+}
+
+impl From<LspCompletionItemKind> for u8 {
+    fn from(kind: LspCompletionItemKind) -> Self {
+        match kind {
+            LspCompletionItemKind::Variable => 6,
+            LspCompletionItemKind::Property => 10,
+            LspCompletionItemKind::Value => 12,
+            LspCompletionItemKind::EnumMember => 20,
+        }
+    }
+}
+
+/// Zero based position, the character offset is counted in UTF-16 code units, as in LSP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspPosition {
+    pub line: usize,
+    pub character: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspRange {
+    pub start: LspPosition,
+    pub end: LspPosition,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspDiagnostic {
+    pub range: LspRange,
+    pub severity: LspDiagnosticSeverity,
+    pub source: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LspCompletionItem {
+    pub label: String,
+    pub kind: LspCompletionItemKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// The edited YAML documents are either application manifests or desired state files
+/// (used by the apply command), the latter are recognized by their top level HTTP API keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LspDocumentKind {
+    AppManifest,
+    DesiredState,
+}
+
+impl LspDocumentKind {
+    pub fn detect(text: &str) -> Self {
+        let is_desired_state = text.lines().any(|line| {
+            line.starts_with("httpApiDefinitions:") || line.starts_with("httpApiDeployments:")
+        });
+        if is_desired_state {
+            Self::DesiredState
+        } else {
+            Self::AppManifest
+        }
+    }
+}
+
+/// Returns the names of the components defined in the document
+pub fn defined_component_names(text: &str) -> BTreeSet<String> {
+    child_keys(text, "components")
+}
+
+/// Validates the document against the manifest model and parses the Rib scripts of the
+/// HTTP API route bindings. Unknown binding components are only reported when the known
+/// component names are available.
+pub fn diagnostics(text: &str, known_component_names: &BTreeSet<String>) -> Vec<LspDiagnostic> {
+    if text.trim().is_empty() {
+        return vec![];
+    }
+
+    let definitions = match LspDocumentKind::detect(text) {
+        LspDocumentKind::AppManifest => app_raw::Application::from_yaml_str(text).map(|app| {
+            app.http_api
+                .map(|http_api| http_api.definitions.into_values().collect::<Vec<_>>())
+                .unwrap_or_default()
+        }),
+        LspDocumentKind::DesiredState => serde_yaml::from_str::<DesiredStateFile>(text)
+            .map(|file| file.http_api_definitions.into_values().collect()),
+    };
+
+    let definitions = match definitions {
+        Ok(definitions) => definitions,
+        Err(err) => {
+            let (line, column) = err
+                .location()
+                .map(|location| {
+                    (
+                        location.line().saturating_sub(1),
+                        location.column().saturating_sub(1),
+                    )
+                })
+                .unwrap_or((0, 0));
+            return vec![line_diagnostic(
+                text,
+                line,
+                column,
+                LspDiagnosticSeverity::Error,
+                err.to_string(),
+            )];
+        }
+    };
+
+    let mut known_component_names = known_component_names.clone();
+    if !known_component_names.is_empty() {
+        known_component_names.extend(child_keys(text, "components"));
+    }
+
+    let mut diagnostics = Vec::new();
+    for binding in definitions
+        .iter()
+        .flat_map(|definition| definition.routes.iter())
+        .map(|route| &route.binding)
+    {
+        for (key, rib) in [
+            ("response", &binding.response),
+            ("idempotencyKey", &binding.idempotency_key),
+            ("invocationContext", &binding.invocation_context),
+        ] {
+            let Some(rib) = rib else {
+                continue;
+            };
+            if contains_template(rib) {
+                continue;
+            }
+            if let Err(err) = rib::from_string(rib.as_str()) {
+                let (line, column) = rib_error_position(text, key, rib, &err);
+                diagnostics.push(line_diagnostic(
+                    text,
+                    line,
+                    column,
+                    LspDiagnosticSeverity::Error,
+                    format!("Failed to parse {key} as Rib: {err}"),
+                ));
+            }
+        }
+
+        if let Some(component_name) = &binding.component_name {
+            if !known_component_names.is_empty()
+                && !contains_template(component_name)
+                && !known_component_names.contains(component_name)
+            {
+                let (line, column) = value_position(text, "componentName", component_name);
+                diagnostics.push(line_diagnostic(
+                    text,
+                    line,
+                    column,
+                    LspDiagnosticSeverity::Warning,
+                    format!("Unknown component: {component_name}"),
+                ));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+/// Completes template variables inside `{{ }}`, component and template names and enum
+/// values after the related keys, and property keys at the start of the line
+pub fn completions(
+    text: &str,
+    position: LspPosition,
+    component_names: &BTreeSet<String>,
+) -> Vec<LspCompletionItem> {
+    let line = text.lines().nth(position.line).unwrap_or_default();
+    let prefix = utf16_prefix(line, position.character);
+
+    let is_in_template = match (prefix.rfind("{{"), prefix.rfind("}}")) {
+        (Some(open), Some(close)) => close < open,
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+    if is_in_template {
+        return COMPONENT_TEMPLATE_VARIABLES
+            .iter()
+            .map(|name| (name.to_string(), "component template variable"))
+            .chain(
+                EXTERNAL_COMMAND_VARIABLES
+                    .iter()
+                    .map(|name| (name.to_string(), "command template variable")),
+            )
+            .chain(
+                child_keys(text, "variables")
+                    .into_iter()
+                    .map(|name| (name, "application variable")),
+            )
+            .map(|(label, detail)| LspCompletionItem {
+                label,
+                kind: LspCompletionItemKind::Variable,
+                detail: Some(detail.to_string()),
+            })
+            .collect();
+    }
+
+    let partial_value_regex =
+        Regex::new(r#"^\s*(?:-\s+)*([A-Za-z_][A-Za-z0-9_\-]*)\s*:\s+["']?[^"'\s]*$"#).unwrap();
+    if let Some(captures) = partial_value_regex.captures(prefix) {
+        let (kind, values): (_, Vec<String>) = match &captures[1] {
+            "componentName" | "target" | "testFor" => {
+                let mut names = component_names.clone();
+                names.extend(child_keys(text, "components"));
+                (LspCompletionItemKind::Value, names.into_iter().collect())
+            }
+            "template" => (
+                LspCompletionItemKind::Value,
+                child_keys(text, "templates").into_iter().collect(),
+            ),
+            "componentType" => (
+                LspCompletionItemKind::EnumMember,
+                enum_values(&["durable", "ephemeral", "library"]),
+            ),
+            "type" => (
+                LspCompletionItemKind::EnumMember,
+                match key_path(text, position.line).iter().rev().nth(1) {
+                    Some(parent) if parent == "binding" => {
+                        enum_values(&["default", "cors-preflight", "file-server", "http-handler"])
+                    }
+                    _ => enum_values(&["wasm-rpc", "static-wasm-rpc", "wasm"]),
+                },
+            ),
+            "method" => (
+                LspCompletionItemKind::EnumMember,
+                enum_values(&[
+                    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+                ]),
+            ),
+            _ => (LspCompletionItemKind::Value, vec![]),
+        };
+        return values
+            .into_iter()
+            .map(|label| LspCompletionItem {
+                label,
+                kind,
+                detail: None,
+            })
+            .collect();
+    }
+
+    let partial_key_regex = Regex::new(r#"^\s*(?:-\s+)*[A-Za-z_]*$"#).unwrap();
+    if partial_key_regex.is_match(prefix) {
+        let mut seen = BTreeSet::new();
+        return PROPERTY_DOCS
+            .iter()
+            .filter_map(|(pattern, doc)| {
+                let key = pattern.rsplit(['.', '/']).next().unwrap_or(pattern);
+                seen.insert(key).then(|| LspCompletionItem {
+                    label: key.to_string(),
+                    kind: LspCompletionItemKind::Property,
+                    detail: Some(doc.to_string()),
+                })
+            })
+            .collect();
+    }
+
+    vec![]
+}
+
+/// Returns the markdown documentation of the property key under the cursor
+pub fn hover(text: &str, position: LspPosition) -> Option<String> {
+    let line = text.lines().nth(position.line)?;
+    let (key_start, key) = line_key(line)?;
+    let character = utf16_prefix(line, position.character).len();
+    if character < key_start || character > key_start + key.len() {
+        return None;
+    }
+
+    let path = key_path(text, position.line);
+    PROPERTY_DOCS
+        .iter()
+        .find(|(pattern, _)| pattern_matches(pattern, &path))
+        .map(|(_, doc)| format!("**{key}**\n\n{doc}"))
+}
+
+fn enum_values(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+fn pattern_matches(pattern: &str, path: &[String]) -> bool {
+    let (anchored, pattern) = match pattern.strip_prefix('/') {
+        Some(pattern) => (true, pattern),
+        None => (false, pattern),
+    };
+    let segments = pattern.split('.').collect::<Vec<_>>();
+    if segments.len() > path.len() || (anchored && segments.len() != path.len()) {
+        return false;
+    }
+    segments
+        .iter()
+        .rev()
+        .zip(path.iter().rev())
+        .all(|(segment, key)| *segment == "*" || segment == key)
+}
+
+/// Returns the indentation and the key of lines defining a mapping key, list item markers
+/// are counted as indentation, so the indentation is also the byte offset of the key
+fn line_key(line: &str) -> Option<(usize, &str)> {
+    let mut indent = 0;
+    loop {
+        let rest = &line[indent..];
+        let trimmed = rest.trim_start_matches([' ', '\t']);
+        indent += rest.len() - trimmed.len();
+        match trimmed.strip_prefix('-') {
+            Some(after_dash) if after_dash.starts_with([' ', '\t']) => indent += 1,
+            _ => break,
+        }
+    }
+
+    // Component names can contain colons, so the key ends at the first colon followed by
+    // whitespace or by the end of the line
+    let rest = line[indent..].trim_end();
+    let key_len = rest.match_indices(':').map(|(idx, _)| idx).find(|idx| {
+        let after_colon = &rest[idx + 1..];
+        after_colon.is_empty() || after_colon.starts_with(char::is_whitespace)
+    })?;
+    let key = &rest[..key_len];
+    let is_valid_key = key
+        .chars()
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+        && key
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || matches!(char, '_' | '-' | ':' | '.'));
+    is_valid_key.then_some((indent, key))
+}
+
+/// Returns the keys from the root to the key defined on the given line, based on indentation
+fn key_path(text: &str, line: usize) -> Vec<String> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let Some((mut indent, key)) = lines.get(line).and_then(|line| line_key(line)) else {
+        return vec![];
+    };
+
+    let mut path = vec![key.to_string()];
+    for line in lines[..line].iter().rev() {
+        if indent == 0 {
+            break;
+        }
+        if let Some((parent_indent, parent_key)) = line_key(line) {
+            if parent_indent < indent {
+                path.push(parent_key.to_string());
+                indent = parent_indent;
+            }
+        }
+    }
+    path.reverse();
+    path
+}
+
+/// Returns the keys directly under the given top level key, also for documents which are
+/// not (yet) valid YAML
+fn child_keys(text: &str, parent: &str) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    let mut in_parent = false;
+    let mut child_indent = None;
+    for line in text.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        match line_key(line) {
+            Some((0, key)) => {
+                in_parent = key == parent;
+                child_indent = None;
+            }
+            Some((indent, key)) if in_parent => {
+                if *child_indent.get_or_insert(indent) == indent {
+                    keys.insert(key.to_string());
+                }
+            }
+            _ => {
+                if !line.starts_with([' ', '\t', '-']) {
+                    in_parent = false;
+                }
+            }
+        }
+    }
+    keys
+}
+
+/// Finds the (zero based) line and character index where the value of the given key
+/// starts in the document, for inline and for block scalar values
+fn value_position(text: &str, key: &str, value: &str) -> (usize, usize) {
+    let needle = value.lines().map(str::trim).find(|line| !line.is_empty());
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut first_key_line = None;
+    for (idx, line) in lines.iter().enumerate() {
+        if line_key(line).map(|(_, line_key)| line_key) != Some(key) {
+            continue;
+        }
+        first_key_line.get_or_insert(idx);
+        let Some(needle) = needle else {
+            return (idx, 0);
+        };
+        let candidates = std::iter::once(idx).chain(
+            lines
+                .iter()
+                .enumerate()
+                .skip(idx + 1)
+                .find(|(_, line)| !line.trim().is_empty())
+                .map(|(idx, _)| idx),
+        );
+        for candidate in candidates {
+            if let Some(byte_idx) = lines[candidate].find(needle) {
+                return (candidate, lines[candidate][..byte_idx].chars().count());
+            }
+        }
+    }
+    (first_key_line.unwrap_or(0), 0)
+}
+
+fn rib_error_position(text: &str, key: &str, rib: &str, err: &str) -> (usize, usize) {
+    let (line, column) = value_position(text, key, rib);
+    let rib_first_line_offset = rib
+        .lines()
+        .position(|line| !line.trim().is_empty())
+        .unwrap_or(0);
+    let rib_error_position_regex =
+        Regex::new("Parse error at line: (\\d+), column: (\\d+)").unwrap();
+    let Some(captures) = rib_error_position_regex.captures(err) else {
+        return (line, column);
+    };
+    let (Ok(err_line), Ok(err_column)) =
+        (captures[1].parse::<usize>(), captures[2].parse::<usize>())
+    else {
+        return (line, column);
+    };
+    let err_line = err_line.saturating_sub(1);
+    let err_column = err_column.saturating_sub(1);
+    if err_line <= rib_first_line_offset {
+        let rib_indent = rib
+            .lines()
+            .nth(rib_first_line_offset)
+            .map(|line| line.len() - line.trim_start().len())
+            .unwrap_or(0);
+        (line, column + err_column.saturating_sub(rib_indent))
+    } else {
+        let line = line + err_line - rib_first_line_offset;
+        let indent = text
+            .lines()
+            .nth(line)
+            .map(|line| line.chars().take_while(|char| char.is_whitespace()).count())
+            .unwrap_or(0);
+        (line, indent + err_column)
+    }
+}
+
+/// Creates a diagnostic from the given character index to the end of the line
+fn line_diagnostic(
+    text: &str,
+    line: usize,
+    column: usize,
+    severity: LspDiagnosticSeverity,
+    message: String,
+) -> LspDiagnostic {
+    let line_text = text.lines().nth(line).unwrap_or_default();
+    let start = line_text
+        .chars()
+        .take(column)
+        .map(char::len_utf16)
+        .sum::<usize>();
+    let end = line_text.encode_utf16().count().max(start);
+    LspDiagnostic {
+        range: LspRange {
+            start: LspPosition {
+                line,
+                character: start,
+            },
+            end: LspPosition {
+                line,
+                character: end,
+            },
+        },
+        severity,
+        source: "golem",
+        message,
+    }
+}
+
+/// Returns the part of the line before the given UTF-16 offset
+fn utf16_prefix(line: &str, character: usize) -> &str {
+    let mut utf16_offset = 0;
+    for (byte_idx, char) in line.char_indices() {
+        if utf16_offset >= character {
+            return &line[..byte_idx];
+        }
+        utf16_offset += char.len_utf16();
+    }
+    line
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::lsp::{completions, diagnostics, hover, LspDiagnosticSeverity, LspPosition};
+    use assert2::assert;
+    use std::collections::BTreeSet;
+    use test_r::test;
+
+    const MANIFEST: &str = r#"variables:
+  region: eu
+components:
+  app:shop:
+    componentType: durable
+httpApi:
+  definitions:
+    shop-api:
+      version: 0.0.1
+      routes:
+        - method: GET
+          path: /cart
+          binding:
+            componentName: app:shop
+            response: |
+              let x = ;
+              x
+        - method: GET
+          path: /other
+          binding:
+            componentName: app:missing
+            response: "{ status: 200u64 }"
+"#;
+
+    #[test]
+    fn manifest_diagnostics_completion_and_hover() {
+        let known_component_names = BTreeSet::from(["app:other".to_string()]);
+
+        let diagnostics = diagnostics(MANIFEST, &known_component_names);
+        assert!(diagnostics.len() == 2);
+        assert!(diagnostics[0].severity == LspDiagnosticSeverity::Error);
+        assert!((15..=16).contains(&diagnostics[0].range.start.line));
+        assert!(diagnostics[1].severity == LspDiagnosticSeverity::Warning);
+        assert!(diagnostics[1].range.start.line == 20);
+        assert!(diagnostics[1].range.start.character == 27);
+
+        let invalid = super::diagnostics("components: {}\nunknownKey: 1\n", &BTreeSet::new());
+        assert!(invalid.len() == 1);
+        assert!(invalid[0].range.start.line == 1);
+
+        let labels = |line: usize, character: usize| {
+            completions(
+                MANIFEST,
+                LspPosition { line, character },
+                &known_component_names,
+            )
+            .into_iter()
+            .map(|item| item.label)
+            .collect::<Vec<_>>()
+        };
+        assert!(labels(13, 27) == vec!["app:other", "app:shop"]);
+        assert!(labels(10, 18).contains(&"POST".to_string()));
+        assert!(labels(4, 4).contains(&"componentType".to_string()));
+
+        let with_template = "components:\n  app:shop:\n    build:\n      - command: echo {{ ";
+        let template_labels = completions(
+            with_template,
+            LspPosition {
+                line: 3,
+                character: 26,
+            },
+            &BTreeSet::new(),
+        )
+        .into_iter()
+        .map(|item| item.label)
+        .collect::<Vec<_>>();
+        assert!(template_labels.contains(&"componentName".to_string()));
+        assert!(template_labels.contains(&"profile".to_string()));
+
+        let hover_response = hover(
+            MANIFEST,
+            LspPosition {
+                line: 14,
+                character: 14,
+            },
+        );
+        assert!(
+            hover_response
+                == Some("**response**\n\nRib script for creating the response.".to_string())
+        );
+        assert!(hover(
+            MANIFEST,
+            LspPosition {
+                line: 4,
+                character: 6
+            }
+        )
+        .is_some_and(|doc| doc.contains("component type")));
+        assert!(hover(
+            MANIFEST,
+            LspPosition {
+                line: 0,
+                character: 2
+            }
+        )
+        .is_some_and(|doc| doc.contains("templated properties")));
+    }
+}
@@ -0,0 +1,298 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Category of the metrics shown by default, based on the metric name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricCategory {
+    Invocations,
+    Errors,
+    Memory,
+    Other,
+}
+
+impl MetricCategory {
+    pub fn from_metric_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.contains("error") || name.contains("fail") {
+            MetricCategory::Errors
+        } else if name.contains("invocation") || name.contains("invoke") {
+            MetricCategory::Invocations
+        } else if name.contains("memory") {
+            MetricCategory::Memory
+        } else {
+            MetricCategory::Other
+        }
+    }
+}
+
+/// A family of samples from the Prometheus text exposition format
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricFamily {
+    pub name: String,
+    pub help: Option<String>,
+    pub metric_type: Option<String>,
+    pub samples: Vec<MetricSample>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSample {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricView {
+    pub service: String,
+    pub category: MetricCategory,
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+    /// Per second change of counters, only available when sampling with a rate window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f64>,
+}
+
+/// Parses the Prometheus text exposition format, samples without a preceding TYPE or HELP line
+/// get their own family. Unparsable lines are skipped.
+pub fn parse_metrics(text: &str) -> Vec<MetricFamily> {
+    let mut families = Vec::<MetricFamily>::new();
+
+    let family_index = |families: &mut Vec<MetricFamily>, name: &str| -> usize {
+        match families.iter().position(|family| family.name == name) {
+            Some(index) => index,
+            None => {
+                families.push(MetricFamily {
+                    name: name.to_string(),
+                    help: None,
+                    metric_type: None,
+                    samples: vec![],
+                });
+                families.len() - 1
+            }
+        }
+    };
+
+    for line in text.lines().map(|line| line.trim()) {
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let mut parts = comment.trim_start().splitn(3, ' ');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some("HELP"), Some(name), help) => {
+                    let index = family_index(&mut families, name);
+                    families[index].help = help.map(|help| help.to_string());
+                }
+                (Some("TYPE"), Some(name), Some(metric_type)) => {
+                    let index = family_index(&mut families, name);
+                    families[index].metric_type = Some(metric_type.trim().to_string());
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        let Some(sample) = parse_sample(line) else {
+            continue;
+        };
+
+        // Histogram and summary samples belong to the family without their suffix
+        let family_name = families
+            .iter()
+            .rev()
+            .find(|family| {
+                sample.name == family.name
+                    || ["_bucket", "_sum", "_count", "_total", "_created"]
+                        .iter()
+                        .any(|suffix| sample.name == format!("{}{}", family.name, suffix))
+            })
+            .map(|family| family.name.clone())
+            .unwrap_or_else(|| sample.name.clone());
+        let index = family_index(&mut families, &family_name);
+        families[index].samples.push(sample);
+    }
+
+    families
+}
+
+fn parse_sample(line: &str) -> Option<MetricSample> {
+    let (name, rest) = match line.find(['{', ' ']) {
+        Some(index) => line.split_at(index),
+        None => return None,
+    };
+
+    let (labels, rest) = match rest.strip_prefix('{') {
+        Some(rest) => parse_labels(rest)?,
+        None => (BTreeMap::new(), rest),
+    };
+
+    let value = rest.split_whitespace().next()?;
+    let value = match value {
+        "+Inf" => f64::INFINITY,
+        "-Inf" => f64::NEG_INFINITY,
+        value => value.parse().ok()?,
+    };
+
+    Some(MetricSample {
+        name: name.to_string(),
+        labels,
+        value,
+    })
+}
+
+/// Parses the labels after the opening brace, returns the labels and the rest of the line
+fn parse_labels(input: &str) -> Option<(BTreeMap<String, String>, &str)> {
+    let mut labels = BTreeMap::new();
+    let mut rest = input;
+    loop {
+        rest = rest.trim_start_matches([' ', ',']);
+        if let Some(rest) = rest.strip_prefix('}') {
+            return Some((labels, rest));
+        }
+
+        let (name, after_name) = rest.split_once('=')?;
+        let after_name = after_name.strip_prefix('"')?;
+
+        let mut value = String::new();
+        let mut chars = after_name.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    char => value.push(char),
+                },
+                (index, '"') => break index,
+                (_, char) => value.push(char),
+            }
+        };
+
+        labels.insert(name.trim().to_string(), value);
+        rest = &after_name[end + 1..];
+    }
+}
+
+/// Renders the families in the OpenMetrics text format
+pub fn render_openmetrics(families: &[MetricFamily]) -> String {
+    let mut output = String::new();
+    for family in families {
+        if let Some(metric_type) = &family.metric_type {
+            let _ = writeln!(output, "# TYPE {} {}", family.name, metric_type);
+        }
+        if let Some(help) = &family.help {
+            let _ = writeln!(output, "# HELP {} {}", family.name, help);
+        }
+        for sample in &family.samples {
+            let labels = sample
+                .labels
+                .iter()
+                .map(|(name, value)| {
+                    format!(
+                        "{}=\"{}\"",
+                        name,
+                        value
+                            .replace('\\', "\\\\")
+                            .replace('"', "\\\"")
+                            .replace('\n', "\\n")
+                    )
+                })
+                .collect::<Vec<_>>();
+            let labels = if labels.is_empty() {
+                String::new()
+            } else {
+                format!("{{{}}}", labels.join(","))
+            };
+            let value = if sample.value.is_infinite() {
+                (if sample.value > 0.0 { "+Inf" } else { "-Inf" }).to_string()
+            } else {
+                sample.value.to_string()
+            };
+            let _ = writeln!(output, "{}{} {}", sample.name, labels, value);
+        }
+    }
+    output.push_str("# EOF\n");
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::metrics::{parse_metrics, render_openmetrics, MetricCategory, MetricSample};
+    use assert2::assert;
+    use std::collections::BTreeMap;
+    use test_r::test;
+
+    const METRICS: &str = r#"
+# HELP invocation_total Number of invocations
+# TYPE invocation_total counter
+invocation_total{component_id="c1",worker_name="w1"} 12
+invocation_total{component_id="c1",worker_name="w\"2"} 3 1700000000000
+# TYPE invocation_duration_seconds histogram
+invocation_duration_seconds_bucket{le="0.1"} 4
+invocation_duration_seconds_bucket{le="+Inf"} 5
+invocation_duration_seconds_sum 0.7
+invocation_duration_seconds_count 5
+process_resident_memory_bytes 1.5e6
+"#;
+
+    #[test]
+    fn parse_and_render_metrics() {
+        let families = parse_metrics(METRICS);
+
+        assert!(
+            families
+                .iter()
+                .map(|family| family.name.as_str())
+                .collect::<Vec<_>>()
+                == vec![
+                    "invocation_total",
+                    "invocation_duration_seconds",
+                    "process_resident_memory_bytes"
+                ]
+        );
+        assert!(families[0].metric_type.as_deref() == Some("counter"));
+        assert!(
+            families[0].samples[1]
+                == MetricSample {
+                    name: "invocation_total".to_string(),
+                    labels: BTreeMap::from([
+                        ("component_id".to_string(), "c1".to_string()),
+                        ("worker_name".to_string(), "w\"2".to_string())
+                    ]),
+                    value: 3.0,
+                }
+        );
+        assert!(families[1].samples.len() == 4);
+        assert!(families[1].samples[1].value == f64::INFINITY);
+        assert!(families[2].samples[0].value == 1_500_000.0);
+
+        assert!(
+            MetricCategory::from_metric_name("invocation_total") == MetricCategory::Invocations
+        );
+        assert!(
+            MetricCategory::from_metric_name("process_resident_memory_bytes")
+                == MetricCategory::Memory
+        );
+
+        let rendered = render_openmetrics(&families);
+        assert!(rendered.ends_with("# EOF\n"));
+        assert!(parse_metrics(&rendered) == families);
+    }
+}
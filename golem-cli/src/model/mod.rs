@@ -16,20 +16,41 @@ pub mod agent;
 pub mod api;
 pub mod app;
 pub mod app_raw;
+pub mod app_test;
+pub mod build_plan;
+pub mod ci;
+pub mod compat;
 pub mod component;
+pub mod dependency_graph;
 pub mod deploy;
 pub mod deploy_diff;
+pub mod desired_state;
+pub mod doctor;
+pub mod event;
+pub mod extension;
+pub mod gateway_import;
+pub mod introspect;
 pub mod invoke_result_view;
+pub mod local_server;
+pub mod lsp;
+pub mod metrics;
+pub mod openapi;
 pub mod plugin_manifest;
 pub mod project;
+pub mod sdk;
+pub mod shared_types;
 pub mod template;
+pub mod terraform;
 pub mod text;
+pub mod trace;
 pub mod wave;
 pub mod worker;
+pub mod worker_archive;
+pub mod worker_replay;
 
 use crate::command::shared_args::{ComponentTemplateName, StreamArgs};
 use crate::config::AuthenticationConfig;
-use crate::config::{NamedProfile, ProfileConfig, ProfileName};
+use crate::config::{NamedProfile, ProfileConfig, ProfileName, ProfileOrigins};
 use crate::log::LogColorize;
 use anyhow::{anyhow, Context};
 use chrono::{DateTime, Utc};
@@ -45,7 +66,7 @@ use golem_templates::model::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::ffi::OsStr;
 use std::fmt::{Debug, Display, Formatter};
 use std::io::Read;
@@ -573,6 +594,38 @@ pub struct WorkersMetadataResponseView {
     pub cursors: BTreeMap<String, String>,
 }
 
+/// Page of a paged worker list, the paged equivalent of [WorkersMetadataResponseView]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkerMetadataPageView(pub Vec<WorkerMetadataView>);
+
+impl From<Vec<WorkerMetadataView>> for WorkerMetadataPageView {
+    fn from(value: Vec<WorkerMetadataView>) -> Self {
+        Self(value)
+    }
+}
+
+/// Logged after the last [WorkerMetadataPageView] of a paged worker list
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkersMetadataCursorsView {
+    pub cursors: BTreeMap<String, String>,
+}
+
+/// Worker metadata view tagged with the profile it was queried with, used for multi-profile commands
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileWorkerMetadataView {
+    pub profile: ProfileName,
+    #[serde(flatten)]
+    pub worker: WorkerMetadataView,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileWorkersMetadataResponseView {
+    pub workers: Vec<ProfileWorkerMetadataView>,
+    /// Profiles with more workers than listed. Scan cursors are not returned in multi-profile
+    /// mode, as they can only be used for a single profile.
+    pub truncated_profiles: BTreeSet<ProfileName>,
+}
+
 impl TrimDateTime for WorkersMetadataResponseView {
     fn trim_date_time_ms(self) -> Self {
         Self {
@@ -608,6 +661,8 @@ pub struct ProfileView {
     pub is_active: bool,
     pub name: ProfileName,
     #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub extends: Option<ProfileName>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     pub url: Option<Url>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub cloud_url: Option<Url>,
@@ -618,10 +673,16 @@ pub struct ProfileView {
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub authenticated: Option<bool>,
     pub config: ProfileConfig,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub origins: ProfileOrigins,
 }
 
 impl ProfileView {
-    pub fn from_profile(active: &ProfileName, profile: NamedProfile) -> Self {
+    pub fn from_profile(
+        active: &ProfileName,
+        profile: NamedProfile,
+        origins: ProfileOrigins,
+    ) -> Self {
         let NamedProfile { name, profile } = profile;
 
         let authenticated = match &profile.auth {
@@ -632,12 +693,14 @@ impl ProfileView {
         ProfileView {
             is_active: &name == active,
             name,
+            extends: profile.extends,
             url: profile.custom_url,
             cloud_url: profile.custom_cloud_url,
             worker_url: profile.custom_worker_url,
             allow_insecure: profile.allow_insecure,
             authenticated,
             config: profile.config,
+            origins,
         }
     }
 }
@@ -705,6 +768,17 @@ impl FromStr for ProjectPolicyId {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ProjectGrantId(pub Uuid);
+
+impl FromStr for ProjectGrantId {
+    type Err = uuid::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ProjectGrantId(Uuid::parse_str(s)?))
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug, EnumIter, Serialize, Deserialize)]
 pub enum Role {
     Admin,
@@ -0,0 +1,197 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::api::route_pattern_var;
+use golem_client::model::HttpApiDefinitionRequest;
+use serde_json::{json, Map, Value};
+
+/// Converts an API definition to an OpenAPI 3 document, which is only meant for previewing
+/// and reviewing the route structure, bindings are described using x-golem-binding extensions.
+pub fn to_openapi_spec(api_definition: &HttpApiDefinitionRequest) -> Value {
+    let mut paths = Map::<String, Value>::new();
+
+    for route in &api_definition.routes {
+        let (path_pattern, query_pattern) = match route.path.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (route.path.as_str(), None),
+        };
+
+        let mut parameters = Vec::<Value>::new();
+        let path = path_pattern
+            .split('/')
+            .map(|segment| match route_pattern_var(segment) {
+                Some(var) => {
+                    parameters.push(json!({
+                        "name": var,
+                        "in": "path",
+                        "required": true,
+                        "schema": { "type": "string" }
+                    }));
+                    format!("{{{var}}}")
+                }
+                None => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        for query_part in query_pattern.into_iter().flat_map(|query| query.split('&')) {
+            if let Some(var) = route_pattern_var(query_part) {
+                parameters.push(json!({
+                    "name": var,
+                    "in": "query",
+                    "required": false,
+                    "schema": { "type": "string" }
+                }));
+            }
+        }
+
+        let binding_type = route
+            .binding
+            .binding_type
+            .as_ref()
+            .map(|binding_type| binding_type.to_string());
+        let component = route.binding.component.as_ref();
+
+        let mut binding = Map::<String, Value>::new();
+        if let Some(binding_type) = &binding_type {
+            binding.insert("type".to_string(), json!(binding_type));
+        }
+        if let Some(component) = component {
+            binding.insert("componentName".to_string(), json!(component.name));
+            if let Some(version) = component.version {
+                binding.insert("componentVersion".to_string(), json!(version));
+            }
+        }
+        if let Some(response) = &route.binding.response {
+            binding.insert("response".to_string(), json!(response));
+        }
+
+        let operation = json!({
+            "summary": match (&binding_type, component) {
+                (Some(binding_type), Some(component)) => format!("{} binding to {}", binding_type, component.name),
+                (Some(binding_type), None) => format!("{binding_type} binding"),
+                (None, Some(component)) => format!("Binding to {}", component.name),
+                (None, None) => "Binding".to_string(),
+            },
+            "parameters": parameters,
+            "security": route
+                .security
+                .as_ref()
+                .map(|security| vec![Map::from_iter([(security.clone(), json!([]))])])
+                .unwrap_or_default(),
+            "responses": {
+                "default": { "description": "Response of the binding" }
+            },
+            "x-golem-binding": binding,
+        });
+
+        let path_item = paths
+            .entry(path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(path_item) = path_item {
+            path_item.insert(route.method.to_string().to_lowercase(), operation);
+        }
+    }
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": api_definition.id,
+            "version": api_definition.version,
+        },
+        "paths": paths,
+    })
+}
+
+/// Swagger UI page, loading the UI from a CDN, and the documents from the given URLs
+pub fn swagger_ui_html(spec_urls: &[(String, String)]) -> String {
+    let urls = spec_urls
+        .iter()
+        .map(|(name, url)| json!({ "name": name, "url": url }))
+        .collect::<Vec<_>>();
+
+    format!(
+        r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8" />
+  <title>Golem API definition preview</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5/swagger-ui.css" />
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-bundle.js" crossorigin></script>
+  <script src="https://unpkg.com/swagger-ui-dist@5/swagger-ui-standalone-preset.js" crossorigin></script>
+  <script>
+    window.onload = () => {{
+      window.ui = SwaggerUIBundle({{
+        urls: {},
+        dom_id: "#swagger-ui",
+        presets: [SwaggerUIBundle.presets.apis, SwaggerUIStandalonePreset],
+        layout: "StandaloneLayout",
+      }});
+    }};
+  </script>
+</body>
+</html>
+"##,
+        Value::Array(urls)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::model::openapi::to_openapi_spec;
+    use assert2::assert;
+    use golem_client::model::{
+        GatewayBindingComponent, GatewayBindingData, GatewayBindingType, HttpApiDefinitionRequest,
+        MethodPattern, RouteRequestData,
+    };
+
+    #[test]
+    fn openapi_spec_with_path_and_query_params() {
+        let api_definition = HttpApiDefinitionRequest {
+            id: "shopping-cart".to_string(),
+            version: "0.1.0".to_string(),
+            security: None,
+            routes: vec![RouteRequestData {
+                method: MethodPattern::Get,
+                path: "/carts/{cart-id}/items/{+rest}?{limit}".to_string(),
+                binding: GatewayBindingData {
+                    binding_type: Some(GatewayBindingType::Default),
+                    component: Some(GatewayBindingComponent {
+                        name: "cart".to_string(),
+                        version: Some(2),
+                    }),
+                    worker_name: None,
+                    idempotency_key: None,
+                    response: Some("let x = 1; x".to_string()),
+                    invocation_context: None,
+                },
+                security: None,
+            }],
+            draft: true,
+        };
+
+        let spec = to_openapi_spec(&api_definition);
+
+        let operation = &spec["paths"]["/carts/{cart-id}/items/{rest}"]["get"];
+        assert!(spec["info"]["title"] == "shopping-cart");
+        assert!(operation["parameters"].as_array().map(|p| p.len()) == Some(3));
+        assert!(operation["parameters"][2]["in"] == "query");
+        assert!(operation["x-golem-binding"]["componentName"] == "cart");
+        assert!(operation["x-golem-binding"]["componentVersion"] == 2);
+    }
+}
@@ -14,8 +14,11 @@
 
 use crate::model::ProjectName;
 use crate::model::{AccountId, ProjectId};
+use clap::ValueEnum;
 use golem_client::model::{Project, ProjectType};
+use golem_common::model::auth::ProjectPermission;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -40,3 +43,62 @@ impl From<Project> for ProjectView {
         }
     }
 }
+
+/// Predefined sets of project actions for sharing projects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProjectRole {
+    /// Read-only access to components, workers, API definitions, deployments and grants
+    Viewer,
+    /// Viewer access, plus managing components, workers, plugin installations and APIs
+    Editor,
+}
+
+impl ProjectRole {
+    pub fn actions(&self) -> Vec<ProjectPermission> {
+        let viewer_actions = vec![
+            ProjectPermission::ViewComponent,
+            ProjectPermission::ViewWorker,
+            ProjectPermission::ViewProjectGrants,
+            ProjectPermission::ViewApiDefinition,
+            ProjectPermission::ViewApiDeployment,
+            ProjectPermission::ViewApiDomain,
+            ProjectPermission::ViewPluginInstallations,
+        ];
+
+        match self {
+            ProjectRole::Viewer => viewer_actions,
+            ProjectRole::Editor => {
+                let mut actions = viewer_actions;
+                actions.extend([
+                    ProjectPermission::CreateComponent,
+                    ProjectPermission::UpdateComponent,
+                    ProjectPermission::DeleteComponent,
+                    ProjectPermission::CreateWorker,
+                    ProjectPermission::UpdateWorker,
+                    ProjectPermission::DeleteWorker,
+                    ProjectPermission::CreateApiDefinition,
+                    ProjectPermission::UpdateApiDefinition,
+                    ProjectPermission::DeleteApiDefinition,
+                    ProjectPermission::UpsertApiDeployment,
+                    ProjectPermission::DeleteApiDeployment,
+                    ProjectPermission::UpsertApiDomain,
+                    ProjectPermission::DeleteApiDomain,
+                    ProjectPermission::CreatePluginInstallation,
+                    ProjectPermission::UpdatePluginInstallation,
+                    ProjectPermission::DeletePluginInstallation,
+                ]);
+                actions
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMemberView {
+    pub grant_id: Uuid,
+    pub account_id: AccountId,
+    pub account_email: Option<String>,
+    pub policy_id: Uuid,
+    pub actions: Vec<String>,
+}
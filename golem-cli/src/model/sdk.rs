@@ -0,0 +1,703 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::api::route_pattern_var;
+use clap::ValueEnum;
+use golem_wasm_ast::analysis::{
+    AnalysedExport, AnalysedFunction, AnalysedType, NameOptionTypePair, NameTypePair, TypeEnum,
+    TypeRecord, TypeVariant,
+};
+use heck::{ToKebabCase, ToLowerCamelCase, ToSnakeCase, ToUpperCamelCase};
+use itertools::Itertools;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SdkLanguage {
+    Rust,
+    Ts,
+}
+
+/// Component name and optional version of the bindings in an OpenAPI export
+pub type SdkComponentRef = (String, Option<u64>);
+
+/// Exports of the components used by the bindings, for resolving the response body types
+pub type SdkComponentExports = BTreeMap<SdkComponentRef, Vec<AnalysedExport>>;
+
+/// Template variables of the generated client library
+#[derive(Debug, Clone, Serialize)]
+pub struct SdkConfig {
+    pub package_name: String,
+    pub api_id: String,
+    pub api_version: String,
+    pub cli_version: String,
+    pub operations: Vec<SdkOperation>,
+    pub type_definitions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SdkOperation {
+    pub name: String,
+    pub method: String,
+    pub path: String,
+    pub summary: String,
+    pub path_segments: Vec<SdkPathSegment>,
+    pub path_params: Vec<SdkParam>,
+    pub query_params: Vec<SdkParam>,
+    pub has_body: bool,
+    pub response_type: String,
+    pub typed_response: bool,
+}
+
+/// Either a literal path segment or the identifier of a path parameter
+#[derive(Debug, Clone, Serialize)]
+pub struct SdkPathSegment {
+    pub literal: Option<String>,
+    pub param: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SdkParam {
+    pub name: String,
+    pub ident: String,
+}
+
+/// Collects the components referenced by the bindings of an OpenAPI export
+pub fn sdk_component_refs(spec: &Value) -> BTreeSet<SdkComponentRef> {
+    spec_operations(spec)
+        .filter_map(|(_, _, operation)| binding_component(operation))
+        .collect()
+}
+
+impl SdkConfig {
+    /// Builds the template variables from an OpenAPI export created by to_openapi_spec. The
+    /// export only describes the routes, so the response types are resolved from the WIT
+    /// types of the functions called in the response mappings, when possible.
+    pub fn new(
+        language: SdkLanguage,
+        spec: &Value,
+        component_exports: &SdkComponentExports,
+        cli_version: &str,
+    ) -> Self {
+        let api_id = spec["info"]["title"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let api_version = spec["info"]["version"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let mut types = SdkTypeMapper::new(language);
+        let mut operation_names = BTreeSet::<String>::new();
+        let mut operations = Vec::<SdkOperation>::new();
+
+        for (path, method, operation) in spec_operations(spec) {
+            let path_segments = path
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(|segment| match route_pattern_var(segment) {
+                    Some(var) => SdkPathSegment {
+                        literal: None,
+                        param: Some(param_ident(language, var)),
+                    },
+                    None => SdkPathSegment {
+                        literal: Some(segment.to_string()),
+                        param: None,
+                    },
+                })
+                .collect::<Vec<_>>();
+
+            let params = |location: &str| -> Vec<SdkParam> {
+                operation["parameters"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|param| param["in"] == location)
+                    .filter_map(|param| param["name"].as_str())
+                    .map(|name| SdkParam {
+                        name: name.to_string(),
+                        ident: param_ident(language, name),
+                    })
+                    .collect()
+            };
+
+            let name = {
+                let words =
+                    std::iter::once(method.to_string())
+                        .chain(path.split('/').filter(|segment| !segment.is_empty()).map(
+                            |segment| match route_pattern_var(segment) {
+                                Some(var) => format!("by-{var}"),
+                                None => segment.to_string(),
+                            },
+                        ))
+                        .join("-");
+                let base_name = match language {
+                    SdkLanguage::Rust => words.to_snake_case(),
+                    SdkLanguage::Ts => words.to_lower_camel_case(),
+                };
+                let mut name = base_name.clone();
+                let mut index = 2;
+                while operation_names.contains(&name) {
+                    name = format!("{base_name}{index}");
+                    index += 1;
+                }
+                operation_names.insert(name.clone());
+                name
+            };
+
+            let response_type = binding_component(operation)
+                .and_then(|component| component_exports.get(&component))
+                .zip(operation["x-golem-binding"]["response"].as_str())
+                .and_then(|(exports, response)| {
+                    let function = response_body_function(response)?;
+                    exported_function_result_type(exports, &function)
+                })
+                .map(|typ| types.type_ref(typ, &format!("{name}-response")));
+
+            operations.push(SdkOperation {
+                path_params: params("path"),
+                query_params: params("query"),
+                name: match language {
+                    SdkLanguage::Rust => rust_ident(&name),
+                    SdkLanguage::Ts => name,
+                },
+                method: method.to_uppercase(),
+                path: path.to_string(),
+                summary: operation["summary"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                path_segments,
+                has_body: matches!(method, "post" | "put" | "patch"),
+                typed_response: response_type.is_some(),
+                response_type: response_type.unwrap_or_else(|| types.untyped()),
+            });
+        }
+
+        Self {
+            package_name: format!("{api_id}-client").to_kebab_case(),
+            api_id,
+            api_version,
+            cli_version: cli_version.to_string(),
+            operations,
+            type_definitions: types.definitions.into_values().collect(),
+        }
+    }
+}
+
+fn spec_operations(spec: &Value) -> impl Iterator<Item = (&str, &str, &Value)> {
+    spec["paths"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .flat_map(|(path, path_item)| {
+            path_item
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(move |(method, operation)| (path.as_str(), method.as_str(), operation))
+        })
+}
+
+fn binding_component(operation: &Value) -> Option<SdkComponentRef> {
+    let binding = &operation["x-golem-binding"];
+    Some((
+        binding["componentName"].as_str()?.to_string(),
+        binding["componentVersion"].as_u64(),
+    ))
+}
+
+/// Detects the function whose result is returned as the response body in response mappings
+/// like `let result = worker.get-item(id); {status: 200, body: result}`, or when the last
+/// expression of the mapping is the function call itself
+pub fn response_body_function(response: &str) -> Option<String> {
+    let body_field =
+        Regex::new(r"(^|[^A-Za-z0-9_-])body\s*:").expect("Failed to compile body field pattern");
+    let body_expr = match body_field.find(response) {
+        Some(body_match) => {
+            let rest = &response[body_match.end()..];
+            let mut depth = 0;
+            let end = rest
+                .char_indices()
+                .find(|(_, char)| match char {
+                    '(' | '{' | '[' => {
+                        depth += 1;
+                        false
+                    }
+                    ')' | '}' | ']' if depth > 0 => {
+                        depth -= 1;
+                        false
+                    }
+                    ',' | '}' | ';' => depth == 0,
+                    _ => false,
+                })
+                .map(|(index, _)| index)
+                .unwrap_or(rest.len());
+            rest[..end].trim()
+        }
+        None => response
+            .trim()
+            .trim_end_matches(';')
+            .rsplit(';')
+            .next()
+            .unwrap_or_default()
+            .trim(),
+    };
+
+    match last_method_call(body_expr) {
+        Some(function) => Some(function),
+        None if is_rib_identifier(body_expr) => {
+            let binding = response
+                .split(';')
+                .filter_map(|statement| {
+                    let (name, expr) = statement.trim().strip_prefix("let ")?.split_once('=')?;
+                    let name = name.split(':').next().unwrap_or_default().trim();
+                    (name == body_expr).then_some(expr)
+                })
+                .last()?;
+            last_method_call(binding)
+        }
+        None => None,
+    }
+}
+
+fn is_rib_identifier(expr: &str) -> bool {
+    !expr.is_empty()
+        && expr
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+}
+
+/// Name of the last function called as a method of a worker, e.g. get-item for
+/// worker.get-item(id), so calls like instance("name") are skipped
+fn last_method_call(expr: &str) -> Option<String> {
+    expr.match_indices('(')
+        .filter_map(|(index, _)| {
+            let prefix = &expr[..index];
+            let name_start = prefix
+                .rfind(|char: char| !(char.is_ascii_alphanumeric() || char == '-' || char == '_'))
+                .map(|index| index + 1)
+                .unwrap_or(0);
+            let name = &prefix[name_start..];
+            (!name.is_empty() && prefix[..name_start].ends_with('.')).then(|| name.to_string())
+        })
+        .last()
+}
+
+/// Result type of the exported function with the given name, if the name is unambiguous
+/// and the function has a single result
+fn exported_function_result_type<'a>(
+    exports: &'a [AnalysedExport],
+    function_name: &str,
+) -> Option<&'a AnalysedType> {
+    let functions = exports
+        .iter()
+        .flat_map(|export| match export {
+            AnalysedExport::Instance(instance) => instance.functions.iter().collect::<Vec<_>>(),
+            AnalysedExport::Function(function) => vec![function],
+        })
+        .filter(|function: &&AnalysedFunction| function.name == function_name)
+        .collect::<Vec<_>>();
+
+    match functions.as_slice() {
+        [function] => {
+            let mut results = function.result.iter();
+            match (results.next(), results.next()) {
+                (Some(result), None) => Some(&result.typ),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn param_ident(language: SdkLanguage, name: &str) -> String {
+    match language {
+        SdkLanguage::Rust => rust_ident(&name.to_snake_case()),
+        SdkLanguage::Ts => {
+            let ident = name.to_lower_camel_case();
+            if TS_RESERVED_WORDS.contains(&ident.as_str()) {
+                format!("{ident}_")
+            } else {
+                ident
+            }
+        }
+    }
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+const TS_RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+];
+
+fn rust_ident(ident: &str) -> String {
+    if RUST_KEYWORDS.contains(&ident) {
+        format!("r#{ident}")
+    } else {
+        ident.to_string()
+    }
+}
+
+/// Maps WIT types to the types of the target language, following the JSON representation of
+/// the values in the response bodies. Named types are collected as type definitions.
+struct SdkTypeMapper {
+    language: SdkLanguage,
+    definitions: BTreeMap<String, String>,
+}
+
+impl SdkTypeMapper {
+    fn new(language: SdkLanguage) -> Self {
+        Self {
+            language,
+            definitions: BTreeMap::new(),
+        }
+    }
+
+    fn untyped(&self) -> String {
+        match self.language {
+            SdkLanguage::Rust => "serde_json::Value".to_string(),
+            SdkLanguage::Ts => "unknown".to_string(),
+        }
+    }
+
+    fn type_ref(&mut self, typ: &AnalysedType, context: &str) -> String {
+        let rust = self.language == SdkLanguage::Rust;
+        match typ {
+            AnalysedType::Record(record) => self.named_type(typ, context, |mapper, name| {
+                mapper.record_definition(name, record)
+            }),
+            AnalysedType::Variant(variant) => self.named_type(typ, context, |mapper, name| {
+                mapper.variant_definition(name, variant)
+            }),
+            AnalysedType::Enum(TypeEnum { cases, .. }) => {
+                self.named_type(typ, context, |mapper, name| {
+                    mapper.enum_definition(name, cases)
+                })
+            }
+            AnalysedType::Flags { .. } => {
+                if rust {
+                    "Vec<String>".to_string()
+                } else {
+                    "string[]".to_string()
+                }
+            }
+            AnalysedType::Option(option) => {
+                let inner = self.type_ref(&option.inner, context);
+                if rust {
+                    format!("Option<{inner}>")
+                } else {
+                    format!("{inner} | null")
+                }
+            }
+            AnalysedType::Result(result) => {
+                let ok = self.opt_type_ref(result.ok.as_deref(), &format!("{context}-ok"));
+                let err = self.opt_type_ref(result.err.as_deref(), &format!("{context}-err"));
+                if rust {
+                    format!("WitResult<{ok}, {err}>")
+                } else {
+                    format!("{{ ok: {ok} }} | {{ err: {err} }}")
+                }
+            }
+            AnalysedType::Tuple(tuple) => {
+                let items = tuple
+                    .items
+                    .iter()
+                    .enumerate()
+                    .map(|(index, item)| self.type_ref(item, &format!("{context}-{index}")))
+                    .collect::<Vec<_>>();
+                if rust {
+                    format!("({},)", items.join(", "))
+                } else {
+                    format!("[{}]", items.join(", "))
+                }
+            }
+            AnalysedType::List(list) => {
+                let inner = self.type_ref(&list.inner, &format!("{context}-item"));
+                if rust {
+                    format!("Vec<{inner}>")
+                } else if inner.contains(' ') {
+                    format!("({inner})[]")
+                } else {
+                    format!("{inner}[]")
+                }
+            }
+            AnalysedType::Str { .. } | AnalysedType::Handle { .. } => {
+                if rust {
+                    "String".to_string()
+                } else {
+                    "string".to_string()
+                }
+            }
+            AnalysedType::Chr { .. } => {
+                if rust {
+                    "char".to_string()
+                } else {
+                    "string".to_string()
+                }
+            }
+            AnalysedType::Bool { .. } => {
+                if rust {
+                    "bool".to_string()
+                } else {
+                    "boolean".to_string()
+                }
+            }
+            AnalysedType::F64 { .. } => self.number("f64"),
+            AnalysedType::F32 { .. } => self.number("f32"),
+            AnalysedType::U64 { .. } => self.number("u64"),
+            AnalysedType::S64 { .. } => self.number("i64"),
+            AnalysedType::U32 { .. } => self.number("u32"),
+            AnalysedType::S32 { .. } => self.number("i32"),
+            AnalysedType::U16 { .. } => self.number("u16"),
+            AnalysedType::S16 { .. } => self.number("i16"),
+            AnalysedType::U8 { .. } => self.number("u8"),
+            AnalysedType::S8 { .. } => self.number("i8"),
+        }
+    }
+
+    fn opt_type_ref(&mut self, typ: Option<&AnalysedType>, context: &str) -> String {
+        match typ {
+            Some(typ) => self.type_ref(typ, context),
+            None => match self.language {
+                SdkLanguage::Rust => "()".to_string(),
+                SdkLanguage::Ts => "null".to_string(),
+            },
+        }
+    }
+
+    fn number(&self, rust_type: &str) -> String {
+        match self.language {
+            SdkLanguage::Rust => rust_type.to_string(),
+            SdkLanguage::Ts => "number".to_string(),
+        }
+    }
+
+    /// Types with the same name are only defined once, as in WIT names are unique per package
+    fn named_type(
+        &mut self,
+        typ: &AnalysedType,
+        context: &str,
+        definition: impl FnOnce(&mut Self, &str) -> String,
+    ) -> String {
+        let name = typ.name().unwrap_or(context).to_upper_camel_case();
+        if !self.definitions.contains_key(&name) {
+            self.definitions.insert(name.clone(), String::new());
+            let definition = definition(self, &name);
+            self.definitions.insert(name.clone(), definition);
+        }
+        name
+    }
+
+    fn record_definition(&mut self, name: &str, record: &TypeRecord) -> String {
+        let fields = record
+            .fields
+            .iter()
+            .map(|NameTypePair { name: field, typ }| {
+                (field, self.type_ref(typ, &format!("{name}-{field}")))
+            })
+            .collect::<Vec<_>>();
+
+        match self.language {
+            SdkLanguage::Rust => format!(
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct {name} {{\n{}}}",
+                fields
+                    .iter()
+                    .map(|(field, typ)| format!(
+                        "    #[serde(rename = \"{field}\")]\n    pub {}: {typ},\n",
+                        rust_ident(&field.to_snake_case())
+                    ))
+                    .join("")
+            ),
+            SdkLanguage::Ts => format!(
+                "export interface {name} {{\n{}}}",
+                fields
+                    .iter()
+                    .map(|(field, typ)| format!("  \"{field}\": {typ};\n"))
+                    .join("")
+            ),
+        }
+    }
+
+    fn variant_definition(&mut self, name: &str, variant: &TypeVariant) -> String {
+        let cases = variant
+            .cases
+            .iter()
+            .map(|NameOptionTypePair { name: case, typ }| {
+                (
+                    case,
+                    typ.as_ref()
+                        .map(|typ| self.type_ref(typ, &format!("{name}-{case}"))),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        match self.language {
+            SdkLanguage::Rust => format!(
+                "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub enum {name} {{\n{}}}",
+                cases
+                    .iter()
+                    .map(|(case, typ)| format!(
+                        "    #[serde(rename = \"{case}\")]\n    {}{},\n",
+                        case.to_upper_camel_case(),
+                        typ.as_ref().map(|typ| format!("({typ})")).unwrap_or_default()
+                    ))
+                    .join("")
+            ),
+            SdkLanguage::Ts => format!(
+                "export type {name} =\n{};",
+                cases
+                    .iter()
+                    .map(|(case, typ)| format!(
+                        "  | {{ \"{case}\": {} }}",
+                        typ.as_deref().unwrap_or("null")
+                    ))
+                    .join("\n")
+            ),
+        }
+    }
+
+    fn enum_definition(&mut self, name: &str, cases: &[String]) -> String {
+        match self.language {
+            SdkLanguage::Rust => format!(
+                "#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]\npub enum {name} {{\n{}}}",
+                cases
+                    .iter()
+                    .map(|case| format!(
+                        "    #[serde(rename = \"{case}\")]\n    {},\n",
+                        case.to_upper_camel_case()
+                    ))
+                    .join("")
+            ),
+            SdkLanguage::Ts => format!(
+                "export type {name} = {};",
+                cases.iter().map(|case| format!("\"{case}\"")).join(" | ")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::sdk::{response_body_function, SdkConfig, SdkLanguage};
+    use assert2::assert;
+    use golem_wasm_ast::analysis::analysed_type::{field, list, record, str, u32};
+    use golem_wasm_ast::analysis::{
+        AnalysedExport, AnalysedFunction, AnalysedFunctionParameter, AnalysedFunctionResult,
+        AnalysedInstance,
+    };
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use test_r::test;
+
+    #[test]
+    fn typed_operations_from_openapi_spec() {
+        let response = r#"let worker = instance("shop"); let items = worker.get-items(category); {status: 200, body: items}"#;
+        assert!(response_body_function(response) == Some("get-items".to_string()));
+        assert!(
+            response_body_function("let worker = instance(); worker.ping()")
+                == Some("ping".to_string())
+        );
+
+        let spec = json!({
+            "openapi": "3.0.3",
+            "info": { "title": "shop", "version": "0.1.0" },
+            "paths": {
+                "/categories/{category}/items": {
+                    "get": {
+                        "summary": "worker binding to shop",
+                        "parameters": [
+                            { "name": "category", "in": "path" },
+                            { "name": "type", "in": "query" }
+                        ],
+                        "x-golem-binding": { "componentName": "shop", "response": response }
+                    }
+                }
+            }
+        });
+        let exports = vec![AnalysedExport::Instance(AnalysedInstance {
+            name: "shop:api/api".to_string(),
+            functions: vec![AnalysedFunction {
+                name: "get-items".to_string(),
+                parameters: vec![AnalysedFunctionParameter {
+                    name: "category".to_string(),
+                    typ: str(),
+                }],
+                result: Some(AnalysedFunctionResult {
+                    typ: list(
+                        record(vec![field("item-name", str()), field("count", u32())])
+                            .named("item"),
+                    ),
+                }),
+            }],
+        })];
+        let component_exports = BTreeMap::from([(("shop".to_string(), None), exports)]);
+
+        let config = SdkConfig::new(SdkLanguage::Rust, &spec, &component_exports, "1.0.0");
+        assert!(config.package_name == "shop-client");
+        let operation = &config.operations[0];
+        assert!(operation.name == "get_categories_by_category_items");
+        assert!(operation.response_type == "Vec<Item>");
+        assert!(operation.query_params[0].ident == "r#type");
+        assert!(config.type_definitions[0].contains("pub item_name: String,"));
+
+        let config = SdkConfig::new(SdkLanguage::Ts, &spec, &component_exports, "1.0.0");
+        let operation = &config.operations[0];
+        assert!(operation.name == "getCategoriesByCategoryItems");
+        assert!(operation.response_type == "Item[]");
+        assert!(config.type_definitions[0].contains("\"item-name\": string;"));
+    }
+}
@@ -0,0 +1,42 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedTypeOwner {
+    pub component_name: String,
+    pub interface_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedType {
+    pub name: String,
+    pub kind: String,
+    pub owners: Vec<SharedTypeOwner>,
+}
+
+/// Type definitions which are identical in multiple components, and are moved (or in dry run mode
+/// would be moved) into a common shared types package.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedTypesReport {
+    pub package_name: String,
+    pub dry_run: bool,
+    pub types: Vec<SharedType>,
+    /// Type names which are shared by multiple components with different definitions
+    pub conflicting_type_names: Vec<String>,
+}
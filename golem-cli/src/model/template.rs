@@ -1,8 +1,45 @@
 use crate::model::app_raw;
+use heck::{
+    ToKebabCase, ToLowerCamelCase, ToPascalCase, ToShoutyKebabCase, ToShoutySnakeCase, ToSnakeCase,
+    ToTitleCase, ToTrainCase, ToUpperCamelCase,
+};
 use minijinja::{Environment, Error};
 use serde::Serialize;
 use std::collections::HashMap;
 
+pub fn template_env<'a>() -> Environment<'a> {
+    let mut env = Environment::new();
+
+    env.add_filter("to_snake_case", |str: &str| str.to_snake_case());
+
+    env.add_filter("to_kebab_case", |str: &str| str.to_kebab_case());
+    env.add_filter("to_lower_camel_case", |str: &str| str.to_lower_camel_case());
+    env.add_filter("to_pascal_case", |str: &str| str.to_pascal_case());
+    env.add_filter("to_shouty_kebab_case", |str: &str| {
+        str.to_shouty_kebab_case()
+    });
+    env.add_filter("to_shouty_snake_case", |str: &str| {
+        str.to_shouty_snake_case()
+    });
+    env.add_filter("to_snake_case", |str: &str| str.to_snake_case());
+    env.add_filter("to_title_case", |str: &str| str.to_title_case());
+    env.add_filter("to_train_case", |str: &str| str.to_train_case());
+    env.add_filter("to_upper_camel_case", |str: &str| str.to_upper_camel_case());
+
+    env
+}
+
+/// Variables available for all component templates
+pub const COMPONENT_TEMPLATE_VARIABLES: &[&str] = &["componentName", "component_name"];
+
+/// Variables of external commands which are only resolved when executing the command
+pub const EXTERNAL_COMMAND_VARIABLES: &[&str] = &["target_wasm", "profile", "os", "arch"];
+
+/// Returns true if the value contains minijinja expressions or statements
+pub fn contains_template(value: &str) -> bool {
+    value.contains("{{") || value.contains("{%")
+}
+
 pub trait Template<C: Serialize> {
     type Rendered;
 
@@ -101,6 +138,10 @@ impl<C: Serialize> Template<C> for app_raw::ExternalCommand {
             mkdirs: self.mkdirs.render(env, ctx)?,
             sources: self.sources.render(env, ctx)?,
             targets: self.targets.render(env, ctx)?,
+            depends_on: self.depends_on.render(env, ctx)?,
+            env: self.env.render(env, ctx)?,
+            // Conditions are expressions, they are only evaluated when executing the command
+            when: self.when.clone(),
         })
     }
 }
@@ -192,6 +233,8 @@ impl<C: Serialize> Template<C> for app_raw::ComponentProperties {
             generated_wit: self.generated_wit.render(env, ctx)?,
             component_wasm: self.component_wasm.render(env, ctx)?,
             linked_wasm: self.linked_wasm.render(env, ctx)?,
+            plugs: self.plugs.render(env, ctx)?,
+            wasi_adapter: self.wasi_adapter.render(env, ctx)?,
             build: self.build.render(env, ctx)?,
             custom_commands: self.custom_commands.render(env, ctx)?,
             clean: self.clean.render(env, ctx)?,
@@ -199,6 +242,60 @@ impl<C: Serialize> Template<C> for app_raw::ComponentProperties {
             files: self.files.clone(),
             plugins: self.plugins.clone(),
             env: self.env.render(env, ctx)?,
+            golem_rust_override: self.golem_rust_override.render(env, ctx)?,
+            optimize: self.optimize.clone(),
+            test_for: self.test_for.render(env, ctx)?,
+        })
+    }
+}
+
+impl<C: Serialize> Template<C> for app_raw::GolemRustOverride {
+    type Rendered = app_raw::GolemRustOverride;
+
+    fn render(
+        &self,
+        env: &minijinja::Environment,
+        ctx: &C,
+    ) -> Result<Self::Rendered, minijinja::Error> {
+        Ok(app_raw::GolemRustOverride {
+            path: self.path.render(env, ctx)?,
+            version: self.version.render(env, ctx)?,
+        })
+    }
+}
+
+impl<C: Serialize> Template<C> for app_raw::HttpApiDefinitionRoute {
+    type Rendered = app_raw::HttpApiDefinitionRoute;
+
+    fn render(
+        &self,
+        env: &minijinja::Environment,
+        ctx: &C,
+    ) -> Result<Self::Rendered, minijinja::Error> {
+        Ok(app_raw::HttpApiDefinitionRoute {
+            method: self.method.clone(),
+            path: self.path.render(env, ctx)?,
+            security: self.security.render(env, ctx)?,
+            binding: self.binding.render(env, ctx)?,
+        })
+    }
+}
+
+impl<C: Serialize> Template<C> for app_raw::HttpApiDefinitionBinding {
+    type Rendered = app_raw::HttpApiDefinitionBinding;
+
+    fn render(
+        &self,
+        env: &minijinja::Environment,
+        ctx: &C,
+    ) -> Result<Self::Rendered, minijinja::Error> {
+        Ok(app_raw::HttpApiDefinitionBinding {
+            type_: self.type_,
+            component_name: self.component_name.clone(),
+            component_version: self.component_version,
+            idempotency_key: self.idempotency_key.render(env, ctx)?,
+            invocation_context: self.invocation_context.render(env, ctx)?,
+            response: self.response.render(env, ctx)?,
         })
     }
 }
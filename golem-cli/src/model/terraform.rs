@@ -0,0 +1,443 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use heck::ToSnakeCase;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use uuid::Uuid;
+
+pub const DEFAULT_TERRAFORM_PROVIDER_SOURCE: &str = "golemcloud/golem";
+
+const COMPONENT_RESOURCE: &str = "golem_component";
+const API_DEFINITION_RESOURCE: &str = "golem_api_definition";
+const API_DEPLOYMENT_RESOURCE: &str = "golem_api_deployment";
+
+/// Server side state of a project, as exported by `export terraform`
+#[derive(Debug, Clone, Default)]
+pub struct TerraformExport {
+    pub provider_source: String,
+    pub project: Option<String>,
+    pub components: Vec<TerraformComponent>,
+    pub api_definitions: Vec<TerraformApiDefinition>,
+    pub api_deployments: Vec<TerraformApiDeployment>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TerraformComponent {
+    pub name: String,
+    pub component_id: Uuid,
+    pub version: u64,
+    pub component_type: String,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TerraformApiDefinition {
+    pub id: String,
+    pub version: String,
+    pub draft: bool,
+    pub routes: Vec<TerraformRoute>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TerraformRoute {
+    pub method: String,
+    pub path: String,
+    pub binding_type: Option<String>,
+    pub component_name: Option<String>,
+    pub component_version: Option<u64>,
+    pub worker_name: Option<String>,
+    pub idempotency_key: Option<String>,
+    pub response: Option<String>,
+    pub security: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TerraformApiDeployment {
+    pub host: String,
+    pub subdomain: Option<String>,
+    /// API definition ids and versions
+    pub api_definitions: Vec<(String, String)>,
+}
+
+impl TerraformApiDeployment {
+    pub fn site(&self) -> String {
+        match &self.subdomain {
+            Some(subdomain) => format!("{}.{}", subdomain, self.host),
+            None => self.host.clone(),
+        }
+    }
+}
+
+impl TerraformExport {
+    /// Renders resource definitions, with references between the exported resources, so
+    /// the dependencies are visible to Terraform
+    pub fn render_resources(&self) -> String {
+        let names = TerraformResourceNames::new(self);
+        let mut out = self.render_header();
+
+        for component in &self.components {
+            let _ = writeln!(
+                out,
+                "\nresource \"{COMPONENT_RESOURCE}\" \"{}\" {{",
+                names.component(&component.name)
+            );
+            let _ = writeln!(out, "  name           = {}", hcl_string(&component.name));
+            let _ = writeln!(
+                out,
+                "  component_type = {}",
+                hcl_string(&component.component_type)
+            );
+            let _ = writeln!(
+                out,
+                "  # The WASM of the exported version ({}) is not part of the export, set its path before applying",
+                component.version
+            );
+            let _ = writeln!(out, "  # source       = \"{}.wasm\"", component.name);
+            if !component.env.is_empty() {
+                let _ = writeln!(out, "  env = {{");
+                for (key, value) in &component.env {
+                    let _ = writeln!(out, "    {} = {}", hcl_string(key), hcl_string(value));
+                }
+                let _ = writeln!(out, "  }}");
+            }
+            let _ = writeln!(out, "}}");
+        }
+
+        for api_definition in &self.api_definitions {
+            let _ = writeln!(
+                out,
+                "\nresource \"{API_DEFINITION_RESOURCE}\" \"{}\" {{",
+                names.api_definition(&api_definition.id, &api_definition.version)
+            );
+            let _ = writeln!(out, "  id      = {}", hcl_string(&api_definition.id));
+            let _ = writeln!(out, "  version = {}", hcl_string(&api_definition.version));
+            let _ = writeln!(out, "  draft   = {}", api_definition.draft);
+            for route in &api_definition.routes {
+                let _ = writeln!(out, "\n  route {{");
+                let _ = writeln!(out, "    method = {}", hcl_string(&route.method));
+                let _ = writeln!(out, "    path   = {}", hcl_string(&route.path));
+                if let Some(security) = &route.security {
+                    let _ = writeln!(out, "    security = {}", hcl_string(security));
+                }
+                let _ = writeln!(out, "    binding {{");
+                if let Some(binding_type) = &route.binding_type {
+                    let _ = writeln!(
+                        out,
+                        "      type              = {}",
+                        hcl_string(binding_type)
+                    );
+                }
+                if let Some(component_name) = &route.component_name {
+                    let _ = writeln!(
+                        out,
+                        "      component_name    = {}",
+                        match names.components.get(component_name) {
+                            Some(name) => format!("{COMPONENT_RESOURCE}.{name}.name"),
+                            None => hcl_string(component_name),
+                        }
+                    );
+                }
+                if let Some(component_version) = route.component_version {
+                    let _ = writeln!(out, "      component_version = {component_version}");
+                }
+                if let Some(worker_name) = &route.worker_name {
+                    let _ = writeln!(out, "      worker_name       = {}", hcl_string(worker_name));
+                }
+                if let Some(idempotency_key) = &route.idempotency_key {
+                    let _ = writeln!(
+                        out,
+                        "      idempotency_key   = {}",
+                        hcl_string(idempotency_key)
+                    );
+                }
+                if let Some(response) = &route.response {
+                    let _ = writeln!(out, "      response          = {}", hcl_string(response));
+                }
+                let _ = writeln!(out, "    }}");
+                let _ = writeln!(out, "  }}");
+            }
+            let _ = writeln!(out, "}}");
+        }
+
+        for api_deployment in &self.api_deployments {
+            let _ = writeln!(
+                out,
+                "\nresource \"{API_DEPLOYMENT_RESOURCE}\" \"{}\" {{",
+                names.api_deployment(&api_deployment.site())
+            );
+            let _ = writeln!(out, "  host      = {}", hcl_string(&api_deployment.host));
+            if let Some(subdomain) = &api_deployment.subdomain {
+                let _ = writeln!(out, "  subdomain = {}", hcl_string(subdomain));
+            }
+            for (id, version) in &api_deployment.api_definitions {
+                let _ = writeln!(out, "\n  api_definition {{");
+                match names.api_definitions.get(&(id.clone(), version.clone())) {
+                    Some(name) => {
+                        let _ = writeln!(out, "    id      = {API_DEFINITION_RESOURCE}.{name}.id");
+                        let _ = writeln!(
+                            out,
+                            "    version = {API_DEFINITION_RESOURCE}.{name}.version"
+                        );
+                    }
+                    None => {
+                        let _ = writeln!(out, "    id      = {}", hcl_string(id));
+                        let _ = writeln!(out, "    version = {}", hcl_string(version));
+                    }
+                }
+                let _ = writeln!(out, "  }}");
+            }
+            let _ = writeln!(out, "}}");
+        }
+
+        out
+    }
+
+    /// Renders import blocks for adopting the existing resources, the matching resource
+    /// definitions can be generated with `terraform plan -generate-config-out=<FILE>`
+    pub fn render_import_blocks(&self) -> String {
+        let names = TerraformResourceNames::new(self);
+        let mut out = self.render_header();
+
+        let mut import_block = |resource: &str, name: &str, id: &str| {
+            let _ = writeln!(out, "\nimport {{");
+            let _ = writeln!(out, "  to = {resource}.{name}");
+            let _ = writeln!(out, "  id = {}", hcl_string(id));
+            let _ = writeln!(out, "}}");
+        };
+
+        for component in &self.components {
+            import_block(
+                COMPONENT_RESOURCE,
+                names.component(&component.name),
+                &component.component_id.to_string(),
+            );
+        }
+        for api_definition in &self.api_definitions {
+            import_block(
+                API_DEFINITION_RESOURCE,
+                names.api_definition(&api_definition.id, &api_definition.version),
+                &format!("{}/{}", api_definition.id, api_definition.version),
+            );
+        }
+        for api_deployment in &self.api_deployments {
+            let site = api_deployment.site();
+            import_block(API_DEPLOYMENT_RESOURCE, names.api_deployment(&site), &site);
+        }
+
+        out
+    }
+
+    fn render_header(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "# Generated by \"golem export terraform\"");
+        let _ = writeln!(out, "terraform {{");
+        let _ = writeln!(out, "  required_providers {{");
+        let _ = writeln!(out, "    golem = {{");
+        let _ = writeln!(out, "      source = {}", hcl_string(&self.provider_source));
+        let _ = writeln!(out, "    }}");
+        let _ = writeln!(out, "  }}");
+        let _ = writeln!(out, "}}");
+        let _ = writeln!(out, "\nprovider \"golem\" {{");
+        if let Some(project) = &self.project {
+            let _ = writeln!(out, "  project = {}", hcl_string(project));
+        }
+        let _ = writeln!(out, "}}");
+        out
+    }
+}
+
+/// Unique Terraform resource names for the exported resources, per resource type
+struct TerraformResourceNames {
+    components: BTreeMap<String, String>,
+    api_definitions: BTreeMap<(String, String), String>,
+    api_deployments: BTreeMap<String, String>,
+}
+
+impl TerraformResourceNames {
+    fn new(export: &TerraformExport) -> Self {
+        let mut used = BTreeSet::new();
+        let components = export
+            .components
+            .iter()
+            .map(|component| {
+                (
+                    component.name.clone(),
+                    unique_resource_name(&mut used, &component.name),
+                )
+            })
+            .collect();
+
+        let mut used = BTreeSet::new();
+        let api_definitions = export
+            .api_definitions
+            .iter()
+            .map(|api_definition| {
+                (
+                    (api_definition.id.clone(), api_definition.version.clone()),
+                    unique_resource_name(
+                        &mut used,
+                        &format!("{}_{}", api_definition.id, api_definition.version),
+                    ),
+                )
+            })
+            .collect();
+
+        let mut used = BTreeSet::new();
+        let api_deployments = export
+            .api_deployments
+            .iter()
+            .map(|api_deployment| {
+                let site = api_deployment.site();
+                let name = unique_resource_name(&mut used, &site);
+                (site, name)
+            })
+            .collect();
+
+        Self {
+            components,
+            api_definitions,
+            api_deployments,
+        }
+    }
+
+    fn component(&self, name: &str) -> &str {
+        &self.components[name]
+    }
+
+    fn api_definition(&self, id: &str, version: &str) -> &str {
+        &self.api_definitions[&(id.to_string(), version.to_string())]
+    }
+
+    fn api_deployment(&self, site: &str) -> &str {
+        &self.api_deployments[site]
+    }
+}
+
+/// Resource names can only contain letters, digits, underscores and dashes, and must not
+/// start with a digit
+fn unique_resource_name(used: &mut BTreeSet<String>, name: &str) -> String {
+    let mut base_name = name
+        .to_snake_case()
+        .chars()
+        .map(|char| {
+            if char.is_ascii_alphanumeric() || char == '_' {
+                char
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if !base_name.starts_with(|char: char| char.is_ascii_alphabetic() || char == '_') {
+        base_name = format!("_{base_name}");
+    }
+
+    let mut name = base_name.clone();
+    let mut index = 2;
+    while used.contains(&name) {
+        name = format!("{base_name}_{index}");
+        index += 1;
+    }
+    used.insert(name.clone());
+    name
+}
+
+/// Quoted HCL string, with template sequences escaped, so values like Rib expressions
+/// are kept as is
+fn hcl_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    let mut chars = value.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                result.push(char);
+                result.push(char);
+            }
+            char => result.push(char),
+        }
+    }
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::terraform::{
+        TerraformApiDefinition, TerraformApiDeployment, TerraformComponent, TerraformExport,
+        TerraformRoute, DEFAULT_TERRAFORM_PROVIDER_SOURCE,
+    };
+    use assert2::assert;
+    use std::collections::BTreeMap;
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn resources_and_import_blocks() {
+        let component_id = Uuid::new_v4();
+        let export = TerraformExport {
+            provider_source: DEFAULT_TERRAFORM_PROVIDER_SOURCE.to_string(),
+            project: None,
+            components: vec![TerraformComponent {
+                name: "shop:cart".to_string(),
+                component_id,
+                version: 3,
+                component_type: "Durable".to_string(),
+                env: BTreeMap::new(),
+            }],
+            api_definitions: vec![TerraformApiDefinition {
+                id: "shop-api".to_string(),
+                version: "0.1.0".to_string(),
+                draft: false,
+                routes: vec![TerraformRoute {
+                    method: "Get".to_string(),
+                    path: "/carts/{user}".to_string(),
+                    binding_type: Some("default".to_string()),
+                    component_name: Some("shop:cart".to_string()),
+                    component_version: Some(3),
+                    worker_name: None,
+                    idempotency_key: None,
+                    response: Some(
+                        r#"let w = instance("${request.path.user}"); w.get()"#.to_string(),
+                    ),
+                    security: None,
+                }],
+            }],
+            api_deployments: vec![TerraformApiDeployment {
+                host: "example.com".to_string(),
+                subdomain: Some("api".to_string()),
+                api_definitions: vec![("shop-api".to_string(), "0.1.0".to_string())],
+            }],
+        };
+
+        let resources = export.render_resources();
+        assert!(resources.contains("resource \"golem_component\" \"shop_cart\" {"));
+        assert!(resources.contains("component_name    = golem_component.shop_cart.name"));
+        assert!(resources.contains(r#"instance(\"$${request.path.user}\")"#));
+        assert!(resources.contains("resource \"golem_api_deployment\" \"api_example_com\" {"));
+        assert!(resources.contains("id      = golem_api_definition.shop_api_0_1_0.id"));
+
+        let import_blocks = export.render_import_blocks();
+        assert!(import_blocks.contains(&format!(
+            "  to = golem_component.shop_cart\n  id = \"{component_id}\"\n"
+        )));
+        assert!(import_blocks.contains("  id = \"shop-api/0.1.0\"\n"));
+        assert!(import_blocks.contains("  id = \"api.example.com\"\n"));
+    }
+}
@@ -15,7 +15,8 @@
 use crate::model::text::fmt::*;
 use crate::model::ComponentName;
 use cli_table::{format::Justify, Table};
-use golem_client::model::{HttpApiDefinitionResponseData, RouteResponseData};
+use golem_client::model::{HttpApiDefinitionResponseData, HttpCors, RouteResponseData};
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 #[derive(Table)]
@@ -54,9 +55,44 @@ impl From<&RouteResponseData> for RouteTableView {
     }
 }
 
+fn format_cors_policies(policies: &[(&str, &HttpCors)]) -> String {
+    policies
+        .iter()
+        .map(|(path, cors)| {
+            let mut properties = vec![
+                format!("origin: {}", cors.allow_origin),
+                format!("methods: {}", cors.allow_methods),
+                format!("headers: {}", cors.allow_headers),
+            ];
+            if let Some(expose_headers) = &cors.expose_headers {
+                properties.push(format!("expose headers: {expose_headers}"));
+            }
+            if let Some(max_age) = cors.max_age {
+                properties.push(format!("max age: {max_age}"));
+            }
+            if let Some(allow_credentials) = cors.allow_credentials {
+                properties.push(format!("credentials: {allow_credentials}"));
+            }
+            format!("{}\n  {}", format_id(path), properties.join("\n  "))
+        })
+        .join("\n")
+}
+
 fn api_definition_fields(def: &HttpApiDefinitionResponseData) -> Vec<(String, String)> {
     let mut fields = FieldsBuilder::new();
 
+    let cors_policies = def
+        .routes
+        .iter()
+        .filter_map(|route| {
+            route
+                .binding
+                .cors_preflight
+                .as_ref()
+                .map(|cors| (route.path.as_str(), cors))
+        })
+        .collect::<Vec<_>>();
+
     fields
         .fmt_field("ID", &def.id, format_main_id)
         .fmt_field("Version", &def.version, format_main_id)
@@ -67,6 +103,12 @@ fn api_definition_fields(def: &HttpApiDefinitionResponseData) -> Vec<(String, St
             def.routes.as_slice(),
             !def.routes.is_empty(),
             format_table::<_, RouteTableView>,
+        )
+        .fmt_field_optional(
+            "CORS",
+            cors_policies.as_slice(),
+            !cors_policies.is_empty(),
+            format_cors_policies,
         );
 
     fields.build()
@@ -17,6 +17,9 @@ use crate::model::api::ApiDeployment;
 use crate::model::text::fmt::*;
 use cli_table::Table;
 use golem_client::model::ApiDefinitionInfo;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub fn format_site(api_deployment: &ApiDeployment) -> String {
     match &api_deployment.site.subdomain {
@@ -74,3 +77,92 @@ impl TextView for Vec<ApiDeployment> {
         );
     }
 }
+
+/// Test request and the response as returned by the API gateway. The gateway does not expose
+/// the result of the response mapping Rib separately, so only the mapping itself is shown.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDeploymentTestView {
+    pub api_definition_id: String,
+    pub api_definition_version: String,
+    pub route: String,
+    pub binding_type: Option<String>,
+    pub component_name: Option<String>,
+    pub request_method: String,
+    pub request_url: String,
+    pub request_headers: BTreeMap<String, String>,
+    pub request_body: Option<String>,
+    pub response_status: u16,
+    pub response_headers: BTreeMap<String, String>,
+    pub response_body: String,
+    pub response_mapping: Option<String>,
+}
+
+fn format_headers(headers: &BTreeMap<String, String>) -> String {
+    headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .join("\n")
+}
+
+fn format_body(body: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(json) => serde_json::to_string_pretty(&json).unwrap_or_else(|_| body.to_string()),
+        Err(_) => body.to_string(),
+    }
+}
+
+impl MessageWithFields for ApiDeploymentTestView {
+    fn message(&self) -> String {
+        format!(
+            "Tested route {} of API {}/{}",
+            format_message_highlight(&self.route),
+            format_message_highlight(&self.api_definition_id),
+            format_message_highlight(&self.api_definition_version),
+        )
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = FieldsBuilder::new();
+
+        fields
+            .fmt_field_option("Binding type", &self.binding_type, format_id)
+            .fmt_field_option("Component name", &self.component_name, format_id)
+            .fmt_field(
+                "Request",
+                &(&self.request_method, &self.request_url),
+                |(method, url)| format!("{} {}", method, url),
+            )
+            .fmt_field_optional(
+                "Request headers",
+                &self.request_headers,
+                !self.request_headers.is_empty(),
+                format_headers,
+            )
+            .fmt_field_option("Request body", &self.request_body, |body| format_body(body))
+            .fmt_field("Response status", &self.response_status, |status| {
+                if (200..300).contains(status) {
+                    format_id(status)
+                } else {
+                    format_warn(status)
+                }
+            })
+            .fmt_field_optional(
+                "Response headers",
+                &self.response_headers,
+                !self.response_headers.is_empty(),
+                format_headers,
+            )
+            .fmt_field_optional(
+                "Response body",
+                self.response_body.as_str(),
+                !self.response_body.is_empty(),
+                format_body,
+            )
+            .fmt_field_option("Response mapping", &self.response_mapping, |rib| {
+                rib.trim().to_string()
+            });
+
+        fields.build()
+    }
+}
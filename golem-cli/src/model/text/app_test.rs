@@ -0,0 +1,80 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize, LogIndent};
+use crate::model::app_test::{AppTestReport, TestStepOutcome};
+use crate::model::text::fmt::*;
+use colored::Colorize;
+
+impl TextView for AppTestReport {
+    fn log(&self) {
+        if self.cases.is_empty() {
+            logln("No test cases found");
+            return;
+        }
+
+        for case in &self.cases {
+            let status = if case.passed() {
+                "passed".log_color_ok_highlight().to_string()
+            } else {
+                "failed".log_color_error().to_string()
+            };
+            logln(format!(
+                "{} ({}/{}): {}",
+                case.name.log_color_highlight(),
+                case.component_name,
+                case.worker_name,
+                status
+            ));
+
+            let _indent = LogIndent::new();
+            for step in &case.steps {
+                let outcome = match step.outcome {
+                    TestStepOutcome::Passed => "ok".log_color_ok_highlight().to_string(),
+                    TestStepOutcome::Mismatch => "mismatch".log_color_error().to_string(),
+                    TestStepOutcome::Failed => "failed".log_color_error().to_string(),
+                    TestStepOutcome::Skipped => "skipped".log_color_warn().to_string(),
+                };
+                logln(format!(
+                    "{}: {}",
+                    format_export(&step.function_name),
+                    outcome
+                ));
+
+                if step.outcome == TestStepOutcome::Passed
+                    || step.outcome == TestStepOutcome::Skipped
+                {
+                    continue;
+                }
+                let _indent = LogIndent::new();
+                if let Some(expected) = &step.expected {
+                    logln(format!("- {expected}").red().to_string());
+                }
+                if let Some(actual) = &step.actual {
+                    logln(format!("+ {actual}").green().to_string());
+                }
+                if let Some(error) = &step.error {
+                    logln(format!("error: {error}").log_color_error().to_string());
+                }
+            }
+        }
+
+        logln("");
+        logln(format!(
+            "{} passed, {} failed",
+            self.passed_count().to_string().log_color_ok_highlight(),
+            self.failed_count().to_string().log_color_highlight(),
+        ));
+    }
+}
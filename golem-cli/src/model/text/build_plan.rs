@@ -0,0 +1,67 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize, LogIndent};
+use crate::model::build_plan::{BuildPlan, BuildPlanFileAction};
+use crate::model::text::fmt::*;
+use itertools::Itertools;
+
+impl TextView for BuildPlan {
+    fn log(&self) {
+        if self.tasks.is_empty() {
+            logln("No build tasks are planned");
+            return;
+        }
+
+        for (step, tasks) in &self.tasks.iter().chunk_by(|task| task.step.clone()) {
+            logln(format!("Step {}:", step.log_color_highlight()));
+            let _indent = LogIndent::new();
+
+            for task in tasks {
+                let status = if task.up_to_date {
+                    "[SKIP]".log_color_help_group()
+                } else {
+                    "[ RUN]".log_color_ok_highlight()
+                };
+                logln(format!(
+                    "{} {}{}",
+                    status,
+                    task.component_name
+                        .as_ref()
+                        .map(|component_name| format!("{}: ", component_name.log_color_highlight()))
+                        .unwrap_or_default(),
+                    task.description
+                ));
+
+                let _indent = LogIndent::new();
+                if let Some(command) = &task.command {
+                    logln(format!(
+                        "{} {} in {}",
+                        "command:".log_color_help_group(),
+                        command.command.log_color_highlight(),
+                        command.dir.log_color_highlight()
+                    ));
+                }
+                for file in &task.files {
+                    let action = match file.action {
+                        BuildPlanFileAction::Create => "create   ".log_color_ok_highlight(),
+                        BuildPlanFileAction::Overwrite => "overwrite".log_color_warn(),
+                        BuildPlanFileAction::Skip => "skip     ".log_color_help_group(),
+                    };
+                    logln(format!("{} {}", action, file.path.log_color_highlight()));
+                }
+            }
+        }
+    }
+}
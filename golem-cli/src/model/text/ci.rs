@@ -0,0 +1,73 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize};
+use crate::model::ci::{CiChange, CiStageStatus, CiSummary};
+use crate::model::text::fmt::*;
+
+fn format_change(change: CiChange, dry_run: bool) -> String {
+    match (change, dry_run) {
+        (CiChange::New, false) => "created".log_color_ok_highlight().to_string(),
+        (CiChange::New, true) => "to be created".log_color_warn().to_string(),
+        (CiChange::Changed, false) => "updated".log_color_ok_highlight().to_string(),
+        (CiChange::Changed, true) => "to be updated".log_color_warn().to_string(),
+        (CiChange::Unchanged, _) => "up-to-date".to_string(),
+    }
+}
+
+impl TextView for CiSummary {
+    fn log(&self) {
+        logln("");
+        logln(
+            if self.dry_run {
+                "CI summary (dry run):"
+            } else {
+                "CI summary:"
+            }
+            .log_color_help_group()
+            .to_string(),
+        );
+        for result in &self.stages {
+            let status = match result.status {
+                CiStageStatus::Succeeded => "succeeded".log_color_ok_highlight().to_string(),
+                CiStageStatus::Failed => "failed".log_color_error_highlight().to_string(),
+                CiStageStatus::Skipped => "skipped".to_string(),
+            };
+            logln(format!("  {:<20} {}", result.stage.to_string(), status));
+        }
+
+        if !self.components.is_empty() {
+            logln("Components:".log_color_help_group().to_string());
+            for component in &self.components {
+                logln(format!(
+                    "  {} {}",
+                    format_id(&component.component_name),
+                    format_change(component.change, self.dry_run)
+                ));
+            }
+        }
+
+        if !self.http_api_definitions.is_empty() {
+            logln("HTTP API definitions:".log_color_help_group().to_string());
+            for api_definition in &self.http_api_definitions {
+                logln(format!(
+                    "  {}@{} {}",
+                    format_id(&api_definition.name),
+                    api_definition.version,
+                    format_change(api_definition.change, self.dry_run)
+                ));
+            }
+        }
+    }
+}
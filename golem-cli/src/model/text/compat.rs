@@ -0,0 +1,56 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize, LogIndent};
+use crate::model::compat::CompatReport;
+use crate::model::text::fmt::*;
+
+impl TextView for CompatReport {
+    fn log(&self) {
+        if self.dependencies.is_empty() {
+            logln("No WASM RPC dependencies found");
+            return;
+        }
+
+        for dep in &self.dependencies {
+            let status = if !dep.vendored {
+                "not generated yet".log_color_warn().to_string()
+            } else if dep.is_compatible() {
+                "compatible".log_color_ok_highlight().to_string()
+            } else {
+                "incompatible".log_color_error().to_string()
+            };
+            logln(format!(
+                "{} -> {} ({}): {}",
+                dep.component_name.log_color_highlight(),
+                dep.dependency_name.log_color_highlight(),
+                dep.package_name,
+                status
+            ));
+
+            let _indent = LogIndent::new();
+            for difference in &dep.differences {
+                let line = format!(
+                    "{}: {}: {}",
+                    difference.interface_name, difference.item, difference.change
+                );
+                if difference.breaking {
+                    logln(line.log_color_error().to_string());
+                } else {
+                    logln(line);
+                }
+            }
+        }
+    }
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::model::component::ComponentView;
+use crate::model::component::{ComponentView, ProfileComponentView};
 use crate::model::text::fmt::*;
 use crate::model::ComponentName;
 use cli_table::{format::Justify, Table};
@@ -48,6 +48,38 @@ impl TextView for Vec<ComponentView> {
     }
 }
 
+#[derive(Table)]
+struct ProfileComponentTableView {
+    #[table(title = "Profile")]
+    pub profile: String,
+    #[table(title = "Name")]
+    pub component_name: ComponentName,
+    #[table(title = "Version", justify = "Justify::Right")]
+    pub component_version: u64,
+    #[table(title = "Size", justify = "Justify::Right")]
+    pub component_size: u64,
+    #[table(title = "Exports count", justify = "Justify::Right")]
+    pub n_exports: usize,
+}
+
+impl From<&ProfileComponentView> for ProfileComponentTableView {
+    fn from(value: &ProfileComponentView) -> Self {
+        Self {
+            profile: value.profile.0.clone(),
+            component_name: value.component.component_name.clone(),
+            component_version: value.component.component_version,
+            component_size: value.component.component_size,
+            n_exports: value.component.exports.len(),
+        }
+    }
+}
+
+impl TextView for Vec<ProfileComponentView> {
+    fn log(&self) {
+        log_table::<_, ProfileComponentTableView>(self.as_slice())
+    }
+}
+
 fn component_view_fields(view: &ComponentView) -> Vec<(String, String)> {
     let mut fields = FieldsBuilder::new();
 
@@ -62,6 +94,12 @@ fn component_view_fields(view: &ComponentView) -> Vec<(String, String)> {
         .fmt_field_optional("Environment", &view.env, !&view.env.is_empty(), |env| {
             format_env(view.show_sensitive, env)
         })
+        .fmt_field_optional(
+            "Build metadata",
+            &view.build_metadata,
+            !view.build_metadata.is_empty(),
+            format_build_metadata,
+        )
         .fmt_field("Exports", &view.exports, |e| format_exports(e.as_slice()))
         .fmt_field_optional(
             "Dynamic WASM RPC links",
@@ -0,0 +1,91 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::logln;
+use crate::model::dependency_graph::{DependencyGraph, DependencyGraphNodeKind, GraphFormat};
+use crate::model::text::fmt::*;
+use std::collections::BTreeMap;
+
+impl TextView for DependencyGraph {
+    fn log(&self) {
+        match self.format {
+            GraphFormat::Dot => log_dot(self),
+            GraphFormat::Mermaid => log_mermaid(self),
+        }
+    }
+}
+
+fn quoted(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn log_dot(graph: &DependencyGraph) {
+    logln("digraph app {");
+    for node in &graph.nodes {
+        let shape = match node.kind {
+            DependencyGraphNodeKind::AppComponent => "box",
+            DependencyGraphNodeKind::LocalFile | DependencyGraphNodeKind::Url => "note",
+            DependencyGraphNodeKind::Deployed | DependencyGraphNodeKind::Registry => "box3d",
+        };
+        logln(format!("  {} [shape={shape}];", quoted(&node.name)));
+    }
+    for edge in &graph.edges {
+        logln(format!(
+            "  {} -> {} [label={}{}];",
+            quoted(&edge.from),
+            quoted(&edge.to),
+            quoted(&edge.dependency_type),
+            if edge.in_cycle { ", color=red" } else { "" }
+        ));
+    }
+    logln("}");
+}
+
+fn log_mermaid(graph: &DependencyGraph) {
+    let ids = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(idx, node)| (node.name.as_str(), format!("n{idx}")))
+        .collect::<BTreeMap<_, _>>();
+
+    logln("graph LR");
+    for node in &graph.nodes {
+        let name = node.name.replace('"', "#quot;");
+        match node.kind {
+            DependencyGraphNodeKind::AppComponent => {
+                logln(format!("  {}[\"{}\"]", ids[node.name.as_str()], name))
+            }
+            DependencyGraphNodeKind::LocalFile | DependencyGraphNodeKind::Url => {
+                logln(format!("  {}[/\"{}\"/]", ids[node.name.as_str()], name))
+            }
+            DependencyGraphNodeKind::Deployed | DependencyGraphNodeKind::Registry => {
+                logln(format!("  {}([\"{}\"])", ids[node.name.as_str()], name))
+            }
+        }
+    }
+    for edge in &graph.edges {
+        logln(format!(
+            "  {} -->|{}| {}",
+            ids[edge.from.as_str()],
+            edge.dependency_type,
+            ids[edge.to.as_str()]
+        ));
+    }
+    for (idx, edge) in graph.edges.iter().enumerate() {
+        if edge.in_cycle {
+            logln(format!("  linkStyle {idx} stroke:red"));
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize, LogIndent};
+use crate::model::doctor::{DoctorCheckStatus, DoctorReport};
+use crate::model::text::fmt::*;
+
+impl TextView for DoctorReport {
+    fn log(&self) {
+        logln(format!(
+            "Diagnostics for profile {}, CLI version {}",
+            format_message_highlight(&self.profile),
+            format_message_highlight(&self.cli_version),
+        ));
+        logln("");
+
+        for check in &self.checks {
+            let status = match check.status {
+                DoctorCheckStatus::Ok => "[ OK ]".log_color_ok_highlight(),
+                DoctorCheckStatus::Warning => "[WARN]".log_color_warn(),
+                DoctorCheckStatus::Failed => "[FAIL]".log_color_error_highlight(),
+                DoctorCheckStatus::Skipped => "[SKIP]".log_color_help_group(),
+            };
+            logln(format!(
+                "{} {}: {}{}",
+                status,
+                check.name.log_color_highlight(),
+                check.details,
+                check
+                    .latency_ms
+                    .map(|latency| format!(" ({latency} ms)"))
+                    .unwrap_or_default()
+            ));
+            if let Some(fix) = &check.fix {
+                let _indent = LogIndent::new();
+                logln(format!("{} {}", "fix:".log_color_help_group(), fix));
+            }
+        }
+
+        logln("");
+        if self.has_failures() {
+            logln(
+                "Some checks failed, see the suggested fixes above"
+                    .log_color_error()
+                    .to_string(),
+            );
+        } else {
+            logln("All checks passed".log_color_ok_highlight().to_string());
+        }
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize};
+use crate::model::event::{WorkerEventType, WorkerEventView};
+use crate::model::text::fmt::*;
+
+impl TextView for WorkerEventView {
+    fn log(&self) {
+        let event_type = self.event_type.to_string();
+        let event_type = match self.event_type {
+            WorkerEventType::WorkerFailed
+            | WorkerEventType::InvocationFailed
+            | WorkerEventType::UpdateFailed => event_type.log_color_error_highlight(),
+            _ => event_type.log_color_highlight(),
+        };
+
+        let mut details = Vec::new();
+        if let Some(status) = &self.status {
+            details.push(format!("status: {}", format_status(status)));
+        }
+        if let Some(component_version) = self.component_version {
+            details.push(format!("version: {component_version}"));
+        }
+
+        logln(format!(
+            "{} {} {}/{}{}{}",
+            self.timestamp.format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+            event_type,
+            self.component_name.0.log_color_highlight(),
+            self.worker_name.log_color_highlight(),
+            if details.is_empty() {
+                "".to_string()
+            } else {
+                format!(" ({})", details.join(", "))
+            },
+            self.error
+                .as_ref()
+                .map(|error| format!(": {}", format_error(error)))
+                .unwrap_or_default()
+        ));
+    }
+}
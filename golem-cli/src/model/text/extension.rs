@@ -0,0 +1,39 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize};
+use crate::model::extension::{ExtensionSource, ExtensionView};
+use crate::model::text::fmt::*;
+
+impl TextView for Vec<ExtensionView> {
+    fn log(&self) {
+        if self.is_empty() {
+            logln("No extensions found.");
+            return;
+        }
+
+        logln("Available extensions:".log_color_help_group().to_string());
+        for extension in self {
+            logln(format!(
+                "  {} ({}, {})",
+                format_id(&extension.name),
+                match extension.source {
+                    ExtensionSource::Installed => "installed",
+                    ExtensionSource::Path => "PATH",
+                },
+                extension.path.display()
+            ));
+        }
+    }
+}
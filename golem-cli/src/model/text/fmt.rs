@@ -13,13 +13,12 @@
 // limitations under the License.
 
 use crate::fuzzy::Match;
-use crate::log::{log_warn_action, logln, LogColorize, LogIndent};
+use crate::log::{log_warn_action, logln, should_colorize, LogColorize, LogIndent};
 use crate::model::deploy_diff::DiffSerialize;
 use crate::model::text::component::is_sensitive_env_var_name;
 use crate::model::{Format, WorkerNameMatch};
 use anyhow::Context;
 use cli_table::{Row, Title, WithTitle};
-use colored::control::SHOULD_COLORIZE;
 use colored::Colorize;
 use golem_client::model::{InitialComponentFile, WorkerStatus};
 use itertools::Itertools;
@@ -37,6 +36,8 @@ pub enum MessageWithFieldsIndentMode {
     NestedIdentAll,
 }
 
+/// Field names are part of the text output contract, the stable ones are listed and checked
+/// in the text view snapshot tests.
 pub trait MessageWithFields {
     fn message(&self) -> String;
     fn fields(&self) -> Vec<(String, String)>;
@@ -222,7 +223,7 @@ static BUILTIN_TYPES: phf::Set<&'static str> = phf::phf_set! {
 
 // A very naive highlighter for basic coloring of builtin types and user defined names
 pub fn format_export(export: &str) -> String {
-    if !SHOULD_COLORIZE.should_colorize() {
+    if !should_colorize() {
         return export.to_string();
     }
 
@@ -315,6 +316,13 @@ pub fn format_env(show_sensitive: bool, env: &BTreeMap<String, String>) -> Strin
         .join("\n")
 }
 
+pub fn format_build_metadata(build_metadata: &BTreeMap<String, String>) -> String {
+    build_metadata
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value.log_color_highlight()))
+        .join("\n")
+}
+
 pub fn format_table<E, R>(table: &[E]) -> String
 where
     R: Title + 'static + for<'b> From<&'b E>,
@@ -479,7 +487,7 @@ pub struct NestedTextViewIndent {
 impl NestedTextViewIndent {
     pub fn new(format: Format) -> Self {
         match format {
-            Format::Text if SHOULD_COLORIZE.should_colorize() => {
+            Format::Text if should_colorize() => {
                 logln("╔═");
                 Self {
                     decorated: true,
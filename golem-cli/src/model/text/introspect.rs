@@ -0,0 +1,69 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize};
+use crate::model::introspect::{ArgValueType, ArgView, CommandCatalogView};
+use crate::model::text::fmt::*;
+
+impl TextView for CommandCatalogView {
+    fn log(&self) {
+        for command in self
+            .command
+            .flatten()
+            .into_iter()
+            .filter(|command| !command.hidden)
+        {
+            logln(format!(
+                "{}{}",
+                format_id(&command.path.join(" ")),
+                command
+                    .about
+                    .as_ref()
+                    .map(|about| format!(" - {about}"))
+                    .unwrap_or_default()
+            ));
+            for arg in command.args.iter().filter(|arg| !arg.hidden) {
+                logln(format!(
+                    "  {}{}",
+                    format_arg(arg).log_color_highlight(),
+                    arg.help
+                        .as_ref()
+                        .map(|help| format!(" - {help}"))
+                        .unwrap_or_default()
+                ));
+            }
+        }
+    }
+}
+
+fn format_arg(arg: &ArgView) -> String {
+    let value_name = arg
+        .value_names
+        .first()
+        .cloned()
+        .unwrap_or_else(|| arg.id.to_uppercase());
+    if arg.positional {
+        format!("<{value_name}>")
+    } else {
+        let name = match (&arg.long, arg.short) {
+            (Some(long), _) => format!("--{long}"),
+            (None, Some(short)) => format!("-{short}"),
+            (None, None) => arg.id.clone(),
+        };
+        match arg.value_type {
+            ArgValueType::Boolean | ArgValueType::Count => name,
+            _ => format!("{name} <{value_name}>"),
+        }
+    }
+}
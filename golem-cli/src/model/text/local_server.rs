@@ -0,0 +1,61 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::LogColorize;
+use crate::model::local_server::LocalServerStatusView;
+use crate::model::text::fmt::*;
+
+impl MessageWithFields for LocalServerStatusView {
+    fn message(&self) -> String {
+        if self.healthy {
+            format!(
+                "Local server is {} at {}",
+                "running".log_color_ok_highlight(),
+                format_message_highlight(&self.router_url)
+            )
+        } else if self.services.is_empty() {
+            "Local server is not running".to_string()
+        } else {
+            format!(
+                "Local server at {} is {}",
+                format_message_highlight(&self.router_url),
+                "not healthy".log_color_error_highlight()
+            )
+        }
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = FieldsBuilder::new();
+
+        fields.fmt_field("Compose file", &self.compose_file, |path| {
+            path.display().to_string()
+        });
+        for service in &self.services {
+            fields.fmt_field(&service.name, service, |service| {
+                let state = if service.state == "running" {
+                    service.state.log_color_ok_highlight()
+                } else {
+                    service.state.log_color_error_highlight()
+                };
+                if service.status.is_empty() {
+                    state.to_string()
+                } else {
+                    format!("{} ({})", state, service.status)
+                }
+            });
+        }
+
+        fields.build()
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::logln;
+use crate::model::metrics::{MetricCategory, MetricView};
+use crate::model::text::fmt::*;
+use cli_table::Table;
+
+#[derive(Table)]
+struct MetricTableView {
+    #[table(title = "Category")]
+    pub category: String,
+    #[table(title = "Service")]
+    pub service: String,
+    #[table(title = "Metric")]
+    pub name: String,
+    #[table(title = "Labels")]
+    pub labels: String,
+    #[table(title = "Value")]
+    pub value: String,
+    #[table(title = "Rate")]
+    pub rate: String,
+}
+
+impl From<&MetricView> for MetricTableView {
+    fn from(value: &MetricView) -> Self {
+        MetricTableView {
+            category: match value.category {
+                MetricCategory::Invocations => "invocations",
+                MetricCategory::Errors => "errors",
+                MetricCategory::Memory => "memory",
+                MetricCategory::Other => "other",
+            }
+            .to_string(),
+            service: value.service.clone(),
+            name: value.name.clone(),
+            labels: value
+                .labels
+                .iter()
+                .map(|(name, value)| format!("{name}={value}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            value: format_metric_value(value.value),
+            rate: value
+                .rate
+                .map(|rate| format!("{rate:.2}/s"))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+fn format_metric_value(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.3}")
+    }
+}
+
+impl TextView for Vec<MetricView> {
+    fn log(&self) {
+        if self.is_empty() {
+            logln("No matching metrics found.");
+            return;
+        }
+
+        log_table::<_, MetricTableView>(self.as_slice());
+    }
+}
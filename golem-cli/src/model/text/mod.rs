@@ -17,13 +17,29 @@ pub mod api_definition;
 pub mod api_deployment;
 pub mod api_domain;
 pub mod api_security;
+pub mod app_test;
+pub mod build_plan;
 pub mod certificate;
+pub mod ci;
+pub mod compat;
 pub mod component;
+pub mod dependency_graph;
+pub mod doctor;
+pub mod event;
+pub mod extension;
 pub mod fmt;
 pub mod help;
+pub mod introspect;
+pub mod local_server;
+pub mod metrics;
 pub mod plugin;
 pub mod profile;
 pub mod project;
+pub mod rib;
+pub mod shared_types;
 pub mod template;
 pub mod token;
 pub mod worker;
+
+#[cfg(test)]
+mod test;
@@ -12,11 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::config::ProfileConfig;
+use crate::config::{
+    ProfileConfig, ProfileContext, PROFILE_KEY_ALLOW_INSECURE, PROFILE_KEY_COMMAND_DEFAULTS,
+    PROFILE_KEY_DEBUG_HTTP, PROFILE_KEY_DEFAULT_FORMAT, PROFILE_KEY_DEFAULT_PROJECT,
+    PROFILE_KEY_HTTP_CONNECT_TIMEOUT, PROFILE_KEY_HTTP_RETRIES, PROFILE_KEY_HTTP_RETRY_BACKOFF,
+    PROFILE_KEY_HTTP_TIMEOUT, PROFILE_KEY_TLS_CA_CERTIFICATE, PROFILE_KEY_TLS_CLIENT_CERTIFICATE,
+    PROFILE_KEY_TLS_CLIENT_KEY, PROFILE_KEY_URL, PROFILE_KEY_WORKER_URL,
+};
 use crate::log::{logln, LogColorize};
 use crate::model::text::fmt::*;
 use crate::model::ProfileView;
 use colored::Colorize;
+use itertools::Itertools;
 
 impl TextView for Vec<ProfileView> {
     fn log(&self) {
@@ -36,6 +43,19 @@ impl TextView for Vec<ProfileView> {
     }
 }
 
+impl ProfileView {
+    fn with_origin<T: ToString>(&self, key: &str, value: T) -> String {
+        match self.origins.get(key) {
+            Some(origin) => format!(
+                "{} (inherited from {})",
+                value.to_string(),
+                origin.0.log_color_highlight()
+            ),
+            None => value.to_string(),
+        }
+    }
+}
+
 impl MessageWithFields for ProfileView {
     fn message(&self) -> String {
         format!("Profile {}", format_message_highlight(&self.name))
@@ -48,21 +68,93 @@ impl MessageWithFields for ProfileView {
             .fmt_field_optional("Active", &self.is_active, self.is_active, |b| {
                 b.to_string().green().to_string()
             })
+            .fmt_field_option("Extends", &self.extends, format_id)
             .fmt_field_optional(
                 "Allow insecure",
                 &self.allow_insecure,
                 self.allow_insecure,
-                |b| b.to_string().red().to_string(),
+                |b| self.with_origin(PROFILE_KEY_ALLOW_INSECURE, b.to_string().red().to_string()),
+            )
+            .field(
+                "Default output format",
+                &self.with_origin(
+                    PROFILE_KEY_DEFAULT_FORMAT,
+                    self.config.default_format.unwrap_or_default(),
+                ),
+            )
+            .fmt_field_option("Default project", &self.config.default_project, |project| {
+                self.with_origin(PROFILE_KEY_DEFAULT_PROJECT, format_id(project))
+            })
+            .fmt_field_option(
+                "Context component",
+                &self.config.context.component,
+                format_id,
+            )
+            .fmt_field_option("Context worker", &self.config.context.worker, format_id)
+            .fmt_field_option("Debug HTTP", &self.config.debug_http, |debug_http| {
+                self.with_origin(PROFILE_KEY_DEBUG_HTTP, debug_http)
+            })
+            .fmt_field_option("HTTP timeout", &self.config.http.timeout_ms, |ms| {
+                self.with_origin(PROFILE_KEY_HTTP_TIMEOUT, format!("{ms} ms"))
+            })
+            .fmt_field_option(
+                "HTTP connect timeout",
+                &self.config.http.connect_timeout_ms,
+                |ms| self.with_origin(PROFILE_KEY_HTTP_CONNECT_TIMEOUT, format!("{ms} ms")),
+            )
+            .fmt_field_option("HTTP retries", &self.config.http.retries, |retries| {
+                self.with_origin(PROFILE_KEY_HTTP_RETRIES, retries)
+            })
+            .fmt_field_option(
+                "HTTP retry backoff",
+                &self.config.http.retry_backoff_ms,
+                |ms| self.with_origin(PROFILE_KEY_HTTP_RETRY_BACKOFF, format!("{ms} ms")),
+            )
+            .fmt_field_option(
+                "TLS CA certificate",
+                &self.config.tls.ca_certificate,
+                |path| self.with_origin(PROFILE_KEY_TLS_CA_CERTIFICATE, path.display()),
+            )
+            .fmt_field_option(
+                "TLS client certificate",
+                &self.config.tls.client_certificate,
+                |path| self.with_origin(PROFILE_KEY_TLS_CLIENT_CERTIFICATE, path.display()),
             )
-            .field("Default output format", &self.config.default_format);
+            .fmt_field_option("TLS client key", &self.config.tls.client_key, |path| {
+                self.with_origin(PROFILE_KEY_TLS_CLIENT_KEY, path.display())
+            })
+            .field("Secret storage", &self.config.secret_storage);
+
+        if !self.config.command_defaults.is_empty() {
+            fields.field(
+                "Command defaults",
+                &self
+                    .config
+                    .command_defaults
+                    .iter()
+                    .map(|(command, flags)| {
+                        self.with_origin(
+                            &format!("{PROFILE_KEY_COMMAND_DEFAULTS}.{command}"),
+                            format!("{}: {}", format_id(command), flags.join(" ")),
+                        )
+                    })
+                    .join("\n"),
+            );
+        }
 
         if let Some(url) = &self.url {
             if let Some(worker_url) = &self.worker_url {
                 fields
-                    .field("Component service URL", url)
-                    .field("Worker service URL", worker_url);
+                    .field(
+                        "Component service URL",
+                        &self.with_origin(PROFILE_KEY_URL, url),
+                    )
+                    .field(
+                        "Worker service URL",
+                        &self.with_origin(PROFILE_KEY_WORKER_URL, worker_url),
+                    );
             } else {
-                fields.field("Service URL", url);
+                fields.field("Service URL", &self.with_origin(PROFILE_KEY_URL, url));
             }
         } else {
             fields.field("Using default URLs", &true);
@@ -72,11 +164,33 @@ impl MessageWithFields for ProfileView {
     }
 }
 
+impl TextView for ProfileContext {
+    fn log(&self) {
+        if self.is_empty() {
+            logln("The context is empty.");
+            return;
+        }
+        if let Some(component) = &self.component {
+            logln(format!(
+                "Component: {}",
+                format_message_highlight(component)
+            ));
+        }
+        if let Some(worker) = &self.worker {
+            logln(format!("Worker: {}", format_message_highlight(worker)));
+        }
+    }
+}
+
 impl TextView for ProfileConfig {
     fn log(&self) {
         logln(format!(
             "Default output format: {}",
-            format_message_highlight(&self.default_format),
-        ))
+            format_message_highlight(&self.default_format.unwrap_or_default()),
+        ));
+        logln(format!(
+            "Secret storage: {}",
+            format_message_highlight(&self.secret_storage),
+        ));
     }
 }
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::model::project::ProjectView;
+use crate::log::logln;
+use crate::model::project::{ProjectMemberView, ProjectView};
 use crate::model::text::fmt::*;
 use cli_table::Table;
 use golem_client::model::{Project, ProjectGrant, ProjectPolicy, ProjectType};
@@ -185,3 +186,42 @@ impl MessageWithFields for ProjectPolicyGetView {
         project_policy_fields(&self.0)
     }
 }
+
+#[derive(Table)]
+struct ProjectMemberTableView {
+    #[table(title = "Grant ID")]
+    pub grant_id: Uuid,
+    #[table(title = "Account ID")]
+    pub account_id: String,
+    #[table(title = "Email")]
+    pub email: String,
+    #[table(title = "Actions")]
+    pub actions: String,
+}
+
+impl From<&ProjectMemberView> for ProjectMemberTableView {
+    fn from(value: &ProjectMemberView) -> Self {
+        Self {
+            grant_id: value.grant_id,
+            account_id: value.account_id.0.clone(),
+            email: value
+                .account_email
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+            actions: value.actions.join("\n"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProjectMemberListView(pub Vec<ProjectMemberView>);
+
+impl TextView for ProjectMemberListView {
+    fn log(&self) {
+        if self.0.is_empty() {
+            logln("The project is not shared with any account.");
+        } else {
+            log_table::<_, ProjectMemberTableView>(&self.0);
+        }
+    }
+}
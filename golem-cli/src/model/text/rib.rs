@@ -0,0 +1,59 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::model::text::fmt::*;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RibEvalView {
+    pub component_name: String,
+    pub component_version: u64,
+    pub inputs: BTreeMap<String, String>,
+    pub output_type: Option<String>,
+    pub evaluated: bool,
+    pub result: Option<String>,
+}
+
+impl MessageWithFields for RibEvalView {
+    fn message(&self) -> String {
+        format!(
+            "Rib expression is valid for component {}@{}",
+            format_message_highlight(&self.component_name),
+            format_message_highlight(&self.component_version),
+        )
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = FieldsBuilder::new();
+
+        fields
+            .fmt_field_optional("Inputs", &self.inputs, !self.inputs.is_empty(), |inputs| {
+                inputs
+                    .iter()
+                    .map(|(name, typ)| format!("{}: {}", format_id(name), typ))
+                    .join("\n")
+            })
+            .fmt_field("Output type", &self.output_type, |output_type| {
+                output_type.clone().unwrap_or_else(|| "-".to_string())
+            })
+            .fmt_field_optional("Result", &self.result, self.evaluated, |result| {
+                result.clone().unwrap_or_else(|| "-".to_string())
+            });
+
+        fields.build()
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::log::{logln, LogColorize, LogIndent};
+use crate::model::shared_types::SharedTypesReport;
+use crate::model::text::fmt::*;
+use itertools::Itertools;
+
+impl TextView for SharedTypesReport {
+    fn log(&self) {
+        if self.types.is_empty() {
+            logln("No identical type definitions found in multiple components");
+        } else {
+            logln(format!(
+                "{} {}:",
+                if self.dry_run {
+                    "Types to be moved to"
+                } else {
+                    "Moved types to"
+                },
+                self.package_name.log_color_highlight()
+            ));
+            let _indent = LogIndent::new();
+            for typ in &self.types {
+                logln(format!(
+                    "{} {} from {}",
+                    typ.kind,
+                    typ.name.log_color_highlight(),
+                    typ.owners
+                        .iter()
+                        .map(|owner| format!(
+                            "{} ({})",
+                            owner.component_name.log_color_highlight(),
+                            owner.interface_name
+                        ))
+                        .join(", ")
+                ));
+            }
+        }
+
+        if !self.conflicting_type_names.is_empty() {
+            logln("");
+            logln(format!(
+                "{} {}",
+                "Skipped types with different definitions in multiple components:".log_color_warn(),
+                self.conflicting_type_names
+                    .iter()
+                    .map(|name| name.log_color_highlight())
+                    .join(", ")
+            ));
+        }
+    }
+}
@@ -0,0 +1,470 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Golden-file snapshot tests of the text views.
+//!
+//! Every view is rendered for all [TERMINAL_WIDTHS] without colors, and compared to
+//! `test-data/text-views/<name>.txt`, then with colors, compared to
+//! `test-data/text-views/<name>.colored.txt`. To create or regenerate the golden files after an
+//! intended output change, run `cargo make bless-text-views` (or the tests with
+//! `GOLEM_CLI_BLESS=1`) and review the diff. Without blessing, a missing golden file fails the
+//! test, so the tests never write into the source tree on their own.
+//!
+//! `colored` can only be forced on process-wide, which would leak into other tests running in
+//! parallel, so the colored snapshots of a test are checked by rerunning the test alone in a
+//! child process, with colors forced on.
+//!
+//! The field names listed in [STABLE_FIELDS] are the documented contract of the text views:
+//! scripts parsing the text output can rely on them, so they must not be renamed or removed.
+
+use crate::config::{ProfileConfig, ProfileContext};
+use crate::log::LogCapture;
+use crate::model::api::ApiDeployment;
+use crate::model::app_test::AppTestReport;
+use crate::model::component::ComponentView;
+use crate::model::text::api_definition::{ApiDefinitionGetView, ApiDefinitionNewView};
+use crate::model::text::component::{ComponentCreateView, ComponentGetView, ComponentUpdateView};
+use crate::model::text::fmt::{MessageWithFields, TextView};
+use crate::model::text::worker::{WorkerCreateView, WorkerGetView};
+use crate::model::{ProfileView, WorkerMetadataView, WorkersMetadataResponseView};
+use camino::Utf8PathBuf;
+use golem_client::model::HttpApiDefinitionResponseData;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use similar::TextDiff;
+use std::fmt::Write;
+use std::process::Command;
+use test_r::test;
+
+/// Terminal widths used for rendering, None stands for output without wrapping (e.g. pipes)
+const TERMINAL_WIDTHS: &[Option<usize>] = &[None, Some(120), Some(80), Some(40)];
+
+/// Field names of the message-with-fields views which are considered stable
+const STABLE_FIELDS: &[(&str, &[&str])] = &[
+    (
+        "component",
+        &[
+            "Component name",
+            "Component ID",
+            "Component type",
+            "Component version",
+            "Component size",
+            "Exports",
+        ],
+    ),
+    (
+        "worker",
+        &[
+            "Component name",
+            "Component version",
+            "Worker name",
+            "Created at",
+            "Status",
+        ],
+    ),
+    ("api-definition", &["ID", "Version", "Routes"]),
+    ("profile", &["Default output format", "Secret storage"]),
+];
+
+/// Set for the child processes checking the colored snapshots
+const COLORED_SNAPSHOTS_ENV: &str = "GOLEM_CLI_TEXT_VIEW_COLORS";
+
+fn colored_snapshots() -> bool {
+    std::env::var(COLORED_SNAPSHOTS_ENV).is_ok_and(|value| value == "1")
+}
+
+fn bless() -> bool {
+    std::env::var("GOLEM_CLI_BLESS").is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+fn golden_file_path(name: &str) -> Utf8PathBuf {
+    Utf8PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("test-data")
+        .join("text-views")
+        .join(if colored_snapshots() {
+            format!("{name}.colored.txt")
+        } else {
+            format!("{name}.txt")
+        })
+}
+
+fn render<V: TextView>(view: &V, terminal_width: Option<usize>) -> String {
+    let colors = colored_snapshots();
+    if colors {
+        colored::control::set_override(true);
+    }
+    let capture = LogCapture::new(terminal_width, colors);
+    view.log();
+    capture.output()
+}
+
+/// Reruns the test in a child process with colors forced on, where [assert_snapshot] checks
+/// the colored snapshots
+fn assert_colored_snapshots(test_name: &str) {
+    if colored_snapshots() {
+        return;
+    }
+
+    let status = Command::new(std::env::current_exe().unwrap())
+        .args([
+            &format!(
+                "{}::{test_name}",
+                module_path!().trim_start_matches("golem_cli::")
+            ),
+            "--exact",
+            "--nocapture",
+        ])
+        .env(COLORED_SNAPSHOTS_ENV, "1")
+        .status()
+        .unwrap();
+    assert!(
+        status.success(),
+        "Colored text view snapshots of {test_name} do not match"
+    );
+}
+
+fn render_all<V: TextView>(view: &V) -> String {
+    let mut snapshot = String::new();
+    for terminal_width in TERMINAL_WIDTHS {
+        let _ = writeln!(
+            snapshot,
+            "=== width: {} ===",
+            terminal_width
+                .map(|width| width.to_string())
+                .unwrap_or_else(|| "unbounded".to_string()),
+        );
+        snapshot.push_str(&render(view, *terminal_width));
+    }
+    snapshot
+}
+
+fn assert_snapshot<V: TextView>(name: &str, view: &V) {
+    let actual = render_all(view);
+    let path = golden_file_path(name);
+
+    if bless() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let Ok(expected) = std::fs::read_to_string(&path) else {
+        panic!(
+            "Text view snapshot {path} is missing, run the tests with GOLEM_CLI_BLESS=1 to create it"
+        )
+    };
+    if expected != actual {
+        panic!(
+            "Text view snapshot {} does not match, rerun with GOLEM_CLI_BLESS=1 to update it if the change is intended:\n{}",
+            path,
+            TextDiff::from_lines(&expected, &actual)
+                .unified_diff()
+                .header("expected", "actual")
+        )
+    }
+}
+
+fn assert_stable_fields<V: MessageWithFields>(contract: &str, view: &V) {
+    let (_, stable_fields) = STABLE_FIELDS
+        .iter()
+        .find(|(name, _)| *name == contract)
+        .unwrap_or_else(|| panic!("Missing stable field contract: {contract}"));
+    let fields = view.fields();
+    for stable_field in *stable_fields {
+        assert!(
+            fields.iter().any(|(name, _)| name == stable_field),
+            "Stable field {stable_field} is missing from the {contract} view"
+        );
+    }
+}
+
+fn from_json<T: DeserializeOwned>(value: serde_json::Value) -> T {
+    serde_json::from_value(value).expect("Failed to deserialize text view fixture")
+}
+
+fn component() -> ComponentView {
+    from_json(json!({
+        "component_name": "shopping:cart",
+        "component_id": "7e3c4b1a-5f2d-4c8e-9a6b-1d2e3f4a5b6c",
+        "component_type": "Durable",
+        "component_version": 3,
+        "component_size": 1843200,
+        "created_at": "2025-03-04T10:20:30Z",
+        "exports": [
+            "shopping:cart-exports/api.{initialize-cart}(user-id: string)",
+            "shopping:cart-exports/api.{add-item}(item: record { product-id: string, name: string, price: f32, quantity: u32 })",
+            "shopping:cart-exports/api.{checkout}() -> variant { error(string), success(record { order-id: string }) }",
+        ],
+        "dynamic_linking": {},
+        "files": [],
+        "env": {
+            "API_TOKEN": "secret-value",
+            "LOG_LEVEL": "debug",
+        },
+    }))
+}
+
+fn worker_metadata(worker_name: &str, status: &str) -> WorkerMetadataView {
+    from_json(json!({
+        "componentName": "shopping:cart",
+        "workerName": worker_name,
+        "args": [],
+        "env": {
+            "REGION": "eu-central-1",
+        },
+        "status": status,
+        "componentVersion": 3,
+        "retryCount": 0,
+        "pendingInvocationCount": 0,
+        "updates": [],
+        "createdAt": "2025-03-04T10:21:00Z",
+        "lastError": null,
+        "componentSize": 1843200,
+        "totalLinearMemorySize": 1179648,
+        "ownedResources": {},
+    }))
+}
+
+fn api_definition(id: &str, draft: bool) -> HttpApiDefinitionResponseData {
+    from_json(json!({
+        "id": id,
+        "version": "0.1.0",
+        "draft": draft,
+        "createdAt": "2025-03-04T10:22:00Z",
+        "routes": [
+            {
+                "method": "Get",
+                "path": "/v1/cart/{user}",
+                "binding": {
+                    "component": {
+                        "name": "shopping:cart",
+                        "version": 3,
+                    },
+                    "workerName": "let user: string = request.path.user; \"cart-${user}\"",
+                    "response": "let result = get-cart-contents(); { status: 200u64, body: result }",
+                },
+            },
+            {
+                "method": "Options",
+                "path": "/v1/cart/{user}",
+                "binding": {
+                    "corsPreflight": {
+                        "allowOrigin": "https://shop.example.com",
+                        "allowMethods": "GET, POST",
+                        "allowHeaders": "Content-Type, Authorization",
+                        "maxAge": 3600,
+                    },
+                },
+            },
+        ],
+    }))
+}
+
+fn api_deployment(subdomain: Option<&str>) -> ApiDeployment {
+    from_json(json!({
+        "apiDefinitions": [
+            { "id": "shopping-cart", "version": "0.1.0" },
+            { "id": "shopping-admin", "version": "0.2.0" },
+        ],
+        "site": {
+            "host": "api.example.com",
+            "subdomain": subdomain,
+        },
+        "createdAt": "2025-03-04T10:23:00Z",
+    }))
+}
+
+fn profile(name: &str, is_active: bool) -> ProfileView {
+    from_json(json!({
+        "is_active": is_active,
+        "name": name,
+        "url": "https://golem.example.com/",
+        "worker_url": "https://worker.golem.example.com/",
+        "config": {
+            "default_format": "Text",
+            "default_project": "shop",
+            "command_defaults": {
+                "worker list": ["--precise"],
+            },
+            "http": {
+                "timeoutMs": 30000,
+                "retries": 3,
+            },
+        },
+    }))
+}
+
+fn app_test_report() -> AppTestReport {
+    from_json(json!({
+        "cases": [
+            {
+                "name": "increments",
+                "componentName": "shopping:cart",
+                "workerName": "smoke-test-increments",
+                "steps": [
+                    {
+                        "functionName": "shopping:cart-exports/api.{add-item}",
+                        "outcome": "passed",
+                        "actual": "()",
+                    },
+                    {
+                        "functionName": "shopping:cart-exports/api.{item-count}",
+                        "outcome": "passed",
+                        "expected": "1",
+                        "actual": "1",
+                    },
+                ],
+            },
+            {
+                "name": "checkout",
+                "componentName": "shopping:cart",
+                "workerName": "cart-checkout",
+                "steps": [
+                    {
+                        "functionName": "shopping:cart-exports/api.{checkout}",
+                        "outcome": "mismatch",
+                        "expected": "success({order-id: \"o-1\"})",
+                        "actual": "error(\"empty cart\")",
+                    },
+                    {
+                        "functionName": "shopping:cart-exports/api.{item-count}",
+                        "outcome": "skipped",
+                        "expected": "0",
+                    },
+                ],
+            },
+            {
+                "name": "unknown-function",
+                "componentName": "shopping:cart",
+                "workerName": "smoke-test-unknown-function",
+                "steps": [
+                    {
+                        "functionName": "remove-all",
+                        "outcome": "failed",
+                        "error": "Function remove-all not found",
+                    },
+                ],
+            },
+        ],
+    }))
+}
+
+#[test]
+fn component_views() {
+    let component = component();
+
+    assert_stable_fields("component", &ComponentGetView(component.clone()));
+
+    assert_snapshot("component-create", &ComponentCreateView(component.clone()));
+    assert_snapshot("component-update", &ComponentUpdateView(component.clone()));
+    assert_snapshot("component-get", &ComponentGetView(component.clone()));
+    assert_snapshot("component-list", &vec![component]);
+    assert_colored_snapshots("component_views");
+}
+
+#[test]
+fn worker_views() {
+    let worker = worker_metadata("cart-user-1", "Idle");
+
+    let precise = WorkerGetView {
+        metadata: worker.clone(),
+        precise: true,
+    };
+    assert_stable_fields("worker", &precise);
+
+    assert_snapshot(
+        "worker-create",
+        &WorkerCreateView {
+            component_name: worker.component_name.clone(),
+            worker_name: Some(worker.worker_name.clone()),
+        },
+    );
+    assert_snapshot("worker-get", &precise);
+    assert_snapshot(
+        "worker-get-not-precise",
+        &WorkerGetView::from_metadata_view(worker.clone()),
+    );
+    assert_snapshot(
+        "worker-list",
+        &WorkersMetadataResponseView {
+            workers: vec![worker, worker_metadata("cart-user-2", "Running")],
+            cursors: [("shopping:cart".to_string(), "1/42".to_string())]
+                .into_iter()
+                .collect(),
+        },
+    );
+    assert_colored_snapshots("worker_views");
+}
+
+#[test]
+fn api_definition_views() {
+    let definition = api_definition("shopping-cart", true);
+
+    assert_stable_fields("api-definition", &ApiDefinitionGetView(definition.clone()));
+
+    assert_snapshot(
+        "api-definition-new",
+        &ApiDefinitionNewView(definition.clone()),
+    );
+    assert_snapshot(
+        "api-definition-get",
+        &ApiDefinitionGetView(definition.clone()),
+    );
+    assert_snapshot(
+        "api-definition-list",
+        &vec![definition, api_definition("shopping-admin", false)],
+    );
+    assert_colored_snapshots("api_definition_views");
+}
+
+#[test]
+fn api_deployment_views() {
+    assert_snapshot("api-deployment", &api_deployment(Some("shop")));
+    assert_snapshot(
+        "api-deployment-list",
+        &vec![api_deployment(Some("shop")), api_deployment(None)],
+    );
+    assert_colored_snapshots("api_deployment_views");
+}
+
+#[test]
+fn app_test_views() {
+    assert_snapshot("app-test-report", &app_test_report());
+    assert_snapshot("app-test-report-empty", &AppTestReport { cases: vec![] });
+    assert_colored_snapshots("app_test_views");
+}
+
+#[test]
+fn profile_views() {
+    let profile = profile("staging", true);
+
+    assert_stable_fields("profile", &profile);
+
+    assert_snapshot("profile-get", &profile);
+    assert_snapshot(
+        "profile-list",
+        &vec![profile.clone(), self::profile("local", false)],
+    );
+    assert_snapshot("profile-config", &profile.config);
+    assert_snapshot(
+        "profile-context",
+        &ProfileContext {
+            component: Some("shopping:cart".to_string()),
+            worker: Some("cart-user-1".to_string()),
+        },
+    );
+    assert_snapshot("profile-context-empty", &ProfileContext::default());
+    assert_snapshot("profile-config-default", &ProfileConfig::default());
+    assert_colored_snapshots("profile_views");
+}
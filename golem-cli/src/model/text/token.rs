@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::log::LogColorize;
 use crate::model::text::fmt::*;
 use chrono::{DateTime, Utc};
 use cli_table::Table;
@@ -47,6 +48,36 @@ impl MessageWithFields for TokenNewView {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenCurrentView {
+    pub profile: String,
+    pub token: Token,
+    pub secret: String,
+}
+
+impl MessageWithFields for TokenCurrentView {
+    fn message(&self) -> String {
+        format!(
+            "Token used by profile {}",
+            self.profile.log_color_highlight()
+        )
+    }
+
+    fn fields(&self) -> Vec<(String, String)> {
+        let mut fields = FieldsBuilder::new();
+
+        fields
+            .fmt_field("Token ID", &self.token.id, format_main_id)
+            .fmt_field("Account ID", &self.token.account_id, format_id)
+            .field("Created at", &self.token.created_at)
+            .field("Expires at", &self.token.expires_at)
+            .field("Secret", &self.secret);
+
+        fields.build()
+    }
+}
+
 #[derive(Table)]
 struct TokenTableView {
     #[table(title = "ID")]
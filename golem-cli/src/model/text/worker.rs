@@ -12,12 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::log::{logln, LogColorize};
+use crate::log::{logln, LogColorize, LogIndent};
 use crate::model::deploy::TryUpdateAllWorkersResult;
 use crate::model::invoke_result_view::InvokeResultView;
 use crate::model::text::fmt::*;
+use crate::model::worker_replay::{ReplayDiff, WorkerReplayReport};
 use crate::model::{
-    ComponentName, WorkerMetadata, WorkerMetadataView, WorkerName, WorkersMetadataResponseView,
+    ComponentName, ProfileWorkerMetadataView, ProfileWorkersMetadataResponseView, WorkerMetadata,
+    WorkerMetadataPageView, WorkerMetadataView, WorkerName, WorkersMetadataCursorsView,
+    WorkersMetadataResponseView,
 };
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
@@ -33,6 +36,7 @@ use golem_wasm_rpc::{print_value_and_type, ValueAndType};
 use indoc::indoc;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt::Write;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -242,15 +246,77 @@ impl From<&WorkerMetadataView> for WorkerMetadataTableView {
 impl TextView for WorkersMetadataResponseView {
     fn log(&self) {
         log_table::<_, WorkerMetadataTableView>(&self.workers);
+        log_worker_cursors(&self.cursors);
+    }
+}
+
+impl TextView for WorkerMetadataPageView {
+    fn log(&self) {
+        log_table::<_, WorkerMetadataTableView>(&self.0);
+    }
+}
+
+impl TextView for WorkersMetadataCursorsView {
+    fn log(&self) {
+        log_worker_cursors(&self.cursors);
+    }
+}
+
+fn log_worker_cursors(cursors: &BTreeMap<String, String>) {
+    if !cursors.is_empty() {
+        logln("");
+    }
+    for (component_name, cursor) in cursors {
+        logln(format!(
+            "Cursor for more results for component {}: {}",
+            component_name.log_color_highlight(),
+            cursor.log_color_highlight()
+        ));
+    }
+}
 
-        if !self.cursors.is_empty() {
+#[derive(Table)]
+struct ProfileWorkerMetadataTableView {
+    #[table(title = "Profile")]
+    pub profile: String,
+    #[table(title = "Component name")]
+    pub component_name: ComponentName,
+    #[table(title = "Worker name")]
+    pub worker_name: WorkerName,
+    #[table(title = "Component\nversion", justify = "Justify::Right")]
+    pub component_version: u64,
+    #[table(title = "Status", justify = "Justify::Right")]
+    pub status: String,
+    #[table(title = "Created at")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&ProfileWorkerMetadataView> for ProfileWorkerMetadataTableView {
+    fn from(value: &ProfileWorkerMetadataView) -> Self {
+        Self {
+            profile: value.profile.0.clone(),
+            component_name: value.worker.component_name.clone(),
+            worker_name: value.worker.worker_name.clone(),
+            status: format_status(&value.worker.status),
+            component_version: value.worker.component_version,
+            created_at: value.worker.created_at,
+        }
+    }
+}
+
+impl TextView for ProfileWorkersMetadataResponseView {
+    fn log(&self) {
+        log_table::<_, ProfileWorkerMetadataTableView>(&self.workers);
+
+        if !self.truncated_profiles.is_empty() {
             logln("");
         }
-        for (component_name, cursor) in &self.cursors {
+        for profile in &self.truncated_profiles {
             logln(format!(
-                "Cursor for more results for component {}: {}",
-                component_name.log_color_highlight(),
-                cursor.log_color_highlight()
+                "More results are available for profile {}, use {} with {} to page through them",
+                profile.0.log_color_highlight(),
+                format!("--profile {}", profile.0).log_color_highlight(),
+                "--scan-cursor".log_color_highlight()
             ));
         }
     }
@@ -297,6 +363,65 @@ impl TextView for InvokeResultView {
     }
 }
 
+impl TextView for WorkerReplayReport {
+    fn log(&self) {
+        if self.invocations.is_empty() {
+            logln("No recorded invocations found");
+            return;
+        }
+
+        for invocation in &self.invocations {
+            let diff = match invocation.diff {
+                ReplayDiff::Unchanged => "unchanged".log_color_ok_highlight().to_string(),
+                ReplayDiff::Changed => "changed".log_color_error().to_string(),
+                ReplayDiff::Failed => "failed".log_color_error().to_string(),
+                ReplayDiff::Fixed => "fixed".log_color_ok_highlight().to_string(),
+                ReplayDiff::NotRecorded => "not recorded".log_color_warn().to_string(),
+            };
+            logln(format!(
+                "{} {}: {}",
+                format_main_id(&format!("#{:0>5}", invocation.oplog_index)),
+                format_export(&invocation.function_name),
+                diff
+            ));
+
+            if invocation.diff == ReplayDiff::Unchanged {
+                continue;
+            }
+            let _indent = LogIndent::new();
+            if let Some(recorded) = &invocation.recorded {
+                logln(format!("- {recorded}").red().to_string());
+            }
+            if let Some(error) = &invocation.recorded_error {
+                logln(format!("- error: {error}").red().to_string());
+            }
+            if let Some(replayed) = &invocation.replayed {
+                logln(format!("+ {replayed}").green().to_string());
+            }
+            if let Some(error) = &invocation.replayed_error {
+                logln(format!("+ error: {error}").green().to_string());
+            }
+        }
+
+        logln("");
+        logln(format!(
+            "{} unchanged, {} changed, {} failed, {} fixed",
+            self.count(ReplayDiff::Unchanged)
+                .to_string()
+                .log_color_ok_highlight(),
+            self.count(ReplayDiff::Changed)
+                .to_string()
+                .log_color_highlight(),
+            self.count(ReplayDiff::Failed)
+                .to_string()
+                .log_color_highlight(),
+            self.count(ReplayDiff::Fixed)
+                .to_string()
+                .log_color_highlight(),
+        ));
+    }
+}
+
 impl TextView for Vec<(u64, PublicOplogEntry)> {
     fn log(&self) {
         for (idx, entry) in self {
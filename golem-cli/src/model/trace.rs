@@ -0,0 +1,226 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde_json::json;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+/// W3C trace context, see https://www.w3.org/TR/trace-context/#traceparent-header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceParent {
+    pub trace_id: String,
+    pub parent_id: String,
+    pub flags: u8,
+}
+
+impl TraceParent {
+    /// Starts a new sampled trace
+    pub fn new() -> Self {
+        Self {
+            trace_id: Uuid::new_v4().simple().to_string(),
+            parent_id: new_span_id(),
+            flags: 1,
+        }
+    }
+
+    /// Context for a new span in the same trace, using the current parent as its parent
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id.clone(),
+            parent_id: new_span_id(),
+            flags: self.flags,
+        }
+    }
+}
+
+impl Default for TraceParent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for TraceParent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id, self.parent_id, self.flags
+        )
+    }
+}
+
+impl FromStr for TraceParent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_hex = |part: &str, len: usize| {
+            part.len() == len
+                && part
+                    .chars()
+                    .all(|char| char.is_ascii_digit() || ('a'..='f').contains(&char))
+                && part.chars().any(|char| char != '0')
+        };
+
+        let parts = s.trim().split('-').collect::<Vec<_>>();
+        match parts.as_slice() {
+            [version, trace_id, parent_id, flags]
+                if *version == "00"
+                    && is_hex(trace_id, 32)
+                    && is_hex(parent_id, 16)
+                    && flags.len() == 2 =>
+            {
+                Ok(Self {
+                    trace_id: trace_id.to_string(),
+                    parent_id: parent_id.to_string(),
+                    flags: u8::from_str_radix(flags, 16)
+                        .map_err(|_| format!("Invalid traceparent flags: {flags}"))?,
+                })
+            }
+            _ => Err(format!(
+                "Invalid traceparent: {s}, expected 00-<32 hex digit trace ID>-<16 hex digit parent ID>-<2 hex digit flags>"
+            )),
+        }
+    }
+}
+
+fn new_span_id() -> String {
+    Uuid::new_v4().simple().to_string()[..16].to_string()
+}
+
+/// Span recorded by the CLI, exported using the OTLP/HTTP JSON encoding
+#[derive(Debug, Clone)]
+pub struct ClientSpan {
+    pub name: String,
+    /// The context of the span itself, the one propagated to the server
+    pub context: TraceParent,
+    pub parent_span_id: Option<String>,
+    pub start: SystemTime,
+    pub end: SystemTime,
+    pub attributes: Vec<(String, String)>,
+    pub error: Option<String>,
+}
+
+impl ClientSpan {
+    pub fn to_otlp_json(&self, service_name: &str) -> serde_json::Value {
+        let unix_nanos = |time: SystemTime| {
+            time.duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+                .to_string()
+        };
+        let attribute =
+            |key: &str, value: &str| json!({"key": key, "value": {"stringValue": value}});
+
+        let mut span = json!({
+            "traceId": self.context.trace_id,
+            "spanId": self.context.parent_id,
+            "name": self.name,
+            // SPAN_KIND_CLIENT
+            "kind": 3,
+            "startTimeUnixNano": unix_nanos(self.start),
+            "endTimeUnixNano": unix_nanos(self.end),
+            "attributes": self
+                .attributes
+                .iter()
+                .map(|(key, value)| attribute(key, value))
+                .collect::<Vec<_>>(),
+            "status": match &self.error {
+                // STATUS_CODE_ERROR
+                Some(error) => json!({"code": 2, "message": error}),
+                // STATUS_CODE_OK
+                None => json!({"code": 1}),
+            },
+        });
+        if let Some(parent_span_id) = &self.parent_span_id {
+            span["parentSpanId"] = json!(parent_span_id);
+        }
+
+        json!({
+            "resourceSpans": [{
+                "resource": {
+                    "attributes": [attribute("service.name", service_name)]
+                },
+                "scopeSpans": [{
+                    "scope": {"name": "golem-cli", "version": crate::version()},
+                    "spans": [span]
+                }]
+            }]
+        })
+    }
+}
+
+/// OTLP/HTTP traces endpoint based on the standard OpenTelemetry exporter environment variables,
+/// client spans are only exported when one of them is set
+pub fn otlp_traces_endpoint() -> Option<String> {
+    let non_empty = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+    non_empty("OTEL_EXPORTER_OTLP_TRACES_ENDPOINT").or_else(|| {
+        non_empty("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .map(|endpoint| format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+    })
+}
+
+/// Additional headers for the OTLP requests, in the form of key1=value1,key2=value2
+pub fn otlp_headers() -> Vec<(String, String)> {
+    std::env::var("OTEL_EXPORTER_OTLP_TRACES_HEADERS")
+        .or_else(|_| std::env::var("OTEL_EXPORTER_OTLP_HEADERS"))
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|header| header.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+pub fn otlp_service_name() -> String {
+    std::env::var("OTEL_SERVICE_NAME")
+        .ok()
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "golem-cli".to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::trace::TraceParent;
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn traceparent_parse_and_child() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+            .parse::<TraceParent>()
+            .unwrap();
+        assert!(traceparent.trace_id == "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(traceparent.parent_id == "00f067aa0ba902b7");
+        assert!(traceparent.flags == 1);
+        assert!(
+            traceparent.to_string() == "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+
+        let child = traceparent.child();
+        assert!(child.trace_id == traceparent.trace_id);
+        assert!(child.parent_id != traceparent.parent_id);
+        assert!(child.to_string().parse::<TraceParent>() == Ok(child.clone()));
+
+        assert!("00-00000000000000000000000000000000-00f067aa0ba902b7-01"
+            .parse::<TraceParent>()
+            .is_err());
+        assert!("00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01"
+            .parse::<TraceParent>()
+            .is_err());
+        assert!("4bf92f3577b34da6a3ce929d0e0e4736"
+            .parse::<TraceParent>()
+            .is_err());
+    }
+}
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use colored::Colorize;
 use golem_wasm_ast::analysis::{
-    AnalysedFunction, AnalysedType, TypeRecord, TypeResult, TypeTuple, TypeVariant,
+    AnalysedFunction, AnalysedType, TypeEnum, TypeFlags, TypeRecord, TypeResult, TypeTuple,
+    TypeVariant,
 };
 
 pub fn type_wave_compatible(typ: &AnalysedType) -> bool {
@@ -66,3 +68,358 @@ pub fn function_wave_compatible(func: &AnalysedFunction) -> bool {
     func.parameters.iter().all(|p| type_wave_compatible(&p.typ))
         && func.result.iter().all(|r| type_wave_compatible(&r.typ))
 }
+
+/// Unmatched or unclosed bracket, the position is a char index of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveBracketError {
+    pub position: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WaveTokenKind {
+    Literal,
+    Number,
+    Keyword,
+    Label,
+    Case,
+    OpenBracket { depth: usize, matched: bool },
+    CloseBracket { depth: usize, matched: bool },
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WaveToken {
+    kind: WaveTokenKind,
+    start: usize,
+    end: usize,
+}
+
+const WAVE_KEYWORDS: &[&str] = &["true", "false", "some", "none", "ok", "err", "inf", "nan"];
+
+fn closing_bracket(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+/// Splits the input into tokens (char index ranges) for highlighting and bracket matching,
+/// unclosed string and char literals last until the end of the input
+fn wave_tokens(input: &str) -> (Vec<WaveToken>, Vec<(usize, char)>) {
+    let chars = input.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut open_brackets = Vec::<(usize, char, usize)>::new();
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let start = idx;
+        let char = chars[idx];
+        let kind = match char {
+            '"' | '\'' => {
+                idx += 1;
+                while idx < chars.len() && chars[idx] != char {
+                    if chars[idx] == '\\' {
+                        idx += 1;
+                    }
+                    idx += 1;
+                }
+                idx = (idx + 1).min(chars.len());
+                WaveTokenKind::Literal
+            }
+            '(' | '[' | '{' => {
+                idx += 1;
+                open_brackets.push((tokens.len(), char, start));
+                WaveTokenKind::OpenBracket {
+                    depth: open_brackets.len() - 1,
+                    matched: false,
+                }
+            }
+            ')' | ']' | '}' => {
+                idx += 1;
+                match open_brackets.last() {
+                    Some((token_idx, open, _)) if closing_bracket(*open) == char => {
+                        let token_idx = *token_idx;
+                        open_brackets.pop();
+                        if let WaveTokenKind::OpenBracket { matched, .. } =
+                            &mut tokens[token_idx].kind
+                        {
+                            *matched = true;
+                        }
+                        WaveTokenKind::CloseBracket {
+                            depth: open_brackets.len(),
+                            matched: true,
+                        }
+                    }
+                    _ => WaveTokenKind::CloseBracket {
+                        depth: open_brackets.len(),
+                        matched: false,
+                    },
+                }
+            }
+            char if char.is_ascii_digit()
+                || (char == '-' && chars.get(idx + 1).is_some_and(|c| c.is_ascii_digit())) =>
+            {
+                idx += 1;
+                while idx < chars.len()
+                    && (chars[idx].is_ascii_alphanumeric() || matches!(chars[idx], '.' | '_'))
+                {
+                    idx += 1;
+                }
+                WaveTokenKind::Number
+            }
+            char if char.is_alphabetic() || char == '%' => {
+                idx += 1;
+                while idx < chars.len() && (chars[idx].is_alphanumeric() || chars[idx] == '-') {
+                    idx += 1;
+                }
+                let word = chars[start..idx].iter().collect::<String>();
+                let next = chars[idx..].iter().find(|char| !char.is_whitespace());
+                if next == Some(&':') {
+                    WaveTokenKind::Label
+                } else if WAVE_KEYWORDS.contains(&word.as_str()) {
+                    WaveTokenKind::Keyword
+                } else {
+                    WaveTokenKind::Case
+                }
+            }
+            _ => {
+                idx += 1;
+                WaveTokenKind::Other
+            }
+        };
+        tokens.push(WaveToken {
+            kind,
+            start,
+            end: idx,
+        });
+    }
+
+    let unclosed = open_brackets
+        .into_iter()
+        .map(|(_, open, position)| (position, open))
+        .collect();
+    (tokens, unclosed)
+}
+
+/// Checks that all brackets are matched, ignoring the ones in string and char literals
+pub fn check_wave_brackets(input: &str) -> Result<(), WaveBracketError> {
+    let (tokens, unclosed) = wave_tokens(input);
+    let chars = input.chars().collect::<Vec<_>>();
+
+    if let Some(token) = tokens.iter().find(|token| {
+        matches!(
+            token.kind,
+            WaveTokenKind::CloseBracket { matched: false, .. }
+        )
+    }) {
+        return Err(WaveBracketError {
+            position: token.start,
+            message: format!(
+                "Unmatched closing bracket {} at column {}",
+                chars[token.start],
+                token.start + 1
+            ),
+        });
+    }
+
+    if let Some((position, open)) = unclosed.last() {
+        return Err(WaveBracketError {
+            position: *position,
+            message: format!(
+                "Unclosed bracket {} at column {}, expected {}",
+                open,
+                position + 1,
+                closing_bracket(*open)
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the closing quote and brackets which complete the input, innermost first
+pub fn wave_closing_suffix(input: &str) -> String {
+    let (tokens, unclosed) = wave_tokens(input);
+    let chars = input.chars().collect::<Vec<_>>();
+
+    let unclosed_quote = tokens
+        .last()
+        .filter(|token| token.kind == WaveTokenKind::Literal)
+        .and_then(|token| {
+            let quote = chars[token.start];
+            let is_closed = token.end - token.start > 1 && chars[token.end - 1] == quote && {
+                let backslashes = chars[token.start + 1..token.end - 1]
+                    .iter()
+                    .rev()
+                    .take_while(|char| **char == '\\')
+                    .count();
+                backslashes % 2 == 0
+            };
+            (!is_closed).then_some(quote)
+        });
+
+    unclosed_quote
+        .into_iter()
+        .chain(
+            unclosed
+                .iter()
+                .rev()
+                .map(|(_, open)| closing_bracket(*open)),
+        )
+        .collect()
+}
+
+/// Colors the WAVE input for terminals, brackets are colored by nesting depth and
+/// unmatched brackets are marked as errors
+pub fn highlight_wave(input: &str) -> String {
+    let (tokens, _) = wave_tokens(input);
+    let chars = input.chars().collect::<Vec<_>>();
+
+    let bracket_color = |text: &str, depth: usize, matched: bool| {
+        if !matched {
+            text.red().bold().to_string()
+        } else {
+            match depth % 3 {
+                0 => text.yellow().to_string(),
+                1 => text.magenta().to_string(),
+                _ => text.cyan().to_string(),
+            }
+        }
+    };
+
+    tokens
+        .into_iter()
+        .map(|token| {
+            let text = chars[token.start..token.end].iter().collect::<String>();
+            match token.kind {
+                WaveTokenKind::Literal => text.green().to_string(),
+                WaveTokenKind::Number => text.bright_cyan().to_string(),
+                WaveTokenKind::Keyword => text.bright_magenta().to_string(),
+                WaveTokenKind::Label => text.bold().to_string(),
+                WaveTokenKind::Case => text.bright_blue().to_string(),
+                WaveTokenKind::OpenBracket { depth, matched }
+                | WaveTokenKind::CloseBracket { depth, matched } => {
+                    bracket_color(&text, depth, matched)
+                }
+                WaveTokenKind::Other => text,
+            }
+        })
+        .collect()
+}
+
+/// Returns a valid example value of the type, used as a template for entering values
+pub fn wave_skeleton(typ: &AnalysedType) -> Option<String> {
+    Some(match typ {
+        AnalysedType::Variant(TypeVariant { cases, .. }) => {
+            let case = cases.first()?;
+            match &case.typ {
+                Some(typ) => format!("{}({})", case.name, wave_skeleton(typ)?),
+                None => case.name.clone(),
+            }
+        }
+        AnalysedType::Result(TypeResult { ok, .. }) => match ok {
+            Some(ok) => format!("ok({})", wave_skeleton(ok)?),
+            None => "ok".to_string(),
+        },
+        AnalysedType::Option(_) => "none".to_string(),
+        AnalysedType::Enum(TypeEnum { cases, .. }) => cases.first()?.clone(),
+        AnalysedType::Flags(_) => "{}".to_string(),
+        AnalysedType::Record(TypeRecord { fields, .. }) => format!(
+            "{{{}}}",
+            fields
+                .iter()
+                .map(|field| Some(format!("{}: {}", field.name, wave_skeleton(&field.typ)?)))
+                .collect::<Option<Vec<_>>>()?
+                .join(", ")
+        ),
+        AnalysedType::Tuple(TypeTuple { items, .. }) => format!(
+            "({})",
+            items
+                .iter()
+                .map(wave_skeleton)
+                .collect::<Option<Vec<_>>>()?
+                .join(", ")
+        ),
+        AnalysedType::List(_) => "[]".to_string(),
+        AnalysedType::Str(_) => "\"\"".to_string(),
+        AnalysedType::Chr(_) => "'a'".to_string(),
+        AnalysedType::F64(_) | AnalysedType::F32(_) => "0.0".to_string(),
+        AnalysedType::U64(_)
+        | AnalysedType::S64(_)
+        | AnalysedType::U32(_)
+        | AnalysedType::S32(_)
+        | AnalysedType::U16(_)
+        | AnalysedType::S16(_)
+        | AnalysedType::U8(_)
+        | AnalysedType::S8(_) => "0".to_string(),
+        AnalysedType::Bool(_) => "false".to_string(),
+        AnalysedType::Handle(_) => return None,
+    })
+}
+
+/// Returns the top level case names and keywords of the type, cases with payloads are
+/// returned with the opening bracket
+pub fn wave_case_names(typ: &AnalysedType) -> Vec<String> {
+    match typ {
+        AnalysedType::Variant(TypeVariant { cases, .. }) => cases
+            .iter()
+            .map(|case| match case.typ {
+                Some(_) => format!("{}(", case.name),
+                None => case.name.clone(),
+            })
+            .collect(),
+        AnalysedType::Enum(TypeEnum { cases, .. }) => cases.clone(),
+        AnalysedType::Flags(TypeFlags { names, .. }) => {
+            names.iter().map(|name| format!("{{{name}")).collect()
+        }
+        AnalysedType::Option(_) => vec!["none".to_string(), "some(".to_string()],
+        AnalysedType::Result(TypeResult { ok, err, .. }) => vec![
+            if ok.is_some() { "ok(" } else { "ok" }.to_string(),
+            if err.is_some() { "err(" } else { "err" }.to_string(),
+        ],
+        AnalysedType::Bool(_) => vec!["true".to_string(), "false".to_string()],
+        _ => vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::wave::{
+        check_wave_brackets, wave_case_names, wave_closing_suffix, wave_skeleton,
+    };
+    use assert2::assert;
+    use golem_wasm_ast::analysis::analysed_type::{
+        bool, case, field, list, option, record, str, u32, unit_case, variant,
+    };
+    use test_r::test;
+
+    #[test]
+    fn brackets_skeletons_and_cases() {
+        assert!(check_wave_brackets(r#"{name: "a)", items: [1, 2]}"#).is_ok());
+
+        let unmatched = check_wave_brackets("{items: [1, 2}").unwrap_err();
+        assert!(unmatched.position == 13);
+        let unclosed = check_wave_brackets("{items: [1, 2]").unwrap_err();
+        assert!(unclosed.position == 0);
+
+        assert!(wave_closing_suffix(r#"{items: [some("a"#) == "\")]}");
+        assert!(wave_closing_suffix(r#"{name: "a\"b"}"#).is_empty());
+
+        let typ = record(vec![
+            field("name", str()),
+            field("count", option(u32())),
+            field("tags", list(str())),
+            field("active", bool()),
+        ]);
+        assert!(
+            wave_skeleton(&typ)
+                == Some(r#"{name: "", count: none, tags: [], active: false}"#.to_string())
+        );
+
+        let typ = variant(vec![case("set", u32()), unit_case("reset")]);
+        assert!(wave_skeleton(&typ) == Some("set(0)".to_string()));
+        assert!(wave_case_names(&typ) == vec!["set(", "reset"]);
+    }
+}
@@ -0,0 +1,292 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Portable archive of an exported worker.
+//!
+//! The archive is a gzip compressed JSON lines stream, so both exporting and importing can
+//! process it record by record, without loading the whole oplog or the files into memory:
+//!
+//!  - the first record is the header, with the format version and the worker metadata
+//!  - oplog entries follow in oplog index order
+//!  - files of the worker's file system are stored as base64 encoded chunks
+//!  - the last record is the footer with the record counts, archives without it are truncated
+
+use anyhow::{anyhow, bail, Context};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use golem_client::model::PublicOplogEntry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+pub const WORKER_ARCHIVE_FORMAT_VERSION: u32 = 1;
+pub const WORKER_ARCHIVE_EXTENSION: &str = "golem-worker.gz";
+
+const FILE_CHUNK_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerArchiveHeader {
+    pub format_version: u32,
+    pub cli_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub component_name: String,
+    pub component_id: Uuid,
+    pub component_version: u64,
+    pub worker_name: String,
+    pub args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerArchiveFooter {
+    pub oplog_entry_count: u64,
+    pub file_count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WorkerArchiveRecord {
+    Header(WorkerArchiveHeader),
+    #[serde(rename_all = "camelCase")]
+    OplogEntry {
+        index: u64,
+        entry: PublicOplogEntry,
+    },
+    /// Chunk of a file, the chunks of a file are stored in order, the first one has offset 0
+    #[serde(rename_all = "camelCase")]
+    FileChunk {
+        path: String,
+        offset: u64,
+        data: String,
+    },
+    Footer(WorkerArchiveFooter),
+}
+
+pub struct WorkerArchiveWriter {
+    encoder: GzEncoder<BufWriter<File>>,
+    footer: WorkerArchiveFooter,
+}
+
+impl WorkerArchiveWriter {
+    pub fn create(path: &Path, header: WorkerArchiveHeader) -> anyhow::Result<Self> {
+        let file = File::create(path)
+            .with_context(|| anyhow!("Failed to create worker archive: {}", path.display()))?;
+        let mut writer = Self {
+            encoder: GzEncoder::new(BufWriter::new(file), Compression::default()),
+            footer: WorkerArchiveFooter {
+                oplog_entry_count: 0,
+                file_count: 0,
+            },
+        };
+        writer.write_record(&WorkerArchiveRecord::Header(header))?;
+        Ok(writer)
+    }
+
+    pub fn write_oplog_entry(&mut self, index: u64, entry: PublicOplogEntry) -> anyhow::Result<()> {
+        self.write_record(&WorkerArchiveRecord::OplogEntry { index, entry })?;
+        self.footer.oplog_entry_count += 1;
+        Ok(())
+    }
+
+    pub fn write_file(&mut self, path: &str, content: &[u8]) -> anyhow::Result<()> {
+        // Empty files still get one (empty) chunk, so they are restored too
+        let chunks = content.chunks(FILE_CHUNK_SIZE).collect::<Vec<_>>();
+        let chunks = if chunks.is_empty() {
+            vec![&[][..]]
+        } else {
+            chunks
+        };
+        for (idx, chunk) in chunks.into_iter().enumerate() {
+            self.write_record(&WorkerArchiveRecord::FileChunk {
+                path: path.to_string(),
+                offset: (idx * FILE_CHUNK_SIZE) as u64,
+                data: BASE64_STANDARD.encode(chunk),
+            })?;
+        }
+        self.footer.file_count += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> anyhow::Result<WorkerArchiveFooter> {
+        let footer = self.footer.clone();
+        self.write_record(&WorkerArchiveRecord::Footer(footer.clone()))?;
+        self.encoder.finish()?.flush()?;
+        Ok(footer)
+    }
+
+    fn write_record(&mut self, record: &WorkerArchiveRecord) -> anyhow::Result<()> {
+        serde_json::to_writer(&mut self.encoder, record)?;
+        self.encoder.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+pub struct WorkerArchiveReader {
+    lines: Lines<BufReader<GzDecoder<File>>>,
+    header: WorkerArchiveHeader,
+    footer: Option<WorkerArchiveFooter>,
+}
+
+impl WorkerArchiveReader {
+    /// Opens the archive and reads its header
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let file = File::open(path)
+            .with_context(|| anyhow!("Failed to open worker archive: {}", path.display()))?;
+        let mut lines = BufReader::new(GzDecoder::new(file)).lines();
+
+        let header = match lines.next() {
+            Some(line) => match serde_json::from_str(&line?)? {
+                WorkerArchiveRecord::Header(header) => header,
+                _ => bail!("Invalid worker archive, missing header"),
+            },
+            None => bail!("Invalid worker archive, the archive is empty"),
+        };
+        if header.format_version > WORKER_ARCHIVE_FORMAT_VERSION {
+            bail!(
+                "Unsupported worker archive format version: {}, the latest supported version is {}",
+                header.format_version,
+                WORKER_ARCHIVE_FORMAT_VERSION
+            );
+        }
+
+        Ok(Self {
+            lines,
+            header,
+            footer: None,
+        })
+    }
+
+    pub fn header(&self) -> &WorkerArchiveHeader {
+        &self.header
+    }
+
+    /// Returns the next oplog entry or file chunk, and None after the footer. Fails if the
+    /// archive ends without a footer.
+    pub fn next_record(&mut self) -> anyhow::Result<Option<WorkerArchiveRecord>> {
+        if self.footer.is_some() {
+            return Ok(None);
+        }
+        let Some(line) = self.lines.next() else {
+            bail!("Invalid worker archive, the archive is truncated");
+        };
+        match serde_json::from_str(&line?)? {
+            WorkerArchiveRecord::Header(_) => bail!("Invalid worker archive, duplicated header"),
+            WorkerArchiveRecord::Footer(footer) => {
+                self.footer = Some(footer);
+                Ok(None)
+            }
+            record => Ok(Some(record)),
+        }
+    }
+
+    /// Reads the remaining records and returns the footer, used for checking that the archive
+    /// is complete before starting to import it
+    pub fn validate(mut self) -> anyhow::Result<WorkerArchiveFooter> {
+        let mut oplog_entry_count = 0;
+        let mut file_count = 0;
+        while let Some(record) = self.next_record()? {
+            match record {
+                WorkerArchiveRecord::OplogEntry { .. } => oplog_entry_count += 1,
+                WorkerArchiveRecord::FileChunk { offset: 0, .. } => file_count += 1,
+                _ => {}
+            }
+        }
+        let footer = self.footer.expect("missing footer after the last record");
+        if footer.oplog_entry_count != oplog_entry_count || footer.file_count != file_count {
+            bail!("Invalid worker archive, the record counts do not match the footer");
+        }
+        Ok(footer)
+    }
+}
+
+pub fn decode_file_chunk(data: &str) -> anyhow::Result<Vec<u8>> {
+    BASE64_STANDARD
+        .decode(data)
+        .context("Invalid worker archive, failed to decode file chunk")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::worker_archive::{
+        decode_file_chunk, WorkerArchiveHeader, WorkerArchiveReader, WorkerArchiveRecord,
+        WorkerArchiveWriter, FILE_CHUNK_SIZE, WORKER_ARCHIVE_FORMAT_VERSION,
+    };
+    use assert2::assert;
+    use chrono::Utc;
+    use std::collections::BTreeMap;
+    use test_r::test;
+    use uuid::Uuid;
+
+    #[test]
+    fn worker_archive_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("worker.golem-worker.gz");
+
+        let header = WorkerArchiveHeader {
+            format_version: WORKER_ARCHIVE_FORMAT_VERSION,
+            cli_version: "1.0.0".to_string(),
+            exported_at: Utc::now(),
+            component_name: "shop:cart".to_string(),
+            component_id: Uuid::new_v4(),
+            component_version: 3,
+            worker_name: "cart-1".to_string(),
+            args: vec![],
+            env: BTreeMap::from([("LOG_LEVEL".to_string(), "debug".to_string())]),
+        };
+        let large_file = (0..FILE_CHUNK_SIZE + 100)
+            .map(|idx| (idx % 251) as u8)
+            .collect::<Vec<_>>();
+
+        let mut writer = WorkerArchiveWriter::create(&path, header.clone()).unwrap();
+        writer.write_file("/data/large.bin", &large_file).unwrap();
+        writer.write_file("/data/empty.txt", &[]).unwrap();
+        let footer = writer.finish().unwrap();
+        assert!(footer.file_count == 2);
+
+        let mut reader = WorkerArchiveReader::open(&path).unwrap();
+        assert!(reader.header() == &header);
+        let mut restored = BTreeMap::<String, Vec<u8>>::new();
+        while let Some(record) = reader.next_record().unwrap() {
+            if let WorkerArchiveRecord::FileChunk { path, offset, data } = record {
+                let content = restored.entry(path).or_default();
+                assert!(content.len() as u64 == offset);
+                content.extend(decode_file_chunk(&data).unwrap());
+            }
+        }
+        assert!(restored["/data/large.bin"] == large_file);
+        assert!(restored["/data/empty.txt"].is_empty());
+
+        assert!(
+            WorkerArchiveReader::open(&path)
+                .unwrap()
+                .validate()
+                .unwrap()
+                == footer
+        );
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+        let truncated = WorkerArchiveReader::open(&path).and_then(|reader| reader.validate());
+        assert!(truncated.is_err());
+    }
+}
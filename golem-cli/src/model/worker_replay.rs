@@ -0,0 +1,257 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use golem_client::model::PublicOplogEntry;
+use golem_wasm_rpc::{print_value_and_type, ValueAndType};
+use serde::{Deserialize, Serialize};
+
+/// Invocation recorded in a worker's oplog, together with its recorded outcome
+#[derive(Debug, Clone)]
+pub struct RecordedInvocation {
+    pub oplog_index: u64,
+    pub function_name: String,
+    pub idempotency_key: String,
+    pub request: Vec<ValueAndType>,
+    /// WAVE encoded result, or the recorded error. None if the invocation did not finish yet.
+    pub outcome: Option<Result<String, String>>,
+}
+
+/// Collects the invocations from the oplog entries, pairing them with their completion or
+/// error entries, and returns the last `last` of them
+pub fn recorded_invocations(
+    entries: impl IntoIterator<Item = (u64, PublicOplogEntry)>,
+    last: usize,
+) -> Vec<RecordedInvocation> {
+    let mut invocations = Vec::<RecordedInvocation>::new();
+    for (oplog_index, entry) in entries {
+        let pending = invocations
+            .last_mut()
+            .filter(|invocation| invocation.outcome.is_none());
+        match entry {
+            PublicOplogEntry::ExportedFunctionInvoked(params) => {
+                invocations.push(RecordedInvocation {
+                    oplog_index,
+                    function_name: params.function_name,
+                    idempotency_key: params.idempotency_key.to_string(),
+                    request: params.request,
+                    outcome: None,
+                });
+            }
+            PublicOplogEntry::ExportedFunctionCompleted(params) => {
+                if let Some(invocation) = pending {
+                    invocation.outcome = Some(Ok(format_result(params.response.as_ref())));
+                }
+            }
+            PublicOplogEntry::Error(params) => {
+                if let Some(invocation) = pending {
+                    invocation.outcome = Some(Err(params.error));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let skip = invocations.len().saturating_sub(last);
+    invocations.into_iter().skip(skip).collect()
+}
+
+/// Number of invocations started in the given oplog entries
+pub fn started_invocation_count(entries: &[(u64, PublicOplogEntry)]) -> usize {
+    entries
+        .iter()
+        .filter(|(_, entry)| matches!(entry, PublicOplogEntry::ExportedFunctionInvoked(_)))
+        .count()
+}
+
+/// Oplog windows as (from, count) pairs, walking backwards from the last oplog index, so
+/// the recent invocations can be fetched without paging through the whole oplog
+pub fn oplog_windows_backwards(
+    last_oplog_index: u64,
+    batch_size: u64,
+) -> impl Iterator<Item = (u64, u64)> {
+    let batch_size = batch_size.max(1);
+    let mut end = last_oplog_index;
+    std::iter::from_fn(move || {
+        if end == 0 {
+            return None;
+        }
+        let from = end.saturating_sub(batch_size) + 1;
+        let count = end - from + 1;
+        end = from - 1;
+        Some((from, count))
+    })
+}
+
+pub fn format_result(result: Option<&ValueAndType>) -> String {
+    match result {
+        Some(result) => print_value_and_type(result).unwrap_or_else(|err| format!("<{err}>")),
+        None => "()".to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplayDiff {
+    /// Same result, or both the recorded and the replayed invocation failed
+    Unchanged,
+    Changed,
+    /// The replayed invocation failed, while the recorded one succeeded
+    Failed,
+    /// The replayed invocation succeeded, while the recorded one failed
+    Fixed,
+    /// The recorded invocation did not finish, so there is nothing to compare against
+    NotRecorded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayedInvocationView {
+    pub oplog_index: u64,
+    pub function_name: String,
+    pub idempotency_key: String,
+    pub diff: ReplayDiff,
+    pub recorded: Option<String>,
+    pub recorded_error: Option<String>,
+    pub replayed: Option<String>,
+    pub replayed_error: Option<String>,
+}
+
+impl ReplayedInvocationView {
+    pub fn new(invocation: &RecordedInvocation, replayed: Result<String, String>) -> Self {
+        let diff = match (&invocation.outcome, &replayed) {
+            (None, _) => ReplayDiff::NotRecorded,
+            (Some(Ok(recorded)), Ok(replayed)) if recorded == replayed => ReplayDiff::Unchanged,
+            (Some(Ok(_)), Ok(_)) => ReplayDiff::Changed,
+            (Some(Ok(_)), Err(_)) => ReplayDiff::Failed,
+            (Some(Err(_)), Ok(_)) => ReplayDiff::Fixed,
+            (Some(Err(_)), Err(_)) => ReplayDiff::Unchanged,
+        };
+        let (recorded, recorded_error) = match &invocation.outcome {
+            Some(Ok(result)) => (Some(result.clone()), None),
+            Some(Err(error)) => (None, Some(error.clone())),
+            None => (None, None),
+        };
+        let (replayed, replayed_error) = match replayed {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            oplog_index: invocation.oplog_index,
+            function_name: invocation.function_name.clone(),
+            idempotency_key: invocation.idempotency_key.clone(),
+            diff,
+            recorded,
+            recorded_error,
+            replayed,
+            replayed_error,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerReplayReport {
+    pub component_name: String,
+    pub worker_name: String,
+    pub sandbox_component_name: String,
+    pub sandbox_worker_name: String,
+    pub invocations: Vec<ReplayedInvocationView>,
+}
+
+impl WorkerReplayReport {
+    pub fn count(&self, diff: ReplayDiff) -> usize {
+        self.invocations
+            .iter()
+            .filter(|invocation| invocation.diff == diff)
+            .count()
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.invocations
+            .iter()
+            .any(|invocation| matches!(invocation.diff, ReplayDiff::Changed | ReplayDiff::Failed))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::worker_replay::{
+        oplog_windows_backwards, RecordedInvocation, ReplayDiff, ReplayedInvocationView,
+        WorkerReplayReport,
+    };
+    use assert2::assert;
+    use test_r::test;
+
+    fn invocation(outcome: Option<Result<&str, &str>>) -> RecordedInvocation {
+        RecordedInvocation {
+            oplog_index: 2,
+            function_name: "shop:cart/api.{add-item}".to_string(),
+            idempotency_key: "key".to_string(),
+            request: vec![],
+            outcome: outcome.map(|outcome| {
+                outcome
+                    .map(|result| result.to_string())
+                    .map_err(|error| error.to_string())
+            }),
+        }
+    }
+
+    #[test]
+    fn replay_diff() {
+        let cases = [
+            (Some(Ok("3")), Ok("3"), ReplayDiff::Unchanged),
+            (Some(Ok("3")), Ok("4"), ReplayDiff::Changed),
+            (Some(Ok("3")), Err("trap"), ReplayDiff::Failed),
+            (Some(Err("trap")), Ok("3"), ReplayDiff::Fixed),
+            (Some(Err("trap")), Err("other trap"), ReplayDiff::Unchanged),
+            (None, Ok("3"), ReplayDiff::NotRecorded),
+        ];
+
+        let invocations = cases
+            .into_iter()
+            .map(|(recorded, replayed, expected)| {
+                let view = ReplayedInvocationView::new(
+                    &invocation(recorded),
+                    replayed
+                        .map(|result| result.to_string())
+                        .map_err(|error| error.to_string()),
+                );
+                assert!(view.diff == expected);
+                view
+            })
+            .collect::<Vec<_>>();
+
+        let report = WorkerReplayReport {
+            component_name: "shop:cart".to_string(),
+            worker_name: "cart-1".to_string(),
+            sandbox_component_name: "shop:cart-replay".to_string(),
+            sandbox_worker_name: "cart-1-replay".to_string(),
+            invocations,
+        };
+        assert!(report.count(ReplayDiff::Unchanged) == 2);
+        assert!(report.has_regressions());
+    }
+
+    #[test]
+    fn oplog_windows() {
+        let windows = oplog_windows_backwards(25, 10).collect::<Vec<_>>();
+        assert!(windows == vec![(16, 10), (6, 10), (1, 5)]);
+
+        let windows = oplog_windows_backwards(20, 10).collect::<Vec<_>>();
+        assert!(windows == vec![(11, 10), (1, 10)]);
+
+        assert!(oplog_windows_backwards(0, 10).next().is_none());
+        assert!(oplog_windows_backwards(2, 0).collect::<Vec<_>>() == vec![(2, 1), (1, 1)]);
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Polling of asynchronous operations (e.g. worker updates) until they complete, with
+//! exponential backoff, jitter and an optional timeout.
+
+use crate::http_proxy::jitter;
+use crate::log::{log_action, LogColorize};
+use anyhow::bail;
+use std::future::Future;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Poller {
+    /// Delay before the second poll, doubled for every further poll
+    pub initial_interval: Duration,
+    pub max_interval: Duration,
+    /// No timeout if not set
+    pub timeout: Option<Duration>,
+}
+
+impl Poller {
+    pub const DEFAULT_INITIAL_INTERVAL: Duration = Duration::from_millis(500);
+    pub const DEFAULT_MAX_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self {
+            initial_interval: Self::DEFAULT_INITIAL_INTERVAL,
+            max_interval: Self::DEFAULT_MAX_INTERVAL,
+            timeout,
+        }
+    }
+
+    /// Uses the timeout only if the poller does not have one, for operations with a natural
+    /// deadline
+    pub fn with_default_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: self.timeout.or(Some(timeout)),
+            ..self
+        }
+    }
+
+    /// Exponential backoff with "equal jitter", see RetryPolicy::delay
+    pub fn interval(&self, attempt: u32, jitter: f64) -> Duration {
+        let interval = self
+            .initial_interval
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_interval);
+        interval / 2 + (interval / 2).mul_f64(jitter.clamp(0.0, 1.0))
+    }
+
+    /// Calls poll until it returns a ready result, logging the progress of waiting for the
+    /// subject (e.g. "update of worker x"), and fails if the timeout is reached
+    pub async fn poll<T, F, Fut>(&self, subject: &str, mut poll: F) -> anyhow::Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<Poll<T>>>,
+    {
+        let started_at = Instant::now();
+        let mut attempt = 0;
+        loop {
+            if let Poll::Ready(result) = poll().await? {
+                return Ok(result);
+            }
+
+            let elapsed = started_at.elapsed();
+            let mut interval = self.interval(attempt, jitter());
+            if let Some(timeout) = self.timeout {
+                if elapsed >= timeout {
+                    bail!(
+                        "Timed out waiting for {subject} after {}s",
+                        elapsed.as_secs()
+                    );
+                }
+                interval = interval.min(timeout - elapsed);
+            }
+
+            if attempt == 0 {
+                log_action("Waiting", format!("for {subject}"));
+            } else {
+                log_action(
+                    "Waiting",
+                    format!(
+                        "for {subject}, {} elapsed",
+                        format!("{}s", elapsed.as_secs()).log_color_highlight()
+                    ),
+                );
+            }
+
+            attempt += 1;
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::poll::Poller;
+    use assert2::assert;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    #[test]
+    fn poller_backs_off_and_times_out() {
+        let poller = Poller {
+            initial_interval: Duration::from_millis(100),
+            max_interval: Duration::from_millis(500),
+            timeout: None,
+        };
+
+        assert!(poller.interval(0, 1.0) == Duration::from_millis(100));
+        assert!(poller.interval(1, 1.0) == Duration::from_millis(200));
+        assert!(poller.interval(1, 0.0) == Duration::from_millis(100));
+        assert!(poller.interval(10, 1.0) == Duration::from_millis(500));
+
+        let poller = Poller {
+            initial_interval: Duration::from_millis(1),
+            max_interval: Duration::from_millis(5),
+            timeout: Some(Duration::from_millis(50)),
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        let mut polls = 0;
+        let result = runtime.block_on(poller.poll("readiness", || {
+            polls += 1;
+            let polls = polls;
+            async move {
+                Ok(if polls == 3 {
+                    Poll::Ready(polls)
+                } else {
+                    Poll::Pending
+                })
+            }
+        }));
+        assert!(result.unwrap() == 3);
+
+        let result =
+            runtime.block_on(poller.poll("readiness", || async { Ok(Poll::<()>::Pending) }));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server version negotiation: the version of the server is read on the first contact with the
+//! services of a profile (and cached for a day), so unsupported servers are reported with
+//! warnings, and features requiring newer servers fail with clear errors instead of failing
+//! on unexpected responses.
+
+use crate::config::ProfileName;
+use chrono::{DateTime, Utc};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use tracing::debug;
+use url::Url;
+
+pub const SERVER_VERSION_CACHE_DIR: &str = "server-versions";
+
+/// Oldest server version the CLI can work with, the newest supported version is the
+/// major and minor version of the CLI
+pub const MIN_SUPPORTED_SERVER_VERSION: Version = Version::new(1, 1, 0);
+
+/// Features which are only available with newer servers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerFeature {
+    WorkerRevert,
+    CancelInvocation,
+}
+
+impl ServerFeature {
+    pub fn min_server_version(&self) -> Version {
+        match self {
+            ServerFeature::WorkerRevert => Version::new(1, 2, 0),
+            ServerFeature::CancelInvocation => Version::new(1, 2, 0),
+        }
+    }
+}
+
+impl Display for ServerFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServerFeature::WorkerRevert => write!(f, "Reverting workers"),
+            ServerFeature::CancelInvocation => write!(f, "Canceling invocations"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerVersionCompatibility {
+    Supported,
+    TooOld,
+    TooNew,
+}
+
+/// Parses CLI and server versions, pre-release and build parts are ignored, as development
+/// builds are expected to be compatible with their release
+pub fn parse_version(version: &str) -> Option<Version> {
+    let version = Version::parse(version.trim_start_matches('v')).ok()?;
+    Some(Version::new(version.major, version.minor, version.patch))
+}
+
+/// The range of supported server versions, from MIN_SUPPORTED_SERVER_VERSION (inclusive) to
+/// the next minor version of the CLI (exclusive)
+pub fn supported_server_versions(cli_version: &Version) -> (Version, Version) {
+    (
+        MIN_SUPPORTED_SERVER_VERSION,
+        Version::new(cli_version.major, cli_version.minor + 1, 0),
+    )
+}
+
+pub fn server_version_compatibility(
+    cli_version: &Version,
+    server_version: &Version,
+) -> ServerVersionCompatibility {
+    let (min, max_exclusive) = supported_server_versions(cli_version);
+    if *server_version < min {
+        ServerVersionCompatibility::TooOld
+    } else if *server_version >= max_exclusive {
+        ServerVersionCompatibility::TooNew
+    } else {
+        ServerVersionCompatibility::Supported
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerVersionCacheEntry {
+    url: Url,
+    version: String,
+    checked_at: DateTime<Utc>,
+}
+
+/// Cache of the server versions per profile, failing to read or write it is not an error,
+/// the version is simply requested again
+pub struct ServerVersionCache {
+    dir: PathBuf,
+}
+
+impl ServerVersionCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, profile_name: &ProfileName) -> PathBuf {
+        self.dir.join(format!(
+            "{}.json",
+            blake3::hash(profile_name.0.as_bytes()).to_hex()
+        ))
+    }
+
+    /// Returns the cached version if it was requested from the same URL in the last day
+    pub fn get(&self, profile_name: &ProfileName, url: &Url) -> Option<String> {
+        let path = self.path(profile_name);
+        let content = std::fs::read(&path).ok()?;
+        let entry: ServerVersionCacheEntry = match serde_json::from_slice(&content) {
+            Ok(entry) => entry,
+            Err(err) => {
+                debug!(
+                    "Ignoring invalid server version cache entry {}: {}",
+                    path.display(),
+                    err
+                );
+                return None;
+            }
+        };
+
+        (entry.url == *url && Utc::now() - entry.checked_at < chrono::Duration::days(1))
+            .then_some(entry.version)
+    }
+
+    pub fn put(&self, profile_name: &ProfileName, url: &Url, version: &str) {
+        let entry = ServerVersionCacheEntry {
+            url: url.clone(),
+            version: version.to_string(),
+            checked_at: Utc::now(),
+        };
+
+        let path = self.path(profile_name);
+        let result = std::fs::create_dir_all(&self.dir)
+            .and_then(|()| std::fs::write(&path, serde_json::to_vec(&entry)?));
+        if let Err(err) = result {
+            debug!(
+                "Failed to write server version cache entry {}: {}",
+                path.display(),
+                err
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::config::ProfileName;
+    use crate::server_version::{
+        parse_version, server_version_compatibility, ServerVersionCache, ServerVersionCompatibility,
+    };
+    use assert2::assert;
+    use url::Url;
+
+    #[test]
+    fn server_version_compatibility_and_cache() {
+        let cli_version = parse_version("v1.3.0-dev.21").unwrap();
+        let compatibility = |server_version: &str| {
+            server_version_compatibility(&cli_version, &parse_version(server_version).unwrap())
+        };
+
+        assert!(compatibility("1.3.2") == ServerVersionCompatibility::Supported);
+        assert!(compatibility("1.2.0") == ServerVersionCompatibility::Supported);
+        assert!(compatibility("1.3.1-rc.1") == ServerVersionCompatibility::Supported);
+        assert!(compatibility("1.0.5") == ServerVersionCompatibility::TooOld);
+        assert!(compatibility("1.4.0") == ServerVersionCompatibility::TooNew);
+        assert!(compatibility("2.0.0") == ServerVersionCompatibility::TooNew);
+        assert!(parse_version("unknown").is_none());
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ServerVersionCache::new(dir.path().join("server-versions"));
+        let profile_name = ProfileName::from("local");
+        let url = Url::parse("http://localhost:9881").unwrap();
+        let other_url = Url::parse("http://localhost:9882").unwrap();
+
+        assert!(cache.get(&profile_name, &url).is_none());
+        cache.put(&profile_name, &url, "1.3.0");
+        assert!(cache.get(&profile_name, &url) == Some("1.3.0".to_string()));
+        assert!(cache.get(&profile_name, &other_url).is_none());
+        assert!(cache.get(&ProfileName::from("cloud"), &url).is_none());
+    }
+}
@@ -0,0 +1,338 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timings of command execution, collected from the tracing spans of the CLI.
+//!
+//! With --timings a breakdown by span name is printed to stderr at the end of the command.
+//! With --trace-file the spans are written in the Chrome trace event format, which can be
+//! opened with chrome://tracing or https://ui.perfetto.dev.
+
+use crate::model::text::fmt::{format_table, log_warn};
+use anyhow::Context as AnyhowContext;
+use cli_table::format::Justify;
+use cli_table::Table;
+use colored::Colorize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// Only the spans of the CLI are collected, not the ones of the dependencies
+const TIMINGS_TARGET: &str = "golem_cli";
+
+#[derive(Debug, Clone)]
+struct SpanTiming {
+    name: String,
+    fields: Vec<(String, String)>,
+    /// Relative to the start of the command
+    started_at: Duration,
+    duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct Timings {
+    started_at: Instant,
+    spans: Arc<Mutex<Vec<SpanTiming>>>,
+}
+
+impl Timings {
+    pub fn new(started_at: Instant) -> Self {
+        Self {
+            started_at,
+            spans: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Tracing layer collecting the closed spans of the CLI
+    pub fn layer<S>(&self) -> impl Layer<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        TimingsLayer {
+            timings: self.clone(),
+        }
+        .with_filter(filter_fn(|metadata| {
+            metadata.target().starts_with(TIMINGS_TARGET)
+        }))
+    }
+
+    /// Records a span which is not traced, e.g. the argument parsing, which happens before
+    /// tracing is initialized
+    pub fn record(&self, name: &str, started_at: Instant, ended_at: Instant) {
+        self.push(SpanTiming {
+            name: name.to_string(),
+            fields: Vec::new(),
+            started_at: started_at.saturating_duration_since(self.started_at),
+            duration: ended_at.saturating_duration_since(started_at),
+        });
+    }
+
+    fn push(&self, span: SpanTiming) {
+        self.spans.lock().unwrap().push(span);
+    }
+
+    /// Prints the summary and writes the trace file, if requested
+    pub fn report(&self, summary: bool, trace_file: Option<&Path>) {
+        if summary {
+            self.log_summary();
+        }
+        if let Some(trace_file) = trace_file {
+            if let Err(err) = self.write_chrome_trace(trace_file) {
+                log_warn(format!("{err:#}"));
+            }
+        }
+    }
+
+    /// Prints the total, count and max durations by span name to stderr
+    pub fn log_summary(&self) {
+        let elapsed = self.started_at.elapsed();
+
+        let mut summaries = Vec::<SpanSummary>::new();
+        let mut summary_indices = HashMap::<String, usize>::new();
+        for span in self.spans.lock().unwrap().iter() {
+            let idx = *summary_indices.entry(span.name.clone()).or_insert_with(|| {
+                summaries.push(SpanSummary {
+                    name: span.name.clone(),
+                    count: 0,
+                    total: Duration::ZERO,
+                    max: Duration::ZERO,
+                });
+                summaries.len() - 1
+            });
+            let summary = &mut summaries[idx];
+            summary.count += 1;
+            summary.total += span.duration;
+            summary.max = summary.max.max(span.duration);
+        }
+        summaries.sort_by(|a, b| b.total.cmp(&a.total));
+
+        eprintln!();
+        eprintln!("{}", "Timings:".bold());
+        eprintln!("{}", format_table::<_, SpanSummaryTableView>(&summaries));
+        eprintln!("{} {}", "Total:".bold(), format_duration(elapsed));
+    }
+
+    /// Writes the spans as Chrome trace events. Overlapping spans, e.g. concurrent HTTP
+    /// requests, are placed on separate threads of the trace, as the events of a thread
+    /// have to be nested.
+    pub fn write_chrome_trace(&self, path: &Path) -> anyhow::Result<()> {
+        let mut spans = self.spans.lock().unwrap().clone();
+        spans.sort_by(|a, b| {
+            a.started_at
+                .cmp(&b.started_at)
+                .then(b.duration.cmp(&a.duration))
+        });
+        let lanes = assign_lanes(&spans);
+
+        let events = spans
+            .iter()
+            .zip(lanes)
+            .map(|(span, lane)| {
+                json!({
+                    "name": span.name,
+                    "cat": "golem-cli",
+                    "ph": "X",
+                    "ts": span.started_at.as_micros() as u64,
+                    "dur": span.duration.as_micros() as u64,
+                    "pid": 1,
+                    "tid": lane + 1,
+                    "args": span.fields.iter().cloned().collect::<serde_json::Map<_, _>>(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create trace file {}", path.display()))?;
+        serde_json::to_writer(
+            BufWriter::new(file),
+            &json!({ "traceEvents": events, "displayTimeUnit": "ms" }),
+        )
+        .with_context(|| format!("Failed to write trace file {}", path.display()))?;
+
+        Ok(())
+    }
+}
+
+/// Assigns the spans (sorted by start) to lanes, where every span is either nested in the
+/// previous span of the lane, or starts after it ended
+fn assign_lanes(spans: &[SpanTiming]) -> Vec<usize> {
+    // The ends of the open spans of every lane
+    let mut lanes = Vec::<Vec<Duration>>::new();
+    spans
+        .iter()
+        .map(|span| {
+            let ended_at = span.started_at + span.duration;
+            for (idx, open_spans) in lanes.iter_mut().enumerate() {
+                while open_spans
+                    .last()
+                    .is_some_and(|open_span_end| *open_span_end <= span.started_at)
+                {
+                    open_spans.pop();
+                }
+                let nested = match open_spans.last() {
+                    Some(open_span_end) => ended_at <= *open_span_end,
+                    None => true,
+                };
+                if nested {
+                    open_spans.push(ended_at);
+                    return idx;
+                }
+            }
+            lanes.push(vec![ended_at]);
+            lanes.len() - 1
+        })
+        .collect()
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+struct SpanSummary {
+    name: String,
+    count: usize,
+    total: Duration,
+    max: Duration,
+}
+
+#[derive(Table)]
+struct SpanSummaryTableView {
+    #[table(title = "Span")]
+    pub name: String,
+    #[table(title = "Count", justify = "Justify::Right")]
+    pub count: usize,
+    #[table(title = "Total", justify = "Justify::Right")]
+    pub total: String,
+    #[table(title = "Max", justify = "Justify::Right")]
+    pub max: String,
+}
+
+impl From<&SpanSummary> for SpanSummaryTableView {
+    fn from(value: &SpanSummary) -> Self {
+        Self {
+            name: value.name.clone(),
+            count: value.count,
+            total: format_duration(value.total),
+            max: format_duration(value.max),
+        }
+    }
+}
+
+struct TimingsLayer {
+    timings: Timings,
+}
+
+struct OpenSpan {
+    started_at: Instant,
+    fields: Vec<(String, String)>,
+}
+
+impl<S> Layer<S> for TimingsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut fields = Vec::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        span.extensions_mut().insert(OpenSpan {
+            started_at: Instant::now(),
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        if let Some(open_span) = span.extensions_mut().get_mut::<OpenSpan>() {
+            values.record(&mut FieldVisitor(&mut open_span.fields));
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(open_span) = span.extensions_mut().remove::<OpenSpan>() else {
+            return;
+        };
+
+        self.timings.push(SpanTiming {
+            name: span.name().to_string(),
+            fields: open_span.fields,
+            started_at: open_span
+                .started_at
+                .saturating_duration_since(self.timings.started_at),
+            duration: open_span.started_at.elapsed(),
+        });
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{value:?}")));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use test_r::test;
+
+    use crate::timings::{assign_lanes, SpanTiming};
+    use assert2::assert;
+    use std::time::Duration;
+
+    #[test]
+    fn nested_spans_share_lanes_and_overlapping_ones_do_not() {
+        let span = |started_at: u64, duration: u64| SpanTiming {
+            name: "span".to_string(),
+            fields: Vec::new(),
+            started_at: Duration::from_millis(started_at),
+            duration: Duration::from_millis(duration),
+        };
+
+        let spans = vec![
+            span(0, 100),
+            span(10, 20),
+            span(15, 30),
+            span(20, 5),
+            span(50, 10),
+            span(200, 10),
+        ];
+
+        assert!(assign_lanes(&spans) == vec![0, 0, 1, 0, 0, 0]);
+    }
+}
@@ -14,8 +14,9 @@
 
 use crate::fs;
 use crate::fs::PathExtra;
-use crate::log::{log_action, log_warn_action, LogColorize};
+use crate::log::{log_action, log_warn_action, LogColorize, LogIndent};
 use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::provenance;
 use crate::wasm_rpc_stubgen::stub::StubDefinition;
 use crate::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
 use crate::wasm_rpc_stubgen::{
@@ -28,7 +29,7 @@ use cargo_toml::{
 };
 use heck::ToSnakeCase;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use toml::Value;
 use toml_edit::{DocumentMut, InlineTable};
@@ -59,16 +60,6 @@ fn default_path() -> String {
     "wit".to_string()
 }
 
-impl Default for ComponentTarget {
-    fn default() -> Self {
-        Self {
-            world: None,
-            path: "wit".to_string(),
-            dependencies: BTreeMap::new(),
-        }
-    }
-}
-
 #[derive(Serialize, Deserialize)]
 struct WitDependency {
     path: String,
@@ -238,7 +229,15 @@ pub fn generate_client_cargo_toml(def: &StubDefinition) -> anyhow::Result<()> {
             def.client_cargo_path().log_color_highlight()
         ),
     );
-    fs::write(def.client_cargo_path(), cargo_toml)?;
+    provenance::warn_if_edited([def.client_cargo_path()])?;
+    fs::write_str(
+        def.client_cargo_path(),
+        provenance::with_provenance_header(
+            &def.client_cargo_path(),
+            &provenance::stub_source_hash(def)?,
+            &cargo_toml,
+        ),
+    )?;
     Ok(())
 }
 
@@ -269,44 +268,231 @@ pub fn is_cargo_workspace_toml(path: &Path) -> anyhow::Result<bool> {
     }
 }
 
-pub fn add_cargo_package_component_deps(
+/// Returns the nearest Cargo.toml defining a workspace in the directory or in its ancestors
+pub fn find_cargo_workspace_toml(dir: &Path) -> anyhow::Result<Option<PathBuf>> {
+    for dir in dir.ancestors() {
+        let cargo_toml_path = dir.join(naming::rust::CARGO_TOML);
+        if !cargo_toml_path.is_file() {
+            continue;
+        }
+        if parse_cargo_toml_document(&cargo_toml_path)?.contains_key("workspace") {
+            return Ok(Some(cargo_toml_path));
+        }
+    }
+    Ok(None)
+}
+
+/// Turns a generated standalone client crate into a member of an existing Cargo workspace.
+///
+/// The crate is added to the workspace members, and its dependencies are added to the workspace
+/// dependencies (already defined ones are kept) and inherited from there. The sealing workspace
+/// and the profiles of the client crate are removed, as those are only allowed in the workspace
+/// root.
+pub fn add_client_crate_to_cargo_workspace(
+    workspace_cargo_toml_path: &Path,
+    client_cargo_toml_path: &Path,
+) -> anyhow::Result<()> {
+    let mut workspace_manifest = parse_cargo_toml_document(workspace_cargo_toml_path)?;
+    let mut client_manifest = parse_cargo_toml_document(client_cargo_toml_path)?;
+
+    let member = PathExtra::new(
+        PathExtra::new(PathExtra::new(client_cargo_toml_path).parent()?)
+            .strip_prefix(PathExtra::new(workspace_cargo_toml_path).parent()?)?,
+    )
+    .to_string()?
+    .replace('\\', "/");
+
+    let workspace = workspace_manifest
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.as_table_like_mut())
+        .ok_or_else(|| {
+            anyhow!(
+                "Expected table for workspace in {}",
+                workspace_cargo_toml_path.log_color_highlight()
+            )
+        })?;
+
+    let members = workspace
+        .entry("members")
+        .or_insert(toml_edit::value(toml_edit::Array::new()))
+        .as_array_mut()
+        .ok_or_else(|| {
+            anyhow!(
+                "Expected array for workspace.members in {}",
+                workspace_cargo_toml_path.log_color_highlight()
+            )
+        })?;
+    if !members
+        .iter()
+        .any(|existing| existing.as_str() == Some(member.as_str()))
+    {
+        members.push(member.as_str());
+    }
+
+    let workspace_dependencies = workspace
+        .entry("dependencies")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            anyhow!(
+                "Expected table for workspace.dependencies in {}",
+                workspace_cargo_toml_path.log_color_highlight()
+            )
+        })?;
+
+    if let Some(dependencies) = client_manifest
+        .get_mut("dependencies")
+        .and_then(|dependencies| dependencies.as_table_like_mut())
+    {
+        for (name, dependency) in dependencies.iter_mut() {
+            if !workspace_dependencies.contains_key(name.get()) {
+                let mut dependency = dependency.clone().into_value().map_err(|_| {
+                    anyhow!(
+                        "Unexpected format for dependency {} in {}",
+                        name.get().log_color_highlight(),
+                        client_cargo_toml_path.log_color_highlight()
+                    )
+                })?;
+                dependency.decor_mut().clear();
+                workspace_dependencies.insert(name.get(), toml_edit::value(dependency));
+            }
+
+            let mut inherited = InlineTable::new();
+            inherited.insert("workspace", true.into());
+            *dependency = toml_edit::value(inherited);
+        }
+    }
+
+    client_manifest.remove("workspace");
+    client_manifest.remove("profile");
+
+    log_action(
+        "Adding",
+        format!(
+            "{} to Cargo workspace {}",
+            member.log_color_highlight(),
+            workspace_cargo_toml_path.log_color_highlight()
+        ),
+    );
+    fs::write_str(
+        client_cargo_toml_path,
+        provenance::update_provenance_header(client_cargo_toml_path, &client_manifest.to_string()),
+    )?;
+    fs::write(workspace_cargo_toml_path, workspace_manifest.to_string())?;
+
+    Ok(())
+}
+
+fn parse_cargo_toml_document(path: &Path) -> anyhow::Result<DocumentMut> {
+    fs::read_to_string(path)?
+        .parse::<DocumentMut>()
+        .with_context(|| anyhow!("Failed to parse {}", path.log_color_highlight()))
+}
+
+/// Synchronizes the WIT dependencies of a cargo-component package with the given WIT packages,
+/// preserving the formatting and comments of the Cargo.toml.
+///
+/// Dependencies are keyed by package name without version, entries of the same package under
+/// a different key (e.g. with version) or pointing to the same path are deduplicated,
+/// and entries pointing to no longer existing paths are removed.
+pub fn sync_cargo_package_component_deps(
     cargo_toml_path: &Path,
     wit_sources: BTreeMap<PackageName, PathBuf>,
 ) -> anyhow::Result<()> {
-    let raw_manifest = fs::read_to_string(cargo_toml_path)?;
-    let mut manifest: Manifest<MetadataRoot> =
-        Manifest::from_slice_with_metadata(raw_manifest.as_bytes())?;
-    if let Some(ref mut package) = manifest.package {
-        if let Some(ref mut metadata) = package.metadata {
-            if let Some(ref mut component) = metadata.component {
-                let mut new_target = ComponentTarget::default();
-                let target = component.target.as_mut().unwrap_or(&mut new_target);
-                let existing: BTreeSet<_> = target.dependencies.keys().cloned().collect();
-                for (package_name, package_path) in wit_sources {
-                    let name = format_package_name_without_version(&package_name);
-                    if !existing.contains(&name) {
-                        target.dependencies.insert(
-                            name,
-                            WitDependency {
-                                path: package_path.to_string_lossy().to_string(),
-                            },
-                        );
-                    }
-                }
+    let project_root = PathExtra::new(cargo_toml_path).parent()?;
+    let mut manifest = parse_cargo_toml_document(cargo_toml_path)?;
+
+    let Some(component) = manifest
+        .get_mut("package")
+        .and_then(|package| package.get_mut("metadata"))
+        .and_then(|metadata| metadata.get_mut("component"))
+        .and_then(|component| component.as_table_like_mut())
+    else {
+        return Ok(());
+    };
 
-                if component.target.is_none() {
-                    component.target = Some(new_target);
-                }
+    let dependencies = component
+        .entry("target")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            anyhow!(
+                "Expected table for package.metadata.component.target in {}",
+                cargo_toml_path.log_color_highlight()
+            )
+        })?
+        .entry("dependencies")
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            anyhow!(
+                "Expected table for package.metadata.component.target.dependencies in {}",
+                cargo_toml_path.log_color_highlight()
+            )
+        })?;
+
+    let dependency_path = |dependency: &toml_edit::Item| {
+        dependency
+            .get("path")
+            .and_then(|path| path.as_str())
+            .map(|path| path.to_string())
+    };
+    let key_without_version = |key: &str| {
+        key.split_once('@')
+            .map(|(name, _)| name.to_string())
+            .unwrap_or_else(|| key.to_string())
+    };
 
-                let cargo_toml = toml::to_string(&manifest)?;
+    let mut changes = Vec::<String>::new();
+
+    let stale_keys = dependencies
+        .iter()
+        .filter(|(_, dependency)| {
+            dependency_path(*dependency).is_some_and(|path| !project_root.join(path).exists())
+        })
+        .map(|(key, _)| key.to_string())
+        .collect::<Vec<_>>();
+    for key in stale_keys {
+        dependencies.remove(&key);
+        changes.push(format!("removed stale dependency {key}"));
+    }
 
-                log_warn_action(
-                    "Updating",
-                    format!("Cargo.toml at {}", cargo_toml_path.log_color_highlight()),
-                );
-                fs::write(cargo_toml_path, cargo_toml)?;
-            }
+    for (package_name, package_path) in wit_sources {
+        let name = format_package_name_without_version(&package_name);
+        let path = package_path.to_string_lossy().replace('\\', "/");
+
+        let duplicate_keys = dependencies
+            .iter()
+            .filter(|(key, dependency)| {
+                *key != name
+                    && (key_without_version(*key) == name
+                        || dependency_path(*dependency).as_deref() == Some(path.as_str()))
+            })
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<_>>();
+        for key in duplicate_keys {
+            dependencies.remove(&key);
+            changes.push(format!("removed duplicated dependency {key}"));
+        }
+
+        if dependencies.get(&name).and_then(dependency_path).as_deref() != Some(path.as_str()) {
+            let mut dependency = InlineTable::new();
+            dependency.insert("path", path.as_str().into());
+            dependencies.insert(&name, toml_edit::value(dependency));
+            changes.push(format!("updated dependency {name}"));
+        }
+    }
+
+    if !changes.is_empty() {
+        log_warn_action(
+            "Updating",
+            format!("Cargo.toml at {}", cargo_toml_path.log_color_highlight()),
+        );
+        let _indent = LogIndent::new();
+        for change in changes {
+            log_action("Changed", change);
         }
+        fs::write(cargo_toml_path, manifest.to_string())?;
     }
 
     Ok(())
@@ -1,17 +1,31 @@
 use crate::fs;
 use crate::fs::PathExtra;
-use crate::log::{log_warn_action, LogColorize};
-use anyhow::Context;
+use crate::log::{log_action, log_warn_action, LogColorize};
+use anyhow::{anyhow, bail, Context};
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::{Path, PathBuf};
 use wac_graph::types::{Package, SubtypeChecker};
 use wac_graph::{CompositionGraph, EncodeOptions, PackageId, PlugError};
+use wit_component::ComponentEncoder;
 
+pub const WASI_PREVIEW1_ADAPTER_NAME: &str = "wasi_snapshot_preview1";
+
+/// The final composition graph: the socket imports satisfied by the plugs,
+/// and the imports left for the host
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Composition {
+    pub plugged_imports: BTreeMap<String, String>,
+    pub host_imports: BTreeSet<String>,
+}
+
+/// Composes the source component with the plugs, core WASM modules are turned into a component
+/// first using the WASI preview1 adapter
 pub async fn compose(
     source_wasm: &Path,
     stub_wasms: &[PathBuf],
     dest_wasm: &Path,
-) -> anyhow::Result<()> {
+    wasi_adapter: Option<&Path>,
+) -> anyhow::Result<Composition> {
     // Based on https://github.com/bytecodealliance/wac/blob/release-0.6.0/src/commands/plug.rs
     // with allowing missing plugs (through the also customized plug function below)
     // and using local packages only (for now)
@@ -21,6 +35,38 @@ pub async fn compose(
     let mut graph = CompositionGraph::new();
 
     let socket = fs::read(source_wasm).context("Failed to read socket component")?;
+    let socket = if wasmparser::Parser::is_core_wasm(&socket) {
+        let Some(wasi_adapter) = wasi_adapter else {
+            bail!(
+                "{} is a core WASM module, a WASI preview1 adapter is required for turning it into a component",
+                source_wasm.log_color_error_highlight()
+            );
+        };
+        log_action(
+            "Adapting",
+            format!(
+                "core WASM module {} with WASI adapter {}",
+                source_wasm.log_color_highlight(),
+                wasi_adapter.log_color_highlight()
+            ),
+        );
+        ComponentEncoder::default()
+            .validate(true)
+            .module(&socket)?
+            .adapter(
+                WASI_PREVIEW1_ADAPTER_NAME,
+                &fs::read(wasi_adapter).context("Failed to read WASI adapter")?,
+            )?
+            .encode()
+            .with_context(|| {
+                anyhow!(
+                    "Failed to turn core WASM module {} into a component",
+                    source_wasm.log_color_highlight()
+                )
+            })?
+    } else {
+        socket
+    };
 
     let socket = Package::from_bytes("socket", None, socket, graph.types_mut())?;
     let socket = graph.register_package(socket)?;
@@ -37,14 +83,14 @@ pub async fn compose(
         plug_packages.push((stub_wasm.to_string_lossy().to_string(), package_id));
     }
 
-    plug(&mut graph, plug_packages, socket)?;
+    let composition = plug(&mut graph, plug_packages, socket)?;
 
     let bytes = graph.encode(EncodeOptions::default())?;
 
     fs::create_dir_all(dest_wasm.parent()?)?;
     fs::write(dest_wasm, bytes)?;
 
-    Ok(())
+    Ok(composition)
 }
 
 // Based on https://github.com/bytecodealliance/wac/blob/release-0.6.0/crates/wac-graph/src/plug.rs#L23
@@ -53,7 +99,7 @@ fn plug(
     graph: &mut CompositionGraph,
     plugs: Vec<(String, PackageId)>,
     socket: PackageId,
-) -> Result<(), PlugError> {
+) -> Result<Composition, PlugError> {
     let socket_instantiation = graph.instantiate(socket);
 
     let mut requested_plugs = BTreeSet::<String>::new();
@@ -92,17 +138,22 @@ fn plug(
         }
     }
 
+    let mut plugged_imports = BTreeMap::<String, String>::new();
     let unused_plugs = {
         for (plug_export_name, _) in graph.get_instantiation_arguments(socket_instantiation) {
-            plug_exports_to_plug
-                .remove(plug_export_name)
-                .iter()
-                .for_each(|plug_name| {
-                    requested_plugs.remove(plug_name);
-                });
+            if let Some(plug_name) = plug_exports_to_plug.remove(plug_export_name) {
+                requested_plugs.remove(&plug_name);
+                plugged_imports.insert(plug_export_name.to_string(), plug_name);
+            }
         }
         requested_plugs
     };
+    let host_imports = graph.types()[graph[socket].ty()]
+        .imports
+        .keys()
+        .filter(|name| !plugged_imports.contains_key(*name))
+        .cloned()
+        .collect();
 
     for plug_name in unused_plugs {
         log_warn_action(
@@ -127,5 +178,8 @@ fn plug(
             .map_err(|err| PlugError::GraphError { source: err.into() })?;
     }
 
-    Ok(())
+    Ok(Composition {
+        plugged_imports,
+        host_imports,
+    })
 }
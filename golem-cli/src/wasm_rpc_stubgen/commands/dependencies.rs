@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::wasm_rpc_stubgen::naming::wit::ClientNaming;
 use crate::wasm_rpc_stubgen::wit_generate::{
-    add_client_as_dependency_to_wit_dir, AddClientAsDepConfig, UpdateCargoToml,
+    add_client_as_dependency_to_wit_dir, add_wit_package_as_dependency_to_wit_dir,
+    AddClientAsDepConfig, PackageRename, UpdateCargoToml,
 };
 use std::path::Path;
 
@@ -21,10 +23,18 @@ pub fn add_stub_dependency(
     stub_wit_root: &Path,
     dest_wit_root: &Path,
     update_cargo_toml: UpdateCargoToml,
+    package_renames: Vec<PackageRename>,
+    client_naming: ClientNaming,
 ) -> anyhow::Result<()> {
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_wit_root.to_path_buf(),
         dest_wit_root: dest_wit_root.to_path_buf(),
         update_cargo_toml,
+        package_renames,
+        client_naming,
     })
 }
+
+pub fn add_wit_dependency(source_wit_dir: &Path, dest_wit_root: &Path) -> anyhow::Result<()> {
+    add_wit_package_as_dependency_to_wit_dir(source_wit_dir, dest_wit_root)
+}
@@ -14,11 +14,14 @@
 
 use crate::fs;
 use crate::log::{log_action, LogColorize, LogIndent};
+use crate::model::app::ClientLanguage;
 use crate::wasm_rpc_stubgen::cargo::generate_client_cargo_toml;
 use crate::wasm_rpc_stubgen::compilation::compile;
+use crate::wasm_rpc_stubgen::go::{generate_client_go_glue, generate_client_go_mod};
 use crate::wasm_rpc_stubgen::naming;
 use crate::wasm_rpc_stubgen::rust::generate_stub_source;
 use crate::wasm_rpc_stubgen::stub::StubDefinition;
+use crate::wasm_rpc_stubgen::typescript::generate_client_ts_bindings;
 use crate::wasm_rpc_stubgen::wit_generate::{
     add_dependencies_to_stub_wit_dir, generate_client_wit_to_target,
 };
@@ -35,6 +38,28 @@ pub fn generate_client(stub_def: &StubDefinition) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn generate_client_for_language(
+    stub_def: &StubDefinition,
+    language: ClientLanguage,
+) -> anyhow::Result<()> {
+    match language {
+        ClientLanguage::Rust => generate_client(stub_def),
+        ClientLanguage::Ts => {
+            let _ = generate_client_wit_dir(stub_def)?;
+            generate_client_ts_bindings(stub_def)
+                .context("Failed to generate the client TypeScript bindings")?;
+            Ok(())
+        }
+        ClientLanguage::Go => {
+            let _ = generate_client_wit_dir(stub_def)?;
+            generate_client_go_mod(stub_def).context("Failed to generate the go.mod file")?;
+            generate_client_go_glue(stub_def)
+                .context("Failed to generate the wit-bindgen-go glue")?;
+            Ok(())
+        }
+    }
+}
+
 pub async fn build(
     stub_def: &StubDefinition,
     dest_wasm: &Path,
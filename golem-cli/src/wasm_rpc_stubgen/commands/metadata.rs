@@ -13,18 +13,44 @@
 // limitations under the License.
 
 use crate::fs::{create_dir_all, PathExtra};
-use crate::wasm_metadata::{AddMetadata, AddMetadataField};
+use crate::wasm_metadata::{AddMetadata, AddMetadataField, Producers};
 use anyhow::Context;
 use std::fs;
 use std::path::Path;
 use wit_parser::PackageName;
 
+/// Name of the producers field holding the build metadata
+pub const BUILD_METADATA_PRODUCERS_FIELD: &str = "golem-build";
+
+/// Build information embedded into the producers section of the final component, so it is
+/// also available in the metadata of the deployed component
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildMetadata {
+    pub git_commit: Option<String>,
+    pub build_time: Option<String>,
+    pub app_version: Option<String>,
+}
+
+impl BuildMetadata {
+    pub fn entries(&self) -> Vec<(&'static str, &str)> {
+        [
+            ("git-commit", &self.git_commit),
+            ("build-time", &self.build_time),
+            ("app-version", &self.app_version),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| value.as_deref().map(|value| (name, value)))
+        .collect()
+    }
+}
+
 /// Writes a name and a version metadata section based on the provided `root_package-name`
-/// to the WASM read from `source`, saving the result to `target`
+/// to the WASM read from `source`, together with the build metadata, saving the result to `target`
 #[allow(clippy::field_reassign_with_default)]
 pub fn add_metadata(
     source: &impl AsRef<Path>,
     root_package_name: PackageName,
+    build_metadata: &BuildMetadata,
     target: &impl AsRef<Path>,
 ) -> anyhow::Result<()> {
     let wasm = fs::read(source)
@@ -39,11 +65,28 @@ pub fn add_metadata(
         None => AddMetadataField::Clear,
         Some(v) => AddMetadataField::Set(crate::wasm_metadata::Version::new(v.to_string())),
     };
+    if let Some(git_commit) = &build_metadata.git_commit {
+        metadata.revision =
+            AddMetadataField::Set(crate::wasm_metadata::Revision::new(git_commit.clone()));
+    }
 
     let updated_wasm = metadata
         .to_wasm(&wasm)
         .context("Adding name and version metadata to the linked WASM")?;
 
+    let build_metadata_entries = build_metadata.entries();
+    let updated_wasm = if build_metadata_entries.is_empty() {
+        updated_wasm
+    } else {
+        let mut producers = Producers::empty();
+        for (name, value) in build_metadata_entries {
+            producers.add(BUILD_METADATA_PRODUCERS_FIELD, name, value);
+        }
+        producers
+            .add_to_wasm(&updated_wasm)
+            .context("Adding build metadata to the linked WASM")?
+    };
+
     create_dir_all(PathExtra::new(target).parent()?)
         .with_context(|| format!("Failed to create target dir for {:?}", target.as_ref()))?;
 
@@ -51,3 +94,47 @@ pub fn add_metadata(
         .with_context(|| format!("Writing final linked WASM to {:?}", target.as_ref()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use crate::wasm_metadata::Producers;
+    use crate::wasm_rpc_stubgen::commands::metadata::{
+        add_metadata, BuildMetadata, BUILD_METADATA_PRODUCERS_FIELD,
+    };
+    use assert2::{assert, let_assert};
+    use test_r::test;
+    use wit_parser::PackageName;
+
+    #[test]
+    fn add_metadata_embeds_build_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("source.wasm");
+        let target = temp_dir.path().join("target.wasm");
+        std::fs::write(&source, b"\0asm\x01\0\0\0").unwrap();
+
+        let build_metadata = BuildMetadata {
+            git_commit: Some("0123456789abcdef".to_string()),
+            build_time: None,
+            app_version: Some("1.0.0".to_string()),
+        };
+        let_assert!(
+            Ok(()) = add_metadata(
+                &source,
+                PackageName {
+                    namespace: "test".to_string(),
+                    name: "component".to_string(),
+                    version: None,
+                },
+                &build_metadata,
+                &target,
+            )
+        );
+
+        let wasm = std::fs::read(&target).unwrap();
+        let_assert!(Ok(Some(producers)) = Producers::from_wasm(&wasm));
+        let_assert!(Some(field) = producers.get(BUILD_METADATA_PRODUCERS_FIELD));
+        assert!(field.get("git-commit") == Some(&"0123456789abcdef".to_string()));
+        assert!(field.get("app-version") == Some(&"1.0.0".to_string()));
+        assert!(field.get("build-time").is_none());
+    }
+}
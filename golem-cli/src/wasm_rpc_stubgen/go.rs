@@ -0,0 +1,73 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::fs;
+use crate::log::{log_action, LogColorize};
+use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::stub::StubDefinition;
+use crate::wasm_rpc_stubgen::{GO_CM_VERSION, GO_VERSION, WIT_BINDGEN_GO_VERSION};
+
+pub fn generate_client_go_mod(def: &StubDefinition) -> anyhow::Result<()> {
+    let go_mod_path = def.client_go_mod_path();
+
+    log_action(
+        "Generating",
+        format!("go.mod to {}", go_mod_path.log_color_highlight()),
+    );
+
+    fs::write_str(
+        go_mod_path,
+        format!(
+            r#"module {module_name}
+
+go {GO_VERSION}
+
+require go.bytecodealliance.org/cm v{GO_CM_VERSION}
+
+tool go.bytecodealliance.org/cmd/wit-bindgen-go
+
+require go.bytecodealliance.org v{WIT_BINDGEN_GO_VERSION} // indirect
+"#,
+            module_name = def.client_go_module_name(),
+        ),
+    )
+}
+
+pub fn generate_client_go_glue(def: &StubDefinition) -> anyhow::Result<()> {
+    let glue_path = def.client_go_glue_path();
+
+    log_action(
+        "Generating",
+        format!("wit-bindgen-go glue to {}", glue_path.log_color_highlight()),
+    );
+
+    fs::write_str(
+        glue_path,
+        format!(
+            r#"// Code generated by golem-cli. DO NOT EDIT.
+
+// Package {package_name} contains the WASM RPC client bindings for the {world_name} world,
+// use "go generate" to (re)generate the bindings with wit-bindgen-go.
+package {package_name}
+
+//go:generate go tool wit-bindgen-go generate --world {client_world_name} --out {bindings_dir} ./{wit_dir}
+"#,
+            package_name = def.client_go_package_name(),
+            world_name = def.source_world_name(),
+            client_world_name = def.client_world_name(),
+            bindings_dir = naming::go::BINDINGS_DIR,
+            wit_dir = naming::wit::WIT_DIR,
+        ),
+    )
+}
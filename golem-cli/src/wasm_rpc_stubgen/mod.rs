@@ -15,9 +15,14 @@
 pub mod cargo;
 pub mod commands;
 pub mod compilation;
+pub mod go;
 pub mod naming;
+pub mod provenance;
+pub mod python;
 pub mod rust;
 pub mod stub;
+pub mod test_component;
+pub mod typescript;
 pub mod wit_encode;
 pub mod wit_generate;
 pub mod wit_resolve;
@@ -26,3 +31,6 @@ pub const WIT_BINDGEN_VERSION: &str = "0.40.0";
 pub const WASI_WIT_VERSION: &str = "0.2.3";
 pub const GOLEM_RPC_WIT_VERSION: &str = "0.2.2";
 pub const GOLEM_API_VERSION: &str = "1.1.7";
+pub const GO_VERSION: &str = "1.24";
+pub const GO_CM_VERSION: &str = "0.2.2";
+pub const WIT_BINDGEN_GO_VERSION: &str = "0.6.2";
@@ -7,31 +7,54 @@ pub mod wit {
     pub static DEPS_DIR: &str = "deps";
     pub static WIT_DIR: &str = "wit";
 
-    pub static CLIENT_WIT_FILE_NAME: &str = "client.wit";
+    pub static DEFAULT_CLIENT_SUFFIX: &str = "-client";
+    pub static DEFAULT_CLIENT_WIT_FILE_NAME: &str = "client.wit";
     pub static EXPORTS_WIT_FILE_NAME: &str = "exports.wit";
 
+    /// Naming scheme of the generated client packages, crates, modules and WIT files, can be
+    /// customized in the application manifest using stubSuffix and stubFileName
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ClientNaming {
+        pub suffix: String,
+        pub wit_file_name: String,
+    }
+
+    impl Default for ClientNaming {
+        fn default() -> Self {
+            Self {
+                suffix: DEFAULT_CLIENT_SUFFIX.to_string(),
+                wit_file_name: DEFAULT_CLIENT_WIT_FILE_NAME.to_string(),
+            }
+        }
+    }
+
     pub fn client_parser_package_name(
         package_name: &wit_parser::PackageName,
+        client_naming: &ClientNaming,
     ) -> wit_parser::PackageName {
         wit_parser::PackageName {
             namespace: package_name.namespace.clone(),
-            name: format!("{}-client", package_name.name),
+            name: format!("{}{}", package_name.name, client_naming.suffix),
             version: package_name.version.clone(),
         }
     }
 
     pub fn client_encoder_package_name(
         package_name: &wit_parser::PackageName,
+        client_naming: &ClientNaming,
     ) -> wit_encoder::PackageName {
         wit_encoder::PackageName::new(
             package_name.namespace.clone(),
-            format!("{}-client", package_name.name),
+            format!("{}{}", package_name.name, client_naming.suffix),
             package_name.version.clone(),
         )
     }
 
-    pub fn client_interface_name(source_world: &wit_parser::World) -> String {
-        format!("{}-client", source_world.name)
+    pub fn client_interface_name(
+        source_world: &wit_parser::World,
+        client_naming: &ClientNaming,
+    ) -> String {
+        format!("{}{}", source_world.name, client_naming.suffix)
     }
 
     pub fn exports_parser_package_name(
@@ -69,12 +92,13 @@ pub mod wit {
 
     pub fn client_target_package_name(
         client_package_name: &wit_parser::PackageName,
+        client_naming: &ClientNaming,
     ) -> wit_parser::PackageName {
         wit_parser::PackageName {
             namespace: client_package_name.namespace.clone(),
             name: client_package_name
                 .name
-                .strip_suffix("-client")
+                .strip_suffix(&client_naming.suffix)
                 .expect("Unexpected client package name")
                 .to_string(),
             version: client_package_name.version.clone(),
@@ -108,6 +132,7 @@ pub mod wit {
 
     pub fn client_import_exports_prefix_from_client_package_name(
         client_package: &wit_parser::PackageName,
+        client_naming: &ClientNaming,
     ) -> anyhow::Result<String> {
         Ok(format!(
             "{}:{}-exports/",
@@ -115,9 +140,10 @@ pub mod wit {
             client_package
                 .name
                 .clone()
-                .strip_suffix("-client")
+                .strip_suffix(&client_naming.suffix)
                 .ok_or_else(|| anyhow!(
-                    "Expected \"-client\" suffix in client package name: {}",
+                    "Expected \"{}\" suffix in client package name: {}",
+                    client_naming.suffix,
                     client_package.to_string()
                 ))?
         ))
@@ -156,7 +182,59 @@ pub mod wit {
     }
 }
 
+pub mod go {
+    use crate::wasm_rpc_stubgen::naming::wit::ClientNaming;
+    use heck::ToSnakeCase;
+
+    pub static GO_MOD: &str = "go.mod";
+    pub static GENERATE_GO: &str = "generate.go";
+    pub static BINDINGS_DIR: &str = "binding";
+
+    pub fn client_module_name(
+        source_world: &wit_parser::World,
+        client_naming: &ClientNaming,
+    ) -> String {
+        format!("{}{}", source_world.name, client_naming.suffix)
+    }
+
+    pub fn client_package_name(
+        source_world: &wit_parser::World,
+        client_naming: &ClientNaming,
+    ) -> String {
+        format!("{}{}", source_world.name, client_naming.suffix).to_snake_case()
+    }
+}
+
+pub mod python {
+    pub static PYPROJECT_TOML: &str = "pyproject.toml";
+    pub static COMPONENTIZE_PY_TOML: &str = "componentize-py.toml";
+}
+
+pub mod typescript {
+    use std::path::PathBuf;
+
+    pub static INTERFACES_DIR: &str = "interfaces";
+
+    pub fn client_module_name(
+        client_package_name: &wit_parser::PackageName,
+        client_interface_name: &str,
+    ) -> String {
+        client_package_name.interface_id(client_interface_name)
+    }
+
+    pub fn client_dts_path(
+        client_package_name: &wit_parser::PackageName,
+        client_interface_name: &str,
+    ) -> PathBuf {
+        PathBuf::from(INTERFACES_DIR).join(format!(
+            "{}-{}-{}.d.ts",
+            client_package_name.namespace, client_package_name.name, client_interface_name
+        ))
+    }
+}
+
 pub mod rust {
+    use crate::wasm_rpc_stubgen::naming::wit::ClientNaming;
     use crate::wasm_rpc_stubgen::stub::{FunctionStub, StubbedEntity};
     use heck::{ToSnakeCase, ToUpperCamelCase};
     use proc_macro2::{Ident, Span};
@@ -169,16 +247,25 @@ pub mod rust {
         source_package_name.namespace.to_snake_case()
     }
 
-    pub fn client_root_name(source_package_name: &wit_parser::PackageName) -> String {
-        format!("{}_client", source_package_name.name.to_snake_case())
+    pub fn client_root_name(
+        source_package_name: &wit_parser::PackageName,
+        client_naming: &ClientNaming,
+    ) -> String {
+        format!("{}{}", source_package_name.name, client_naming.suffix).to_snake_case()
     }
 
-    pub fn client_crate_name(source_world: &wit_parser::World) -> String {
-        format!("{}-client", source_world.name)
+    pub fn client_crate_name(
+        source_world: &wit_parser::World,
+        client_naming: &ClientNaming,
+    ) -> String {
+        format!("{}{}", source_world.name, client_naming.suffix)
     }
 
-    pub fn client_interface_name(source_world: &wit_parser::World) -> String {
-        to_rust_ident(&format!("{}-client", source_world.name)).to_snake_case()
+    pub fn client_interface_name(
+        source_world: &wit_parser::World,
+        client_naming: &ClientNaming,
+    ) -> String {
+        to_rust_ident(&format!("{}{}", source_world.name, client_naming.suffix)).to_snake_case()
     }
 
     pub fn client_world_name(source_world: &wit_parser::World) -> String {
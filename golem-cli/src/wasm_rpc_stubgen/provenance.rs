@@ -0,0 +1,239 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provenance headers of generated files.
+//!
+//! Generated files start with a comment line recording the generator version, the hash of the
+//! sources the file was generated from, and the hash of the generated content, which allows
+//! detecting manual edits made after generation.
+
+use crate::fs;
+use crate::log::{log_warn_action, LogColorize};
+use crate::wasm_rpc_stubgen::stub::StubDefinition;
+use anyhow::{anyhow, Context};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const PROVENANCE_MARKER: &str = "@generated by golem-cli";
+const SOURCE_HASH_LABEL: &str = "source hash: ";
+const CONTENT_HASH_LABEL: &str = "content hash: ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub version: String,
+    pub source_hash: String,
+    pub content_hash: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratedFileState {
+    NotGenerated,
+    Unchanged,
+    Edited,
+}
+
+/// Line comment prefix for the file types which get provenance headers
+fn comment_prefix(path: &Path) -> Option<&'static str> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("wit") | Some("rs") => Some("//"),
+        Some("toml") => Some("#"),
+        _ => None,
+    }
+}
+
+fn hash(content: &str) -> String {
+    blake3::hash(content.as_bytes()).to_hex().to_string()
+}
+
+/// Splits the content to the provenance header and the generated content
+fn split_header(content: &str) -> (Option<&str>, &str) {
+    match content.split_once('\n') {
+        Some((first_line, rest)) if first_line.contains(PROVENANCE_MARKER) => {
+            (Some(first_line), rest)
+        }
+        None if content.contains(PROVENANCE_MARKER) => (Some(content), ""),
+        _ => (None, content),
+    }
+}
+
+pub fn parse_provenance_header(content: &str) -> Option<Provenance> {
+    let (header, _) = split_header(content);
+    let header = header?;
+    let (_, rest) = header.split_once(PROVENANCE_MARKER)?;
+    let mut parts = rest.split(',').map(|part| part.trim());
+    let version = parts.next()?.to_string();
+    let source_hash = parts.next()?.strip_prefix(SOURCE_HASH_LABEL)?.to_string();
+    let content_hash = parts.next()?.strip_prefix(CONTENT_HASH_LABEL)?.to_string();
+    Some(Provenance {
+        version,
+        source_hash,
+        content_hash,
+    })
+}
+
+/// Returns the content without the provenance header
+pub fn strip_provenance_header(content: &str) -> &str {
+    split_header(content).1
+}
+
+/// Returns the source hash of the content, ignoring its provenance header, so copying generated
+/// files keeps the same source hash
+pub fn content_source_hash(content: &str) -> String {
+    hash(strip_provenance_header(content))
+}
+
+/// Hashes the source WIT files of the stubbed package
+pub fn stub_source_hash(def: &StubDefinition) -> anyhow::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    for (package_id, _, sources) in def.packages_with_wit_sources() {
+        if package_id != def.source_package_id {
+            continue;
+        }
+        for source in &sources.files {
+            hasher.update(strip_provenance_header(&fs::read_to_string(source)?).as_bytes());
+        }
+    }
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// Adds or replaces the provenance header, file types without line comments are left unchanged
+pub fn with_provenance_header(path: &Path, source_hash: &str, content: &str) -> String {
+    let content = strip_provenance_header(content);
+    match comment_prefix(path) {
+        Some(prefix) => format!(
+            "{prefix} {PROVENANCE_MARKER} {}, {SOURCE_HASH_LABEL}{source_hash}, {CONTENT_HASH_LABEL}{}\n{content}",
+            golem_common::golem_version(),
+            hash(content)
+        ),
+        None => content.to_string(),
+    }
+}
+
+/// Updates the content hash of a generated file which was updated by a generator step, keeping
+/// the original source hash
+pub fn update_provenance_header(path: &Path, content: &str) -> String {
+    match parse_provenance_header(content) {
+        Some(provenance) => with_provenance_header(path, &provenance.source_hash, content),
+        None => content.to_string(),
+    }
+}
+
+pub fn generated_file_state(content: &str) -> GeneratedFileState {
+    match parse_provenance_header(content) {
+        Some(provenance) => {
+            if provenance.content_hash == hash(strip_provenance_header(content)) {
+                GeneratedFileState::Unchanged
+            } else {
+                GeneratedFileState::Edited
+            }
+        }
+        None => GeneratedFileState::NotGenerated,
+    }
+}
+
+fn path_generated_file_state(path: &Path) -> anyhow::Result<GeneratedFileState> {
+    if comment_prefix(path).is_none() || !path.is_file() {
+        return Ok(GeneratedFileState::NotGenerated);
+    }
+    // Non UTF-8 files cannot have provenance headers
+    match std::fs::read(path) {
+        Ok(content) => Ok(String::from_utf8(content)
+            .map(|content| generated_file_state(&content))
+            .unwrap_or(GeneratedFileState::NotGenerated)),
+        Err(err) => Err(anyhow!(err))
+            .with_context(|| anyhow!("Failed to read {}", path.log_color_highlight())),
+    }
+}
+
+/// Warns about generated files which were manually edited and are going to be overwritten
+pub fn warn_if_edited<P: AsRef<Path>>(targets: impl IntoIterator<Item = P>) -> anyhow::Result<()> {
+    for target in targets {
+        let target = target.as_ref();
+        if path_generated_file_state(target)? == GeneratedFileState::Edited {
+            log_warn_action(
+                "Overwriting",
+                format!(
+                    "manually edited generated file {}",
+                    target.log_color_highlight()
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Collects the generated files in the directories, and the ones which were manually edited
+pub fn find_generated_files(
+    dirs: impl IntoIterator<Item = PathBuf>,
+) -> anyhow::Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    let mut generated = Vec::new();
+    let mut edited = Vec::new();
+    for dir in dirs {
+        if !dir.exists() {
+            continue;
+        }
+        for entry in WalkDir::new(&dir).sort_by_file_name() {
+            let entry = entry.with_context(|| {
+                anyhow!("Failed to read directory {}", dir.log_color_highlight())
+            })?;
+            match path_generated_file_state(entry.path())? {
+                GeneratedFileState::NotGenerated => {}
+                GeneratedFileState::Unchanged => generated.push(entry.path().to_path_buf()),
+                GeneratedFileState::Edited => {
+                    generated.push(entry.path().to_path_buf());
+                    edited.push(entry.path().to_path_buf());
+                }
+            }
+        }
+    }
+    Ok((generated, edited))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wasm_rpc_stubgen::provenance::{
+        generated_file_state, parse_provenance_header, strip_provenance_header,
+        update_provenance_header, with_provenance_header, GeneratedFileState,
+    };
+    use assert2::{assert, let_assert};
+    use std::path::Path;
+    use test_r::test;
+
+    #[test]
+    fn provenance_header_roundtrip_and_edit_detection() {
+        let path = Path::new("client.wit");
+        let content = "package test:client;\n";
+
+        let generated = with_provenance_header(path, "abc", content);
+        assert!(generated.starts_with("// @generated by golem-cli "));
+        assert!(strip_provenance_header(&generated) == content);
+        assert!(with_provenance_header(path, "abc", &generated) == generated);
+        assert!(generated_file_state(&generated) == GeneratedFileState::Unchanged);
+        assert!(generated_file_state(content) == GeneratedFileState::NotGenerated);
+
+        let_assert!(Some(provenance) = parse_provenance_header(&generated));
+        assert!(provenance.source_hash == "abc");
+
+        let edited = format!("{generated}interface api {{}}\n");
+        assert!(generated_file_state(&edited) == GeneratedFileState::Edited);
+        assert!(
+            generated_file_state(&update_provenance_header(path, &edited))
+                == GeneratedFileState::Unchanged
+        );
+
+        let cargo_toml = with_provenance_header(Path::new("Cargo.toml"), "abc", "[package]\n");
+        assert!(cargo_toml.starts_with("# @generated by golem-cli "));
+        assert!(with_provenance_header(Path::new("client.wasm"), "abc", "x") == "x");
+    }
+}
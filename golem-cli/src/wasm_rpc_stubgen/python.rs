@@ -0,0 +1,91 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::fs;
+use crate::fs::PathExtra;
+use crate::log::{log_warn_action, LogColorize};
+use crate::wasm_rpc_stubgen::naming;
+use anyhow::{anyhow, Context};
+use heck::ToSnakeCase;
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+pub fn is_python_project(project_root: &Path) -> bool {
+    project_root.join(naming::python::PYPROJECT_TOML).is_file()
+}
+
+/// Registers the client interface import in the componentize-py config of the project,
+/// so the generated Python bindings use a stable module name for the client
+pub fn add_componentize_py_client_deps(
+    project_root: &Path,
+    wit_path: &Path,
+    client_import_name: &str,
+    client_interface_name: &str,
+) -> anyhow::Result<()> {
+    let config_path = PathExtra::new(project_root.join(naming::python::COMPONENTIZE_PY_TOML));
+
+    let relative_wit_path = wit_path.strip_prefix(project_root).with_context(|| {
+        anyhow!(
+            "Failed to create relative path for wit dir: {}, project root: {}",
+            wit_path.log_color_highlight(),
+            project_root.log_color_highlight()
+        )
+    })?;
+
+    let mut config = if config_path.exists() {
+        fs::read_to_string(&config_path)?
+            .parse::<DocumentMut>()
+            .with_context(|| {
+                anyhow!(
+                    "Failed to parse componentize-py config: {}",
+                    config_path.log_color_highlight()
+                )
+            })?
+    } else {
+        DocumentMut::new()
+    };
+
+    if !config.contains_key("wit_directory") {
+        config["wit_directory"] = toml_edit::value(relative_wit_path.to_string_lossy().to_string());
+    }
+
+    let import_interface_names = config["import_interface_names"]
+        .or_insert(toml_edit::table())
+        .as_table_like_mut()
+        .ok_or_else(|| {
+            anyhow!(
+                "Expected table for import_interface_names in {}",
+                config_path.log_color_highlight()
+            )
+        })?;
+
+    if import_interface_names.contains_key(client_import_name) {
+        return Ok(());
+    }
+    import_interface_names.insert(
+        client_import_name,
+        toml_edit::value(client_interface_name.to_snake_case()),
+    );
+
+    log_warn_action(
+        "Updating",
+        format!(
+            "componentize-py config at {}",
+            config_path.log_color_highlight()
+        ),
+    );
+    fs::write_str(&config_path, config.to_string())?;
+
+    Ok(())
+}
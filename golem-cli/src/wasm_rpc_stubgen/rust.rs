@@ -16,6 +16,7 @@ use crate::fs;
 use crate::fs::PathExtra;
 use crate::log::{log_action, LogColorize};
 use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::provenance;
 use crate::wasm_rpc_stubgen::stub::{FunctionResultStub, FunctionStub, StubDefinition};
 use crate::wasm_rpc_stubgen::{GOLEM_RPC_WIT_VERSION, WASI_WIT_VERSION};
 use anyhow::anyhow;
@@ -216,7 +217,9 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
             }
         }
 
-        let constructor = if entity.is_resource() {
+        let constructor = if !entity.has_constructor() {
+            quote! {}
+        } else if entity.is_resource() {
             let constructor_stub = FunctionStub {
                 name: "new".to_string(),
                 params: entity.constructor_params(),
@@ -352,7 +355,15 @@ pub fn generate_stub_source(def: &StubDefinition) -> anyhow::Result<()> {
     );
     println!("target: {:?}", target_rust_path.as_path());
     fs::create_dir_all(target_rust_path.parent()?)?;
-    fs::write(def.client_rust_path(), src)?;
+    provenance::warn_if_edited([def.client_rust_path()])?;
+    fs::write_str(
+        def.client_rust_path(),
+        provenance::with_provenance_header(
+            &def.client_rust_path(),
+            &provenance::stub_source_hash(def)?,
+            &src,
+        ),
+    )?;
     Ok(())
 }
 
@@ -406,7 +417,9 @@ fn generate_function_stub_source(
         params.push(quote! {&self});
     }
 
-    if mode == FunctionMode::Constructor && !def.config.is_ephemeral {
+    if (mode == FunctionMode::Constructor || mode == FunctionMode::Static)
+        && !def.config.is_ephemeral
+    {
         params.push(quote! { wasm_rpc_worker_name: String });
     } else if mode == FunctionMode::CustomConstructor {
         if def.config.is_ephemeral {
@@ -453,31 +466,7 @@ fn generate_function_stub_source(
     );
 
     let rpc = match mode {
-        FunctionMode::Static => {
-            let first_param = function
-                .params
-                .first()
-                .ok_or(anyhow!("static function has no params"))?;
-            let first_param_ident =
-                Ident::new(&to_rust_ident(&first_param.name), Span::call_site());
-
-            let type_id = match &first_param.typ {
-                Type::Id(type_id) => {
-                    let type_def = def.get_type_def(*type_id)?;
-
-                    match &type_def.kind {
-                        TypeDefKind::Handle(Handle::Borrow(type_id)) => Ok(*type_id),
-                        TypeDefKind::Handle(Handle::Own(type_id)) => Ok(*type_id),
-                        _ => Err(anyhow!("first parameter of static method is not a handle")),
-                    }
-                }
-                _ => Err(anyhow!("first parameter of static method is not a handle")),
-            }?;
-            let first_param_type = resource_type_ident(def.get_type_def(type_id)?)?;
-
-            quote! { #first_param_ident.get::<#first_param_type>().rpc }
-        }
-        FunctionMode::Constructor | FunctionMode::CustomConstructor => {
+        FunctionMode::Static | FunctionMode::Constructor | FunctionMode::CustomConstructor => {
             quote! { rpc }
         }
         _ => {
@@ -485,8 +474,10 @@ fn generate_function_stub_source(
         }
     };
 
+    // Static functions are not bound to a remote resource, so they select the target worker
+    // the same way as the default constructor
     let component_name = def.config.component_name.as_str();
-    let init = if mode == FunctionMode::Constructor {
+    let init = if mode == FunctionMode::Constructor || mode == FunctionMode::Static {
         if def.config.is_ephemeral {
             quote! {
                 let component_name = #component_name;
@@ -737,7 +728,7 @@ fn get_remote_function_name(entity: &StubbedEntity, function_name: &str) -> Stri
     }
 }
 
-fn type_to_rust_ident(typ: &Type, def: &StubDefinition) -> anyhow::Result<TokenStream> {
+pub fn type_to_rust_ident(typ: &Type, def: &StubDefinition) -> anyhow::Result<TokenStream> {
     match typ {
         Type::Bool => Ok(quote! { bool }),
         Type::U8 => Ok(quote! { u8 }),
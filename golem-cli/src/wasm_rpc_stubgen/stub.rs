@@ -41,6 +41,7 @@ pub struct StubConfig {
     pub seal_cargo_workspace: bool,
     pub component_name: AppComponentName,
     pub is_ephemeral: bool,
+    pub client_naming: naming::wit::ClientNaming,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -137,11 +138,17 @@ impl StubDefinition {
     }
 
     pub fn client_parser_package_name(&self) -> PackageName {
-        naming::wit::client_parser_package_name(&self.source_package_name)
+        naming::wit::client_parser_package_name(
+            &self.source_package_name,
+            &self.config.client_naming,
+        )
     }
 
     pub fn client_encoder_package_name(&self) -> wit_encoder::PackageName {
-        naming::wit::client_encoder_package_name(&self.source_package_name)
+        naming::wit::client_encoder_package_name(
+            &self.source_package_name,
+            &self.config.client_naming,
+        )
     }
 
     pub fn source_world(&self) -> &World {
@@ -164,12 +171,12 @@ impl StubDefinition {
     }
 
     pub fn client_crate_name(&self) -> String {
-        naming::rust::client_crate_name(self.source_world())
+        naming::rust::client_crate_name(self.source_world(), &self.config.client_naming)
     }
 
     pub fn rust_client_interface_name(&self) -> proc_macro2::Ident {
         proc_macro2::Ident::new(
-            &naming::rust::client_interface_name(self.source_world()),
+            &naming::rust::client_interface_name(self.source_world(), &self.config.client_naming),
             Span::call_site(),
         )
     }
@@ -183,7 +190,7 @@ impl StubDefinition {
 
     pub fn rust_client_root_name(&self) -> proc_macro2::Ident {
         proc_macro2::Ident::new(
-            &naming::rust::client_root_name(&self.source_package_name),
+            &naming::rust::client_root_name(&self.source_package_name, &self.config.client_naming),
             Span::call_site(),
         )
     }
@@ -193,7 +200,39 @@ impl StubDefinition {
     }
 
     pub fn client_interface_name(&self) -> String {
-        naming::wit::client_interface_name(self.source_world())
+        naming::wit::client_interface_name(self.source_world(), &self.config.client_naming)
+    }
+
+    pub fn client_go_mod_path(&self) -> PathBuf {
+        self.config.client_root.join(naming::go::GO_MOD)
+    }
+
+    pub fn client_go_glue_path(&self) -> PathBuf {
+        self.config.client_root.join(naming::go::GENERATE_GO)
+    }
+
+    pub fn client_go_module_name(&self) -> String {
+        naming::go::client_module_name(self.source_world(), &self.config.client_naming)
+    }
+
+    pub fn client_go_package_name(&self) -> String {
+        naming::go::client_package_name(self.source_world(), &self.config.client_naming)
+    }
+
+    pub fn client_ts_module_name(&self) -> String {
+        naming::typescript::client_module_name(
+            &self.client_parser_package_name(),
+            &self.client_interface_name(),
+        )
+    }
+
+    pub fn client_ts_path(&self) -> PathBuf {
+        self.config
+            .client_root
+            .join(naming::typescript::client_dts_path(
+                &self.client_parser_package_name(),
+                &self.client_interface_name(),
+            ))
     }
 
     pub fn client_world_name(&self) -> String {
@@ -206,7 +245,7 @@ impl StubDefinition {
 
     pub fn client_wit_path(&self) -> PathBuf {
         self.client_wit_root()
-            .join(naming::wit::CLIENT_WIT_FILE_NAME)
+            .join(&self.config.client_naming.wit_file_name)
     }
 
     pub fn resolve_client_wit(&self) -> anyhow::Result<ResolvedWitDir> {
@@ -713,6 +752,15 @@ impl StubbedEntity {
         }
     }
 
+    /// Resources without a constructor can only be obtained as results of other functions
+    pub fn has_constructor(&self) -> bool {
+        match self {
+            Self::WorldFunctions(_) => true,
+            Self::Interface(_) => true,
+            Self::Resource(inner) => inner.constructor_params.is_some(),
+        }
+    }
+
     pub fn functions(&self) -> impl Iterator<Item = &FunctionStub> {
         match self {
             Self::WorldFunctions(inner) => inner.functions.iter(),
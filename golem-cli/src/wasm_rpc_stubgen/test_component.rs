@@ -0,0 +1,443 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sources of generated Rust test components.
+//!
+//! A test component depends on the client of its target, and exports a single function which
+//! invokes every function of the target that can be called with default-valued arguments.
+
+use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::rust::type_to_rust_ident;
+use crate::wasm_rpc_stubgen::stub::{FunctionStub, StubDefinition, StubbedEntity};
+use anyhow::{anyhow, bail};
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use wit_bindgen_rust::to_rust_ident;
+use wit_parser::{PackageName, Type, TypeDefKind};
+
+pub const TEST_INTERFACE_NAME: &str = "smoke-test";
+pub const TEST_FUNCTION_NAME: &str = "run";
+
+pub struct TestComponentSource {
+    pub wit: String,
+    pub lib_rs: String,
+    /// Functions of the target which cannot be called with default values, with the reason
+    pub skipped_functions: Vec<(String, String)>,
+}
+
+/// Parses a test component name in 'namespace:name' form as WIT package name
+pub fn test_package_name(component_name: &str) -> anyhow::Result<PackageName> {
+    let Some((namespace, name)) = component_name.split_once(':') else {
+        bail!("Invalid test component name {component_name}, expected 'namespace:name' form");
+    };
+    Ok(PackageName {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        version: None,
+    })
+}
+
+/// Fully qualified name of the exported test function, usable for invocation
+pub fn test_function_name(test_package_name: &PackageName) -> String {
+    format!(
+        "{}.{{{}}}",
+        naming::wit::exports_parser_package_name(test_package_name)
+            .interface_id(TEST_INTERFACE_NAME),
+        TEST_FUNCTION_NAME
+    )
+}
+
+pub fn generate_test_component(
+    def: &StubDefinition,
+    test_package_name: &PackageName,
+) -> anyhow::Result<TestComponentSource> {
+    let mut skipped_functions = Vec::new();
+    let lib_rs = generate_lib_rs(def, test_package_name, &mut skipped_functions)?;
+
+    Ok(TestComponentSource {
+        wit: generate_wit(def, test_package_name),
+        lib_rs,
+        skipped_functions,
+    })
+}
+
+fn generate_wit(def: &StubDefinition, test_package_name: &PackageName) -> String {
+    let target = def.client_parser_package_name();
+    let target = naming::wit::client_target_package_name(&target, &def.config.client_naming);
+
+    format!(
+        r#"package {test_package_name};
+
+/// Smoke test of {target}, generated by golem-cli
+interface {TEST_INTERFACE_NAME} {{
+  /// Invokes the functions of the target worker with default arguments, and returns the number of invocations
+  {TEST_FUNCTION_NAME}: func(target-worker-name: string) -> u32;
+}}
+
+world {} {{
+  export {TEST_INTERFACE_NAME};
+}}
+"#,
+        test_package_name.name
+    )
+}
+
+fn generate_lib_rs(
+    def: &StubDefinition,
+    test_package_name: &PackageName,
+    skipped_functions: &mut Vec<(String, String)>,
+) -> anyhow::Result<String> {
+    let test_ns = Ident::new(
+        &naming::rust::root_namespace(test_package_name),
+        Span::call_site(),
+    );
+    let test_exports = Ident::new(
+        &format!("{}_exports", test_package_name.name.to_snake_case()),
+        Span::call_site(),
+    );
+    let test_interface = Ident::new(&to_rust_ident(TEST_INTERFACE_NAME), Span::call_site());
+    let test_function = Ident::new(&to_rust_ident(TEST_FUNCTION_NAME), Span::call_site());
+
+    let worker_name = if def.config.is_ephemeral {
+        Ident::new("_target_worker_name", Span::call_site())
+    } else {
+        Ident::new("target_worker_name", Span::call_site())
+    };
+
+    let mut entity_calls = Vec::new();
+    for entity in def.stubbed_entities() {
+        entity_calls.push(generate_entity_calls(
+            def,
+            entity,
+            &worker_name,
+            skipped_functions,
+        )?);
+    }
+
+    let lib = quote! {
+        #[allow(static_mut_refs)]
+        mod bindings;
+
+        use crate::bindings::exports::#test_ns::#test_exports::#test_interface::Guest;
+
+        struct Component;
+
+        impl Guest for Component {
+            fn #test_function(#worker_name: String) -> u32 {
+                let mut invocations = 0;
+                #(#entity_calls)*
+                invocations
+            }
+        }
+
+        bindings::export!(Component with_types_in bindings);
+    };
+
+    let syntax_tree = syn::parse2(lib)?;
+    Ok(prettyplease::unparse(&syntax_tree))
+}
+
+fn generate_entity_calls(
+    def: &StubDefinition,
+    entity: &StubbedEntity,
+    worker_name: &Ident,
+    skipped_functions: &mut Vec<(String, String)>,
+) -> anyhow::Result<TokenStream> {
+    let root_ns = def.rust_root_namespace();
+    let root_name = def.rust_client_root_name();
+    let client_interface_name = def.rust_client_interface_name();
+    let entity_ident = Ident::new(
+        &to_rust_ident(entity.name()).to_upper_camel_case(),
+        Span::call_site(),
+    );
+    let entity_type =
+        quote! { crate::bindings::#root_ns::#root_name::#client_interface_name::#entity_ident };
+
+    let worker_name_arg = (!def.config.is_ephemeral).then(|| quote! { &#worker_name });
+
+    let mut calls = Vec::new();
+
+    for function in entity.static_functions() {
+        match function_args(def, function) {
+            Ok(args) => {
+                let function_ident = blocking_function_ident(function);
+                let args = worker_name_arg.iter().cloned().chain(args);
+                calls.push(quote! {
+                    let _ = #entity_type::#function_ident(#(#args),*);
+                    invocations += 1;
+                });
+            }
+            Err(err) => skipped_functions.push((
+                format!("{}.{}", entity.name(), function.name),
+                err.to_string(),
+            )),
+        }
+    }
+
+    let constructor_args = if entity.has_constructor() {
+        entity
+            .constructor_params()
+            .iter()
+            .map(|param| param_default(def, &param.typ))
+            .collect::<anyhow::Result<Vec<_>>>()
+    } else {
+        Err(anyhow!("resource has no constructor"))
+    };
+
+    match constructor_args {
+        Ok(constructor_args) => {
+            let mut method_calls = Vec::new();
+            for function in entity.functions() {
+                match function_args(def, function) {
+                    Ok(args) => {
+                        let function_ident = blocking_function_ident(function);
+                        method_calls.push(quote! {
+                            let _ = instance.#function_ident(#(#args),*);
+                            invocations += 1;
+                        });
+                    }
+                    Err(err) => skipped_functions.push((
+                        format!("{}.{}", entity.name(), function.name),
+                        err.to_string(),
+                    )),
+                }
+            }
+
+            if !method_calls.is_empty() {
+                let args = worker_name_arg.into_iter().chain(constructor_args);
+                calls.push(quote! {
+                    {
+                        let instance = #entity_type::new(#(#args),*);
+                        #(#method_calls)*
+                    }
+                });
+            }
+        }
+        Err(err) => {
+            for function in entity.functions() {
+                skipped_functions.push((
+                    format!("{}.{}", entity.name(), function.name),
+                    err.to_string(),
+                ));
+            }
+        }
+    }
+
+    Ok(quote! { #(#calls)* })
+}
+
+fn blocking_function_ident(function: &FunctionStub) -> Ident {
+    Ident::new(
+        &to_rust_ident(&naming::wit::blocking_function_name(function)),
+        Span::call_site(),
+    )
+}
+
+fn function_args(
+    def: &StubDefinition,
+    function: &FunctionStub,
+) -> anyhow::Result<Vec<TokenStream>> {
+    function
+        .params
+        .iter()
+        .map(|param| param_default(def, &param.typ))
+        .collect()
+}
+
+/// Default value in the form wit-bindgen expects for parameters of imported functions,
+/// where strings, lists and non-copy types are borrowed
+fn param_default(def: &StubDefinition, typ: &Type) -> anyhow::Result<TokenStream> {
+    match typ {
+        Type::String => Ok(quote! { "" }),
+        Type::Id(type_id) => match &def.get_type_def(*type_id)?.kind {
+            TypeDefKind::List(_) => Ok(quote! { &[] }),
+            TypeDefKind::Option(inner) => {
+                param_default(def, inner)?;
+                Ok(quote! { None })
+            }
+            TypeDefKind::Result(result) => match &result.ok {
+                Some(ok) => {
+                    let ok = param_default(def, ok)?;
+                    Ok(quote! { Ok(#ok) })
+                }
+                None => Ok(quote! { Ok(()) }),
+            },
+            TypeDefKind::Tuple(tuple) => {
+                let types = tuple
+                    .types
+                    .iter()
+                    .map(|typ| param_default(def, typ))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok(quote! { (#(#types,)*) })
+            }
+            TypeDefKind::Type(inner) => param_default(def, inner),
+            TypeDefKind::Record(_) | TypeDefKind::Variant(_) if has_owned_data(def, typ)? => {
+                let value = owned_default(def, typ)?;
+                Ok(quote! { &#value })
+            }
+            _ => owned_default(def, typ),
+        },
+        _ => owned_default(def, typ),
+    }
+}
+
+fn owned_default(def: &StubDefinition, typ: &Type) -> anyhow::Result<TokenStream> {
+    match typ {
+        Type::Bool => Ok(quote! { false }),
+        Type::U8
+        | Type::U16
+        | Type::U32
+        | Type::U64
+        | Type::S8
+        | Type::S16
+        | Type::S32
+        | Type::S64 => Ok(quote! { 0 }),
+        Type::F32 | Type::F64 => Ok(quote! { 0.0 }),
+        Type::Char => Ok(quote! { 'a' }),
+        Type::String => Ok(quote! { String::new() }),
+        Type::ErrorContext => bail!("error-context parameters are not supported"),
+        Type::Id(type_id) => match &def.get_type_def(*type_id)?.kind {
+            TypeDefKind::Record(record) => {
+                let type_path = type_to_rust_ident(typ, def)?;
+                let fields = record
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let field_ident =
+                            Ident::new(&to_rust_ident(&field.name), Span::call_site());
+                        let value = owned_default(def, &field.ty)?;
+                        Ok(quote! { #field_ident: #value })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok(quote! { #type_path { #(#fields),* } })
+            }
+            TypeDefKind::Variant(variant) => {
+                let type_path = type_to_rust_ident(typ, def)?;
+                let case = variant
+                    .cases
+                    .first()
+                    .ok_or_else(|| anyhow!("variant has no cases"))?;
+                let case_ident = Ident::new(
+                    &to_rust_ident(&case.name).to_upper_camel_case(),
+                    Span::call_site(),
+                );
+                match &case.ty {
+                    Some(case_typ) => {
+                        let value = owned_default(def, case_typ)?;
+                        Ok(quote! { #type_path::#case_ident(#value) })
+                    }
+                    None => Ok(quote! { #type_path::#case_ident }),
+                }
+            }
+            TypeDefKind::Enum(enum_def) => {
+                let type_path = type_to_rust_ident(typ, def)?;
+                let case = enum_def
+                    .cases
+                    .first()
+                    .ok_or_else(|| anyhow!("enum has no cases"))?;
+                let case_ident = Ident::new(&case.name.to_upper_camel_case(), Span::call_site());
+                Ok(quote! { #type_path::#case_ident })
+            }
+            TypeDefKind::Flags(_) => {
+                let type_path = type_to_rust_ident(typ, def)?;
+                Ok(quote! { #type_path::empty() })
+            }
+            TypeDefKind::Tuple(tuple) => {
+                let types = tuple
+                    .types
+                    .iter()
+                    .map(|typ| owned_default(def, typ))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                Ok(quote! { (#(#types,)*) })
+            }
+            TypeDefKind::Option(inner) => {
+                owned_default(def, inner)?;
+                Ok(quote! { None })
+            }
+            TypeDefKind::Result(result) => match &result.ok {
+                Some(ok) => {
+                    let ok = owned_default(def, ok)?;
+                    Ok(quote! { Ok(#ok) })
+                }
+                None => Ok(quote! { Ok(()) }),
+            },
+            TypeDefKind::List(_) => Ok(quote! { Vec::new() }),
+            TypeDefKind::Type(inner) => owned_default(def, inner),
+            TypeDefKind::Handle(_) | TypeDefKind::Resource => {
+                bail!("resource handle parameters are not supported")
+            }
+            _ => bail!("parameter type is not supported"),
+        },
+    }
+}
+
+/// Types with strings, lists or handles in them are not copy types in the generated bindings
+fn has_owned_data(def: &StubDefinition, typ: &Type) -> anyhow::Result<bool> {
+    match typ {
+        Type::String => Ok(true),
+        Type::Id(type_id) => match &def.get_type_def(*type_id)?.kind {
+            TypeDefKind::Record(record) => {
+                for field in &record.fields {
+                    if has_owned_data(def, &field.ty)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            TypeDefKind::Variant(variant) => {
+                for case_typ in variant.cases.iter().filter_map(|case| case.ty.as_ref()) {
+                    if has_owned_data(def, case_typ)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            TypeDefKind::Tuple(tuple) => {
+                for typ in &tuple.types {
+                    if has_owned_data(def, typ)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            TypeDefKind::Option(inner) | TypeDefKind::Type(inner) => has_owned_data(def, inner),
+            TypeDefKind::Result(result) => {
+                for typ in result.ok.iter().chain(result.err.iter()) {
+                    if has_owned_data(def, typ)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            TypeDefKind::Enum(_) | TypeDefKind::Flags(_) => Ok(false),
+            _ => Ok(true),
+        },
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wasm_rpc_stubgen::test_component::{test_function_name, test_package_name};
+    use assert2::assert;
+    use test_r::test;
+
+    #[test]
+    fn test_function_name_uses_exports_package() {
+        let package_name = test_package_name("app:counter-test").unwrap();
+        assert!(test_function_name(&package_name) == "app:counter-test-exports/smoke-test.{run}");
+        assert!(test_package_name("counter-test").is_err());
+    }
+}
@@ -0,0 +1,485 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TypeScript declarations for the generated client interface, following the conventions of
+//! `jco types` (camelCase members, tagged unions for variants, exceptions for result errors).
+
+use crate::fs;
+use crate::log::{log_action, LogColorize};
+use crate::wasm_rpc_stubgen::naming;
+use crate::wasm_rpc_stubgen::stub::{
+    FunctionParamStub, FunctionResultStub, FunctionStub, StubDefinition, StubbedEntity,
+};
+use anyhow::{anyhow, bail};
+use heck::{ToLowerCamelCase, ToUpperCamelCase};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use wit_parser::{Handle, Type, TypeDefKind, TypeId};
+
+static COMMON_TYPES: &str = r#"
+export interface Uuid {
+  highBits: bigint,
+  lowBits: bigint,
+}
+export interface ComponentId {
+  uuid: Uuid,
+}
+export interface WorkerId {
+  componentId: ComponentId,
+  workerName: string,
+}
+export interface Datetime {
+  seconds: bigint,
+  nanoseconds: number,
+}
+export class Pollable {
+  ready(): boolean;
+  block(): void;
+}
+export class CancellationToken {
+  cancel(): void;
+}
+export type Result<T, E> = { tag: 'ok', val: T } | { tag: 'err', val: E };
+"#;
+
+pub fn generate_client_ts_bindings(def: &StubDefinition) -> anyhow::Result<()> {
+    let src = generate_client_ts_source(def)?;
+
+    let target_ts_path = def.client_ts_path();
+
+    log_action(
+        "Generating",
+        format!(
+            "TypeScript client bindings to {}",
+            target_ts_path.log_color_highlight()
+        ),
+    );
+    fs::write_str(target_ts_path, src)?;
+    Ok(())
+}
+
+pub fn generate_client_ts_source(def: &StubDefinition) -> anyhow::Result<String> {
+    let module_name = def.client_ts_module_name();
+    let namespace_name = module_name_to_namespace(&module_name);
+
+    let mut src = String::new();
+    writeln!(src, "/** @module Interface {module_name} **/")?;
+
+    let entity_classes = def
+        .stubbed_entities()
+        .iter()
+        .map(|entity| ts_type_name(entity.name()))
+        .collect::<Vec<_>>();
+    writeln!(src, "export namespace {namespace_name} {{")?;
+    for class_name in &entity_classes {
+        writeln!(src, "  export {{ {class_name} }};")?;
+    }
+    writeln!(src, "}}")?;
+    writeln!(src)?;
+
+    // Types used from the golem and WASI interfaces
+    write!(src, "{}", COMMON_TYPES.trim_start())?;
+
+    // Type definitions used by the stubbed functions, including the transitively used ones
+    for type_id in collect_named_types(def)? {
+        write_type_def(def, &mut src, type_id)?;
+    }
+
+    // Async result wrappers
+    for entity in def.stubbed_entities() {
+        for (function, _) in entity.all_functions() {
+            if function.results.is_empty() {
+                continue;
+            }
+            let result_type = match &function.results {
+                FunctionResultStub::Anon(typ) => ts_type(def, typ)?,
+                FunctionResultStub::Unit => {
+                    bail!("Unit result is not supported as async stub result")
+                }
+                FunctionResultStub::SelfType => bail!("Unexpected self return type"),
+            };
+            writeln!(
+                src,
+                "\nexport class {} {{\n  subscribe(): Pollable;\n  get(): {} | undefined;\n}}",
+                ts_type_name(&function.async_result_type(entity)),
+                result_type
+            )?;
+        }
+    }
+
+    // Client classes
+    for entity in def.stubbed_entities() {
+        write_entity_class(def, &mut src, entity)?;
+    }
+
+    Ok(src)
+}
+
+fn write_entity_class(
+    def: &StubDefinition,
+    src: &mut String,
+    entity: &StubbedEntity,
+) -> anyhow::Result<()> {
+    let class_name = ts_type_name(entity.name());
+    let constructor_params = entity.constructor_params();
+
+    writeln!(src, "\nexport class {class_name} {{")?;
+
+    if entity.has_constructor() {
+        let mut params = Vec::new();
+        if !def.config.is_ephemeral {
+            params.push("workerName: string".to_string());
+        }
+        params.extend(ts_params(def, &constructor_params)?);
+        writeln!(src, "  constructor({});", params.join(", "))?;
+
+        let mut params = vec![if def.config.is_ephemeral {
+            "componentId: ComponentId".to_string()
+        } else {
+            "workerId: WorkerId".to_string()
+        }];
+        params.extend(ts_params(def, &constructor_params)?);
+        writeln!(src, "  static custom({}): {class_name};", params.join(", "))?;
+    }
+
+    for (function, is_static) in entity.all_functions() {
+        let modifier = if is_static { "static " } else { "" };
+        let mut params = Vec::new();
+        if is_static && !def.config.is_ephemeral {
+            params.push("workerName: string".to_string());
+        }
+        params.extend(ts_params(def, &function.params)?);
+
+        writeln!(
+            src,
+            "  {modifier}{}({}): {};",
+            naming::wit::blocking_function_name(function).to_lower_camel_case(),
+            params.join(", "),
+            ts_blocking_result_type(def, function)?
+        )?;
+
+        writeln!(
+            src,
+            "  {modifier}{}({}): {};",
+            function.name.to_lower_camel_case(),
+            params.join(", "),
+            if function.results.is_empty() {
+                "void".to_string()
+            } else {
+                ts_type_name(&function.async_result_type(entity))
+            }
+        )?;
+
+        let mut schedule_params = params;
+        schedule_params.push("scheduledFor: Datetime".to_string());
+        writeln!(
+            src,
+            "  {modifier}{}({}): CancellationToken;",
+            naming::wit::schedule_function_name(function).to_lower_camel_case(),
+            schedule_params.join(", ")
+        )?;
+    }
+
+    writeln!(src, "}}")?;
+
+    Ok(())
+}
+
+fn write_type_def(def: &StubDefinition, src: &mut String, type_id: TypeId) -> anyhow::Result<()> {
+    let type_def = def.get_type_def(type_id)?;
+    let type_name = ts_named_type_name(def, type_id)?;
+
+    match &type_def.kind {
+        TypeDefKind::Record(record) => {
+            writeln!(src, "export interface {type_name} {{")?;
+            for field in &record.fields {
+                writeln!(
+                    src,
+                    "  {}: {},",
+                    field.name.to_lower_camel_case(),
+                    ts_type(def, &field.ty)?
+                )?;
+            }
+            writeln!(src, "}}")?;
+        }
+        TypeDefKind::Flags(flags) => {
+            writeln!(src, "export interface {type_name} {{")?;
+            for flag in &flags.flags {
+                writeln!(src, "  {}?: boolean,", flag.name.to_lower_camel_case())?;
+            }
+            writeln!(src, "}}")?;
+        }
+        TypeDefKind::Enum(enum_) => {
+            writeln!(
+                src,
+                "export type {type_name} = {};",
+                enum_
+                    .cases
+                    .iter()
+                    .map(|case| format!("'{}'", case.name))
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            )?;
+        }
+        TypeDefKind::Variant(variant) => {
+            let case_type_names = variant
+                .cases
+                .iter()
+                .map(|case| format!("{type_name}{}", case.name.to_upper_camel_case()))
+                .collect::<Vec<_>>();
+            writeln!(
+                src,
+                "export type {type_name} = {};",
+                case_type_names.join(" | ")
+            )?;
+            for (case, case_type_name) in variant.cases.iter().zip(case_type_names) {
+                writeln!(src, "export interface {case_type_name} {{")?;
+                writeln!(src, "  tag: '{}',", case.name)?;
+                if let Some(typ) = &case.ty {
+                    writeln!(src, "  val: {},", ts_type(def, typ)?)?;
+                }
+                writeln!(src, "}}")?;
+            }
+        }
+        TypeDefKind::Resource => {
+            writeln!(src, "export class {type_name} {{\n}}")?;
+        }
+        _ => {
+            writeln!(
+                src,
+                "export type {type_name} = {};",
+                ts_type_def_kind(def, &type_def.kind)?
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn ts_params(def: &StubDefinition, params: &[FunctionParamStub]) -> anyhow::Result<Vec<String>> {
+    params
+        .iter()
+        .map(|param| {
+            Ok(format!(
+                "{}: {}",
+                param.name.to_lower_camel_case(),
+                ts_type(def, &param.typ)?
+            ))
+        })
+        .collect()
+}
+
+// Follows jco, where a top-level result return type is mapped to the ok value, and errors are thrown
+fn ts_blocking_result_type(
+    def: &StubDefinition,
+    function: &FunctionStub,
+) -> anyhow::Result<String> {
+    match &function.results {
+        FunctionResultStub::Unit => Ok("void".to_string()),
+        FunctionResultStub::SelfType => bail!("Unexpected self return type"),
+        FunctionResultStub::Anon(typ) => {
+            if let Type::Id(type_id) = typ {
+                let type_def = def.get_type_def(*type_id)?;
+                if type_def.name.is_none() {
+                    if let TypeDefKind::Result(result) = &type_def.kind {
+                        return match &result.ok {
+                            Some(ok) => ts_type(def, ok),
+                            None => Ok("void".to_string()),
+                        };
+                    }
+                }
+            }
+            ts_type(def, typ)
+        }
+    }
+}
+
+fn ts_type(def: &StubDefinition, typ: &Type) -> anyhow::Result<String> {
+    match typ {
+        Type::Bool => Ok("boolean".to_string()),
+        Type::U8 | Type::U16 | Type::U32 | Type::S8 | Type::S16 | Type::S32 => {
+            Ok("number".to_string())
+        }
+        Type::U64 | Type::S64 => Ok("bigint".to_string()),
+        Type::F32 | Type::F64 => Ok("number".to_string()),
+        Type::Char | Type::String => Ok("string".to_string()),
+        Type::Id(type_id) => {
+            let type_def = def.get_type_def(*type_id)?;
+            if type_def.name.is_some() {
+                ts_named_type_name(def, *type_id)
+            } else {
+                ts_type_def_kind(def, &type_def.kind)
+            }
+        }
+        _ => Err(anyhow!("Unsupported type for TypeScript bindings: {typ:?}")),
+    }
+}
+
+fn ts_type_def_kind(def: &StubDefinition, kind: &TypeDefKind) -> anyhow::Result<String> {
+    match kind {
+        TypeDefKind::Type(typ) => ts_type(def, typ),
+        TypeDefKind::List(typ) => Ok(match typ {
+            Type::U8 => "Uint8Array".to_string(),
+            Type::S8 => "Int8Array".to_string(),
+            Type::U16 => "Uint16Array".to_string(),
+            Type::S16 => "Int16Array".to_string(),
+            Type::U32 => "Uint32Array".to_string(),
+            Type::S32 => "Int32Array".to_string(),
+            Type::U64 => "BigUint64Array".to_string(),
+            Type::S64 => "BigInt64Array".to_string(),
+            Type::F32 => "Float32Array".to_string(),
+            Type::F64 => "Float64Array".to_string(),
+            typ => format!("Array<{}>", ts_type(def, typ)?),
+        }),
+        TypeDefKind::Option(typ) => Ok(format!("{} | undefined", ts_type(def, typ)?)),
+        TypeDefKind::Result(result) => Ok(format!(
+            "Result<{}, {}>",
+            result
+                .ok
+                .as_ref()
+                .map(|typ| ts_type(def, typ))
+                .transpose()?
+                .unwrap_or_else(|| "void".to_string()),
+            result
+                .err
+                .as_ref()
+                .map(|typ| ts_type(def, typ))
+                .transpose()?
+                .unwrap_or_else(|| "void".to_string()),
+        )),
+        TypeDefKind::Tuple(tuple) => Ok(format!(
+            "[{}]",
+            tuple
+                .types
+                .iter()
+                .map(|typ| ts_type(def, typ))
+                .collect::<anyhow::Result<Vec<_>>>()?
+                .join(", ")
+        )),
+        TypeDefKind::Handle(Handle::Own(type_id) | Handle::Borrow(type_id)) => {
+            ts_named_type_name(def, *type_id)
+        }
+        kind => Err(anyhow!(
+            "Unsupported anonymous type for TypeScript bindings: {kind:?}"
+        )),
+    }
+}
+
+fn ts_named_type_name(def: &StubDefinition, type_id: TypeId) -> anyhow::Result<String> {
+    match def.get_stub_used_type_alias(type_id) {
+        Some(alias) => Ok(ts_type_name(alias)),
+        None => {
+            let type_def = def.get_type_def(type_id)?;
+            let name = type_def
+                .name
+                .as_ref()
+                .ok_or_else(|| anyhow!("Missing type name, type id: {type_id:?}"))?;
+            Ok(ts_type_name(name))
+        }
+    }
+}
+
+fn ts_type_name(name: &str) -> String {
+    name.to_upper_camel_case()
+}
+
+fn module_name_to_namespace(module_name: &str) -> String {
+    module_name
+        .split('@')
+        .next()
+        .unwrap_or(module_name)
+        .replace([':', '/'], "-")
+        .to_upper_camel_case()
+}
+
+// Collects the named types used by the stub, including the ones only used by other types,
+// except resources which are stubbed on their own
+fn collect_named_types(def: &StubDefinition) -> anyhow::Result<Vec<TypeId>> {
+    let stubbed_resource_names = def
+        .stubbed_entities()
+        .iter()
+        .filter(|entity| entity.is_resource())
+        .map(|entity| entity.name().to_string())
+        .collect::<BTreeSet<_>>();
+
+    let mut queue = def
+        .stub_used_type_defs()
+        .iter()
+        .map(|type_def| type_def.type_id)
+        .collect::<Vec<_>>();
+    for entity in def.stubbed_entities() {
+        for param in entity.constructor_params() {
+            if let Type::Id(type_id) = param.typ {
+                queue.push(type_id);
+            }
+        }
+        for (function, _) in entity.all_functions() {
+            for param in &function.params {
+                if let Type::Id(type_id) = param.typ {
+                    queue.push(type_id);
+                }
+            }
+            if let FunctionResultStub::Anon(Type::Id(type_id)) = function.results {
+                queue.push(type_id);
+            }
+        }
+    }
+
+    let mut visited = BTreeSet::<TypeId>::new();
+    let mut result = Vec::new();
+    while let Some(type_id) = queue.pop() {
+        if !visited.insert(type_id) {
+            continue;
+        }
+
+        let type_def = def.get_type_def(type_id)?;
+        if let Some(name) = &type_def.name {
+            let is_stubbed_resource = matches!(type_def.kind, TypeDefKind::Resource)
+                && stubbed_resource_names.contains(name);
+            if !is_stubbed_resource {
+                result.push(type_id);
+            }
+        }
+
+        let mut child_types = Vec::<Type>::new();
+        match &type_def.kind {
+            TypeDefKind::Record(record) => {
+                child_types.extend(record.fields.iter().map(|field| field.ty))
+            }
+            TypeDefKind::Variant(variant) => {
+                child_types.extend(variant.cases.iter().filter_map(|case| case.ty))
+            }
+            TypeDefKind::Tuple(tuple) => child_types.extend(tuple.types.iter().cloned()),
+            TypeDefKind::Option(typ) | TypeDefKind::List(typ) | TypeDefKind::Type(typ) => {
+                child_types.push(*typ)
+            }
+            TypeDefKind::Result(result) => {
+                child_types.extend(result.ok);
+                child_types.extend(result.err);
+            }
+            TypeDefKind::Handle(Handle::Own(type_id) | Handle::Borrow(type_id)) => {
+                child_types.push(Type::Id(*type_id))
+            }
+            _ => {}
+        }
+        for typ in child_types {
+            if let Type::Id(type_id) = typ {
+                queue.push(type_id);
+            }
+        }
+    }
+
+    result.sort_by_key(|type_id| ts_named_type_name(def, *type_id).unwrap_or_default());
+    Ok(result)
+}
@@ -16,14 +16,17 @@ use crate::fs;
 use crate::fs::{OverwriteSafeAction, OverwriteSafeActions, PathExtra};
 use crate::log::{log_action, log_action_plan, log_warn_action, LogColorize, LogIndent};
 use crate::wasm_rpc_stubgen::naming::wit::{
-    package_dep_dir_name_from_encoder, package_dep_dir_name_from_parser,
+    package_dep_dir_name_from_encoder, package_dep_dir_name_from_parser, ClientNaming,
+};
+use crate::wasm_rpc_stubgen::provenance::{
+    content_source_hash, strip_provenance_header, stub_source_hash, with_provenance_header,
 };
 use crate::wasm_rpc_stubgen::stub::{
     FunctionParamStub, FunctionResultStub, FunctionStub, StubDefinition,
 };
 use crate::wasm_rpc_stubgen::wit_encode::EncodedWitDir;
 use crate::wasm_rpc_stubgen::wit_resolve::{ResolvedWitDir, WitDepsResolver};
-use crate::wasm_rpc_stubgen::{cargo, naming};
+use crate::wasm_rpc_stubgen::{cargo, naming, provenance, python};
 use crate::wasm_rpc_stubgen::{GOLEM_RPC_WIT_VERSION, WASI_WIT_VERSION};
 use anyhow::{anyhow, bail, Context};
 use itertools::Itertools;
@@ -52,7 +55,11 @@ pub fn generate_client_wit_to_target(def: &StubDefinition) -> anyhow::Result<()>
 
     let out = generate_client_wit_from_stub_def(def)?;
     fs::create_dir_all(def.client_wit_root())?;
-    fs::write(def.client_wit_path(), out)?;
+    provenance::warn_if_edited([def.client_wit_path()])?;
+    fs::write_str(
+        def.client_wit_path(),
+        with_provenance_header(&def.client_wit_path(), &stub_source_hash(def)?, &out),
+    )?;
     Ok(())
 }
 
@@ -119,7 +126,7 @@ pub fn generate_client_package_from_stub_def(def: &StubDefinition) -> anyhow::Re
             let mut stub_functions = Vec::<ResourceFunc>::new();
 
             // Constructors
-            {
+            if entity.has_constructor() {
                 let mut constructor = ResourceFunc::constructor();
                 let mut params = entity.constructor_params().to_encoder(def)?;
 
@@ -130,9 +137,7 @@ pub fn generate_client_package_from_stub_def(def: &StubDefinition) -> anyhow::Re
                 }
                 constructor.set_params(params);
                 stub_functions.push(constructor);
-            }
 
-            {
                 let mut custom_constructor = ResourceFunc::static_("custom", false);
                 let mut params = entity.constructor_params().to_encoder(def)?;
                 params.items_mut().insert(
@@ -168,7 +173,7 @@ pub fn generate_client_package_from_stub_def(def: &StubDefinition) -> anyhow::Re
                             ResourceFunc::method(function_name, false)
                         }
                     };
-                    blocking_function.set_params(function.params.to_encoder(def)?);
+                    blocking_function.set_params(function_params(def, function, is_static)?);
                     if !function.results.is_empty() {
                         blocking_function.set_result(function.results.to_encoder(def)?);
                     }
@@ -184,7 +189,7 @@ pub fn generate_client_package_from_stub_def(def: &StubDefinition) -> anyhow::Re
                             ResourceFunc::method(function.name.clone(), false)
                         }
                     };
-                    async_function.set_params(function.params.to_encoder(def)?);
+                    async_function.set_params(function_params(def, function, is_static)?);
                     if !function.results.is_empty() {
                         async_function.set_result(Some(Type::Named(Ident::new(
                             function.async_result_type(entity),
@@ -204,7 +209,7 @@ pub fn generate_client_package_from_stub_def(def: &StubDefinition) -> anyhow::Re
                         }
                     };
 
-                    let mut params: Params = function.params.to_encoder(def)?;
+                    let mut params = function_params(def, function, is_static)?;
                     params.push(
                         Ident::new("scheduled-for"),
                         Type::named(Ident::new("wasi-clocks-datetime")),
@@ -234,6 +239,22 @@ pub fn generate_client_package_from_stub_def(def: &StubDefinition) -> anyhow::Re
     Ok(package)
 }
 
+/// Static functions are not bound to a remote resource, so similarly to the default constructor
+/// they select the target worker by name, unless the component is ephemeral
+fn function_params(
+    def: &StubDefinition,
+    function: &FunctionStub,
+    is_static: bool,
+) -> anyhow::Result<Params> {
+    let mut params: Params = function.params.to_encoder(def)?;
+    if is_static && !def.config.is_ephemeral {
+        params
+            .items_mut()
+            .insert(0, (Ident::new("worker-name"), Type::String));
+    }
+    Ok(params)
+}
+
 fn add_async_return_type(
     def: &StubDefinition,
     stub_interface: &mut Interface,
@@ -342,7 +363,11 @@ pub fn add_dependencies_to_stub_wit_dir(def: &StubDefinition) -> anyhow::Result<
                     dest.log_color_highlight()
                 ),
             );
-            fs::copy(source, &dest)?;
+            let content = fs::read_to_string(source)?;
+            fs::write_str(
+                &dest,
+                with_provenance_header(&dest, &content_source_hash(&content), &content),
+            )?;
         }
     }
 
@@ -409,6 +434,8 @@ fn write_embedded_source(target_dir: &Path, contents: &[(&str, &str)]) -> anyhow
     Ok(())
 }
 
+/// Controls updating the project file of the destination component, which is the Cargo.toml for
+/// Rust components, and the componentize-py.toml for Python components
 #[derive(PartialEq, Eq)]
 pub enum UpdateCargoToml {
     Update,
@@ -420,6 +447,332 @@ pub struct AddClientAsDepConfig {
     pub client_wit_root: PathBuf,
     pub dest_wit_root: PathBuf,
     pub update_cargo_toml: UpdateCargoToml,
+    pub package_renames: Vec<PackageRename>,
+    pub client_naming: ClientNaming,
+}
+
+/// Renames a package of the client, together with its derived client and exports packages, in the
+/// copied WIT files, so clients of unrelated components using the same package name can be added
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageRename {
+    from: String,
+    to: String,
+}
+
+impl PackageRename {
+    /// Both package names are expected in 'namespace:name' form, without version
+    pub fn new(from: &str, to: &str) -> anyhow::Result<Self> {
+        for package_name in [from, to] {
+            let is_valid = package_name
+                .split_once(':')
+                .is_some_and(|(namespace, name)| {
+                    [namespace, name].iter().all(|part| {
+                        !part.is_empty()
+                            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                    })
+                });
+            if !is_valid {
+                bail!(
+                    "Invalid package name for renaming: {}, expected 'namespace:name' form",
+                    package_name.log_color_error_highlight()
+                );
+            }
+        }
+
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    pub fn from(&self) -> &str {
+        &self.from
+    }
+
+    pub fn to(&self) -> &str {
+        &self.to
+    }
+
+    fn with_derived_packages(&self, client_naming: &ClientNaming) -> [(String, String); 3] {
+        [
+            (self.from.clone(), self.to.clone()),
+            (
+                format!("{}{}", self.from, client_naming.suffix),
+                format!("{}{}", self.to, client_naming.suffix),
+            ),
+            (
+                format!("{}-exports", self.from),
+                format!("{}-exports", self.to),
+            ),
+        ]
+    }
+}
+
+/// Replaces the 'namespace:name' package references which are not part of a longer package name,
+/// so references with interfaces and versions are renamed too
+fn rename_packages(content: &str, renames: &[(String, String)]) -> String {
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '-';
+
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(c) = rest.chars().next() {
+        let at_boundary = !result
+            .chars()
+            .next_back()
+            .is_some_and(|c| is_name_char(c) || c == ':' || c == '%');
+        let rename = at_boundary
+            .then(|| {
+                renames.iter().find(|(from, _)| {
+                    rest.starts_with(from.as_str())
+                        && !rest[from.len()..].chars().next().is_some_and(is_name_char)
+                })
+            })
+            .flatten();
+
+        match rename {
+            Some((from, to)) => {
+                result.push_str(to);
+                rest = &rest[from.len()..];
+            }
+            None => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    result
+}
+
+fn renamed_package_name(package_name: &PackageName, renames: &[(String, String)]) -> PackageName {
+    let renamed = rename_packages(
+        &format!("{}:{}", package_name.namespace, package_name.name),
+        renames,
+    );
+    match renamed.split_once(':') {
+        Some((namespace, name)) => PackageName {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            version: package_name.version.clone(),
+        },
+        None => package_name.clone(),
+    }
+}
+
+/// Checks if the client was generated from the destination component itself, and if so, checks
+/// that the exports captured in the client are the same as the current exports of the destination.
+///
+/// The check is done on the resolved interfaces, so it is not affected by formatting or
+/// by the client being generated with a different version of the generator.
+pub fn is_self_client(
+    client_wit_root: &ResolvedWitDir,
+    dest_wit_root: &ResolvedWitDir,
+    client_naming: &ClientNaming,
+) -> anyhow::Result<bool> {
+    let client_package = client_wit_root.main_package()?;
+    let dest_package = dest_wit_root.main_package()?;
+
+    if !client_package.name.name.ends_with(&client_naming.suffix) {
+        return Ok(false);
+    }
+
+    let target_package_name =
+        naming::wit::client_target_package_name(&client_package.name, client_naming);
+    if target_package_name != dest_package.name {
+        return Ok(false);
+    }
+
+    let exports_package_name = naming::wit::exports_parser_package_name(&target_package_name);
+    let client_exports_package = client_wit_root
+        .resolve
+        .package_names
+        .get(&exports_package_name)
+        .map(|package_id| client_wit_root.package(*package_id))
+        .transpose()?
+        .ok_or_else(|| {
+            anyhow!(
+                "Missing exports package {} in client {}",
+                exports_package_name.to_string().log_color_highlight(),
+                client_wit_root.path.log_color_highlight()
+            )
+        })?;
+
+    // The destination either already contains the extracted exports package,
+    // or the exported interfaces are still part of its main package
+    let (dest_exports_package, is_exports_package) = match dest_wit_root
+        .resolve
+        .package_names
+        .get(&exports_package_name)
+    {
+        Some(package_id) => (dest_wit_root.package(*package_id)?, true),
+        None => (dest_package, false),
+    };
+
+    let mut differences = Vec::<String>::new();
+    for (interface_name, client_interface_id) in &client_exports_package.interfaces {
+        match dest_exports_package.interfaces.get(interface_name) {
+            Some(dest_interface_id) => {
+                let client_items =
+                    interface_item_signatures(&client_wit_root.resolve, *client_interface_id)?;
+                let dest_items =
+                    interface_item_signatures(&dest_wit_root.resolve, *dest_interface_id)?;
+                for (item_name, client_signature) in &client_items {
+                    match dest_items.get(item_name) {
+                        Some(dest_signature) if dest_signature == client_signature => {}
+                        Some(_) => {
+                            differences.push(format!("changed {interface_name}.{item_name}"))
+                        }
+                        None => differences.push(format!("removed {interface_name}.{item_name}")),
+                    }
+                }
+                for item_name in dest_items.keys() {
+                    if !client_items.contains_key(item_name) {
+                        differences.push(format!("added {interface_name}.{item_name}"));
+                    }
+                }
+            }
+            None => differences.push(format!("removed interface {interface_name}")),
+        }
+    }
+    if is_exports_package {
+        for interface_name in dest_exports_package.interfaces.keys() {
+            if !client_exports_package
+                .interfaces
+                .contains_key(interface_name)
+            {
+                differences.push(format!("added interface {interface_name}"));
+            }
+        }
+    }
+
+    if !differences.is_empty() {
+        bail!(
+            "Self client {} is out of date, differences: {}. Run '{} app build' to regenerate it.",
+            client_package.name.to_string().log_color_highlight(),
+            differences
+                .iter()
+                .map(|diff| diff.log_color_highlight())
+                .join(", "),
+            crate::command_name()
+        );
+    }
+
+    Ok(true)
+}
+
+fn interface_item_signatures(
+    resolve: &Resolve,
+    interface_id: wit_parser::InterfaceId,
+) -> anyhow::Result<BTreeMap<String, String>> {
+    let interface = resolve
+        .interfaces
+        .get(interface_id)
+        .ok_or_else(|| anyhow!("Interface not found, interface id: {interface_id:?}"))?;
+
+    let mut signatures = BTreeMap::new();
+    for (type_name, type_id) in &interface.types {
+        let type_def = &resolve.types[*type_id];
+        signatures.insert(
+            format!("type {type_name}"),
+            type_def_kind_signature(resolve, &type_def.kind),
+        );
+    }
+    for (function_name, function) in &interface.functions {
+        signatures.insert(
+            format!("func {function_name}"),
+            format!(
+                "func({}){}",
+                function
+                    .params
+                    .iter()
+                    .map(|(name, typ)| format!("{name}: {}", type_signature(resolve, typ)))
+                    .join(", "),
+                function
+                    .result
+                    .as_ref()
+                    .map(|typ| format!(" -> {}", type_signature(resolve, typ)))
+                    .unwrap_or_default()
+            ),
+        );
+    }
+    Ok(signatures)
+}
+
+fn type_signature(resolve: &Resolve, typ: &wit_parser::Type) -> String {
+    match typ {
+        wit_parser::Type::Id(type_id) => {
+            let type_def = &resolve.types[*type_id];
+            match &type_def.name {
+                Some(name) => name.clone(),
+                None => type_def_kind_signature(resolve, &type_def.kind),
+            }
+        }
+        typ => format!("{typ:?}").to_lowercase(),
+    }
+}
+
+fn type_def_kind_signature(resolve: &Resolve, kind: &wit_parser::TypeDefKind) -> String {
+    use wit_parser::{Handle, TypeDefKind};
+
+    let opt_type_signature = |typ: &Option<wit_parser::Type>| {
+        typ.as_ref()
+            .map(|typ| type_signature(resolve, typ))
+            .unwrap_or_else(|| "_".to_string())
+    };
+
+    match kind {
+        TypeDefKind::Record(record) => format!(
+            "record {{ {} }}",
+            record
+                .fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, type_signature(resolve, &field.ty)))
+                .join(", ")
+        ),
+        TypeDefKind::Resource => "resource".to_string(),
+        TypeDefKind::Handle(Handle::Own(type_id)) => format!(
+            "own<{}>",
+            type_signature(resolve, &wit_parser::Type::Id(*type_id))
+        ),
+        TypeDefKind::Handle(Handle::Borrow(type_id)) => format!(
+            "borrow<{}>",
+            type_signature(resolve, &wit_parser::Type::Id(*type_id))
+        ),
+        TypeDefKind::Flags(flags) => format!(
+            "flags {{ {} }}",
+            flags.flags.iter().map(|flag| &flag.name).join(", ")
+        ),
+        TypeDefKind::Tuple(tuple) => format!(
+            "tuple<{}>",
+            tuple
+                .types
+                .iter()
+                .map(|typ| type_signature(resolve, typ))
+                .join(", ")
+        ),
+        TypeDefKind::Variant(variant) => format!(
+            "variant {{ {} }}",
+            variant
+                .cases
+                .iter()
+                .map(|case| format!("{}({})", case.name, opt_type_signature(&case.ty)))
+                .join(", ")
+        ),
+        TypeDefKind::Enum(enum_) => format!(
+            "enum {{ {} }}",
+            enum_.cases.iter().map(|case| &case.name).join(", ")
+        ),
+        TypeDefKind::Option(typ) => format!("option<{}>", type_signature(resolve, typ)),
+        TypeDefKind::Result(result) => format!(
+            "result<{}, {}>",
+            opt_type_signature(&result.ok),
+            opt_type_signature(&result.err)
+        ),
+        TypeDefKind::List(typ) => format!("list<{}>", type_signature(resolve, typ)),
+        TypeDefKind::Future(typ) => format!("future<{}>", opt_type_signature(typ)),
+        TypeDefKind::Stream(typ) => format!("stream<{}>", opt_type_signature(typ)),
+        TypeDefKind::Type(typ) => type_signature(resolve, typ),
+        kind => kind.as_str().to_string(),
+    }
 }
 
 fn can_skip(
@@ -466,8 +819,44 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
 
     let dest_resolved_wit_root = ResolvedWitDir::new(&config.dest_wit_root)?;
 
+    if is_self_client(
+        &client_resolved_wit_root,
+        &dest_resolved_wit_root,
+        &config.client_naming,
+    )? {
+        log_action(
+            "Using",
+            format!(
+                "self client {}",
+                client_package.name.to_string().log_color_highlight()
+            ),
+        );
+    }
+
     let mut dest_encoded_wit_root = EncodedWitDir::new(&dest_resolved_wit_root.resolve)?;
 
+    let renames = config
+        .package_renames
+        .iter()
+        .flat_map(|rename| rename.with_derived_packages(&config.client_naming))
+        .collect::<Vec<_>>();
+    let copy_action = |source: &PathBuf, target: PathBuf| {
+        let header_target = target.clone();
+        OverwriteSafeAction::copy_file_transformed(source.clone(), target, |content| {
+            let source_hash = content_source_hash(&content);
+            let content = if renames.is_empty() {
+                content
+            } else {
+                rename_packages(&content, &renames)
+            };
+            Ok(with_provenance_header(
+                &header_target,
+                &source_hash,
+                &content,
+            ))
+        })
+    };
+
     let mut actions = OverwriteSafeActions::new();
     let mut package_names_to_package_path = BTreeMap::<wit_parser::PackageName, PathBuf>::new();
 
@@ -477,40 +866,57 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
             .get(package_id)
             .ok_or_else(|| anyhow!("Failed to get package sources for {}", package_name))?;
 
+        let renamed_package_name = renamed_package_name(package_name, &renames);
+        let is_renamed = renamed_package_name != *package_name;
+        if is_renamed {
+            log_action(
+                "Renaming",
+                format!(
+                    "package {} to {}",
+                    package_name.to_string().log_color_highlight(),
+                    renamed_package_name.to_string().log_color_highlight()
+                ),
+            );
+        }
+
         if *package_id == client_resolved_wit_root.package_id {
-            let package_path = naming::wit::package_wit_dep_dir_from_parser(package_name);
+            let package_path = naming::wit::package_wit_dep_dir_from_parser(&renamed_package_name);
 
-            package_names_to_package_path.insert(package_name.clone(), package_path.clone());
+            package_names_to_package_path.insert(renamed_package_name.clone(), package_path);
 
             for source in &package_sources.files {
-                actions.add(OverwriteSafeAction::CopyFile {
-                    source: source.clone(),
-                    target: config
+                actions.add(copy_action(
+                    source,
+                    config
                         .dest_wit_root
                         .join(naming::wit::DEPS_DIR)
-                        .join(naming::wit::package_dep_dir_name_from_parser(package_name))
+                        .join(naming::wit::package_dep_dir_name_from_parser(
+                            &renamed_package_name,
+                        ))
                         .join(PathExtra::new(&source).file_name_to_string()?),
-                });
+                )?);
             }
-        } else if !can_skip(
-            &client_resolved_wit_root.resolve,
-            &dest_resolved_wit_root.resolve,
-            package_name,
-        ) {
+        } else if is_renamed
+            || !can_skip(
+                &client_resolved_wit_root.resolve,
+                &dest_resolved_wit_root.resolve,
+                package_name,
+            )
+        {
             package_names_to_package_path.insert(
-                package_name.clone(),
+                renamed_package_name,
                 naming::wit::package_wit_dep_dir_from_package_dir_name(
                     &PathExtra::new(&package_sources.dir).file_name_to_string()?,
                 ),
             );
 
             for source in &package_sources.files {
-                actions.add(OverwriteSafeAction::CopyFile {
-                    source: source.clone(),
-                    target: config
+                actions.add(copy_action(
+                    source,
+                    config
                         .dest_wit_root
                         .join(PathExtra::new(&source).strip_prefix(&config.client_wit_root)?),
-                });
+                )?);
             }
         } else {
             log_warn_action(
@@ -548,9 +954,17 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
     //       in the source, and could create invalid imports.
     remove_world_named_interface_imports(
         package,
-        &naming::wit::client_import_exports_prefix_from_client_package_name(&client_package.name)?,
+        &rename_packages(
+            &naming::wit::client_import_exports_prefix_from_client_package_name(
+                &client_package.name,
+                &config.client_naming,
+            )?,
+            &renames,
+        ),
     );
-    add_world_named_interface_import(package, &naming::wit::client_import_name(client_package)?);
+    let client_import_name =
+        rename_packages(&naming::wit::client_import_name(client_package)?, &renames);
+    add_world_named_interface_import(package, &client_import_name);
     let content = package.to_string();
 
     actions.add(OverwriteSafeAction::WriteFile {
@@ -559,6 +973,7 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
     });
 
     // Check overwrites
+    provenance::warn_if_edited(actions.targets())?;
     let forbidden_overwrites = actions.run(true, false, log_action_plan)?;
     if !forbidden_overwrites.is_empty() {
         eprintln!("The following files would have been overwritten with new content:");
@@ -577,11 +992,25 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
                 cargo::is_cargo_component_toml(&target_cargo_toml).context(format!(
                     "The file {target_cargo_toml:?} is not a valid cargo-component project"
                 ))?;
-                cargo::add_cargo_package_component_deps(
+                cargo::sync_cargo_package_component_deps(
                     &target_cargo_toml,
                     package_names_to_package_path,
                 )?;
             }
+        } else if python::is_python_project(target_parent) {
+            if config.update_cargo_toml != UpdateCargoToml::NoUpdate {
+                let client_interface_name = client_package
+                    .interfaces
+                    .first()
+                    .map(|(name, _)| name.clone())
+                    .ok_or_else(|| anyhow!("Missing client interface"))?;
+                python::add_componentize_py_client_deps(
+                    target_parent,
+                    &config.dest_wit_root,
+                    &client_import_name,
+                    &client_interface_name,
+                )?;
+            }
         } else if config.update_cargo_toml == UpdateCargoToml::Update {
             return Err(anyhow!(
                 "Cannot update {:?} file because it does not exist or is not a file",
@@ -595,6 +1024,114 @@ pub fn add_client_as_dependency_to_wit_dir(config: AddClientAsDepConfig) -> anyh
     Ok(())
 }
 
+/// Adds a plain WIT package and its package dependencies to the deps of the destination, without
+/// generating a client, for sharing type-only packages between components.
+///
+/// Worlds are removed from the added package, as only its interfaces can be used from the
+/// destination, and the imports of the worlds could refer to packages missing from the destination.
+pub fn add_wit_package_as_dependency_to_wit_dir(
+    source_wit_dir: &Path,
+    dest_wit_root: &Path,
+) -> anyhow::Result<()> {
+    log_action(
+        "Adding",
+        format!(
+            "WIT dependency to {} from {}",
+            dest_wit_root.log_color_highlight(),
+            source_wit_dir.log_color_highlight()
+        ),
+    );
+
+    let _indent = LogIndent::new();
+
+    let source_resolved_wit_dir = ResolvedWitDir::new(source_wit_dir)?;
+    let dest_resolved_wit_root = ResolvedWitDir::new(dest_wit_root)?;
+
+    let mut source_encoded_wit_dir = EncodedWitDir::new(&source_resolved_wit_dir.resolve)?;
+
+    let mut actions = OverwriteSafeActions::new();
+
+    for (package_name, package_id) in &source_resolved_wit_dir.resolve.package_names {
+        if *package_id == source_resolved_wit_dir.package_id {
+            if dest_resolved_wit_root.main_package()?.name == *package_name {
+                bail!(
+                    "Cannot add WIT package {} as dependency to itself",
+                    package_name.to_string().log_color_error_highlight()
+                );
+            }
+
+            let package = source_encoded_wit_dir.package(*package_id)?;
+            package
+                .items_mut()
+                .retain(|item| !matches!(item, PackageItem::World(_)));
+            if package.items_mut().is_empty() {
+                bail!(
+                    "WIT package {} has no interfaces to add as dependency",
+                    package_name.to_string().log_color_error_highlight()
+                );
+            }
+
+            let target = dest_wit_root
+                .join(naming::wit::DEPS_DIR)
+                .join(package_dep_dir_name_from_parser(package_name))
+                .join(naming::wit::package_merged_wit_name(package_name));
+            let mut source_content = String::new();
+            if let Some(package_sources) = source_resolved_wit_dir.package_sources.get(package_id) {
+                for source in &package_sources.files {
+                    source_content.push_str(strip_provenance_header(&fs::read_to_string(source)?));
+                }
+            }
+            actions.add(OverwriteSafeAction::WriteFile {
+                content: with_provenance_header(
+                    &target,
+                    &content_source_hash(&source_content),
+                    &package.to_string(),
+                ),
+                target,
+            });
+        } else if !can_skip(
+            &source_resolved_wit_dir.resolve,
+            &dest_resolved_wit_root.resolve,
+            package_name,
+        ) {
+            let package_sources = source_resolved_wit_dir
+                .package_sources
+                .get(package_id)
+                .ok_or_else(|| anyhow!("Failed to get package sources for {}", package_name))?;
+
+            for source in &package_sources.files {
+                let target =
+                    dest_wit_root.join(PathExtra::new(&source).strip_prefix(source_wit_dir)?);
+                let header_target = target.clone();
+                actions.add(OverwriteSafeAction::copy_file_transformed(
+                    source.clone(),
+                    target,
+                    |content| {
+                        Ok(with_provenance_header(
+                            &header_target,
+                            &content_source_hash(&content),
+                            &content,
+                        ))
+                    },
+                )?);
+            }
+        } else {
+            log_warn_action(
+                "Skipping",
+                format!(
+                    "package dependency {}, already exists in destination",
+                    package_name.to_string().log_color_highlight()
+                ),
+            );
+        }
+    }
+
+    provenance::warn_if_edited(actions.targets())?;
+    actions.run(true, false, log_action_plan)?;
+
+    Ok(())
+}
+
 trait ToEncoder {
     type EncoderType;
     fn to_encoder(&self, stub_definition: &StubDefinition) -> anyhow::Result<Self::EncoderType>;
@@ -1162,6 +1699,96 @@ pub fn extract_wasm_interface_as_wit_dep(
     Ok(result)
 }
 
+/// Reconstructs the WIT of a deployed component from its WASM into a WIT directory, which can be
+/// used as the source of a client, similarly to the generated base WIT of application components.
+///
+/// The root world of the component is moved into the package named as the root package of the
+/// component, which becomes the main package, all the other packages are written as deps.
+pub fn extract_deployed_component_wit(
+    component_bytes: &[u8],
+    root_package_name: &str,
+    wit_dir: &Path,
+) -> anyhow::Result<()> {
+    let (mut resolve, world_id) = match wit_parser::decoding::decode(component_bytes)? {
+        wit_parser::decoding::DecodedWasm::Component(resolve, world_id) => (resolve, world_id),
+        wit_parser::decoding::DecodedWasm::WitPackage(_, _) => {
+            bail!("Expected a WASM component, got a WIT package")
+        }
+    };
+
+    let package_name = parse_package_name(root_package_name)?;
+    let world_package_id = resolve.worlds[world_id]
+        .package
+        .ok_or_else(|| anyhow!("Missing package for the component world"))?;
+    let main_package_id = match resolve.package_names.get(&package_name) {
+        Some(package_id) => *package_id,
+        None => {
+            resolve.packages[world_package_id].name = package_name.clone();
+            world_package_id
+        }
+    };
+
+    resolve.packages[world_package_id]
+        .worlds
+        .retain(|_, id| *id != world_id);
+    resolve.worlds[world_id].name = package_name.name.clone();
+    resolve.worlds[world_id].package = Some(main_package_id);
+    resolve.packages[main_package_id]
+        .worlds
+        .insert(package_name.name.clone(), world_id);
+
+    for (package_id, package) in resolve.packages.iter() {
+        if package.interfaces.is_empty() && package.worlds.is_empty() {
+            continue;
+        }
+
+        let package_dir = if package_id == main_package_id {
+            wit_dir.to_path_buf()
+        } else {
+            wit_dir
+                .join(naming::wit::DEPS_DIR)
+                .join(package_dep_dir_name_from_parser(&package.name))
+        };
+
+        let mut wit_printer = WitPrinter::default();
+        wit_printer.emit_docs(true);
+        wit_printer.print(&resolve, package_id, &[])?;
+
+        fs::write_str(
+            package_dir.join(format!("{}.wit", package.name.name)),
+            wit_printer.output.to_string(),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn parse_package_name(package_name: &str) -> anyhow::Result<PackageName> {
+    let (name, version) = match package_name.split_once('@') {
+        Some((name, version)) => (
+            name,
+            Some(Version::parse(version).with_context(|| {
+                anyhow!(
+                    "Invalid version in package name {}",
+                    package_name.log_color_error_highlight()
+                )
+            })?),
+        ),
+        None => (package_name, None),
+    };
+    let Some((namespace, name)) = name.split_once(':') else {
+        bail!(
+            "Invalid package name {}, expected 'namespace:name' form",
+            package_name.log_color_error_highlight()
+        );
+    };
+    Ok(PackageName {
+        namespace: namespace.to_string(),
+        name: name.to_string(),
+        version,
+    })
+}
+
 trait UsedTypeIdents {
     fn used_type_idents(&self) -> HashSet<Ident>;
 }
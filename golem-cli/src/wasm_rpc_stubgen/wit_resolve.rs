@@ -399,13 +399,17 @@ impl ResolvedWitApplication {
         &mut self,
         component_name: AppComponentName,
         resolved_component: ResolvedWitComponent,
+        client_naming: &naming::wit::ClientNaming,
     ) {
         self.package_to_component.insert(
             resolved_component.main_package_name.clone(),
             component_name.clone(),
         );
         self.stub_package_to_component.insert(
-            naming::wit::client_parser_package_name(&resolved_component.main_package_name),
+            naming::wit::client_parser_package_name(
+                &resolved_component.main_package_name,
+                client_naming,
+            ),
             component_name.clone(),
         );
         self.interface_package_to_component.insert(
@@ -500,7 +504,11 @@ impl ResolvedWitApplication {
 
             match resolved_component {
                 Ok(resolved_component) => {
-                    self.add_resolved_component(component_name.clone(), resolved_component);
+                    self.add_resolved_component(
+                        component_name.clone(),
+                        resolved_component,
+                        &app.client_naming(),
+                    );
                 }
                 Err(err) => validation.add_error(format!("{err:?}")),
             }
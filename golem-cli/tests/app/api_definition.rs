@@ -0,0 +1,58 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::fixture::Fixture;
+use crate::app::{cmd, TestContext};
+use crate::Tracing;
+use assert2::{assert, check};
+use indoc::indoc;
+use test_r::{inherit_test_dep, test};
+
+inherit_test_dep!(Tracing);
+
+const COUNTER_API_ROUTES: &str = indoc! {r#"
+    - method: GET
+      path: /counters/{name}
+      binding:
+        componentName: app:counter
+        response: |
+          let name: string = request.path.name;
+          let worker = instance("${name}");
+          let value = worker.get();
+          { status: 200u64, body: value }
+"#};
+
+#[test]
+fn api_definition_list(_tracing: &Tracing) {
+    let ctx = Fixture::component("app:counter")
+        .with_worker("w1")
+        .with_api_definition("counter-api", "0.1.0", COUNTER_API_ROUTES)
+        .provision(TestContext::recorded("api_definition_list"));
+
+    let outputs = ctx.cli([cmd::API, cmd::DEFINITION, cmd::LIST]);
+    assert!(outputs.success());
+    check!(outputs.stdout_contains_row_with_cells(&["counter-api", "0.1.0"]));
+}
+
+#[test]
+fn deployed_api_definition(_tracing: &Tracing) {
+    let ctx = Fixture::component("app:counter")
+        .with_api_definition("counter-api", "0.1.0", COUNTER_API_ROUTES)
+        .with_api_deployment("localhost:9006")
+        .provision(TestContext::new());
+
+    let outputs = ctx.cli([cmd::API, cmd::DEPLOYMENT, cmd::LIST, "counter-api"]);
+    assert!(outputs.success());
+    check!(outputs.stdout_contains("localhost:9006"));
+}
@@ -0,0 +1,39 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::fixture::Fixture;
+use crate::app::{cmd, TestContext};
+use crate::Tracing;
+use assert2::{assert, check};
+use test_r::{inherit_test_dep, test};
+
+inherit_test_dep!(Tracing);
+
+#[test]
+fn component_list_and_get(_tracing: &Tracing) {
+    let ctx = Fixture::component("app:counter")
+        .with_component("app:other")
+        .deployed()
+        .provision(TestContext::new());
+
+    let outputs = ctx.cli([cmd::COMPONENT, cmd::LIST]);
+    assert!(outputs.success());
+    check!(outputs.stdout_contains("app:counter"));
+    check!(outputs.stdout_contains("app:other"));
+
+    let outputs = ctx.cli([cmd::COMPONENT, cmd::GET, "app:counter"]);
+    assert!(outputs.success());
+    check!(outputs.stdout_contains("app:counter"));
+    check!(!outputs.stdout_contains("app:other"));
+}
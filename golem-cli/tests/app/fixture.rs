@@ -0,0 +1,384 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::{cmd, flag, TestContext};
+use assert2::assert;
+use colored::Colorize;
+use golem_cli::fs;
+use itertools::Itertools;
+use serde_json::Value;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+
+const APP_NAME: &str = "test-app-name";
+
+/// Fluent builder of the resources a test starts from, e.g.:
+///
+/// ```ignore
+/// let ctx = Fixture::component("app:counter")
+///     .with_worker("w1")
+///     .with_api_definition("counter-api", "0.1.0", routes)
+///     .with_api_deployment("localhost:9006")
+///     .provision(TestContext::new());
+/// ```
+///
+/// All resources are provisioned through the CLI under test. Workers and API definitions
+/// require a running server, so the server is started and the app is deployed when any of
+/// them are requested (or when using [Fixture::deployed]). Server side resources are deleted
+/// when the returned [FixtureContext] is dropped.
+pub struct Fixture {
+    components: Vec<ComponentFixture>,
+    api_definitions: Vec<ApiDefinitionFixture>,
+    api_deployments: Vec<String>,
+    deployed: bool,
+}
+
+struct ComponentFixture {
+    name: String,
+    language: String,
+    manifest: Option<String>,
+    workers: Vec<String>,
+}
+
+struct ApiDefinitionFixture {
+    name: String,
+    version: String,
+    routes: String,
+}
+
+impl Fixture {
+    /// Starts a fixture with a new app containing a single Rust component
+    pub fn component(component_name: &str) -> Self {
+        Self {
+            components: vec![],
+            api_definitions: vec![],
+            api_deployments: vec![],
+            deployed: false,
+        }
+        .with_component(component_name)
+    }
+
+    /// Adds another Rust component, the component specific options apply to the last added one
+    pub fn with_component(mut self, component_name: &str) -> Self {
+        self.components.push(ComponentFixture {
+            name: component_name.to_string(),
+            language: "rust".to_string(),
+            manifest: None,
+            workers: vec![],
+        });
+        self
+    }
+
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.last_component().language = language.to_string();
+        self
+    }
+
+    /// Replaces the generated component manifest
+    pub fn with_component_manifest(mut self, manifest: &str) -> Self {
+        self.last_component().manifest = Some(manifest.to_string());
+        self
+    }
+
+    pub fn with_worker(mut self, worker_name: &str) -> Self {
+        self.last_component().workers.push(worker_name.to_string());
+        self
+    }
+
+    /// Adds an API definition to the app manifest and deploys it, the routes are expected in
+    /// the manifest format, as a YAML list
+    pub fn with_api_definition(mut self, name: &str, version: &str, routes: &str) -> Self {
+        self.api_definitions.push(ApiDefinitionFixture {
+            name: name.to_string(),
+            version: version.to_string(),
+            routes: routes.to_string(),
+        });
+        self
+    }
+
+    /// Deploys all the API definitions of the fixture to the host (using the local profile)
+    pub fn with_api_deployment(mut self, host: &str) -> Self {
+        self.api_deployments.push(host.to_string());
+        self
+    }
+
+    /// Starts the server and deploys the app, even if no server side resources are requested
+    pub fn deployed(mut self) -> Self {
+        self.deployed = true;
+        self
+    }
+
+    fn last_component(&mut self) -> &mut ComponentFixture {
+        self.components
+            .last_mut()
+            .expect("fixture has at least one component")
+    }
+
+    fn requires_server(&self) -> bool {
+        self.deployed
+            || !self.api_definitions.is_empty()
+            || !self.api_deployments.is_empty()
+            || self
+                .components
+                .iter()
+                .any(|component| !component.workers.is_empty())
+    }
+
+    pub fn provision(self, ctx: TestContext) -> FixtureContext {
+        println!("{}", "> provisioning fixture".bold());
+
+        let mut ctx = FixtureContext {
+            ctx,
+            workers: vec![],
+            api_definitions: vec![],
+        };
+
+        let languages = self
+            .components
+            .iter()
+            .map(|component| component.language.as_str())
+            .unique()
+            .collect::<Vec<_>>();
+        let outputs = ctx.cli([cmd::APP, cmd::NEW, APP_NAME].into_iter().chain(languages));
+        assert!(outputs.success(), "failed to create fixture app");
+
+        ctx.cd(APP_NAME);
+
+        for component in &self.components {
+            let outputs = ctx.cli([
+                cmd::COMPONENT,
+                cmd::NEW,
+                component.language.as_str(),
+                component.name.as_str(),
+            ]);
+            assert!(
+                outputs.success(),
+                "failed to create fixture component {}",
+                component.name
+            );
+
+            if let Some(manifest) = &component.manifest {
+                fs::write_str(
+                    ctx.cwd_path_join(component_manifest_path(component)),
+                    manifest,
+                )
+                .unwrap();
+            }
+        }
+
+        if !self.api_definitions.is_empty() {
+            fs::write_str(
+                ctx.cwd_path_join(Path::new("common-fixture").join("golem.yaml")),
+                api_manifest(&self.api_definitions, &self.api_deployments),
+            )
+            .unwrap();
+        }
+
+        if !self.requires_server() {
+            return ctx;
+        }
+
+        ctx.start_server();
+
+        let outputs = ctx.cli([cmd::APP, cmd::DEPLOY, flag::YES]);
+        assert!(outputs.success(), "failed to deploy fixture app");
+
+        for component in &self.components {
+            for worker in &component.workers {
+                let worker = format!("{}/{}", component.name, worker);
+                let outputs = ctx.cli([cmd::WORKER, cmd::NEW, worker.as_str()]);
+                assert!(
+                    outputs.success(),
+                    "failed to create fixture worker {worker}"
+                );
+                ctx.workers.push(worker);
+            }
+        }
+
+        for api_definition in &self.api_definitions {
+            let outputs = ctx.cli([
+                cmd::API,
+                cmd::DEFINITION,
+                cmd::DEPLOY,
+                api_definition.name.as_str(),
+                flag::YES,
+            ]);
+            assert!(
+                outputs.success(),
+                "failed to deploy fixture API definition {}",
+                api_definition.name
+            );
+            ctx.api_definitions
+                .push((api_definition.name.clone(), api_definition.version.clone()));
+        }
+
+        for host in &self.api_deployments {
+            let outputs = ctx.cli([
+                cmd::API,
+                cmd::DEPLOYMENT,
+                cmd::DEPLOY,
+                host.as_str(),
+                flag::YES,
+            ]);
+            assert!(
+                outputs.success(),
+                "failed to deploy fixture API deployment {host}"
+            );
+        }
+
+        ctx
+    }
+}
+
+fn component_manifest_path(component: &ComponentFixture) -> PathBuf {
+    Path::new(&format!("components-{}", component.language))
+        .join(component.name.replace(':', "-"))
+        .join("golem.yaml")
+}
+
+fn api_manifest(api_definitions: &[ApiDefinitionFixture], api_deployments: &[String]) -> String {
+    let mut manifest = "httpApi:\n  definitions:\n".to_string();
+    for api_definition in api_definitions {
+        manifest.push_str(&format!(
+            "    {}:\n      version: {}\n      routes:\n",
+            api_definition.name, api_definition.version
+        ));
+        for line in api_definition.routes.lines() {
+            manifest.push_str(&format!("        {line}\n"));
+        }
+    }
+    if !api_deployments.is_empty() {
+        manifest.push_str("  deployments:\n    local:\n");
+        for host in api_deployments {
+            manifest.push_str(&format!("    - host: {host}\n      definitions:\n"));
+            for api_definition in api_definitions {
+                manifest.push_str(&format!("      - {}\n", api_definition.name));
+            }
+        }
+    }
+    manifest
+}
+
+/// Test context with provisioned fixture resources, deleting the server side ones on drop
+pub struct FixtureContext {
+    ctx: TestContext,
+    workers: Vec<String>,
+    api_definitions: Vec<(String, String)>,
+}
+
+impl Deref for FixtureContext {
+    type Target = TestContext;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ctx
+    }
+}
+
+impl DerefMut for FixtureContext {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.ctx
+    }
+}
+
+impl FixtureContext {
+    /// Sites of the deployments of the fixture API definitions
+    fn api_deployment_sites(&self) -> Vec<String> {
+        let mut sites = vec![];
+        for (id, _) in &self.api_definitions {
+            let outputs = self.ctx.cli([
+                cmd::API,
+                cmd::DEPLOYMENT,
+                cmd::LIST,
+                id.as_str(),
+                "--format",
+                "json",
+            ]);
+            let deployments = outputs
+                .success()
+                .then(|| serde_json::from_str::<Value>(&outputs.stdout.join("\n")).ok())
+                .flatten();
+            let Some(Value::Array(deployments)) = deployments else {
+                println!("{} {id}", "> failed to list API deployments of".red());
+                continue;
+            };
+
+            for deployment in deployments {
+                let host = deployment["site"]["host"].as_str().unwrap_or_default();
+                let site = match deployment["site"]["subdomain"].as_str() {
+                    Some(subdomain) => format!("{subdomain}.{host}"),
+                    None => host.to_string(),
+                };
+                if !sites.contains(&site) {
+                    sites.push(site);
+                }
+            }
+        }
+        sites
+    }
+}
+
+impl Drop for FixtureContext {
+    fn drop(&mut self) {
+        // Deleted resources are gone anyway when the server was already stopped by the test
+        if self.ctx.server_process.is_none() {
+            return;
+        }
+
+        println!("{}", "> cleaning up fixture".bold());
+
+        // Failures are only reported, as panicking here would abort failing tests. Deployed
+        // API definitions cannot be deleted, so the deployments (including the ones created by
+        // the test) are deleted first, then the definitions, and finally the workers.
+        for site in self.api_deployment_sites() {
+            let outputs = self.ctx.cli([
+                cmd::API,
+                cmd::DEPLOYMENT,
+                cmd::DELETE,
+                site.as_str(),
+                flag::YES,
+            ]);
+            if !outputs.success() {
+                println!("{} {site}", "> failed to delete API deployment".red());
+            }
+        }
+
+        for (id, version) in self.api_definitions.drain(..).rev() {
+            let outputs = self.ctx.cli([
+                cmd::API,
+                cmd::DEFINITION,
+                cmd::DELETE,
+                "--id",
+                id.as_str(),
+                "--version",
+                version.as_str(),
+                flag::YES,
+            ]);
+            if !outputs.success() {
+                println!(
+                    "{} {id}/{version}",
+                    "> failed to delete API definition".red()
+                );
+            }
+        }
+
+        for worker in self.workers.drain(..).rev() {
+            let outputs = self
+                .ctx
+                .cli([cmd::WORKER, cmd::DELETE, worker.as_str(), flag::YES]);
+            if !outputs.success() {
+                println!("{} {worker}", "> failed to delete worker".red());
+            }
+        }
+    }
+}
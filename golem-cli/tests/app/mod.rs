@@ -12,8 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod api_definition;
+mod component;
+mod fixture;
 mod plugins;
+mod worker;
 
+use crate::app::fixture::Fixture;
 use crate::test_r_get_dep_tracing;
 use crate::Tracing;
 use assert2::{assert, check};
@@ -23,6 +28,7 @@ use golem_cli::model::invoke_result_view::InvokeResultView;
 use golem_templates::model::GuestLanguage;
 use indoc::indoc;
 use itertools::Itertools;
+use std::cell::Cell;
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
@@ -34,10 +40,15 @@ use tracing::info;
 
 mod cmd {
     pub static ADD_DEPENDENCY: &str = "add-dependency";
+    pub static ADD_WIT_DEPENDENCY: &str = "add-wit-dependency";
+    pub static API: &str = "api";
     pub static APP: &str = "app";
     pub static BUILD: &str = "build";
     pub static COMPLETION: &str = "completion";
     pub static COMPONENT: &str = "component";
+    pub static DEFINITION: &str = "definition";
+    pub static DELETE: &str = "delete";
+    pub static DEPLOYMENT: &str = "deployment";
     pub static DEPLOY: &str = "deploy";
     pub static NEW: &str = "new";
     pub static WORKER: &str = "worker";
@@ -45,6 +56,7 @@ mod cmd {
     pub static PLUGIN: &str = "plugin";
     pub static REGISTER: &str = "register";
     pub static GET: &str = "get";
+    pub static LIST: &str = "list";
 }
 
 mod flag {
@@ -276,7 +288,7 @@ fn basic_dependencies_build(_tracing: &Tracing) {
 
 #[test]
 fn basic_ifs_deploy(_tracing: &Tracing) {
-    let mut ctx = TestContext::new();
+    let mut ctx = TestContext::recorded("basic_ifs_deploy");
     let app_name = "test-app-name";
 
     let outputs = ctx.cli([cmd::APP, cmd::NEW, app_name, "rust"]);
@@ -388,9 +400,51 @@ fn custom_app_subcommand_with_builtin_name() {
     check!(outputs.stdout_contains("Executing external command 'cargo tree'"));
 }
 
+#[test]
+fn add_wit_dependency_to_component() {
+    let ctx = Fixture::component("app:rust").provision(TestContext::new());
+
+    fs::write_str(
+        ctx.cwd_path_join(Path::new("shared-wit").join("types.wit")),
+        indoc! {"
+            package app:shared-types;
+
+            interface types {
+              record point {
+                x: s32,
+                y: s32,
+              }
+            }
+        "},
+    )
+    .unwrap();
+
+    let outputs = ctx.cli([cmd::APP, cmd::ADD_WIT_DEPENDENCY, "shared-wit", "app:rust"]);
+    assert!(outputs.success());
+
+    let dep_wit = ctx.cwd_path_join(
+        Path::new("components-rust")
+            .join("app-rust")
+            .join("wit")
+            .join("deps")
+            .join("app_shared-types")
+            .join("app_shared-types.wit"),
+    );
+    let dep_wit = fs::read_to_string(dep_wit).unwrap();
+    check!(dep_wit.contains("package app:shared-types;"));
+    check!(dep_wit.contains("record point"));
+
+    // Adding the same package again is a no-op
+    let outputs = ctx.cli([cmd::APP, cmd::ADD_WIT_DEPENDENCY, "shared-wit", "app:rust"]);
+    assert!(outputs.success());
+
+    let outputs = ctx.cli([cmd::APP, cmd::ADD_WIT_DEPENDENCY, "missing-wit", "app:rust"]);
+    assert!(!outputs.success());
+}
+
 #[test]
 fn wasm_library_dependency_type() -> anyhow::Result<()> {
-    let mut ctx = TestContext::new();
+    let mut ctx = TestContext::recorded("wasm_library_dependency_type");
     let app_name = "test-app-name";
 
     let outputs = ctx.cli([cmd::APP, cmd::NEW, app_name, "rust"]);
@@ -842,6 +896,37 @@ impl From<std::process::Output> for Output {
     }
 }
 
+/// Selects how the tests using recorded server responses are run, using GOLEM_CLI_TEST_HTTP:
+/// - replay: the CLI is served the recorded responses, the server is not started, this is the
+///   default when the test has recordings, and fails the test if a recording is missing
+/// - record: runs against the server and records the responses, the recordings in the source
+///   tree are only updated when the test passes
+/// - live: runs against the server without recording, this is the default when the test has
+///   no recordings yet
+///
+/// Tests created with [TestContext::new] always run against the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HttpMode {
+    Live,
+    Record,
+    Replay,
+}
+
+impl HttpMode {
+    fn for_cassettes(cassette_dir: &Path) -> Self {
+        match std::env::var("GOLEM_CLI_TEST_HTTP").ok().as_deref() {
+            Some("live") => Self::Live,
+            Some("record") => Self::Record,
+            Some("replay") => Self::Replay,
+            None if cassette_dir.exists() => Self::Replay,
+            None => Self::Live,
+            Some(other) => panic!(
+                "Invalid GOLEM_CLI_TEST_HTTP: {other}, expected one of: live, record, replay"
+            ),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct TestContext {
     golem_path: PathBuf,
@@ -851,11 +936,18 @@ struct TestContext {
     data_dir: TempDir,
     working_dir: PathBuf,
     server_process: Option<Child>,
+    http_mode: HttpMode,
+    cassette_dir: Option<PathBuf>,
+    recording_dir: Option<TempDir>,
+    cli_invocations: Cell<usize>,
 }
 
 impl Drop for TestContext {
     fn drop(&mut self) {
         self.stop_server();
+        if !std::thread::panicking() {
+            self.publish_recordings();
+        }
     }
 }
 
@@ -876,6 +968,10 @@ impl TestContext {
             data_dir: TempDir::new().unwrap(),
             working_dir,
             server_process: None,
+            http_mode: HttpMode::Live,
+            cassette_dir: None,
+            recording_dir: None,
+            cli_invocations: Cell::new(0),
         };
 
         info!(ctx = ?ctx ,"Created test context");
@@ -883,6 +979,79 @@ impl TestContext {
         ctx
     }
 
+    /// Test context using the recorded server responses (cassettes) of the named test, stored
+    /// in tests/cassettes, one file for every CLI invocation. Recordings have to be updated with
+    /// GOLEM_CLI_TEST_HTTP=record when the requests of the test change.
+    fn recorded(test_name: &str) -> Self {
+        let cassette_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("cassettes")
+            .join(test_name);
+        let http_mode = HttpMode::for_cassettes(&cassette_dir);
+        if http_mode == HttpMode::Replay {
+            assert!(
+                cassette_dir.exists(),
+                "Missing recordings of test {test_name} in {}, record them against a server with GOLEM_CLI_TEST_HTTP=record",
+                cassette_dir.display()
+            );
+        }
+
+        println!(
+            "{} {:?} {}",
+            "> HTTP mode:".bold(),
+            http_mode,
+            cassette_dir.display()
+        );
+
+        let mut ctx = Self::new();
+        ctx.http_mode = http_mode;
+        ctx.cassette_dir = Some(cassette_dir);
+        // Recordings are only moved to the source tree once the test passed
+        ctx.recording_dir = (http_mode == HttpMode::Record).then(|| TempDir::new().unwrap());
+        ctx
+    }
+
+    fn cassette_file_name(invocation: usize) -> String {
+        format!("{invocation:03}.json")
+    }
+
+    /// Replaces the recordings of the test with the new ones, also removing the recordings of
+    /// the CLI invocations the test does not make anymore
+    fn publish_recordings(&self) {
+        let (Some(cassette_dir), Some(recording_dir)) = (&self.cassette_dir, &self.recording_dir)
+        else {
+            return;
+        };
+
+        std::fs::create_dir_all(cassette_dir).unwrap();
+        for entry in std::fs::read_dir(cassette_dir).unwrap() {
+            let path = entry.unwrap().path();
+            let is_stale = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|index| index.parse::<usize>().ok())
+                .is_some_and(|index| index >= self.cli_invocations.get());
+            if is_stale {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+        for invocation in 0..self.cli_invocations.get() {
+            let file_name = Self::cassette_file_name(invocation);
+            std::fs::copy(
+                recording_dir.path().join(&file_name),
+                cassette_dir.join(&file_name),
+            )
+            .unwrap();
+        }
+
+        println!(
+            "{} {}",
+            "> updated recordings:".bold(),
+            cassette_dir.display()
+        );
+    }
+
     #[must_use]
     fn cli<I, S>(&self, args: I) -> Output
     where
@@ -909,12 +1078,39 @@ impl TestContext {
         );
         println!("{} {}", "> golem-cli".bold(), args.iter().join(" ").blue());
 
-        let output: Output = Command::new(&self.golem_cli_path)
-            .args(args)
-            .current_dir(working_dir)
-            .output()
-            .unwrap()
-            .into();
+        let mut command = Command::new(&self.golem_cli_path);
+        command.args(args).current_dir(working_dir);
+
+        let invocation = self.cli_invocations.get();
+        self.cli_invocations.set(invocation + 1);
+        let recording = match (&self.recording_dir, &self.cassette_dir) {
+            (Some(recording_dir), _) => {
+                let cassette = recording_dir
+                    .path()
+                    .join(Self::cassette_file_name(invocation));
+                command.env("GOLEM_HTTP_RECORD", &cassette);
+                Some(cassette)
+            }
+            (None, Some(cassette_dir)) if self.http_mode == HttpMode::Replay => {
+                let cassette = cassette_dir.join(Self::cassette_file_name(invocation));
+                assert!(
+                    cassette.exists(),
+                    "Missing recording {} for CLI invocation {invocation}, record it against a server with GOLEM_CLI_TEST_HTTP=record",
+                    cassette.display()
+                );
+                command.env("GOLEM_HTTP_REPLAY", &cassette);
+                None
+            }
+            _ => None,
+        };
+
+        let output: Output = command.output().unwrap().into();
+
+        // Invocations without server requests get an empty recording, so replaying can require
+        // a recording for every invocation
+        if let Some(cassette) = recording.filter(|cassette| !cassette.exists()) {
+            std::fs::write(cassette, r#"{"interactions":[]}"#).unwrap();
+        }
 
         let status_prefix = {
             let status_prefix = "> status:".bold();
@@ -940,6 +1136,11 @@ impl TestContext {
     fn start_server(&mut self) {
         assert!(self.server_process.is_none(), "server is already running");
 
+        if self.http_mode == HttpMode::Replay {
+            println!("{}", "> skipping golem server, replaying recordings".bold());
+            return;
+        }
+
         println!("{}", "> starting golem server".bold());
         println!(
             "{} {}",
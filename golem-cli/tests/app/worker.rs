@@ -0,0 +1,38 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::app::fixture::Fixture;
+use crate::app::{cmd, TestContext};
+use crate::Tracing;
+use assert2::{assert, check};
+use test_r::{inherit_test_dep, test};
+
+inherit_test_dep!(Tracing);
+
+#[test]
+fn worker_list_and_get(_tracing: &Tracing) {
+    let ctx = Fixture::component("app:counter")
+        .with_worker("w1")
+        .with_worker("w2")
+        .provision(TestContext::new());
+
+    let outputs = ctx.cli([cmd::WORKER, cmd::LIST, "app:counter"]);
+    assert!(outputs.success());
+    check!(outputs.stdout_contains("w1"));
+    check!(outputs.stdout_contains("w2"));
+
+    let outputs = ctx.cli([cmd::WORKER, cmd::GET, "app:counter/w1"]);
+    assert!(outputs.success());
+    check!(outputs.stdout_contains("w1"));
+}
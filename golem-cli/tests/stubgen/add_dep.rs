@@ -16,13 +16,16 @@
 
 use test_r::test;
 
-use assert2::assert;
+use assert2::{assert, let_assert};
 use fs_extra::dir::CopyOptions;
 use golem_cli::model::app::AppComponentName;
+use golem_cli::wasm_rpc_stubgen::commands::dependencies::add_wit_dependency;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_client_wit_dir;
+use golem_cli::wasm_rpc_stubgen::naming::wit::ClientNaming;
 use golem_cli::wasm_rpc_stubgen::stub::{RustDependencyOverride, StubConfig, StubDefinition};
 use golem_cli::wasm_rpc_stubgen::wit_generate::{
-    add_client_as_dependency_to_wit_dir, AddClientAsDepConfig, UpdateCargoToml,
+    add_client_as_dependency_to_wit_dir, is_self_client, AddClientAsDepConfig, PackageRename,
+    UpdateCargoToml,
 };
 use golem_cli::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
 use golem_cli::wasm_rpc_stubgen::{GOLEM_RPC_WIT_VERSION, WASI_WIT_VERSION};
@@ -49,6 +52,8 @@ fn all_wit_types_no_collision() {
         client_wit_root: stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -69,6 +74,36 @@ fn all_wit_types_no_collision() {
     );
 }
 
+#[test]
+fn all_wit_types_updates_componentize_py_config() {
+    let (_source_dir, stub_dir) = init_stub("all-wit-types");
+    let dest_dir = init_caller("caller-no-dep");
+    std::fs::remove_file(dest_dir.path().join("Cargo.toml")).unwrap();
+    std::fs::write(
+        dest_dir.path().join("pyproject.toml"),
+        "[project]\nname = \"caller\"\n",
+    )
+    .unwrap();
+
+    let stub_wit_root = stub_dir.path().join("wit");
+    let dest_wit_root = dest_dir.path().join("wit");
+
+    add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
+        client_wit_root: stub_wit_root.clone(),
+        dest_wit_root: dest_wit_root.clone(),
+        update_cargo_toml: UpdateCargoToml::UpdateIfExists,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
+    })
+    .unwrap();
+
+    assert_valid_wit_root(&dest_wit_root);
+
+    let config = std::fs::read_to_string(dest_dir.path().join("componentize-py.toml")).unwrap();
+    assert!(config.contains("wit_directory = \"wit\""));
+    assert!(config.contains("\"test:main-client/api-client\" = \"api_client\""));
+}
+
 #[test]
 fn all_wit_types_re_add_with_changes() {
     let (source_dir, stub_dir) = init_stub("all-wit-types");
@@ -83,6 +118,8 @@ fn all_wit_types_re_add_with_changes() {
         client_wit_root: stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -108,6 +145,8 @@ fn all_wit_types_re_add_with_changes() {
         client_wit_root: alternative_stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -130,6 +169,83 @@ fn all_wit_types_re_add_with_changes() {
     );
 }
 
+#[test]
+fn all_wit_types_same_package_name_renamed() {
+    let (_source_dir, stub_dir) = init_stub("all-wit-types");
+    let dest_dir = init_caller("caller-no-dep");
+
+    let stub_wit_root = stub_dir.path().join("wit");
+    let dest_wit_root = dest_dir.path().join("wit");
+
+    // The caller uses the same package name as the called component
+    let caller_wit = dest_wit_root.join("caller.wit");
+    std::fs::write(
+        &caller_wit,
+        std::fs::read_to_string(&caller_wit)
+            .unwrap()
+            .replace("package test:caller;", "package test:main;"),
+    )
+    .unwrap();
+
+    add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
+        client_wit_root: stub_wit_root.clone(),
+        dest_wit_root: dest_wit_root.clone(),
+        update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![PackageRename::new("test:main", "other:main").unwrap()],
+        client_naming: ClientNaming::default(),
+    })
+    .unwrap();
+
+    assert_valid_wit_root(&dest_wit_root);
+    assert_has_wasm_rpc_wit_deps(&dest_wit_root);
+
+    assert_has_package_by_name(
+        &PackageName::new("other", "main-client", None),
+        &dest_wit_root,
+    );
+    assert_has_package_by_name(
+        &PackageName::new("other", "main-exports", None),
+        &dest_wit_root,
+    );
+    assert_has_no_package_by_name(
+        &PackageName::new("test", "main-client", None),
+        &dest_wit_root,
+    );
+    assert_has_no_package_by_name(
+        &PackageName::new("test", "main-exports", None),
+        &dest_wit_root,
+    );
+
+    let caller_wit = std::fs::read_to_string(&caller_wit).unwrap();
+    assert!(caller_wit.contains("import other:main-client/api-client;"));
+}
+
+#[test]
+fn wit_package_without_client() {
+    let dest_dir = init_caller("caller-no-dep");
+    let dest_wit_root = dest_dir.path().join("wit");
+    let source_wit_dir = Path::new("test-data/wit/shared-types");
+
+    add_wit_dependency(source_wit_dir, &dest_wit_root).unwrap();
+    // Adding again is a no-op
+    add_wit_dependency(source_wit_dir, &dest_wit_root).unwrap();
+
+    assert_valid_wit_root(&dest_wit_root);
+
+    let package_name = PackageName::new("test", "shared-types", None);
+    assert_has_package_by_name(&package_name, &dest_wit_root);
+
+    let added_package = std::fs::read_to_string(
+        dest_wit_root
+            .join("deps")
+            .join("test_shared-types")
+            .join("test_shared-types.wit"),
+    )
+    .unwrap();
+    assert!(added_package.contains("interface types"));
+    assert!(!added_package.contains("world shared"));
+}
+
 #[test]
 fn many_ways_to_export_no_collision() {
     let (source_dir, stub_dir) = init_stub("many-ways-to-export");
@@ -142,6 +258,8 @@ fn many_ways_to_export_no_collision() {
         client_wit_root: stub_wit_root.clone(),
         dest_wit_root: dest_wit_root.clone(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -180,12 +298,16 @@ fn direct_circular() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -233,12 +355,16 @@ fn direct_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -256,12 +382,16 @@ fn direct_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -313,12 +443,16 @@ fn direct_circular_same_world_name() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -368,18 +502,24 @@ fn indirect_circular() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_c.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_c_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -442,18 +582,24 @@ fn indirect_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_c.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_c_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -473,18 +619,24 @@ fn indirect_circular_readd() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_c.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_b_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
         client_wit_root: stub_c_dir.path().join("wit"),
         dest_wit_root: dest_b.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -544,6 +696,8 @@ fn self_circular() {
         client_wit_root: stub_a_dir.path().join("wit"),
         dest_wit_root: dest_a.path().to_path_buf(),
         update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -558,7 +712,126 @@ fn self_circular() {
     );
 }
 
+#[test]
+fn self_client_matches_destination() {
+    let (_source_a_dir, stub_a_dir) = init_stub("self-circular");
+    let dest_a = init_caller("self-circular");
+
+    let client_wit_root = ResolvedWitDir::new(&stub_a_dir.path().join("wit")).unwrap();
+    let dest_wit_root = ResolvedWitDir::new(dest_a.path()).unwrap();
+
+    assert!(is_self_client(&client_wit_root, &dest_wit_root, &ClientNaming::default()).unwrap());
+}
+
+#[test]
+fn self_client_other_destination() {
+    let (_source_dir, stub_dir) = init_stub("all-wit-types");
+    let dest_dir = init_caller("caller-no-dep");
+
+    let client_wit_root = ResolvedWitDir::new(&stub_dir.path().join("wit")).unwrap();
+    let dest_wit_root = ResolvedWitDir::new(&dest_dir.path().join("wit")).unwrap();
+
+    assert!(!is_self_client(&client_wit_root, &dest_wit_root, &ClientNaming::default()).unwrap());
+}
+
+#[test]
+fn self_client_out_of_date() {
+    let (_source_a_dir, stub_a_dir) = init_stub("self-circular");
+    let dest_a = init_caller("self-circular");
+
+    let a_wit = dest_a.path().join("a.wit");
+    std::fs::write(
+        &a_wit,
+        std::fs::read_to_string(&a_wit).unwrap().replace(
+            "func-a: func() -> typ-a;",
+            "func-a: func(id: u64) -> typ-a;\n  func-b: func();",
+        ),
+    )
+    .unwrap();
+
+    let client_wit_root = ResolvedWitDir::new(&stub_a_dir.path().join("wit")).unwrap();
+    let dest_wit_root = ResolvedWitDir::new(dest_a.path()).unwrap();
+
+    let_assert!(
+        Err(error) = is_self_client(&client_wit_root, &dest_wit_root, &ClientNaming::default())
+    );
+    let error = strip_ansi_escapes::strip_str(error.to_string());
+    assert!(error.contains("is out of date"));
+    assert!(error.contains("changed api-a.func-a"));
+    assert!(error.contains("added api-a.func-b"));
+}
+
+#[test]
+fn custom_client_naming() {
+    let client_naming = ClientNaming {
+        suffix: "-rpc".to_string(),
+        wit_file_name: "rpc.wit".to_string(),
+    };
+
+    let (source_dir, stub_dir) = init_stub_with_naming("all-wit-types", client_naming.clone());
+    let def = StubDefinition::new(StubConfig {
+        source_wit_root: source_dir.path().to_path_buf(),
+        client_root: stub_dir.path().to_path_buf(),
+        selected_world: None,
+        stub_crate_version: "1.0.0".to_string(),
+        golem_rust_override: RustDependencyOverride::default(),
+        extract_source_exports_package: false,
+        seal_cargo_workspace: false,
+        component_name: AppComponentName::from("test:component"),
+        is_ephemeral: false,
+        client_naming: client_naming.clone(),
+    })
+    .unwrap();
+
+    assert!(def.client_parser_package_name().to_string() == "test:main-rpc");
+    assert!(def.client_interface_name() == "api-rpc");
+    assert!(def.client_crate_name() == "api-rpc");
+    assert!(def.rust_client_interface_name() == "api_rpc");
+    assert!(def.rust_client_root_name() == "main_rpc");
+    assert!(def.client_go_module_name() == "api-rpc");
+    assert!(def.client_go_package_name() == "api_rpc");
+    assert!(def.client_wit_path() == stub_dir.path().join("wit").join("rpc.wit"));
+    assert!(def.client_wit_path().exists());
+    assert!(!stub_dir.path().join("wit").join("client.wit").exists());
+
+    let stub_wit_root = stub_dir.path().join("wit");
+    let dest_dir = init_caller("caller-no-dep");
+    let dest_wit_root = dest_dir.path().join("wit");
+
+    add_client_as_dependency_to_wit_dir(AddClientAsDepConfig {
+        client_wit_root: stub_wit_root.clone(),
+        dest_wit_root: dest_wit_root.clone(),
+        update_cargo_toml: UpdateCargoToml::NoUpdate,
+        package_renames: vec![],
+        client_naming: client_naming.clone(),
+    })
+    .unwrap();
+
+    assert_valid_wit_root(&dest_wit_root);
+    assert_has_same_wit_package(
+        &PackageName::new("test", "main-rpc", None),
+        &dest_wit_root,
+        &stub_wit_root,
+    );
+    assert_has_no_package_by_name(
+        &PackageName::new("test", "main-client", None),
+        &dest_wit_root,
+    );
+
+    let (_source_a_dir, stub_a_dir) = init_stub_with_naming("self-circular", client_naming.clone());
+    let dest_a = init_caller("self-circular");
+    let client_wit_root = ResolvedWitDir::new(&stub_a_dir.path().join("wit")).unwrap();
+    let dest_wit_root = ResolvedWitDir::new(dest_a.path()).unwrap();
+
+    assert!(is_self_client(&client_wit_root, &dest_wit_root, &client_naming).unwrap());
+    assert!(!is_self_client(&client_wit_root, &dest_wit_root, &ClientNaming::default()).unwrap());
+}
+
 fn init_stub(name: &str) -> (TempDir, TempDir) {
+    init_stub_with_naming(name, ClientNaming::default())
+}
+
+fn init_stub_with_naming(name: &str, client_naming: ClientNaming) -> (TempDir, TempDir) {
     let source = tempfile::Builder::new()
         .disable_cleanup(true)
         .tempdir()
@@ -588,6 +861,7 @@ fn init_stub(name: &str) -> (TempDir, TempDir) {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        client_naming,
     })
     .unwrap();
     let _ = generate_client_wit_dir(&def).unwrap();
@@ -605,6 +879,7 @@ fn regenerate_stub(stub_dir: &Path, source_wit_root: &Path) {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     let _ = generate_client_wit_dir(&def).unwrap();
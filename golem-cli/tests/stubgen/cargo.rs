@@ -2,7 +2,12 @@ use crate::stubgen::{cargo_component_build, test_data_path};
 use assert2::check;
 use fs_extra::dir::CopyOptions;
 use golem_cli::fs;
-use golem_cli::wasm_rpc_stubgen::cargo::regenerate_cargo_package_component;
+use golem_cli::wasm_rpc_stubgen::cargo::{
+    add_client_crate_to_cargo_workspace, find_cargo_workspace_toml,
+    regenerate_cargo_package_component, sync_cargo_package_component_deps,
+};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
 use tempfile::TempDir;
 use test_r::test;
 
@@ -128,3 +133,123 @@ generate_unused_types = true
     check!(cargo_toml.contains("\"test:sub\" = { path = \"wit/deps/sub\" }"));
     check!(cargo_toml.contains("[package.metadata.component.bindings]\nderives = [\"serde::Serialize\", \"serde::Deserialize\"]\ngenerate_unused_types = true"));
 }
+
+#[test]
+fn add_client_crate_to_existing_cargo_workspace() {
+    let workspace_dir = TempDir::new().unwrap();
+    let workspace_cargo_toml_path = workspace_dir.path().join("Cargo.toml");
+    let client_dir = workspace_dir.path().join("clients").join("counter");
+    let client_cargo_toml_path = client_dir.join("Cargo.toml");
+
+    fs::write_str(
+        &workspace_cargo_toml_path,
+        r#"# Workspace comment
+[workspace]
+resolver = "2"
+members = ["app"]
+
+[workspace.dependencies]
+wit-bindgen-rt = "0.40.0"
+"#,
+    )
+    .unwrap();
+    fs::write_str(
+        &client_cargo_toml_path,
+        r#"[workspace]
+
+[package]
+name = "counter-client"
+version = "0.0.1"
+edition = "2021"
+
+[dependencies]
+wit-bindgen-rt = { version = "0.41.0", features = ["bitflags"] }
+golem-rust = { version = "1.7.0", default-features = false }
+
+[profile.release]
+lto = true
+"#,
+    )
+    .unwrap();
+
+    check!(
+        find_cargo_workspace_toml(&client_dir).unwrap() == Some(workspace_cargo_toml_path.clone())
+    );
+
+    add_client_crate_to_cargo_workspace(&workspace_cargo_toml_path, &client_cargo_toml_path)
+        .unwrap();
+    // Adding again must not duplicate members
+    add_client_crate_to_cargo_workspace(&workspace_cargo_toml_path, &client_cargo_toml_path)
+        .unwrap();
+
+    let workspace_cargo_toml = fs::read_to_string(&workspace_cargo_toml_path).unwrap();
+    println!(">\n{workspace_cargo_toml}");
+    check!(workspace_cargo_toml.contains("# Workspace comment"));
+    check!(workspace_cargo_toml.contains("members = [\"app\", \"clients/counter\"]"));
+    check!(workspace_cargo_toml.contains("wit-bindgen-rt = \"0.40.0\""));
+    check!(workspace_cargo_toml
+        .contains("golem-rust = { version = \"1.7.0\", default-features = false }"));
+
+    let client_cargo_toml = fs::read_to_string(&client_cargo_toml_path).unwrap();
+    println!(">\n{client_cargo_toml}");
+    check!(!client_cargo_toml.contains("[workspace]"));
+    check!(!client_cargo_toml.contains("[profile.release]"));
+    check!(client_cargo_toml.contains("wit-bindgen-rt = { workspace = true }"));
+    check!(client_cargo_toml.contains("golem-rust = { workspace = true }"));
+}
+
+#[test]
+fn sync_cargo_toml_component_deps() {
+    let project_dir = TempDir::new().unwrap();
+    let cargo_toml_path = project_dir.path().join("Cargo.toml");
+    for dep in ["a", "b", "c"] {
+        fs::create_dir_all(project_dir.path().join("wit").join("deps").join(dep)).unwrap();
+    }
+
+    fs::write_str(
+        &cargo_toml_path,
+        r#"[package]
+name = "component"
+version = "0.0.1"
+
+# Component metadata
+[package.metadata.component.target]
+path = "wit"
+
+[package.metadata.component.target.dependencies]
+"test:a@1.0.0" = { path = "wit/deps/a" }
+"test:old" = { path = "wit/deps/old" }
+# Kept dependency
+"test:b" = { path = "wit/deps/b" }
+"test:b2" = { path = "wit/deps/c" }
+"#,
+    )
+    .unwrap();
+
+    let package_name = |name: &str| wit_parser::PackageName {
+        namespace: "test".to_string(),
+        name: name.to_string(),
+        version: None,
+    };
+    let wit_sources = || {
+        BTreeMap::from([
+            (package_name("a"), PathBuf::from("wit/deps/a")),
+            (package_name("c"), PathBuf::from("wit/deps/c")),
+        ])
+    };
+
+    sync_cargo_package_component_deps(&cargo_toml_path, wit_sources()).unwrap();
+    let cargo_toml = fs::read_to_string(&cargo_toml_path).unwrap();
+    println!(">\n{cargo_toml}");
+    check!(cargo_toml.contains("# Component metadata\n[package.metadata.component.target]"));
+    check!(cargo_toml.contains("# Kept dependency\n\"test:b\" = { path = \"wit/deps/b\" }"));
+    check!(cargo_toml.contains("\"test:a\" = { path = \"wit/deps/a\" }"));
+    check!(cargo_toml.contains("\"test:c\" = { path = \"wit/deps/c\" }"));
+    check!(!cargo_toml.contains("test:a@1.0.0"));
+    check!(!cargo_toml.contains("test:old"));
+    check!(!cargo_toml.contains("test:b2"));
+
+    // Syncing again must not change anything
+    sync_cargo_package_component_deps(&cargo_toml_path, wit_sources()).unwrap();
+    check!(fs::read_to_string(&cargo_toml_path).unwrap() == cargo_toml);
+}
@@ -22,6 +22,7 @@ use golem_cli::model::app::AppComponentName;
 use golem_cli::wasm_rpc_stubgen::commands::composition::compose;
 use golem_cli::wasm_rpc_stubgen::commands::dependencies::add_stub_dependency;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_and_build_client;
+use golem_cli::wasm_rpc_stubgen::naming::wit::ClientNaming;
 use golem_cli::wasm_rpc_stubgen::stub::{StubConfig, StubDefinition};
 use golem_cli::wasm_rpc_stubgen::wit_generate::UpdateCargoToml;
 use golem_wasm_ast::component::Component;
@@ -39,6 +40,8 @@ async fn compose_with_single_stub() {
         &stub_dir.path().join("wit"),
         &caller_dir.path().join("wit"),
         UpdateCargoToml::Update,
+        vec![],
+        ClientNaming::default(),
     )
     .unwrap();
 
@@ -55,7 +58,7 @@ async fn compose_with_single_stub() {
     assert_is_component(&component_wasm);
 
     let dest_wasm = caller_dir.path().join("target/result.wasm");
-    compose(&component_wasm, &[stub_wasm], &dest_wasm)
+    compose(&component_wasm, &[stub_wasm], &dest_wasm, None)
         .await
         .unwrap();
 }
@@ -84,6 +87,7 @@ async fn init_stub(name: &str) -> (TempDir, TempDir, PathBuf) {
         seal_cargo_workspace: true,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     let wasm_path = generate_and_build_client(&def, false).await.unwrap();
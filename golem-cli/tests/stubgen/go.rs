@@ -0,0 +1,86 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests in this module are verifying the Go client module created by the stub generator
+
+use test_r::test;
+
+use crate::stubgen::test_data_path;
+use fs_extra::dir::CopyOptions;
+use golem_cli::model::app::{AppComponentName, ClientLanguage};
+use golem_cli::wasm_rpc_stubgen::commands::generate::generate_client_for_language;
+use golem_cli::wasm_rpc_stubgen::naming::wit::ClientNaming;
+use golem_cli::wasm_rpc_stubgen::stub::{RustDependencyOverride, StubConfig, StubDefinition};
+use golem_cli::wasm_rpc_stubgen::wit_resolve::ResolvedWitDir;
+use golem_cli::wasm_rpc_stubgen::{GO_CM_VERSION, GO_VERSION, WIT_BINDGEN_GO_VERSION};
+use tempfile::{tempdir, TempDir};
+
+#[test]
+fn all_wit_types() {
+    let source_wit_root = init_source("all-wit-types");
+    let target_root = tempdir().unwrap();
+
+    let def = StubDefinition::new(StubConfig {
+        source_wit_root: source_wit_root.path().to_path_buf(),
+        client_root: target_root.path().to_path_buf(),
+        selected_world: None,
+        stub_crate_version: "1.0.0".to_string(),
+        golem_rust_override: RustDependencyOverride::default(),
+        extract_source_exports_package: true,
+        seal_cargo_workspace: false,
+        component_name: AppComponentName::from("test:component"),
+        is_ephemeral: false,
+        client_naming: ClientNaming::default(),
+    })
+    .unwrap();
+
+    generate_client_for_language(&def, ClientLanguage::Go).unwrap();
+
+    let go_mod = std::fs::read_to_string(target_root.path().join("go.mod")).unwrap();
+    assert!(go_mod.starts_with("module api-client\n"));
+    assert!(go_mod.contains(&format!("\ngo {GO_VERSION}\n")));
+    assert!(go_mod.contains(&format!(
+        "\nrequire go.bytecodealliance.org/cm v{GO_CM_VERSION}\n"
+    )));
+    assert!(go_mod.contains("\ntool go.bytecodealliance.org/cmd/wit-bindgen-go\n"));
+    assert!(go_mod.contains(&format!(
+        "\nrequire go.bytecodealliance.org v{WIT_BINDGEN_GO_VERSION} // indirect\n"
+    )));
+
+    let generate_go = std::fs::read_to_string(target_root.path().join("generate.go")).unwrap();
+    assert!(generate_go.starts_with("// Code generated by golem-cli. DO NOT EDIT.\n"));
+    assert!(generate_go.contains("\npackage api_client\n"));
+    assert!(generate_go.contains(
+        "\n//go:generate go tool wit-bindgen-go generate --world wasm-rpc-client-api --out binding ./wit\n"
+    ));
+
+    // The world referenced by the go:generate directive has to be part of the generated WIT
+    let client_wit_root = ResolvedWitDir::new(&target_root.path().join("wit")).unwrap();
+    let client_package = client_wit_root.main_package().unwrap();
+    assert!(client_package.worlds.contains_key("wasm-rpc-client-api"));
+}
+
+fn init_source(name: &str) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let source = test_data_path().join("wit").join(name);
+
+    fs_extra::dir::copy(
+        source,
+        temp_dir.path(),
+        &CopyOptions::new().content_only(true).overwrite(true),
+    )
+    .unwrap();
+
+    temp_dir
+}
@@ -19,7 +19,9 @@ use test_r::tag_suite;
 mod add_dep;
 mod cargo;
 mod compose;
+mod go;
 mod stub_wasm;
+mod typescript;
 mod wit;
 
 tag_suite!(cargo, uses_cargo);
@@ -19,6 +19,7 @@ use crate::stubgen::{golem_rust_override, test_data_path};
 use fs_extra::dir::CopyOptions;
 use golem_cli::model::app::AppComponentName;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_and_build_client;
+use golem_cli::wasm_rpc_stubgen::naming::wit::ClientNaming;
 use golem_cli::wasm_rpc_stubgen::stub::{StubConfig, StubDefinition};
 use golem_wasm_ast::analysis::analysed_type::*;
 use golem_wasm_ast::analysis::wit_parser::WitAnalysisContext;
@@ -54,6 +55,7 @@ async fn all_wit_types() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -368,6 +370,7 @@ async fn resource() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -421,6 +424,7 @@ async fn circular_resources() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:main"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -482,6 +486,7 @@ async fn inline_resources() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:main"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
 
@@ -0,0 +1,85 @@
+// Copyright 2024-2025 Golem Cloud
+//
+// Licensed under the Golem Source License v1.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://license.golem.cloud/LICENSE
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests in this module are verifying the TypeScript client bindings created by the stub generator
+
+use test_r::test;
+
+use crate::stubgen::test_data_path;
+use fs_extra::dir::CopyOptions;
+use golem_cli::model::app::{AppComponentName, ClientLanguage};
+use golem_cli::wasm_rpc_stubgen::commands::generate::generate_client_for_language;
+use golem_cli::wasm_rpc_stubgen::naming::wit::ClientNaming;
+use golem_cli::wasm_rpc_stubgen::stub::{RustDependencyOverride, StubConfig, StubDefinition};
+use tempfile::{tempdir, TempDir};
+
+#[test]
+fn all_wit_types() {
+    let source_wit_root = init_source("all-wit-types");
+    let target_root = tempdir().unwrap();
+
+    let def = StubDefinition::new(StubConfig {
+        source_wit_root: source_wit_root.path().to_path_buf(),
+        client_root: target_root.path().to_path_buf(),
+        selected_world: None,
+        stub_crate_version: "1.0.0".to_string(),
+        golem_rust_override: RustDependencyOverride::default(),
+        extract_source_exports_package: true,
+        seal_cargo_workspace: false,
+        component_name: AppComponentName::from("test:component"),
+        is_ephemeral: false,
+        client_naming: ClientNaming::default(),
+    })
+    .unwrap();
+
+    generate_client_for_language(&def, ClientLanguage::Ts).unwrap();
+
+    let ts_path = target_root
+        .path()
+        .join("interfaces")
+        .join("test-main-client-api-client.d.ts");
+    let src = std::fs::read_to_string(&ts_path).unwrap();
+
+    assert!(src.contains("/** @module Interface test:main-client/api-client **/"));
+    assert!(src.contains("export class Iface1 {"));
+    assert!(src.contains("  constructor(workerName: string);"));
+    assert!(src.contains("  static custom(workerId: WorkerId): Iface1;"));
+    assert!(src.contains("  blockingGetBool(): boolean;"));
+    assert!(src.contains("  getBool(): FutureGetBoolResult;"));
+    assert!(
+        src.contains("  scheduleSetBool(b: boolean, scheduledFor: Datetime): CancellationToken;")
+    );
+    assert!(src.contains("  blockingIdentityU64(x: bigint): bigint;"));
+    assert!(src.contains("  blockingGetOrders(): Array<Order>;"));
+    assert!(src.contains("  blockingValidatePt(pt: Point): void;"));
+    assert!(src.contains("export type Color = 'red' | 'green' | 'blue';"));
+    assert!(src.contains("export interface Permissions {"));
+    assert!(src.contains("export interface ProductItem {"));
+    assert!(src.contains("  productId: string,"));
+    assert!(src.contains("export type CheckoutResult = CheckoutResultError | CheckoutResultSuccess | CheckoutResultUnknown;"));
+}
+
+fn init_source(name: &str) -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    let source = test_data_path().join("wit").join(name);
+
+    fs_extra::dir::copy(
+        source,
+        temp_dir.path(),
+        &CopyOptions::new().content_only(true).overwrite(true),
+    )
+    .unwrap();
+
+    temp_dir
+}
@@ -19,10 +19,11 @@ use test_r::test;
 use fs_extra::dir::CopyOptions;
 use golem_cli::model::app::AppComponentName;
 use golem_cli::wasm_rpc_stubgen::commands::generate::generate_client_wit_dir;
+use golem_cli::wasm_rpc_stubgen::naming::wit::ClientNaming;
 use golem_cli::wasm_rpc_stubgen::stub::{RustDependencyOverride, StubConfig, StubDefinition};
 use std::path::Path;
 use tempfile::{tempdir, TempDir};
-use wit_parser::{FunctionKind, Resolve, TypeDefKind, TypeOwner};
+use wit_parser::{Function, FunctionKind, Handle, Resolve, Type, TypeDefKind, TypeId, TypeOwner};
 
 test_r::enable!();
 
@@ -41,6 +42,7 @@ fn all_wit_types() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     let resolve = generate_client_wit_dir(&def).unwrap().resolve;
@@ -146,6 +148,7 @@ fn many_ways_to_export() {
         seal_cargo_workspace: false,
         component_name: AppComponentName::from("test:component"),
         is_ephemeral: false,
+        client_naming: ClientNaming::default(),
     })
     .unwrap();
     let resolve = generate_client_wit_dir(&def).unwrap().resolve;
@@ -171,6 +174,166 @@ fn many_ways_to_export() {
     // TODO: add asserts for non-unique types
 }
 
+#[test]
+fn resources() {
+    let source_wit_root = init_source("resources");
+    let target_root = tempdir().unwrap();
+
+    let def = StubDefinition::new(StubConfig {
+        source_wit_root: source_wit_root.path().to_path_buf(),
+        client_root: target_root.path().to_path_buf(),
+        selected_world: None,
+        stub_crate_version: "1.0.0".to_string(),
+        golem_rust_override: RustDependencyOverride::default(),
+        extract_source_exports_package: true,
+        seal_cargo_workspace: false,
+        component_name: AppComponentName::from("test:component"),
+        is_ephemeral: false,
+        client_naming: ClientNaming::default(),
+    })
+    .unwrap();
+    let resolve = generate_client_wit_dir(&def).unwrap().resolve;
+
+    let resource1 = stub_resource_id(&resolve, "api-client", "resource1");
+    let resource2 = stub_resource_id(&resolve, "api-client", "resource2");
+
+    // Constructors
+    let constructor = stub_function(&resolve, "api-client", "[constructor]resource1").unwrap();
+    assert_eq!(param_names(constructor), vec!["worker-name", "name"]);
+    assert!(stub_function(&resolve, "api-client", "[static]resource1.custom").is_some());
+    assert!(stub_function(&resolve, "api-client", "[constructor]resource2").is_none());
+    assert!(stub_function(&resolve, "api-client", "[static]resource2.custom").is_none());
+
+    // Methods with borrowed and owned handles
+    for prefix in ["", "blocking-", "schedule-"] {
+        let function = stub_function(
+            &resolve,
+            "api-client",
+            &format!("[method]resource1.{prefix}func5"),
+        )
+        .unwrap();
+        assert_eq!(function.kind, FunctionKind::Method(resource1));
+        assert_handle(&resolve, &function.params[1].1, resource2, true);
+
+        let function = stub_function(
+            &resolve,
+            "api-client",
+            &format!("[method]resource1.{prefix}func6"),
+        )
+        .unwrap();
+        assert_handle(&resolve, &function.params[1].1, resource2, false);
+    }
+    let blocking_func6 =
+        stub_function(&resolve, "api-client", "[method]resource1.blocking-func6").unwrap();
+    assert_handle(
+        &resolve,
+        blocking_func6.result.as_ref().unwrap(),
+        resource2,
+        false,
+    );
+
+    // Async polling variant of a method returning an owned handle
+    let future_get = stub_function(
+        &resolve,
+        "api-client",
+        "[method]future-resource1-func6-result.get",
+    )
+    .unwrap();
+    let Some(Type::Id(option_id)) = future_get.result else {
+        panic!("Expected option result for future get");
+    };
+    let TypeDefKind::Option(inner) = &resolve.types[option_id].kind else {
+        panic!("Expected option result for future get");
+    };
+    assert_handle(&resolve, inner, resource2, false);
+
+    // Static functions select the target worker
+    for prefix in ["", "blocking-", "schedule-"] {
+        let create = stub_function(
+            &resolve,
+            "api-client",
+            &format!("[static]resource1.{prefix}create"),
+        )
+        .unwrap();
+        assert_eq!(create.kind, FunctionKind::Static(resource1));
+        assert_eq!(param_names(create)[..2], ["worker-name", "name"]);
+
+        let compare = stub_function(
+            &resolve,
+            "api-client",
+            &format!("[static]resource1.{prefix}compare"),
+        )
+        .unwrap();
+        assert_eq!(param_names(compare)[..3], ["worker-name", "a", "b"]);
+        assert_handle(&resolve, &compare.params[1].1, resource1, true);
+        assert_handle(&resolve, &compare.params[2].1, resource1, true);
+    }
+    let blocking_create =
+        stub_function(&resolve, "api-client", "[static]resource1.blocking-create").unwrap();
+    assert_handle(
+        &resolve,
+        blocking_create.result.as_ref().unwrap(),
+        resource1,
+        false,
+    );
+
+    // Methods of a resource without a constructor
+    for prefix in ["", "blocking-", "schedule-"] {
+        let function = stub_function(
+            &resolve,
+            "api-client",
+            &format!("[method]resource2.{prefix}value"),
+        )
+        .unwrap();
+        assert_eq!(function.kind, FunctionKind::Method(resource2));
+    }
+}
+
+fn stub_resource_id(resolve: &Resolve, interface_name: &str, resource_name: &str) -> TypeId {
+    let (_, iface) = resolve
+        .interfaces
+        .iter()
+        .find(|(_iface_id, iface)| iface.name == Some(interface_name.to_string()))
+        .unwrap();
+    let resource_id = *iface.types.get(resource_name).unwrap();
+    assert_eq!(resolve.types[resource_id].kind, TypeDefKind::Resource);
+    resource_id
+}
+
+fn stub_function<'a>(
+    resolve: &'a Resolve,
+    interface_name: &str,
+    function_name: &str,
+) -> Option<&'a Function> {
+    let (_, iface) = resolve
+        .interfaces
+        .iter()
+        .find(|(_iface_id, iface)| iface.name == Some(interface_name.to_string()))
+        .unwrap();
+    iface.functions.get(function_name)
+}
+
+fn param_names(function: &Function) -> Vec<&str> {
+    function
+        .params
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|name| *name != "self")
+        .collect()
+}
+
+fn assert_handle(resolve: &Resolve, typ: &Type, resource_id: TypeId, is_borrow: bool) {
+    let Type::Id(type_id) = typ else {
+        panic!("Expected handle type, got: {typ:?}");
+    };
+    let expected = if is_borrow {
+        Handle::Borrow(resource_id)
+    } else {
+        Handle::Own(resource_id)
+    };
+    assert_eq!(resolve.types[*type_id].kind, TypeDefKind::Handle(expected));
+}
+
 fn assert_has_package_name(resolve: &Resolve, package_name: &str) {
     assert!(resolve
         .packages
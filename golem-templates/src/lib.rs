@@ -709,5 +709,6 @@ fn parse_template(
             .map(|te| te.iter().cloned().collect())
             .unwrap_or_default(),
         transform: metadata.transform.unwrap_or(true),
+        post_generate: metadata.post_generate.unwrap_or_default(),
     }
 }
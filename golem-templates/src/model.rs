@@ -432,6 +432,8 @@ pub struct Template {
     pub exclude: HashSet<String>,
     pub transform_exclude: HashSet<String>,
     pub transform: bool,
+    /// Commands to be executed in the new component directory after generating it
+    pub post_generate: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -465,6 +467,8 @@ pub(crate) struct TemplateMetadata {
     #[serde(rename = "transformExclude")]
     pub transform_exclude: Option<Vec<String>>,
     pub transform: Option<bool>,
+    #[serde(rename = "postGenerate")]
+    pub post_generate: Option<Vec<String>>,
 }
 
 #[cfg(test)]